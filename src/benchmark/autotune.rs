@@ -0,0 +1,323 @@
+//! Concurrency autotuning
+//!
+//! Adjusts closed-loop concurrency in a feedback loop until the p95 latency
+//! settles just under a target, and reports the sustainable concurrency and
+//! throughput at that latency.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use tracing::info;
+
+use super::runner::{BenchmarkConfig, BenchmarkRunner, LoadPattern};
+use crate::models::GatewayImpl;
+
+/// Largest factor concurrency changes by in one step
+const MAX_STEP_FACTOR: f64 = 2.0;
+
+/// Feedback loop parameters
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ConcurrencyTuner {
+    /// Target p95 latency in milliseconds
+    pub target_p95_ms: f64,
+    /// Maximum tolerated error rate (0.0 - 1.0)
+    pub max_error_rate: f64,
+    /// Lowest concurrency tried (also the starting point)
+    pub min_concurrency: u32,
+    /// Highest concurrency tried
+    pub max_concurrency: u32,
+    /// Maximum number of measurement steps
+    pub max_steps: u32,
+    /// Converged once p95 is within this fraction below the target
+    pub tolerance: f64,
+}
+
+impl Default for ConcurrencyTuner {
+    fn default() -> Self {
+        Self {
+            target_p95_ms: 50.0,
+            max_error_rate: 0.01,
+            min_concurrency: 1,
+            max_concurrency: 512,
+            max_steps: 12,
+            tolerance: 0.1,
+        }
+    }
+}
+
+/// One measurement at a fixed concurrency
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TuningStep {
+    pub concurrency: u32,
+    /// Achieved RPS
+    pub achieved_rps: f64,
+    /// Measured p95 latency in milliseconds
+    pub p95_ms: f64,
+    /// Measured error rate (0.0 - 1.0)
+    pub error_rate: f64,
+    /// Whether latency and errors were within target
+    pub within_target: bool,
+}
+
+/// Autotuning result for one gateway
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TuningResult {
+    /// Gateway tested
+    pub gateway: GatewayImpl,
+    /// Loop parameters
+    pub tuner: ConcurrencyTuner,
+    /// Highest concurrency that held the target (None if even the minimum failed)
+    pub sustainable_concurrency: Option<u32>,
+    /// Throughput at the sustainable concurrency
+    pub sustainable_rps: Option<f64>,
+    /// p95 at the sustainable concurrency
+    pub p95_ms: Option<f64>,
+    /// Whether the loop settled before running out of steps
+    pub converged: bool,
+    /// Every step in execution order
+    pub steps: Vec<TuningStep>,
+}
+
+impl ConcurrencyTuner {
+    /// Create with a p95 target in milliseconds
+    pub fn new(target_p95_ms: f64) -> Self {
+        Self {
+            target_p95_ms,
+            ..Default::default()
+        }
+    }
+
+    /// Set concurrency range
+    pub fn with_range(mut self, min: u32, max: u32) -> Self {
+        self.min_concurrency = min.max(1);
+        self.max_concurrency = max.max(self.min_concurrency);
+        self
+    }
+
+    /// Set maximum tolerated error rate
+    pub fn with_max_error_rate(mut self, rate: f64) -> Self {
+        self.max_error_rate = rate;
+        self
+    }
+
+    /// Set maximum number of steps
+    pub fn with_max_steps(mut self, steps: u32) -> Self {
+        self.max_steps = steps.max(1);
+        self
+    }
+
+    /// Whether a measurement holds the target
+    pub fn evaluate(&self, p95_ms: f64, error_rate: f64) -> bool {
+        p95_ms <= self.target_p95_ms && error_rate <= self.max_error_rate
+    }
+
+    /// Tune concurrency against a gateway using closed-loop benchmarks
+    pub async fn run(&self, config: &BenchmarkConfig) -> Result<TuningResult> {
+        info!(
+            "Autotuning {}: p95 <= {:.1}ms, concurrency {}-{}",
+            config.gateway.name(),
+            self.target_p95_ms,
+            self.min_concurrency,
+            self.max_concurrency
+        );
+
+        self.tune_with(config.gateway, |concurrency| {
+            let config = config
+                .clone()
+                .with_pattern(LoadPattern::Max { concurrency })
+                .with_concurrency(concurrency);
+            async move {
+                let result = BenchmarkRunner::new(config)
+                    .run()
+                    .await
+                    .with_context(|| format!("Step at concurrency {concurrency} failed"))?;
+                let m = &result.metrics;
+                Ok((
+                    m.throughput.rps,
+                    m.latency.percentiles.p95,
+                    1.0 - m.throughput.success_rate,
+                ))
+            }
+        })
+        .await
+    }
+
+    /// Run the loop with a custom probe returning (achieved rps, p95 ms, error rate)
+    pub async fn tune_with<F, Fut>(
+        &self,
+        gateway: GatewayImpl,
+        mut probe: F,
+    ) -> Result<TuningResult>
+    where
+        F: FnMut(u32) -> Fut,
+        Fut: Future<Output = Result<(f64, f64, f64)>>,
+    {
+        let mut steps = Vec::new();
+        let mut best: Option<TuningStep> = None;
+        // Highest passing and lowest failing concurrency seen so far
+        let mut good: Option<u32> = None;
+        let mut bad: Option<u32> = None;
+        let mut converged = false;
+        let mut concurrency = self.min_concurrency;
+
+        for _ in 0..self.max_steps {
+            let step = self.measure(concurrency, probe(concurrency)).await?;
+            steps.push(step.clone());
+
+            if step.within_target {
+                good = good.max(Some(concurrency));
+                if best.as_ref().is_none_or(|b| concurrency > b.concurrency) {
+                    best = Some(step.clone());
+                }
+                if step.p95_ms >= self.target_p95_ms * (1.0 - self.tolerance)
+                    || concurrency >= self.max_concurrency
+                {
+                    converged = true;
+                    break;
+                }
+            } else {
+                bad = Some(bad.map_or(concurrency, |b| b.min(concurrency)));
+                if concurrency <= self.min_concurrency {
+                    converged = true;
+                    break;
+                }
+            }
+
+            // Scale by the latency headroom, staying between known good and bad values
+            let lo = good.map_or(self.min_concurrency, |g| g + 1);
+            let hi = bad.map_or(self.max_concurrency, |b| b - 1);
+            if lo > hi {
+                converged = true;
+                break;
+            }
+            let ratio = (self.target_p95_ms / step.p95_ms.max(f64::EPSILON))
+                .clamp(1.0 / MAX_STEP_FACTOR, MAX_STEP_FACTOR);
+            let next = (concurrency as f64 * ratio).round() as u32;
+            concurrency = if step.within_target {
+                next.max(concurrency + 1)
+            } else {
+                next.min(concurrency - 1)
+            }
+            .clamp(lo, hi);
+        }
+
+        Ok(TuningResult {
+            gateway,
+            tuner: self.clone(),
+            sustainable_concurrency: best.as_ref().map(|b| b.concurrency),
+            sustainable_rps: best.as_ref().map(|b| b.achieved_rps),
+            p95_ms: best.as_ref().map(|b| b.p95_ms),
+            converged,
+            steps,
+        })
+    }
+
+    /// Await one probe and evaluate it against the target
+    async fn measure(
+        &self,
+        concurrency: u32,
+        probe: impl Future<Output = Result<(f64, f64, f64)>>,
+    ) -> Result<TuningStep> {
+        let (achieved_rps, p95_ms, error_rate) = probe.await?;
+        let within_target = self.evaluate(p95_ms, error_rate);
+        info!(
+            "  concurrency {:>4}: {:.1} RPS, p95 {:.2}ms, errors {:.2}% -> {}",
+            concurrency,
+            achieved_rps,
+            p95_ms,
+            error_rate * 100.0,
+            if within_target { "ok" } else { "over target" }
+        );
+
+        Ok(TuningStep {
+            concurrency,
+            achieved_rps,
+            p95_ms,
+            error_rate,
+            within_target,
+        })
+    }
+}
+
+impl TuningResult {
+    /// Whether the target held even at the maximum concurrency
+    pub fn reached_max(&self) -> bool {
+        self.sustainable_concurrency == Some(self.tuner.max_concurrency)
+    }
+
+    /// One-line summary
+    pub fn format_summary(&self) -> String {
+        match (self.sustainable_concurrency, self.sustainable_rps) {
+            (Some(concurrency), Some(rps)) => format!(
+                "{}: concurrency {}{}, {:.1} RPS at p95 {:.2}ms (target {:.1}ms){}",
+                self.gateway.name(),
+                concurrency,
+                if self.reached_max() { "+" } else { "" },
+                rps,
+                self.p95_ms.unwrap_or_default(),
+                self.tuner.target_p95_ms,
+                if self.converged {
+                    ""
+                } else {
+                    ", not converged"
+                }
+            ),
+            _ => format!(
+                "{}: p95 target {:.1}ms not met at concurrency {}",
+                self.gateway.name(),
+                self.tuner.target_p95_ms,
+                self.tuner.min_concurrency
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Simulated gateway saturating at 2000 RPS with a 5ms base latency
+    async fn simulated(concurrency: u32) -> Result<(f64, f64, f64)> {
+        let c = concurrency as f64;
+        let rps = (c * 200.0).min(2000.0);
+        // Little's law: latency grows once throughput is saturated
+        let p95 = (c / rps * 1000.0).max(5.0) * 1.2;
+        Ok((rps, p95, 0.0))
+    }
+
+    #[tokio::test]
+    async fn test_tune_converges() {
+        let tuner = ConcurrencyTuner::new(50.0).with_range(1, 1000);
+        let result = tuner
+            .tune_with(GatewayImpl::Envoy, simulated)
+            .await
+            .unwrap();
+
+        assert!(result.converged);
+        let concurrency = result.sustainable_concurrency.unwrap();
+        // p95 = c / 2000 * 1200 <= 50 → c <= 83
+        assert!((70..=83).contains(&concurrency), "settled at {concurrency}");
+        assert_eq!(result.sustainable_rps, Some(2000.0));
+        assert!(result.steps.len() < 12);
+    }
+
+    #[tokio::test]
+    async fn test_tune_bounds() {
+        let tuner = ConcurrencyTuner::new(50.0).with_range(1, 16);
+        let result = tuner
+            .tune_with(GatewayImpl::Nginx, simulated)
+            .await
+            .unwrap();
+        assert_eq!(result.sustainable_concurrency, Some(16));
+        assert!(result.reached_max());
+
+        let tuner = ConcurrencyTuner::new(2.0).with_range(1, 16);
+        let result = tuner
+            .tune_with(GatewayImpl::Nginx, simulated)
+            .await
+            .unwrap();
+        assert_eq!(result.sustainable_concurrency, None);
+        assert_eq!(result.steps.len(), 1);
+        assert!(result.format_summary().contains("not met"));
+    }
+}
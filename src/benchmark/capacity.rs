@@ -0,0 +1,324 @@
+//! Latency-vs-RPS capacity search
+//!
+//! Finds the maximum sustainable throughput (knee point) at which a p99
+//! latency SLO still holds, by stepping RPS up until the SLO breaks and
+//! then binary-searching between the last passing and first failing rate.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use tracing::info;
+
+use super::runner::{BenchmarkConfig, BenchmarkRunner, LoadPattern};
+use crate::models::GatewayImpl;
+
+/// Fraction of the target RPS that must actually be achieved
+const MIN_ACHIEVED_RATIO: f64 = 0.9;
+
+/// Capacity search parameters
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CapacitySearch {
+    /// p99 latency SLO in milliseconds
+    pub slo_p99_ms: f64,
+    /// Maximum tolerated error rate (0.0 - 1.0)
+    pub max_error_rate: f64,
+    /// First RPS to probe
+    pub min_rps: u32,
+    /// Upper bound for the search
+    pub max_rps: u32,
+    /// Stop bisecting once the bracket is this narrow
+    pub precision_rps: u32,
+}
+
+impl Default for CapacitySearch {
+    fn default() -> Self {
+        Self {
+            slo_p99_ms: 50.0,
+            max_error_rate: 0.01,
+            min_rps: 50,
+            max_rps: 10_000,
+            precision_rps: 25,
+        }
+    }
+}
+
+/// Outcome of one probe at a fixed RPS
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CapacityProbe {
+    /// Target RPS
+    pub rps: u32,
+    /// Achieved RPS
+    pub achieved_rps: f64,
+    /// Measured p99 latency in milliseconds
+    pub p99_ms: f64,
+    /// Measured error rate (0.0 - 1.0)
+    pub error_rate: f64,
+    /// Whether the SLO held
+    pub passed: bool,
+}
+
+/// Capacity search result for one gateway
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CapacityResult {
+    /// Gateway tested
+    pub gateway: GatewayImpl,
+    /// Search parameters
+    pub search: CapacitySearch,
+    /// Highest RPS where the SLO held (None if even `min_rps` failed)
+    pub max_sustainable_rps: Option<u32>,
+    /// p99 at the knee point
+    pub knee_p99_ms: Option<f64>,
+    /// Every probe in execution order
+    pub probes: Vec<CapacityProbe>,
+}
+
+impl CapacitySearch {
+    /// Create with a p99 SLO in milliseconds
+    pub fn new(slo_p99_ms: f64) -> Self {
+        Self {
+            slo_p99_ms,
+            ..Default::default()
+        }
+    }
+
+    /// Set RPS search range
+    pub fn with_range(mut self, min_rps: u32, max_rps: u32) -> Self {
+        self.min_rps = min_rps.max(1);
+        self.max_rps = max_rps.max(self.min_rps);
+        self
+    }
+
+    /// Set maximum tolerated error rate
+    pub fn with_max_error_rate(mut self, rate: f64) -> Self {
+        self.max_error_rate = rate;
+        self
+    }
+
+    /// Set bisection precision
+    pub fn with_precision(mut self, rps: u32) -> Self {
+        self.precision_rps = rps.max(1);
+        self
+    }
+
+    /// Whether a measurement satisfies the SLO at the target rate
+    pub fn evaluate(&self, rps: u32, achieved_rps: f64, p99_ms: f64, error_rate: f64) -> bool {
+        p99_ms <= self.slo_p99_ms
+            && error_rate <= self.max_error_rate
+            && achieved_rps >= rps as f64 * MIN_ACHIEVED_RATIO
+    }
+
+    /// Run the search against a gateway using fixed-rate benchmarks
+    pub async fn run(&self, config: &BenchmarkConfig) -> Result<CapacityResult> {
+        info!(
+            "Capacity search for {}: p99 <= {:.1}ms, {}-{} RPS",
+            config.gateway.name(),
+            self.slo_p99_ms,
+            self.min_rps,
+            self.max_rps
+        );
+
+        self.search_with(config.gateway, |rps| {
+            let config = config
+                .clone()
+                .with_pattern(LoadPattern::Constant { rps })
+                .with_concurrency(config.concurrency.max(rps / 10));
+            async move {
+                let result = BenchmarkRunner::new(config)
+                    .run()
+                    .await
+                    .with_context(|| format!("Probe at {rps} RPS failed"))?;
+                let m = &result.metrics;
+                Ok((
+                    m.throughput.rps,
+                    m.latency.percentiles.p99,
+                    1.0 - m.throughput.success_rate,
+                ))
+            }
+        })
+        .await
+    }
+
+    /// Run the search with a custom probe returning (achieved rps, p99 ms, error rate)
+    pub async fn search_with<F, Fut>(
+        &self,
+        gateway: GatewayImpl,
+        mut probe: F,
+    ) -> Result<CapacityResult>
+    where
+        F: FnMut(u32) -> Fut,
+        Fut: Future<Output = Result<(f64, f64, f64)>>,
+    {
+        let mut probes = Vec::new();
+
+        // Step phase: double the rate until the SLO breaks
+        let mut best: Option<CapacityProbe> = None;
+        let mut failed_at = None;
+        let mut rps = self.min_rps;
+        loop {
+            let result = self.measure(rps, probe(rps)).await?;
+            probes.push(result.clone());
+            if !result.passed {
+                failed_at = Some(rps);
+                break;
+            }
+            best = Some(result);
+            if rps >= self.max_rps {
+                break;
+            }
+            rps = rps.saturating_mul(2).min(self.max_rps);
+        }
+
+        // Bisect between the last passing and first failing rate
+        if let (Some(mut lo), Some(mut hi)) = (best.as_ref().map(|b| b.rps), failed_at) {
+            while hi - lo > self.precision_rps {
+                let mid = lo + (hi - lo) / 2;
+                let result = self.measure(mid, probe(mid)).await?;
+                probes.push(result.clone());
+                if result.passed {
+                    lo = mid;
+                    best = Some(result);
+                } else {
+                    hi = mid;
+                }
+            }
+        }
+
+        Ok(CapacityResult {
+            gateway,
+            search: self.clone(),
+            max_sustainable_rps: best.as_ref().map(|b| b.rps),
+            knee_p99_ms: best.as_ref().map(|b| b.p99_ms),
+            probes,
+        })
+    }
+
+    /// Await one probe and evaluate it against the SLO
+    async fn measure(
+        &self,
+        rps: u32,
+        probe: impl Future<Output = Result<(f64, f64, f64)>>,
+    ) -> Result<CapacityProbe> {
+        let (achieved_rps, p99_ms, error_rate) = probe.await?;
+        let passed = self.evaluate(rps, achieved_rps, p99_ms, error_rate);
+        info!(
+            "  {:>6} RPS: achieved {:.1}, p99 {:.2}ms, errors {:.2}% -> {}",
+            rps,
+            achieved_rps,
+            p99_ms,
+            error_rate * 100.0,
+            if passed { "ok" } else { "SLO violated" }
+        );
+
+        Ok(CapacityProbe {
+            rps,
+            achieved_rps,
+            p99_ms,
+            error_rate,
+            passed,
+        })
+    }
+}
+
+impl CapacityResult {
+    /// Whether the search hit the upper bound without breaking the SLO
+    pub fn reached_max(&self) -> bool {
+        self.max_sustainable_rps == Some(self.search.max_rps)
+    }
+
+    /// One-line summary
+    pub fn format_summary(&self) -> String {
+        match self.max_sustainable_rps {
+            Some(rps) => format!(
+                "{}: {} RPS{} at p99 {:.2}ms (SLO {:.1}ms)",
+                self.gateway.name(),
+                rps,
+                if self.reached_max() { "+" } else { "" },
+                self.knee_p99_ms.unwrap_or_default(),
+                self.search.slo_p99_ms
+            ),
+            None => format!(
+                "{}: SLO {:.1}ms not met at {} RPS",
+                self.gateway.name(),
+                self.search.slo_p99_ms,
+                self.search.min_rps
+            ),
+        }
+    }
+}
+
+/// Parse a latency like "50ms", "0.5s" or "50" (milliseconds)
+pub fn parse_latency_ms(s: &str) -> Option<f64> {
+    let s = s.trim();
+    let value = if let Some(ms) = s.strip_suffix("ms") {
+        ms.trim().parse::<f64>().ok()?
+    } else if let Some(secs) = s.strip_suffix('s') {
+        secs.trim().parse::<f64>().ok()? * 1000.0
+    } else {
+        s.parse::<f64>().ok()?
+    };
+    (value > 0.0).then_some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_latency() {
+        assert_eq!(parse_latency_ms("50ms"), Some(50.0));
+        assert_eq!(parse_latency_ms("0.5s"), Some(500.0));
+        assert_eq!(parse_latency_ms("20"), Some(20.0));
+        assert_eq!(parse_latency_ms("fast"), None);
+        assert_eq!(parse_latency_ms("0ms"), None);
+    }
+
+    /// Simulated gateway whose p99 grows past 50ms above 1300 RPS
+    async fn simulated(rps: u32) -> Result<(f64, f64, f64)> {
+        let p99 = if rps <= 1300 { 20.0 } else { 120.0 };
+        Ok((rps as f64, p99, 0.0))
+    }
+
+    #[tokio::test]
+    async fn test_search_finds_knee() {
+        let search = CapacitySearch::new(50.0)
+            .with_range(100, 10_000)
+            .with_precision(10);
+        let result = search
+            .search_with(GatewayImpl::Envoy, simulated)
+            .await
+            .unwrap();
+
+        let knee = result.max_sustainable_rps.unwrap();
+        assert!((1290..=1300).contains(&knee), "knee was {knee}");
+        assert!(!result.reached_max());
+        assert!(result.probes.iter().any(|p| !p.passed));
+    }
+
+    #[tokio::test]
+    async fn test_search_bounds() {
+        let search = CapacitySearch::new(50.0).with_range(100, 800);
+        let result = search
+            .search_with(GatewayImpl::Nginx, simulated)
+            .await
+            .unwrap();
+        assert_eq!(result.max_sustainable_rps, Some(800));
+        assert!(result.reached_max());
+
+        let search = CapacitySearch::new(10.0).with_range(100, 800);
+        let result = search
+            .search_with(GatewayImpl::Nginx, simulated)
+            .await
+            .unwrap();
+        assert_eq!(result.max_sustainable_rps, None);
+        assert_eq!(result.probes.len(), 1);
+    }
+
+    #[test]
+    fn test_evaluate_requires_achieved_rate() {
+        let search = CapacitySearch::new(50.0);
+        assert!(search.evaluate(1000, 980.0, 30.0, 0.0));
+        assert!(!search.evaluate(1000, 700.0, 30.0, 0.0));
+        assert!(!search.evaluate(1000, 1000.0, 30.0, 0.05));
+        assert!(!search.evaluate(1000, 1000.0, 60.0, 0.0));
+    }
+}
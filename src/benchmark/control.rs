@@ -0,0 +1,188 @@
+//! Pause/resume control for long benchmark runs
+//!
+//! Load can be quiesced with SIGUSR1 (toggle) or a pause file without
+//! discarding metrics collected so far. Paused time is excluded from
+//! the measured duration.
+
+use anyhow::Result;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tracing::info;
+
+/// Poll interval for the pause file
+const PAUSE_FILE_POLL_MS: u64 = 500;
+
+/// Accumulated pause bookkeeping
+#[derive(Debug, Default)]
+struct PauseState {
+    since: Option<Instant>,
+    total: Duration,
+}
+
+/// Shared pause/resume handle
+#[derive(Clone)]
+pub struct PauseControl {
+    tx: Arc<watch::Sender<bool>>,
+    state: Arc<Mutex<PauseState>>,
+}
+
+impl PauseControl {
+    pub fn new() -> Self {
+        let (tx, _rx) = watch::channel(false);
+        Self {
+            tx: Arc::new(tx),
+            state: Arc::new(Mutex::new(PauseState::default())),
+        }
+    }
+
+    /// Pause load generation
+    pub fn pause(&self) {
+        let mut state = self.state.lock().unwrap();
+        if state.since.is_none() {
+            state.since = Some(Instant::now());
+            self.tx.send_replace(true);
+        }
+    }
+
+    /// Resume load generation
+    pub fn resume(&self) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(since) = state.since.take() {
+            state.total += since.elapsed();
+            self.tx.send_replace(false);
+        }
+    }
+
+    /// Toggle pause state, returning true if now paused
+    pub fn toggle(&self) -> bool {
+        if self.is_paused() {
+            self.resume();
+            false
+        } else {
+            self.pause();
+            true
+        }
+    }
+
+    /// Whether load is currently paused
+    pub fn is_paused(&self) -> bool {
+        *self.tx.borrow()
+    }
+
+    /// Total time spent paused, including an ongoing pause
+    pub fn paused_for(&self) -> Duration {
+        let state = self.state.lock().unwrap();
+        state.total + state.since.map(|s| s.elapsed()).unwrap_or_default()
+    }
+
+    /// Time elapsed since `start`, excluding pauses
+    pub fn active_elapsed(&self, start: Instant) -> Duration {
+        start.elapsed().saturating_sub(self.paused_for())
+    }
+
+    /// Wait until load is not paused
+    pub async fn wait_if_paused(&self) {
+        let mut rx = self.tx.subscribe();
+        while *rx.borrow_and_update() {
+            if rx.changed().await.is_err() {
+                break;
+            }
+        }
+    }
+
+    /// Toggle pause on SIGUSR1
+    #[cfg(unix)]
+    pub fn listen_signal(&self) -> Result<JoinHandle<()>> {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let mut sig = signal(SignalKind::user_defined1())?;
+        let control = self.clone();
+
+        Ok(tokio::spawn(async move {
+            while sig.recv().await.is_some() {
+                if control.toggle() {
+                    info!("SIGUSR1 received: load paused");
+                } else {
+                    info!("SIGUSR1 received: load resumed");
+                }
+            }
+        }))
+    }
+
+    /// Pause while `path` exists, resume once it is removed
+    pub fn watch_file(&self, path: impl Into<PathBuf>) -> JoinHandle<()> {
+        let path = path.into();
+        let control = self.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let exists = path.exists();
+                if exists && !control.is_paused() {
+                    control.pause();
+                    info!("Pause file {} found: load paused", path.display());
+                } else if !exists && control.is_paused() {
+                    control.resume();
+                    info!("Pause file {} removed: load resumed", path.display());
+                }
+                tokio::time::sleep(Duration::from_millis(PAUSE_FILE_POLL_MS)).await;
+            }
+        })
+    }
+}
+
+impl Default for PauseControl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toggle_pause() {
+        let control = PauseControl::new();
+        assert!(!control.is_paused());
+
+        assert!(control.toggle());
+        assert!(control.is_paused());
+
+        assert!(!control.toggle());
+        assert!(!control.is_paused());
+    }
+
+    #[test]
+    fn test_paused_time_excluded() {
+        let control = PauseControl::new();
+        let start = Instant::now();
+
+        control.pause();
+        std::thread::sleep(Duration::from_millis(30));
+        control.resume();
+
+        assert!(control.paused_for() >= Duration::from_millis(30));
+        assert!(control.active_elapsed(start) < start.elapsed());
+    }
+
+    #[tokio::test]
+    async fn test_wait_resumes() {
+        let control = PauseControl::new();
+        control.pause();
+
+        let waiter = control.clone();
+        let handle = tokio::spawn(async move { waiter.wait_if_paused().await });
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(!handle.is_finished());
+
+        control.resume();
+        tokio::time::timeout(Duration::from_secs(1), handle)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+}
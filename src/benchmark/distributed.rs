@@ -0,0 +1,392 @@
+//! Distributed load generation
+//!
+//! Runs `gateway-poc benchmark run` on several load-generator workers
+//! (in-cluster pods or KubeVirt VMs over SSH) and merges their results.
+
+use anyhow::{Context, Result};
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use super::metrics::Metrics;
+use super::runner::{BenchmarkConfig, BenchmarkResult};
+use crate::k8s::{K8sClient, PodManager, TestPodConfig};
+use crate::kubevirt::{shell_quote, SshClient, SshConfig, VmiManager};
+use crate::utils::Teardown;
+
+/// Label applied to load-generator pods
+const WORKER_LABEL: &str = "gateway-poc-loadgen";
+
+/// Seconds to wait for a worker pod to start
+const POD_START_TIMEOUT_SECS: u64 = 120;
+
+/// Where load-generator workers run
+#[derive(Clone, Debug)]
+pub enum WorkerBackend {
+    /// In-cluster pods; the image must contain the gateway-poc binary
+    Pods {
+        namespace: String,
+        image: String,
+        /// Secrets the pods pull the image with
+        image_pull_secrets: Vec<String>,
+    },
+    /// KubeVirt VMs reached over SSH (gateway-poc must be installed)
+    Vms {
+        names: Vec<String>,
+        namespace: String,
+        ssh: SshConfig,
+    },
+}
+
+/// Metrics reported by a single worker
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WorkerMetrics {
+    /// Worker name (pod or VM)
+    pub worker: String,
+    /// Metrics measured by this worker
+    pub metrics: Metrics,
+}
+
+/// Coordinates load generation across multiple workers
+pub struct DistributedRunner {
+    config: BenchmarkConfig,
+    rps: u32,
+    workers: u32,
+    backend: WorkerBackend,
+}
+
+impl DistributedRunner {
+    /// Create a runner; `rps` and concurrency are split across workers
+    pub fn new(config: BenchmarkConfig, rps: u32, workers: u32, backend: WorkerBackend) -> Self {
+        let workers = match &backend {
+            WorkerBackend::Vms { names, .. } => names.len() as u32,
+            WorkerBackend::Pods { .. } => workers.max(1),
+        };
+
+        Self {
+            config,
+            rps,
+            workers,
+            backend,
+        }
+    }
+
+    /// Number of workers
+    pub fn workers(&self) -> u32 {
+        self.workers
+    }
+
+    /// Command line executed on each worker
+    pub fn worker_command(&self) -> Vec<String> {
+        let c = &self.config;
+        let per_worker = |v: u32| (v / self.workers).max(1).to_string();
+
+        let mut cmd: Vec<String> = [
+            "gateway-poc",
+            "benchmark",
+            "run",
+            "--gateway",
+            c.gateway.short_name(),
+            "--ip",
+            &c.gateway_ip,
+            "--port",
+            &c.port.to_string(),
+            "--path",
+            &c.path,
+            "--hostname",
+            &c.hostname,
+            "--duration",
+            &c.duration_secs.to_string(),
+            "--concurrency",
+            &per_worker(c.concurrency),
+            "--rps",
+            &per_worker(self.rps),
+            "--pattern",
+            c.pattern.name(),
+            "--warmup",
+            &c.warmup_secs.to_string(),
+            "--drain",
+            &c.drain_secs.to_string(),
+            "--format",
+            "json",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+        if let Some(steady_state) = &c.steady_state {
+            cmd.push("--steady-state".to_string());
+            cmd.push("--steady-window".to_string());
+            cmd.push(steady_state.window_secs.to_string());
+        }
+        if let Some(mix) = &c.mix {
+            // JSON keeps request bodies, which `--target` specs cannot carry
+            cmd.push("--mix-json".to_string());
+            cmd.push(serde_json::to_string(mix).unwrap_or_default());
+        }
+
+        cmd
+    }
+
+    /// Run the benchmark on all workers and merge the results
+    pub async fn run(&self) -> Result<BenchmarkResult> {
+        info!(
+            "Starting distributed benchmark with {} workers",
+            self.workers
+        );
+
+        let results = match &self.backend {
+            WorkerBackend::Pods {
+                namespace,
+                image,
+                image_pull_secrets,
+            } => self.run_pods(namespace, image, image_pull_secrets).await?,
+            WorkerBackend::Vms {
+                names,
+                namespace,
+                ssh,
+            } => self.run_vms(names, namespace, ssh).await?,
+        };
+
+        let mut worker_results = Vec::new();
+        for (worker, result) in results {
+            match result {
+                Ok(r) => worker_results.push((worker, r)),
+                Err(e) => warn!("Worker {} failed: {:#}", worker, e),
+            }
+        }
+
+        merge_results(&self.config, worker_results).context("No worker produced a benchmark result")
+    }
+
+    /// Deploy load-generator pods, run, and clean up
+    async fn run_pods(
+        &self,
+        namespace: &str,
+        image: &str,
+        image_pull_secrets: &[String],
+    ) -> Result<Vec<(String, Result<BenchmarkResult>)>> {
+        let pods = PodManager::new(K8sClient::new(namespace).await?);
+        let names: Vec<String> = (0..self.workers)
+            .map(|i| format!("{WORKER_LABEL}-{i}"))
+            .collect();
+        let teardown = Teardown::new();
+
+        teardown
+            .guard(async {
+                for name in &names {
+                    let pod_config = TestPodConfig::new(name, namespace)
+                        .with_image(image)
+                        .with_label("app", WORKER_LABEL)
+                        .with_image_pull_secrets(image_pull_secrets.to_vec());
+                    pods.create_test_pod(&pod_config).await?;
+
+                    let (pods, name, namespace) =
+                        (pods.clone(), name.clone(), namespace.to_string());
+                    teardown.register(format!("delete worker pod {name}"), move || {
+                        let (pods, name, namespace) =
+                            (pods.clone(), name.clone(), namespace.clone());
+                        async move { pods.delete_pod(&name, &namespace).await }
+                    });
+                }
+
+                // Start all workers together once every pod is up
+                for name in &names {
+                    pods.wait_running(name, namespace, POD_START_TIMEOUT_SECS)
+                        .await
+                        .with_context(|| format!("Worker pod {name} did not start"))?;
+                }
+
+                let command = self.worker_command();
+                let runs = names.iter().map(|name| {
+                    let command = command.clone();
+                    let pods = &pods;
+                    async move {
+                        let output = pods.exec_in_pod(name, namespace, command).await;
+                        (name.clone(), output.and_then(|o| parse_worker_output(&o)))
+                    }
+                });
+
+                Ok(join_all(runs).await)
+            })
+            .await
+    }
+
+    /// Run on KubeVirt VMs over SSH
+    async fn run_vms(
+        &self,
+        names: &[String],
+        namespace: &str,
+        ssh: &SshConfig,
+    ) -> Result<Vec<(String, Result<BenchmarkResult>)>> {
+        let vmis = VmiManager::new(K8sClient::new(namespace).await?);
+        let mut hosts = Vec::new();
+        for name in names {
+            let ip = vmis
+                .get_ip(name, namespace)
+                .await?
+                .with_context(|| format!("VM {name} has no IP address"))?;
+            hosts.push((name.clone(), ip));
+        }
+
+        let client = SshClient::new(ssh.clone());
+        let command = self
+            .worker_command()
+            .iter()
+            .map(|a| shell_quote(a))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let runs = hosts.iter().map(|(name, ip)| {
+            let client = &client;
+            let command = &command;
+            async move {
+                let output = client.exec(ip, command).await.and_then(|o| {
+                    if o.is_success() {
+                        Ok(o.stdout)
+                    } else {
+                        anyhow::bail!("exit code {}: {}", o.exit_code, o.stderr.trim())
+                    }
+                });
+                (name.clone(), output.and_then(|o| parse_worker_output(&o)))
+            }
+        });
+
+        Ok(join_all(runs).await)
+    }
+}
+
+/// Extract the JSON benchmark result from worker stdout
+pub fn parse_worker_output(stdout: &str) -> Result<BenchmarkResult> {
+    let line = stdout
+        .lines()
+        .rev()
+        .find(|l| l.trim_start().starts_with('{'))
+        .context("Worker output contains no JSON result")?;
+    serde_json::from_str(line).context("Failed to parse worker result")
+}
+
+/// Merge per-worker results into a single benchmark result
+pub fn merge_results(
+    config: &BenchmarkConfig,
+    results: Vec<(String, BenchmarkResult)>,
+) -> Option<BenchmarkResult> {
+    if results.is_empty() {
+        return None;
+    }
+
+    let parts: Vec<Metrics> = results.iter().map(|(_, r)| r.metrics.clone()).collect();
+
+    Some(BenchmarkResult {
+        config: config.clone(),
+        metrics: Metrics::merge(&parts),
+        start_time: results.iter().map(|(_, r)| r.start_time).min()?,
+        end_time: results.iter().map(|(_, r)| r.end_time).max()?,
+        warmup_performed: results.iter().any(|(_, r)| r.warmup_performed),
+        paused_secs: results
+            .iter()
+            .map(|(_, r)| r.paused_secs)
+            .fold(0.0, f64::max),
+        workers: results
+            .into_iter()
+            .map(|(worker, r)| WorkerMetrics {
+                worker,
+                metrics: r.metrics,
+            })
+            .collect(),
+        resources: None,
+        crashes: Vec::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmark::{LoadPattern, TrafficMix, TrafficTarget};
+    use crate::models::GatewayImpl;
+
+    fn config() -> BenchmarkConfig {
+        BenchmarkConfig::new(GatewayImpl::Envoy, "10.0.0.1")
+            .with_pattern(LoadPattern::Constant { rps: 400 })
+            .with_concurrency(40)
+    }
+
+    #[test]
+    fn test_worker_command_splits_load() {
+        let backend = WorkerBackend::Pods {
+            namespace: "default".to_string(),
+            image: "gateway-poc:latest".to_string(),
+            image_pull_secrets: Vec::new(),
+        };
+        let runner = DistributedRunner::new(config(), 400, 4, backend);
+        let cmd = runner.worker_command();
+
+        let arg = |flag: &str| {
+            let i = cmd.iter().position(|a| a == flag).unwrap();
+            cmd[i + 1].clone()
+        };
+        assert_eq!(arg("--rps"), "100");
+        assert_eq!(arg("--concurrency"), "10");
+        assert_eq!(arg("--pattern"), "constant");
+        assert_eq!(arg("--format"), "json");
+        assert!(!cmd.iter().any(|a| a == "--mix-json"));
+
+        // Mixes travel as JSON so request bodies reach the workers
+        let mix = TrafficMix::new()
+            .with_target(TrafficTarget::new("/", 80, "GET"))
+            .with_target(TrafficTarget::new("/api/cart", 20, "POST").with_body(r#"{"qty":1}"#));
+        let backend = WorkerBackend::Pods {
+            namespace: "default".to_string(),
+            image: "gateway-poc:latest".to_string(),
+            image_pull_secrets: Vec::new(),
+        };
+        let runner = DistributedRunner::new(config().with_mix(mix.clone()), 400, 4, backend);
+        let cmd = runner.worker_command();
+        let i = cmd.iter().position(|a| a == "--mix-json").unwrap();
+        let sent: TrafficMix = serde_json::from_str(&cmd[i + 1]).unwrap();
+        assert_eq!(sent, mix);
+    }
+
+    #[test]
+    fn test_vm_backend_worker_count() {
+        let backend = WorkerBackend::Vms {
+            names: vec!["vm-a".to_string(), "vm-b".to_string()],
+            namespace: "default".to_string(),
+            ssh: SshConfig::default(),
+        };
+        let runner = DistributedRunner::new(config(), 400, 8, backend);
+        assert_eq!(runner.workers(), 2);
+    }
+
+    #[test]
+    fn test_parse_and_merge_worker_output() {
+        let result = BenchmarkResult {
+            config: config(),
+            metrics: Metrics::default(),
+            start_time: 100,
+            end_time: 160,
+            warmup_performed: true,
+            paused_secs: 0.0,
+            workers: Vec::new(),
+            resources: None,
+            crashes: Vec::new(),
+        };
+        let json = serde_json::to_string(&result).unwrap();
+        let stdout = format!("Starting benchmark...\n{json}\n");
+
+        let parsed = parse_worker_output(&stdout).unwrap();
+        assert_eq!(parsed.start_time, 100);
+        assert!(parse_worker_output("no json here").is_err());
+
+        let merged = merge_results(
+            &config(),
+            vec![
+                ("w0".to_string(), parsed.clone()),
+                ("w1".to_string(), parsed),
+            ],
+        )
+        .unwrap();
+        assert_eq!(merged.workers.len(), 2);
+        assert_eq!(merged.end_time, 160);
+        assert!(merge_results(&config(), Vec::new()).is_none());
+    }
+}
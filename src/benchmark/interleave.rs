@@ -0,0 +1,328 @@
+//! Interleaved gateway comparison
+//!
+//! Runs every gateway several times in shuffled A/B/A/B order with idle
+//! gaps between load phases, so cache warmth and cluster load drift are
+//! spread across gateways instead of favouring whichever ran first, and
+//! reports how much the position within a round moved the numbers.
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use serde::Serialize;
+use std::time::Duration;
+
+use super::metrics::{Metrics, ThroughputStats};
+use super::runner::BenchmarkResult;
+use crate::models::GatewayImpl;
+
+/// Interleaved schedule parameters
+#[derive(Clone, Debug, Serialize)]
+pub struct Interleave {
+    /// Load phases per gateway
+    pub rounds: u32,
+    /// Idle time between phases in seconds
+    pub gap_secs: u64,
+    /// Shuffle seed (printed so a schedule can be replayed)
+    pub seed: u64,
+}
+
+/// One load phase of the schedule
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct Phase {
+    /// Round number (1-based)
+    pub round: u32,
+    /// Position within the round (1-based)
+    pub position: usize,
+    pub gateway: GatewayImpl,
+}
+
+impl Interleave {
+    pub fn new(rounds: u32) -> Self {
+        Self {
+            rounds: rounds.max(1),
+            gap_secs: 10,
+            seed: rand::random(),
+        }
+    }
+
+    pub fn with_gap(mut self, secs: u64) -> Self {
+        self.gap_secs = secs;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn gap(&self) -> Duration {
+        Duration::from_secs(self.gap_secs)
+    }
+
+    /// Phases in execution order; every round runs each gateway once in a
+    /// freshly shuffled order
+    pub fn schedule(&self, gateways: &[GatewayImpl]) -> Vec<Phase> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut order = gateways.to_vec();
+        let mut phases = Vec::new();
+        for round in 1..=self.rounds {
+            order.shuffle(&mut rng);
+            phases.extend(order.iter().enumerate().map(|(i, gateway)| Phase {
+                round,
+                position: i + 1,
+                gateway: *gateway,
+            }));
+        }
+        phases
+    }
+}
+
+/// Mean deviation of phases run at one position from their gateway's mean
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct OrderingEffect {
+    pub position: usize,
+    pub phases: usize,
+    /// RPS deviation in percent
+    pub rps_delta_pct: f64,
+    /// p99 latency deviation in percent
+    pub p99_delta_pct: f64,
+}
+
+/// Results of an interleaved comparison
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct InterleavedRun {
+    pub phases: Vec<(Phase, BenchmarkResult)>,
+}
+
+impl InterleavedRun {
+    pub fn push(&mut self, phase: Phase, result: BenchmarkResult) {
+        self.phases.push((phase, result));
+    }
+
+    fn gateways(&self) -> Vec<GatewayImpl> {
+        let mut gateways = Vec::new();
+        for (phase, _) in &self.phases {
+            if !gateways.contains(&phase.gateway) {
+                gateways.push(phase.gateway);
+            }
+        }
+        gateways
+    }
+
+    fn results_for(&self, gateway: GatewayImpl) -> Vec<&BenchmarkResult> {
+        self.phases
+            .iter()
+            .filter(|(phase, _)| phase.gateway == gateway)
+            .map(|(_, result)| result)
+            .collect()
+    }
+
+    /// One result per gateway combining all of its phases
+    pub fn combined(&self) -> Vec<BenchmarkResult> {
+        self.gateways()
+            .into_iter()
+            .map(|gateway| {
+                let results = self.results_for(gateway);
+                let parts: Vec<Metrics> = results.iter().map(|r| r.metrics.clone()).collect();
+                let mut metrics = Metrics::merge(&parts);
+                // Phases ran one after another, not concurrently
+                let duration: f64 = parts.iter().map(|m| m.throughput.duration_secs).sum();
+                metrics.throughput = ThroughputStats::new(
+                    metrics.throughput.total_requests,
+                    metrics.throughput.successful_requests,
+                    Duration::from_secs_f64(duration),
+                );
+
+                let mut combined = results[0].clone();
+                combined.metrics = metrics;
+                combined.end_time = results.iter().map(|r| r.end_time).max().unwrap_or(0);
+                combined.paused_secs = results.iter().map(|r| r.paused_secs).sum();
+                combined.crashes = results.iter().flat_map(|r| r.crashes.clone()).collect();
+                combined
+            })
+            .collect()
+    }
+
+    /// How running first, second, ... within a round shifted RPS and p99
+    pub fn ordering_effects(&self) -> Vec<OrderingEffect> {
+        let means: Vec<(GatewayImpl, f64, f64)> = self
+            .gateways()
+            .into_iter()
+            .map(|gateway| {
+                let results = self.results_for(gateway);
+                let n = results.len() as f64;
+                let rps = results
+                    .iter()
+                    .map(|r| r.metrics.throughput.rps)
+                    .sum::<f64>()
+                    / n;
+                let p99 = results
+                    .iter()
+                    .map(|r| r.metrics.latency.percentiles.p99)
+                    .sum::<f64>()
+                    / n;
+                (gateway, rps, p99)
+            })
+            .collect();
+
+        let positions = self
+            .phases
+            .iter()
+            .map(|(p, _)| p.position)
+            .max()
+            .unwrap_or(0);
+        (1..=positions)
+            .filter_map(|position| {
+                let deltas: Vec<(f64, f64)> = self
+                    .phases
+                    .iter()
+                    .filter(|(phase, _)| phase.position == position)
+                    .filter_map(|(phase, result)| {
+                        let (_, rps, p99) = means.iter().find(|(g, _, _)| *g == phase.gateway)?;
+                        Some((
+                            relative_pct(result.metrics.throughput.rps, *rps),
+                            relative_pct(result.metrics.latency.percentiles.p99, *p99),
+                        ))
+                    })
+                    .collect();
+                if deltas.is_empty() {
+                    return None;
+                }
+                let n = deltas.len() as f64;
+                Some(OrderingEffect {
+                    position,
+                    phases: deltas.len(),
+                    rps_delta_pct: deltas.iter().map(|d| d.0).sum::<f64>() / n,
+                    p99_delta_pct: deltas.iter().map(|d| d.1).sum::<f64>() / n,
+                })
+            })
+            .collect()
+    }
+
+    /// Text table of the ordering effects
+    pub fn format_ordering(&self) -> String {
+        let mut output = String::from("\nOrdering Effects (vs. each gateway's mean):\n");
+        output.push_str(&format!(
+            "  {:<10} {:>7} {:>10} {:>10}\n",
+            "Position", "Phases", "RPS", "p99"
+        ));
+        for effect in self.ordering_effects() {
+            output.push_str(&format!(
+                "  {:<10} {:>7} {:>+9.1}% {:>+9.1}%\n",
+                ordinal(effect.position),
+                effect.phases,
+                effect.rps_delta_pct,
+                effect.p99_delta_pct
+            ));
+        }
+        output
+    }
+}
+
+fn relative_pct(value: f64, mean: f64) -> f64 {
+    if mean > 0.0 {
+        (value / mean - 1.0) * 100.0
+    } else {
+        0.0
+    }
+}
+
+fn ordinal(n: usize) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (1, 11) | (2, 12) | (3, 13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{n}{suffix}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmark::{BenchmarkConfig, LatencyStats};
+
+    fn result(gateway: GatewayImpl, rps: f64, p99: f64) -> BenchmarkResult {
+        let requests = (rps * 10.0) as u64;
+        let mut metrics = Metrics {
+            throughput: ThroughputStats::new(requests, requests, Duration::from_secs(10)),
+            latency: LatencyStats {
+                count: requests as usize,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        metrics.latency.percentiles.p99 = p99;
+        BenchmarkResult {
+            config: BenchmarkConfig::new(gateway, "127.0.0.1"),
+            metrics,
+            start_time: 0,
+            end_time: 10,
+            warmup_performed: false,
+            paused_secs: 0.0,
+            workers: Vec::new(),
+            resources: None,
+            crashes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_schedule() {
+        let gateways = [GatewayImpl::Nginx, GatewayImpl::Envoy, GatewayImpl::Istio];
+        let interleave = Interleave::new(4).with_seed(7);
+        let phases = interleave.schedule(&gateways);
+        assert_eq!(phases.len(), 12);
+        for round in 1..=4 {
+            let mut order: Vec<GatewayImpl> = phases
+                .iter()
+                .filter(|p| p.round == round)
+                .map(|p| p.gateway)
+                .collect();
+            order.sort_by_key(|g| g.name());
+            assert_eq!(order.len(), 3);
+            order.dedup();
+            assert_eq!(order.len(), 3);
+        }
+        assert_eq!(phases, Interleave::new(4).with_seed(7).schedule(&gateways));
+    }
+
+    #[test]
+    fn test_ordering_effects() {
+        let phase = |round, position, gateway| Phase {
+            round,
+            position,
+            gateway,
+        };
+        let mut run = InterleavedRun::default();
+        // Whichever gateway runs first is 10% slower
+        run.push(
+            phase(1, 1, GatewayImpl::Nginx),
+            result(GatewayImpl::Nginx, 90.0, 11.0),
+        );
+        run.push(
+            phase(1, 2, GatewayImpl::Envoy),
+            result(GatewayImpl::Envoy, 110.0, 9.0),
+        );
+        run.push(
+            phase(2, 1, GatewayImpl::Envoy),
+            result(GatewayImpl::Envoy, 90.0, 11.0),
+        );
+        run.push(
+            phase(2, 2, GatewayImpl::Nginx),
+            result(GatewayImpl::Nginx, 110.0, 9.0),
+        );
+
+        let effects = run.ordering_effects();
+        assert_eq!(effects.len(), 2);
+        assert_eq!(effects[0].phases, 2);
+        assert!((effects[0].rps_delta_pct + 10.0).abs() < 1e-9);
+        assert!((effects[1].p99_delta_pct + 10.0).abs() < 1e-9);
+
+        let combined = run.combined();
+        assert_eq!(combined.len(), 2);
+        assert_eq!(combined[0].metrics.throughput.total_requests, 2000);
+        assert!((combined[0].metrics.throughput.rps - 100.0).abs() < 1e-9);
+        assert!(run.format_ordering().contains("1st"));
+    }
+}
@@ -3,125 +3,11 @@
 //! Provides latency percentiles, throughput calculation, and statistical analysis.
 
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
 use std::time::{Duration, Instant};
 
-/// Latency percentiles (p50, p90, p95, p99, p999)
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
-pub struct Percentiles {
-    /// 50th percentile (median)
-    pub p50: f64,
-    /// 90th percentile
-    pub p90: f64,
-    /// 95th percentile
-    pub p95: f64,
-    /// 99th percentile
-    pub p99: f64,
-    /// 99.9th percentile
-    pub p999: f64,
-}
-
-impl Percentiles {
-    /// Calculate percentiles from sorted latencies (in milliseconds)
-    pub fn from_sorted(latencies: &[f64]) -> Self {
-        if latencies.is_empty() {
-            return Self::default();
-        }
-
-        Self {
-            p50: percentile(latencies, 50.0),
-            p90: percentile(latencies, 90.0),
-            p95: percentile(latencies, 95.0),
-            p99: percentile(latencies, 99.0),
-            p999: percentile(latencies, 99.9),
-        }
-    }
-
-    /// Format as table row
-    pub fn format_row(&self) -> String {
-        format!(
-            "{:>8.2} {:>8.2} {:>8.2} {:>8.2} {:>8.2}",
-            self.p50, self.p90, self.p95, self.p99, self.p999
-        )
-    }
-}
-
-/// Calculate percentile value from sorted array
-fn percentile(sorted: &[f64], p: f64) -> f64 {
-    if sorted.is_empty() {
-        return 0.0;
-    }
-    if sorted.len() == 1 {
-        return sorted[0];
-    }
-
-    let idx = (p / 100.0) * (sorted.len() - 1) as f64;
-    let lower = idx.floor() as usize;
-    let upper = idx.ceil() as usize;
-    let fraction = idx - lower as f64;
-
-    if upper >= sorted.len() {
-        sorted[sorted.len() - 1]
-    } else {
-        sorted[lower] * (1.0 - fraction) + sorted[upper] * fraction
-    }
-}
-
-/// Latency statistics
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
-pub struct LatencyStats {
-    /// Minimum latency in milliseconds
-    pub min: f64,
-    /// Maximum latency in milliseconds
-    pub max: f64,
-    /// Mean latency in milliseconds
-    pub mean: f64,
-    /// Standard deviation in milliseconds
-    pub std_dev: f64,
-    /// Latency percentiles
-    pub percentiles: Percentiles,
-    /// Total number of samples
-    pub count: usize,
-}
-
-impl LatencyStats {
-    /// Calculate statistics from latency samples (in milliseconds)
-    pub fn from_samples(samples: &[f64]) -> Self {
-        if samples.is_empty() {
-            return Self::default();
-        }
-
-        let mut sorted = samples.to_vec();
-        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-
-        let min = sorted[0];
-        let max = sorted[sorted.len() - 1];
-        let sum: f64 = sorted.iter().sum();
-        let mean = sum / sorted.len() as f64;
-
-        let variance: f64 =
-            sorted.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / sorted.len() as f64;
-        let std_dev = variance.sqrt();
-
-        let percentiles = Percentiles::from_sorted(&sorted);
-
-        Self {
-            min,
-            max,
-            mean,
-            std_dev,
-            percentiles,
-            count: sorted.len(),
-        }
-    }
-
-    /// Format as summary string
-    pub fn format_summary(&self) -> String {
-        format!(
-            "min={:.2}ms max={:.2}ms mean={:.2}ms std={:.2}ms p95={:.2}ms p99={:.2}ms",
-            self.min, self.max, self.mean, self.std_dev, self.percentiles.p95, self.percentiles.p99
-        )
-    }
-}
+use super::control::PauseControl;
+use crate::models::{LatencyStats, Percentiles};
 
 /// Throughput statistics
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -185,6 +71,97 @@ pub struct Metrics {
     pub throughput: ThroughputStats,
     /// Error rate by type
     pub errors: ErrorStats,
+    /// Per-endpoint breakdown (traffic mix only)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub endpoints: BTreeMap<String, EndpointMetrics>,
+    /// Samples excluded by warmup and steady-state detection
+    #[serde(default)]
+    pub discarded: DiscardedSamples,
+}
+
+impl Metrics {
+    /// Combine metrics from concurrent load generators
+    pub fn merge(parts: &[Metrics]) -> Self {
+        let total: u64 = parts.iter().map(|m| m.throughput.total_requests).sum();
+        let successful: u64 = parts.iter().map(|m| m.throughput.successful_requests).sum();
+        let duration_secs = parts
+            .iter()
+            .map(|m| m.throughput.duration_secs)
+            .fold(0.0, f64::max);
+
+        let mut errors = ErrorStats::default();
+        let mut discarded = DiscardedSamples::default();
+        let mut endpoint_parts: BTreeMap<String, Vec<&EndpointMetrics>> = BTreeMap::new();
+
+        for m in parts {
+            errors.add(&m.errors);
+            discarded.warmup += m.discarded.warmup;
+            discarded.ramp_up += m.discarded.ramp_up;
+            discarded.cancelled += m.discarded.cancelled;
+            discarded.steady_state_secs = discarded
+                .steady_state_secs
+                .max(m.discarded.steady_state_secs);
+            for (name, ep) in &m.endpoints {
+                endpoint_parts.entry(name.clone()).or_default().push(ep);
+            }
+        }
+
+        let endpoints = endpoint_parts
+            .into_iter()
+            .map(|(name, eps)| {
+                let latencies: Vec<LatencyStats> = eps.iter().map(|e| e.latency.clone()).collect();
+                let mut errors = ErrorStats::default();
+                for ep in &eps {
+                    errors.add(&ep.errors);
+                }
+                let merged = EndpointMetrics {
+                    latency: LatencyStats::merge(&latencies),
+                    total_requests: eps.iter().map(|e| e.total_requests).sum(),
+                    failed_requests: eps.iter().map(|e| e.failed_requests).sum(),
+                    errors,
+                };
+                (name, merged)
+            })
+            .collect();
+
+        let latencies: Vec<LatencyStats> = parts.iter().map(|m| m.latency.clone()).collect();
+
+        Metrics {
+            latency: LatencyStats::merge(&latencies),
+            throughput: ThroughputStats::new(
+                total,
+                successful,
+                Duration::from_secs_f64(duration_secs),
+            ),
+            errors,
+            endpoints,
+            discarded,
+        }
+    }
+}
+
+/// Metrics for a single endpoint in a traffic mix
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct EndpointMetrics {
+    /// Latency statistics
+    pub latency: LatencyStats,
+    /// Total requests
+    pub total_requests: u64,
+    /// Failed requests
+    pub failed_requests: u64,
+    /// Error breakdown
+    pub errors: ErrorStats,
+}
+
+impl EndpointMetrics {
+    /// Error rate (0.0 - 1.0)
+    pub fn error_rate(&self) -> f64 {
+        if self.total_requests > 0 {
+            self.failed_requests as f64 / self.total_requests as f64
+        } else {
+            0.0
+        }
+    }
 }
 
 /// Error statistics
@@ -212,6 +189,15 @@ impl ErrorStats {
             + self.other_errors
     }
 
+    /// Add counts from another set of error stats
+    pub fn add(&mut self, other: &ErrorStats) {
+        self.connection_errors += other.connection_errors;
+        self.timeout_errors += other.timeout_errors;
+        self.client_errors += other.client_errors;
+        self.server_errors += other.server_errors;
+        self.other_errors += other.other_errors;
+    }
+
     /// Record an error by status code or type
     pub fn record(
         &mut self,
@@ -235,36 +221,251 @@ impl ErrorStats {
     }
 }
 
+/// Samples excluded from the reported metrics
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiscardedSamples {
+    /// Requests completed during warmup
+    pub warmup: u64,
+    /// Requests dropped before steady state was reached
+    pub ramp_up: u64,
+    /// Seconds after warmup at which steady state was detected
+    pub steady_state_secs: Option<u64>,
+    /// Requests still in flight when the stop drain timed out (not errors)
+    #[serde(default)]
+    pub cancelled: u64,
+}
+
+impl DiscardedSamples {
+    /// Total discarded requests
+    pub fn total(&self) -> u64 {
+        self.warmup + self.ramp_up + self.cancelled
+    }
+
+    /// Human-readable note for reports, if anything was dropped
+    pub fn note(&self) -> Option<String> {
+        if self.total() == 0 {
+            return None;
+        }
+
+        let mut notes = Vec::new();
+        if self.warmup + self.ramp_up > 0 {
+            let mut note = format!(
+                "Dropped {} warmup and {} ramp-up samples",
+                self.warmup, self.ramp_up
+            );
+            if let Some(secs) = self.steady_state_secs {
+                note.push_str(&format!(" (steady state after {secs}s)"));
+            }
+            notes.push(note);
+        }
+        if self.cancelled > 0 {
+            notes.push(format!(
+                "Cancelled {} in-flight requests at shutdown",
+                self.cancelled
+            ));
+        }
+        Some(notes.join("; "))
+    }
+}
+
+/// Steady-state detection based on moving-average RPS
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SteadyState {
+    /// Moving-average window in seconds
+    pub window_secs: u64,
+    /// Maximum relative change between consecutive windows (0.0 - 1.0)
+    pub tolerance: f64,
+}
+
+impl Default for SteadyState {
+    fn default() -> Self {
+        Self {
+            window_secs: 5,
+            tolerance: 0.1,
+        }
+    }
+}
+
+impl SteadyState {
+    /// Find the first second at which per-second RPS stabilizes
+    pub fn detect(&self, per_second: &[u64]) -> Option<usize> {
+        let w = self.window_secs.max(1) as usize;
+        if per_second.len() < 2 * w {
+            return None;
+        }
+
+        let mean = |s: &[u64]| s.iter().sum::<u64>() as f64 / s.len() as f64;
+
+        (0..=per_second.len() - 2 * w).find(|&i| {
+            let current = mean(&per_second[i..i + w]);
+            let next = mean(&per_second[i + w..i + 2 * w]);
+            next > 0.0 && (current - next).abs() / next <= self.tolerance
+        })
+    }
+}
+
+/// Rolling view of the most recent requests, for live progress
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct LiveSample {
+    /// Active seconds since the benchmark started (including warmup)
+    pub elapsed_secs: f64,
+    /// Whether the benchmark is still warming up
+    pub warmup: bool,
+    /// Requests recorded so far (after warmup)
+    pub total_requests: u64,
+    /// Failed requests recorded so far
+    pub total_errors: u64,
+    /// Requests per second within the window
+    pub rps: f64,
+    /// Mean latency within the window
+    pub mean_ms: f64,
+    /// p99 latency within the window
+    pub p99_ms: f64,
+    /// Failed requests within the window
+    pub errors: u64,
+}
+
+/// A single recorded request
+#[derive(Clone, Debug)]
+struct Sample {
+    /// Seconds since the end of warmup
+    offset_secs: f64,
+    latency_ms: f64,
+    success: bool,
+    status_code: Option<u16>,
+    is_timeout: bool,
+    is_connection_error: bool,
+    /// Index into the collector's endpoint names
+    endpoint: Option<usize>,
+}
+
 /// Real-time metrics collector
 pub struct MetricsCollector {
-    /// Latency samples in milliseconds
-    latencies: Vec<f64>,
+    /// Recorded samples after warmup
+    samples: Vec<Sample>,
     /// Start time
     start_time: Instant,
-    /// Successful request count
-    success_count: u64,
-    /// Failed request count
+    /// Warmup window in seconds
+    warmup_secs: f64,
+    /// Requests completed during warmup
+    warmup_dropped: u64,
+    /// Endpoint names referenced by samples
+    endpoint_names: Vec<String>,
+    /// Optional steady-state detection
+    steady_state: Option<SteadyState>,
+    /// Pause handle; paused time is excluded from offsets
+    control: Option<PauseControl>,
+    /// Active seconds at which load generation stopped
+    stopped_at: Option<f64>,
+    /// In-flight requests cancelled at shutdown
+    cancelled: u64,
+    /// Failed requests after warmup
+    error_count: u64,
+    /// Span of the live samples in seconds
+    live_window_secs: f64,
+    /// Offset and latency of the requests inside the live window
+    live: VecDeque<LiveEntry>,
+    /// Running totals over `live`
+    live_latency_ms: f64,
+    live_errors: u64,
+}
+
+/// A request inside the live window
+#[derive(Clone, Copy, Debug)]
+struct LiveEntry {
+    offset_secs: f64,
+    latency_ms: f64,
+    success: bool,
+}
+
+/// Raw samples for one endpoint
+#[derive(Default)]
+struct EndpointSamples {
+    latencies: Vec<f64>,
     fail_count: u64,
-    /// Error statistics
     errors: ErrorStats,
 }
 
+impl EndpointSamples {
+    fn to_metrics(&self) -> EndpointMetrics {
+        EndpointMetrics {
+            latency: LatencyStats::from_samples(&self.latencies),
+            total_requests: self.latencies.len() as u64,
+            failed_requests: self.fail_count,
+            errors: self.errors.clone(),
+        }
+    }
+}
+
 impl MetricsCollector {
     /// Create a new collector
     pub fn new() -> Self {
         Self {
-            latencies: Vec::new(),
+            samples: Vec::new(),
             start_time: Instant::now(),
-            success_count: 0,
-            fail_count: 0,
-            errors: ErrorStats::default(),
+            warmup_secs: 0.0,
+            warmup_dropped: 0,
+            endpoint_names: Vec::new(),
+            steady_state: None,
+            control: None,
+            stopped_at: None,
+            cancelled: 0,
+            error_count: 0,
+            live_window_secs: 1.0,
+            live: VecDeque::new(),
+            live_latency_ms: 0.0,
+            live_errors: 0,
+        }
+    }
+
+    /// Span of the samples summarized by `live_sample` (default 1s)
+    pub fn with_live_window(mut self, window: Duration) -> Self {
+        self.live_window_secs = window.as_secs_f64();
+        self
+    }
+
+    /// Exclude samples recorded during the warmup window
+    pub fn with_warmup(mut self, warmup: Duration) -> Self {
+        self.warmup_secs = warmup.as_secs_f64();
+        self
+    }
+
+    /// Discard the ramp-up window once RPS stabilizes
+    pub fn with_steady_state(mut self, steady_state: SteadyState) -> Self {
+        self.steady_state = Some(steady_state);
+        self
+    }
+
+    /// Exclude time paused via `control` from measurements
+    pub fn with_pause_control(mut self, control: PauseControl) -> Self {
+        self.control = Some(control);
+        self
+    }
+
+    /// Elapsed time since start, excluding pauses
+    fn active_secs(&self) -> f64 {
+        match &self.control {
+            Some(control) => control.active_elapsed(self.start_time).as_secs_f64(),
+            None => self.start_time.elapsed().as_secs_f64(),
+        }
+    }
+
+    /// Mark the end of load generation; requests completing while in-flight
+    /// work drains are still recorded, but do not extend the measured window
+    pub fn mark_stopped(&mut self) {
+        if self.stopped_at.is_none() {
+            self.stopped_at = Some(self.active_secs());
         }
     }
 
+    /// Count a request abandoned when the stop drain timed out
+    pub fn record_cancelled(&mut self) {
+        self.cancelled += 1;
+    }
+
     /// Record a successful request
     pub fn record_success(&mut self, latency_ms: f64) {
-        self.latencies.push(latency_ms);
-        self.success_count += 1;
+        self.push(latency_ms, true, None, false, false, None);
     }
 
     /// Record a failed request
@@ -275,45 +476,204 @@ impl MetricsCollector {
         is_timeout: bool,
         is_connection_error: bool,
     ) {
-        self.latencies.push(latency_ms);
-        self.fail_count += 1;
-        self.errors
-            .record(status_code, is_timeout, is_connection_error);
+        self.push(
+            latency_ms,
+            false,
+            status_code,
+            is_timeout,
+            is_connection_error,
+            None,
+        );
     }
 
     /// Record a request result
     pub fn record(&mut self, latency_ms: f64, success: bool, status_code: Option<u16>) {
-        if success {
-            self.record_success(latency_ms);
-        } else {
-            self.record_failure(latency_ms, status_code, false, false);
+        self.push(latency_ms, success, status_code, false, false, None);
+    }
+
+    /// Record a request result for a named endpoint
+    pub fn record_endpoint(
+        &mut self,
+        endpoint: &str,
+        latency_ms: f64,
+        success: bool,
+        status_code: Option<u16>,
+        is_connection_error: bool,
+    ) {
+        let index = match self.endpoint_names.iter().position(|n| n == endpoint) {
+            Some(index) => index,
+            None => {
+                self.endpoint_names.push(endpoint.to_string());
+                self.endpoint_names.len() - 1
+            }
+        };
+        self.push(
+            latency_ms,
+            success,
+            status_code,
+            false,
+            is_connection_error,
+            Some(index),
+        );
+    }
+
+    fn push(
+        &mut self,
+        latency_ms: f64,
+        success: bool,
+        status_code: Option<u16>,
+        is_timeout: bool,
+        is_connection_error: bool,
+        endpoint: Option<usize>,
+    ) {
+        let elapsed = self.active_secs();
+        self.push_at(
+            elapsed,
+            latency_ms,
+            success,
+            status_code,
+            is_timeout,
+            is_connection_error,
+            endpoint,
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn push_at(
+        &mut self,
+        elapsed_secs: f64,
+        latency_ms: f64,
+        success: bool,
+        status_code: Option<u16>,
+        is_timeout: bool,
+        is_connection_error: bool,
+        endpoint: Option<usize>,
+    ) {
+        if elapsed_secs < self.warmup_secs {
+            self.warmup_dropped += 1;
+            return;
+        }
+
+        let offset_secs = elapsed_secs - self.warmup_secs;
+        if !success {
+            self.error_count += 1;
+        }
+        self.live.push_back(LiveEntry {
+            offset_secs,
+            latency_ms,
+            success,
+        });
+        self.live_latency_ms += latency_ms;
+        if !success {
+            self.live_errors += 1;
         }
+        self.trim_live(offset_secs - self.live_window_secs);
+
+        self.samples.push(Sample {
+            offset_secs,
+            latency_ms,
+            success,
+            status_code,
+            is_timeout,
+            is_connection_error,
+            endpoint,
+        });
     }
 
-    /// Get current metrics snapshot
-    pub fn snapshot(&self) -> Metrics {
-        let duration = self.start_time.elapsed();
-        let total = self.success_count + self.fail_count;
+    /// Seconds measured since the end of warmup
+    fn measured_secs(&self) -> f64 {
+        let end = self.stopped_at.unwrap_or_else(|| self.active_secs());
+        (end - self.warmup_secs).max(0.0)
+    }
 
-        Metrics {
-            latency: LatencyStats::from_samples(&self.latencies),
-            throughput: ThroughputStats::new(total, self.success_count, duration),
-            errors: self.errors.clone(),
+    /// Completed requests per full second after warmup
+    fn per_second_counts(&self, measured_secs: f64) -> Vec<u64> {
+        let mut counts = vec![0u64; measured_secs.floor() as usize];
+        for sample in &self.samples {
+            if let Some(bucket) = counts.get_mut(sample.offset_secs as usize) {
+                *bucket += 1;
+            }
         }
+        counts
     }
 
-    /// Finalize and return metrics
-    pub fn finalize(self) -> Metrics {
-        let duration = self.start_time.elapsed();
-        let total = self.success_count + self.fail_count;
+    /// Build metrics from the samples inside the measurement window
+    fn build_metrics(&self, measured_secs: f64) -> Metrics {
+        let mut discarded = DiscardedSamples {
+            warmup: self.warmup_dropped,
+            cancelled: self.cancelled,
+            ..Default::default()
+        };
+
+        let mut cutoff_secs = 0.0;
+        if let Some(steady_state) = &self.steady_state {
+            if let Some(secs) = steady_state.detect(&self.per_second_counts(measured_secs)) {
+                cutoff_secs = secs as f64;
+                discarded.steady_state_secs = Some(secs as u64);
+            }
+        }
+
+        let start = self
+            .samples
+            .partition_point(|s| s.offset_secs < cutoff_secs);
+        discarded.ramp_up = start as u64;
+        let kept = &self.samples[start..];
+
+        let mut latencies = Vec::with_capacity(kept.len());
+        let mut success_count = 0;
+        let mut errors = ErrorStats::default();
+        let mut endpoints: BTreeMap<usize, EndpointSamples> = BTreeMap::new();
+
+        for sample in kept {
+            latencies.push(sample.latency_ms);
+            if sample.success {
+                success_count += 1;
+            } else {
+                errors.record(
+                    sample.status_code,
+                    sample.is_timeout,
+                    sample.is_connection_error,
+                );
+            }
+
+            if let Some(index) = sample.endpoint {
+                let ep = endpoints.entry(index).or_default();
+                ep.latencies.push(sample.latency_ms);
+                if !sample.success {
+                    ep.fail_count += 1;
+                    ep.errors.record(
+                        sample.status_code,
+                        sample.is_timeout,
+                        sample.is_connection_error,
+                    );
+                }
+            }
+        }
+
+        let duration = Duration::from_secs_f64((measured_secs - cutoff_secs).max(0.0));
 
         Metrics {
-            latency: LatencyStats::from_samples(&self.latencies),
-            throughput: ThroughputStats::new(total, self.success_count, duration),
-            errors: self.errors,
+            latency: LatencyStats::from_samples(&latencies),
+            throughput: ThroughputStats::new(kept.len() as u64, success_count, duration),
+            errors,
+            endpoints: endpoints
+                .iter()
+                .map(|(i, ep)| (self.endpoint_names[*i].clone(), ep.to_metrics()))
+                .collect(),
+            discarded,
         }
     }
 
+    /// Get current metrics snapshot
+    pub fn snapshot(&self) -> Metrics {
+        self.build_metrics(self.measured_secs())
+    }
+
+    /// Finalize and return metrics
+    pub fn finalize(self) -> Metrics {
+        self.snapshot()
+    }
+
     /// Get elapsed time
     pub fn elapsed(&self) -> Duration {
         self.start_time.elapsed()
@@ -321,12 +681,62 @@ impl MetricsCollector {
 
     /// Get current request count
     pub fn request_count(&self) -> u64 {
-        self.success_count + self.fail_count
+        self.samples.len() as u64
+    }
+
+    /// Drop live entries older than `since` (seconds after warmup)
+    fn trim_live(&mut self, since: f64) {
+        while let Some(entry) = self.live.front().copied() {
+            if entry.offset_secs >= since {
+                break;
+            }
+            self.live.pop_front();
+            self.live_latency_ms -= entry.latency_ms;
+            if !entry.success {
+                self.live_errors -= 1;
+            }
+        }
+        if self.live.is_empty() {
+            // Don't carry float drift from the running sum
+            self.live_latency_ms = 0.0;
+        }
+    }
+
+    /// Summarize the requests of the live window
+    ///
+    /// Only the window is touched, so the cost does not grow with the run.
+    pub fn live_sample(&mut self) -> LiveSample {
+        self.live_sample_at(self.active_secs())
+    }
+
+    fn live_sample_at(&mut self, elapsed_secs: f64) -> LiveSample {
+        let measured = (elapsed_secs - self.warmup_secs).max(0.0);
+        let since = (measured - self.live_window_secs).max(0.0);
+        self.trim_live(since);
+
+        let mut latencies: Vec<f64> = self.live.iter().map(|e| e.latency_ms).collect();
+        latencies.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let span = (measured - since).max(f64::EPSILON);
+
+        LiveSample {
+            elapsed_secs,
+            warmup: elapsed_secs < self.warmup_secs,
+            total_requests: self.request_count(),
+            total_errors: self.error_count,
+            rps: self.live.len() as f64 / span,
+            mean_ms: if latencies.is_empty() {
+                0.0
+            } else {
+                self.live_latency_ms / latencies.len() as f64
+            },
+            p99_ms: Percentiles::from_sorted(&latencies).p99,
+            errors: self.live_errors,
+        }
     }
 
     /// Get current RPS
     pub fn current_rps(&self) -> f64 {
-        let elapsed = self.start_time.elapsed().as_secs_f64();
+        let elapsed = self.measured_secs();
         if elapsed > 0.0 {
             self.request_count() as f64 / elapsed
         } else {
@@ -345,28 +755,6 @@ impl Default for MetricsCollector {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_percentiles() {
-        let data: Vec<f64> = (1..=100).map(|x| x as f64).collect();
-        let p = Percentiles::from_sorted(&data);
-
-        assert!((p.p50 - 50.0).abs() < 1.0);
-        assert!((p.p90 - 90.0).abs() < 1.0);
-        assert!((p.p95 - 95.0).abs() < 1.0);
-        assert!((p.p99 - 99.0).abs() < 1.0);
-    }
-
-    #[test]
-    fn test_latency_stats() {
-        let samples: Vec<f64> = vec![1.0, 2.0, 3.0, 4.0, 5.0];
-        let stats = LatencyStats::from_samples(&samples);
-
-        assert_eq!(stats.min, 1.0);
-        assert_eq!(stats.max, 5.0);
-        assert_eq!(stats.mean, 3.0);
-        assert_eq!(stats.count, 5);
-    }
-
     #[test]
     fn test_throughput_stats() {
         let stats = ThroughputStats::new(1000, 950, Duration::from_secs(10));
@@ -394,6 +782,143 @@ mod tests {
         assert_eq!(metrics.errors.server_errors, 10);
     }
 
+    #[test]
+    fn test_endpoint_breakdown() {
+        let mut collector = MetricsCollector::new();
+        collector.record_endpoint("GET /api/list", 5.0, true, Some(200), false);
+        collector.record_endpoint("GET /api/list", 7.0, true, Some(200), false);
+        collector.record_endpoint("POST /api/create", 20.0, false, Some(503), false);
+
+        let metrics = collector.finalize();
+        assert_eq!(metrics.throughput.total_requests, 3);
+        assert_eq!(metrics.endpoints.len(), 2);
+
+        let list = &metrics.endpoints["GET /api/list"];
+        assert_eq!(list.total_requests, 2);
+        assert_eq!(list.error_rate(), 0.0);
+
+        let create = &metrics.endpoints["POST /api/create"];
+        assert_eq!(create.failed_requests, 1);
+        assert_eq!(create.errors.server_errors, 1);
+    }
+
+    #[test]
+    fn test_warmup_samples_excluded() {
+        let mut collector = MetricsCollector::new().with_warmup(Duration::from_secs(2));
+        collector.push_at(0.5, 50.0, true, Some(200), false, false, None);
+        collector.push_at(1.5, 40.0, false, Some(503), false, false, None);
+        collector.push_at(2.5, 5.0, true, Some(200), false, false, None);
+
+        let metrics = collector.build_metrics(1.0);
+        assert_eq!(metrics.discarded.warmup, 2);
+        assert_eq!(metrics.throughput.total_requests, 1);
+        assert_eq!(metrics.errors.total(), 0);
+        assert_eq!(metrics.latency.max, 5.0);
+        assert_eq!(
+            metrics.discarded.note().unwrap(),
+            "Dropped 2 warmup and 0 ramp-up samples"
+        );
+    }
+
+    #[test]
+    fn test_graceful_stop() {
+        let mut collector = MetricsCollector::new();
+        collector.mark_stopped();
+        let stopped_at = collector.stopped_at.unwrap();
+        collector.mark_stopped();
+        assert_eq!(collector.stopped_at, Some(stopped_at));
+
+        // Drained completions count; cancelled requests are not errors
+        std::thread::sleep(Duration::from_millis(20));
+        collector.record(5.0, true, Some(200));
+        collector.record_cancelled();
+        assert_eq!(collector.measured_secs(), stopped_at);
+
+        let metrics = collector.snapshot();
+        assert_eq!(metrics.throughput.total_requests, 1);
+        assert_eq!(metrics.errors.total(), 0);
+        assert_eq!(metrics.discarded.cancelled, 1);
+        assert_eq!(
+            metrics.discarded.note().unwrap(),
+            "Cancelled 1 in-flight requests at shutdown"
+        );
+    }
+
+    #[test]
+    fn test_live_sample_window() {
+        let mut collector = MetricsCollector::new()
+            .with_warmup(Duration::from_secs(1))
+            .with_live_window(Duration::from_secs(1));
+        collector.push_at(1.5, 10.0, true, Some(200), false, false, None);
+        collector.push_at(3.2, 20.0, true, Some(200), false, false, None);
+        collector.push_at(3.6, 30.0, false, Some(503), false, false, None);
+
+        assert!(collector.live_sample_at(0.5).warmup);
+
+        let live = collector.live_sample_at(4.0);
+        assert!(!live.warmup);
+        assert_eq!(live.total_requests, 3);
+        assert_eq!(live.total_errors, 1);
+        assert_eq!(live.errors, 1);
+        assert!((live.rps - 2.0).abs() < 1e-9);
+        assert!((live.mean_ms - 25.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_steady_state_detect() {
+        let detector = SteadyState {
+            window_secs: 2,
+            tolerance: 0.1,
+        };
+        let ramp = [10, 20, 40, 70, 100, 100, 100, 100, 100, 100];
+        assert_eq!(detector.detect(&ramp), Some(4));
+        assert_eq!(detector.detect(&[100, 100, 100, 100]), Some(0));
+        assert_eq!(detector.detect(&[10, 20, 40]), None);
+    }
+
+    #[test]
+    fn test_steady_state_discards_ramp_up() {
+        let mut collector = MetricsCollector::new().with_steady_state(SteadyState {
+            window_secs: 1,
+            tolerance: 0.1,
+        });
+        // 1 req in second 0, 5 in second 1, then 10/s for seconds 2-4
+        let per_second = [1, 5, 10, 10, 10];
+        for (sec, count) in per_second.iter().enumerate() {
+            for i in 0..*count {
+                let offset = sec as f64 + i as f64 / 10.0;
+                collector.push_at(offset, 5.0, true, Some(200), false, false, None);
+            }
+        }
+
+        let metrics = collector.build_metrics(5.0);
+        assert_eq!(metrics.discarded.steady_state_secs, Some(2));
+        assert_eq!(metrics.discarded.ramp_up, 6);
+        assert_eq!(metrics.throughput.total_requests, 30);
+        assert!((metrics.throughput.rps - 10.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_merge_metrics() {
+        let mut a = MetricsCollector::new();
+        let mut b = MetricsCollector::new();
+        for _ in 0..10 {
+            a.push_at(0.5, 10.0, true, Some(200), false, false, None);
+            b.push_at(0.5, 30.0, false, Some(502), false, false, None);
+        }
+
+        let merged = Metrics::merge(&[a.build_metrics(1.0), b.build_metrics(1.0)]);
+        assert_eq!(merged.throughput.total_requests, 20);
+        assert_eq!(merged.throughput.failed_requests, 10);
+        assert!((merged.throughput.rps - 20.0).abs() < 0.01);
+        assert_eq!(merged.errors.server_errors, 10);
+        assert_eq!(merged.latency.count, 20);
+        assert_eq!(merged.latency.min, 10.0);
+        assert_eq!(merged.latency.max, 30.0);
+        assert!((merged.latency.mean - 20.0).abs() < 0.01);
+        assert!((merged.latency.std_dev - 10.0).abs() < 0.01);
+    }
+
     #[test]
     fn test_error_stats() {
         let mut errors = ErrorStats::default();
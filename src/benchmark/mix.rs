@@ -0,0 +1,219 @@
+//! Weighted traffic mix for benchmarks
+//!
+//! Distributes benchmark requests across multiple endpoints by weight.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::config::VarResolver;
+
+/// A single weighted benchmark endpoint
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrafficTarget {
+    /// URL path
+    pub path: String,
+    /// Relative weight
+    pub weight: u32,
+    /// HTTP method
+    #[serde(default = "default_method")]
+    pub method: String,
+    /// Optional request body
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+fn default_method() -> String {
+    "GET".to_string()
+}
+
+impl TrafficTarget {
+    /// Create a new target
+    pub fn new(path: impl Into<String>, weight: u32, method: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            weight,
+            method: method.into().to_uppercase(),
+            body: None,
+        }
+    }
+
+    /// Set request body
+    pub fn with_body(mut self, body: impl Into<String>) -> Self {
+        self.body = Some(body.into());
+        self
+    }
+
+    /// Parse from `path:weight:method` (method defaults to GET)
+    pub fn parse(spec: &str) -> Result<Self> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        let (path, weight, method) = match parts.as_slice() {
+            [path, weight] => (*path, *weight, "GET"),
+            [path, weight, method] => (*path, *weight, *method),
+            _ => anyhow::bail!("Invalid target '{spec}', expected path:weight[:method]"),
+        };
+
+        if !path.starts_with('/') {
+            anyhow::bail!("Invalid target '{spec}': path must start with '/'");
+        }
+        let weight: u32 = weight
+            .parse()
+            .with_context(|| format!("Invalid weight in target '{spec}'"))?;
+
+        Ok(Self::new(path, weight, method))
+    }
+
+    /// Key used for per-endpoint metrics (e.g. "GET /api/list")
+    pub fn key(&self) -> String {
+        format!("{} {}", self.method, self.path)
+    }
+}
+
+/// Weighted set of benchmark endpoints
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrafficMix {
+    /// Endpoints in the mix
+    pub targets: Vec<TrafficTarget>,
+}
+
+impl TrafficMix {
+    /// Create an empty mix
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a target
+    pub fn with_target(mut self, target: TrafficTarget) -> Self {
+        self.targets.push(target);
+        self
+    }
+
+    /// Build from repeated `path:weight:method` specs
+    pub fn from_specs<S: AsRef<str>>(specs: &[S]) -> Result<Self> {
+        let targets = specs
+            .iter()
+            .map(|s| TrafficTarget::parse(s.as_ref()))
+            .collect::<Result<Vec<_>>>()?;
+        let mix = Self { targets };
+        mix.validate()?;
+        Ok(mix)
+    }
+
+    /// Load from a YAML mix file, binding `${...}` variables
+    pub fn load(path: impl AsRef<Path>, vars: &VarResolver) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read mix file: {}", path.display()))?;
+        let document: serde_yaml::Value = serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse mix file: {}", path.display()))?;
+        let document = vars
+            .resolve_value(document)
+            .with_context(|| format!("Failed to substitute variables in {}", path.display()))?;
+        let mut mix: Self = serde_yaml::from_value(document)
+            .with_context(|| format!("Failed to parse mix file: {}", path.display()))?;
+        for target in &mut mix.targets {
+            target.method = target.method.to_uppercase();
+        }
+        mix.validate()?;
+        Ok(mix)
+    }
+
+    /// Validate the mix
+    pub fn validate(&self) -> Result<()> {
+        if self.targets.is_empty() {
+            anyhow::bail!("Traffic mix has no targets");
+        }
+        if self.total_weight() == 0 {
+            anyhow::bail!("Traffic mix weights sum to zero");
+        }
+        Ok(())
+    }
+
+    /// Sum of all weights
+    pub fn total_weight(&self) -> u32 {
+        self.targets.iter().map(|t| t.weight).sum()
+    }
+
+    /// Pick a target for a roll in `0..total_weight()`
+    pub fn pick(&self, roll: u32) -> &TrafficTarget {
+        let mut acc = 0;
+        for target in &self.targets {
+            acc += target.weight;
+            if roll < acc {
+                return target;
+            }
+        }
+        &self.targets[self.targets.len() - 1]
+    }
+
+    /// Pick a random target by weight
+    pub fn pick_random(&self) -> &TrafficTarget {
+        self.pick(rand::random_range(0..self.total_weight()))
+    }
+
+    /// Share of traffic for a target (0.0 - 1.0)
+    pub fn share(&self, target: &TrafficTarget) -> f64 {
+        let total = self.total_weight();
+        if total == 0 {
+            0.0
+        } else {
+            target.weight as f64 / total as f64
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_target() {
+        let t = TrafficTarget::parse("/api/create:20:post").unwrap();
+        assert_eq!(t.path, "/api/create");
+        assert_eq!(t.weight, 20);
+        assert_eq!(t.method, "POST");
+
+        let t = TrafficTarget::parse("/health:10").unwrap();
+        assert_eq!(t.method, "GET");
+
+        assert!(TrafficTarget::parse("health:10").is_err());
+        assert!(TrafficTarget::parse("/health:x").is_err());
+    }
+
+    #[test]
+    fn test_pick_by_weight() {
+        let mix =
+            TrafficMix::from_specs(&["/api/list:70:GET", "/api/create:20:POST", "/health:10"])
+                .unwrap();
+
+        assert_eq!(mix.total_weight(), 100);
+        assert_eq!(mix.pick(0).path, "/api/list");
+        assert_eq!(mix.pick(69).path, "/api/list");
+        assert_eq!(mix.pick(70).path, "/api/create");
+        assert_eq!(mix.pick(95).path, "/health");
+    }
+
+    #[test]
+    fn test_load_yaml_mix() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mix.yaml");
+        std::fs::write(
+            &path,
+            "targets:\n  - path: /api/list\n    weight: 3\n  - path: /api/create\n    weight: 1\n    method: post\n",
+        )
+        .unwrap();
+
+        let mix = TrafficMix::load(&path, &VarResolver::from_env()).unwrap();
+        assert_eq!(mix.targets.len(), 2);
+        assert_eq!(mix.targets[0].method, "GET");
+        assert_eq!(mix.targets[1].method, "POST");
+        assert!((mix.share(&mix.targets[0]) - 0.75).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_empty_mix_invalid() {
+        assert!(TrafficMix::new().validate().is_err());
+        let zero = TrafficMix::new().with_target(TrafficTarget::new("/", 0, "GET"));
+        assert!(zero.validate().is_err());
+    }
+}
@@ -6,10 +6,26 @@
 #![allow(dead_code)]
 #![allow(unused_imports)]
 
+mod autotune;
+mod capacity;
+mod control;
+mod distributed;
+mod interleave;
 mod metrics;
+mod mix;
 mod report;
 mod runner;
 
-pub use metrics::{LatencyStats, Metrics, MetricsCollector, Percentiles, ThroughputStats};
+pub use crate::models::{LatencyStats, Percentiles};
+pub use autotune::{ConcurrencyTuner, TuningResult, TuningStep};
+pub use capacity::{parse_latency_ms, CapacityProbe, CapacityResult, CapacitySearch};
+pub use control::PauseControl;
+pub use distributed::{DistributedRunner, WorkerBackend, WorkerMetrics};
+pub use interleave::{Interleave, InterleavedRun, OrderingEffect, Phase};
+pub use metrics::{
+    DiscardedSamples, EndpointMetrics, LiveSample, Metrics, MetricsCollector, SteadyState,
+    ThroughputStats,
+};
+pub use mix::{TrafficMix, TrafficTarget};
 pub use report::{BenchmarkReport, ReportFormat as BenchmarkReportFormat};
 pub use runner::{BenchmarkConfig, BenchmarkResult, BenchmarkRunner, LoadPattern};
@@ -4,6 +4,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::autotune::TuningResult;
+use super::capacity::CapacityResult;
 use super::runner::{BenchmarkComparison, BenchmarkResult};
 
 /// Report output format
@@ -67,6 +69,237 @@ impl BenchmarkReport {
         }
     }
 
+    /// Generate capacity search report, ranked by sustainable RPS
+    pub fn capacity(results: &[CapacityResult], format: ReportFormat) -> String {
+        let mut ranked: Vec<&CapacityResult> = results.iter().collect();
+        ranked.sort_by_key(|r| std::cmp::Reverse(r.max_sustainable_rps));
+
+        match format {
+            ReportFormat::Json => serde_json::to_string(results).unwrap_or_default(),
+            ReportFormat::JsonPretty => serde_json::to_string_pretty(results).unwrap_or_default(),
+            ReportFormat::Markdown | ReportFormat::Html => Self::capacity_markdown(&ranked),
+            ReportFormat::Csv => Self::capacity_csv(&ranked),
+            ReportFormat::Text => Self::capacity_text(&ranked),
+        }
+    }
+
+    /// Generate autotuning report, ranked by sustainable RPS
+    pub fn autotune(results: &[TuningResult], format: ReportFormat) -> String {
+        let mut ranked: Vec<&TuningResult> = results.iter().collect();
+        ranked.sort_by(|a, b| {
+            b.sustainable_rps
+                .unwrap_or(-1.0)
+                .total_cmp(&a.sustainable_rps.unwrap_or(-1.0))
+        });
+
+        match format {
+            ReportFormat::Json => serde_json::to_string(results).unwrap_or_default(),
+            ReportFormat::JsonPretty => serde_json::to_string_pretty(results).unwrap_or_default(),
+            ReportFormat::Markdown | ReportFormat::Html => Self::autotune_markdown(&ranked),
+            ReportFormat::Csv => Self::autotune_csv(&ranked),
+            ReportFormat::Text => Self::autotune_text(&ranked),
+        }
+    }
+
+    /// Autotuning results as text
+    fn autotune_text(results: &[&TuningResult]) -> String {
+        let mut output = String::new();
+        output.push_str(&format!("\n{:=^70}\n", " Concurrency Autotuning "));
+        if let Some(first) = results.first() {
+            output.push_str(&format!(
+                "Target: p95 <= {:.1}ms, errors <= {:.1}%\n\n",
+                first.tuner.target_p95_ms,
+                first.tuner.max_error_rate * 100.0
+            ));
+        }
+        output.push_str(&format!(
+            "  {:<4} {:<24} {:>11} {:>10} {:>10} {:>6}\n",
+            "Rank", "Gateway", "Concurrency", "RPS", "p95 (ms)", "Steps"
+        ));
+        for (i, r) in results.iter().enumerate() {
+            output.push_str(&format!(
+                "  {:<4} {:<24} {:>11} {:>10} {:>10} {:>6}\n",
+                i + 1,
+                r.gateway.name(),
+                tuned_concurrency(r),
+                r.sustainable_rps
+                    .map(|v| format!("{v:.1}"))
+                    .unwrap_or_else(|| "-".to_string()),
+                r.p95_ms
+                    .map(|p| format!("{p:.2}"))
+                    .unwrap_or_else(|| "-".to_string()),
+                r.steps.len()
+            ));
+        }
+        output.push_str(&format!("\n{:=^70}\n", ""));
+        output
+    }
+
+    /// Autotuning results as markdown
+    fn autotune_markdown(results: &[&TuningResult]) -> String {
+        let mut output = String::new();
+        output.push_str("# Gateway Concurrency Autotuning\n\n");
+        if let Some(first) = results.first() {
+            output.push_str(&format!(
+                "Target: p95 <= {:.1}ms, errors <= {:.1}%\n\n",
+                first.tuner.target_p95_ms,
+                first.tuner.max_error_rate * 100.0
+            ));
+        }
+        output.push_str("| Rank | Gateway | Sustainable Concurrency | RPS | p95 (ms) |\n");
+        output.push_str("|------|---------|-------------------------|-----|----------|\n");
+        for (i, r) in results.iter().enumerate() {
+            output.push_str(&format!(
+                "| {} | {} | {} | {} | {} |\n",
+                i + 1,
+                r.gateway.name(),
+                tuned_concurrency(r),
+                r.sustainable_rps
+                    .map(|v| format!("{v:.1}"))
+                    .unwrap_or_else(|| "-".to_string()),
+                r.p95_ms
+                    .map(|p| format!("{p:.2}"))
+                    .unwrap_or_else(|| "-".to_string())
+            ));
+        }
+
+        for r in results {
+            output.push_str(&format!("\n## {} Steps\n\n", r.gateway.name()));
+            output.push_str("| Concurrency | Achieved RPS | p95 (ms) | Errors | Target |\n");
+            output.push_str("|-------------|--------------|----------|--------|--------|\n");
+            for s in &r.steps {
+                output.push_str(&format!(
+                    "| {} | {:.1} | {:.2} | {:.2}% | {} |\n",
+                    s.concurrency,
+                    s.achieved_rps,
+                    s.p95_ms,
+                    s.error_rate * 100.0,
+                    if s.within_target { "✓" } else { "✗" }
+                ));
+            }
+        }
+
+        output
+    }
+
+    /// Autotuning results as CSV
+    fn autotune_csv(results: &[&TuningResult]) -> String {
+        let mut output = String::from(
+            "gateway,target_p95_ms,sustainable_concurrency,sustainable_rps,p95_ms,converged,steps\n",
+        );
+        for r in results {
+            output.push_str(&format!(
+                "{},{:.2},{},{},{},{},{}\n",
+                r.gateway.short_name(),
+                r.tuner.target_p95_ms,
+                r.sustainable_concurrency
+                    .map(|v| v.to_string())
+                    .unwrap_or_default(),
+                r.sustainable_rps
+                    .map(|v| format!("{v:.1}"))
+                    .unwrap_or_default(),
+                r.p95_ms.map(|v| format!("{v:.2}")).unwrap_or_default(),
+                r.converged,
+                r.steps.len()
+            ));
+        }
+        output
+    }
+
+    /// Capacity results as text
+    fn capacity_text(results: &[&CapacityResult]) -> String {
+        let mut output = String::new();
+        output.push_str(&format!("\n{:=^70}\n", " Capacity Search "));
+        if let Some(first) = results.first() {
+            output.push_str(&format!(
+                "SLO: p99 <= {:.1}ms, errors <= {:.1}%\n\n",
+                first.search.slo_p99_ms,
+                first.search.max_error_rate * 100.0
+            ));
+        }
+        output.push_str(&format!(
+            "  {:<4} {:<24} {:>12} {:>12} {:>8}\n",
+            "Rank", "Gateway", "Max RPS", "p99 (ms)", "Probes"
+        ));
+        for (i, r) in results.iter().enumerate() {
+            output.push_str(&format!(
+                "  {:<4} {:<24} {:>12} {:>12} {:>8}\n",
+                i + 1,
+                r.gateway.name(),
+                capacity_rps(r),
+                r.knee_p99_ms
+                    .map(|p| format!("{p:.2}"))
+                    .unwrap_or_else(|| "-".to_string()),
+                r.probes.len()
+            ));
+        }
+        output.push_str(&format!("\n{:=^70}\n", ""));
+        output
+    }
+
+    /// Capacity results as markdown
+    fn capacity_markdown(results: &[&CapacityResult]) -> String {
+        let mut output = String::new();
+        output.push_str("# Gateway Capacity Search\n\n");
+        if let Some(first) = results.first() {
+            output.push_str(&format!(
+                "SLO: p99 <= {:.1}ms, errors <= {:.1}%\n\n",
+                first.search.slo_p99_ms,
+                first.search.max_error_rate * 100.0
+            ));
+        }
+        output.push_str("| Rank | Gateway | Max Sustainable RPS | p99 at Knee (ms) |\n");
+        output.push_str("|------|---------|---------------------|------------------|\n");
+        for (i, r) in results.iter().enumerate() {
+            output.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                i + 1,
+                r.gateway.name(),
+                capacity_rps(r),
+                r.knee_p99_ms
+                    .map(|p| format!("{p:.2}"))
+                    .unwrap_or_else(|| "-".to_string())
+            ));
+        }
+
+        for r in results {
+            output.push_str(&format!("\n## {} Probes\n\n", r.gateway.name()));
+            output.push_str("| Target RPS | Achieved RPS | p99 (ms) | Errors | SLO |\n");
+            output.push_str("|------------|--------------|----------|--------|-----|\n");
+            for p in &r.probes {
+                output.push_str(&format!(
+                    "| {} | {:.1} | {:.2} | {:.2}% | {} |\n",
+                    p.rps,
+                    p.achieved_rps,
+                    p.p99_ms,
+                    p.error_rate * 100.0,
+                    if p.passed { "✓" } else { "✗" }
+                ));
+            }
+        }
+
+        output
+    }
+
+    /// Capacity results as CSV
+    fn capacity_csv(results: &[&CapacityResult]) -> String {
+        let mut output =
+            String::from("gateway,slo_p99_ms,max_sustainable_rps,knee_p99_ms,probes\n");
+        for r in results {
+            output.push_str(&format!(
+                "{},{:.2},{},{},{}\n",
+                r.gateway.short_name(),
+                r.search.slo_p99_ms,
+                r.max_sustainable_rps
+                    .map(|v| v.to_string())
+                    .unwrap_or_default(),
+                r.knee_p99_ms.map(|v| format!("{v:.2}")).unwrap_or_default(),
+                r.probes.len()
+            ));
+        }
+        output
+    }
+
     /// Single result as text
     fn single_text(result: &BenchmarkResult) -> String {
         let mut output = String::new();
@@ -82,6 +315,16 @@ impl BenchmarkReport {
         output.push_str(&format!("  Duration:      {} seconds\n", c.duration_secs));
         output.push_str(&format!("  Concurrency:   {}\n", c.concurrency));
         output.push_str(&format!("  Load Pattern:  {:?}\n", c.pattern));
+        output.push_str(&format!("  Warmup:        {} seconds\n", c.warmup_secs));
+        if let Some(note) = m.discarded.note() {
+            output.push_str(&format!("  Note:          {note}\n"));
+        }
+        if result.paused_secs > 0.0 {
+            output.push_str(&format!(
+                "  Paused:        {:.1} seconds (excluded)\n",
+                result.paused_secs
+            ));
+        }
 
         output.push_str("\nThroughput:\n");
         output.push_str(&format!(
@@ -146,6 +389,65 @@ impl BenchmarkReport {
             output.push_str(&format!("  Other:        {:>10}\n", m.errors.other_errors));
         }
 
+        if !m.endpoints.is_empty() {
+            output.push_str("\nEndpoints:\n");
+            output.push_str(&format!(
+                "  {:<30} {:>10} {:>8} {:>10} {:>10}\n",
+                "Endpoint", "Requests", "Errors", "P50", "P99"
+            ));
+            for (name, ep) in &m.endpoints {
+                output.push_str(&format!(
+                    "  {:<30} {:>10} {:>7.1}% {:>10.2} {:>10.2}\n",
+                    name,
+                    ep.total_requests,
+                    ep.error_rate() * 100.0,
+                    ep.latency.percentiles.p50,
+                    ep.latency.percentiles.p99
+                ));
+            }
+        }
+
+        if let Some(usage) = result.resources.as_ref().filter(|r| !r.is_empty()) {
+            output.push_str("\nGateway Resources:\n");
+            output.push_str(&format!("  Pods:          {}\n", usage.selector));
+            output.push_str(&format!(
+                "  CPU (cores):   avg {:.2}, peak {:.2}\n",
+                usage.avg_cpu_cores(),
+                usage.peak_cpu_cores()
+            ));
+            output.push_str(&format!(
+                "  Memory Peak:   {:.1} MiB\n",
+                usage.peak_memory_mib()
+            ));
+            if let Some(rps_per_core) = result.rps_per_core() {
+                output.push_str(&format!("  RPS per Core:  {rps_per_core:.1}\n"));
+            }
+        }
+
+        if !result.workers.is_empty() {
+            output.push_str("\nWorkers:\n");
+            output.push_str(&format!(
+                "  {:<30} {:>10} {:>10} {:>10}\n",
+                "Worker", "Requests", "RPS", "P99"
+            ));
+            for w in &result.workers {
+                output.push_str(&format!(
+                    "  {:<30} {:>10} {:>10.1} {:>10.2}\n",
+                    w.worker,
+                    w.metrics.throughput.total_requests,
+                    w.metrics.throughput.rps,
+                    w.metrics.latency.percentiles.p99
+                ));
+            }
+        }
+
+        if result.is_tainted() {
+            output.push_str("\nTainted: gateway pods crashed during the run\n");
+            for crash in &result.crashes {
+                output.push_str(&format!("  {crash}\n"));
+            }
+        }
+
         output.push_str(&format!("\n{:=^70}\n", ""));
         output
     }
@@ -163,6 +465,18 @@ impl BenchmarkReport {
         output.push_str(&format!("| Target URL | `{}` |\n", c.url()));
         output.push_str(&format!("| Duration | {} seconds |\n", c.duration_secs));
         output.push_str(&format!("| Concurrency | {} |\n", c.concurrency));
+        output.push_str(&format!("| Warmup | {} seconds |\n", c.warmup_secs));
+
+        if result.paused_secs > 0.0 {
+            output.push_str(&format!(
+                "| Paused | {:.1} seconds (excluded) |\n",
+                result.paused_secs
+            ));
+        }
+
+        if let Some(note) = m.discarded.note() {
+            output.push_str(&format!("\n> {note}\n"));
+        }
 
         output.push_str("\n## Throughput\n\n");
         output.push_str("| Metric | Value |\n");
@@ -198,6 +512,67 @@ impl BenchmarkReport {
         output.push_str(&format!("| Mean | {:.2} |\n", m.latency.mean));
         output.push_str(&format!("| Std Dev | {:.2} |\n", m.latency.std_dev));
 
+        if !m.endpoints.is_empty() {
+            output.push_str("\n## Endpoints\n\n");
+            output.push_str("| Endpoint | Requests | Error Rate | P50 | P99 |\n");
+            output.push_str("|----------|----------|------------|-----|-----|\n");
+            for (name, ep) in &m.endpoints {
+                output.push_str(&format!(
+                    "| `{}` | {} | {:.1}% | {:.2} | {:.2} |\n",
+                    name,
+                    ep.total_requests,
+                    ep.error_rate() * 100.0,
+                    ep.latency.percentiles.p50,
+                    ep.latency.percentiles.p99
+                ));
+            }
+        }
+
+        if let Some(usage) = result.resources.as_ref().filter(|r| !r.is_empty()) {
+            output.push_str("\n## Gateway Resources\n\n");
+            output.push_str("| Metric | Value |\n");
+            output.push_str("|--------|-------|\n");
+            output.push_str(&format!("| Pods | `{}` |\n", usage.selector));
+            output.push_str(&format!(
+                "| CPU avg | {:.2} cores |\n",
+                usage.avg_cpu_cores()
+            ));
+            output.push_str(&format!(
+                "| CPU peak | {:.2} cores |\n",
+                usage.peak_cpu_cores()
+            ));
+            output.push_str(&format!(
+                "| Memory peak | {:.1} MiB |\n",
+                usage.peak_memory_mib()
+            ));
+            if let Some(rps_per_core) = result.rps_per_core() {
+                output.push_str(&format!("| RPS per core | {rps_per_core:.1} |\n"));
+            }
+        }
+
+        if !result.workers.is_empty() {
+            output.push_str("\n## Workers\n\n");
+            output.push_str("| Worker | Requests | RPS | P99 |\n");
+            output.push_str("|--------|----------|-----|-----|\n");
+            for w in &result.workers {
+                output.push_str(&format!(
+                    "| {} | {} | {:.1} | {:.2} |\n",
+                    w.worker,
+                    w.metrics.throughput.total_requests,
+                    w.metrics.throughput.rps,
+                    w.metrics.latency.percentiles.p99
+                ));
+            }
+        }
+
+        if result.is_tainted() {
+            output.push_str("\n## Gateway Crashes\n\n");
+            output.push_str("Results are tainted: gateway pods crashed during the run.\n\n");
+            for crash in &result.crashes {
+                output.push_str(&format!("- {crash}\n"));
+            }
+        }
+
         output
     }
 
@@ -360,7 +735,7 @@ impl BenchmarkReport {
             output.push_str(&format!(
                 "| {} | {} | {:.1} | {:.2} | {:.2} | {:.2} | {:.1}% |\n",
                 i + 1,
-                result.config.gateway.name(),
+                gateway_label(result),
                 result.metrics.throughput.rps,
                 result.metrics.latency.percentiles.p50,
                 result.metrics.latency.percentiles.p95,
@@ -383,18 +758,61 @@ impl BenchmarkReport {
             ));
         }
 
+        if comparison.has_resources() {
+            output.push_str("\n## Gateway Resources\n\n");
+            output.push_str(
+                "| Gateway | RPS/core | CPU avg (cores) | CPU peak (cores) | Memory peak (MiB) |\n",
+            );
+            output.push_str(
+                "|---------|----------|-----------------|------------------|-------------------|\n",
+            );
+
+            for result in comparison.by_rps() {
+                let Some(usage) = result.resources.as_ref().filter(|r| !r.is_empty()) else {
+                    continue;
+                };
+                output.push_str(&format!(
+                    "| {} | {} | {:.2} | {:.2} | {:.1} |\n",
+                    result.config.gateway.name(),
+                    result
+                        .rps_per_core()
+                        .map(|v| format!("{v:.1}"))
+                        .unwrap_or_else(|| "-".to_string()),
+                    usage.avg_cpu_cores(),
+                    usage.peak_cpu_cores(),
+                    usage.peak_memory_mib()
+                ));
+            }
+        }
+
+        if comparison.has_crashes() {
+            output.push_str("\n## Gateway Crashes\n\n");
+            output.push_str("| Gateway | Crash |\n");
+            output.push_str("|---------|-------|\n");
+            for result in comparison.by_rps() {
+                for crash in &result.crashes {
+                    output.push_str(&format!(
+                        "| {} | {} |\n",
+                        result.config.gateway.name(),
+                        crash
+                    ));
+                }
+            }
+        }
+
         output
     }
 
     /// Comparison as CSV
     fn comparison_csv(results: &[BenchmarkResult]) -> String {
         let mut output = String::new();
-        output.push_str("gateway,rps,success_rate,latency_p50,latency_p95,latency_p99,latency_p999,total_requests,failed_requests\n");
+        output.push_str("gateway,rps,success_rate,latency_p50,latency_p95,latency_p99,latency_p999,total_requests,failed_requests,rps_per_core,memory_peak_mib\n");
 
         for result in results {
             let m = &result.metrics;
+            let opt = |v: Option<f64>| v.map(|v| format!("{v:.2}")).unwrap_or_default();
             output.push_str(&format!(
-                "{},{:.2},{:.4},{:.2},{:.2},{:.2},{:.2},{},{}\n",
+                "{},{:.2},{:.4},{:.2},{:.2},{:.2},{:.2},{},{},{},{}\n",
                 result.config.gateway.short_name(),
                 m.throughput.rps,
                 m.throughput.success_rate,
@@ -403,7 +821,9 @@ impl BenchmarkReport {
                 m.latency.percentiles.p99,
                 m.latency.percentiles.p999,
                 m.throughput.total_requests,
-                m.throughput.failed_requests
+                m.throughput.failed_requests,
+                opt(result.rps_per_core()),
+                opt(result.peak_memory_mib())
             ));
         }
 
@@ -436,7 +856,7 @@ impl BenchmarkReport {
                     <td class="{}">{:.1}%</td>
                 </tr>"#,
                 i + 1,
-                result.config.gateway.name(),
+                gateway_label(result),
                 m.throughput.rps,
                 m.latency.percentiles.p50,
                 m.latency.percentiles.p95,
@@ -494,6 +914,33 @@ impl BenchmarkReport {
     }
 }
 
+/// Sustainable RPS for display ("+" when the search bound was reached)
+fn capacity_rps(result: &CapacityResult) -> String {
+    match result.max_sustainable_rps {
+        Some(rps) if result.reached_max() => format!("{rps}+"),
+        Some(rps) => rps.to_string(),
+        None => "-".to_string(),
+    }
+}
+
+/// Gateway name, marked when a pod crash tainted the result
+fn gateway_label(result: &BenchmarkResult) -> String {
+    if result.is_tainted() {
+        format!("{} (tainted)", result.config.gateway.name())
+    } else {
+        result.config.gateway.name().to_string()
+    }
+}
+
+/// Sustainable concurrency for display ("+" when the upper bound was reached)
+fn tuned_concurrency(result: &TuningResult) -> String {
+    match result.sustainable_concurrency {
+        Some(c) if result.reached_max() => format!("{c}+"),
+        Some(c) => c.to_string(),
+        None => "-".to_string(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
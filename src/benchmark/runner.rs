@@ -3,17 +3,34 @@
 //! Provides configurable load testing with various patterns.
 
 use anyhow::Result;
+use futures::future::join_all;
 use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
-use tokio::time::sleep;
-use tracing::{debug, info};
-
-use super::metrics::{Metrics, MetricsCollector};
-use crate::http::HttpClient;
-use crate::models::GatewayImpl;
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, timeout};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, info_span, Instrument};
+
+use super::control::PauseControl;
+use super::distributed::WorkerMetrics;
+use super::metrics::{Metrics, MetricsCollector, SteadyState};
+use super::mix::TrafficMix;
+use crate::http::{
+    authority, AuthHeaders, ConnectionSettings, DnsOverrides, HttpClient, HttpClientConfig,
+    HttpRequest,
+};
+use crate::k8s::{CrashWatcher, CrashWindow, ResourceCollector};
+use crate::models::{GatewayImpl, PodCrash, ResourceUsage};
+use crate::tui::{self, ProgressEvent, ProgressReporter};
+
+/// Window of the live samples sent to progress reporters
+const LIVE_WINDOW_SECS: f64 = 1.0;
+
+/// How often the end of the load window is checked
+const STOP_POLL_INTERVAL: Duration = Duration::from_millis(50);
 
 /// Load pattern for benchmark
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -50,6 +67,41 @@ impl Default for LoadPattern {
 }
 
 impl LoadPattern {
+    /// Build a pattern from its CLI name and target RPS
+    pub fn from_name(name: &str, rps: u32, duration_secs: u64, concurrency: u32) -> Self {
+        match name.to_lowercase().as_str() {
+            "ramp" => LoadPattern::Ramp {
+                start_rps: rps / 2,
+                end_rps: rps,
+                duration_secs,
+            },
+            "step" => LoadPattern::Step {
+                start_rps: rps / 4,
+                step_rps: rps / 4,
+                step_interval_secs: duration_secs / 4,
+                max_rps: rps,
+            },
+            "spike" => LoadPattern::Spike {
+                base_rps: rps / 2,
+                spike_rps: rps * 2,
+                spike_duration_secs: duration_secs / 6,
+            },
+            "max" => LoadPattern::Max { concurrency },
+            _ => LoadPattern::Constant { rps },
+        }
+    }
+
+    /// CLI name of the pattern
+    pub fn name(&self) -> &'static str {
+        match self {
+            LoadPattern::Constant { .. } => "constant",
+            LoadPattern::Ramp { .. } => "ramp",
+            LoadPattern::Step { .. } => "step",
+            LoadPattern::Spike { .. } => "spike",
+            LoadPattern::Max { .. } => "max",
+        }
+    }
+
     /// Get target RPS at a given time offset
     pub fn rps_at(&self, elapsed_secs: f64, total_duration_secs: f64) -> u32 {
         match self {
@@ -114,8 +166,25 @@ pub struct BenchmarkConfig {
     pub timeout_ms: u64,
     /// Warmup duration in seconds
     pub warmup_secs: u64,
-    /// Enable keep-alive
-    pub keep_alive: bool,
+    /// Seconds in-flight requests may finish after load stops
+    #[serde(default = "default_drain_secs")]
+    pub drain_secs: u64,
+    /// Connection reuse (keep-alive, idle pool size, TCP_NODELAY)
+    #[serde(flatten)]
+    pub connection: ConnectionSettings,
+    /// Steady-state detection (discards ramp-up samples)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub steady_state: Option<SteadyState>,
+    /// Weighted endpoint mix (overrides `path` when set)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mix: Option<TrafficMix>,
+    /// Nameservers and address family used when `gateway_ip` is a hostname
+    #[serde(default, skip_serializing_if = "DnsOverrides::is_empty")]
+    pub dns: DnsOverrides,
+}
+
+fn default_drain_secs() -> u64 {
+    5
 }
 
 impl Default for BenchmarkConfig {
@@ -131,7 +200,11 @@ impl Default for BenchmarkConfig {
             concurrency: 10,
             timeout_ms: 5000,
             warmup_secs: 5,
-            keep_alive: true,
+            drain_secs: default_drain_secs(),
+            connection: ConnectionSettings::default(),
+            steady_state: None,
+            mix: None,
+            dns: DnsOverrides::default(),
         }
     }
 }
@@ -176,9 +249,60 @@ impl BenchmarkConfig {
         self
     }
 
+    /// Set warmup duration
+    pub fn with_warmup(mut self, secs: u64) -> Self {
+        self.warmup_secs = secs;
+        self
+    }
+
+    /// Set how long in-flight requests may finish after load stops
+    pub fn with_drain(mut self, secs: u64) -> Self {
+        self.drain_secs = secs;
+        self
+    }
+
+    /// Enable steady-state detection
+    pub fn with_steady_state(mut self, steady_state: SteadyState) -> Self {
+        self.steady_state = Some(steady_state);
+        self
+    }
+
+    /// Set weighted traffic mix
+    pub fn with_mix(mut self, mix: TrafficMix) -> Self {
+        self.mix = Some(mix);
+        self
+    }
+
+    /// Set how a gateway hostname resolves
+    pub fn with_dns(mut self, dns: DnsOverrides) -> Self {
+        self.dns = dns;
+        self
+    }
+
+    /// Reuse connections or open one per request
+    pub fn with_connection(mut self, connection: ConnectionSettings) -> Self {
+        self.connection = connection;
+        self
+    }
+
+    /// HTTP connection settings for this run (one pooled connection per
+    /// worker unless the idle pool size is set)
+    pub fn http_config(&self) -> HttpClientConfig {
+        HttpClientConfig::default()
+            .with_timeout(self.timeout_ms.div_ceil(1000))
+            .with_pool_size(self.concurrency.max(1) as usize)
+            .with_connection(&self.connection)
+            .with_dns_overrides(self.dns.clone())
+    }
+
     /// Get full URL
     pub fn url(&self) -> String {
-        format!("http://{}:{}{}", self.gateway_ip, self.port, self.path)
+        self.url_for(&self.path)
+    }
+
+    /// Get full URL for a path
+    pub fn url_for(&self, path: &str) -> String {
+        format!("http://{}{}", authority(&self.gateway_ip, self.port), path)
     }
 }
 
@@ -195,6 +319,18 @@ pub struct BenchmarkResult {
     pub end_time: u64,
     /// Whether warmup was performed
     pub warmup_performed: bool,
+    /// Time spent paused in seconds (excluded from metrics)
+    #[serde(default)]
+    pub paused_secs: f64,
+    /// Per-worker metrics (distributed runs only)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub workers: Vec<WorkerMetrics>,
+    /// Gateway pod CPU/memory during the measured window
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resources: Option<ResourceUsage>,
+    /// Gateway pod crashes during the run (results are tainted)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub crashes: Vec<PodCrash>,
 }
 
 impl BenchmarkResult {
@@ -213,6 +349,26 @@ impl BenchmarkResult {
             self.metrics.throughput.format_summary()
         )
     }
+
+    /// Throughput per gateway CPU core, if resources were collected
+    pub fn rps_per_core(&self) -> Option<f64> {
+        self.resources
+            .as_ref()
+            .and_then(|r| r.rps_per_core(self.metrics.throughput.rps))
+    }
+
+    /// Whether a gateway pod crashed while the benchmark ran
+    pub fn is_tainted(&self) -> bool {
+        !self.crashes.is_empty()
+    }
+
+    /// Peak gateway memory in MiB, if resources were collected
+    pub fn peak_memory_mib(&self) -> Option<f64> {
+        self.resources
+            .as_ref()
+            .filter(|r| !r.is_empty())
+            .map(|r| r.peak_memory_mib())
+    }
 }
 
 /// Benchmark runner
@@ -221,50 +377,126 @@ pub struct BenchmarkRunner {
     http_client: HttpClient,
     running: Arc<AtomicBool>,
     request_count: Arc<AtomicU64>,
+    control: PauseControl,
+    resources: Option<ResourceCollector>,
+    crashes: Option<CrashWatcher>,
+    progress: Option<ProgressReporter>,
 }
 
 impl BenchmarkRunner {
     /// Create a new benchmark runner
     pub fn new(config: BenchmarkConfig) -> Self {
-        let timeout_secs = config.timeout_ms / 1000;
         let http_client =
-            HttpClient::with_timeout(timeout_secs.max(1)).expect("Failed to create HTTP client");
+            HttpClient::from_config(config.http_config()).expect("Failed to create HTTP client");
 
         Self {
             config,
             http_client,
             running: Arc::new(AtomicBool::new(false)),
             request_count: Arc::new(AtomicU64::new(0)),
+            control: PauseControl::new(),
+            resources: None,
+            crashes: None,
+            progress: None,
         }
     }
 
+    /// Publish live latency/error samples (e.g. to the dashboard)
+    pub fn with_progress(mut self, reporter: ProgressReporter) -> Self {
+        self.progress = Some(reporter);
+        self
+    }
+
+    /// Send load through an existing client, sharing its connection pool
+    pub fn with_http_client(mut self, client: HttpClient) -> Self {
+        self.http_client = client;
+        self
+    }
+
+    /// Authenticate load requests with tokens from a provider
+    pub fn with_auth(mut self, auth: AuthHeaders) -> Self {
+        self.http_client = self.http_client.with_auth(auth);
+        self
+    }
+
+    /// Record gateway pod CPU/memory while the benchmark runs
+    pub fn with_resource_collector(mut self, collector: ResourceCollector) -> Self {
+        self.resources = Some(collector);
+        self
+    }
+
+    /// Flag the result if a gateway pod crashes while the benchmark runs
+    pub fn with_crash_watcher(mut self, watcher: CrashWatcher) -> Self {
+        self.crashes = Some(watcher);
+        self
+    }
+
+    /// Handle for pausing and resuming load
+    pub fn control(&self) -> PauseControl {
+        self.control.clone()
+    }
+
     /// Run the benchmark
     pub async fn run(&self) -> Result<BenchmarkResult> {
+        let span = info_span!(
+            "benchmark",
+            gateway = self.config.gateway.name(),
+            pattern = self.config.pattern.name()
+        );
+        self.execute().instrument(span).await
+    }
+
+    async fn execute(&self) -> Result<BenchmarkResult> {
         info!(
             "Starting benchmark for {} at {}",
             self.config.gateway.name(),
             self.config.url()
         );
 
-        // Warmup phase
-        let warmup_performed = if self.config.warmup_secs > 0 {
-            info!("Warmup phase: {} seconds", self.config.warmup_secs);
-            self.warmup().await?;
-            true
-        } else {
-            false
-        };
+        // Warmup runs under the real load; its samples are excluded
+        let warmup_performed = self.config.warmup_secs > 0;
+        if warmup_performed {
+            info!(
+                "Warmup phase: {} seconds (samples excluded)",
+                self.config.warmup_secs
+            );
+        }
 
         let start_time = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
             .as_secs();
 
+        let monitor = self.resources.clone().map(|c| c.start());
+        let window = CrashWindow::open(self.crashes.as_ref()).await;
+        tui::emit(
+            &self.progress,
+            ProgressEvent::BenchmarkStarted {
+                gateway: self.config.gateway.name().to_string(),
+                duration_secs: self.config.duration_secs,
+                warmup_secs: self.config.warmup_secs,
+            },
+        );
+
         // Main benchmark
         self.running.store(true, Ordering::SeqCst);
-        let metrics = self.run_load_test().await?;
+        let metrics = self.run_load_test().await;
         self.running.store(false, Ordering::SeqCst);
 
+        let resources = match monitor {
+            Some(monitor) => {
+                let mut usage = monitor.stop().await;
+                // Warmup samples are excluded like the request metrics
+                let warmup_secs = self.config.warmup_secs as f64;
+                usage.samples.retain(|s| s.elapsed_secs >= warmup_secs);
+                info!("Gateway resources: {}", usage);
+                Some(usage)
+            }
+            None => None,
+        };
+        let crashes = window.finish().await;
+        let metrics = metrics?;
+
         let end_time = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
@@ -283,37 +515,48 @@ impl BenchmarkRunner {
             start_time,
             end_time,
             warmup_performed,
+            paused_secs: self.control.paused_for().as_secs_f64(),
+            workers: Vec::new(),
+            resources,
+            crashes,
         })
     }
 
-    /// Warmup phase
-    async fn warmup(&self) -> Result<()> {
-        let url = self.config.url();
-        let warmup_duration = Duration::from_secs(self.config.warmup_secs);
-        let start = Instant::now();
-
-        while start.elapsed() < warmup_duration {
-            let _ = self.http_client.get(&url).await;
-            sleep(Duration::from_millis(100)).await;
-        }
-
-        Ok(())
-    }
-
     /// Run the main load test
     async fn run_load_test(&self) -> Result<Metrics> {
-        let collector = Arc::new(Mutex::new(MetricsCollector::new()));
-        let duration = Duration::from_secs(self.config.duration_secs);
+        let warmup = Duration::from_secs(self.config.warmup_secs);
+        let mut collector = MetricsCollector::new()
+            .with_warmup(warmup)
+            .with_live_window(Duration::from_secs_f64(LIVE_WINDOW_SECS))
+            .with_pause_control(self.control.clone());
+        if let Some(steady_state) = self.config.steady_state {
+            collector = collector.with_steady_state(steady_state);
+        }
+        let collector = Arc::new(Mutex::new(collector));
+        let duration = warmup + Duration::from_secs(self.config.duration_secs);
+
+        let sampler = self.progress.clone().map(|reporter| {
+            let collector = collector.clone();
+            tokio::spawn(async move {
+                loop {
+                    sleep(Duration::from_secs(1)).await;
+                    let sample = collector.lock().await.live_sample();
+                    reporter.emit(ProgressEvent::BenchmarkSample(sample));
+                }
+            })
+        });
 
-        match &self.config.pattern {
+        let result = match &self.config.pattern {
             LoadPattern::Max { concurrency } => {
                 self.run_max_throughput(*concurrency, duration, collector.clone())
-                    .await?;
-            }
-            _ => {
-                self.run_rate_limited(duration, collector.clone()).await?;
+                    .await
             }
+            _ => self.run_rate_limited(duration, collector.clone()).await,
+        };
+        if let Some(sampler) = sampler {
+            sampler.abort();
         }
+        result?;
 
         let metrics = collector.lock().await.snapshot();
         Ok(metrics)
@@ -325,30 +568,35 @@ impl BenchmarkRunner {
         duration: Duration,
         collector: Arc<Mutex<MetricsCollector>>,
     ) -> Result<()> {
-        let url = self.config.url();
-        let hostname = self.config.hostname.clone();
+        let config = Arc::new(self.config.clone());
         let start = Instant::now();
-        let total_duration_secs = duration.as_secs_f64();
+        let warmup_secs = self.config.warmup_secs as f64;
+        let total_duration_secs = self.config.duration_secs as f64;
+
+        let shutdown = CancellationToken::new();
 
         let mut handles = Vec::new();
         let concurrency = self.config.concurrency.min(100);
 
         for _ in 0..concurrency {
-            let url = url.clone();
-            let hostname = hostname.clone();
+            let config = config.clone();
             let collector = collector.clone();
             let client = self.http_client.clone();
             let running = self.running.clone();
+            let control = self.control.clone();
             let pattern = self.config.pattern.clone();
+            let shutdown = shutdown.clone();
 
             let handle = tokio::spawn(async move {
                 while running.load(Ordering::SeqCst) {
-                    let elapsed = start.elapsed();
+                    control.wait_if_paused().await;
+                    let elapsed = control.active_elapsed(start);
                     if elapsed >= duration {
                         break;
                     }
 
-                    let elapsed_secs = elapsed.as_secs_f64();
+                    // Warmup holds the pattern's initial rate
+                    let elapsed_secs = (elapsed.as_secs_f64() - warmup_secs).max(0.0);
                     let target_rps = pattern.rps_at(elapsed_secs, total_duration_secs);
 
                     // Calculate delay for rate limiting
@@ -358,24 +606,13 @@ impl BenchmarkRunner {
                         0
                     };
 
-                    let request_start = Instant::now();
-                    let result = client.get_with_host(&url, &hostname).await;
-                    let latency_ms = request_start.elapsed().as_secs_f64() * 1000.0;
-
-                    let mut coll = collector.lock().await;
-                    match result {
-                        Ok(resp) => {
-                            let success = resp.status_code >= 200 && resp.status_code < 400;
-                            coll.record(latency_ms, success, Some(resp.status_code));
-                        }
-                        Err(_) => {
-                            coll.record_failure(latency_ms, None, false, true);
-                        }
-                    }
-                    drop(coll);
+                    send_request(&client, &config, &collector, &shutdown).await;
 
                     if delay_ms > 0 {
-                        sleep(Duration::from_millis(delay_ms as u64)).await;
+                        tokio::select! {
+                            _ = sleep(Duration::from_millis(delay_ms as u64)) => {}
+                            _ = shutdown.cancelled() => break,
+                        }
                     }
                 }
             });
@@ -383,10 +620,8 @@ impl BenchmarkRunner {
             handles.push(handle);
         }
 
-        // Wait for all workers
-        for handle in handles {
-            let _ = handle.await;
-        }
+        self.drain(handles, start, duration, &collector, shutdown)
+            .await;
 
         Ok(())
     }
@@ -398,35 +633,27 @@ impl BenchmarkRunner {
         duration: Duration,
         collector: Arc<Mutex<MetricsCollector>>,
     ) -> Result<()> {
-        let url = self.config.url();
-        let hostname = self.config.hostname.clone();
+        let config = Arc::new(self.config.clone());
         let start = Instant::now();
+        let shutdown = CancellationToken::new();
 
         let mut handles = Vec::new();
 
         for _ in 0..concurrency {
-            let url = url.clone();
-            let hostname = hostname.clone();
+            let config = config.clone();
             let collector = collector.clone();
             let client = self.http_client.clone();
             let running = self.running.clone();
+            let control = self.control.clone();
+            let shutdown = shutdown.clone();
 
             let handle = tokio::spawn(async move {
-                while running.load(Ordering::SeqCst) && start.elapsed() < duration {
-                    let request_start = Instant::now();
-                    let result = client.get_with_host(&url, &hostname).await;
-                    let latency_ms = request_start.elapsed().as_secs_f64() * 1000.0;
-
-                    let mut coll = collector.lock().await;
-                    match result {
-                        Ok(resp) => {
-                            let success = resp.status_code >= 200 && resp.status_code < 400;
-                            coll.record(latency_ms, success, Some(resp.status_code));
-                        }
-                        Err(_) => {
-                            coll.record_failure(latency_ms, None, false, true);
-                        }
+                while running.load(Ordering::SeqCst) {
+                    control.wait_if_paused().await;
+                    if control.active_elapsed(start) >= duration {
+                        break;
                     }
+                    send_request(&client, &config, &collector, &shutdown).await;
                 }
             });
 
@@ -435,8 +662,9 @@ impl BenchmarkRunner {
 
         // Progress reporting
         let collector_progress = collector.clone();
+        let control = self.control.clone();
         let progress_handle = tokio::spawn(async move {
-            while start.elapsed() < duration {
+            while control.active_elapsed(start) < duration {
                 sleep(Duration::from_secs(5)).await;
                 let coll = collector_progress.lock().await;
                 let elapsed = coll.elapsed().as_secs_f64();
@@ -449,15 +677,42 @@ impl BenchmarkRunner {
             }
         });
 
-        // Wait for all workers
-        for handle in handles {
-            let _ = handle.await;
-        }
+        self.drain(handles, start, duration, &collector, shutdown)
+            .await;
         progress_handle.abort();
 
         Ok(())
     }
 
+    /// Graceful stop: once the load window ends (or `stop` is called) no new
+    /// requests are scheduled, and in-flight ones get `drain_secs` to finish
+    /// before they are cancelled and counted apart from errors
+    async fn drain(
+        &self,
+        handles: Vec<JoinHandle<()>>,
+        start: Instant,
+        duration: Duration,
+        collector: &Mutex<MetricsCollector>,
+        shutdown: CancellationToken,
+    ) {
+        while self.running.load(Ordering::SeqCst) && self.control.active_elapsed(start) < duration {
+            sleep(STOP_POLL_INTERVAL).await;
+        }
+        collector.lock().await.mark_stopped();
+
+        let drain = Duration::from_secs(self.config.drain_secs);
+        let workers = join_all(handles);
+        tokio::pin!(workers);
+        if timeout(drain, &mut workers).await.is_err() {
+            shutdown.cancel();
+            workers.await;
+            info!(
+                "Cancelled in-flight requests still running {}s after load stopped",
+                self.config.drain_secs
+            );
+        }
+    }
+
     /// Stop the benchmark
     pub fn stop(&self) {
         self.running.store(false, Ordering::SeqCst);
@@ -469,6 +724,53 @@ impl BenchmarkRunner {
     }
 }
 
+/// Send a single request, picking an endpoint from the mix if configured
+async fn send_request(
+    client: &HttpClient,
+    config: &BenchmarkConfig,
+    collector: &Mutex<MetricsCollector>,
+    shutdown: &CancellationToken,
+) {
+    let target = config.mix.as_ref().map(|m| m.pick_random());
+
+    let request = match target {
+        Some(t) => {
+            let request = HttpRequest::new(&t.method, config.url_for(&t.path));
+            match &t.body {
+                Some(body) => request.body(body.clone()),
+                None => request,
+            }
+        }
+        None => HttpRequest::get(config.url()),
+    }
+    .header("Host", &config.hostname);
+
+    let (result, latency_ms) = tokio::select! {
+        sent = client.send_timed(request) => sent,
+        _ = shutdown.cancelled() => {
+            collector.lock().await.record_cancelled();
+            return;
+        }
+    };
+
+    let mut coll = collector.lock().await;
+    let (success, status_code) = match &result {
+        Ok(resp) => (
+            resp.status_code >= 200 && resp.status_code < 400,
+            Some(resp.status_code),
+        ),
+        Err(_) => (false, None),
+    };
+
+    match target {
+        Some(t) => {
+            coll.record_endpoint(&t.key(), latency_ms, success, status_code, result.is_err())
+        }
+        None if result.is_err() => coll.record_failure(latency_ms, None, false, true),
+        None => coll.record(latency_ms, success, status_code),
+    }
+}
+
 /// Compare multiple gateway benchmarks
 pub struct BenchmarkComparison {
     results: Vec<BenchmarkResult>,
@@ -535,8 +837,58 @@ impl BenchmarkComparison {
         output.push_str(
             "└────────────────────────┴──────────┴──────────┴──────────┴──────────┴──────────┘\n",
         );
+
+        if self.has_resources() {
+            output.push_str("\nGateway Resources:\n");
+            output.push_str(&format!(
+                "  {:<22} {:>10} {:>10} {:>10} {:>14}\n",
+                "Gateway", "RPS/core", "CPU avg", "CPU peak", "Mem peak(MiB)"
+            ));
+            for result in self.by_rps() {
+                let Some(usage) = result.resources.as_ref().filter(|r| !r.is_empty()) else {
+                    continue;
+                };
+                output.push_str(&format!(
+                    "  {:<22} {:>10} {:>10.2} {:>10.2} {:>14.1}\n",
+                    result.config.gateway.name(),
+                    result
+                        .rps_per_core()
+                        .map(|v| format!("{v:.1}"))
+                        .unwrap_or_else(|| "-".to_string()),
+                    usage.avg_cpu_cores(),
+                    usage.peak_cpu_cores(),
+                    usage.peak_memory_mib()
+                ));
+            }
+        }
+
+        if self.has_crashes() {
+            output.push_str("\nTainted Results (gateway pods crashed):\n");
+            for result in &self.results {
+                for crash in &result.crashes {
+                    output.push_str(&format!(
+                        "  {:<22} {}\n",
+                        result.config.gateway.name(),
+                        crash
+                    ));
+                }
+            }
+        }
+
         output
     }
+
+    /// Whether any result includes gateway resource usage
+    pub fn has_resources(&self) -> bool {
+        self.results
+            .iter()
+            .any(|r| r.resources.as_ref().is_some_and(|u| !u.is_empty()))
+    }
+
+    /// Whether a gateway pod crashed during any result
+    pub fn has_crashes(&self) -> bool {
+        self.results.iter().any(|r| r.is_tainted())
+    }
 }
 
 #[cfg(test)]
@@ -587,6 +939,62 @@ mod tests {
         assert_eq!(config.gateway_ip, "10.0.0.1");
         assert_eq!(config.duration_secs, 120);
         assert_eq!(config.concurrency, 20);
+
+        let http = config.http_config();
+        assert_eq!(http.timeout_secs, 5);
+        assert_eq!(http.pool_max_idle_per_host, 20);
+    }
+
+    #[test]
+    fn test_benchmark_config_steady_state() {
+        let config = BenchmarkConfig::new(GatewayImpl::Envoy, "10.0.0.1")
+            .with_warmup(10)
+            .with_steady_state(SteadyState::default());
+
+        assert_eq!(config.warmup_secs, 10);
+        assert_eq!(config.steady_state.unwrap().window_secs, 5);
+    }
+
+    #[test]
+    fn test_benchmark_config_mix() {
+        let mix = TrafficMix::from_specs(&["/api/list:70", "/api/create:30:POST"]).unwrap();
+        let config = BenchmarkConfig::new(GatewayImpl::Nginx, "10.0.0.1").with_mix(mix);
+
+        assert_eq!(config.mix.as_ref().unwrap().targets.len(), 2);
+        assert_eq!(config.url_for("/api/list"), "http://10.0.0.1:80/api/list");
+    }
+
+    #[test]
+    fn test_rps_per_core() {
+        let mut usage = ResourceUsage::new("app=gw");
+        usage.samples.push(crate::models::ResourceSample {
+            elapsed_secs: 10.0,
+            pods: 2,
+            cpu_cores: 0.5,
+            memory_bytes: 64 * 1024 * 1024,
+        });
+
+        let mut result = BenchmarkResult {
+            config: BenchmarkConfig::default(),
+            metrics: Metrics::default(),
+            start_time: 0,
+            end_time: 60,
+            warmup_performed: false,
+            paused_secs: 0.0,
+            workers: Vec::new(),
+            resources: None,
+            crashes: Vec::new(),
+        };
+        result.metrics.throughput.rps = 1000.0;
+        assert_eq!(result.rps_per_core(), None);
+
+        result.resources = Some(usage);
+        assert_eq!(result.rps_per_core(), Some(2000.0));
+        assert_eq!(result.peak_memory_mib(), Some(64.0));
+
+        let comparison = BenchmarkComparison::new(vec![result]);
+        assert!(comparison.has_resources());
+        assert!(comparison.format_table().contains("Gateway Resources"));
     }
 
     #[test]
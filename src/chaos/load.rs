@@ -0,0 +1,188 @@
+//! Background load for chaos experiments
+//!
+//! Paced workers send requests through the gateway for the whole
+//! experiment and record every outcome into one-second buckets, so the
+//! effect of a fault can be read off a timeline.
+
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::http::HttpClient;
+
+/// Requests sent and failed in one second of an experiment
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChaosSecond {
+    /// Seconds since the load started
+    pub offset_secs: u64,
+    pub requests: u64,
+    pub errors: u64,
+    pub total_latency_ms: f64,
+    /// Offset of the second's first failed request, in milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_error_ms: Option<u64>,
+    /// Offset of the second's last failed request, in milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error_ms: Option<u64>,
+}
+
+impl ChaosSecond {
+    pub fn avg_latency_ms(&self) -> f64 {
+        if self.requests == 0 {
+            0.0
+        } else {
+            self.total_latency_ms / self.requests as f64
+        }
+    }
+}
+
+/// Per-second outcomes of all workers
+#[derive(Clone, Debug, Default)]
+pub struct LoadTimeline {
+    seconds: Vec<ChaosSecond>,
+}
+
+impl LoadTimeline {
+    /// Record a request that finished `offset_ms` after the load started
+    pub fn record(&mut self, offset_ms: u64, failed: bool, latency_ms: f64) {
+        let index = (offset_ms / 1000) as usize;
+        while self.seconds.len() <= index {
+            let offset_secs = self.seconds.len() as u64;
+            self.seconds.push(ChaosSecond {
+                offset_secs,
+                ..Default::default()
+            });
+        }
+        let second = &mut self.seconds[index];
+        second.requests += 1;
+        second.total_latency_ms += latency_ms;
+        if failed {
+            second.errors += 1;
+            second.first_error_ms = Some(
+                second
+                    .first_error_ms
+                    .map_or(offset_ms, |t| t.min(offset_ms)),
+            );
+            second.last_error_ms =
+                Some(second.last_error_ms.map_or(offset_ms, |t| t.max(offset_ms)));
+        }
+    }
+
+    pub fn into_seconds(self) -> Vec<ChaosSecond> {
+        self.seconds
+    }
+}
+
+/// Paced GET load against one URL
+pub struct BackgroundLoad {
+    client: HttpClient,
+    url: String,
+    hostname: String,
+    concurrency: u32,
+    rps: u32,
+}
+
+impl BackgroundLoad {
+    pub fn new(
+        client: HttpClient,
+        url: impl Into<String>,
+        hostname: impl Into<String>,
+        concurrency: u32,
+        rps: u32,
+    ) -> Self {
+        Self {
+            client,
+            url: url.into(),
+            hostname: hostname.into(),
+            concurrency: concurrency.max(1),
+            rps: rps.max(1),
+        }
+    }
+
+    /// Pause between one worker's requests so all workers together send `rps`
+    pub fn worker_interval(&self) -> Duration {
+        Duration::from_secs_f64(self.concurrency as f64 / self.rps as f64)
+    }
+
+    /// Run the workers from `start` until `start + duration`
+    ///
+    /// Transport errors and 5xx responses count as failures; a request that
+    /// outlives the client timeout is a transport error.
+    pub async fn run(&self, start: Instant, duration: Duration) -> LoadTimeline {
+        let timeline = Arc::new(Mutex::new(LoadTimeline::default()));
+        let deadline = start + duration;
+        let interval = self.worker_interval();
+
+        let mut handles = Vec::new();
+        for worker in 0..self.concurrency {
+            let client = self.client.clone();
+            let url = self.url.clone();
+            let hostname = self.hostname.clone();
+            let timeline = timeline.clone();
+            // Stagger workers across the interval instead of firing together
+            let offset = interval.mul_f64(worker as f64 / self.concurrency as f64);
+            handles.push(tokio::spawn(async move {
+                let mut ticker = tokio::time::interval_at((start + offset).into(), interval);
+                ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+                loop {
+                    ticker.tick().await;
+                    if Instant::now() >= deadline {
+                        break;
+                    }
+                    let sent = Instant::now();
+                    let failed = match client.get_with_host(&url, &hostname).await {
+                        Ok(resp) => resp.status_code >= 500,
+                        Err(_) => true,
+                    };
+                    let latency_ms = sent.elapsed().as_secs_f64() * 1000.0;
+                    let offset_ms = sent.duration_since(start).as_millis() as u64;
+                    timeline
+                        .lock()
+                        .unwrap()
+                        .record(offset_ms, failed, latency_ms);
+                }
+            }));
+        }
+        for handle in handles {
+            let _ = handle.await;
+        }
+
+        let timeline = timeline.lock().unwrap().clone();
+        timeline
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timeline_record() {
+        let mut timeline = LoadTimeline::default();
+        timeline.record(200, false, 10.0);
+        timeline.record(2_400, true, 30.0);
+        timeline.record(2_100, true, 50.0);
+
+        let seconds = timeline.into_seconds();
+        assert_eq!(seconds.len(), 3);
+        assert_eq!(seconds[0].requests, 1);
+        assert_eq!(
+            seconds[1],
+            ChaosSecond {
+                offset_secs: 1,
+                ..Default::default()
+            }
+        );
+        assert_eq!(seconds[2].errors, 2);
+        assert_eq!(seconds[2].first_error_ms, Some(2_100));
+        assert_eq!(seconds[2].last_error_ms, Some(2_400));
+        assert_eq!(seconds[2].avg_latency_ms(), 40.0);
+    }
+
+    #[test]
+    fn test_worker_interval() {
+        let client = HttpClient::new().unwrap();
+        let load = BackgroundLoad::new(client, "http://10.0.0.1/", "example.com", 10, 100);
+        assert_eq!(load.worker_interval(), Duration::from_millis(100));
+    }
+}
@@ -0,0 +1,328 @@
+//! Chaos experiments
+//!
+//! Injects a fault into a gateway or its backends while background load
+//! runs, then measures how much of the error budget the fault consumed and
+//! how long the gateway took to serve cleanly again.
+
+mod load;
+mod result;
+
+pub use load::{BackgroundLoad, ChaosSecond};
+pub use result::ChaosResult;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+use crate::http::{authority, HttpClient};
+use crate::k8s::FaultInjector;
+use crate::models::GatewayImpl;
+
+/// Request rate of the single probe used without `--during-load`
+const PROBE_RPS: u32 = 10;
+
+/// Fault a chaos experiment injects
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ChaosFault {
+    /// Kill one backend pod without a grace period
+    PodKill,
+    /// Gracefully delete the gateway's data-plane pods so they restart
+    GwRestart,
+    /// Delay one backend pod's traffic with `tc netem` for a while
+    NetemDelay,
+}
+
+impl ChaosFault {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::PodKill => "pod-kill",
+            Self::GwRestart => "gw-restart",
+            Self::NetemDelay => "netem-delay",
+        }
+    }
+
+    /// Whether the fault slows traffic down rather than breaking it
+    pub fn adds_latency(&self) -> bool {
+        matches!(self, Self::NetemDelay)
+    }
+
+    pub fn all() -> [Self; 3] {
+        [Self::PodKill, Self::GwRestart, Self::NetemDelay]
+    }
+
+    /// Parse a fault name ("pod-kill", "gw-restart", "netem-delay")
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let name = s.trim().to_lowercase();
+        Self::all()
+            .into_iter()
+            .find(|fault| fault.name() == name)
+            .ok_or_else(|| {
+                let names: Vec<_> = Self::all().iter().map(|f| f.name()).collect();
+                format!("Unknown fault '{s}' (expected {})", names.join(", "))
+            })
+    }
+}
+
+impl fmt::Display for ChaosFault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A fault injected into a gateway under load
+#[derive(Clone, Debug)]
+pub struct ChaosExperiment {
+    gateway: GatewayImpl,
+    gateway_ip: String,
+    port: u16,
+    path: String,
+    hostname: String,
+    fault: ChaosFault,
+    /// `app` label of the backend pods pod-kill and netem-delay pick from
+    backend: String,
+    /// Gateway resource whose data plane gw-restart deletes
+    gateway_name: String,
+    /// Namespace of the gateway pods gw-restart deletes
+    gateway_namespace: String,
+    duration: Duration,
+    fault_at: Duration,
+    delay_ms: u64,
+    delay_duration: Duration,
+    during_load: bool,
+    concurrency: u32,
+    rps: u32,
+    timeout_secs: u64,
+    slo_percent: f64,
+}
+
+impl ChaosExperiment {
+    pub fn new(gateway: GatewayImpl, gateway_ip: impl Into<String>, port: u16) -> Self {
+        Self {
+            gateway,
+            gateway_ip: gateway_ip.into(),
+            port,
+            path: "/".to_string(),
+            hostname: "example.com".to_string(),
+            fault: ChaosFault::PodKill,
+            backend: "echo".to_string(),
+            gateway_name: gateway.gateway_name(),
+            gateway_namespace: "default".to_string(),
+            duration: Duration::from_secs(120),
+            fault_at: Duration::from_secs(30),
+            delay_ms: 500,
+            delay_duration: Duration::from_secs(30),
+            during_load: false,
+            concurrency: 10,
+            rps: 100,
+            timeout_secs: 2,
+            slo_percent: 99.9,
+        }
+    }
+
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    pub fn with_hostname(mut self, hostname: impl Into<String>) -> Self {
+        self.hostname = hostname.into();
+        self
+    }
+
+    pub fn with_fault(mut self, fault: ChaosFault) -> Self {
+        self.fault = fault;
+        self
+    }
+
+    pub fn with_backend(mut self, backend: impl Into<String>) -> Self {
+        self.backend = backend.into();
+        self
+    }
+
+    pub fn with_gateway_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.gateway_namespace = namespace.into();
+        self
+    }
+
+    /// Total run time and when into it the fault is injected
+    pub fn with_schedule(mut self, duration: Duration, fault_at: Duration) -> Self {
+        self.duration = duration;
+        self.fault_at = fault_at;
+        self
+    }
+
+    /// Delay netem-delay adds and for how long
+    pub fn with_delay(mut self, delay_ms: u64, duration: Duration) -> Self {
+        self.delay_ms = delay_ms;
+        self.delay_duration = duration;
+        self
+    }
+
+    /// Background load; without it a single probe sends 10 req/s
+    pub fn with_load(mut self, during_load: bool, concurrency: u32, rps: u32) -> Self {
+        self.during_load = during_load;
+        self.concurrency = concurrency;
+        self.rps = rps;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout_secs: u64) -> Self {
+        self.timeout_secs = timeout_secs;
+        self
+    }
+
+    pub fn with_slo(mut self, slo_percent: f64) -> Self {
+        self.slo_percent = slo_percent;
+        self
+    }
+
+    fn load(&self, client: HttpClient) -> BackgroundLoad {
+        let url = format!(
+            "http://{}{}",
+            authority(&self.gateway_ip, self.port),
+            self.path
+        );
+        if self.during_load {
+            BackgroundLoad::new(client, url, &self.hostname, self.concurrency, self.rps)
+        } else {
+            BackgroundLoad::new(client, url, &self.hostname, 1, PROBE_RPS)
+        }
+    }
+
+    /// Run the load, inject the fault on schedule and analyze the timeline
+    pub async fn run(&self, injector: &FaultInjector, id: &str) -> Result<ChaosResult> {
+        if self.fault_at >= self.duration {
+            anyhow::bail!(
+                "The fault must be injected before the run ends ({}s >= {}s)",
+                self.fault_at.as_secs(),
+                self.duration.as_secs()
+            );
+        }
+
+        let client = HttpClient::with_timeout(self.timeout_secs)?;
+        let load = self.load(client);
+        info!(
+            "Running {} chaos experiment against {} for {}s",
+            self.fault,
+            self.gateway,
+            self.duration.as_secs()
+        );
+
+        let started_at = chrono::Utc::now();
+        let start = Instant::now();
+        let inject = async {
+            tokio::time::sleep_until((start + self.fault_at).into()).await;
+            let at = start.elapsed();
+            (at, self.inject(injector).await)
+        };
+        let (timeline, (fault_at, injected)) = tokio::join!(load.run(start, self.duration), inject);
+
+        let rps = if self.during_load {
+            self.rps
+        } else {
+            PROBE_RPS
+        };
+        let result = ChaosResult::analyze(
+            id,
+            self.gateway.short_name(),
+            self.fault,
+            started_at,
+            fault_at.as_millis() as u64,
+            self.slo_percent,
+            timeline.into_seconds(),
+        )
+        .with_load(self.during_load, rps);
+        Ok(match injected {
+            Ok(target) => result.with_target(target),
+            Err(e) => {
+                warn!("Failed to inject {}: {:#}", self.fault, e);
+                result.with_error(format!("{e:#}"))
+            }
+        })
+    }
+
+    /// Inject the fault, returning what it hit
+    async fn inject(&self, injector: &FaultInjector) -> Result<String> {
+        match self.fault {
+            ChaosFault::PodKill => {
+                let pod = self.backend_pod(injector).await?;
+                injector.kill_pod(&pod).await?;
+                Ok(pod)
+            }
+            ChaosFault::GwRestart => {
+                let selector = self
+                    .gateway
+                    .serving_pod_selector(&self.gateway_name)
+                    .with_context(|| format!("No pod selector known for {}", self.gateway))?;
+                let pods = injector
+                    .restart_pods(&selector, &self.gateway_namespace)
+                    .await?;
+                if pods.is_empty() {
+                    anyhow::bail!(
+                        "No {} pods match {selector} in namespace {}",
+                        self.gateway,
+                        self.gateway_namespace
+                    );
+                }
+                Ok(pods.join(", "))
+            }
+            ChaosFault::NetemDelay => {
+                let pod = self.backend_pod(injector).await?;
+                injector
+                    .netem_delay(&pod, self.delay_ms, self.delay_duration)
+                    .await?;
+                Ok(format!(
+                    "{pod} (+{}ms for {}s)",
+                    self.delay_ms,
+                    self.delay_duration.as_secs()
+                ))
+            }
+        }
+    }
+
+    /// A running backend pod to hit
+    async fn backend_pod(&self, injector: &FaultInjector) -> Result<String> {
+        let selector = format!("app={}", self.backend);
+        injector
+            .running_pods(&selector)
+            .await?
+            .into_iter()
+            .next()
+            .with_context(|| format!("No running backend pods match {selector}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_chaos_fault() {
+        for fault in ChaosFault::all() {
+            assert_eq!(ChaosFault::parse(fault.name()), Ok(fault));
+        }
+        assert_eq!(ChaosFault::parse(" GW-Restart "), Ok(ChaosFault::GwRestart));
+        assert!(ChaosFault::parse("reboot")
+            .unwrap_err()
+            .contains("pod-kill, gw-restart, netem-delay"));
+        assert_eq!(
+            serde_json::to_string(&ChaosFault::NetemDelay).unwrap(),
+            "\"netem-delay\""
+        );
+    }
+
+    #[test]
+    fn test_probe_without_load() {
+        let experiment =
+            ChaosExperiment::new(GatewayImpl::Envoy, "10.0.0.1", 80).with_load(false, 20, 500);
+        let load = experiment.load(HttpClient::new().unwrap());
+        assert_eq!(load.worker_interval(), Duration::from_millis(100));
+
+        let experiment = experiment.with_load(true, 20, 500);
+        let load = experiment.load(HttpClient::new().unwrap());
+        assert_eq!(load.worker_interval(), Duration::from_millis(40));
+    }
+}
@@ -0,0 +1,486 @@
+//! Chaos experiment results
+//!
+//! Reads the load timeline around the injection instant: errors in excess
+//! of the pre-fault baseline are charged against the SLO's error budget,
+//! and the gateway counts as recovered once the last failure is followed by
+//! a run of clean seconds. Faults that slow traffic down rather than break
+//! it also count seconds whose latency rises well above the baseline.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{ChaosFault, ChaosSecond};
+
+/// Clean seconds after the last failure for the gateway to count as recovered
+pub const RECOVERY_CLEAN_SECS: usize = 5;
+
+/// A second is slow when its average latency is this many times the baseline
+const SLOW_LATENCY_FACTOR: f64 = 2.0;
+
+/// ... and at least this much above it, so jitter on a fast baseline is not
+const SLOW_LATENCY_MIN_MS: f64 = 50.0;
+
+/// Outcome of one chaos experiment
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ChaosResult {
+    pub id: String,
+    pub gateway: String,
+    pub fault: ChaosFault,
+    /// What the fault hit (pod names)
+    pub target: String,
+    pub started_at: DateTime<Utc>,
+    pub duration_secs: u64,
+    /// When the fault was injected, relative to the start of the load
+    pub fault_at_ms: u64,
+    pub during_load: bool,
+    pub rps: u32,
+    /// Availability objective the error budget derives from, in percent
+    pub slo_percent: f64,
+    pub requests: u64,
+    pub errors: u64,
+    /// Error rate before the fault, in percent
+    pub baseline_error_rate: f64,
+    /// Errors from the injection on, beyond what the baseline rate predicts
+    pub fault_errors: u64,
+    /// Failed requests the SLO allows over the whole run
+    pub error_budget: f64,
+    /// Share of the error budget the fault used up, in percent (may exceed 100)
+    pub error_budget_consumed: f64,
+    /// Injection to the first failed request (or slow second)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detection_ms: Option<u64>,
+    /// Injection to the last failed request (or end of the last slow
+    /// second) before a clean stretch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recovery_ms: Option<u64>,
+    pub recovered: bool,
+    pub baseline_latency_ms: f64,
+    /// Highest per-second average latency after the injection
+    pub peak_latency_ms: f64,
+    /// Per-second average latency counted as impact, for faults that add
+    /// latency
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slow_latency_ms: Option<f64>,
+    /// Why the fault could not be injected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub timeline: Vec<ChaosSecond>,
+}
+
+impl ChaosResult {
+    /// Analyze a load timeline around a fault injected at `fault_at_ms`
+    pub fn analyze(
+        id: impl Into<String>,
+        gateway: impl Into<String>,
+        fault: ChaosFault,
+        started_at: DateTime<Utc>,
+        fault_at_ms: u64,
+        slo_percent: f64,
+        timeline: Vec<ChaosSecond>,
+    ) -> Self {
+        let fault_second = (fault_at_ms / 1000) as usize;
+        let (before, after) = timeline.split_at(fault_second.min(timeline.len()));
+
+        let sum = |seconds: &[ChaosSecond]| {
+            seconds.iter().fold((0, 0, 0.0), |(r, e, l), s| {
+                (r + s.requests, e + s.errors, l + s.total_latency_ms)
+            })
+        };
+        let (baseline_requests, baseline_errors, baseline_latency) = sum(before);
+        let (after_requests, after_errors, _) = sum(after);
+        let requests = baseline_requests + after_requests;
+        let errors = baseline_errors + after_errors;
+
+        let baseline_rate = ratio(baseline_errors as f64, baseline_requests as f64);
+        let expected = baseline_rate * after_requests as f64;
+        let fault_errors = (after_errors as f64 - expected).round().max(0.0) as u64;
+        let error_budget = (1.0 - slo_percent / 100.0) * requests as f64;
+        let error_budget_consumed = ratio(fault_errors as f64, error_budget) * 100.0;
+
+        let baseline_latency_ms = ratio(baseline_latency, baseline_requests as f64);
+        let slow_latency_ms = fault.adds_latency().then(|| {
+            (baseline_latency_ms * SLOW_LATENCY_FACTOR)
+                .max(baseline_latency_ms + SLOW_LATENCY_MIN_MS)
+        });
+
+        // Impact at or after the injection instant per second, as the span
+        // from the first to the last failure, or the whole second when slow
+        let impact = |s: &ChaosSecond| -> Option<(u64, u64)> {
+            match (s.first_error_ms, s.last_error_ms) {
+                (Some(first), Some(last)) if last >= fault_at_ms => {
+                    return Some((first.max(fault_at_ms), last));
+                }
+                _ => {}
+            }
+            let slow =
+                slow_latency_ms.is_some_and(|slow| s.requests > 0 && s.avg_latency_ms() > slow);
+            slow.then(|| {
+                (
+                    (s.offset_secs * 1000).max(fault_at_ms),
+                    (s.offset_secs + 1) * 1000,
+                )
+            })
+        };
+        let impacted: Vec<(usize, (u64, u64))> = after
+            .iter()
+            .enumerate()
+            .filter_map(|(index, s)| impact(s).map(|span| (index, span)))
+            .collect();
+        let detection_ms = impacted.first().map(|(_, (first, _))| first - fault_at_ms);
+        let (recovered, recovery_ms) = match impacted.last() {
+            None => (true, None),
+            Some((index, (_, last))) => {
+                let clean = after[index + 1..].iter().filter(|s| s.requests > 0).count();
+                let recovered = clean >= RECOVERY_CLEAN_SECS;
+                (recovered, Some(last - fault_at_ms).filter(|_| recovered))
+            }
+        };
+
+        Self {
+            id: id.into(),
+            gateway: gateway.into(),
+            fault,
+            target: String::new(),
+            started_at,
+            duration_secs: timeline.len() as u64,
+            fault_at_ms,
+            during_load: true,
+            rps: 0,
+            slo_percent,
+            requests,
+            errors,
+            baseline_error_rate: baseline_rate * 100.0,
+            fault_errors,
+            error_budget,
+            error_budget_consumed,
+            detection_ms,
+            recovery_ms,
+            recovered,
+            baseline_latency_ms,
+            peak_latency_ms: after
+                .iter()
+                .map(ChaosSecond::avg_latency_ms)
+                .fold(0.0, f64::max),
+            slow_latency_ms,
+            error: None,
+            timeline,
+        }
+    }
+
+    pub fn with_target(mut self, target: impl Into<String>) -> Self {
+        self.target = target.into();
+        self
+    }
+
+    pub fn with_load(mut self, during_load: bool, rps: u32) -> Self {
+        self.during_load = during_load;
+        self.rps = rps;
+        self
+    }
+
+    pub fn with_error(mut self, error: impl Into<String>) -> Self {
+        self.error = Some(error.into());
+        self
+    }
+
+    /// Whether the gateway recovered within the error budget
+    pub fn passed(&self) -> bool {
+        self.error.is_none() && self.recovered && self.error_budget_consumed <= 100.0
+    }
+
+    /// Human-readable report with a timeline of the run
+    pub fn format_table(&self) -> String {
+        let mut output = format!(
+            "Chaos experiment {}: {} on {}\n",
+            self.id, self.fault, self.gateway
+        );
+        output.push_str(&format!("{:-<60}\n", ""));
+        output.push_str(&format!(
+            "Target:        {}\n",
+            if self.target.is_empty() {
+                "n/a"
+            } else {
+                &self.target
+            }
+        ));
+        output.push_str(&format!(
+            "Load:          {} req/s{} for {}s, fault at {:.1}s\n",
+            self.rps,
+            if self.during_load {
+                ""
+            } else {
+                " (probe only)"
+            },
+            self.duration_secs,
+            self.fault_at_ms as f64 / 1000.0
+        ));
+        output.push_str(&format!(
+            "Requests:      {} ({} failed, {} from the fault)\n",
+            self.requests, self.errors, self.fault_errors
+        ));
+        output.push_str(&format!(
+            "Baseline:      {:.2}% errors, {:.1}ms avg latency\n",
+            self.baseline_error_rate, self.baseline_latency_ms
+        ));
+        output.push_str(&format!(
+            "Peak latency:  {:.1}ms (per-second average)\n",
+            self.peak_latency_ms
+        ));
+        if let Some(slow) = self.slow_latency_ms {
+            output.push_str(&format!(
+                "Slow seconds:  average latency above {slow:.1}ms\n"
+            ));
+        }
+        let no_impact = if self.slow_latency_ms.is_some() {
+            "no failed requests or slow seconds"
+        } else {
+            "no failed requests"
+        };
+        output.push_str(&format!(
+            "Detection:     {}\n",
+            self.detection_ms
+                .map_or(no_impact.to_string(), |ms| format!(
+                    "{ms}ms after injection"
+                ))
+        ));
+        output.push_str(&format!(
+            "Recovery:      {}\n",
+            match (self.recovered, self.recovery_ms) {
+                (true, Some(ms)) => format!("{ms}ms after injection"),
+                (true, None) => "no impact".to_string(),
+                (false, _) => "not recovered before the run ended".to_string(),
+            }
+        ));
+        output.push_str(&format!(
+            "Error budget:  {:.1}% consumed ({} of {:.1} errors allowed at {}% SLO)\n",
+            self.error_budget_consumed, self.fault_errors, self.error_budget, self.slo_percent
+        ));
+        if let Some(error) = &self.error {
+            output.push_str(&format!("Injection failed: {error}\n"));
+        }
+
+        output.push_str(&format!(
+            "\n{:>8} {:>9} {:>7} {:>12}\n",
+            "Offset", "Requests", "Errors", "Avg latency"
+        ));
+        let window = timeline_window(self.timeline.len());
+        let fault_second = self.fault_at_ms / 1000;
+        for chunk in self.timeline.chunks(window) {
+            let start = chunk[0].offset_secs;
+            let requests: u64 = chunk.iter().map(|s| s.requests).sum();
+            let errors: u64 = chunk.iter().map(|s| s.errors).sum();
+            let latency: f64 = chunk.iter().map(|s| s.total_latency_ms).sum();
+            let marker = if (start..start + chunk.len() as u64).contains(&fault_second) {
+                "  ◀ fault"
+            } else {
+                ""
+            };
+            output.push_str(&format!(
+                "{:>7}s {:>9} {:>7} {:>10.1}ms{}\n",
+                start,
+                requests,
+                errors,
+                ratio(latency, requests as f64),
+                marker
+            ));
+        }
+
+        output.push_str(&format!("{:-<60}\n", ""));
+        output.push_str(if self.passed() {
+            "Recovered within the error budget\n"
+        } else {
+            "Failed: error budget exhausted or gateway did not recover\n"
+        });
+        output
+    }
+}
+
+/// Seconds per timeline row, keeping the report to about 30 rows
+fn timeline_window(seconds: usize) -> usize {
+    seconds.div_ceil(30).max(5)
+}
+
+fn ratio(part: f64, whole: f64) -> f64 {
+    if whole == 0.0 {
+        0.0
+    } else {
+        part / whole
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chaos::load::LoadTimeline;
+
+    /// 100 req/s for `secs` seconds, failing every request in `outage`
+    fn timeline(secs: u64, outage: std::ops::Range<u64>) -> Vec<ChaosSecond> {
+        let mut timeline = LoadTimeline::default();
+        for ms in (0..secs * 1000).step_by(10) {
+            timeline.record(ms, outage.contains(&(ms / 1000)), 2.0);
+        }
+        timeline.into_seconds()
+    }
+
+    #[test]
+    fn test_analyze_outage() {
+        let result = ChaosResult::analyze(
+            "run",
+            "envoy",
+            ChaosFault::PodKill,
+            Utc::now(),
+            30_000,
+            99.0,
+            timeline(60, 31..33),
+        );
+        assert_eq!(result.requests, 6000);
+        assert_eq!(result.fault_errors, 200);
+        assert_eq!(result.baseline_error_rate, 0.0);
+        assert_eq!(result.detection_ms, Some(1000));
+        assert_eq!(result.recovery_ms, Some(2990));
+        assert!(result.recovered);
+        // 200 errors against a budget of 60
+        assert!((result.error_budget_consumed - 333.33).abs() < 0.01);
+        assert!(!result.passed());
+    }
+
+    #[test]
+    fn test_analyze_no_impact() {
+        let result = ChaosResult::analyze(
+            "run",
+            "envoy",
+            ChaosFault::GwRestart,
+            Utc::now(),
+            10_000,
+            99.9,
+            timeline(20, 0..0),
+        );
+        assert_eq!(result.detection_ms, None);
+        assert_eq!(result.recovery_ms, None);
+        assert!(result.recovered);
+        assert_eq!(result.error_budget_consumed, 0.0);
+        assert!(result.passed());
+    }
+
+    #[test]
+    fn test_analyze_not_recovered() {
+        let result = ChaosResult::analyze(
+            "run",
+            "envoy",
+            ChaosFault::NetemDelay,
+            Utc::now(),
+            10_000,
+            99.9,
+            timeline(20, 12..18),
+        );
+        assert_eq!(result.detection_ms, Some(2000));
+        assert!(!result.recovered);
+        assert_eq!(result.recovery_ms, None);
+        assert!(!result.passed());
+    }
+
+    #[test]
+    fn test_analyze_latency() {
+        // 2ms baseline, 500ms from second 31 to 39, no failures
+        let mut load = LoadTimeline::default();
+        for ms in (0..60_000).step_by(10) {
+            let latency = if (31..40).contains(&(ms / 1000)) {
+                500.0
+            } else {
+                2.0
+            };
+            load.record(ms, false, latency);
+        }
+        let result = ChaosResult::analyze(
+            "run",
+            "envoy",
+            ChaosFault::NetemDelay,
+            Utc::now(),
+            30_000,
+            99.9,
+            load.into_seconds(),
+        );
+        assert_eq!(result.slow_latency_ms, Some(52.0));
+        assert_eq!(result.detection_ms, Some(1000));
+        assert_eq!(result.recovery_ms, Some(10_000));
+        assert!(result.recovered);
+        assert!(result.passed());
+
+        // Pod faults do not count latency
+        let result = ChaosResult::analyze(
+            "run",
+            "envoy",
+            ChaosFault::PodKill,
+            Utc::now(),
+            30_000,
+            99.9,
+            result.timeline,
+        );
+        assert_eq!(result.slow_latency_ms, None);
+        assert_eq!(result.detection_ms, None);
+    }
+
+    #[test]
+    fn test_latency_not_recovered() {
+        let mut load = LoadTimeline::default();
+        for ms in (0..20_000).step_by(10) {
+            load.record(ms, false, if ms >= 12_000 { 500.0 } else { 2.0 });
+        }
+        let result = ChaosResult::analyze(
+            "run",
+            "envoy",
+            ChaosFault::NetemDelay,
+            Utc::now(),
+            10_000,
+            99.9,
+            load.into_seconds(),
+        );
+        assert_eq!(result.detection_ms, Some(2000));
+        assert!(!result.recovered);
+        assert!(!result.passed());
+    }
+
+    #[test]
+    fn test_baseline_errors_are_not_charged() {
+        // One failure in every 100 requests before and after the fault
+        let mut load = LoadTimeline::default();
+        for ms in (0..20_000).step_by(10) {
+            load.record(ms, ms % 1000 == 0, 2.0);
+        }
+        let result = ChaosResult::analyze(
+            "run",
+            "envoy",
+            ChaosFault::PodKill,
+            Utc::now(),
+            10_000,
+            99.0,
+            load.into_seconds(),
+        );
+        assert_eq!(result.baseline_error_rate, 1.0);
+        assert_eq!(result.fault_errors, 0);
+        assert_eq!(result.error_budget_consumed, 0.0);
+    }
+
+    #[test]
+    fn test_format_table() {
+        let result = ChaosResult::analyze(
+            "run",
+            "envoy",
+            ChaosFault::PodKill,
+            Utc::now(),
+            30_000,
+            99.0,
+            timeline(60, 31..33),
+        )
+        .with_target("echo-abc")
+        .with_load(true, 100);
+        let table = result.format_table();
+        assert!(table.contains("pod-kill on envoy"));
+        assert!(table.contains("Target:        echo-abc"));
+        assert!(table.contains("Recovery:      2990ms after injection"));
+        assert!(table.contains("30s       500     200        2.0ms  ◀ fault"));
+        assert!(table.contains("Failed: error budget exhausted"));
+        assert_eq!(timeline_window(60), 5);
+        assert_eq!(timeline_window(600), 20);
+    }
+}
@@ -2,7 +2,22 @@
 //!
 //! Defines command-line interface using clap.
 
-use clap::{Parser, Subcommand};
+use clap::parser::ValueSource;
+use clap::{ArgAction, ArgGroup, CommandFactory, FromArgMatches, Parser, Subcommand};
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::net::IpAddr;
+use std::path::PathBuf;
+
+use crate::chaos::ChaosFault;
+use crate::config::env::env_var;
+use crate::config::SettingSource;
+use crate::http::AuthProvider;
+use crate::kubevirt::Provision;
+use crate::models::{
+    BackendFault, CanarySettings, Experimental, GatewayApiVersion, SoakSettings, TestCategory,
+};
+use crate::results::GateCondition;
 
 /// Kubernetes Gateway API Implementation Comparison Tool
 #[derive(Parser, Debug)]
@@ -15,11 +30,222 @@ pub struct Args {
     #[command(subcommand)]
     pub command: Command,
 
-    /// Enable verbose output
-    #[arg(short, long, global = true)]
-    pub verbose: bool,
+    /// Increase log verbosity (-v debug, -vv trace with request headers, -vvv trace everything)
+    #[arg(short, long, global = true, action = ArgAction::Count)]
+    pub verbose: u8,
+
+    /// Only print errors and the final summary
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    pub quiet: bool,
+
+    /// Log format (text, json)
+    #[arg(long, global = true, default_value = "text")]
+    pub log_format: String,
+
+    /// Append logs to a file instead of the console
+    #[arg(long, global = true)]
+    pub log_file: Option<String>,
+
+    /// Namespace for routes, test pods, VMs and installs
+    ///
+    /// Falls back to GATEWAY_POC_NAMESPACE, then `app.namespace` in the config
+    /// file, then "default" ("gateway-system" for deploy).
+    #[arg(long, global = true)]
+    pub namespace: Option<String>,
+
+    /// Options of the invoked command given on the command line or in the
+    /// environment
+    #[arg(skip)]
+    pub given: GivenArgs,
 }
 
+/// Options given on the command line or through `GATEWAY_POC_*` variables
+/// (rather than defaulted), by id; config file layers leave them alone
+#[derive(Clone, Debug, Default)]
+pub struct GivenArgs(BTreeMap<String, SettingSource>);
+
+impl GivenArgs {
+    /// Where the option with this id (field name) was given, if it was
+    pub fn source(&self, id: &str) -> Option<&SettingSource> {
+        self.0.get(id)
+    }
+
+    /// Ids of the options taken from environment variables, with the variable
+    pub fn env_vars(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0.iter().filter_map(|(id, source)| match source {
+            SettingSource::Env(var) => Some((id.as_str(), var.as_str())),
+            _ => None,
+        })
+    }
+}
+
+/// The command line; every option can also be given through a
+/// `GATEWAY_POC_*` variable (see [`env_options`])
+pub fn command() -> clap::Command {
+    Args::command()
+}
+
+/// Option that can be set through an environment variable
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EnvOption {
+    /// `GATEWAY_POC_<COMMAND>_<OPTION>`, or `GATEWAY_POC_<OPTION>` for
+    /// global options
+    pub var: String,
+    /// Subcommand path, empty for global options (`benchmark run`)
+    pub command: String,
+    /// Long flag (`--duration`)
+    pub flag: String,
+}
+
+/// Every option settable through the environment, by subcommand
+pub fn env_options() -> Vec<EnvOption> {
+    fn collect(command: &clap::Command, path: &mut Vec<String>, options: &mut Vec<EnvOption>) {
+        for arg in command.get_arguments() {
+            if let (Some(var), Some(flag)) = (settable_var(arg, path), flag(arg)) {
+                options.push(EnvOption {
+                    var,
+                    command: path.join(" "),
+                    flag,
+                });
+            }
+        }
+        for sub in command.get_subcommands() {
+            path.push(sub.get_name().to_string());
+            collect(sub, path, options);
+            path.pop();
+        }
+    }
+    let mut options = Vec::new();
+    collect(&command(), &mut Vec::new(), &mut options);
+    options
+}
+
+/// Variable behind an option of the command at `path`, if it has one
+fn settable_var(arg: &clap::Arg, path: &[String]) -> Option<String> {
+    let settable = !arg.is_positional()
+        && !matches!(
+            arg.get_action(),
+            ArgAction::Count | ArgAction::Help | ArgAction::Version
+        );
+    settable.then(|| env_var(path, arg.get_id().as_str()))
+}
+
+/// Flag that sets the option (`--duration`)
+fn flag(arg: &clap::Arg) -> Option<String> {
+    match (arg.get_long(), arg.get_short()) {
+        (Some(long), _) => Some(format!("--{long}")),
+        (None, Some(short)) => Some(format!("-{short}")),
+        (None, None) => None,
+    }
+}
+
+/// Command-line form of an option taken from the environment; `None` for a
+/// flag the value turns off
+fn env_arg(arg: &clap::Arg, value: &str) -> Option<String> {
+    let flag = flag(arg)?;
+    if !arg.get_action().takes_values() {
+        // As clap reads flags from the environment: anything but a falsey value
+        let falsey = matches!(
+            value.to_lowercase().as_str(),
+            "" | "0" | "n" | "no" | "f" | "false" | "off"
+        );
+        return (!falsey).then_some(flag);
+    }
+    Some(format!("{flag}={value}"))
+}
+
+impl Args {
+    /// Parse the process arguments and environment, recording which
+    /// options were given
+    pub fn parse_with_sources() -> Self {
+        Self::try_parse_with_env(std::env::args_os(), |var| std::env::var(var).ok())
+            .unwrap_or_else(|e| e.exit())
+    }
+
+    /// Parse `argv`, taking the options it leaves out from the variables
+    /// `env` looks up, with where each option came from recorded in
+    /// [`Args::given`]
+    pub fn try_parse_with_env<I, T>(
+        argv: I,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self, clap::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString>,
+    {
+        let mut argv: Vec<OsString> = argv.into_iter().map(Into::into).collect();
+
+        // A first pass finds the subcommand and the options on the command
+        // line; variables only fill in the rest, for that subcommand only
+        let first = command().ignore_errors(true).try_get_matches_from(&argv)?;
+        let root = command();
+        let mut env_ids = BTreeMap::new();
+        let mut env_args = Vec::new();
+        let mut level = Some((&root, &first));
+        let mut path = Vec::new();
+        while let Some((cmd, matches)) = level {
+            for arg in cmd.get_arguments() {
+                let id = arg.get_id().as_str();
+                let Some(var) = settable_var(arg, &path) else {
+                    continue;
+                };
+                if matches.value_source(id) == Some(ValueSource::CommandLine) {
+                    continue;
+                }
+                if let Some(option) = env(&var).and_then(|value| env_arg(arg, &value)) {
+                    env_args.push(OsString::from(option));
+                    env_ids.insert(id.to_string(), var);
+                }
+            }
+            level = matches.subcommand().and_then(|(name, sub)| {
+                path.push(name.to_string());
+                cmd.find_subcommand(name).map(|cmd| (cmd, sub))
+            });
+        }
+        // Options go before any `--` so trailing arguments stay verbatim
+        let end = argv.iter().position(|a| a == "--").unwrap_or(argv.len());
+        argv.splice(end..end, env_args);
+
+        let matches = command().try_get_matches_from(argv)?;
+        let mut args = Self::from_arg_matches(&matches)?;
+        let mut level = Some(&matches);
+        while let Some(matches) = level {
+            for id in matches.ids() {
+                if matches.value_source(id.as_str()) != Some(ValueSource::CommandLine) {
+                    continue;
+                }
+                let source = match env_ids.get(id.as_str()) {
+                    Some(var) => SettingSource::Env(var.clone()),
+                    None => SettingSource::CommandLine,
+                };
+                args.given.0.insert(id.to_string(), source);
+            }
+            level = matches.subcommand().map(|(_, sub)| sub);
+        }
+        Ok(args)
+    }
+
+    /// Whether the subcommand takes over the terminal with the dashboard
+    pub fn uses_tui(&self) -> bool {
+        match &self.command {
+            Command::Test(args) => args.tui,
+            Command::Benchmark(BenchmarkArgs {
+                action: BenchmarkAction::Run { tui, .. },
+            }) => *tui,
+            _ => false,
+        }
+    }
+
+    /// Run whose failures `test --replay-failures` re-executes
+    pub fn replay_run(&self) -> Option<&str> {
+        match &self.command {
+            Command::Test(args) => args.replay_failures.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand, Debug)]
 pub enum Command {
     /// Run Gateway API tests
@@ -42,12 +268,349 @@ pub enum Command {
 
     /// Manage configuration and profiles
     Config(ConfigArgs),
+
+    /// Run as a monitoring daemon with a remote control API
+    Serve(ServeArgs),
+
+    /// Walk a canary through increasing route weights and validate each step
+    Canary(CanaryArgs),
+
+    /// Generate documentation from the test catalog and stored results
+    Docs(DocsArgs),
+
+    /// Diagnose local tooling and cluster prerequisites
+    Doctor(DoctorArgs),
+
+    /// Wait until an HTTP endpoint answers with the expected status
+    Wait(WaitArgs),
+
+    /// Delete resources the tool created (labelled managed-by=gateway-poc)
+    Cleanup(CleanupArgs),
+
+    /// Compare the response headers gateways return for identical requests
+    Headers(HeadersArgs),
+
+    /// Inject faults while traffic flows and measure error budget and recovery
+    Chaos(ChaosArgs),
+}
+
+/// Arguments for headers command
+#[derive(Parser, Debug)]
+pub struct HeadersArgs {
+    /// Gateway to request as gateway=ip (repeatable, at least two)
+    #[arg(long = "target", required = true)]
+    pub targets: Vec<String>,
+
+    /// Request path (repeatable)
+    #[arg(long = "path", default_value = "/")]
+    pub paths: Vec<String>,
+
+    /// Host header
+    #[arg(long, default_value = "example.com")]
+    pub hostname: String,
+
+    /// Gateway port [default: 80, or 443 with --https]
+    #[arg(short, long)]
+    pub port: Option<u16>,
+
+    /// Request over HTTPS (certificates are not verified)
+    #[arg(long)]
+    pub https: bool,
+
+    /// Also list headers every gateway returns identically
+    #[arg(long)]
+    pub all: bool,
+
+    /// Output format (text, json, markdown)
+    #[arg(short, long, default_value = "text")]
+    pub format: String,
+
+    /// Save the comparison to a file
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+/// Arguments for chaos command
+#[derive(Parser, Debug)]
+pub struct ChaosArgs {
+    #[command(subcommand)]
+    pub action: ChaosAction,
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Subcommand, Debug)]
+pub enum ChaosAction {
+    /// Inject a fault during a run and store the result
+    Run {
+        /// Gateway implementation under test
+        #[arg(short, long, default_value = "nginx")]
+        gateway: String,
+
+        /// Gateway IP address (default: the gateway's `connections` entry)
+        #[arg(short, long)]
+        ip: Option<String>,
+
+        /// Gateway port
+        #[arg(short, long, default_value = "80")]
+        port: u16,
+
+        /// Request path
+        #[arg(long, default_value = "/")]
+        path: String,
+
+        /// Host header
+        #[arg(long, default_value = "example.com")]
+        hostname: String,
+
+        /// Fault to inject: pod-kill, gw-restart, netem-delay
+        #[arg(long, value_parser = ChaosFault::parse)]
+        fault: ChaosFault,
+
+        /// Run background load at --rps instead of a single 10 req/s probe
+        #[arg(long)]
+        during_load: bool,
+
+        /// Run time (e.g. 90s, 2m)
+        #[arg(short, long, default_value = "2m", value_parser = SoakSettings::parse_duration)]
+        duration: std::time::Duration,
+
+        /// When into the run the fault is injected
+        #[arg(long, default_value = "30s", value_parser = SoakSettings::parse_duration)]
+        fault_at: std::time::Duration,
+
+        /// Concurrent load workers
+        #[arg(short, long, default_value = "10", requires = "during_load")]
+        concurrency: u32,
+
+        /// Total requests per second of the background load
+        #[arg(short, long, default_value = "100", requires = "during_load")]
+        rps: u32,
+
+        /// Request timeout in seconds (slower requests count as errors)
+        #[arg(long, default_value = "2")]
+        timeout: u64,
+
+        /// Availability SLO in percent; the error budget is what it leaves
+        #[arg(long, default_value = "99.9")]
+        slo: f64,
+
+        /// `app` label of the backend pods pod-kill and netem-delay target
+        #[arg(long, default_value = "echo")]
+        backend: String,
+
+        /// Namespace of the gateway pods gw-restart deletes (default: the
+        /// configured namespace)
+        #[arg(long)]
+        gateway_namespace: Option<String>,
+
+        /// Delay netem-delay adds, in milliseconds
+        #[arg(long, default_value = "500")]
+        delay_ms: u64,
+
+        /// How long netem-delay keeps the delay in place
+        #[arg(long, default_value = "30s", value_parser = SoakSettings::parse_duration)]
+        delay_duration: std::time::Duration,
+
+        /// Output format (table, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+    },
+
+    /// List stored chaos experiments
+    List,
+
+    /// Show a stored chaos experiment
+    Show {
+        /// Experiment ID
+        id: String,
+
+        /// Output format (table, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+    },
+}
+
+/// Arguments for cleanup command
+///
+/// Routes and Gateways are always included; `--namespace` limits the scope
+/// to one namespace (default: all namespaces).
+#[derive(Parser, Debug)]
+pub struct CleanupArgs {
+    /// Only resources created for this gateway implementation
+    #[arg(short, long)]
+    pub gateway: Option<String>,
+
+    /// Also delete test pods, load generators, and backend fixtures
+    #[arg(long)]
+    pub fixtures: bool,
+
+    /// Also delete KubeVirt VMs
+    #[arg(long)]
+    pub vms: bool,
+
+    /// Delete fixtures, VMs, and the namespaces the tool created as well
+    #[arg(long)]
+    pub all: bool,
+
+    /// Only list what would be deleted
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// Arguments for wait command
+#[derive(Parser, Debug)]
+pub struct WaitArgs {
+    /// URL to poll (e.g. http://10.0.0.1/health)
+    #[arg(short, long)]
+    pub url: String,
+
+    /// Seconds to wait before giving up
+    #[arg(short, long, default_value = "120")]
+    pub timeout: u64,
+
+    /// Expected status code (default: any 2xx)
+    #[arg(short, long)]
+    pub status: Option<u16>,
+
+    /// Host header to send
+    #[arg(long)]
+    pub hostname: Option<String>,
+
+    /// Seconds between attempts
+    #[arg(long, default_value = "2")]
+    pub interval: u64,
+}
+
+/// Arguments for doctor command
+#[derive(Parser, Debug)]
+pub struct DoctorArgs {
+    /// Output format (table, json)
+    #[arg(short, long, default_value = "table")]
+    pub format: String,
+
+    /// Also write the report to a file
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+/// Arguments for docs command
+#[derive(Parser, Debug)]
+pub struct DocsArgs {
+    #[command(subcommand)]
+    pub action: DocsAction,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum DocsAction {
+    /// Catalog of what each test validates and which gateways pass it
+    Features {
+        /// Output format (markdown, json)
+        #[arg(short, long, default_value = "markdown")]
+        format: String,
+
+        /// Write to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+/// Arguments for canary command
+#[derive(Parser, Debug)]
+pub struct CanaryArgs {
+    /// Gateway IP address
+    #[arg(short, long)]
+    pub ip: String,
+
+    /// Gateway port
+    #[arg(short, long, default_value = "80")]
+    pub port: u16,
+
+    /// Request path
+    #[arg(long, default_value = "/")]
+    pub path: String,
+
+    /// Host header
+    #[arg(long, default_value = "example.com")]
+    pub hostname: String,
+
+    /// HTTPRoute with the weighted rule
+    #[arg(short, long)]
+    pub route: String,
+
+    /// Rule index within the route
+    #[arg(long, default_value = "0")]
+    pub rule: usize,
+
+    /// Stable backend service
+    #[arg(long)]
+    pub stable: String,
+
+    /// Canary backend service
+    #[arg(long)]
+    pub canary: String,
+
+    /// Canary weights in percent, in order
+    #[arg(long, default_value = "1,5,25,50,100")]
+    pub steps: String,
+
+    /// Requests used to validate each step
+    #[arg(long, default_value = "400")]
+    pub samples: usize,
+
+    /// Requests per propagation probe
+    #[arg(long, default_value = "50")]
+    pub probe_size: usize,
+
+    /// Confidence level for distribution bounds (0.99 or 99%)
+    #[arg(long, default_value = "0.99", value_parser = CanarySettings::parse_confidence)]
+    pub confidence: f64,
+
+    /// Seconds to wait for a weight change to propagate
+    #[arg(long, default_value = "60")]
+    pub propagation_timeout: u64,
+
+    /// Leave weights in place when a step fails
+    #[arg(long)]
+    pub no_rollback: bool,
+
+    /// Output format (table, json)
+    #[arg(short, long, default_value = "table")]
+    pub format: String,
+}
+
+/// Arguments for serve command
+#[derive(Parser, Debug)]
+pub struct ServeArgs {
+    /// Listen address for the control API
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    pub bind: String,
+
+    /// Bearer token for the API
+    #[arg(long)]
+    pub token: Option<String>,
+
+    /// Target gateways as gateway=ip (repeatable)
+    #[arg(long = "target")]
+    pub targets: Vec<String>,
+
+    /// Seconds between scheduled runs (0 = on demand only)
+    #[arg(long, default_value = "300")]
+    pub interval: u64,
+
+    /// Number of concurrent tests per gateway
+    #[arg(short, long, default_value = "4")]
+    pub concurrent: usize,
+
+    /// Serve the shields.io badge endpoints without the bearer token
+    #[arg(long)]
+    pub public_badges: bool,
 }
 
 /// Arguments for test command
 #[derive(Parser, Debug)]
 pub struct TestArgs {
-    /// Gateway implementation to test
+    /// Gateway implementation to test ("auto" detects it from the cluster's
+    /// GatewayClasses)
     #[arg(short, long, default_value = "nginx")]
     pub gateway: String,
 
@@ -55,7 +618,7 @@ pub struct TestArgs {
     #[arg(short, long)]
     pub ip: Option<String>,
 
-    /// Specific test number to run (1-17)
+    /// Specific test number to run (`gateway-poc list` shows the catalog)
     #[arg(short, long)]
     pub test: Option<u8>,
 
@@ -67,6 +630,15 @@ pub struct TestArgs {
     #[arg(short, long, default_value = "1")]
     pub rounds: u32,
 
+    /// Soak: run rounds back to back for this long (e.g. 30m, 6h, 1d) and
+    /// report pass rates per time bucket; implies --parallel
+    #[arg(long, value_parser = SoakSettings::parse_duration, conflicts_with_all = ["rounds", "test"])]
+    pub duration: Option<std::time::Duration>,
+
+    /// Minutes between saves of a running soak to the results store
+    #[arg(long, default_value = "10", requires = "duration", value_parser = clap::value_parser!(u64).range(1..))]
+    pub checkpoint_interval: u64,
+
     /// Run tests in parallel
     #[arg(short, long)]
     pub parallel: bool,
@@ -99,9 +671,184 @@ pub struct TestArgs {
     #[arg(long, default_value = "30")]
     pub timeout: u64,
 
-    /// Skip specific tests (comma-separated test numbers)
+    /// Time budget for the whole suite in seconds; tests still running are
+    /// aborted and the rest skipped, and the partial run is still saved
+    #[arg(long)]
+    pub suite_timeout: Option<u64>,
+
+    /// Save the run to the results store for `results` and reports; runs
+    /// stopped early are always saved
+    #[arg(long)]
+    pub save: bool,
+
+    /// Re-run failed tests up to N times; passes on retry are reported as flaky
+    #[arg(long, default_value = "0")]
+    pub retries: u32,
+
+    /// Delay between retries in milliseconds
+    #[arg(long, default_value = "1000", requires = "retries")]
+    pub retry_delay: u64,
+
+    /// Skip specific tests (comma-separated test numbers, e.g. 3,7)
+    #[arg(long, value_delimiter = ',', conflicts_with = "test")]
+    pub skip: Vec<u8>,
+
+    /// Re-run only the tests that failed in a stored run, against the same
+    /// target, with trace logging written to a transcript file
+    #[arg(long, value_name = "RUN_ID", conflicts_with_all = ["test", "rounds", "parallel", "profile"])]
+    pub replay_failures: Option<String>,
+
+    /// Run only these categories (routing, tls, traffic, advanced, ai; comma-separated)
+    #[arg(long, value_delimiter = ',', value_parser = TestCategory::parse, conflicts_with = "test")]
+    pub only: Vec<TestCategory>,
+
+    /// Run only tests carrying one of these tags (comma-separated or repeatable)
+    #[arg(long = "tag", value_delimiter = ',', conflicts_with = "test")]
+    pub tags: Vec<String>,
+
+    /// Enable experimental test groups outside the default suite (ai,
+    /// disruptive)
+    #[arg(long, value_delimiter = ',', value_parser = Experimental::parse)]
+    pub experimental: Vec<Experimental>,
+
+    /// Resolve a hostname in the test clients, e.g. `*.example.com` or
+    /// `app1.example.com=10.0.0.5` (default address: the gateway IP; repeatable)
+    #[arg(long, value_name = "HOST[=IP]")]
+    pub resolve: Vec<String>,
+
+    /// Nameserver for other hostnames instead of the system resolver (repeatable)
+    #[arg(long, value_name = "IP[:PORT]")]
+    pub dns_server: Vec<String>,
+
+    /// Address family for dual-stack hostnames: auto (happy eyeballs), v4, v6, or
+    /// dual (like auto, and test 22 requires both families)
+    #[arg(long, default_value = "auto")]
+    pub ip_family: String,
+
+    /// Open a new connection for every request instead of reusing pooled ones
+    #[arg(long, conflicts_with = "max_idle_per_host")]
+    pub no_keep_alive: bool,
+
+    /// Idle connections kept per host by each test client
+    #[arg(long, value_name = "N")]
+    pub max_idle_per_host: Option<usize>,
+
+    /// Leave Nagle's algorithm enabled on test connections
+    #[arg(long)]
+    pub no_tcp_nodelay: bool,
+
+    /// Local address to send session affinity requests from; switches test 10
+    /// to source-IP affinity (repeatable, addresses must be assigned to this host)
+    #[arg(long, value_name = "IP")]
+    pub source_address: Vec<IpAddr>,
+
+    /// Authorization token source: `bearer:TOKEN`, `bearer-env:VAR`,
+    /// `oidc:TOKEN_URL` (client credentials from GATEWAY_POC_OIDC_* env vars)
+    /// or `exec:COMMAND [ARGS]`
+    #[arg(long, value_name = "SPEC", value_parser = AuthProvider::parse)]
+    pub auth: Option<AuthProvider>,
+
+    /// Capture cluster health (nodes, pending pods, events, API latency) each round,
+    /// and the nodes of the backend pods that served each test
+    #[arg(long)]
+    pub capture_env: bool,
+
+    /// Exclude rounds that started on an unhealthy cluster from aggregation
+    /// (not with --duration: soak reports count them instead)
+    #[arg(long, requires = "capture_env", conflicts_with = "duration")]
+    pub exclude_anomalous: bool,
+
+    /// Flag rounds as tainted if a gateway pod restarts while they run
+    #[arg(long)]
+    pub watch_crashes: bool,
+
+    /// Save events, gateway pod logs, and HTTPRoute status for each failed
+    /// test into a directory under DIR
+    #[arg(long, value_name = "DIR")]
+    pub diagnostics: Option<std::path::PathBuf>,
+
+    /// Namespace of the gateway pods for --watch-crashes and --diagnostics
+    /// (default: all namespaces) and for Zero-Downtime Reload (default: the
+    /// test namespace)
+    #[arg(long)]
+    pub gateway_namespace: Option<String>,
+
+    /// Report the HTTP requests, bytes, and error classes of each round
+    #[arg(long)]
+    pub http_metrics: bool,
+
+    /// Apply implementation-specific policies and fixtures before the tests
+    /// that need them (rate limit, session affinity, consistent hash, CORS,
+    /// cross-namespace and TLS backends) and remove what was created
+    /// afterwards; Consistent Hashing also scales its backend by one replica
+    #[arg(long)]
+    pub setup_policies: bool,
+
+    /// Test profile (config file or built-in) providing the tests, rounds,
+    /// parallelism, ports, hostname, Load Test settings and round variants;
+    /// options given here override it
+    #[arg(long)]
+    pub profile: Option<String>,
+
+    /// Load Test engine (simple, benchmark)
+    #[arg(long)]
+    pub load_engine: Option<String>,
+
+    /// Load Test concurrent users
+    #[arg(long)]
+    pub load_users: Option<usize>,
+
+    /// Load Test total requests (simple engine)
+    #[arg(long)]
+    pub load_requests: Option<usize>,
+
+    /// Load Test duration in seconds (benchmark engine)
+    #[arg(long)]
+    pub load_duration: Option<u64>,
+
+    /// Confidence level of the Canary Traffic split check (0.99 or 99%);
+    /// higher levels and smaller canary weights send more requests
+    #[arg(long, default_value = "0.99", value_parser = CanarySettings::parse_confidence)]
+    pub confidence: f64,
+
+    /// Fault the Failover Recovery test injects into its backend: delete-pod,
+    /// cordon-node or scale-to-zero (default: none, only watch for recovery)
+    #[arg(long, default_value = "none", value_parser = BackendFault::parse)]
+    pub failover_fault: BackendFault,
+
+    /// Exit 1 when a condition holds, e.g. `pass-rate<100%`, `failed>0` or `flaky>0` (repeatable)
+    #[arg(long, value_parser = GateCondition::parse)]
+    pub fail_on: Vec<GateCondition>,
+
+    /// Gateway API version to gate tests on (default: detect from installed CRDs)
+    #[arg(long, value_parser = GatewayApiVersion::parse)]
+    pub gateway_api_version: Option<GatewayApiVersion>,
+
+    /// Gateway API release channel used with --gateway-api-version
+    #[arg(long, default_value = "standard", requires = "gateway_api_version")]
+    pub gateway_api_channel: String,
+
+    /// Run the suite inside this KubeVirt VM over SSH, for gateways only
+    /// reachable from the VM network; results are stored locally
+    #[arg(long, value_name = "VM", conflicts_with_all = ["tui", "replay_failures"])]
+    pub vm: Option<String>,
+
+    /// gateway-poc binary uploaded for --vm (default: this executable); must
+    /// be statically linked and built for the VM's architecture
+    #[arg(long, value_name = "PATH", requires = "vm")]
+    pub vm_binary: Option<PathBuf>,
+
+    /// SSH user for --vm
+    #[arg(long, default_value = "fedora", requires = "vm")]
+    pub ssh_user: String,
+
+    /// SSH private key for --vm (default: app.kubevirt.ssh_key)
+    #[arg(long, requires = "vm")]
+    pub ssh_key: Option<String>,
+
+    /// Show a live terminal dashboard instead of scrolling output
     #[arg(long)]
-    pub skip: Option<String>,
+    pub tui: bool,
 
     /// Save results to file
     #[arg(short, long)]
@@ -115,7 +862,7 @@ pub struct ListArgs {
     #[arg(short, long)]
     pub detailed: bool,
 
-    /// Show gateway implementations
+    /// Show gateway implementations and their optional capabilities
     #[arg(short, long)]
     pub gateways: bool,
 }
@@ -146,17 +893,45 @@ pub enum VmAction {
         /// VM disk size in GB
         #[arg(long, default_value = "50")]
         disk: u32,
-    },
 
-    /// Delete KubeVirt VMs
-    Delete {
-        /// Delete all VMs
-        #[arg(short, long)]
-        all: bool,
+        /// Pull the container disk image on every node before creating VMs
+        #[arg(long)]
+        prepull: bool,
+
+        /// Pre-pull timeout in seconds
+        #[arg(long, default_value = "600", requires = "prepull")]
+        prepull_timeout: u64,
+
+        /// Install a preset at first boot via cloud-init (k3s, containerd)
+        #[arg(long, value_parser = Provision::parse)]
+        provision: Vec<Provision>,
+
+        /// Shell script run as root at first boot, after the presets
+        #[arg(long, value_name = "FILE")]
+        provision_script: Option<PathBuf>,
+
+        /// Seconds to wait for provisioning to finish (needs an SSH key)
+        #[arg(long, default_value = "900")]
+        provision_timeout: u64,
+
+        /// Print the VirtualMachine manifests instead of creating them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Delete KubeVirt VMs
+    Delete {
+        /// Delete all VMs
+        #[arg(short, long)]
+        all: bool,
 
         /// Specific VM name to delete
         #[arg(short, long)]
         name: Option<String>,
+
+        /// Print the VMs that would be deleted without deleting them
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Show VM status
@@ -167,11 +942,99 @@ pub enum VmAction {
         /// VM name
         name: String,
     },
+
+    /// Create, resize, relabel, replace and delete VMs to match a pool spec
+    Apply {
+        /// YAML file declaring the VM pools
+        #[arg(long)]
+        spec: PathBuf,
+
+        /// Print the changes without making them
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Seconds to wait for each VM to stop, be deleted or become ready
+        #[arg(long, default_value = "300")]
+        timeout: u64,
+    },
+
+    /// Run a command in a VM over SSH, e.g. `vm exec vm-0 -- uname -m`
+    Exec {
+        /// VM name
+        name: String,
+
+        /// SSH user
+        #[arg(long, default_value = "fedora")]
+        ssh_user: String,
+
+        /// SSH private key (default: app.kubevirt.ssh_key)
+        #[arg(long)]
+        ssh_key: Option<String>,
+
+        /// Command and its arguments
+        #[arg(last = true, required = true)]
+        command: Vec<String>,
+    },
+
+    /// Start a stopped VM and wait for it to be ready
+    Start {
+        /// VM name
+        name: String,
+
+        /// Seconds to wait for the VM to become ready
+        #[arg(long, default_value = "300")]
+        timeout: u64,
+    },
+
+    /// Stop a running VM
+    Stop {
+        /// VM name
+        name: String,
+
+        /// Seconds to wait for the VM to stop
+        #[arg(long, default_value = "300")]
+        timeout: u64,
+    },
+
+    /// Stop and start a VM, waiting for it to be ready again
+    Restart {
+        /// VM name
+        name: String,
+
+        /// Seconds to wait for each of stopping and becoming ready
+        #[arg(long, default_value = "300")]
+        timeout: u64,
+    },
+
+    /// Change a VM's CPU cores and/or memory and restart it
+    Resize {
+        /// VM name
+        name: String,
+
+        /// VM CPU cores
+        #[arg(long, required_unless_present = "memory")]
+        cpu: Option<u32>,
+
+        /// VM memory in GB
+        #[arg(long)]
+        memory: Option<u32>,
+
+        /// Only update the VM spec; the new size applies on the next restart
+        #[arg(long)]
+        no_restart: bool,
+
+        /// Seconds to wait for each of stopping and becoming ready
+        #[arg(long, default_value = "300")]
+        timeout: u64,
+    },
 }
 
 /// Arguments for results command
 #[derive(Parser, Debug)]
 pub struct ResultsArgs {
+    #[command(subcommand)]
+    pub action: Option<ResultsAction>,
+
     /// Show summary only
     #[arg(short, long)]
     pub summary: bool,
@@ -184,11 +1047,72 @@ pub struct ResultsArgs {
     #[arg(short, long, default_value = "table")]
     pub format: String,
 
-    /// Export to file
+    /// Export to file (.md, .html, .txt), or PNG/SVG charts to a directory (e.g. charts/)
     #[arg(short, long)]
     pub export: Option<String>,
 }
 
+#[derive(Subcommand, Debug)]
+pub enum ResultsAction {
+    /// Attach a reviewer note to a stored run (rendered in reports)
+    Annotate {
+        /// Run ID
+        run_id: String,
+
+        /// Test number the note refers to (omit for a note on the whole run)
+        #[arg(short, long)]
+        test: Option<u8>,
+
+        /// Note text
+        #[arg(short, long)]
+        note: String,
+
+        /// Reviewer name (default: $USER)
+        #[arg(long)]
+        author: Option<String>,
+    },
+
+    /// Package runs and benchmark snapshots into a portable .tar.zst bundle
+    ExportBundle {
+        /// Output file (e.g. out.tar.zst)
+        output: PathBuf,
+
+        /// Run IDs to include
+        runs: Vec<String>,
+
+        /// Include all runs of a gateway (repeatable)
+        #[arg(short, long)]
+        gateway: Vec<String>,
+
+        /// Include every stored run
+        #[arg(long)]
+        all: bool,
+
+        /// Leave out benchmark snapshots
+        #[arg(long)]
+        no_benchmarks: bool,
+    },
+
+    /// Show how the gateway configuration differed between two runs
+    SetupDiff {
+        /// First run ID
+        left: String,
+
+        /// Second run ID
+        right: String,
+    },
+
+    /// Import runs and benchmark snapshots from a bundle
+    ImportBundle {
+        /// Bundle file
+        path: PathBuf,
+
+        /// Replace runs that already exist
+        #[arg(long)]
+        force: bool,
+    },
+}
+
 /// Arguments for deploy command
 #[derive(Parser, Debug)]
 pub struct DeployArgs {
@@ -203,13 +1127,17 @@ pub enum DeployAction {
         /// Gateway implementation to install
         gateway: String,
 
-        /// Namespace for installation
-        #[arg(short, long, default_value = "gateway-system")]
-        namespace: String,
-
         /// Wait timeout in seconds
         #[arg(long, default_value = "300")]
         timeout: u64,
+
+        /// After installing, wait until this URL answers with a 2xx
+        #[arg(long)]
+        wait_url: Option<String>,
+
+        /// Print the commands and manifests instead of applying them
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Uninstall a gateway implementation
@@ -217,9 +1145,9 @@ pub enum DeployAction {
         /// Gateway implementation to uninstall
         gateway: String,
 
-        /// Namespace
-        #[arg(short, long, default_value = "gateway-system")]
-        namespace: String,
+        /// Print the commands instead of running them
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// List installed gateways
@@ -230,13 +1158,26 @@ pub enum DeployAction {
         /// Gateway implementation to check
         gateway: String,
 
-        /// Gateway IP address
+        /// Gateway IP address (default: the gateway's `connections` entry)
         #[arg(short, long)]
-        ip: String,
+        ip: Option<String>,
 
         /// Gateway port
         #[arg(short, long, default_value = "80")]
         port: u16,
+
+        /// Also check the certificate of the HTTPS listener, sending this
+        /// hostname as SNI
+        #[arg(long, value_name = "HOSTNAME")]
+        tls: Option<String>,
+
+        /// HTTPS listener port
+        #[arg(long, default_value = "443", requires = "tls")]
+        tls_port: u16,
+
+        /// Warn when the certificate expires within this many days
+        #[arg(long, default_value = "30", value_name = "DAYS", requires = "tls")]
+        cert_warning_days: i64,
     },
 
     /// Run pre-flight checks
@@ -244,9 +1185,9 @@ pub enum DeployAction {
         /// Gateway implementation
         gateway: String,
 
-        /// Gateway IP address
+        /// Gateway IP address (default: the gateway's `connections` entry)
         #[arg(short, long)]
-        ip: String,
+        ip: Option<String>,
 
         /// Gateway port
         #[arg(short, long, default_value = "80")]
@@ -258,6 +1199,15 @@ pub enum DeployAction {
         /// Install experimental CRDs
         #[arg(long)]
         experimental: bool,
+
+        /// Gateway API release to install (e.g. v1.0.0, v1.1.0, v1.2.1)
+        #[arg(
+            long,
+            visible_alias = "version",
+            default_value = "v1.2.1",
+            value_parser = GatewayApiVersion::parse
+        )]
+        gateway_api_version: GatewayApiVersion,
     },
 
     /// Generate Kubernetes manifests
@@ -266,7 +1216,8 @@ pub enum DeployAction {
         #[arg(short, long, default_value = "nginx")]
         gateway: String,
 
-        /// Resource type (gateway, httproute)
+        /// Resource type (gateway, httproute, hostname-rewrite, consistent-hash,
+        /// session-affinity, rate-limit, cors, backend-tls, ai)
         #[arg(short, long, default_value = "gateway")]
         resource: String,
 
@@ -277,6 +1228,11 @@ pub enum DeployAction {
         /// Output format (yaml, json)
         #[arg(short, long, default_value = "yaml")]
         format: String,
+
+        /// Write the full test topology (gateway, routes, backends,
+        /// kustomization.yaml) to this directory instead
+        #[arg(long, value_name = "DIR", conflicts_with_all = ["resource", "format"])]
+        bundle: Option<PathBuf>,
     },
 }
 
@@ -287,22 +1243,45 @@ pub struct BenchmarkArgs {
     pub action: BenchmarkAction,
 }
 
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand, Debug)]
 pub enum BenchmarkAction {
     /// Run a benchmark against a gateway
+    #[command(group(ArgGroup::new("pod_watch").multiple(true)))]
     Run {
         /// Gateway implementation to benchmark
         #[arg(short, long, default_value = "nginx")]
         gateway: String,
 
-        /// Gateway IP address
+        /// Gateway IP address or a dual-stack hostname (default: the
+        /// gateway's `connections` entry)
         #[arg(short, long)]
-        ip: String,
+        ip: Option<String>,
 
         /// Gateway port
         #[arg(short, long, default_value = "80")]
         port: u16,
 
+        /// Nameserver for a hostname `--ip` instead of the system resolver (repeatable)
+        #[arg(long, value_name = "IP[:PORT]")]
+        dns_server: Vec<String>,
+
+        /// Address family for a dual-stack `--ip`: auto (happy eyeballs), v4, v6, dual
+        #[arg(long, default_value = "auto")]
+        ip_family: String,
+
+        /// Open a new connection for every request (measures handshake cost)
+        #[arg(long, conflicts_with = "max_idle_per_host")]
+        no_keep_alive: bool,
+
+        /// Idle connections kept per host (default: one per concurrent worker)
+        #[arg(long, value_name = "N")]
+        max_idle_per_host: Option<usize>,
+
+        /// Leave Nagle's algorithm enabled on load connections
+        #[arg(long)]
+        no_tcp_nodelay: bool,
+
         /// Target URL path
         #[arg(long, default_value = "/")]
         path: String,
@@ -327,10 +1306,91 @@ pub enum BenchmarkAction {
         #[arg(long, default_value = "constant")]
         pattern: String,
 
-        /// Warmup duration in seconds
+        /// Warmup duration in seconds (samples excluded from results)
         #[arg(long, default_value = "5")]
         warmup: u64,
 
+        /// Seconds in-flight requests may finish after load stops; later ones
+        /// are cancelled and reported apart from errors
+        #[arg(long, default_value = "5")]
+        drain: u64,
+
+        /// Discard ramp-up samples until RPS stabilizes
+        #[arg(long)]
+        steady_state: bool,
+
+        /// Steady-state moving-average window in seconds
+        #[arg(long, default_value = "5", requires = "steady_state")]
+        steady_window: u64,
+
+        /// Pause load while this file exists (SIGUSR1 also toggles pause)
+        #[arg(long)]
+        pause_file: Option<String>,
+
+        /// Generate load from multiple workers and merge their results
+        #[arg(long)]
+        distributed: bool,
+
+        /// Number of load-generator pods (distributed mode)
+        #[arg(long, default_value = "4")]
+        workers: u32,
+
+        /// Load-generator image containing gateway-poc (distributed mode)
+        #[arg(long, default_value = "gateway-poc:latest")]
+        worker_image: String,
+
+        /// Namespace for load-generator pods or VMs (default: --namespace)
+        #[arg(long)]
+        worker_namespace: Option<String>,
+
+        /// KubeVirt VMs to use as workers instead of pods (comma-separated)
+        #[arg(long)]
+        worker_vms: Option<String>,
+
+        /// SSH user for VM workers
+        #[arg(long, default_value = "fedora")]
+        ssh_user: String,
+
+        /// SSH private key for VM workers
+        #[arg(long)]
+        ssh_key: Option<String>,
+
+        /// Weighted target path:weight[:method] (repeatable)
+        #[arg(long = "target")]
+        targets: Vec<String>,
+
+        /// YAML traffic mix file
+        #[arg(long)]
+        mix: Option<String>,
+
+        /// Traffic mix as JSON, as handed to distributed workers
+        #[arg(long, hide = true, conflicts_with_all = ["mix", "targets"])]
+        mix_json: Option<String>,
+
+        /// Authorization token source (see `test --auth`)
+        #[arg(long, value_name = "SPEC", value_parser = AuthProvider::parse, conflicts_with = "distributed")]
+        auth: Option<AuthProvider>,
+
+        /// Record gateway pod CPU/memory via metrics-server
+        #[arg(long, conflicts_with = "distributed", group = "pod_watch")]
+        resources: bool,
+
+        /// Flag the result as tainted if a gateway pod restarts during the run
+        #[arg(long, conflicts_with = "distributed", group = "pod_watch")]
+        watch_crashes: bool,
+
+        /// Namespace of the gateway pods (default: all namespaces)
+        #[arg(long, requires = "pod_watch")]
+        gateway_namespace: Option<String>,
+
+        /// Exit 1 when a condition holds, e.g. `p99>100ms` or `error-rate>0.1%` (repeatable)
+        #[arg(long, value_parser = GateCondition::parse)]
+        fail_on: Vec<GateCondition>,
+
+        /// Show a live latency/error dashboard while the benchmark runs
+        #[arg(long, conflicts_with = "distributed")]
+        tui: bool,
+
         /// Output format (text, json, markdown, csv, html)
         #[arg(short, long, default_value = "text")]
         format: String,
@@ -341,14 +1401,15 @@ pub enum BenchmarkAction {
     },
 
     /// Compare benchmarks across multiple gateways
+    #[command(group(ArgGroup::new("pod_watch").multiple(true)))]
     Compare {
         /// Gateway implementations to compare (comma-separated)
         #[arg(short, long, default_value = "nginx,envoy,istio")]
         gateways: String,
 
-        /// Gateway IP address
+        /// Gateway IP address (default: each gateway's `connections` entry)
         #[arg(short, long)]
-        ip: String,
+        ip: Option<String>,
 
         /// Gateway port
         #[arg(short, long, default_value = "80")]
@@ -358,15 +1419,185 @@ pub enum BenchmarkAction {
         #[arg(short, long, default_value = "30")]
         duration: u64,
 
-        /// Number of concurrent connections
+        /// Number of concurrent connections [default: 10, or the preset's]
+        #[arg(short, long)]
+        concurrency: Option<u32>,
+
+        /// Target requests per second [default: 100, or the preset's]
+        #[arg(short, long)]
+        rps: Option<u32>,
+
+        /// Scenario preset (ecommerce-edge, api-gateway, grpc-microservices)
+        #[arg(long)]
+        preset: Option<String>,
+
+        /// Apply the preset's HTTPRoutes to each gateway before benchmarking
+        #[arg(long, requires = "preset")]
+        apply_routes: bool,
+
+        /// Namespace for preset HTTPRoutes (default: --namespace)
+        #[arg(long, requires = "apply_routes")]
+        route_namespace: Option<String>,
+
+        /// Record gateway pod CPU/memory via metrics-server
+        #[arg(long, group = "pod_watch")]
+        resources: bool,
+
+        /// Flag results as tainted if a gateway pod restarts during their run
+        #[arg(long, group = "pod_watch")]
+        watch_crashes: bool,
+
+        /// Namespace of the gateway pods (default: all namespaces)
+        #[arg(long, requires = "pod_watch")]
+        gateway_namespace: Option<String>,
+
+        /// Also search each gateway's capacity under this p99 SLO (e.g. 50ms)
+        #[arg(long)]
+        slo_p99: Option<String>,
+
+        /// Run each gateway N times in shuffled A/B/A/B order
+        #[arg(long, value_name = "ROUNDS", conflicts_with = "slo_p99")]
+        interleave: Option<u32>,
+
+        /// Idle seconds between interleaved load phases
+        #[arg(long, default_value = "10", requires = "interleave")]
+        gap: u64,
+
+        /// Seed for the interleaved order (default: random)
+        #[arg(long, requires = "interleave")]
+        seed: Option<u64>,
+
+        /// Length of each interleaved measurement window in seconds [default: --duration]
+        #[arg(long, requires = "interleave")]
+        window: Option<u64>,
+
+        /// Benchmark all gateways at once (for gateways on separate nodes)
+        #[arg(long, conflicts_with_all = ["interleave", "slo_p99"])]
+        parallel: bool,
+
+        /// Output format (text, json, markdown, csv, html)
+        #[arg(short, long, default_value = "text")]
+        format: String,
+
+        /// Save report to file
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// Find the maximum RPS that meets a p99 latency SLO
+    Capacity {
+        /// Gateway implementations to search (comma-separated)
+        #[arg(short, long, default_value = "nginx")]
+        gateways: String,
+
+        /// Gateway IP address
+        #[arg(short, long)]
+        ip: String,
+
+        /// Gateway port
+        #[arg(short, long, default_value = "80")]
+        port: u16,
+
+        /// Target URL path
+        #[arg(long, default_value = "/")]
+        path: String,
+
+        /// Host header
+        #[arg(long, default_value = "example.com")]
+        hostname: String,
+
+        /// p99 latency SLO (e.g. 50ms, 0.2s)
+        #[arg(long, default_value = "50ms")]
+        slo_p99: String,
+
+        /// Maximum tolerated error rate in percent
+        #[arg(long, default_value = "1.0")]
+        max_errors: f64,
+
+        /// First RPS to probe
+        #[arg(long, default_value = "50")]
+        min_rps: u32,
+
+        /// Upper bound for the search
+        #[arg(long, default_value = "10000")]
+        max_rps: u32,
+
+        /// Stop once the search bracket is this narrow (RPS)
+        #[arg(long, default_value = "25")]
+        precision: u32,
+
+        /// Duration of each probe in seconds
+        #[arg(short, long, default_value = "20")]
+        duration: u64,
+
+        /// Minimum number of concurrent connections
         #[arg(short, long, default_value = "10")]
         concurrency: u32,
 
-        /// Target requests per second
-        #[arg(short, long, default_value = "100")]
-        rps: u32,
+        /// Warmup per probe in seconds
+        #[arg(long, default_value = "3")]
+        warmup: u64,
 
-        /// Output format (text, json, markdown, csv, html)
+        /// Output format (text, json, markdown, csv)
+        #[arg(short, long, default_value = "text")]
+        format: String,
+
+        /// Save report to file
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// Find the concurrency that holds a target p95 latency
+    Autotune {
+        /// Gateway implementations to tune (comma-separated)
+        #[arg(short, long, default_value = "nginx")]
+        gateways: String,
+
+        /// Gateway IP address
+        #[arg(short, long)]
+        ip: String,
+
+        /// Gateway port
+        #[arg(short, long, default_value = "80")]
+        port: u16,
+
+        /// Target URL path
+        #[arg(long, default_value = "/")]
+        path: String,
+
+        /// Host header
+        #[arg(long, default_value = "example.com")]
+        hostname: String,
+
+        /// Target p95 latency (e.g. 50ms, 0.2s)
+        #[arg(long, default_value = "50ms")]
+        target_p95: String,
+
+        /// Maximum tolerated error rate in percent
+        #[arg(long, default_value = "1.0")]
+        max_errors: f64,
+
+        /// Starting (and lowest) concurrency
+        #[arg(long, default_value = "1")]
+        min_concurrency: u32,
+
+        /// Highest concurrency to try
+        #[arg(long, default_value = "512")]
+        max_concurrency: u32,
+
+        /// Maximum number of feedback steps
+        #[arg(long, default_value = "12")]
+        max_steps: u32,
+
+        /// Duration of each step in seconds
+        #[arg(short, long, default_value = "15")]
+        duration: u64,
+
+        /// Warmup per step in seconds
+        #[arg(long, default_value = "3")]
+        warmup: u64,
+
+        /// Output format (text, json, markdown, csv)
         #[arg(short, long, default_value = "text")]
         format: String,
 
@@ -412,6 +1643,10 @@ pub enum ConfigAction {
         #[arg(short, long)]
         env: bool,
 
+        /// Show the effective test settings and where each one came from
+        #[arg(long, conflicts_with = "env")]
+        resolved: bool,
+
         /// Output format (yaml, json)
         #[arg(short, long, default_value = "yaml")]
         format: String,
@@ -439,6 +1674,13 @@ pub enum ConfigAction {
         detailed: bool,
     },
 
+    /// List scenario presets
+    Presets {
+        /// Show routes and traffic mix
+        #[arg(short, long)]
+        detailed: bool,
+    },
+
     /// Show profile details
     Profile {
         /// Profile name
@@ -451,10 +1693,11 @@ pub enum ConfigAction {
 
     /// Set configuration value
     Set {
-        /// Configuration key (e.g., app.default_gateway)
+        /// Configuration key as a dot path (e.g., app.default_gateway,
+        /// kubevirt.default_cpu, test_profiles.smoke.rounds)
         key: String,
 
-        /// Configuration value
+        /// Configuration value, checked against the setting's type (lists as [a, b])
         value: String,
 
         /// Config file path
@@ -464,7 +1707,7 @@ pub enum ConfigAction {
 
     /// Get configuration value
     Get {
-        /// Configuration key
+        /// Configuration key as a dot path (see `config set`)
         key: String,
 
         /// Config file path
@@ -511,4 +1754,813 @@ mod tests {
             _ => panic!("Expected Test command"),
         }
     }
+
+    #[test]
+    fn test_given_args() {
+        let env = BTreeMap::from([
+            ("GATEWAY_POC_TEST_CONCURRENT", "4"),
+            ("GATEWAY_POC_TEST_ROUNDS", "7"),
+            ("GATEWAY_POC_TEST_PARALLEL", "yes"),
+            ("GATEWAY_POC_NAMESPACE", "gateway-tests"),
+            // Another command's option
+            ("GATEWAY_POC_BENCHMARK_RUN_DURATION", "30"),
+        ]);
+        let args = Args::try_parse_with_env(
+            ["gateway-poc", "test", "--profile", "smoke", "--rounds", "1"],
+            |var| env.get(var).map(|value| value.to_string()),
+        )
+        .unwrap();
+        // Given even though it equals the default, and over the variable
+        assert_eq!(
+            args.given.source("rounds"),
+            Some(&SettingSource::CommandLine)
+        );
+        assert!(args.given.source("profile").is_some());
+        assert_eq!(
+            args.given.source("concurrent"),
+            Some(&SettingSource::Env(
+                "GATEWAY_POC_TEST_CONCURRENT".to_string()
+            ))
+        );
+        assert!(args.given.source("timeout").is_none());
+        assert!(args.given.source("duration").is_none());
+        assert!(args
+            .given
+            .env_vars()
+            .any(|var| var == ("namespace", "GATEWAY_POC_NAMESPACE")));
+        assert_eq!(args.namespace.as_deref(), Some("gateway-tests"));
+        match args.command {
+            Command::Test(test_args) => {
+                assert_eq!(test_args.concurrent, 4);
+                assert_eq!(test_args.rounds, 1);
+                assert!(test_args.parallel);
+            }
+            _ => panic!("Expected Test command"),
+        }
+    }
+
+    #[test]
+    fn test_env_options_unique() {
+        let options = env_options();
+        let vars: std::collections::BTreeSet<_> = options.iter().map(|o| &o.var).collect();
+        assert_eq!(vars.len(), options.len());
+        assert!(!vars.contains(&crate::config::env::config_var()));
+        assert!(options.iter().any(|o| o.var == "GATEWAY_POC_TEST_DURATION"));
+        assert!(options
+            .iter()
+            .any(|o| o.var == "GATEWAY_POC_BENCHMARK_RUN_DURATION" && o.flag == "--duration"));
+    }
+
+    #[test]
+    fn test_test_load_args() {
+        let args = Args::parse_from([
+            "gateway-poc",
+            "test",
+            "--profile",
+            "performance",
+            "--load-engine",
+            "benchmark",
+            "--load-users",
+            "25",
+        ]);
+        match args.command {
+            Command::Test(test_args) => {
+                assert_eq!(test_args.profile.as_deref(), Some("performance"));
+                assert_eq!(test_args.load_engine.as_deref(), Some("benchmark"));
+                assert_eq!(test_args.load_users, Some(25));
+                assert_eq!(test_args.load_requests, None);
+            }
+            _ => panic!("Expected Test command"),
+        }
+    }
+
+    #[test]
+    fn test_gateway_api_version_args() {
+        let args = Args::parse_from(["gateway-poc", "test", "--gateway-api-version", "v1.1.0"]);
+        match args.command {
+            Command::Test(test_args) => {
+                assert_eq!(test_args.gateway_api_version, Some(GatewayApiVersion::V1_1));
+                assert_eq!(test_args.gateway_api_channel, "standard");
+            }
+            _ => panic!("Expected Test command"),
+        }
+
+        let args = Args::parse_from(["gateway-poc", "deploy", "crds"]);
+        match args.command {
+            Command::Deploy(DeployArgs {
+                action:
+                    DeployAction::Crds {
+                        gateway_api_version,
+                        ..
+                    },
+            }) => assert_eq!(gateway_api_version, GatewayApiVersion::DEFAULT),
+            _ => panic!("Expected deploy crds command"),
+        }
+
+        let args = Args::parse_from(["gateway-poc", "deploy", "crds", "--version", "v1.0.0"]);
+        match args.command {
+            Command::Deploy(DeployArgs {
+                action:
+                    DeployAction::Crds {
+                        gateway_api_version,
+                        ..
+                    },
+            }) => assert_eq!(gateway_api_version, GatewayApiVersion::V1_0),
+            _ => panic!("Expected deploy crds command"),
+        }
+
+        assert!(
+            Args::try_parse_from(["gateway-poc", "test", "--gateway-api-version", "latest"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_benchmark_run_targets() {
+        let args = Args::parse_from([
+            "gateway-poc",
+            "benchmark",
+            "run",
+            "--ip",
+            "10.0.0.1",
+            "--target",
+            "/api/list:70",
+            "--target",
+            "/api/create:30:POST",
+        ]);
+        match args.command {
+            Command::Benchmark(bench_args) => match bench_args.action {
+                BenchmarkAction::Run { targets, mix, .. } => {
+                    assert_eq!(targets, vec!["/api/list:70", "/api/create:30:POST"]);
+                    assert!(mix.is_none());
+                }
+                _ => panic!("Expected Run action"),
+            },
+            _ => panic!("Expected Benchmark command"),
+        }
+    }
+
+    #[test]
+    fn test_serve_args() {
+        let args = Args::parse_from([
+            "gateway-poc",
+            "serve",
+            "--target",
+            "nginx=10.0.0.1",
+            "--interval",
+            "0",
+        ]);
+        match args.command {
+            Command::Serve(serve_args) => {
+                assert_eq!(serve_args.targets, vec!["nginx=10.0.0.1"]);
+                assert_eq!(serve_args.interval, 0);
+                assert_eq!(serve_args.bind, "127.0.0.1:8080");
+            }
+            _ => panic!("Expected Serve command"),
+        }
+    }
+
+    #[test]
+    fn test_global_log_args() {
+        let args = Args::parse_from([
+            "gateway-poc",
+            "test",
+            "--log-format",
+            "json",
+            "--log-file",
+            "/tmp/run.log",
+        ]);
+        assert_eq!(args.log_format, "json");
+        assert_eq!(args.log_file.as_deref(), Some("/tmp/run.log"));
+
+        let args = Args::parse_from(["gateway-poc", "list"]);
+        assert_eq!(args.log_format, "text");
+        assert!(args.log_file.is_none());
+        assert_eq!(args.verbose, 0);
+        assert!(!args.quiet);
+    }
+
+    #[test]
+    fn test_global_verbosity_args() {
+        let args = Args::parse_from(["gateway-poc", "test", "-vv"]);
+        assert_eq!(args.verbose, 2);
+
+        let args = Args::parse_from(["gateway-poc", "-q", "test"]);
+        assert!(args.quiet);
+
+        assert!(Args::try_parse_from(["gateway-poc", "test", "-v", "--quiet"]).is_err());
+    }
+
+    #[test]
+    fn test_global_namespace() {
+        let args = Args::parse_from(["gateway-poc", "cleanup", "--namespace", "team-a"]);
+        assert_eq!(args.namespace.as_deref(), Some("team-a"));
+
+        let args = Args::parse_from(["gateway-poc", "--namespace", "team-a", "vm", "status"]);
+        assert_eq!(args.namespace.as_deref(), Some("team-a"));
+
+        let args = Args::parse_from(["gateway-poc", "list"]);
+        assert!(args.namespace.is_none());
+    }
+
+    #[test]
+    fn test_cleanup_args() {
+        let args = Args::parse_from([
+            "gateway-poc",
+            "cleanup",
+            "-g",
+            "envoy",
+            "--vms",
+            "--dry-run",
+        ]);
+        match args.command {
+            Command::Cleanup(cleanup) => {
+                assert_eq!(cleanup.gateway.as_deref(), Some("envoy"));
+                assert!(cleanup.vms && cleanup.dry_run);
+                assert!(!cleanup.fixtures && !cleanup.all);
+            }
+            _ => panic!("Expected Cleanup command"),
+        }
+    }
+
+    #[test]
+    fn test_vm_provision_args() {
+        let args = Args::parse_from([
+            "gateway-poc",
+            "vm",
+            "create",
+            "--provision",
+            "k3s",
+            "--provision-script",
+            "setup.sh",
+        ]);
+        match args.command {
+            Command::Vm(vm) => match vm.action {
+                VmAction::Create {
+                    provision,
+                    provision_script,
+                    ..
+                } => {
+                    assert_eq!(provision, [Provision::K3s]);
+                    assert_eq!(provision_script, Some(PathBuf::from("setup.sh")));
+                }
+                _ => panic!("Expected vm create"),
+            },
+            _ => panic!("Expected Vm command"),
+        }
+    }
+
+    #[test]
+    fn test_vm_exec_args() {
+        let args = Args::parse_from([
+            "gateway-poc",
+            "vm",
+            "exec",
+            "vm-0",
+            "--",
+            "cat",
+            "--number",
+            "/etc/os-release",
+        ]);
+        match args.command {
+            Command::Vm(vm) => match vm.action {
+                VmAction::Exec { name, command, .. } => {
+                    assert_eq!(name, "vm-0");
+                    assert_eq!(command, ["cat", "--number", "/etc/os-release"]);
+                }
+                _ => panic!("Expected vm exec"),
+            },
+            _ => panic!("Expected Vm command"),
+        }
+
+        assert!(Args::try_parse_from(["gateway-poc", "vm", "exec", "vm-0"]).is_err());
+    }
+
+    #[test]
+    fn test_vm_resize_args() {
+        let args = Args::parse_from(["gateway-poc", "vm", "resize", "vm-0", "--memory", "16"]);
+        match args.command {
+            Command::Vm(vm) => match vm.action {
+                VmAction::Resize {
+                    name,
+                    cpu,
+                    memory,
+                    no_restart,
+                    ..
+                } => {
+                    assert_eq!(name, "vm-0");
+                    assert_eq!((cpu, memory), (None, Some(16)));
+                    assert!(!no_restart);
+                }
+                _ => panic!("Expected vm resize"),
+            },
+            _ => panic!("Expected Vm command"),
+        }
+
+        // A resize needs something to change
+        assert!(Args::try_parse_from(["gateway-poc", "vm", "resize", "vm-0"]).is_err());
+    }
+
+    #[test]
+    fn test_dry_run_args() {
+        let args = Args::parse_from(["gateway-poc", "deploy", "install", "envoy", "--dry-run"]);
+        match args.command {
+            Command::Deploy(deploy) => {
+                assert!(matches!(
+                    deploy.action,
+                    DeployAction::Install { dry_run: true, .. }
+                ))
+            }
+            _ => panic!("Expected Deploy command"),
+        }
+
+        let args = Args::parse_from(["gateway-poc", "vm", "create", "-w", "2", "--dry-run"]);
+        match args.command {
+            Command::Vm(vm) => assert!(matches!(
+                vm.action,
+                VmAction::Create {
+                    workers: 2,
+                    dry_run: true,
+                    ..
+                }
+            )),
+            _ => panic!("Expected Vm command"),
+        }
+    }
+
+    #[test]
+    fn test_manifest_bundle_args() {
+        let args = Args::parse_from([
+            "gateway-poc",
+            "deploy",
+            "manifest",
+            "-g",
+            "envoy",
+            "--bundle",
+            "./out",
+        ]);
+        match args.command {
+            Command::Deploy(deploy) => match deploy.action {
+                DeployAction::Manifest {
+                    gateway, bundle, ..
+                } => {
+                    assert_eq!(gateway, "envoy");
+                    assert_eq!(bundle, Some(PathBuf::from("./out")));
+                }
+                _ => panic!("Expected Manifest action"),
+            },
+            _ => panic!("Expected Deploy command"),
+        }
+
+        assert!(Args::try_parse_from([
+            "gateway-poc",
+            "deploy",
+            "manifest",
+            "-r",
+            "httproute",
+            "--bundle",
+            "./out",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_filter_args() {
+        let args = Args::parse_from([
+            "gateway-poc",
+            "test",
+            "--skip",
+            "3,7",
+            "--only",
+            "routing,tls",
+            "--tag",
+            "core",
+            "--tag",
+            "policy",
+        ]);
+        match args.command {
+            Command::Test(test_args) => {
+                assert_eq!(test_args.skip, vec![3, 7]);
+                assert_eq!(
+                    test_args.only,
+                    vec![TestCategory::Routing, TestCategory::Tls]
+                );
+                assert_eq!(test_args.tags, vec!["core", "policy"]);
+            }
+            _ => panic!("Expected Test command"),
+        }
+
+        assert!(Args::try_parse_from(["gateway-poc", "test", "--only", "misc"]).is_err());
+        assert!(Args::try_parse_from(["gateway-poc", "test", "-t", "3", "--skip", "3"]).is_err());
+    }
+
+    #[test]
+    fn test_replay_failures_args() {
+        let args = Args::parse_from([
+            "gateway-poc",
+            "test",
+            "--replay-failures",
+            "20250101_120000",
+        ]);
+        assert_eq!(args.replay_run(), Some("20250101_120000"));
+        assert_eq!(Args::parse_from(["gateway-poc", "test"]).replay_run(), None);
+
+        assert!(Args::try_parse_from([
+            "gateway-poc",
+            "test",
+            "--replay-failures",
+            "x",
+            "--rounds",
+            "5"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_tui_args() {
+        let args = Args::parse_from(["gateway-poc", "test", "--parallel", "--tui"]);
+        assert!(args.uses_tui());
+
+        let args = Args::parse_from(["gateway-poc", "benchmark", "run", "--ip", "10.0.0.1"]);
+        assert!(!args.uses_tui());
+
+        assert!(Args::try_parse_from([
+            "gateway-poc",
+            "benchmark",
+            "run",
+            "--ip",
+            "10.0.0.1",
+            "--distributed",
+            "--tui",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_canary_args() {
+        let args = Args::parse_from([
+            "gateway-poc",
+            "canary",
+            "--ip",
+            "10.0.0.1",
+            "--route",
+            "app",
+            "--stable",
+            "app-v1",
+            "--canary",
+            "app-v2",
+        ]);
+        match args.command {
+            Command::Canary(canary) => {
+                assert_eq!(canary.route, "app");
+                assert_eq!(canary.steps, "1,5,25,50,100");
+                assert_eq!(canary.samples, 400);
+                assert!(!canary.no_rollback);
+            }
+            _ => panic!("Expected Canary command"),
+        }
+    }
+
+    #[test]
+    fn test_results_annotate_args() {
+        let args = Args::parse_from([
+            "gateway-poc",
+            "results",
+            "annotate",
+            "20250101_120000_0042",
+            "--test",
+            "8",
+            "--note",
+            "Kong needs plugin install",
+        ]);
+        match args.command {
+            Command::Results(ResultsArgs {
+                action:
+                    Some(ResultsAction::Annotate {
+                        run_id, test, note, ..
+                    }),
+                ..
+            }) => {
+                assert_eq!(run_id, "20250101_120000_0042");
+                assert_eq!(test, Some(8));
+                assert_eq!(note, "Kong needs plugin install");
+            }
+            _ => panic!("Expected results annotate"),
+        }
+
+        let args = Args::parse_from(["gateway-poc", "results", "--summary"]);
+        assert!(matches!(
+            args.command,
+            Command::Results(ResultsArgs { action: None, .. })
+        ));
+    }
+
+    #[test]
+    fn test_retry_args() {
+        let args = Args::parse_from(["gateway-poc", "test", "--retries", "2"]);
+        match args.command {
+            Command::Test(test_args) => {
+                assert_eq!(test_args.retries, 2);
+                assert_eq!(test_args.retry_delay, 1000);
+            }
+            _ => panic!("Expected Test command"),
+        }
+
+        assert!(Args::try_parse_from(["gateway-poc", "test", "--retry-delay", "500"]).is_err());
+    }
+
+    #[test]
+    fn test_confidence_args() {
+        let args = Args::parse_from(["gateway-poc", "test", "--confidence", "95%"]);
+        match args.command {
+            Command::Test(test_args) => assert_eq!(test_args.confidence, 0.95),
+            _ => panic!("Expected Test command"),
+        }
+
+        assert!(Args::try_parse_from(["gateway-poc", "test", "--confidence", "0.1"]).is_err());
+    }
+
+    #[test]
+    fn test_failover_fault_args() {
+        let args = Args::parse_from(["gateway-poc", "test", "--failover-fault", "scale-to-zero"]);
+        match args.command {
+            Command::Test(test_args) => {
+                assert_eq!(test_args.failover_fault, BackendFault::ScaleToZero)
+            }
+            _ => panic!("Expected Test command"),
+        }
+
+        let args = Args::parse_from(["gateway-poc", "test"]);
+        match args.command {
+            Command::Test(test_args) => {
+                assert_eq!(test_args.failover_fault, BackendFault::None)
+            }
+            _ => panic!("Expected Test command"),
+        }
+        assert!(Args::try_parse_from(["gateway-poc", "test", "--failover-fault", "kill"]).is_err());
+    }
+
+    #[test]
+    fn test_chaos_run_args() {
+        let args = Args::parse_from([
+            "gateway-poc",
+            "chaos",
+            "run",
+            "--gateway",
+            "envoy",
+            "--fault",
+            "gw-restart",
+            "--during-load",
+            "--rps",
+            "200",
+            "--duration",
+            "5m",
+        ]);
+        match args.command {
+            Command::Chaos(ChaosArgs {
+                action:
+                    ChaosAction::Run {
+                        fault,
+                        during_load,
+                        rps,
+                        duration,
+                        fault_at,
+                        ..
+                    },
+            }) => {
+                assert_eq!(fault, ChaosFault::GwRestart);
+                assert!(during_load);
+                assert_eq!(rps, 200);
+                assert_eq!(duration, std::time::Duration::from_secs(300));
+                assert_eq!(fault_at, std::time::Duration::from_secs(30));
+            }
+            _ => panic!("Expected Chaos Run command"),
+        }
+
+        // The load shape only applies to background load
+        assert!(Args::try_parse_from([
+            "gateway-poc",
+            "chaos",
+            "run",
+            "--fault",
+            "pod-kill",
+            "--rps",
+            "200"
+        ])
+        .is_err());
+        assert!(
+            Args::try_parse_from(["gateway-poc", "chaos", "run", "--fault", "reboot"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_duration_args() {
+        let args = Args::parse_from(["gateway-poc", "test", "--duration", "6h"]);
+        match args.command {
+            Command::Test(test_args) => {
+                assert_eq!(
+                    test_args.duration,
+                    Some(std::time::Duration::from_secs(6 * 3600))
+                );
+                assert_eq!(test_args.checkpoint_interval, 10);
+            }
+            _ => panic!("Expected Test command"),
+        }
+
+        assert!(Args::try_parse_from(["gateway-poc", "test", "--duration", "6"]).is_err());
+        assert!(
+            Args::try_parse_from(["gateway-poc", "test", "--duration", "1h", "--rounds", "5"])
+                .is_err()
+        );
+        assert!(
+            Args::try_parse_from(["gateway-poc", "test", "--checkpoint-interval", "5"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_fail_on_args() {
+        let args = Args::parse_from([
+            "gateway-poc",
+            "test",
+            "--fail-on",
+            "pass-rate<100%",
+            "--fail-on",
+            "failed>0",
+        ]);
+        match args.command {
+            Command::Test(test_args) => assert_eq!(test_args.fail_on.len(), 2),
+            _ => panic!("Expected Test command"),
+        }
+
+        let args = Args::parse_from([
+            "gateway-poc",
+            "benchmark",
+            "run",
+            "--ip",
+            "10.0.0.1",
+            "--fail-on",
+            "p99>100ms",
+        ]);
+        match args.command {
+            Command::Benchmark(BenchmarkArgs {
+                action: BenchmarkAction::Run { fail_on, .. },
+            }) => assert_eq!(fail_on[0].threshold, 100.0),
+            _ => panic!("Expected Run action"),
+        }
+
+        assert!(Args::try_parse_from(["gateway-poc", "test", "--fail-on", "p99~1"]).is_err());
+    }
+
+    #[test]
+    fn test_benchmark_compare_preset() {
+        let args = Args::parse_from([
+            "gateway-poc",
+            "benchmark",
+            "compare",
+            "--ip",
+            "10.0.0.1",
+            "--preset",
+            "api-gateway",
+            "--apply-routes",
+        ]);
+        match args.command {
+            Command::Benchmark(BenchmarkArgs {
+                action:
+                    BenchmarkAction::Compare {
+                        preset,
+                        apply_routes,
+                        route_namespace,
+                        rps,
+                        ..
+                    },
+            }) => {
+                assert_eq!(preset.as_deref(), Some("api-gateway"));
+                assert!(apply_routes);
+                assert_eq!(route_namespace, None);
+                assert_eq!(rps, None);
+            }
+            _ => panic!("Expected Compare action"),
+        }
+
+        // Routes can only be applied for a preset
+        assert!(Args::try_parse_from([
+            "gateway-poc",
+            "benchmark",
+            "compare",
+            "--ip",
+            "10.0.0.1",
+            "--apply-routes",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_benchmark_compare_modes() {
+        let args = Args::parse_from([
+            "gateway-poc",
+            "benchmark",
+            "compare",
+            "--ip",
+            "10.0.0.1",
+            "--interleave",
+            "3",
+            "--window",
+            "5",
+        ]);
+        match args.command {
+            Command::Benchmark(BenchmarkArgs {
+                action:
+                    BenchmarkAction::Compare {
+                        interleave,
+                        window,
+                        gap,
+                        parallel,
+                        ..
+                    },
+            }) => {
+                assert_eq!((interleave, window, gap), (Some(3), Some(5), 10));
+                assert!(!parallel);
+            }
+            _ => panic!("Expected Benchmark Compare command"),
+        }
+
+        let compare = |extra: &[&str]| {
+            let mut argv = vec!["gateway-poc", "benchmark", "compare", "--ip", "10.0.0.1"];
+            argv.extend_from_slice(extra);
+            Args::try_parse_from(argv)
+        };
+        assert!(compare(&["--parallel"]).is_ok());
+        assert!(compare(&["--parallel", "--interleave", "2"]).is_err());
+        assert!(compare(&["--window", "5"]).is_err());
+
+        let watch = [
+            "--resources",
+            "--watch-crashes",
+            "--gateway-namespace",
+            "gw",
+        ];
+        assert!(compare(&watch).is_ok());
+        assert!(compare(&["--gateway-namespace", "gw"]).is_err());
+    }
+
+    #[test]
+    fn test_benchmark_capacity() {
+        let args = Args::parse_from([
+            "gateway-poc",
+            "benchmark",
+            "capacity",
+            "--ip",
+            "10.0.0.1",
+            "--slo-p99",
+            "20ms",
+            "--gateways",
+            "nginx,envoy",
+        ]);
+        match args.command {
+            Command::Benchmark(BenchmarkArgs {
+                action:
+                    BenchmarkAction::Capacity {
+                        slo_p99,
+                        gateways,
+                        max_rps,
+                        ..
+                    },
+            }) => {
+                assert_eq!(slo_p99, "20ms");
+                assert_eq!(gateways, "nginx,envoy");
+                assert_eq!(max_rps, 10000);
+            }
+            _ => panic!("Expected Benchmark Capacity command"),
+        }
+    }
+
+    #[test]
+    fn test_benchmark_run_distributed() {
+        let args = Args::parse_from([
+            "gateway-poc",
+            "benchmark",
+            "run",
+            "--ip",
+            "10.0.0.1",
+            "--distributed",
+            "--workers",
+            "8",
+        ]);
+        match args.command {
+            Command::Benchmark(bench_args) => match bench_args.action {
+                BenchmarkAction::Run {
+                    distributed,
+                    workers,
+                    worker_vms,
+                    ..
+                } => {
+                    assert!(distributed);
+                    assert_eq!(workers, 8);
+                    assert!(worker_vms.is_none());
+                }
+                _ => panic!("Expected Run action"),
+            },
+            _ => panic!("Expected Benchmark command"),
+        }
+    }
 }
@@ -4,10 +4,14 @@
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 use super::profile::{GatewayProfile, TestProfile};
+use super::secrets::SecretsConfig;
+use super::vars::VarResolver;
 use super::{AppConfig, KubeVirtConfig};
+use crate::models::{registry, GatewayDefinition, GatewayImpl, TestCase};
 
 /// Configuration file locations (in order of precedence)
 const CONFIG_LOCATIONS: &[&str] = &[
@@ -41,6 +45,27 @@ pub struct ConfigFile {
     /// Environment-specific overrides
     #[serde(default)]
     pub environments: Vec<EnvironmentConfig>,
+
+    /// User-defined gateway implementations
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub gateways: Vec<GatewayDefinition>,
+
+    /// Where each implementation is reached, by gateway name; used when a
+    /// command names the gateway without `--ip`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub connections: BTreeMap<String, GatewayConnection>,
+
+    /// References to registry credentials and image pull Secrets
+    #[serde(default, skip_serializing_if = "SecretsConfig::is_empty")]
+    pub secrets: SecretsConfig,
+}
+
+/// Just the gateway definitions of a config file, registered before the
+/// rest is parsed so profiles can refer to them
+#[derive(Deserialize)]
+struct GatewaysSection {
+    #[serde(default)]
+    gateways: Vec<GatewayDefinition>,
 }
 
 fn default_version() -> String {
@@ -55,6 +80,9 @@ impl Default for ConfigFile {
             gateway_profiles: Vec::new(),
             test_profiles: Vec::new(),
             environments: Vec::new(),
+            gateways: Vec::new(),
+            connections: BTreeMap::new(),
+            secrets: SecretsConfig::default(),
         }
     }
 }
@@ -65,8 +93,11 @@ impl ConfigFile {
         Self::default()
     }
 
-    /// Find configuration file in standard locations
+    /// Find configuration file: GATEWAY_POC_CONFIG, else the standard locations
     pub fn find() -> Option<PathBuf> {
+        if let Ok(path) = std::env::var(super::env::config_var()) {
+            return Some(expand_path(&path));
+        }
         for location in CONFIG_LOCATIONS {
             let path = expand_path(location);
             if path.exists() {
@@ -78,8 +109,13 @@ impl ConfigFile {
 
     /// Load configuration from default location
     pub fn load_default() -> Result<Self> {
+        Self::load_default_with(&VarResolver::from_env())
+    }
+
+    /// Load configuration from default location, binding `${...}` variables
+    pub fn load_default_with(vars: &VarResolver) -> Result<Self> {
         if let Some(path) = Self::find() {
-            Self::load(&path)
+            Self::load_with(&path, vars)
         } else {
             Ok(Self::default())
         }
@@ -87,22 +123,53 @@ impl ConfigFile {
 
     /// Load configuration from file
     pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        Self::load_with(path, &VarResolver::from_env())
+    }
+
+    /// Load configuration from file, binding `${...}` variables
+    pub fn load_with(path: impl AsRef<Path>, vars: &VarResolver) -> Result<Self> {
         let path = path.as_ref();
         let content = std::fs::read_to_string(path)
             .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+        Self::register_gateways_from(path, &content)?;
 
-        let config: Self = if is_yaml_file(path) {
+        let document: serde_yaml::Value = if is_yaml_file(path) {
             serde_yaml::from_str(&content)
                 .with_context(|| format!("Failed to parse YAML config: {}", path.display()))?
         } else {
             serde_json::from_str(&content)
                 .with_context(|| format!("Failed to parse JSON config: {}", path.display()))?
         };
+        let document = vars
+            .resolve_value(document)
+            .with_context(|| format!("Failed to substitute variables in {}", path.display()))?;
+        let config: Self = serde_yaml::from_value(document)
+            .with_context(|| format!("Invalid config file: {}", path.display()))?;
 
         config.validate()?;
         Ok(config)
     }
 
+    /// Register the user-defined gateways of a config file
+    pub fn register_gateways(path: impl AsRef<Path>) -> Result<Vec<GatewayImpl>> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+        Self::register_gateways_from(path, &content)
+    }
+
+    fn register_gateways_from(path: &Path, content: &str) -> Result<Vec<GatewayImpl>> {
+        let section: GatewaysSection = if is_yaml_file(path) {
+            serde_yaml::from_str(content)
+                .with_context(|| format!("Failed to parse YAML config: {}", path.display()))?
+        } else {
+            serde_json::from_str(content)
+                .with_context(|| format!("Failed to parse JSON config: {}", path.display()))?
+        };
+        registry::register_all(&section.gateways)
+            .with_context(|| format!("Invalid gateway definition in {}", path.display()))
+    }
+
     /// Save configuration to file
     pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
         let path = path.as_ref();
@@ -124,6 +191,27 @@ impl ConfigFile {
         Ok(())
     }
 
+    /// Value at a dot-path key (`app.timeout_secs`, `test_profiles.smoke.rounds`)
+    pub fn get_key(&self, key: &str) -> Result<serde_yaml::Value> {
+        let document = serde_yaml::to_value(self).context("Failed to serialize config")?;
+        super::keys::get(&document, key).cloned()
+    }
+
+    /// Set a dot-path key from its command-line form, checked against the
+    /// type of the setting
+    pub fn set_key(&mut self, key: &str, value: &str) -> Result<()> {
+        let mut document = serde_yaml::to_value(&*self).context("Failed to serialize config")?;
+        super::keys::set(&mut document, key, value)?;
+        let updated: ConfigFile = serde_yaml::from_value(document)
+            .with_context(|| format!("Invalid value for {key}: {value}"))?;
+        // Keys the config does not have are dropped on the way back
+        let document = serde_yaml::to_value(&updated).context("Failed to serialize config")?;
+        super::keys::get(&document, key)?;
+        updated.validate()?;
+        *self = updated;
+        Ok(())
+    }
+
     /// Validate configuration
     pub fn validate(&self) -> Result<()> {
         // Validate version
@@ -131,17 +219,44 @@ impl ConfigFile {
             anyhow::bail!("Unsupported config version: {}", self.version);
         }
 
+        let kube_api = &self.app.kube_api;
+        if !kube_api.qps.is_finite() || kube_api.qps < 0.0 {
+            anyhow::bail!(
+                "Invalid kube_api.qps {}: must be 0 (unlimited) or more",
+                kube_api.qps
+            );
+        }
+        if kube_api.qps > 0.0 && kube_api.burst == 0 {
+            anyhow::bail!("Invalid kube_api.burst 0: must be at least 1");
+        }
+
+        self.secrets.validate()?;
+        if let Some(key) = &self.app.kubevirt.ssh_key {
+            key.validate().context("Invalid app.kubevirt.ssh_key")?;
+        }
+
+        for name in self.connections.keys() {
+            if GatewayImpl::from_str(name).is_none() {
+                anyhow::bail!("Unknown gateway '{name}' in connections");
+            }
+        }
+
         // Validate test profiles
         for profile in &self.test_profiles {
             for test_num in &profile.tests {
-                if *test_num < 1 || *test_num > 17 {
+                if TestCase::from_number(*test_num).is_none() {
                     anyhow::bail!(
-                        "Invalid test number {} in profile '{}'. Valid range: 1-17",
+                        "Invalid test number {} in profile '{}'. Valid range: 1-{}",
                         test_num,
-                        profile.name
+                        profile.name,
+                        TestCase::all().len()
                     );
                 }
             }
+            profile
+                .round_plan()
+                .validate()
+                .with_context(|| format!("Invalid round variants in profile '{}'", profile.name))?;
         }
 
         Ok(())
@@ -160,6 +275,8 @@ impl ConfigFile {
                 parallel: true,
                 max_concurrent: 4,
                 kubevirt: KubeVirtConfig::default(),
+                namespace: None,
+                kube_api: Default::default(),
             },
             gateway_profiles: vec![
                 GatewayProfile::default_for(GatewayImpl::Nginx),
@@ -186,9 +303,28 @@ impl ConfigFile {
                     extra: std::collections::HashMap::new(),
                 },
             ],
+            gateways: Vec::new(),
+            connections: BTreeMap::from([(
+                "nginx".to_string(),
+                GatewayConnection {
+                    ip: Some("192.168.1.100".to_string()),
+                    hostname: Some("nginx.example.com".to_string()),
+                    namespace: Some("nginx-gateway".to_string()),
+                    ..Default::default()
+                },
+            )]),
+            secrets: SecretsConfig::default(),
         }
     }
 
+    /// Connection settings of `implementation` (keyed by any of its names)
+    pub fn connection(&self, implementation: GatewayImpl) -> Option<&GatewayConnection> {
+        self.connections
+            .iter()
+            .find(|(name, _)| GatewayImpl::from_str(name) == Some(implementation))
+            .map(|(_, connection)| connection)
+    }
+
     /// Get environment by name
     pub fn environment(&self, name: &str) -> Option<&EnvironmentConfig> {
         self.environments.iter().find(|e| e.name == name)
@@ -222,6 +358,9 @@ impl ConfigFile {
         if other.app.max_concurrent != 4 {
             self.app.max_concurrent = other.app.max_concurrent;
         }
+        if !other.app.kube_api.is_default() {
+            self.app.kube_api = other.app.kube_api;
+        }
 
         // Add profiles from other
         for profile in other.gateway_profiles {
@@ -239,6 +378,23 @@ impl ConfigFile {
                 self.environments.push(env);
             }
         }
+        for gateway in other.gateways {
+            if !self.gateways.iter().any(|g| g.name == gateway.name) {
+                self.gateways.push(gateway);
+            }
+        }
+        self.connections.extend(other.connections);
+        for credential in other.secrets.registries {
+            self.secrets
+                .registries
+                .retain(|c| c.registry != credential.registry);
+            self.secrets.registries.push(credential);
+        }
+        for name in other.secrets.image_pull_secrets {
+            if !self.secrets.image_pull_secrets.contains(&name) {
+                self.secrets.image_pull_secrets.push(name);
+            }
+        }
     }
 }
 
@@ -284,8 +440,27 @@ impl EnvironmentConfig {
     }
 }
 
+/// Where one gateway implementation is reached; unset fields fall back to
+/// the command's defaults
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct GatewayConnection {
+    /// Gateway IP address
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ip: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_port: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub https_port: Option<u16>,
+    /// Hostname for the Host header
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<String>,
+    /// Namespace of the gateway pods
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+}
+
 /// Expand ~ to home directory
-fn expand_path(path: &str) -> PathBuf {
+pub(super) fn expand_path(path: &str) -> PathBuf {
     if let Some(stripped) = path.strip_prefix("~/") {
         if let Some(home) = dirs::home_dir() {
             return home.join(stripped);
@@ -357,6 +532,51 @@ mod tests {
         assert!(!config.environments.is_empty());
     }
 
+    #[test]
+    fn test_set_key() {
+        let mut config = ConfigFile::example();
+        config.set_key("kubevirt.default_cpu", "8").unwrap();
+        assert_eq!(config.app.kubevirt.default_cpu, 8);
+        // Optional settings that are not set yet
+        config.set_key("app.namespace", "gateway-poc").unwrap();
+        assert_eq!(config.app.namespace.as_deref(), Some("gateway-poc"));
+        assert_eq!(
+            config.get_key("app.namespace").unwrap(),
+            serde_yaml::Value::from("gateway-poc")
+        );
+
+        let err = config.set_key("app.rounds", "3").unwrap_err().to_string();
+        assert!(err.contains("valid keys: default_gateway"), "{err}");
+        // Fits the YAML integer but not the u32 field
+        assert!(config.set_key("app.default_rounds", "-1").is_err());
+        assert_eq!(
+            config.app.default_rounds,
+            ConfigFile::example().app.default_rounds
+        );
+    }
+
+    #[test]
+    fn test_gateway_connections() {
+        let config: ConfigFile = serde_yaml::from_str(
+            "connections:\n  envoy:\n    ip: 10.0.0.5\n    https_port: 8443\n",
+        )
+        .unwrap();
+        let connection = config.connection(GatewayImpl::Envoy).unwrap();
+        assert_eq!(connection.ip.as_deref(), Some("10.0.0.5"));
+        assert_eq!(connection.https_port, Some(8443));
+        assert!(connection.http_port.is_none());
+        assert!(config.connection(GatewayImpl::Nginx).is_none());
+        config.validate().unwrap();
+
+        let mut config = config;
+        config.set_key("connections.nginx.ip", "10.0.0.6").unwrap();
+        assert_eq!(
+            config.connection(GatewayImpl::Nginx).unwrap().ip.as_deref(),
+            Some("10.0.0.6")
+        );
+        assert!(config.set_key("connections.nope.ip", "10.0.0.7").is_err());
+    }
+
     #[test]
     fn test_config_file_save_load() {
         let dir = tempdir().unwrap();
@@ -370,6 +590,50 @@ mod tests {
         assert_eq!(loaded.app.default_gateway, config.app.default_gateway);
     }
 
+    #[test]
+    fn test_profile_variables() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.yaml");
+        std::fs::write(
+            &path,
+            r#"
+test_profiles:
+  - name: nightly
+    description: "Nightly run ${RUN_ID} against ${GATEWAY_IP}"
+    tests: [1, 2]
+    rounds: ${GATEWAY_POC_UNSET_ROUNDS:-5}
+    parallel: false
+    timeout_secs: 30
+    tags: []
+"#,
+        )
+        .unwrap();
+
+        // Run-time values stay as placeholders until bound
+        let early = ConfigFile::load(&path).unwrap();
+        let profile = early.test_profile("nightly").unwrap();
+        assert_eq!(profile.rounds, 5);
+        assert!(profile.description.contains("${GATEWAY_IP}"));
+
+        let vars = VarResolver::from_env()
+            .with_var("RUN_ID", "r1")
+            .with_var("GATEWAY_IP", "10.0.0.9");
+        let bound = ConfigFile::load_with(&path, &vars).unwrap();
+        assert_eq!(
+            bound.test_profile("nightly").unwrap().description,
+            "Nightly run r1 against 10.0.0.9"
+        );
+
+        std::fs::write(
+            &path,
+            "test_profiles:\n  - name: ${GATEWAY_POC_UNSET_NAME}\n",
+        )
+        .unwrap();
+        let err = format!("{:#}", ConfigFile::load(&path).unwrap_err());
+        assert!(err.contains("${GATEWAY_POC_UNSET_NAME}"), "{err}");
+        assert!(err.contains("test_profiles[0].name"), "{err}");
+    }
+
     #[test]
     fn test_environment_config() {
         let env = EnvironmentConfig::new("test", "10.0.0.1")
@@ -392,9 +656,61 @@ mod tests {
             parallel: false,
             timeout_secs: 30,
             tags: Vec::new(),
+            load_test: None,
+            round_variants: Vec::new(),
+            concurrency: None,
+            http_port: None,
+            https_port: None,
+            hostname: None,
         });
 
         assert!(config.validate().is_err());
+
+        let mut config = ConfigFile::default();
+        config.app.kube_api.burst = 0;
+        assert!(config.validate().is_err());
+        config.app.kube_api.qps = 0.0;
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_custom_gateway_profile() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("config.yaml");
+        std::fs::write(
+            &path,
+            r#"
+gateways:
+  - name: haproxy-file-test
+    gateway_class: haproxy
+    helm:
+      repo: https://haproxytech.github.io/helm-charts
+      chart: kubernetes-ingress
+    pod_selector: app.kubernetes.io/name=kubernetes-ingress
+gateway_profiles:
+  - name: haproxy
+    gateway: haproxy-file-test
+    namespace: haproxy
+    http_port: 80
+    https_port: 443
+    grpc_port: null
+    hostname: example.com
+    install_method: helm
+    helm: null
+    labels: {}
+    annotations: {}
+"#,
+        )
+        .unwrap();
+
+        let config = ConfigFile::load(&path).unwrap();
+        let gateway = config.gateway_profile("haproxy").unwrap().gateway;
+        assert_eq!(gateway.gateway_class(), "haproxy");
+        assert_eq!(config.gateways.len(), 1);
+
+        let profile = GatewayProfile::default_for(gateway);
+        assert_eq!(profile.name, "haproxy-file-test-default");
+        assert_eq!(profile.helm.unwrap().chart, "kubernetes-ingress");
     }
 
     #[test]
@@ -0,0 +1,282 @@
+//! Dot-path access to configuration values
+//!
+//! Keys walk the serialized config: mapping keys by name, list entries by
+//! their `name` field or index (`test_profiles.smoke.rounds`,
+//! `gateway_profiles.0.https_port`). Keys of the `app` section may drop the
+//! `app.` prefix (`kubevirt.default_cpu`).
+
+use anyhow::{Context, Result};
+use serde_yaml::Value;
+
+/// One step of a resolved key
+#[derive(Clone, Debug, PartialEq)]
+enum Step {
+    Key(String),
+    Index(usize),
+}
+
+/// Value at `key`
+pub fn get<'a>(document: &'a Value, key: &str) -> Result<&'a Value> {
+    let (steps, missing) = locate(document, key)?;
+    let value = walk(document, &steps);
+    match missing.first() {
+        Some(segment) => Err(unknown_key(value, segment, key, &steps)),
+        None => Ok(value),
+    }
+}
+
+/// Set `key` to `raw`, parsed to the type of the value it replaces
+///
+/// Keys that are not set yet (optional settings, map entries) take `raw` as
+/// a YAML scalar, with any missing sections on the way created; whether the
+/// key exists at all is for the caller to check by deserializing the result.
+pub fn set(document: &mut Value, key: &str, raw: &str) -> Result<()> {
+    let (steps, missing) = locate(document, key)?;
+    let mut slot = walk_mut(document, &steps);
+    for (i, segment) in missing.iter().enumerate() {
+        let Value::Mapping(mapping) = slot else {
+            unreachable!("locate stops at a mapping");
+        };
+        let empty = if i + 1 == missing.len() {
+            Value::Null
+        } else {
+            Value::Mapping(Default::default())
+        };
+        slot = mapping
+            .entry(Value::String(segment.clone()))
+            .or_insert(empty);
+    }
+    *slot = typed_value(slot, raw, key)?;
+    Ok(())
+}
+
+/// Steps along `key` as far as the document has it, and the segments past
+/// the first key missing from a mapping
+fn locate(document: &Value, key: &str) -> Result<(Vec<Step>, Vec<String>)> {
+    let mut segments: Vec<&str> = key.split('.').collect();
+    if segments.iter().any(|s| s.is_empty()) {
+        anyhow::bail!("Invalid configuration key '{key}'");
+    }
+    let in_app = document
+        .get("app")
+        .is_some_and(|app| app.get(segments[0]).is_some());
+    if document.get(segments[0]).is_none() && in_app {
+        segments.insert(0, "app");
+    }
+
+    let mut steps = Vec::new();
+    let mut current = document;
+    for (i, segment) in segments.iter().enumerate() {
+        match current {
+            Value::Mapping(_) => {
+                let Some(value) = current.get(*segment) else {
+                    let missing = segments[i..].iter().map(|s| s.to_string()).collect();
+                    return Ok((steps, missing));
+                };
+                current = value;
+                steps.push(Step::Key(segment.to_string()));
+            }
+            Value::Sequence(entries) => {
+                let index = entry_index(entries, segment).ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "No entry '{segment}' in {} (entries: {})",
+                        display_path(&steps),
+                        entry_names(entries)
+                    )
+                })?;
+                current = &entries[index];
+                steps.push(Step::Index(index));
+            }
+            _ => anyhow::bail!(
+                "{} is a single value; it has no key '{segment}'",
+                display_path(&steps)
+            ),
+        }
+    }
+    Ok((steps, Vec::new()))
+}
+
+fn walk<'a>(document: &'a Value, steps: &[Step]) -> &'a Value {
+    steps.iter().fold(document, |value, step| match step {
+        Step::Key(key) => &value[key.as_str()],
+        Step::Index(index) => &value[*index],
+    })
+}
+
+fn walk_mut<'a>(document: &'a mut Value, steps: &[Step]) -> &'a mut Value {
+    steps.iter().fold(document, |value, step| match step {
+        Step::Key(key) => &mut value[key.as_str()],
+        Step::Index(index) => &mut value[*index],
+    })
+}
+
+/// Entry of a list by `name` field, else by index
+fn entry_index(entries: &[Value], segment: &str) -> Option<usize> {
+    entries
+        .iter()
+        .position(|e| e.get("name").and_then(Value::as_str) == Some(segment))
+        .or_else(|| segment.parse().ok().filter(|i| *i < entries.len()))
+}
+
+fn entry_names(entries: &[Value]) -> String {
+    if entries.is_empty() {
+        return "none".to_string();
+    }
+    entries
+        .iter()
+        .enumerate()
+        .map(|(i, e)| match e.get("name").and_then(Value::as_str) {
+            Some(name) => name.to_string(),
+            None => i.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn unknown_key(parent: &Value, segment: &str, key: &str, steps: &[Step]) -> anyhow::Error {
+    let valid = match parent {
+        Value::Mapping(mapping) => mapping
+            .keys()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(", "),
+        _ => String::new(),
+    };
+    anyhow::anyhow!(
+        "Unknown configuration key '{key}': {} has no '{segment}' (valid keys: {valid})",
+        display_path(steps)
+    )
+}
+
+fn display_path(steps: &[Step]) -> String {
+    if steps.is_empty() {
+        return "the config file".to_string();
+    }
+    steps
+        .iter()
+        .map(|step| match step {
+            Step::Key(key) => key.clone(),
+            Step::Index(index) => index.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// `raw` as the type of `current`
+fn typed_value(current: &Value, raw: &str, key: &str) -> Result<Value> {
+    let parsed = || -> Result<Value> {
+        serde_yaml::from_str(raw).with_context(|| format!("Invalid value for {key}: {raw}"))
+    };
+    let value = match current {
+        Value::String(_) => Value::String(raw.to_string()),
+        Value::Bool(_) => Value::Bool(
+            raw.parse()
+                .map_err(|_| anyhow::anyhow!("{key} expects true or false, got '{raw}'"))?,
+        ),
+        Value::Number(number) => {
+            let value = parsed()?;
+            let integer = value.as_i64().is_some() || value.as_u64().is_some();
+            if !value.is_number() || (!number.is_f64() && !integer) {
+                let kind = if number.is_f64() {
+                    "a number"
+                } else {
+                    "an integer"
+                };
+                anyhow::bail!("{key} expects {kind}, got '{raw}'");
+            }
+            value
+        }
+        Value::Sequence(_) => {
+            let value = parsed()?;
+            if !value.is_sequence() {
+                anyhow::bail!("{key} expects a list such as [a, b], got '{raw}'");
+            }
+            value
+        }
+        Value::Mapping(mapping) => {
+            let keys = mapping
+                .keys()
+                .filter_map(Value::as_str)
+                .collect::<Vec<_>>()
+                .join(", ");
+            anyhow::bail!("{key} is a section; set one of its keys ({keys})");
+        }
+        Value::Null | Value::Tagged(_) => parsed()?,
+    };
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document() -> Value {
+        serde_yaml::from_str(
+            "app:\n  parallel: false\n  default_rounds: 1\n  kubevirt:\n    default_cpu: 2\n\
+             test_profiles:\n  - name: smoke\n    tests: [1, 2]\n    description: quick\n",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_get_paths() {
+        let document = document();
+        assert_eq!(
+            get(&document, "app.default_rounds").unwrap(),
+            &Value::from(1)
+        );
+        // The app section may be left out
+        assert_eq!(
+            get(&document, "kubevirt.default_cpu").unwrap(),
+            &Value::from(2)
+        );
+        assert_eq!(
+            get(&document, "test_profiles.smoke.description").unwrap(),
+            &Value::from("quick")
+        );
+        assert_eq!(
+            get(&document, "test_profiles.0.description").unwrap(),
+            &Value::from("quick")
+        );
+
+        let err = get(&document, "app.rounds").unwrap_err().to_string();
+        assert!(
+            err.contains("valid keys: parallel, default_rounds, kubevirt"),
+            "{err}"
+        );
+        let err = get(&document, "test_profiles.nightly")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("entries: smoke"), "{err}");
+    }
+
+    #[test]
+    fn test_set_typed() {
+        let mut document = document();
+        set(&mut document, "kubevirt.default_cpu", "8").unwrap();
+        assert_eq!(document["app"]["kubevirt"]["default_cpu"], Value::from(8));
+        set(&mut document, "app.parallel", "true").unwrap();
+        assert_eq!(document["app"]["parallel"], Value::from(true));
+        set(&mut document, "connections.envoy.https_port", "8443").unwrap();
+        assert_eq!(
+            document["connections"]["envoy"]["https_port"],
+            Value::from(8443)
+        );
+        set(&mut document, "test_profiles.smoke.tests", "[3, 4]").unwrap();
+        assert_eq!(
+            get(&document, "test_profiles.smoke.tests.1").unwrap(),
+            &Value::from(4)
+        );
+        // Strings stay strings even when they look like numbers
+        set(&mut document, "test_profiles.smoke.description", "8").unwrap();
+        assert_eq!(
+            get(&document, "test_profiles.smoke.description").unwrap(),
+            &Value::from("8")
+        );
+
+        assert!(set(&mut document, "app.parallel", "yes").is_err());
+        assert!(set(&mut document, "app.default_rounds", "1.5").is_err());
+        assert!(set(&mut document, "app.kubevirt", "4").is_err());
+        assert!(set(&mut document, "app.parallel.x", "4").is_err());
+    }
+}
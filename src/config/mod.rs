@@ -7,15 +7,25 @@
 
 pub mod env;
 pub mod file;
+pub mod keys;
+pub mod preset;
 pub mod profile;
+pub mod resolve;
+pub mod secrets;
+pub mod vars;
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
-pub use env::{EnvBuilder, EnvConfig};
-pub use file::{ConfigFile, ConfigWatcher, EnvironmentConfig};
+pub use file::{ConfigFile, ConfigWatcher, EnvironmentConfig, GatewayConnection};
+pub use preset::{PresetRoute, ScenarioPreset};
 pub use profile::{GatewayProfile, InstallMethod, ProfileManager, TestProfile};
+pub use resolve::{ResolvedConfig, SettingSource};
+pub use secrets::{RegistryCredential, SecretRef, SecretsConfig};
+pub use vars::VarResolver;
+
+use crate::k8s::ApiRateLimit;
 
 /// Application configuration
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -37,6 +47,14 @@ pub struct AppConfig {
 
     /// KubeVirt configuration
     pub kubevirt: KubeVirtConfig,
+
+    /// Namespace for routes, test pods and VMs (overridden by `--namespace`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+
+    /// Client-side QPS/burst limit for the tool's own Kubernetes API requests
+    #[serde(default, skip_serializing_if = "ApiRateLimit::is_default")]
+    pub kube_api: ApiRateLimit,
 }
 
 impl Default for AppConfig {
@@ -48,6 +66,8 @@ impl Default for AppConfig {
             parallel: false,
             max_concurrent: 4,
             kubevirt: KubeVirtConfig::default(),
+            namespace: None,
+            kube_api: ApiRateLimit::default(),
         }
     }
 }
@@ -110,6 +130,11 @@ pub struct KubeVirtConfig {
 
     /// SSH key path
     pub ssh_key_path: Option<String>,
+
+    /// SSH private key from an environment variable or Kubernetes Secret
+    /// (instead of `ssh_key_path`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ssh_key: Option<SecretRef>,
 }
 
 impl Default for KubeVirtConfig {
@@ -121,6 +146,7 @@ impl Default for KubeVirtConfig {
             default_disk: 50,
             image_url: "docker.io/kubevirt/fedora-cloud-container-disk-demo:latest".to_string(),
             ssh_key_path: None,
+            ssh_key: None,
         }
     }
 }
@@ -0,0 +1,326 @@
+//! Scenario presets
+//!
+//! Curated, realistic scenarios (routes, filters, policies and a benchmark
+//! traffic mix) that can be run identically against every gateway.
+
+use serde::{Deserialize, Serialize};
+
+use crate::benchmark::{BenchmarkConfig, BenchmarkResult, LoadPattern, TrafficMix, TrafficTarget};
+use crate::k8s::{HTTPRoute, HTTPRouteBuilder, RuleBuilder, GATEWAY_LABEL};
+use crate::models::{GatewayConfig, GatewayImpl};
+
+/// A single HTTPRoute rule in a preset
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PresetRoute {
+    /// Path prefix to match
+    pub path: String,
+    /// Backend service name
+    pub backend: String,
+    /// Backend service port
+    pub port: u16,
+    /// Optional header match (name, value)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub header: Option<(String, String)>,
+    /// Canary backend and its weight percentage
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub canary: Option<(String, i32)>,
+    /// Full-path rewrite filter
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rewrite: Option<String>,
+    /// Request timeout policy (e.g. "5s")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<String>,
+}
+
+impl PresetRoute {
+    /// Route a path prefix to a backend
+    pub fn new(path: impl Into<String>, backend: impl Into<String>, port: u16) -> Self {
+        Self {
+            path: path.into(),
+            backend: backend.into(),
+            port,
+            header: None,
+            canary: None,
+            rewrite: None,
+            timeout: None,
+        }
+    }
+
+    /// Require a header match
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.header = Some((name.into(), value.into()));
+        self
+    }
+
+    /// Split a percentage of traffic to a canary backend
+    pub fn with_canary(mut self, backend: impl Into<String>, weight: i32) -> Self {
+        self.canary = Some((backend.into(), weight.clamp(0, 100)));
+        self
+    }
+
+    /// Rewrite the request path
+    pub fn with_rewrite(mut self, path: impl Into<String>) -> Self {
+        self.rewrite = Some(path.into());
+        self
+    }
+
+    /// Set request timeout
+    pub fn with_timeout(mut self, timeout: impl Into<String>) -> Self {
+        self.timeout = Some(timeout.into());
+        self
+    }
+}
+
+/// Realistic scenario run the same way across implementations
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScenarioPreset {
+    /// Preset name
+    pub name: String,
+    /// Description
+    pub description: String,
+    /// Host header / route hostname
+    pub hostname: String,
+    /// HTTPRoute rules
+    pub routes: Vec<PresetRoute>,
+    /// Benchmark traffic mix
+    pub mix: TrafficMix,
+    /// Target requests per second
+    pub rps: u32,
+    /// Concurrent connections
+    pub concurrency: u32,
+    /// Suggested p99 latency SLO in milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slo_p99_ms: Option<f64>,
+}
+
+impl ScenarioPreset {
+    /// Storefront edge: page views, catalog browsing, cart and checkout
+    pub fn ecommerce_edge() -> Self {
+        Self {
+            name: "ecommerce-edge".to_string(),
+            description: "Storefront edge with catalog, cart and checkout".to_string(),
+            hostname: "shop.example.com".to_string(),
+            routes: vec![
+                PresetRoute::new("/", "storefront", 8080),
+                PresetRoute::new("/api/products", "catalog", 8080).with_timeout("2s"),
+                PresetRoute::new("/api/cart", "cart", 8080).with_timeout("5s"),
+                PresetRoute::new("/api/checkout", "checkout", 8080)
+                    .with_canary("checkout-canary", 10)
+                    .with_timeout("10s"),
+            ],
+            mix: TrafficMix::new()
+                .with_target(TrafficTarget::new("/", 50, "GET"))
+                .with_target(TrafficTarget::new("/api/products", 30, "GET"))
+                .with_target(
+                    TrafficTarget::new("/api/cart", 15, "POST")
+                        .with_body(r#"{"sku":"A-100","qty":1}"#),
+                )
+                .with_target(
+                    TrafficTarget::new("/api/checkout", 5, "POST").with_body(r#"{"cart":"c-1"}"#),
+                ),
+            rps: 500,
+            concurrency: 50,
+            slo_p99_ms: Some(100.0),
+        }
+    }
+
+    /// Versioned API with header routing, canary and legacy rewrite
+    pub fn api_gateway() -> Self {
+        Self {
+            name: "api-gateway".to_string(),
+            description: "Versioned REST API with canary and legacy rewrites".to_string(),
+            hostname: "api.example.com".to_string(),
+            routes: vec![
+                PresetRoute::new("/v1", "api-v1", 8080).with_timeout("3s"),
+                PresetRoute::new("/v2", "api-v2", 8080)
+                    .with_canary("api-v2-canary", 10)
+                    .with_timeout("3s"),
+                PresetRoute::new("/v2", "api-v2-beta", 8080).with_header("x-api-beta", "true"),
+                PresetRoute::new("/legacy", "api-v1", 8080).with_rewrite("/v1"),
+            ],
+            mix: TrafficMix::new()
+                .with_target(TrafficTarget::new("/v1/users", 40, "GET"))
+                .with_target(TrafficTarget::new("/v2/users", 35, "GET"))
+                .with_target(
+                    TrafficTarget::new("/v2/orders", 15, "POST").with_body(r#"{"item":42}"#),
+                )
+                .with_target(TrafficTarget::new("/legacy", 10, "GET")),
+            rps: 1000,
+            concurrency: 100,
+            slo_p99_ms: Some(50.0),
+        }
+    }
+
+    /// gRPC services fronted by the gateway alongside their HTTP/JSON APIs
+    pub fn grpc_microservices() -> Self {
+        Self {
+            name: "grpc-microservices".to_string(),
+            description: "gRPC services with HTTP/JSON fronts and retries".to_string(),
+            hostname: "grpc.example.com".to_string(),
+            routes: vec![
+                PresetRoute::new("/orders.OrderService", "orders-grpc", 9090).with_timeout("5s"),
+                PresetRoute::new("/inventory.InventoryService", "inventory-grpc", 9090)
+                    .with_timeout("5s"),
+                PresetRoute::new("/api/orders", "orders-http", 8080),
+                PresetRoute::new("/api/inventory", "inventory-http", 8080)
+                    .with_canary("inventory-http-canary", 20),
+            ],
+            mix: TrafficMix::new()
+                .with_target(TrafficTarget::new("/api/orders", 45, "GET"))
+                .with_target(TrafficTarget::new("/api/inventory", 40, "GET"))
+                .with_target(
+                    TrafficTarget::new("/api/orders", 15, "POST").with_body(r#"{"sku":"B-7"}"#),
+                ),
+            rps: 800,
+            concurrency: 80,
+            slo_p99_ms: Some(75.0),
+        }
+    }
+
+    /// Get predefined presets
+    pub fn predefined() -> Vec<ScenarioPreset> {
+        vec![
+            Self::ecommerce_edge(),
+            Self::api_gateway(),
+            Self::grpc_microservices(),
+        ]
+    }
+
+    /// Find preset by name
+    pub fn find(name: &str) -> Option<ScenarioPreset> {
+        Self::predefined().into_iter().find(|p| p.name == name)
+    }
+
+    /// Names of all predefined presets
+    pub fn names() -> Vec<String> {
+        Self::predefined().into_iter().map(|p| p.name).collect()
+    }
+
+    /// HTTPRoute for this preset attached to a gateway's Gateway resource
+    pub fn http_route(&self, gateway: GatewayImpl, namespace: &str) -> HTTPRoute {
+        let parent = GatewayConfig::new(gateway).name;
+        let mut builder = HTTPRouteBuilder::new(format!("preset-{}", self.name), namespace)
+            .parent_ref(parent)
+            .hostname(&self.hostname)
+            .label(GATEWAY_LABEL, gateway.short_name());
+
+        for route in &self.routes {
+            let mut rule = RuleBuilder::new().path_prefix(&route.path);
+            if let Some((name, value)) = &route.header {
+                rule = rule.header(name, value);
+            }
+            rule = match &route.canary {
+                Some((canary, weight)) => rule
+                    .backend_with_weight(&route.backend, route.port, 100 - weight)
+                    .backend_with_weight(canary, route.port, *weight),
+                None => rule.backend(&route.backend, route.port),
+            };
+            if let Some(path) = &route.rewrite {
+                rule = rule.url_rewrite(path);
+            }
+            if let Some(timeout) = &route.timeout {
+                rule = rule.timeout(timeout);
+            }
+            builder = builder.rule(rule.build());
+        }
+
+        builder.build()
+    }
+
+    /// Benchmark configuration driving the preset's traffic mix
+    pub fn benchmark_config(&self, gateway: GatewayImpl, ip: &str, port: u16) -> BenchmarkConfig {
+        let mut config = BenchmarkConfig::new(gateway, ip)
+            .with_pattern(LoadPattern::Constant { rps: self.rps })
+            .with_concurrency(self.concurrency)
+            .with_hostname(&self.hostname)
+            .with_mix(self.mix.clone());
+        config.port = port;
+        config
+    }
+
+    /// Each gateway's p99 against the preset's SLO, if it has one
+    pub fn slo_report(&self, results: &[BenchmarkResult]) -> Option<String> {
+        let slo = self.slo_p99_ms?;
+        let mut report = format!("SLO ({}): p99 <= {slo:.0}ms\n", self.name);
+        for result in results {
+            let p99 = result.metrics.latency.percentiles.p99;
+            let mark = if p99 <= slo { "✓" } else { "✗" };
+            report.push_str(&format!(
+                "  {mark} {:22} {p99:.1}ms\n",
+                result.config.gateway.name()
+            ));
+        }
+        Some(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmark::Metrics;
+
+    #[test]
+    fn test_predefined_presets() {
+        let presets = ScenarioPreset::predefined();
+        assert_eq!(presets.len(), 3);
+        for preset in &presets {
+            assert!(!preset.routes.is_empty());
+            assert!(!preset.mix.targets.is_empty());
+        }
+        assert!(ScenarioPreset::find("api-gateway").is_some());
+        assert!(ScenarioPreset::find("unknown").is_none());
+    }
+
+    #[test]
+    fn test_preset_http_route() {
+        let preset = ScenarioPreset::api_gateway();
+        let route = preset.http_route(GatewayImpl::Envoy, "default");
+
+        assert_eq!(route.metadata.name, Some("preset-api-gateway".to_string()));
+        assert_eq!(route.spec.hostnames, vec!["api.example.com"]);
+        assert_eq!(route.spec.rules.len(), preset.routes.len());
+        assert_eq!(route.spec.rules[1].backend_refs[0].weight, Some(90));
+        assert_eq!(route.spec.rules[3].filters.len(), 1);
+    }
+
+    #[test]
+    fn test_preset_benchmark_config() {
+        let preset = ScenarioPreset::ecommerce_edge();
+        let config = preset.benchmark_config(GatewayImpl::Nginx, "10.0.0.1", 8080);
+
+        assert_eq!(config.hostname, "shop.example.com");
+        assert_eq!(config.port, 8080);
+        assert_eq!(config.concurrency, 50);
+        assert_eq!(config.mix.as_ref().map(|m| m.targets.len()), Some(4));
+    }
+
+    fn result(gateway: GatewayImpl, p99: f64) -> BenchmarkResult {
+        let mut metrics = Metrics::default();
+        metrics.latency.percentiles.p99 = p99;
+        BenchmarkResult {
+            config: BenchmarkConfig::new(gateway, "10.0.0.1"),
+            metrics,
+            start_time: 0,
+            end_time: 10,
+            warmup_performed: false,
+            paused_secs: 0.0,
+            workers: Vec::new(),
+            resources: None,
+            crashes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_preset_slo_report() {
+        let preset = ScenarioPreset::api_gateway();
+        let results = [
+            result(GatewayImpl::Envoy, 20.0),
+            result(GatewayImpl::Nginx, 80.0),
+        ];
+
+        let report = preset.slo_report(&results).unwrap();
+        assert!(report.contains("p99 <= 50ms"));
+        assert!(report.contains("✓ Envoy Gateway"));
+        assert!(report.contains("✗ NGINX Gateway Fabric"));
+    }
+}
@@ -5,7 +5,9 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-use crate::models::GatewayImpl;
+use crate::models::{
+    GatewayImpl, LoadTestEngine, LoadTestSettings, RoundPlan, RoundVariant, TestCase,
+};
 
 /// Gateway profile with predefined settings
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -85,6 +87,9 @@ impl GatewayProfile {
             GatewayImpl::Kong => Self::kong_default(),
             GatewayImpl::Traefik => Self::traefik_default(),
             GatewayImpl::Kgateway => Self::kgateway_default(),
+            GatewayImpl::Contour => Self::contour_default(),
+            GatewayImpl::Apisix => Self::apisix_default(),
+            GatewayImpl::Custom(_) => Self::custom_default(gateway),
         }
     }
 
@@ -188,6 +193,51 @@ impl GatewayProfile {
         profile
     }
 
+    /// Contour default profile
+    pub fn contour_default() -> Self {
+        let mut profile = Self::new("contour-default", GatewayImpl::Contour);
+        profile.namespace = "projectcontour".to_string();
+        profile.install_method = InstallMethod::Manifest;
+        profile
+    }
+
+    /// Apache APISIX default profile
+    pub fn apisix_default() -> Self {
+        let mut profile = Self::new("apisix-default", GatewayImpl::Apisix);
+        profile.namespace = "apisix".to_string();
+        profile.helm = Some(HelmSettings {
+            repo: "https://charts.apiseven.com".to_string(),
+            chart: "apisix".to_string(),
+            version: None,
+            values: {
+                let mut values = HashMap::new();
+                values.insert(
+                    "ingress-controller.enabled".to_string(),
+                    serde_yaml::Value::Bool(true),
+                );
+                values
+            },
+        });
+        profile
+    }
+
+    /// Default profile of a user-defined implementation, from its definition
+    pub fn custom_default(gateway: GatewayImpl) -> Self {
+        let mut profile = Self::new(format!("{}-default", gateway.short_name()), gateway);
+        profile.helm = gateway.definition().map(|d| HelmSettings {
+            repo: d.helm.repo.clone().unwrap_or_default(),
+            chart: d.helm.chart.clone(),
+            version: d.helm.version.clone(),
+            values: d
+                .helm
+                .values
+                .iter()
+                .map(|(k, v)| (k.clone(), serde_yaml::Value::String(v.clone())))
+                .collect(),
+        });
+        profile
+    }
+
     /// Set namespace
     pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
         self.namespace = namespace.into();
@@ -232,6 +282,22 @@ pub struct TestProfile {
     pub timeout_secs: u64,
     /// Tags for filtering
     pub tags: Vec<String>,
+    /// Load Test (test 16) parameters
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub load_test: Option<LoadTestSettings>,
+    /// Per-round parameter overrides; when set, they define the rounds
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub round_variants: Vec<RoundVariant>,
+    /// Concurrent tests when parallel (default: `--concurrent`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub concurrency: Option<usize>,
+    /// Listener ports and test hostname (default: the `test` flags)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_port: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub https_port: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<String>,
 }
 
 impl TestProfile {
@@ -245,19 +311,35 @@ impl TestProfile {
             parallel: false,
             timeout_secs: 30,
             tags: Vec::new(),
+            load_test: None,
+            round_variants: Vec::new(),
+            concurrency: None,
+            http_port: None,
+            https_port: None,
+            hostname: None,
         }
     }
 
-    /// All tests profile (1-17)
+    /// All tests profile (every test outside the experimental groups)
     pub fn all() -> Self {
         Self {
             name: "all".to_string(),
-            description: "Run all 17 test cases".to_string(),
-            tests: (1..=17).collect(),
+            description: "Run all non-experimental test cases".to_string(),
+            tests: TestCase::all()
+                .into_iter()
+                .filter(|t| t.experimental().is_none())
+                .map(|t| t.number())
+                .collect(),
             rounds: 1,
             parallel: true,
             timeout_secs: 30,
             tags: vec!["comprehensive".to_string()],
+            load_test: None,
+            round_variants: Vec::new(),
+            concurrency: None,
+            http_port: None,
+            https_port: None,
+            hostname: None,
         }
     }
 
@@ -271,6 +353,12 @@ impl TestProfile {
             parallel: false,
             timeout_secs: 30,
             tags: vec!["quick".to_string(), "smoke".to_string()],
+            load_test: None,
+            round_variants: Vec::new(),
+            concurrency: None,
+            http_port: None,
+            https_port: None,
+            hostname: None,
         }
     }
 
@@ -279,11 +367,17 @@ impl TestProfile {
         Self {
             name: "routing".to_string(),
             description: "HTTP routing test cases".to_string(),
-            tests: vec![1, 2, 3, 4, 5], // Host, path, header, method, query routing
+            tests: vec![1, 2, 3, 11, 12, 23], // Host, path, header routing, rewrite, header modifier, wildcard
             rounds: 1,
             parallel: true,
             timeout_secs: 30,
             tags: vec!["routing".to_string()],
+            load_test: None,
+            round_variants: Vec::new(),
+            concurrency: None,
+            http_port: None,
+            https_port: None,
+            hostname: None,
         }
     }
 
@@ -292,11 +386,17 @@ impl TestProfile {
         Self {
             name: "tls".to_string(),
             description: "TLS and security test cases".to_string(),
-            tests: vec![6, 7, 8], // TLS termination, redirect, backend TLS
+            tests: vec![4, 5, 6, 25], // TLS termination, redirect, backend TLS, SNI mismatch
             rounds: 1,
             parallel: false,
             timeout_secs: 60,
             tags: vec!["tls".to_string(), "security".to_string()],
+            load_test: None,
+            round_variants: Vec::new(),
+            concurrency: None,
+            http_port: None,
+            https_port: None,
+            hostname: None,
         }
     }
 
@@ -305,11 +405,17 @@ impl TestProfile {
         Self {
             name: "traffic".to_string(),
             description: "Traffic management test cases".to_string(),
-            tests: vec![9, 10, 11, 12], // Canary, rate limit, retry, timeout
+            tests: vec![7, 8, 9, 10], // Canary, rate limit, timeout/retry, session affinity
             rounds: 3,
             parallel: false,
             timeout_secs: 60,
             tags: vec!["traffic".to_string()],
+            load_test: None,
+            round_variants: Vec::new(),
+            concurrency: None,
+            http_port: None,
+            https_port: None,
+            hostname: None,
         }
     }
 
@@ -318,11 +424,17 @@ impl TestProfile {
         Self {
             name: "advanced".to_string(),
             description: "Advanced features test cases".to_string(),
-            tests: vec![13, 14, 15, 16, 17], // Session, rewrite, gRPC, load test, observability
+            tests: vec![13, 14, 15, 17, 18], // Cross namespace, gRPC, health check, failover, hashing
             rounds: 1,
             parallel: false,
             timeout_secs: 120,
             tags: vec!["advanced".to_string()],
+            load_test: None,
+            round_variants: Vec::new(),
+            concurrency: None,
+            http_port: None,
+            https_port: None,
+            hostname: None,
         }
     }
 
@@ -336,9 +448,38 @@ impl TestProfile {
             parallel: false,
             timeout_secs: 300,
             tags: vec!["performance".to_string(), "load".to_string()],
+            load_test: Some(LoadTestSettings {
+                engine: LoadTestEngine::Benchmark,
+                concurrent_users: 50,
+                duration_secs: 30,
+                max_p99_latency_ms: Some(500),
+                ..Default::default()
+            }),
+            round_variants: Vec::new(),
+            concurrency: None,
+            http_port: None,
+            https_port: None,
+            hostname: None,
         }
     }
 
+    /// Concurrency sensitivity profile (10 rounds each at 4 and 16) over the
+    /// non-experimental catalog
+    pub fn concurrency_sweep() -> Self {
+        let tests = TestCase::all()
+            .into_iter()
+            .filter(|t| t.experimental().is_none())
+            .map(|t| t.number())
+            .collect();
+        Self::new("concurrency-sweep")
+            .with_description("Pass rate and duration at increasing concurrency")
+            .with_tests(tests)
+            .parallel(true)
+            .with_tag("sensitivity")
+            .with_round_variant(RoundVariant::new(10).with_concurrency(4))
+            .with_round_variant(RoundVariant::new(10).with_concurrency(16))
+    }
+
     /// Set description
     pub fn with_description(mut self, desc: impl Into<String>) -> Self {
         self.description = desc.into();
@@ -363,6 +504,23 @@ impl TestProfile {
         self
     }
 
+    /// Set Load Test parameters
+    pub fn with_load_test(mut self, settings: LoadTestSettings) -> Self {
+        self.load_test = Some(settings);
+        self
+    }
+
+    /// Add a block of rounds with parameter overrides
+    pub fn with_round_variant(mut self, variant: RoundVariant) -> Self {
+        self.round_variants.push(variant);
+        self
+    }
+
+    /// Rounds and their parameter overrides (empty = uniform rounds)
+    pub fn round_plan(&self) -> RoundPlan {
+        RoundPlan::new(self.round_variants.clone())
+    }
+
     /// Add tag
     pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
         self.tags.push(tag.into());
@@ -379,6 +537,7 @@ impl TestProfile {
             Self::traffic(),
             Self::advanced(),
             Self::performance(),
+            Self::concurrency_sweep(),
         ]
     }
 
@@ -476,7 +635,9 @@ mod tests {
     #[test]
     fn test_test_profile_all() {
         let profile = TestProfile::all();
-        assert_eq!(profile.tests.len(), 17);
+        assert!(profile.tests.contains(&18));
+        // The AI tests need --experimental
+        assert!(!profile.tests.contains(&19));
     }
 
     #[test]
@@ -492,6 +653,28 @@ mod tests {
         assert!(profiles.len() >= 6);
     }
 
+    #[test]
+    fn test_concurrency_sweep_profile() {
+        let profile = TestProfile::find("concurrency-sweep").unwrap();
+        let plan = profile.round_plan();
+        assert_eq!(plan.total_rounds(), 20);
+        assert_eq!(plan.variant(11).unwrap().concurrency, Some(16));
+        assert!(profile.parallel);
+        assert_eq!(
+            profile.tests.len(),
+            TestCase::all()
+                .iter()
+                .filter(|t| t.experimental().is_none())
+                .count()
+        );
+
+        let yaml = serde_yaml::to_string(&profile).unwrap();
+        assert!(yaml.contains("round_variants"));
+        assert!(!serde_yaml::to_string(&TestProfile::smoke())
+            .unwrap()
+            .contains("round_variants"));
+    }
+
     #[test]
     fn test_find_profile() {
         let profile = TestProfile::find("routing");
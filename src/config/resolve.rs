@@ -0,0 +1,173 @@
+//! Layered configuration
+//!
+//! Options resolve from, lowest first: built-in defaults, the config file
+//! (`app` section, then the selected test profile), `GATEWAY_POC_*`
+//! environment variables, and command-line flags.
+
+use serde::Serialize;
+use std::fmt;
+
+/// Layer an effective setting came from
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SettingSource {
+    Default,
+    ConfigFile,
+    /// Test profile selected with `--profile`
+    Profile(String),
+    /// Environment variable (its name)
+    Env(String),
+    CommandLine,
+}
+
+impl fmt::Display for SettingSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SettingSource::Default => f.write_str("default"),
+            SettingSource::ConfigFile => f.write_str("config file"),
+            SettingSource::Profile(name) => write!(f, "profile {name}"),
+            SettingSource::Env(var) => f.write_str(var),
+            SettingSource::CommandLine => f.write_str("command line"),
+        }
+    }
+}
+
+impl Serialize for SettingSource {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// One effective setting
+#[derive(Clone, Debug, Serialize)]
+pub struct Setting {
+    pub key: String,
+    pub value: String,
+    pub source: SettingSource,
+}
+
+/// Effective settings of a command and where each came from
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(transparent)]
+pub struct ResolvedConfig {
+    settings: Vec<Setting>,
+}
+
+impl ResolvedConfig {
+    /// Resolve `key` into `target`
+    ///
+    /// A value given on the command line or in the environment (`given`)
+    /// is kept; otherwise the last of `layers` (lowest first) that has a
+    /// value wins, and without one the default in `target` stays.
+    pub fn resolve<T: fmt::Display>(
+        &mut self,
+        key: &str,
+        target: &mut T,
+        given: Option<&SettingSource>,
+        layers: Vec<(SettingSource, Option<T>)>,
+    ) {
+        let mut source = SettingSource::Default;
+        match given {
+            Some(given) => source = given.clone(),
+            None => {
+                for (layer, value) in layers {
+                    if let Some(value) = value {
+                        *target = value;
+                        source = layer;
+                    }
+                }
+            }
+        }
+        self.record(key, &*target, source);
+    }
+
+    /// Record a setting resolved elsewhere
+    pub fn record(&mut self, key: &str, value: impl fmt::Display, source: SettingSource) {
+        self.settings.retain(|s| s.key != key);
+        self.settings.push(Setting {
+            key: key.to_string(),
+            value: value.to_string(),
+            source,
+        });
+    }
+
+    pub fn settings(&self) -> &[Setting] {
+        &self.settings
+    }
+
+    /// Where `key` came from, if resolved
+    pub fn source(&self, key: &str) -> Option<&SettingSource> {
+        self.settings
+            .iter()
+            .find(|s| s.key == key)
+            .map(|s| &s.source)
+    }
+}
+
+impl fmt::Display for ResolvedConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let key_width = self.settings.iter().map(|s| s.key.len()).max().unwrap_or(0);
+        let value_width = self
+            .settings
+            .iter()
+            .map(|s| s.value.len())
+            .max()
+            .unwrap_or(0);
+        for setting in &self.settings {
+            writeln!(
+                f,
+                "{:key_width$}  {:value_width$}  ({})",
+                setting.key, setting.value, setting.source
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_precedence() {
+        let mut resolved = ResolvedConfig::default();
+
+        let mut rounds = 1;
+        resolved.resolve(
+            "rounds",
+            &mut rounds,
+            None,
+            vec![
+                (SettingSource::ConfigFile, Some(3)),
+                (SettingSource::Profile("smoke".to_string()), Some(5)),
+            ],
+        );
+        assert_eq!(rounds, 5);
+        assert_eq!(
+            resolved.source("rounds"),
+            Some(&SettingSource::Profile("smoke".to_string()))
+        );
+
+        let mut timeout = 60;
+        let env = SettingSource::Env("GATEWAY_POC_TIMEOUT".to_string());
+        resolved.resolve(
+            "timeout",
+            &mut timeout,
+            Some(&env),
+            vec![(SettingSource::ConfigFile, Some(30))],
+        );
+        assert_eq!(timeout, 60);
+
+        let mut hostname = "example.com".to_string();
+        resolved.resolve(
+            "hostname",
+            &mut hostname,
+            None,
+            vec![(SettingSource::ConfigFile, None)],
+        );
+        assert_eq!(resolved.source("hostname"), Some(&SettingSource::Default));
+
+        let table = resolved.to_string();
+        assert!(table.contains("rounds    5            (profile smoke)"));
+        assert!(table.contains("(GATEWAY_POC_TIMEOUT)"));
+    }
+}
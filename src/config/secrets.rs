@@ -0,0 +1,163 @@
+//! Credentials referenced from the config file
+//!
+//! Passwords and keys are never written into the YAML; a [`SecretRef`]
+//! names where to read them when a command needs them: an environment
+//! variable, a file, or a key of a Kubernetes Secret (read with
+//! `k8s::resolve_secret`).
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::PathBuf;
+
+/// Where a secret value is read from; exactly one field is set
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecretRef {
+    /// Environment variable holding the value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env: Option<String>,
+
+    /// File holding the value (`~` expands)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+
+    /// Key of a Kubernetes Secret
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret: Option<SecretKeyRef>,
+}
+
+/// Key of a Kubernetes Secret
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecretKeyRef {
+    pub name: String,
+    pub key: String,
+    /// Namespace of the Secret (default: the command's namespace)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+}
+
+impl SecretRef {
+    pub fn env(var: impl Into<String>) -> Self {
+        Self {
+            env: Some(var.into()),
+            ..Default::default()
+        }
+    }
+
+    pub fn file(path: impl Into<String>) -> Self {
+        Self {
+            file: Some(path.into()),
+            ..Default::default()
+        }
+    }
+
+    pub fn secret(name: impl Into<String>, key: impl Into<String>) -> Self {
+        Self {
+            secret: Some(SecretKeyRef {
+                name: name.into(),
+                key: key.into(),
+                namespace: None,
+            }),
+            ..Default::default()
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        let set = [
+            self.env.is_some(),
+            self.file.is_some(),
+            self.secret.is_some(),
+        ];
+        if set.iter().filter(|s| **s).count() != 1 {
+            anyhow::bail!("A secret reference needs exactly one of env, file or secret");
+        }
+        Ok(())
+    }
+
+    /// File holding the value with `~` expanded, for `file` references
+    pub fn file_path(&self) -> Option<PathBuf> {
+        self.file.as_deref().map(super::file::expand_path)
+    }
+}
+
+impl fmt::Display for SecretRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.env, &self.file, &self.secret) {
+            (Some(var), _, _) => write!(f, "env {var}"),
+            (_, Some(file), _) => write!(f, "file {file}"),
+            (_, _, Some(secret)) => match &secret.namespace {
+                Some(ns) => write!(f, "secret {ns}/{} key {}", secret.name, secret.key),
+                None => write!(f, "secret {} key {}", secret.name, secret.key),
+            },
+            _ => f.write_str("(empty)"),
+        }
+    }
+}
+
+/// Login to a Helm chart registry or repository
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistryCredential {
+    /// Registry host (`ghcr.io`) or repository URL
+    pub registry: String,
+    pub username: String,
+    pub password: SecretRef,
+}
+
+/// `secrets` section of the config file
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecretsConfig {
+    /// Logins for private Helm registries, used by `deploy install`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub registries: Vec<RegistryCredential>,
+
+    /// Existing `kubernetes.io/dockerconfigjson` Secrets the test fixtures
+    /// pull their images with
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub image_pull_secrets: Vec<String>,
+}
+
+impl SecretsConfig {
+    pub fn is_empty(&self) -> bool {
+        self.registries.is_empty() && self.image_pull_secrets.is_empty()
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        for credential in &self.registries {
+            credential.password.validate().with_context(|| {
+                format!("Invalid password for registry {}", credential.registry)
+            })?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secret_ref_yaml() {
+        let secrets: SecretsConfig = serde_yaml::from_str(
+            "registries:\n  - registry: ghcr.io\n    username: ci\n    password:\n      secret: { name: ghcr, key: token }\n\
+             image_pull_secrets: [regcred]\n",
+        )
+        .unwrap();
+        secrets.validate().unwrap();
+        assert_eq!(
+            secrets.registries[0].password,
+            SecretRef::secret("ghcr", "token")
+        );
+        assert_eq!(
+            secrets.registries[0].password.to_string(),
+            "secret ghcr key token"
+        );
+        assert_eq!(secrets.image_pull_secrets, ["regcred"]);
+
+        let both = SecretRef {
+            env: Some("KEY".to_string()),
+            ..SecretRef::file("/tmp/key")
+        };
+        assert!(both.validate().is_err());
+        assert!(SecretRef::default().validate().is_err());
+    }
+}
@@ -0,0 +1,230 @@
+//! Variable substitution in profile and scenario files
+//!
+//! String values may reference `${NAME}` or `${NAME:-default}`; `$$` is a
+//! literal `$`. Names resolve against values discovered at run time (gateway
+//! IP, run ID, namespace) first, then the environment. Run-time names that
+//! are not bound yet are left in place, so a file can be loaded before the
+//! run starts and bound late; any other missing variable is an error.
+
+use anyhow::{Context, Result};
+use serde_yaml::Value;
+use std::collections::BTreeMap;
+
+/// Names bound at run time rather than read from the environment
+pub const RUNTIME_VARS: [&str; 4] = ["GATEWAY", "GATEWAY_IP", "NAMESPACE", "RUN_ID"];
+
+/// Resolves `${NAME}` references
+#[derive(Clone, Debug)]
+pub struct VarResolver {
+    bound: BTreeMap<String, String>,
+    env: fn(&str) -> Option<String>,
+}
+
+impl Default for VarResolver {
+    fn default() -> Self {
+        Self {
+            bound: BTreeMap::new(),
+            env: |name| std::env::var(name).ok(),
+        }
+    }
+}
+
+impl VarResolver {
+    /// Resolver over the environment, deferring run-time names
+    pub fn from_env() -> Self {
+        Self::default()
+    }
+
+    /// Bind a run-time value
+    pub fn with_var(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.bound.insert(name.into(), value.into());
+        self
+    }
+
+    /// Substitute the references of one string
+    pub fn resolve(&self, input: &str) -> Result<String> {
+        let mut output = String::with_capacity(input.len());
+        let mut missing = Vec::new();
+        let mut rest = input;
+
+        while let Some(pos) = rest.find('$') {
+            output.push_str(&rest[..pos]);
+            rest = &rest[pos..];
+            if let Some(after) = rest.strip_prefix("$$") {
+                output.push('$');
+                rest = after;
+                continue;
+            }
+            let Some(body) = rest.strip_prefix("${") else {
+                output.push('$');
+                rest = &rest[1..];
+                continue;
+            };
+            let end = body
+                .find('}')
+                .with_context(|| format!("Unterminated variable reference in '{input}'"))?;
+            let reference = &body[..end];
+            let (name, default) = match reference.split_once(":-") {
+                Some((name, default)) => (name, Some(default)),
+                None => (reference, None),
+            };
+            if !is_var_name(name) {
+                anyhow::bail!("Invalid variable reference '${{{reference}}}' in '{input}'");
+            }
+
+            if RUNTIME_VARS.contains(&name) && !self.bound.contains_key(name) {
+                output.push_str(&rest[..end + 3]);
+            } else {
+                match self.lookup(name).or(default.map(str::to_string)) {
+                    Some(value) => output.push_str(&value),
+                    None => missing.push(format!("${{{name}}}")),
+                }
+            }
+            rest = &body[end + 1..];
+        }
+        output.push_str(rest);
+
+        if !missing.is_empty() {
+            anyhow::bail!(
+                "Undefined variable {} (set it in the environment or use ${{NAME:-default}})",
+                missing.join(", ")
+            );
+        }
+        Ok(output)
+    }
+
+    /// Substitute every string of a YAML document
+    ///
+    /// A value that is a single reference takes the type of its substitution,
+    /// so `rounds: ${ROUNDS}` becomes a number.
+    pub fn resolve_value(&self, value: Value) -> Result<Value> {
+        self.resolve_at(value, "")
+    }
+
+    fn resolve_at(&self, value: Value, path: &str) -> Result<Value> {
+        match value {
+            Value::String(s) if s.contains('$') => {
+                let resolved = self
+                    .resolve(&s)
+                    .with_context(|| format!("In {}", display_path(path)))?;
+                if is_single_reference(&s) && resolved != s {
+                    if let Ok(typed @ (Value::Number(_) | Value::Bool(_))) =
+                        serde_yaml::from_str(&resolved)
+                    {
+                        return Ok(typed);
+                    }
+                }
+                Ok(Value::String(resolved))
+            }
+            Value::Sequence(items) => items
+                .into_iter()
+                .enumerate()
+                .map(|(i, item)| self.resolve_at(item, &format!("{path}[{i}]")))
+                .collect::<Result<_>>()
+                .map(Value::Sequence),
+            Value::Mapping(map) => {
+                let mut resolved = serde_yaml::Mapping::new();
+                for (key, item) in map {
+                    let child = match key.as_str() {
+                        Some(key) if path.is_empty() => key.to_string(),
+                        Some(key) => format!("{path}.{key}"),
+                        None => path.to_string(),
+                    };
+                    resolved.insert(key, self.resolve_at(item, &child)?);
+                }
+                Ok(Value::Mapping(resolved))
+            }
+            other => Ok(other),
+        }
+    }
+
+    fn lookup(&self, name: &str) -> Option<String> {
+        self.bound.get(name).cloned().or_else(|| (self.env)(name))
+    }
+}
+
+fn is_var_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Whether a string is exactly one `${...}` reference
+fn is_single_reference(s: &str) -> bool {
+    s.strip_prefix("${")
+        .and_then(|body| body.strip_suffix('}'))
+        .is_some_and(|inner| !inner.contains('}'))
+}
+
+fn display_path(path: &str) -> &str {
+    if path.is_empty() {
+        "document"
+    } else {
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolver() -> VarResolver {
+        VarResolver {
+            bound: BTreeMap::new(),
+            env: |name| match name {
+                "USERS" => Some("50".to_string()),
+                "HOST" => Some("gw.example.com".to_string()),
+                _ => None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_resolve_string() {
+        let vars = resolver().with_var("GATEWAY_IP", "10.0.0.5");
+        assert_eq!(
+            vars.resolve("http://${GATEWAY_IP}/${HOST}").unwrap(),
+            "http://10.0.0.5/gw.example.com"
+        );
+        assert_eq!(vars.resolve("${MISSING:-fallback}").unwrap(), "fallback");
+        assert_eq!(vars.resolve("cost $$5 $x").unwrap(), "cost $5 $x");
+
+        let err = vars.resolve("${MISSING} ${OTHER}").unwrap_err().to_string();
+        assert!(err.contains("${MISSING}, ${OTHER}"), "{err}");
+        assert!(vars.resolve("${UNTERMINATED").is_err());
+        assert!(vars.resolve("${1BAD}").is_err());
+    }
+
+    #[test]
+    fn test_runtime_vars_deferred() {
+        let unbound = resolver();
+        assert_eq!(unbound.resolve("run-${RUN_ID}").unwrap(), "run-${RUN_ID}");
+        assert_eq!(
+            unbound.resolve("${NAMESPACE:-default}").unwrap(),
+            "${NAMESPACE:-default}"
+        );
+        let bound = resolver().with_var("RUN_ID", "20260101_0001");
+        assert_eq!(bound.resolve("run-${RUN_ID}").unwrap(), "run-20260101_0001");
+    }
+
+    #[test]
+    fn test_resolve_value_types() {
+        let doc: Value = serde_yaml::from_str(
+            "name: load-${NAMESPACE}\nload_test:\n  concurrent_users: ${USERS}\ntags: [\"${HOST}\"]\n",
+        )
+        .unwrap();
+        let resolved = resolver()
+            .with_var("NAMESPACE", "default")
+            .resolve_value(doc)
+            .unwrap();
+        assert_eq!(resolved["name"], Value::from("load-default"));
+        assert_eq!(resolved["load_test"]["concurrent_users"], Value::from(50));
+        assert_eq!(resolved["tags"][0], Value::from("gw.example.com"));
+
+        let doc: Value = serde_yaml::from_str("test_profiles:\n  - rounds: ${ROUNDS}\n").unwrap();
+        let err = format!("{:#}", resolver().resolve_value(doc).unwrap_err());
+        assert!(err.contains("test_profiles[0].rounds"), "{err}");
+    }
+}
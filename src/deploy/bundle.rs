@@ -0,0 +1,625 @@
+//! GitOps bundle of the test topology
+//!
+//! Writes the Gateway, one route file per test, the echo backends they point
+//! at, and a `kustomization.yaml`, so the topology can be committed to Git and
+//! applied by Argo CD or Flux (`kubectl apply -k` works too).
+
+use anyhow::{Context, Result};
+use k8s_openapi::api::core::v1::Namespace;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::manifest::{HeaderModifier, HeaderValue, HttpRouteFilter, HttpRouteManifest};
+use super::ManifestGenerator;
+use crate::k8s::{echo_backend, set_image_pull_secrets, tls_echo_backend, TlsBackendRefs};
+use crate::models::{GatewayImpl, TestCase};
+use crate::tests::{
+    RedirectCase, BACKEND_TLS_HOSTNAME, BACKEND_TLS_PATH, BACKEND_TLS_PORT, BACKEND_TLS_SERVICE,
+    CONSISTENT_HASH_BACKEND, CONSISTENT_HASH_HEADER, CONSISTENT_HASH_PATH, CONSISTENT_HASH_ROUTE,
+    CORS_HEADER, CORS_METHODS, CORS_ORIGIN, CORS_PATH, CORS_POLICY, CORS_ROUTE,
+    CROSS_NAMESPACE_TARGETS, FAILOVER_BACKEND, HOSTNAME_REWRITE_PATH, HOSTNAME_REWRITE_TARGET,
+    RATE_LIMIT_PATH, RATE_LIMIT_ROUTE, SESSION_AFFINITY_BACKEND, SESSION_AFFINITY_PATH,
+    SESSION_AFFINITY_ROUTE, SESSION_COOKIE, SNI_MISMATCH_BACKEND, SNI_MISMATCH_HOST,
+    WILDCARD_BACKEND, WILDCARD_HOSTNAME,
+};
+
+/// Annotation a policy needs on a backend Service: (service, key, value)
+type ServiceAnnotation = (String, String, String);
+
+/// Service port every route points at
+const BACKEND_PORT: u16 = 8080;
+
+/// Backend of tests that only need a 2xx
+const ECHO_BACKEND: &str = "echo";
+
+/// TLS secret the HTTPS listener terminates with (not generated)
+const TLS_SECRET: &str = "gateway-tls";
+
+// The TLS backend's certificate Secrets and CA ConfigMap (`TlsBackendRefs`)
+// are not generated either: they hold key material
+
+/// One file of the bundle, relative to its root
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BundleFile {
+    pub path: String,
+    pub content: String,
+}
+
+/// Test topology for one implementation as a kustomize directory
+pub struct TestBundle {
+    generator: ManifestGenerator,
+    namespace: String,
+    gateway_name: String,
+    hostname: String,
+    image_pull_secrets: Vec<String>,
+}
+
+impl TestBundle {
+    pub fn new(gateway_impl: GatewayImpl) -> Self {
+        Self {
+            generator: ManifestGenerator::new(gateway_impl),
+            namespace: "default".to_string(),
+            gateway_name: "test-gateway".to_string(),
+            hostname: "example.com".to_string(),
+            image_pull_secrets: Vec::new(),
+        }
+    }
+
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = namespace.into();
+        self.generator = self.generator.namespace(self.namespace.clone());
+        self
+    }
+
+    pub fn with_gateway_name(mut self, name: impl Into<String>) -> Self {
+        self.gateway_name = name.into();
+        self
+    }
+
+    /// Secrets the backends pull their images with
+    pub fn with_image_pull_secrets(mut self, names: Vec<String>) -> Self {
+        self.image_pull_secrets = names;
+        self
+    }
+
+    /// Hostname of the TLS and redirect routes (tests' `--hostname`)
+    pub fn with_hostname(mut self, hostname: impl Into<String>) -> Self {
+        self.hostname = hostname.into();
+        self
+    }
+
+    /// All files of the bundle, `kustomization.yaml` last
+    pub fn files(&self) -> Vec<BundleFile> {
+        let mut files = vec![BundleFile {
+            path: "gateway.yaml".to_string(),
+            content: ManifestGenerator::to_yaml(
+                &self
+                    .generator
+                    .gateway_with_tls(&self.gateway_name, TLS_SECRET),
+            ),
+        }];
+
+        let mut backends: Vec<(String, String)> = Vec::new();
+        let mut annotations = Vec::new();
+        for test in TestCase::all()
+            .into_iter()
+            .filter(|t| t.experimental().is_none())
+        {
+            let documents = self.test_documents(test, &mut backends, &mut annotations);
+            if documents.is_empty() {
+                continue;
+            }
+            files.push(BundleFile {
+                path: format!("routes/{:02}-{}.yaml", test.number(), slug(test.name())),
+                content: join_documents(&documents),
+            });
+        }
+
+        backends.sort();
+        backends.dedup();
+        files.push(BundleFile {
+            path: "backends.yaml".to_string(),
+            content: join_documents(&self.backend_documents(&backends, &annotations)),
+        });
+
+        let resources: Vec<String> = files.iter().map(|f| f.path.clone()).collect();
+        files.push(BundleFile {
+            path: "kustomization.yaml".to_string(),
+            content: ManifestGenerator::to_yaml(&serde_json::json!({
+                "apiVersion": "kustomize.config.k8s.io/v1beta1",
+                "kind": "Kustomization",
+                "resources": resources,
+            })),
+        });
+        files
+    }
+
+    /// Write the bundle under `dir`, returning the paths written
+    pub fn write(&self, dir: &Path) -> Result<Vec<PathBuf>> {
+        let mut written = Vec::new();
+        for file in self.files() {
+            let path = dir.join(&file.path);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create {}", parent.display()))?;
+            }
+            fs::write(&path, &file.content)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            written.push(path);
+        }
+        Ok(written)
+    }
+
+    /// Routes (and policies) a test needs, recording the backends they use
+    /// and the annotations policies need on their Services
+    fn test_documents(
+        &self,
+        test: TestCase,
+        backends: &mut Vec<(String, String)>,
+        annotations: &mut Vec<ServiceAnnotation>,
+    ) -> Vec<serde_json::Value> {
+        let gen = &self.generator;
+        let gw = self.gateway_name.as_str();
+        let path_route = |name: &str, path: &str, backend: &str| {
+            gen.http_route_path(name, gw, path, backend, BACKEND_PORT)
+        };
+
+        let mut extra = Vec::new();
+        let routes: Vec<HttpRouteManifest> = match test {
+            TestCase::HostRouting => ["app1", "app2"]
+                .iter()
+                .map(|app| {
+                    let host = format!("{app}.example.com");
+                    gen.http_route_host(&format!("host-{app}"), gw, &host, app, BACKEND_PORT)
+                })
+                .collect(),
+            TestCase::PathRouting => vec![
+                path_route("path-api-v1", "/api/v1", "api-v1"),
+                path_route("path-api-v2", "/api/v2", "api-v2"),
+            ],
+            TestCase::HeaderRouting => vec![gen.http_route_header(
+                "header-version-v1",
+                gw,
+                "X-Version",
+                "v1",
+                "version-v1",
+                BACKEND_PORT,
+            )],
+            TestCase::WildcardHostname => vec![gen.http_route_host(
+                "wildcard-hostname",
+                gw,
+                WILDCARD_HOSTNAME,
+                WILDCARD_BACKEND,
+                BACKEND_PORT,
+            )],
+            TestCase::TlsTermination => {
+                let mut route = gen.http_route_host(
+                    "tls-termination",
+                    gw,
+                    &self.hostname,
+                    ECHO_BACKEND,
+                    BACKEND_PORT,
+                );
+                attach_to_listener(&mut route, "https");
+                vec![route]
+            }
+            TestCase::SniMismatch => {
+                // The TLS hostname's route, plus another hostname on the
+                // same HTTPS listener that a mismatched Host could reach
+                let mut own = gen.http_route_host(
+                    "sni-mismatch",
+                    gw,
+                    &self.hostname,
+                    ECHO_BACKEND,
+                    BACKEND_PORT,
+                );
+                let mut other = gen.http_route_host(
+                    "sni-mismatch-other",
+                    gw,
+                    SNI_MISMATCH_HOST,
+                    SNI_MISMATCH_BACKEND,
+                    BACKEND_PORT,
+                );
+                attach_to_listener(&mut own, "https");
+                attach_to_listener(&mut other, "https");
+                vec![own, other]
+            }
+            TestCase::HttpsRedirect => {
+                let redirects = RedirectCase::matrix()
+                    .into_iter()
+                    .map(|case| (case.prefix.clone(), case.filter()))
+                    .collect();
+                let mut route = gen.http_route_redirects("https-redirect", gw, redirects);
+                route.spec.hostnames = Some(vec![self.hostname.clone()]);
+                attach_to_listener(&mut route, "http");
+                vec![route]
+            }
+            TestCase::BackendTls => {
+                let refs = TlsBackendRefs::for_service(BACKEND_TLS_SERVICE);
+                if let Some(policy) = gen.backend_tls_policy(
+                    "backend-tls-policy",
+                    BACKEND_TLS_SERVICE,
+                    BACKEND_TLS_HOSTNAME,
+                    &refs.ca_config_map,
+                    &refs.client_secret,
+                ) {
+                    extra.push(to_value(&policy));
+                }
+                vec![gen.http_route_path(
+                    "backend-tls",
+                    gw,
+                    BACKEND_TLS_PATH,
+                    BACKEND_TLS_SERVICE,
+                    BACKEND_TLS_PORT,
+                )]
+            }
+            // Also serves "/" for the health and load tests
+            TestCase::CanaryTraffic => vec![gen.http_route_canary(
+                "canary-traffic",
+                gw,
+                "stable",
+                "canary",
+                10,
+                BACKEND_PORT,
+            )],
+            TestCase::RateLimiting => {
+                let mut route = path_route(RATE_LIMIT_ROUTE, RATE_LIMIT_PATH, ECHO_BACKEND);
+                // The test's default limit; Istio's filter would limit every
+                // route of the Gateway, so only the test run applies it
+                if let Some(policy) = gen
+                    .rate_limit_policy("rate-limit", RATE_LIMIT_ROUTE, gw, 10, 5)
+                    .filter(|policy| policy.kind != "EnvoyFilter")
+                {
+                    gen.attach_policy(&mut route, &policy.metadata.name);
+                    extra.push(to_value(&policy));
+                }
+                vec![route]
+            }
+            TestCase::TimeoutRetry => vec![
+                path_route("timeout-slow", "/slow", ECHO_BACKEND),
+                path_route("retry-flaky", "/flaky", ECHO_BACKEND),
+            ],
+            TestCase::SessionAffinity => {
+                if let Some(policy) = gen.session_affinity_policy(
+                    "session-affinity-policy",
+                    SESSION_AFFINITY_ROUTE,
+                    SESSION_AFFINITY_BACKEND,
+                    SESSION_COOKIE,
+                ) {
+                    if let Some((key, value)) = gen.policy_service_annotation(&policy) {
+                        annotations.push((SESSION_AFFINITY_BACKEND.to_string(), key, value));
+                    }
+                    extra.push(to_value(&policy));
+                }
+                vec![path_route(
+                    SESSION_AFFINITY_ROUTE,
+                    SESSION_AFFINITY_PATH,
+                    SESSION_AFFINITY_BACKEND,
+                )]
+            }
+            TestCase::UrlRewrite => vec![
+                gen.http_route_rewrite(
+                    "url-rewrite",
+                    gw,
+                    "/old-api",
+                    "/new-api",
+                    ECHO_BACKEND,
+                    BACKEND_PORT,
+                ),
+                gen.http_route_hostname_rewrite(
+                    "hostname-rewrite",
+                    gw,
+                    HOSTNAME_REWRITE_PATH,
+                    HOSTNAME_REWRITE_TARGET,
+                    ECHO_BACKEND,
+                    BACKEND_PORT,
+                ),
+            ],
+            TestCase::HeaderModifier => {
+                let mut route = path_route("header-modifier", "/header-test", ECHO_BACKEND);
+                if let Some(rule) = route.spec.rules.as_mut().and_then(|r| r.first_mut()) {
+                    rule.filters = Some(vec![HttpRouteFilter {
+                        filter_type: "ResponseHeaderModifier".to_string(),
+                        request_header_modifier: None,
+                        response_header_modifier: Some(HeaderModifier {
+                            set: vec![HeaderValue {
+                                name: "X-Gateway".to_string(),
+                                value: "true".to_string(),
+                            }],
+                            add: Vec::new(),
+                            remove: Vec::new(),
+                        }),
+                        request_redirect: None,
+                        url_rewrite: None,
+                        extension_ref: None,
+                    }]);
+                }
+                vec![route]
+            }
+            TestCase::CrossNamespace => CROSS_NAMESPACE_TARGETS
+                .iter()
+                .map(|(path, namespace, service)| {
+                    extra.push(to_value(&gen.reference_grant(
+                        "allow-test-routes",
+                        namespace,
+                        &[service],
+                    )));
+                    gen.http_route_cross_namespace(
+                        &format!("cross-{namespace}"),
+                        gw,
+                        path,
+                        service,
+                        namespace,
+                        BACKEND_PORT,
+                    )
+                })
+                .collect(),
+            TestCase::GrpcRouting => {
+                backends.push((self.namespace.clone(), "grpc-backend".to_string()));
+                return vec![self.grpc_route()];
+            }
+            TestCase::FailoverRecovery => {
+                vec![path_route("failover", "/failover", FAILOVER_BACKEND)]
+            }
+            TestCase::ConsistentHashing => {
+                let route = path_route(
+                    CONSISTENT_HASH_ROUTE,
+                    CONSISTENT_HASH_PATH,
+                    CONSISTENT_HASH_BACKEND,
+                );
+                if let Some(policy) = gen.consistent_hash_policy(
+                    "consistent-hash-policy",
+                    CONSISTENT_HASH_ROUTE,
+                    CONSISTENT_HASH_BACKEND,
+                    CONSISTENT_HASH_HEADER,
+                ) {
+                    if let Some((key, value)) = gen.policy_service_annotation(&policy) {
+                        annotations.push((CONSISTENT_HASH_BACKEND.to_string(), key, value));
+                    }
+                    extra.push(to_value(&policy));
+                }
+                vec![route]
+            }
+            TestCase::Cors => {
+                let mut route = path_route(CORS_ROUTE, CORS_PATH, ECHO_BACKEND);
+                if let Some(policy) = gen.cors_policy(
+                    CORS_POLICY,
+                    CORS_ROUTE,
+                    CORS_ORIGIN,
+                    &CORS_METHODS,
+                    &[CORS_HEADER],
+                ) {
+                    gen.attach_policy(&mut route, &policy.metadata.name);
+                    extra.push(to_value(&policy));
+                }
+                vec![route]
+            }
+            // Served by the canary route on "/"
+            TestCase::HealthCheck
+            | TestCase::LoadTest
+            | TestCase::DualStack
+            | TestCase::MissingHost
+            | TestCase::LargeBody
+            | TestCase::HeaderHandling
+            | TestCase::ForwardedHeaders
+            | TestCase::Compression
+            | TestCase::Http2
+            | TestCase::ZeroDowntimeReload => Vec::new(),
+            // Handshakes only, with the HTTPS listener itself
+            TestCase::TlsPolicy => Vec::new(),
+            // Creates and deletes its own route while it runs
+            TestCase::RoutePropagation => Vec::new(),
+            TestCase::AiStreaming | TestCase::AiTokenLatency | TestCase::AiBodyLimit => Vec::new(),
+        };
+
+        for backend in routes
+            .iter()
+            .flat_map(|r| r.spec.rules.iter().flatten())
+            .flat_map(|r| r.backend_refs.iter().flatten())
+        {
+            let namespace = backend.namespace.as_ref().unwrap_or(&self.namespace);
+            backends.push((namespace.clone(), backend.name.clone()));
+        }
+        routes.iter().map(to_value).chain(extra).collect()
+    }
+
+    fn grpc_route(&self) -> serde_json::Value {
+        serde_json::json!({
+            "apiVersion": "gateway.networking.k8s.io/v1",
+            "kind": "GRPCRoute",
+            "metadata": self.metadata("grpc-routing", &self.namespace),
+            "spec": {
+                "parentRefs": [{ "name": self.gateway_name, "namespace": self.namespace }],
+                "rules": [{
+                    "matches": [{ "method": { "service": "helloworld.Greeter", "method": "SayHello" } }],
+                    "backendRefs": [{ "name": "grpc-backend", "port": BACKEND_PORT }],
+                }],
+            },
+        })
+    }
+
+    /// Deployment and Service per (namespace, backend), plus foreign namespaces
+    fn backend_documents(
+        &self,
+        backends: &[(String, String)],
+        annotations: &[ServiceAnnotation],
+    ) -> Vec<serde_json::Value> {
+        let mut documents = Vec::new();
+        let mut namespaces: Vec<&String> = backends
+            .iter()
+            .map(|(ns, _)| ns)
+            .filter(|ns| **ns != self.namespace)
+            .collect();
+        namespaces.dedup();
+        for namespace in namespaces {
+            documents.push(to_value(&Namespace {
+                metadata: ObjectMeta {
+                    name: Some(namespace.clone()),
+                    labels: Some(self.generator.default_labels()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }));
+        }
+
+        for (namespace, name) in backends {
+            let (mut deployment, mut service) = if name == BACKEND_TLS_SERVICE {
+                tls_echo_backend(
+                    name,
+                    namespace,
+                    BACKEND_TLS_PORT,
+                    self.generator.default_labels(),
+                )
+            } else {
+                echo_backend(
+                    name,
+                    namespace,
+                    BACKEND_PORT,
+                    self.generator.default_labels(),
+                )
+            };
+            set_image_pull_secrets(&mut deployment, &self.image_pull_secrets);
+            // Policies (Kong's upstream policy) live in this namespace
+            for (_, key, value) in annotations
+                .iter()
+                .filter(|(service, _, _)| service == name && *namespace == self.namespace)
+            {
+                service
+                    .metadata
+                    .annotations
+                    .get_or_insert_with(Default::default)
+                    .insert(key.clone(), value.clone());
+            }
+            documents.push(to_value(&deployment));
+            documents.push(to_value(&service));
+        }
+        documents
+    }
+
+    fn metadata(&self, name: &str, namespace: &str) -> serde_json::Value {
+        serde_json::json!({
+            "name": name,
+            "namespace": namespace,
+            "labels": self.generator.default_labels(),
+        })
+    }
+}
+
+/// Attach a route to one listener of its Gateway
+fn attach_to_listener(route: &mut HttpRouteManifest, section: &str) {
+    for parent in route.spec.parent_refs.iter_mut().flatten() {
+        parent.section_name = Some(section.to_string());
+    }
+}
+
+fn to_value<T: Serialize>(resource: &T) -> serde_json::Value {
+    serde_json::to_value(resource).unwrap_or_default()
+}
+
+/// Multi-document YAML
+fn join_documents(documents: &[serde_json::Value]) -> String {
+    documents
+        .iter()
+        .map(ManifestGenerator::to_yaml)
+        .collect::<Vec<_>>()
+        .join("---\n")
+}
+
+/// File-name form of a test name, e.g. "Timeout & Retry" -> "timeout-retry"
+fn slug(name: &str) -> String {
+    name.to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slug() {
+        assert_eq!(slug("Timeout & Retry"), "timeout-retry");
+        assert_eq!(slug("Backend TLS (mTLS)"), "backend-tls-mtls");
+    }
+
+    #[test]
+    fn test_bundle_files() {
+        let files = TestBundle::new(GatewayImpl::Envoy)
+            .with_namespace("gw-tests")
+            .files();
+        let file = |path: &str| files.iter().find(|f| f.path == path);
+
+        assert!(file("gateway.yaml")
+            .unwrap()
+            .content
+            .contains("name: gateway-tls"));
+        assert!(file("routes/01-host-routing.yaml").is_some());
+        assert!(file("routes/16-load-test.yaml").is_none());
+        assert!(file("routes/18-consistent-hashing.yaml")
+            .unwrap()
+            .content
+            .contains("kind: BackendTrafficPolicy"));
+        assert!(file("routes/06-backend-tls-mtls.yaml")
+            .unwrap()
+            .content
+            .contains("kind: BackendTLSPolicy"));
+        assert!(file("routes/10-session-affinity.yaml")
+            .unwrap()
+            .content
+            .contains("type: Cookie"));
+        assert!(file("routes/08-rate-limiting.yaml")
+            .unwrap()
+            .content
+            .contains("type: Local"));
+        assert!(file("routes/13-cross-namespace.yaml")
+            .unwrap()
+            .content
+            .contains("kind: ReferenceGrant"));
+
+        let backends = &file("backends.yaml").unwrap().content;
+        for name in [
+            "app1",
+            "stable",
+            "grpc-backend",
+            CONSISTENT_HASH_BACKEND,
+            BACKEND_TLS_SERVICE,
+        ] {
+            assert!(backends.contains(&format!("name: {name}\n  namespace: gw-tests")));
+        }
+        assert!(backends.contains("name: service-a\n  namespace: namespace-a"));
+        assert!(backends.contains("name: service-b\n  namespace: namespace-b"));
+        assert!(backends.contains("secretName: tls-backend-cert"));
+
+        let kustomization = files.last().unwrap();
+        assert_eq!(kustomization.path, "kustomization.yaml");
+        for f in &files[..files.len() - 1] {
+            assert!(kustomization.content.contains(&format!("- {}", f.path)));
+        }
+    }
+
+    #[test]
+    fn test_bundle_kong_service_annotations() {
+        let files = TestBundle::new(GatewayImpl::Kong).files();
+        let backends = &files
+            .iter()
+            .find(|f| f.path == "backends.yaml")
+            .unwrap()
+            .content;
+        assert!(backends.contains("konghq.com/upstream-policy: consistent-hash-policy"));
+        assert!(backends.contains("konghq.com/upstream-policy: session-affinity-policy"));
+    }
+
+    #[test]
+    fn test_bundle_write() {
+        let dir = tempfile::tempdir().unwrap();
+        let written = TestBundle::new(GatewayImpl::Nginx)
+            .write(&dir.path().join("out"))
+            .unwrap();
+        assert!(written.iter().all(|p| p.exists()));
+        assert!(dir.path().join("out/routes/02-path-routing.yaml").exists());
+    }
+}
@@ -0,0 +1,248 @@
+//! Cleanup of tool-created resources
+//!
+//! Only resources carrying the ownership label are listed or deleted, so a
+//! shared cluster keeps everything the tool did not create itself. Resources
+//! are deleted routes first and namespaces last, so a round can be reset
+//! without waiting on finalizers of objects still in use.
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+use tracing::debug;
+
+use super::kubectl::kubectl;
+use crate::k8s::{gateway_selector, managed_selector};
+use crate::models::GatewayImpl;
+
+/// Gateway API objects, always included
+const ROUTE_KINDS: [&str; 5] = [
+    "httproutes.gateway.networking.k8s.io",
+    "grpcroutes.gateway.networking.k8s.io",
+    "referencegrants.gateway.networking.k8s.io",
+    "backendtlspolicies.gateway.networking.k8s.io",
+    "gateways.gateway.networking.k8s.io",
+];
+
+/// Test pods, load generators, and backend fixtures
+const FIXTURE_KINDS: [&str; 5] = ["deployments", "services", "pods", "configmaps", "secrets"];
+
+/// VMs and the image pre-puller left by an interrupted `vm create --prepull`
+const VM_KINDS: [&str; 2] = ["virtualmachines.kubevirt.io", "daemonsets.apps"];
+
+/// A tool-created resource found by [`Cleanup::find`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ManagedResource {
+    /// Plural resource type, e.g. `httproutes.gateway.networking.k8s.io`
+    pub kind: String,
+    pub name: String,
+    /// None for cluster-scoped resources (namespaces)
+    pub namespace: Option<String>,
+}
+
+impl ManagedResource {
+    /// `kind/name`, as kubectl takes it
+    fn reference(&self) -> String {
+        format!("{}/{}", self.kind, self.name)
+    }
+}
+
+impl fmt::Display for ManagedResource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.namespace {
+            Some(namespace) => write!(f, "{namespace}: {}", self.reference()),
+            None => f.write_str(&self.reference()),
+        }
+    }
+}
+
+/// Which tool-created resources to remove
+#[derive(Clone, Debug, Default)]
+pub struct Cleanup {
+    gateway: Option<GatewayImpl>,
+    namespace: Option<String>,
+    fixtures: bool,
+    vms: bool,
+    namespaces: bool,
+}
+
+impl Cleanup {
+    /// Routes and Gateways in every namespace
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only resources created for one implementation
+    pub fn with_gateway(mut self, gateway: GatewayImpl) -> Self {
+        self.gateway = Some(gateway);
+        self
+    }
+
+    /// Only resources in one namespace
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    /// Include test pods, load generators, and backend fixtures
+    pub fn with_fixtures(mut self, fixtures: bool) -> Self {
+        self.fixtures = fixtures;
+        self
+    }
+
+    /// Include KubeVirt VMs
+    pub fn with_vms(mut self, vms: bool) -> Self {
+        self.vms = vms;
+        self
+    }
+
+    /// Include namespaces the tool created
+    pub fn with_namespaces(mut self, namespaces: bool) -> Self {
+        self.namespaces = namespaces;
+        self
+    }
+
+    /// Resource types to look for, in deletion order
+    fn kinds(&self) -> Vec<&'static str> {
+        let mut kinds = ROUTE_KINDS.to_vec();
+        if self.fixtures {
+            kinds.extend(FIXTURE_KINDS);
+        }
+        if self.vms {
+            kinds.extend(VM_KINDS);
+        }
+        kinds
+    }
+
+    fn selector(&self) -> String {
+        match self.gateway {
+            Some(gateway) => gateway_selector(gateway.short_name()),
+            None => managed_selector(),
+        }
+    }
+
+    /// Tool-created resources in scope, in deletion order
+    ///
+    /// Types the cluster does not serve (e.g. KubeVirt not installed) are skipped.
+    pub async fn find(&self) -> Result<Vec<ManagedResource>> {
+        let selector = self.selector();
+        let scope = match &self.namespace {
+            Some(namespace) => vec!["-n", namespace.as_str()],
+            None => vec!["--all-namespaces"],
+        };
+
+        let mut resources = Vec::new();
+        for kind in self.kinds() {
+            let mut args = vec!["get", kind, "-l", &selector, "-o", "json"];
+            args.extend(&scope);
+            resources.extend(list(kind, &args).await?);
+        }
+
+        if self.namespaces {
+            let args = ["get", "namespaces", "-l", &selector, "-o", "json"];
+            resources.extend(
+                list("namespaces", &args)
+                    .await?
+                    .into_iter()
+                    .filter(|ns| self.namespace.as_ref().is_none_or(|n| *n == ns.name)),
+            );
+        }
+        Ok(resources)
+    }
+
+    /// Delete resources returned by [`Cleanup::find`], keeping their order
+    pub async fn delete(&self, resources: &[ManagedResource]) -> Result<()> {
+        // One kubectl call per run of resources sharing a namespace and type
+        let batches = resources.chunk_by(|a, b| a.namespace == b.namespace && a.kind == b.kind);
+        for batch in batches {
+            let refs: Vec<String> = batch.iter().map(ManagedResource::reference).collect();
+            let mut args = vec!["delete", "--ignore-not-found", "--wait=false"];
+            if let Some(namespace) = &batch[0].namespace {
+                args.extend(["-n", namespace.as_str()]);
+            }
+            args.extend(refs.iter().map(String::as_str));
+            let output = kubectl(&args).await.context("Failed to run kubectl")?;
+            if !output.success {
+                anyhow::bail!("kubectl delete failed: {}", output.stderr.trim());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Resources of one type from `kubectl get -o json`
+async fn list(kind: &str, args: &[&str]) -> Result<Vec<ManagedResource>> {
+    let output = kubectl(args).await.context("Failed to run kubectl")?;
+    if !output.success {
+        debug!("Skipping {}: {}", kind, output.stderr.trim());
+        return Ok(Vec::new());
+    }
+    let list: Value = serde_json::from_str(&output.stdout)
+        .with_context(|| format!("Invalid kubectl output for {kind}"))?;
+    Ok(parse_list(kind, &list))
+}
+
+fn parse_list(kind: &str, list: &Value) -> Vec<ManagedResource> {
+    list["items"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|item| {
+            let metadata = &item["metadata"];
+            Some(ManagedResource {
+                kind: kind.to_string(),
+                name: metadata["name"].as_str()?.to_string(),
+                namespace: metadata["namespace"].as_str().map(str::to_string),
+            })
+        })
+        .collect()
+}
+
+/// Resource counts per type, for a summary line
+pub fn count_by_kind(resources: &[ManagedResource]) -> BTreeMap<&str, usize> {
+    let mut counts = BTreeMap::new();
+    for resource in resources {
+        let short = resource.kind.split('.').next().unwrap_or(&resource.kind);
+        *counts.entry(short).or_default() += 1;
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_scope() {
+        let cleanup = Cleanup::new();
+        assert_eq!(cleanup.kinds().len(), 5);
+        assert_eq!(cleanup.selector(), managed_selector());
+
+        let cleanup = Cleanup::new()
+            .with_gateway(GatewayImpl::Envoy)
+            .with_fixtures(true)
+            .with_vms(true);
+        let kinds = cleanup.kinds();
+        assert_eq!(kinds.first(), Some(&"httproutes.gateway.networking.k8s.io"));
+        assert_eq!(kinds.last(), Some(&"daemonsets.apps"));
+        assert!(cleanup.selector().ends_with("gateway-poc.io/gateway=envoy"));
+    }
+
+    #[test]
+    fn test_parse_list() {
+        let list = json!({
+            "items": [
+                { "metadata": { "name": "preset-api", "namespace": "team-a" } },
+                { "metadata": { "name": "gateway-poc-bench" } },
+            ]
+        });
+        let routes = parse_list("httproutes.gateway.networking.k8s.io", &list);
+        assert_eq!(
+            routes[0].to_string(),
+            "team-a: httproutes.gateway.networking.k8s.io/preset-api"
+        );
+        assert_eq!(routes[1].namespace, None);
+        assert_eq!(count_by_kind(&routes)["httproutes"], 2);
+    }
+}
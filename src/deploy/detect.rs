@@ -0,0 +1,197 @@
+//! Gateway auto-detection
+//!
+//! Lists the GatewayClasses in the cluster and matches them to known
+//! implementations (built-in or from the registry), so `test --gateway auto`
+//! can pick the implementation of an inherited cluster.
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::fmt;
+use std::io::{BufRead, IsTerminal, Write};
+
+use super::kubectl::kubectl;
+use crate::models::GatewayImpl;
+
+/// GatewayClass found in the cluster
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DetectedGateway {
+    pub class_name: String,
+    pub controller_name: String,
+    /// Accepted condition is True
+    pub accepted: bool,
+    /// Matching implementation (None = unknown controller)
+    pub implementation: Option<GatewayImpl>,
+}
+
+impl DetectedGateway {
+    fn from_class(class: &Value) -> Option<Self> {
+        let class_name = class["metadata"]["name"].as_str()?.to_string();
+        let controller_name = class["spec"]["controllerName"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        let accepted = class["status"]["conditions"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .any(|c| c["type"] == "Accepted" && c["status"] == "True");
+        let implementation = GatewayImpl::from_gateway_class(&class_name, &controller_name);
+        Some(Self {
+            class_name,
+            controller_name,
+            accepted,
+            implementation,
+        })
+    }
+}
+
+impl fmt::Display for DetectedGateway {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.implementation {
+            Some(gateway) => write!(f, "{} (GatewayClass {}", gateway.name(), self.class_name)?,
+            None => write!(f, "unknown (GatewayClass {}", self.class_name)?,
+        }
+        if !self.accepted {
+            write!(f, ", not accepted")?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// GatewayClasses in the cluster, parsed from `kubectl get gatewayclass -o json`
+pub fn parse_gateway_classes(list: &Value) -> Vec<DetectedGateway> {
+    list["items"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(DetectedGateway::from_class)
+        .collect()
+}
+
+/// List the cluster's GatewayClasses and match them to implementations
+pub async fn detect_gateways() -> Result<Vec<DetectedGateway>> {
+    let output = kubectl(&["get", "gatewayclass", "-o", "json"])
+        .await
+        .context("Failed to run kubectl")?;
+    if !output.success {
+        anyhow::bail!("Failed to list GatewayClasses: {}", output.stderr.trim());
+    }
+    let list: Value =
+        serde_json::from_str(&output.stdout).context("Invalid GatewayClass JSON from kubectl")?;
+    Ok(parse_gateway_classes(&list))
+}
+
+/// Known implementations among the detected classes, accepted ones first
+pub fn candidates(detected: &[DetectedGateway]) -> Vec<GatewayImpl> {
+    let mut sorted: Vec<&DetectedGateway> = detected.iter().collect();
+    sorted.sort_by_key(|d| !d.accepted);
+    let mut found = Vec::new();
+    for gateway in sorted.into_iter().filter_map(|d| d.implementation) {
+        if !found.contains(&gateway) {
+            found.push(gateway);
+        }
+    }
+    found
+}
+
+/// Pick the implementation installed in the cluster
+///
+/// Selects the only known implementation, or asks which one to use when
+/// several are installed and stdin is a terminal.
+pub async fn select_gateway() -> Result<GatewayImpl> {
+    let detected = detect_gateways().await?;
+    let found = candidates(&detected);
+    match found.as_slice() {
+        [] if detected.is_empty() => {
+            anyhow::bail!("No GatewayClasses in the cluster; install one with `deploy install`")
+        }
+        [] => {
+            let classes: Vec<String> = detected.iter().map(|d| d.to_string()).collect();
+            anyhow::bail!(
+                "No known implementation among the cluster's GatewayClasses: {}; pass --gateway \
+                 or define it (with controller_name) in the config file's gateways section",
+                classes.join(", ")
+            )
+        }
+        [gateway] => Ok(*gateway),
+        _ if std::io::stdin().is_terminal() => prompt(&found),
+        _ => {
+            let names: Vec<&str> = found.iter().map(|g| g.short_name()).collect();
+            anyhow::bail!(
+                "Several implementations installed ({}); pass --gateway",
+                names.join(", ")
+            )
+        }
+    }
+}
+
+fn prompt(found: &[GatewayImpl]) -> Result<GatewayImpl> {
+    eprintln!("Several gateway implementations are installed:");
+    for (i, gateway) in found.iter().enumerate() {
+        eprintln!("  {}. {}", i + 1, gateway.name());
+    }
+    let stdin = std::io::stdin();
+    loop {
+        eprint!("Test which one? [1-{}]: ", found.len());
+        std::io::stderr().flush()?;
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            anyhow::bail!("No gateway selected");
+        }
+        let choice = line.trim();
+        if let Some(gateway) = choice
+            .parse::<usize>()
+            .ok()
+            .and_then(|n| n.checked_sub(1))
+            .and_then(|i| found.get(i))
+            .copied()
+            .or_else(|| GatewayImpl::from_str(choice).filter(|g| found.contains(g)))
+        {
+            return Ok(gateway);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_detect_gateway_classes() {
+        let list = json!({"items": [
+            {
+                "metadata": {"name": "eg"},
+                "spec": {"controllerName": "gateway.envoyproxy.io/gatewayclass-controller"},
+                "status": {"conditions": [{"type": "Accepted", "status": "True"}]}
+            },
+            {
+                "metadata": {"name": "my-kong"},
+                "spec": {"controllerName": "konghq.com/kic-gateway-controller"},
+                "status": {"conditions": [{"type": "Accepted", "status": "False"}]}
+            },
+            {
+                "metadata": {"name": "envoy-internal"},
+                "spec": {"controllerName": "gateway.envoyproxy.io/gatewayclass-controller"}
+            },
+            {
+                "metadata": {"name": "acme-unknown"},
+                "spec": {"controllerName": "example.com/acme-unknown-controller"}
+            }
+        ]});
+        let detected = parse_gateway_classes(&list);
+        assert_eq!(detected.len(), 4);
+        assert_eq!(detected[1].implementation, Some(GatewayImpl::Kong));
+        assert!(!detected[1].accepted);
+        assert_eq!(detected[3].implementation, None);
+        assert_eq!(
+            detected[1].to_string(),
+            "Kong Gateway (GatewayClass my-kong, not accepted)"
+        );
+
+        assert_eq!(
+            candidates(&detected),
+            [GatewayImpl::Envoy, GatewayImpl::Kong]
+        );
+    }
+}
@@ -0,0 +1,485 @@
+//! Environment diagnostics
+//!
+//! A broader pre-flight: checks the local tooling, cluster reachability,
+//! the installed Gateway API CRDs, metrics-server and KubeVirt, and reports
+//! each problem with a hint on how to fix it.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::fmt;
+use std::io;
+use tokio::process::Command;
+use tracing::info;
+
+use super::health::{HealthCheck, PreFlightChecker};
+use super::kubectl::kubectl;
+use crate::models::{GatewayApiVersion, InstalledGatewayApi};
+
+/// Outcome of one doctor check
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Ok,
+    Warn,
+    Fail,
+}
+
+impl CheckStatus {
+    fn symbol(&self) -> &'static str {
+        match self {
+            CheckStatus::Ok => "✓",
+            CheckStatus::Warn => "!",
+            CheckStatus::Fail => "✗",
+        }
+    }
+}
+
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CheckStatus::Ok => "ok",
+            CheckStatus::Warn => "warn",
+            CheckStatus::Fail => "fail",
+        })
+    }
+}
+
+/// One doctor check and how to fix it
+#[derive(Clone, Debug, Serialize)]
+pub struct DoctorCheck {
+    pub section: String,
+    pub name: String,
+    pub status: CheckStatus,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remediation: Option<String>,
+}
+
+impl DoctorCheck {
+    fn new(section: &str, name: &str, status: CheckStatus, message: impl Into<String>) -> Self {
+        Self {
+            section: section.to_string(),
+            name: name.to_string(),
+            status,
+            message: message.into(),
+            remediation: None,
+        }
+    }
+
+    pub fn ok(section: &str, name: &str, message: impl Into<String>) -> Self {
+        Self::new(section, name, CheckStatus::Ok, message)
+    }
+
+    pub fn warn(section: &str, name: &str, message: impl Into<String>) -> Self {
+        Self::new(section, name, CheckStatus::Warn, message)
+    }
+
+    pub fn fail(section: &str, name: &str, message: impl Into<String>) -> Self {
+        Self::new(section, name, CheckStatus::Fail, message)
+    }
+
+    /// Attach a remediation hint
+    pub fn with_remediation(mut self, hint: impl Into<String>) -> Self {
+        self.remediation = Some(hint.into());
+        self
+    }
+}
+
+/// Local tool, the arguments that print its version, and whether it is required
+struct Tool {
+    binary: &'static str,
+    version_args: &'static [&'static str],
+    required: bool,
+    purpose: &'static str,
+    install: &'static str,
+}
+
+const TOOLS: [Tool; 6] = [
+    Tool {
+        binary: "kubectl",
+        version_args: &["version", "--client"],
+        required: true,
+        purpose: "all cluster operations",
+        install: "https://kubernetes.io/docs/tasks/tools/",
+    },
+    Tool {
+        binary: "helm",
+        version_args: &["version", "--short"],
+        required: false,
+        purpose: "installing most gateways",
+        install: "https://helm.sh/docs/intro/install/",
+    },
+    Tool {
+        binary: "istioctl",
+        version_args: &["version", "--remote=false"],
+        required: false,
+        purpose: "installing Istio",
+        install: "https://istio.io/latest/docs/setup/getting-started/#download",
+    },
+    Tool {
+        binary: "cilium",
+        version_args: &["version", "--client"],
+        required: false,
+        purpose: "installing Cilium",
+        install: "https://docs.cilium.io/en/stable/gettingstarted/k8s-install-default/#install-the-cilium-cli",
+    },
+    Tool {
+        binary: "virtctl",
+        version_args: &["version", "--client"],
+        required: false,
+        purpose: "managing KubeVirt VMs",
+        install: "https://kubevirt.io/user-guide/user_workloads/virtctl_client_tool/",
+    },
+    Tool {
+        binary: "openssl",
+        version_args: &["version"],
+        required: false,
+        purpose: "generating Backend TLS test certificates",
+        install: "https://openssl-library.org/source/",
+    },
+];
+
+/// Runs every environment check
+#[derive(Default)]
+pub struct Doctor;
+
+impl Doctor {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Run all checks (cluster checks are skipped when it is unreachable)
+    pub async fn run(&self) -> DoctorReport {
+        info!("Running environment diagnostics");
+
+        let mut checks = Vec::new();
+        for tool in &TOOLS {
+            checks.push(check_tool(tool).await);
+        }
+
+        let cluster = PreFlightChecker::check_cluster().await;
+        let reachable = cluster.passed;
+        checks.push(cluster_check(cluster));
+
+        if reachable {
+            let (crds, installed) = PreFlightChecker::check_gateway_api_crds().await;
+            checks.push(crd_check(crds, installed.as_ref()));
+            checks.push(check_metrics_server().await);
+            checks.push(check_kubevirt().await);
+        }
+
+        DoctorReport { checks }
+    }
+}
+
+async fn check_tool(tool: &Tool) -> DoctorCheck {
+    let output = Command::new(tool.binary)
+        .args(tool.version_args)
+        .output()
+        .await;
+    tool_check(
+        tool,
+        output.map(|o| {
+            let text = if o.stdout.is_empty() {
+                &o.stderr
+            } else {
+                &o.stdout
+            };
+            (
+                o.status.success(),
+                String::from_utf8_lossy(text).into_owned(),
+            )
+        }),
+    )
+}
+
+/// Classify a tool's version command output
+fn tool_check(tool: &Tool, output: io::Result<(bool, String)>) -> DoctorCheck {
+    let missing = if tool.required {
+        DoctorCheck::fail
+    } else {
+        DoctorCheck::warn
+    };
+    match output {
+        Ok((true, text)) => {
+            let version = text.lines().next().unwrap_or("").trim().to_string();
+            DoctorCheck::ok("Tools", tool.binary, version)
+        }
+        Ok((false, text)) => missing(
+            "Tools",
+            tool.binary,
+            format!(
+                "{} is installed but failed: {}",
+                tool.binary,
+                text.lines().next().unwrap_or("").trim()
+            ),
+        )
+        .with_remediation(format!(
+            "Run `{} {}` to see the error",
+            tool.binary,
+            tool.version_args.join(" ")
+        )),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => missing(
+            "Tools",
+            tool.binary,
+            format!("Not found in PATH (needed for {})", tool.purpose),
+        )
+        .with_remediation(format!("Install {}: {}", tool.binary, tool.install)),
+        Err(e) => missing("Tools", tool.binary, format!("Failed to run: {e}")),
+    }
+}
+
+/// The pre-flight cluster check with a hint when it fails
+fn cluster_check(check: HealthCheck) -> DoctorCheck {
+    if check.passed {
+        DoctorCheck::ok("Cluster", "API server", "Reachable")
+    } else {
+        DoctorCheck::fail("Cluster", "API server", check.message).with_remediation(
+            "Check the current context with `kubectl config current-context` and that the cluster is running",
+        )
+    }
+}
+
+/// Classify the pre-flight CRD check and the installed bundle against the
+/// supported releases
+fn crd_check(check: HealthCheck, installed: Option<&InstalledGatewayApi>) -> DoctorCheck {
+    let Some(installed) = installed.filter(|_| check.passed) else {
+        return DoctorCheck::fail("Cluster", "Gateway API CRDs", check.message).with_remediation(
+            format!(
+                "Run `gateway-poc deploy crds --gateway-api-version {}`",
+                GatewayApiVersion::DEFAULT
+            ),
+        );
+    };
+    let Some(spec) = &installed.spec else {
+        return DoctorCheck::warn(
+            "Cluster",
+            "Gateway API CRDs",
+            "Installed, but the bundle version is not annotated",
+        )
+        .with_remediation(format!(
+            "Reinstall from an official release: `gateway-poc deploy crds --gateway-api-version {}`",
+            GatewayApiVersion::DEFAULT
+        ));
+    };
+
+    let supported = GatewayApiVersion::supported();
+    let known = supported
+        .iter()
+        .any(|v| (v.major, v.minor) == (spec.version.major, spec.version.minor));
+    let message = format!("{} ({} channel)", spec.version, spec.channel);
+    if known {
+        DoctorCheck::ok("Cluster", "Gateway API CRDs", message)
+    } else {
+        let versions: Vec<String> = supported.iter().map(|v| v.to_string()).collect();
+        DoctorCheck::warn(
+            "Cluster",
+            "Gateway API CRDs",
+            format!("{message} is not a validated release"),
+        )
+        .with_remediation(format!(
+            "Validated releases: {}; install one with `gateway-poc deploy crds --gateway-api-version {}`",
+            versions.join(", "),
+            GatewayApiVersion::DEFAULT
+        ))
+    }
+}
+
+async fn check_metrics_server() -> DoctorCheck {
+    let output = kubectl(&[
+        "get",
+        "apiservice",
+        "v1beta1.metrics.k8s.io",
+        "-o",
+        "jsonpath={.status.conditions[?(@.type==\"Available\")].status}",
+    ])
+    .await;
+    match output {
+        Ok(o) if o.success && o.stdout.trim() == "True" => {
+            DoctorCheck::ok("Cluster", "metrics-server", "Available")
+        }
+        Ok(o) if o.success => DoctorCheck::warn(
+            "Cluster",
+            "metrics-server",
+            "Registered but not available",
+        )
+        .with_remediation("Check the metrics-server pods: `kubectl -n kube-system get pods -l k8s-app=metrics-server`"),
+        _ => DoctorCheck::warn(
+            "Cluster",
+            "metrics-server",
+            "Not installed (resource usage will not be collected)",
+        )
+        .with_remediation(
+            "kubectl apply -f https://github.com/kubernetes-sigs/metrics-server/releases/latest/download/components.yaml",
+        ),
+    }
+}
+
+async fn check_kubevirt() -> DoctorCheck {
+    let output = kubectl(&[
+        "get",
+        "kubevirt",
+        "-A",
+        "-o",
+        "jsonpath={.items[*].status.phase}",
+    ])
+    .await;
+    let phase = match output {
+        Ok(o) if o.success => Some(o.stdout.trim().to_string()),
+        _ => None,
+    };
+    kubevirt_check(phase.as_deref())
+}
+
+/// Classify the KubeVirt CR phase (None when the CRD is missing)
+fn kubevirt_check(phase: Option<&str>) -> DoctorCheck {
+    match phase {
+        Some("Deployed") => DoctorCheck::ok("Cluster", "KubeVirt", "Deployed"),
+        Some("") | None => DoctorCheck::warn(
+            "Cluster",
+            "KubeVirt",
+            "Not installed (only needed for `gateway-poc vm`)",
+        )
+        .with_remediation("Install KubeVirt: https://kubevirt.io/user-guide/cluster_admin/installation/"),
+        Some(phase) => DoctorCheck::warn("Cluster", "KubeVirt", format!("Phase {phase}"))
+            .with_remediation(
+                "Inspect the operator: `kubectl -n kubevirt get kubevirt,pods` and `kubectl -n kubevirt describe kubevirt`",
+            ),
+    }
+}
+
+/// Result of all doctor checks
+#[derive(Clone, Debug, Serialize)]
+pub struct DoctorReport {
+    pub checks: Vec<DoctorCheck>,
+}
+
+impl DoctorReport {
+    /// Whether no check failed (warnings are allowed)
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(|c| c.status != CheckStatus::Fail)
+    }
+
+    fn count(&self, status: CheckStatus) -> usize {
+        self.checks.iter().filter(|c| c.status == status).count()
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Format as text, grouped by section with remediation hints
+    pub fn format_table(&self) -> String {
+        let mut output = String::new();
+        let mut section = "";
+        for check in &self.checks {
+            if check.section != section {
+                section = &check.section;
+                output.push_str(&format!("\n{section}\n"));
+            }
+            output.push_str(&format!(
+                "  {} {:18} {}\n",
+                check.status.symbol(),
+                check.name,
+                check.message
+            ));
+            if check.status != CheckStatus::Ok {
+                if let Some(hint) = &check.remediation {
+                    output.push_str(&format!("    → {hint}\n"));
+                }
+            }
+        }
+        output.push_str(&format!(
+            "\n{} ok, {} warning(s), {} failure(s)\n",
+            self.count(CheckStatus::Ok),
+            self.count(CheckStatus::Warn),
+            self.count(CheckStatus::Fail)
+        ));
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::GatewayApiSpec;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_tool_check() {
+        let kubectl = &TOOLS[0];
+        let helm = &TOOLS[1];
+        let not_found = || Err(io::Error::from(io::ErrorKind::NotFound));
+
+        let ok = tool_check(helm, Ok((true, "v3.14.0+g3fc9f4b\n".to_string())));
+        assert_eq!(ok.status, CheckStatus::Ok);
+        assert_eq!(ok.message, "v3.14.0+g3fc9f4b");
+
+        let missing = tool_check(helm, not_found());
+        assert_eq!(missing.status, CheckStatus::Warn);
+        assert!(missing.remediation.unwrap().contains("helm.sh"));
+        assert_eq!(tool_check(kubectl, not_found()).status, CheckStatus::Fail);
+    }
+
+    #[test]
+    fn test_crd_check() {
+        let annotations = |version: &str| {
+            BTreeMap::from([
+                (
+                    "gateway.networking.k8s.io/bundle-version".to_string(),
+                    version.to_string(),
+                ),
+                (
+                    "gateway.networking.k8s.io/channel".to_string(),
+                    "experimental".to_string(),
+                ),
+            ])
+        };
+        let installed = |annotations: &BTreeMap<String, String>| InstalledGatewayApi {
+            spec: GatewayApiSpec::from_annotations(annotations),
+            served_versions: vec!["v1".to_string()],
+        };
+        let check = |annotations: &BTreeMap<String, String>| {
+            crd_check(
+                HealthCheck::pass("Gateway API", ""),
+                Some(&installed(annotations)),
+            )
+        };
+        let ok = check(&annotations("v1.1.0"));
+        assert_eq!(ok.status, CheckStatus::Ok);
+        assert_eq!(ok.message, "v1.1.0 (experimental channel)");
+
+        assert_eq!(check(&annotations("v0.8.1")).status, CheckStatus::Warn);
+        assert_eq!(check(&BTreeMap::new()).status, CheckStatus::Warn);
+
+        let missing = crd_check(
+            HealthCheck::fail("Gateway API", "Gateway API CRDs not found"),
+            None,
+        );
+        assert_eq!(missing.status, CheckStatus::Fail);
+        assert_eq!(missing.message, "Gateway API CRDs not found");
+        assert!(missing.remediation.unwrap().contains("deploy crds"));
+        assert_eq!(kubevirt_check(Some("Deployed")).status, CheckStatus::Ok);
+        assert_eq!(kubevirt_check(None).status, CheckStatus::Warn);
+    }
+
+    #[test]
+    fn test_report_passed_with_warnings() {
+        let mut report = DoctorReport {
+            checks: vec![
+                DoctorCheck::ok("Tools", "kubectl", "v1.30.0"),
+                DoctorCheck::warn("Tools", "virtctl", "Not found").with_remediation("Install it"),
+            ],
+        };
+        assert!(report.passed());
+        let table = report.format_table();
+        assert!(table.contains("→ Install it"));
+        assert!(table.contains("1 ok, 1 warning(s), 0 failure(s)"));
+
+        report
+            .checks
+            .push(cluster_check(HealthCheck::fail("Cluster", "Unreachable")));
+        assert!(!report.passed());
+        assert!(report.to_json().unwrap().contains("\"status\": \"fail\""));
+    }
+}
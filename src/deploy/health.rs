@@ -3,13 +3,16 @@
 //! Provides readiness and health verification for gateways.
 
 use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::net::IpAddr;
 use std::time::Duration;
-use tokio::process::Command;
 use tokio::time::sleep;
 use tracing::{debug, info};
 
-use crate::http::HttpClient;
-use crate::models::GatewayImpl;
+use super::installer::installed_gateway_api;
+use super::kubectl::{self, kubectl};
+use crate::http::{authority, HttpClient, TlsInfo, TlsProbe};
+use crate::models::{GatewayApiChannel, GatewayApiVersion, GatewayImpl, InstalledGatewayApi};
 
 /// Health check configuration
 #[derive(Clone, Debug)]
@@ -28,6 +31,9 @@ pub struct HealthCheckConfig {
 
     /// HTTP health endpoint path
     pub health_path: String,
+
+    /// Warn when the listener certificate expires within this many days
+    pub cert_warning_days: i64,
 }
 
 impl Default for HealthCheckConfig {
@@ -38,6 +44,7 @@ impl Default for HealthCheckConfig {
             retry_interval_secs: 5,
             success_threshold: 3,
             health_path: "/healthz".to_string(),
+            cert_warning_days: 30,
         }
     }
 }
@@ -61,6 +68,11 @@ impl HealthCheckConfig {
         self.retry_interval_secs = secs;
         self
     }
+
+    pub fn cert_warning(mut self, days: i64) -> Self {
+        self.cert_warning_days = days;
+        self
+    }
 }
 
 /// Health checker for gateway components
@@ -120,20 +132,18 @@ impl HealthChecker {
         let name = "GatewayClass";
         let gateway_class = gateway.gateway_class();
 
-        let output = Command::new("kubectl")
-            .args([
-                "get",
-                "gatewayclass",
-                gateway_class,
-                "-o",
-                "jsonpath={.status.conditions[?(@.type=='Accepted')].status}",
-            ])
-            .output()
-            .await;
+        let output = kubectl(&[
+            "get",
+            "gatewayclass",
+            gateway_class,
+            "-o",
+            "jsonpath={.status.conditions[?(@.type=='Accepted')].status}",
+        ])
+        .await;
 
         match output {
-            Ok(o) if o.status.success() => {
-                let status = String::from_utf8_lossy(&o.stdout);
+            Ok(o) if o.success => {
+                let status = o.stdout.as_str();
                 if status.trim() == "True" {
                     HealthCheck::pass(name, "GatewayClass is accepted")
                 } else {
@@ -141,7 +151,7 @@ impl HealthChecker {
                 }
             }
             Ok(o) => {
-                let stderr = String::from_utf8_lossy(&o.stderr);
+                let stderr = o.stderr.as_str();
                 HealthCheck::fail(name, format!("GatewayClass not found: {stderr}"))
             }
             Err(e) => HealthCheck::fail(name, format!("kubectl error: {e}")),
@@ -151,7 +161,7 @@ impl HealthChecker {
     /// Check HTTP connectivity to gateway
     async fn check_http_connectivity(&self, ip: &str, port: u16) -> HealthCheck {
         let name = "HTTP Connectivity";
-        let url = format!("http://{ip}:{port}/");
+        let url = format!("http://{}/", authority(ip, port));
 
         debug!("Checking HTTP connectivity to {}", url);
 
@@ -175,22 +185,20 @@ impl HealthChecker {
         let name = "Pods";
         let label_selector = gateway.pod_selector();
 
-        let output = Command::new("kubectl")
-            .args([
-                "get",
-                "pods",
-                "-l",
-                label_selector,
-                "-A",
-                "-o",
-                "jsonpath={.items[*].status.phase}",
-            ])
-            .output()
-            .await;
+        let output = kubectl(&[
+            "get",
+            "pods",
+            "-l",
+            label_selector,
+            "-A",
+            "-o",
+            "jsonpath={.items[*].status.phase}",
+        ])
+        .await;
 
         match output {
-            Ok(o) if o.status.success() => {
-                let phases = String::from_utf8_lossy(&o.stdout);
+            Ok(o) if o.success => {
+                let phases = o.stdout.as_str();
                 let phases: Vec<&str> = phases.split_whitespace().collect();
 
                 if phases.is_empty() {
@@ -208,7 +216,7 @@ impl HealthChecker {
                 }
             }
             Ok(o) => {
-                let stderr = String::from_utf8_lossy(&o.stderr);
+                let stderr = o.stderr.as_str();
                 HealthCheck::fail(name, format!("Failed to get pods: {stderr}"))
             }
             Err(e) => HealthCheck::fail(name, format!("kubectl error: {e}")),
@@ -219,22 +227,20 @@ impl HealthChecker {
     pub async fn check_gateway_resource(&self, name: &str, namespace: &str) -> HealthCheck {
         let check_name = "Gateway Resource";
 
-        let output = Command::new("kubectl")
-            .args([
-                "get",
-                "gateway",
-                name,
-                "-n",
-                namespace,
-                "-o",
-                "jsonpath={.status.conditions[?(@.type=='Accepted')].status}",
-            ])
-            .output()
-            .await;
+        let output = kubectl(&[
+            "get",
+            "gateway",
+            name,
+            "-n",
+            namespace,
+            "-o",
+            "jsonpath={.status.conditions[?(@.type=='Accepted')].status}",
+        ])
+        .await;
 
         match output {
-            Ok(o) if o.status.success() => {
-                let status = String::from_utf8_lossy(&o.stdout);
+            Ok(o) if o.success => {
+                let status = o.stdout.as_str();
                 if status.trim() == "True" {
                     HealthCheck::pass(check_name, "Gateway is accepted")
                 } else {
@@ -242,7 +248,7 @@ impl HealthChecker {
                 }
             }
             Ok(o) => {
-                let stderr = String::from_utf8_lossy(&o.stderr);
+                let stderr = o.stderr.as_str();
                 HealthCheck::fail(check_name, format!("Gateway not found: {stderr}"))
             }
             Err(e) => HealthCheck::fail(check_name, format!("kubectl error: {e}")),
@@ -253,22 +259,20 @@ impl HealthChecker {
     pub async fn check_httproute(&self, name: &str, namespace: &str) -> HealthCheck {
         let check_name = "HTTPRoute";
 
-        let output = Command::new("kubectl")
-            .args([
-                "get",
-                "httproute",
-                name,
-                "-n",
-                namespace,
-                "-o",
-                "jsonpath={.status.parents[*].conditions[?(@.type=='Accepted')].status}",
-            ])
-            .output()
-            .await;
+        let output = kubectl(&[
+            "get",
+            "httproute",
+            name,
+            "-n",
+            namespace,
+            "-o",
+            "jsonpath={.status.parents[*].conditions[?(@.type=='Accepted')].status}",
+        ])
+        .await;
 
         match output {
-            Ok(o) if o.status.success() => {
-                let status = String::from_utf8_lossy(&o.stdout);
+            Ok(o) if o.success => {
+                let status = o.stdout.as_str();
                 if status.contains("True") {
                     HealthCheck::pass(check_name, "HTTPRoute is accepted")
                 } else {
@@ -276,7 +280,7 @@ impl HealthChecker {
                 }
             }
             Ok(o) => {
-                let stderr = String::from_utf8_lossy(&o.stderr);
+                let stderr = o.stderr.as_str();
                 HealthCheck::fail(check_name, format!("HTTPRoute not found: {stderr}"))
             }
             Err(e) => HealthCheck::fail(check_name, format!("kubectl error: {e}")),
@@ -310,6 +314,8 @@ impl HealthChecker {
                 });
             }
 
+            // Poll live state, not lookups cached by the previous attempt
+            kubectl::invalidate();
             let status = self.check_gateway(gateway, ip, port).await;
 
             if status.healthy {
@@ -337,45 +343,99 @@ impl HealthChecker {
 
     /// Quick connectivity check
     pub async fn ping(&self, ip: &str, port: u16) -> bool {
-        let url = format!("http://{ip}:{port}/");
+        let url = format!("http://{}/", authority(ip, port));
         self.http_client.get(&url).await.is_ok()
     }
 
     /// Check TLS connectivity
+    ///
+    /// Handshakes with `ip` using `hostname` as SNI, accepting self-signed
+    /// certificates; the check carries what was negotiated and served.
     pub async fn check_tls(&self, ip: &str, port: u16, hostname: &str) -> HealthCheck {
         let name = "TLS Connectivity";
-        let _url = format!("https://{ip}:{port}/");
-
-        // Use curl for TLS check with SNI
-        let output = Command::new("curl")
-            .args([
-                "-s",
-                "-o",
-                "/dev/null",
-                "-w",
-                "%{http_code}",
-                "--resolve",
-                &format!("{hostname}:{port}:{ip}"),
-                "-k", // Allow self-signed certs
-                &format!("https://{hostname}:{port}/"),
-            ])
-            .output()
-            .await;
+        let ip: IpAddr = match ip.parse() {
+            Ok(ip) => ip,
+            Err(_) => return HealthCheck::fail(name, format!("Invalid IP address: {ip}")),
+        };
+
+        let probe = TlsProbe::new(hostname)
+            .with_timeout(self.config.check_timeout_secs)
+            .with_accept_invalid_certs(true);
+        match probe.connect(ip, port).await {
+            Ok(info) => HealthCheck::pass(name, info.to_string()).with_tls(info),
+            Err(e) => HealthCheck::fail(name, format!("TLS connection failed: {e:#}")),
+        }
+    }
 
-        match output {
-            Ok(o) if o.status.success() => {
-                let code = String::from_utf8_lossy(&o.stdout);
-                HealthCheck::pass(name, format!("TLS connection OK (status: {})", code.trim()))
-            }
-            Ok(o) => {
-                let stderr = String::from_utf8_lossy(&o.stderr);
-                HealthCheck::fail(name, format!("TLS connection failed: {stderr}"))
-            }
-            Err(e) => HealthCheck::fail(name, format!("curl error: {e}")),
+    /// Check the certificate of a TLS listener
+    ///
+    /// Fails when the certificate is expired or does not cover `hostname`,
+    /// warns when it expires within `cert_warning_days`. An untrusted chain
+    /// (the self-signed certificates of test gateways) is only reported.
+    pub async fn check_certificate(&self, ip: &str, port: u16, hostname: &str) -> HealthCheck {
+        let name = "Certificate";
+        let ip: IpAddr = match ip.parse() {
+            Ok(ip) => ip,
+            Err(_) => return HealthCheck::fail(name, format!("Invalid IP address: {ip}")),
+        };
+
+        let probe = TlsProbe::new(hostname)
+            .with_timeout(self.config.check_timeout_secs)
+            .with_accept_invalid_certs(true);
+        match probe.connect(ip, port).await {
+            Ok(info) => certificate_check(
+                name,
+                info,
+                hostname,
+                Utc::now(),
+                self.config.cert_warning_days,
+            ),
+            Err(e) => HealthCheck::fail(name, format!("TLS connection failed: {e:#}")),
         }
     }
 }
 
+/// Judge the certificate of a completed handshake
+fn certificate_check(
+    name: &str,
+    info: TlsInfo,
+    hostname: &str,
+    now: DateTime<Utc>,
+    warning_days: i64,
+) -> HealthCheck {
+    let Some(cert) = &info.certificate else {
+        return HealthCheck::fail(name, "No certificate presented").with_tls(info);
+    };
+    let days = cert.expires_in(now).num_days();
+    let chain = if info.verification_error.is_some() {
+        "untrusted"
+    } else {
+        "trusted"
+    };
+
+    let check = if cert.not_after <= now {
+        HealthCheck::fail(
+            name,
+            format!("Expired {}", cert.not_after.format("%Y-%m-%d")),
+        )
+    } else if cert.not_before > now {
+        HealthCheck::fail(
+            name,
+            format!("Not valid before {}", cert.not_before.format("%Y-%m-%d")),
+        )
+    } else if !cert.matches(hostname) {
+        HealthCheck::fail(
+            name,
+            format!("Not valid for {hostname} ({})", cert.names.join(", ")),
+        )
+    } else if days < warning_days {
+        HealthCheck::warn(name, format!("Expires in {days} days, {chain}"))
+    } else {
+        HealthCheck::pass(name, format!("Valid for {days} days, {chain}"))
+    };
+    check.with_tls(info)
+}
+
 /// Health status of a gateway
 #[derive(Clone, Debug)]
 pub struct HealthStatus {
@@ -393,6 +453,19 @@ pub struct HealthStatus {
 }
 
 impl HealthStatus {
+    /// Add a check, updating the overall status
+    pub fn add_check(&mut self, check: HealthCheck) {
+        self.checks.push(check);
+        let passed = self.checks.iter().filter(|c| c.passed).count();
+        let total = self.checks.len();
+        self.healthy = passed == total;
+        self.message = if self.healthy {
+            "All health checks passed".to_string()
+        } else {
+            format!("{passed}/{total} checks passed")
+        };
+    }
+
     /// Format as table
     pub fn format_table(&self) -> String {
         let mut output = String::new();
@@ -410,7 +483,7 @@ impl HealthStatus {
         output.push_str("├─────────────────────────────────────────────────────────────┤\n");
 
         for check in &self.checks {
-            let status = if check.passed { "✓" } else { "✗" };
+            let status = check.symbol();
             output.push_str(&format!(
                 "│ {} {:20} {:35} │\n",
                 status,
@@ -434,8 +507,14 @@ pub struct HealthCheck {
     /// Whether check passed
     pub passed: bool,
 
+    /// Passed, but needs attention
+    pub warning: bool,
+
     /// Result message
     pub message: String,
+
+    /// Handshake details of TLS checks
+    pub tls: Option<TlsInfo>,
 }
 
 impl HealthCheck {
@@ -443,7 +522,16 @@ impl HealthCheck {
         Self {
             name: name.into(),
             passed: true,
+            warning: false,
             message: message.into(),
+            tls: None,
+        }
+    }
+
+    pub fn warn(name: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            warning: true,
+            ..Self::pass(name, message)
         }
     }
 
@@ -451,7 +539,22 @@ impl HealthCheck {
         Self {
             name: name.into(),
             passed: false,
+            warning: false,
             message: message.into(),
+            tls: None,
+        }
+    }
+
+    pub fn with_tls(mut self, tls: TlsInfo) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
+    fn symbol(&self) -> &'static str {
+        match (self.passed, self.warning) {
+            (false, _) => "✗",
+            (true, true) => "!",
+            (true, false) => "✓",
         }
     }
 }
@@ -486,10 +589,18 @@ impl PreFlightChecker {
         checks.push(self.check_kubectl().await);
 
         // Check cluster connectivity
-        checks.push(self.check_cluster().await);
-
-        // Check Gateway API CRDs
-        checks.push(self.check_gateway_api_crds().await);
+        checks.push(Self::check_cluster().await);
+
+        // Check Gateway API CRDs and that they satisfy the implementation
+        let mut warnings = Vec::new();
+        let (crds, installed) = Self::check_gateway_api_crds().await;
+        checks.push(crds);
+        if let Some(warning) = installed
+            .as_ref()
+            .and_then(|installed| compatibility_warning(gateway, installed))
+        {
+            warnings.push(warning);
+        }
 
         // Check gateway health
         let health = self.health_checker.check_gateway(gateway, ip, port).await;
@@ -497,10 +608,13 @@ impl PreFlightChecker {
 
         let passed = checks.iter().filter(|c| c.passed).count();
         let total = checks.len();
+        let (hits, misses) = kubectl::cache_stats();
+        debug!("kubectl lookups: {} cached, {} executed", hits, misses);
 
         PreFlightResult {
             passed: passed == total,
             checks,
+            warnings,
             message: if passed == total {
                 "All pre-flight checks passed. Ready to run tests.".to_string()
             } else {
@@ -510,41 +624,70 @@ impl PreFlightChecker {
     }
 
     async fn check_kubectl(&self) -> HealthCheck {
-        let output = Command::new("kubectl").arg("version").output().await;
+        let output = kubectl(&["version"]).await;
 
         match output {
-            Ok(o) if o.status.success() => HealthCheck::pass("kubectl", "kubectl is available"),
+            Ok(o) if o.success => HealthCheck::pass("kubectl", "kubectl is available"),
             _ => HealthCheck::fail("kubectl", "kubectl not found or not working"),
         }
     }
 
-    async fn check_cluster(&self) -> HealthCheck {
-        let output = Command::new("kubectl")
-            .args(["cluster-info"])
-            .output()
-            .await;
+    /// Whether the API server answers (also run by `doctor`)
+    pub(crate) async fn check_cluster() -> HealthCheck {
+        let output = kubectl(&["cluster-info"]).await;
 
         match output {
-            Ok(o) if o.status.success() => HealthCheck::pass("Cluster", "Cluster is reachable"),
-            _ => HealthCheck::fail("Cluster", "Cannot connect to cluster"),
+            Ok(o) if o.success => HealthCheck::pass("Cluster", "Cluster is reachable"),
+            Ok(o) => match o.stderr.lines().next().map(str::trim) {
+                Some(error) if !error.is_empty() => {
+                    HealthCheck::fail("Cluster", format!("Cannot connect to cluster: {error}"))
+                }
+                _ => HealthCheck::fail("Cluster", "Cannot connect to cluster"),
+            },
+            Err(_) => HealthCheck::fail("Cluster", "Cannot connect to cluster"),
         }
     }
 
-    async fn check_gateway_api_crds(&self) -> HealthCheck {
-        let output = Command::new("kubectl")
-            .args(["get", "crd", "gateways.gateway.networking.k8s.io"])
-            .output()
-            .await;
-
-        match output {
-            Ok(o) if o.status.success() => {
-                HealthCheck::pass("Gateway API", "Gateway API CRDs installed")
-            }
-            _ => HealthCheck::fail("Gateway API", "Gateway API CRDs not found"),
+    /// Installed Gateway API CRDs, failing when missing or older than v1.0
+    /// (also run by `doctor`)
+    pub(crate) async fn check_gateway_api_crds() -> (HealthCheck, Option<InstalledGatewayApi>) {
+        match installed_gateway_api().await {
+            Ok(Some(installed)) if !installed.serves_v1() => (
+                HealthCheck::fail("Gateway API", format!("CRDs predate v1.0 ({installed})")),
+                Some(installed),
+            ),
+            Ok(Some(installed)) => (
+                HealthCheck::pass("Gateway API", installed.to_string()),
+                Some(installed),
+            ),
+            Ok(None) => (
+                HealthCheck::fail("Gateway API", "Gateway API CRDs not found"),
+                None,
+            ),
+            Err(e) => (HealthCheck::fail("Gateway API", format!("{e:#}")), None),
         }
     }
 }
 
+/// Warning when the installed CRDs are older or narrower than a gateway needs
+fn compatibility_warning(gateway: GatewayImpl, installed: &InstalledGatewayApi) -> Option<String> {
+    let required = gateway.gateway_api_requirement()?;
+    let shortfall = match &installed.spec {
+        Some(spec) => spec.shortfall(&required)?,
+        None => format!("needs {required}, installed release is unknown"),
+    };
+    let experimental = if required.channel == GatewayApiChannel::Experimental {
+        " --experimental"
+    } else {
+        ""
+    };
+    Some(format!(
+        "{} {shortfall}; run `gateway-poc deploy crds{experimental} --version {}`",
+        gateway.name(),
+        GatewayApiVersion::DEFAULT.max(required.version)
+    ))
+}
+
 /// Pre-flight check result
 #[derive(Clone, Debug)]
 pub struct PreFlightResult {
@@ -554,6 +697,9 @@ pub struct PreFlightResult {
     /// Individual checks
     pub checks: Vec<HealthCheck>,
 
+    /// Problems that do not block the run
+    pub warnings: Vec<String>,
+
     /// Result message
     pub message: String,
 }
@@ -568,7 +714,7 @@ impl PreFlightResult {
         output.push_str("├─────────────────────────────────────────────────────────────┤\n");
 
         for check in &self.checks {
-            let status = if check.passed { "✓" } else { "✗" };
+            let status = check.symbol();
             output.push_str(&format!(
                 "│ {} {:20} {:35} │\n",
                 status,
@@ -578,6 +724,9 @@ impl PreFlightResult {
         }
 
         output.push_str("├─────────────────────────────────────────────────────────────┤\n");
+        for warning in &self.warnings {
+            output.push_str(&format!("│ ! {:57} │\n", truncate(warning, 57)));
+        }
         output.push_str(&format!(
             "│ Result: {}                                              │\n",
             if self.passed { "READY" } else { "BLOCKED" }
@@ -591,6 +740,7 @@ impl PreFlightResult {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::GatewayApiSpec;
 
     #[test]
     fn test_health_check_config() {
@@ -613,6 +763,81 @@ mod tests {
         assert!(!fail.passed);
     }
 
+    #[test]
+    fn test_compatibility_warning() {
+        let installed = |version, channel| InstalledGatewayApi {
+            spec: Some(GatewayApiSpec::new(version, channel)),
+            served_versions: vec!["v1".to_string()],
+        };
+        let standard = installed(GatewayApiVersion::V1_2, GatewayApiChannel::Standard);
+        assert!(compatibility_warning(GatewayImpl::Kgateway, &standard).is_none());
+
+        let warning = compatibility_warning(GatewayImpl::Cilium, &standard).unwrap();
+        assert!(warning.contains("experimental channel"), "{warning}");
+        assert!(warning.contains("deploy crds --experimental --version v1.2.1"));
+
+        let old = installed(GatewayApiVersion::V1_0, GatewayApiChannel::Experimental);
+        assert!(compatibility_warning(GatewayImpl::Kgateway, &old)
+            .unwrap()
+            .contains("needs v1.2+"));
+    }
+
+    #[tokio::test]
+    async fn test_check_tls() {
+        let address = crate::http::tls::tests::tls_server("gateway.test", &["http/1.1"]).await;
+        let checker = HealthChecker::new(HealthCheckConfig::new()).unwrap();
+
+        let check = checker
+            .check_tls("127.0.0.1", address.port(), "gateway.test")
+            .await;
+        assert!(check.passed, "{}", check.message);
+        assert!(
+            check.message.starts_with("TLSv1.3, http/1.1"),
+            "{}",
+            check.message
+        );
+        assert_eq!(
+            check.tls.unwrap().certificate.unwrap().names,
+            ["gateway.test"]
+        );
+
+        let check = checker.check_tls("gateway", 443, "gateway.test").await;
+        assert!(!check.passed);
+    }
+
+    #[tokio::test]
+    async fn test_certificate_check() {
+        let address = crate::http::tls::tests::tls_server("gateway.test", &[]).await;
+        let checker = HealthChecker::new(HealthCheckConfig::new()).unwrap();
+        let check = checker
+            .check_certificate("127.0.0.1", address.port(), "gateway.test")
+            .await;
+        assert!(check.passed && !check.warning, "{}", check.message);
+        assert!(
+            check.message.ends_with("days, untrusted"),
+            "{}",
+            check.message
+        );
+
+        let info = check.tls.unwrap();
+        let not_after = info.certificate.as_ref().unwrap().not_after;
+        let judge = |hostname, now, warning_days| {
+            certificate_check("Certificate", info.clone(), hostname, now, warning_days)
+        };
+
+        let soon = judge("gateway.test", not_after - chrono::Duration::days(10), 30);
+        assert!(soon.passed && soon.warning);
+        assert_eq!(soon.message, "Expires in 10 days, untrusted");
+        assert!(judge("gateway.test", not_after - chrono::Duration::days(10), 7).symbol() == "✓");
+
+        let expired = judge("gateway.test", not_after + chrono::Duration::days(1), 30);
+        assert!(!expired.passed);
+        assert!(expired.message.starts_with("Expired"));
+
+        let other = judge("other.test", Utc::now(), 30);
+        assert_eq!(other.message, "Not valid for other.test (gateway.test)");
+    }
+
     #[test]
     fn test_truncate() {
         assert_eq!(truncate("short", 10), "short");
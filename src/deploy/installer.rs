@@ -3,14 +3,38 @@
 //! Installs, configures, and manages Gateway API implementations.
 
 use anyhow::{Context, Result};
+use k8s_openapi::api::core::v1::Namespace;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
 use std::collections::BTreeMap;
+use std::fmt;
+use std::process::{ExitStatus, Output};
+use std::sync::Mutex;
 use std::time::Duration;
 use tokio::process::Command;
 use tokio::time::sleep;
 use tracing::{debug, info, warn};
 
-use crate::k8s::K8sClient;
-use crate::models::GatewayImpl;
+use super::kubectl::{self, kubectl};
+use crate::http::{poll_until, EndpointWait, HttpClient};
+use crate::k8s::{managed_selector, owner_labels, K8sClient};
+use crate::kubevirt::shell_quote;
+use crate::models::{GatewayApiChannel, GatewayApiVersion, GatewayImpl, InstalledGatewayApi};
+
+/// CRD whose bundle annotations and served versions identify the install
+const GATEWAY_CRD: &str = "gateways.gateway.networking.k8s.io";
+
+/// Contour Gateway provisioner (deploys Contour and Envoy per Gateway)
+const CONTOUR_PROVISIONER_URL: &str =
+    "https://projectcontour.io/quickstart/contour-gateway-provisioner.yaml";
+
+/// GatewayClass served by the Contour provisioner
+const CONTOUR_GATEWAY_CLASS: &str = r#"apiVersion: gateway.networking.k8s.io/v1
+kind: GatewayClass
+metadata:
+  name: contour
+spec:
+  controllerName: projectcontour.io/gateway-controller
+"#;
 
 /// Gateway installer configuration
 #[derive(Clone, Debug)]
@@ -26,6 +50,52 @@ pub struct InstallerConfig {
 
     /// Additional Helm values
     pub helm_values: BTreeMap<String, String>,
+
+    /// Gateway API release to install CRDs from
+    pub gateway_api_version: GatewayApiVersion,
+
+    /// Endpoint that must answer before the gateway counts as ready
+    pub ready_url: Option<String>,
+
+    /// Record the commands and manifests instead of changing the cluster
+    pub dry_run: bool,
+
+    /// Credentials for private chart registries and repositories
+    pub registry_logins: Vec<RegistryLogin>,
+}
+
+/// Credentials for one chart registry (`ghcr.io`) or repository URL; the
+/// password reaches helm on stdin only
+#[derive(Clone, PartialEq, Eq)]
+pub struct RegistryLogin {
+    pub registry: String,
+    pub username: String,
+    pub password: String,
+}
+
+impl RegistryLogin {
+    /// Whether this login is for the registry or repository of `reference`
+    /// (`oci://` chart reference or repository URL)
+    fn matches(&self, reference: &str) -> bool {
+        registry_host(&self.registry) == registry_host(reference)
+    }
+}
+
+impl fmt::Debug for RegistryLogin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RegistryLogin")
+            .field("registry", &self.registry)
+            .field("username", &self.username)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Host of a registry, chart reference or repository URL
+fn registry_host(reference: &str) -> &str {
+    let rest = reference
+        .split_once("://")
+        .map_or(reference, |(_, rest)| rest);
+    rest.split('/').next().unwrap_or(rest)
 }
 
 impl Default for InstallerConfig {
@@ -35,6 +105,10 @@ impl Default for InstallerConfig {
             timeout_secs: 300,
             release_prefix: "gateway-poc".to_string(),
             helm_values: BTreeMap::new(),
+            gateway_api_version: GatewayApiVersion::default(),
+            ready_url: None,
+            dry_run: false,
+            registry_logins: Vec::new(),
         }
     }
 }
@@ -58,12 +132,48 @@ impl InstallerConfig {
         self.helm_values.insert(key.into(), value.into());
         self
     }
+
+    pub fn gateway_api_version(mut self, version: GatewayApiVersion) -> Self {
+        self.gateway_api_version = version;
+        self
+    }
+
+    pub fn ready_url(mut self, url: impl Into<String>) -> Self {
+        self.ready_url = Some(url.into());
+        self
+    }
+
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    pub fn registry_login(
+        mut self,
+        registry: impl Into<String>,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Self {
+        self.registry_logins.push(RegistryLogin {
+            registry: registry.into(),
+            username: username.into(),
+            password: password.into(),
+        });
+        self
+    }
+
+    /// Login for the registry or repository of `reference`, if configured
+    fn registry_login_for(&self, reference: &str) -> Option<&RegistryLogin> {
+        self.registry_logins.iter().find(|l| l.matches(reference))
+    }
 }
 
 /// Gateway installer
 pub struct GatewayInstaller {
     config: InstallerConfig,
     k8s_client: Option<K8sClient>,
+    /// Steps recorded in dry-run mode
+    plan: Mutex<Vec<PlannedStep>>,
 }
 
 impl GatewayInstaller {
@@ -72,7 +182,61 @@ impl GatewayInstaller {
         Self {
             config,
             k8s_client: None,
+            plan: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Commands and manifests recorded so far in dry-run mode
+    pub fn planned_steps(&self) -> Vec<PlannedStep> {
+        self.plan.lock().unwrap().clone()
+    }
+
+    /// Run a command, or only record it in dry-run mode
+    async fn exec<S: AsRef<str>>(&self, program: &str, args: &[S]) -> std::io::Result<Output> {
+        self.exec_with_input(program, args, None).await
+    }
+
+    /// Run a command with `input` on stdin (kept out of the recorded plan)
+    async fn exec_with_input<S: AsRef<str>>(
+        &self,
+        program: &str,
+        args: &[S],
+        input: Option<&str>,
+    ) -> std::io::Result<Output> {
+        let args: Vec<&str> = args.iter().map(AsRef::as_ref).collect();
+        if self.config.dry_run {
+            let mut command = vec![program.to_string()];
+            command.extend(args.iter().map(|a| a.to_string()));
+            self.plan
+                .lock()
+                .unwrap()
+                .push(PlannedStep::Command(command));
+            return Ok(Output {
+                status: ExitStatus::default(),
+                stdout: Vec::new(),
+                stderr: Vec::new(),
+            });
         }
+        let Some(input) = input else {
+            return Command::new(program).args(&args).output().await;
+        };
+
+        use tokio::io::AsyncWriteExt;
+        let mut child = Command::new(program)
+            .args(&args)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(input.as_bytes()).await?;
+        }
+        child.wait_with_output().await
+    }
+
+    /// Whether a CLI is on the PATH (assumed in dry-run mode)
+    async fn cli_available(&self, program: &str) -> bool {
+        self.config.dry_run || Command::new(program).arg("version").output().await.is_ok()
     }
 
     /// Set Kubernetes client
@@ -83,47 +247,35 @@ impl GatewayInstaller {
 
     /// Install Gateway API CRDs
     pub async fn install_gateway_api_crds(&self) -> Result<()> {
-        info!("Installing Gateway API CRDs...");
-
-        let output = Command::new("kubectl")
-            .args([
-                "apply",
-                "-f",
-                "https://github.com/kubernetes-sigs/gateway-api/releases/download/v1.0.0/standard-install.yaml",
-            ])
-            .output()
+        self.install_gateway_api_channel(GatewayApiChannel::Standard)
             .await
-            .context("Failed to install Gateway API CRDs")?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            anyhow::bail!("Failed to install Gateway API CRDs: {stderr}");
-        }
-
-        info!("Gateway API CRDs installed successfully");
-        Ok(())
     }
 
     /// Install experimental Gateway API CRDs (includes TCPRoute, etc.)
     pub async fn install_gateway_api_experimental(&self) -> Result<()> {
-        info!("Installing experimental Gateway API CRDs...");
+        self.install_gateway_api_channel(GatewayApiChannel::Experimental)
+            .await
+    }
 
-        let output = Command::new("kubectl")
-            .args([
-                "apply",
-                "-f",
-                "https://github.com/kubernetes-sigs/gateway-api/releases/download/v1.0.0/experimental-install.yaml",
-            ])
-            .output()
+    /// Install the configured Gateway API release from a channel
+    pub async fn install_gateway_api_channel(&self, channel: GatewayApiChannel) -> Result<()> {
+        let version = self.config.gateway_api_version;
+        info!("Installing Gateway API {version} CRDs ({channel} channel)...");
+
+        let output = self
+            .exec("kubectl", &["apply", "-f", &version.crd_url(channel)])
             .await
-            .context("Failed to install experimental Gateway API CRDs")?;
+            .with_context(|| format!("Failed to install {channel} Gateway API CRDs"))?;
+        kubectl::invalidate();
 
         if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            anyhow::bail!("Failed to install experimental CRDs: {stderr}");
+            anyhow::bail!(
+                "Failed to install {channel} Gateway API {version} CRDs: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
         }
 
-        info!("Experimental Gateway API CRDs installed successfully");
+        info!("Gateway API {version} CRDs installed successfully");
         Ok(())
     }
 
@@ -134,7 +286,7 @@ impl GatewayInstaller {
         // Create namespace if needed
         self.ensure_namespace().await?;
 
-        match gateway {
+        let result = match gateway {
             GatewayImpl::Nginx => self.install_nginx().await,
             GatewayImpl::Envoy => self.install_envoy_gateway().await,
             GatewayImpl::Istio => self.install_istio().await,
@@ -142,7 +294,17 @@ impl GatewayInstaller {
             GatewayImpl::Kong => self.install_kong().await,
             GatewayImpl::Traefik => self.install_traefik().await,
             GatewayImpl::Kgateway => self.install_kgateway().await,
-        }
+            GatewayImpl::Contour => self.install_contour().await,
+            GatewayImpl::Apisix => self.install_apisix().await,
+            GatewayImpl::Custom(_) => self.install_custom(gateway).await,
+        };
+        kubectl::invalidate();
+        result.map(|mut result| {
+            if self.config.dry_run {
+                result.status = InstallStatus::Planned;
+            }
+            result
+        })
     }
 
     /// Uninstall a gateway implementation
@@ -151,14 +313,27 @@ impl GatewayInstaller {
 
         let release_name = format!("{}-{}", self.config.release_prefix, gateway.short_name());
 
-        match gateway {
+        let result = match gateway {
             GatewayImpl::Istio => self.uninstall_istio().await,
             GatewayImpl::Cilium => self.uninstall_cilium().await,
+            GatewayImpl::Contour => self.uninstall_contour().await,
             _ => self.helm_uninstall(&release_name).await,
-        }
+        };
+        kubectl::invalidate();
+        result
     }
 
     async fn ensure_namespace(&self) -> Result<()> {
+        if self.config.dry_run {
+            self.plan
+                .lock()
+                .unwrap()
+                .push(PlannedStep::Manifest(namespace_manifest(
+                    &self.config.namespace,
+                )));
+            return Ok(());
+        }
+
         let output = Command::new("kubectl")
             .args([
                 "create",
@@ -184,8 +359,17 @@ impl GatewayInstaller {
             .output()
             .await?;
 
-        // Ignore "already exists" error
-        if !output.status.success() {
+        // Ignore "already exists" error; only namespaces created here are
+        // labelled, so cleanup never deletes one the tool did not create
+        if output.status.success() {
+            let _ = kubectl(&[
+                "label",
+                "namespace",
+                &self.config.namespace,
+                &managed_selector(),
+            ])
+            .await;
+        } else {
             let stderr = String::from_utf8_lossy(&output.stderr);
             if !stderr.contains("already exists") {
                 debug!("Namespace creation note: {}", stderr);
@@ -264,8 +448,8 @@ impl GatewayInstaller {
         info!("Installing Istio with istioctl...");
 
         // Check if istioctl exists
-        let check = Command::new("istioctl").arg("version").output().await;
-        if check.is_err() {
+        let check = self.cli_available("istioctl").await;
+        if !check {
             return Ok(InstallResult {
                 gateway: GatewayImpl::Istio,
                 release_name: "istio".to_string(),
@@ -276,9 +460,8 @@ impl GatewayInstaller {
         }
 
         // Install Istio with minimal profile
-        let output = Command::new("istioctl")
-            .args(["install", "--set", "profile=minimal", "-y"])
-            .output()
+        let output = self
+            .exec("istioctl", &["install", "--set", "profile=minimal", "-y"])
             .await
             .context("Failed to run istioctl")?;
 
@@ -306,21 +489,22 @@ impl GatewayInstaller {
         info!("Installing Cilium...");
 
         // Check if cilium CLI exists
-        let check = Command::new("cilium").arg("version").output().await;
-        if check.is_err() {
+        if !self.cli_available("cilium").await {
             // Fall back to Helm
             return self.install_cilium_helm().await;
         }
 
-        let output = Command::new("cilium")
-            .args([
-                "install",
-                "--set",
-                "kubeProxyReplacement=true",
-                "--set",
-                "gatewayAPI.enabled=true",
-            ])
-            .output()
+        let output = self
+            .exec(
+                "cilium",
+                &[
+                    "install",
+                    "--set",
+                    "kubeProxyReplacement=true",
+                    "--set",
+                    "gatewayAPI.enabled=true",
+                ],
+            )
             .await
             .context("Failed to run cilium install")?;
 
@@ -466,12 +650,122 @@ impl GatewayInstaller {
         })
     }
 
+    async fn install_contour(&self) -> Result<InstallResult> {
+        info!("Installing Contour Gateway provisioner...");
+
+        let output = self
+            .exec("kubectl", &["apply", "-f", CONTOUR_PROVISIONER_URL])
+            .await
+            .context("Failed to install Contour")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Contour install failed: {stderr}");
+        }
+        self.kubectl_apply_stdin(CONTOUR_GATEWAY_CLASS).await?;
+
+        Ok(InstallResult {
+            gateway: GatewayImpl::Contour,
+            release_name: "contour-gateway-provisioner".to_string(),
+            namespace: "projectcontour".to_string(),
+            gateway_class: "contour".to_string(),
+            status: InstallStatus::Installed,
+        })
+    }
+
+    async fn install_apisix(&self) -> Result<InstallResult> {
+        let release_name = format!("{}-apisix", self.config.release_prefix);
+
+        self.helm_repo_add("apisix", "https://charts.apiseven.com")
+            .await?;
+
+        let args = vec![
+            "upgrade".to_string(),
+            "--install".to_string(),
+            release_name.clone(),
+            "apisix/apisix".to_string(),
+            "--namespace".to_string(),
+            self.config.namespace.clone(),
+            "--create-namespace".to_string(),
+            "--set".to_string(),
+            "ingress-controller.enabled=true".to_string(),
+            "--set".to_string(),
+            "ingress-controller.config.kubernetes.enableGatewayAPI=true".to_string(),
+            "--wait".to_string(),
+            "--timeout".to_string(),
+            format!("{}s", self.config.timeout_secs),
+        ];
+
+        self.helm_install(&args).await?;
+
+        Ok(InstallResult {
+            gateway: GatewayImpl::Apisix,
+            release_name,
+            namespace: self.config.namespace.clone(),
+            gateway_class: "apisix".to_string(),
+            status: InstallStatus::Installed,
+        })
+    }
+
+    /// Install a user-defined implementation from its registered Helm chart
+    async fn install_custom(&self, gateway: GatewayImpl) -> Result<InstallResult> {
+        let definition = gateway
+            .definition()
+            .filter(|d| !d.is_placeholder())
+            .with_context(|| format!("{gateway} is not a registered gateway"))?;
+        let release_name = format!("{}-{}", self.config.release_prefix, definition.name);
+        let helm = &definition.helm;
+
+        let chart = match &helm.repo {
+            Some(repo) => {
+                self.helm_repo_add(&definition.name, repo).await?;
+                format!("{}/{}", definition.name, helm.chart)
+            }
+            None => helm.chart.clone(),
+        };
+
+        let mut args = vec![
+            "upgrade".to_string(),
+            "--install".to_string(),
+            release_name.clone(),
+            chart,
+            "--namespace".to_string(),
+            self.config.namespace.clone(),
+            "--create-namespace".to_string(),
+            "--wait".to_string(),
+            "--timeout".to_string(),
+            format!("{}s", self.config.timeout_secs),
+        ];
+        if let Some(version) = &helm.version {
+            args.push("--version".to_string());
+            args.push(version.clone());
+        }
+        // Definition values first so installer values override them
+        for (key, value) in helm.values.iter().chain(&self.config.helm_values) {
+            args.push("--set".to_string());
+            args.push(format!("{key}={value}"));
+        }
+
+        self.helm_install(&args).await?;
+
+        Ok(InstallResult {
+            gateway,
+            release_name,
+            namespace: self.config.namespace.clone(),
+            gateway_class: definition.gateway_class.clone(),
+            status: InstallStatus::Installed,
+        })
+    }
+
     async fn helm_repo_add(&self, name: &str, url: &str) -> Result<()> {
         debug!("Adding Helm repo: {} -> {}", name, url);
 
-        let output = Command::new("helm")
-            .args(["repo", "add", name, url])
-            .output()
+        let mut args = vec!["repo", "add", name, url];
+        let login = self.config.registry_login_for(url);
+        if let Some(login) = login {
+            args.extend(["--username", &login.username, "--password-stdin"]);
+        }
+        let output = self
+            .exec_with_input("helm", &args, login.map(|l| l.password.as_str()))
             .await
             .context("Failed to add Helm repo")?;
 
@@ -484,20 +778,52 @@ impl GatewayInstaller {
         }
 
         // Update repo
-        let _ = Command::new("helm")
-            .args(["repo", "update", name])
-            .output()
-            .await;
+        let _ = self.exec("helm", &["repo", "update", name]).await;
 
         Ok(())
     }
 
+    /// Log in to the registry of an `oci://` chart when credentials are configured
+    async fn helm_registry_login(&self, chart: &str) -> Result<()> {
+        let Some(login) = self.config.registry_login_for(chart) else {
+            return Ok(());
+        };
+        let host = registry_host(chart);
+        info!("Logging in to Helm registry {}", host);
+
+        let output = self
+            .exec_with_input(
+                "helm",
+                &[
+                    "registry",
+                    "login",
+                    host,
+                    "--username",
+                    &login.username,
+                    "--password-stdin",
+                ],
+                Some(&login.password),
+            )
+            .await
+            .context("Failed to run helm registry login")?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "Helm registry login to {host} failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+        Ok(())
+    }
+
     async fn helm_install(&self, args: &[String]) -> Result<()> {
         debug!("Running helm with args: {:?}", args);
 
-        let output = Command::new("helm")
-            .args(args)
-            .output()
+        if let Some(chart) = args.iter().find(|a| a.starts_with("oci://")) {
+            self.helm_registry_login(chart).await?;
+        }
+
+        let output = self
+            .exec("helm", args)
             .await
             .context("Failed to run helm")?;
 
@@ -515,14 +841,16 @@ impl GatewayInstaller {
     async fn helm_uninstall(&self, release_name: &str) -> Result<()> {
         info!("Uninstalling Helm release: {}", release_name);
 
-        let output = Command::new("helm")
-            .args([
-                "uninstall",
-                release_name,
-                "--namespace",
-                &self.config.namespace,
-            ])
-            .output()
+        let output = self
+            .exec(
+                "helm",
+                &[
+                    "uninstall",
+                    release_name,
+                    "--namespace",
+                    &self.config.namespace,
+                ],
+            )
             .await
             .context("Failed to run helm uninstall")?;
 
@@ -539,10 +867,7 @@ impl GatewayInstaller {
     async fn uninstall_istio(&self) -> Result<()> {
         info!("Uninstalling Istio...");
 
-        let output = Command::new("istioctl")
-            .args(["uninstall", "--purge", "-y"])
-            .output()
-            .await;
+        let output = self.exec("istioctl", &["uninstall", "--purge", "-y"]).await;
 
         match output {
             Ok(o) if o.status.success() => {
@@ -560,7 +885,7 @@ impl GatewayInstaller {
         info!("Uninstalling Cilium...");
 
         // Try cilium CLI first
-        let output = Command::new("cilium").args(["uninstall"]).output().await;
+        let output = self.exec("cilium", &["uninstall"]).await;
 
         match output {
             Ok(o) if o.status.success() => {
@@ -576,6 +901,56 @@ impl GatewayInstaller {
         Ok(())
     }
 
+    async fn uninstall_contour(&self) -> Result<()> {
+        info!("Uninstalling Contour...");
+
+        let _ = self
+            .exec(
+                "kubectl",
+                &["delete", "gatewayclass", "contour", "--ignore-not-found"],
+            )
+            .await;
+        let output = self
+            .exec(
+                "kubectl",
+                &[
+                    "delete",
+                    "-f",
+                    CONTOUR_PROVISIONER_URL,
+                    "--ignore-not-found",
+                ],
+            )
+            .await
+            .context("Failed to uninstall Contour")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Contour uninstall failed: {stderr}");
+        }
+
+        Ok(())
+    }
+
+    /// Apply a manifest passed on stdin
+    async fn kubectl_apply_stdin(&self, manifest: &str) -> Result<()> {
+        if self.config.dry_run {
+            self.plan
+                .lock()
+                .unwrap()
+                .push(PlannedStep::Manifest(manifest.to_string()));
+            return Ok(());
+        }
+
+        let output = self
+            .exec_with_input("kubectl", &["apply", "-f", "-"], Some(manifest))
+            .await
+            .context("Failed to run kubectl apply")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("kubectl apply failed: {stderr}");
+        }
+        Ok(())
+    }
+
     /// List installed gateways
     pub async fn list_installed(&self) -> Result<Vec<InstallResult>> {
         let mut results = Vec::new();
@@ -607,12 +982,9 @@ impl GatewayInstaller {
         let gateway_class = gateway.gateway_class();
 
         // Check if GatewayClass exists
-        let output = Command::new("kubectl")
-            .args(["get", "gatewayclass", gateway_class, "-o", "name"])
-            .output()
-            .await?;
+        let output = kubectl(&["get", "gatewayclass", gateway_class, "-o", "name"]).await?;
 
-        if output.status.success() {
+        if output.success {
             return Ok(InstallStatus::Installed);
         }
 
@@ -631,6 +1003,9 @@ impl GatewayInstaller {
     }
 
     /// Wait for gateway to be ready
+    ///
+    /// Ready means the GatewayClass is accepted and, when a ready URL is
+    /// configured, that URL answers with a 2xx.
     pub async fn wait_ready(&self, gateway: GatewayImpl) -> Result<bool> {
         let start = std::time::Instant::now();
         let timeout = Duration::from_secs(self.config.timeout_secs);
@@ -638,12 +1013,7 @@ impl GatewayInstaller {
 
         info!("Waiting for {} to be ready...", gateway.name());
 
-        loop {
-            if start.elapsed() > timeout {
-                warn!("Timeout waiting for {} to be ready", gateway.name());
-                return Ok(false);
-            }
-
+        let accepted = poll_until(timeout, Duration::from_secs(5), || async {
             // Check GatewayClass status
             let output = Command::new("kubectl")
                 .args([
@@ -654,19 +1024,50 @@ impl GatewayInstaller {
                     "jsonpath={.status.conditions[?(@.type=='Accepted')].status}",
                 ])
                 .output()
-                .await?;
+                .await
+                .map_err(|e| format!("kubectl error: {e}"))?;
+            let status = String::from_utf8_lossy(&output.stdout);
+            if output.status.success() && status.trim() == "True" {
+                Ok(())
+            } else {
+                Err(format!("GatewayClass {gateway_class} not accepted"))
+            }
+        })
+        .await;
+        if let Err(e) = accepted {
+            warn!("{} not ready: {:#}", gateway.name(), e);
+            return Ok(false);
+        }
 
-            if output.status.success() {
-                let status = String::from_utf8_lossy(&output.stdout);
-                if status.trim() == "True" {
-                    info!("{} is ready", gateway.name());
-                    return Ok(true);
-                }
+        if let Some(url) = &self.config.ready_url {
+            let remaining = timeout.saturating_sub(start.elapsed());
+            let client = HttpClient::with_timeout(5)?;
+            let wait = EndpointWait::new(url).with_timeout(remaining);
+            if let Err(e) = wait.wait(&client).await {
+                warn!("{} not serving {}: {:#}", gateway.name(), url, e);
+                return Ok(false);
             }
+        }
+
+        info!("{} is ready", gateway.name());
+        Ok(true)
+    }
+}
 
-            sleep(Duration::from_secs(5)).await;
+/// Detect the installed Gateway API CRDs (None if not installed)
+pub async fn installed_gateway_api() -> Result<Option<InstalledGatewayApi>> {
+    let output = kubectl(&["get", "crd", GATEWAY_CRD, "-o", "json"])
+        .await
+        .context("Failed to run kubectl")?;
+    if !output.success {
+        if output.stderr.contains("NotFound") {
+            return Ok(None);
         }
+        anyhow::bail!("Failed to read {GATEWAY_CRD}: {}", output.stderr.trim());
     }
+    let crd: serde_json::Value =
+        serde_json::from_str(&output.stdout).context("Invalid CRD JSON from kubectl")?;
+    Ok(Some(InstalledGatewayApi::from_crd(&crd)))
 }
 
 /// Installation result
@@ -694,9 +1095,53 @@ pub enum InstallStatus {
     Installed,
     NotInstalled,
     Installing,
+    /// Recorded by a dry run, nothing applied
+    Planned,
     Failed(String),
 }
 
+/// A cluster change recorded instead of applied in dry-run mode
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PlannedStep {
+    /// Command line (program and arguments)
+    Command(Vec<String>),
+    /// Manifest that would be passed to `kubectl apply`
+    Manifest(String),
+}
+
+impl fmt::Display for PlannedStep {
+    /// Shell-ready form, so a plan can be reviewed or replayed as a script
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlannedStep::Command(args) => {
+                let quoted: Vec<String> = args.iter().map(|a| shell_quote(a)).collect();
+                write!(f, "{}", quoted.join(" "))
+            }
+            PlannedStep::Manifest(yaml) => {
+                writeln!(f, "kubectl apply -f - <<'EOF'")?;
+                write!(f, "{}", yaml)?;
+                if !yaml.ends_with('\n') {
+                    writeln!(f)?;
+                }
+                write!(f, "EOF")
+            }
+        }
+    }
+}
+
+/// Namespace manifest carrying the ownership label
+fn namespace_manifest(namespace: &str) -> String {
+    let ns = Namespace {
+        metadata: ObjectMeta {
+            name: Some(namespace.to_string()),
+            labels: Some(owner_labels()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    serde_yaml::to_string(&ns).unwrap_or_default()
+}
+
 impl InstallStatus {
     pub fn is_installed(&self) -> bool {
         matches!(self, InstallStatus::Installed)
@@ -707,6 +1152,7 @@ impl InstallStatus {
             InstallStatus::Installed => "Installed",
             InstallStatus::NotInstalled => "Not Installed",
             InstallStatus::Installing => "Installing",
+            InstallStatus::Planned => "Planned (dry run)",
             InstallStatus::Failed(_) => "Failed",
         }
     }
@@ -721,9 +1167,11 @@ mod tests {
         let config = InstallerConfig::new()
             .namespace("test-ns")
             .timeout(600)
-            .helm_value("key", "value");
+            .helm_value("key", "value")
+            .gateway_api_version(GatewayApiVersion::V1_1);
 
         assert_eq!(config.namespace, "test-ns");
+        assert_eq!(config.gateway_api_version.to_string(), "v1.1.0");
         assert_eq!(config.timeout_secs, 600);
         assert_eq!(config.helm_values.get("key"), Some(&"value".to_string()));
     }
@@ -733,5 +1181,63 @@ mod tests {
         assert!(InstallStatus::Installed.is_installed());
         assert!(!InstallStatus::NotInstalled.is_installed());
         assert_eq!(InstallStatus::Installing.as_str(), "Installing");
+        assert!(!InstallStatus::Planned.is_installed());
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_records_steps() {
+        let installer =
+            GatewayInstaller::new(InstallerConfig::new().namespace("gw-test").dry_run(true));
+        let result = installer.install(GatewayImpl::Contour).await.unwrap();
+        assert!(matches!(result.status, InstallStatus::Planned));
+
+        let steps = installer.planned_steps();
+        assert_eq!(steps.len(), 3);
+        assert!(matches!(&steps[0], PlannedStep::Manifest(m) if m.contains("name: gw-test")));
+        assert_eq!(
+            steps[1].to_string(),
+            format!("kubectl apply -f {CONTOUR_PROVISIONER_URL}")
+        );
+        assert!(steps[2]
+            .to_string()
+            .starts_with("kubectl apply -f - <<'EOF'\napiVersion"));
+        assert!(steps[2].to_string().ends_with("\nEOF"));
+    }
+
+    #[tokio::test]
+    async fn test_registry_login_keeps_password_off_the_command_line() {
+        let config =
+            InstallerConfig::new()
+                .dry_run(true)
+                .registry_login("docker.io", "ci", "s3cret");
+        assert!(format!("{config:?}").contains("RegistryLogin { registry: \"docker.io\""));
+        assert!(!format!("{config:?}").contains("s3cret"));
+
+        let installer = GatewayInstaller::new(config);
+        installer.install(GatewayImpl::Envoy).await.unwrap();
+        let steps: Vec<String> = installer
+            .planned_steps()
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert!(steps
+            .iter()
+            .any(|s| s == "helm registry login docker.io --username ci --password-stdin"));
+        assert!(steps.iter().all(|s| !s.contains("s3cret")));
+        assert_eq!(
+            registry_host("https://charts.konghq.com"),
+            "charts.konghq.com"
+        );
+    }
+
+    #[test]
+    fn test_shell_quote() {
+        let step = PlannedStep::Command(vec![
+            "helm".into(),
+            "--set".into(),
+            "a=b c".into(),
+            "it's".into(),
+        ]);
+        assert_eq!(step.to_string(), r"helm --set 'a=b c' 'it'\''s'");
     }
 }
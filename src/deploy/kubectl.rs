@@ -0,0 +1,179 @@
+//! Cached kubectl invocations
+//!
+//! Pre-flight and health checks run the same read-only lookups (CRDs,
+//! GatewayClass status, gateway pods) over and over. Successful lookups are
+//! reused for a few seconds; any other kubectl command, and installs or
+//! uninstalls, clear the cache so later lookups see the change.
+
+use std::collections::HashMap;
+use std::io;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::process::Command;
+use tracing::trace;
+
+use crate::k8s::acquire_api_permit;
+
+/// How long a successful lookup is reused
+pub const LOOKUP_TTL: Duration = Duration::from_secs(5);
+
+/// Read-only kubectl subcommands whose output is cached
+const LOOKUP_VERBS: [&str; 5] = [
+    "get",
+    "version",
+    "cluster-info",
+    "api-resources",
+    "api-versions",
+];
+
+/// Output of a kubectl invocation
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KubectlOutput {
+    pub success: bool,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// Lookup results by argument list
+#[derive(Debug)]
+struct LookupCache {
+    ttl: Duration,
+    entries: HashMap<Vec<String>, (Instant, KubectlOutput)>,
+    hits: u64,
+    misses: u64,
+}
+
+impl LookupCache {
+    fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    fn get(&mut self, args: &[String]) -> Option<KubectlOutput> {
+        let cached = self
+            .entries
+            .get(args)
+            .filter(|(at, _)| at.elapsed() < self.ttl)
+            .map(|(_, output)| output.clone());
+        match cached {
+            Some(_) => self.hits += 1,
+            None => self.misses += 1,
+        }
+        cached
+    }
+
+    /// Keep a lookup result (failures are not cached)
+    fn store(&mut self, args: Vec<String>, output: &KubectlOutput) {
+        if output.success {
+            self.entries.insert(args, (Instant::now(), output.clone()));
+        }
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+fn cache() -> std::sync::MutexGuard<'static, LookupCache> {
+    static CACHE: OnceLock<Mutex<LookupCache>> = OnceLock::new();
+    CACHE
+        .get_or_init(|| Mutex::new(LookupCache::new(LOOKUP_TTL)))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+/// Whether a command only reads cluster state
+fn is_lookup(args: &[String]) -> bool {
+    args.first()
+        .is_some_and(|verb| LOOKUP_VERBS.contains(&verb.as_str()))
+}
+
+/// Run kubectl, reusing a recent result for read-only lookups
+pub async fn kubectl(args: &[&str]) -> io::Result<KubectlOutput> {
+    let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+    let lookup = is_lookup(&args);
+    if lookup {
+        if let Some(output) = cache().get(&args) {
+            trace!("kubectl {} (cached)", args.join(" "));
+            return Ok(output);
+        }
+    }
+
+    acquire_api_permit().await;
+    let output = Command::new("kubectl").args(&args).output().await?;
+    let output = KubectlOutput {
+        success: output.status.success(),
+        stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+        stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+    };
+
+    let mut cache = cache();
+    if lookup {
+        cache.store(args, &output);
+    } else {
+        cache.clear();
+    }
+    Ok(output)
+}
+
+/// Drop all cached lookups (after cluster changes made outside kubectl)
+pub fn invalidate() {
+    cache().clear();
+}
+
+/// Cache hits and misses so far
+pub fn cache_stats() -> (u64, u64) {
+    let cache = cache();
+    (cache.hits, cache.misses)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    fn output(stdout: &str, success: bool) -> KubectlOutput {
+        KubectlOutput {
+            success,
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_lookup_cache() {
+        let mut cache = LookupCache::new(Duration::from_secs(60));
+        let class = args(&["get", "gatewayclass", "envoy-gateway"]);
+        let pods = args(&["get", "pods", "-A"]);
+
+        assert_eq!(cache.get(&class), None);
+        cache.store(class.clone(), &output("True", true));
+        cache.store(pods.clone(), &output("", false));
+        assert_eq!(cache.get(&class), Some(output("True", true)));
+        assert_eq!(cache.get(&pods), None);
+        assert_eq!((cache.hits, cache.misses), (1, 2));
+
+        cache.clear();
+        assert_eq!(cache.get(&class), None);
+
+        let mut expired = LookupCache::new(Duration::ZERO);
+        expired.store(class.clone(), &output("True", true));
+        assert_eq!(expired.get(&class), None);
+    }
+
+    #[test]
+    fn test_is_lookup() {
+        assert!(is_lookup(&args(&["get", "crd"])));
+        assert!(is_lookup(&args(&["cluster-info"])));
+        assert!(!is_lookup(&args(&["apply", "-f", "-"])));
+        assert!(!is_lookup(&args(&["exec", "pod", "--", "curl"])));
+        assert!(!is_lookup(&[]));
+    }
+}
@@ -5,6 +5,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
+use crate::k8s::{owner_labels, GATEWAY_LABEL};
 use crate::models::GatewayImpl;
 
 /// Gateway resource manifest
@@ -27,6 +28,30 @@ pub struct HttpRouteManifest {
     pub spec: HttpRouteSpec,
 }
 
+/// Implementation-specific policy resource (spec varies by implementation)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyManifest {
+    pub api_version: String,
+    pub kind: String,
+    pub metadata: Metadata,
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    pub spec: serde_json::Value,
+    /// Top-level fields of resources without a spec (e.g. KongPlugin)
+    #[serde(flatten)]
+    pub fields: serde_json::Map<String, serde_json::Value>,
+}
+
+/// ReferenceGrant resource manifest
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReferenceGrantManifest {
+    pub api_version: String,
+    pub kind: String,
+    pub metadata: Metadata,
+    pub spec: ReferenceGrantSpec,
+}
+
 /// Kubernetes metadata
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -222,6 +247,16 @@ pub struct HttpRouteFilter {
     pub request_redirect: Option<RequestRedirect>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub url_rewrite: Option<UrlRewrite>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extension_ref: Option<ExtensionRef>,
+}
+
+/// Implementation-specific filter resource in the route's namespace
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExtensionRef {
+    pub group: String,
+    pub kind: String,
+    pub name: String,
 }
 
 /// Header modifier
@@ -253,6 +288,8 @@ pub struct RequestRedirect {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hostname: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathRewrite>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub port: Option<u16>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub status_code: Option<u16>,
@@ -293,9 +330,37 @@ pub struct BackendRef {
     pub weight: Option<u32>,
 }
 
+/// ReferenceGrant spec
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReferenceGrantSpec {
+    pub from: Vec<ReferenceGrantFrom>,
+    pub to: Vec<ReferenceGrantTo>,
+}
+
+/// Resources in another namespace allowed to make references
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReferenceGrantFrom {
+    pub group: String,
+    pub kind: String,
+    pub namespace: String,
+}
+
+/// Resources in the grant's namespace that may be referenced
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReferenceGrantTo {
+    pub group: String,
+    pub kind: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
 /// Manifest generator
 pub struct ManifestGenerator {
     namespace: String,
+    gateway_impl: GatewayImpl,
     gateway_class: String,
 }
 
@@ -304,6 +369,7 @@ impl ManifestGenerator {
     pub fn new(gateway_impl: GatewayImpl) -> Self {
         Self {
             namespace: "default".to_string(),
+            gateway_impl,
             gateway_class: gateway_impl.gateway_class().to_string(),
         }
     }
@@ -427,6 +493,70 @@ impl ManifestGenerator {
         route
     }
 
+    /// Generate HTTPRoute whose backend is a Service in another namespace
+    ///
+    /// Needs a [`ReferenceGrant`](Self::reference_grant) in `backend_namespace`.
+    pub fn http_route_cross_namespace(
+        &self,
+        name: &str,
+        gateway_name: &str,
+        path: &str,
+        backend: &str,
+        backend_namespace: &str,
+        port: u16,
+    ) -> HttpRouteManifest {
+        let mut route = self.http_route_path(name, gateway_name, path, backend, port);
+        for backend in route
+            .spec
+            .rules
+            .iter_mut()
+            .flatten()
+            .flat_map(|r| r.backend_refs.iter_mut().flatten())
+        {
+            backend.namespace = Some(backend_namespace.to_string());
+        }
+        route
+    }
+
+    /// Generate a ReferenceGrant in `target_namespace` letting HTTPRoutes in
+    /// this namespace reference its Services (all of them when `services` is empty)
+    pub fn reference_grant(
+        &self,
+        name: &str,
+        target_namespace: &str,
+        services: &[&str],
+    ) -> ReferenceGrantManifest {
+        let service = |name: Option<&str>| ReferenceGrantTo {
+            group: String::new(),
+            kind: "Service".to_string(),
+            name: name.map(str::to_string),
+        };
+        let to = if services.is_empty() {
+            vec![service(None)]
+        } else {
+            services.iter().map(|s| service(Some(s))).collect()
+        };
+
+        ReferenceGrantManifest {
+            api_version: "gateway.networking.k8s.io/v1beta1".to_string(),
+            kind: "ReferenceGrant".to_string(),
+            metadata: Metadata {
+                name: name.to_string(),
+                namespace: Some(target_namespace.to_string()),
+                labels: self.default_labels(),
+                annotations: BTreeMap::new(),
+            },
+            spec: ReferenceGrantSpec {
+                from: vec![ReferenceGrantFrom {
+                    group: "gateway.networking.k8s.io".to_string(),
+                    kind: "HTTPRoute".to_string(),
+                    namespace: self.namespace.clone(),
+                }],
+                to,
+            },
+        }
+    }
+
     /// Generate HTTPRoute with host routing
     pub fn http_route_host(
         &self,
@@ -519,23 +649,56 @@ impl ManifestGenerator {
 
     /// Generate HTTPRoute with HTTPS redirect
     pub fn http_route_redirect_https(&self, name: &str, gateway_name: &str) -> HttpRouteManifest {
-        let mut route = self.http_route(name, gateway_name);
-        route.spec.rules = Some(vec![HttpRouteRule {
-            matches: None,
-            filters: Some(vec![HttpRouteFilter {
-                filter_type: "RequestRedirect".to_string(),
-                request_header_modifier: None,
-                response_header_modifier: None,
-                request_redirect: Some(RequestRedirect {
+        self.http_route_redirects(
+            name,
+            gateway_name,
+            vec![(
+                None,
+                RequestRedirect {
                     scheme: Some("https".to_string()),
                     hostname: None,
+                    path: None,
                     port: Some(443),
                     status_code: Some(301),
+                },
+            )],
+        )
+    }
+
+    /// Generate HTTPRoute with one redirect rule per path prefix (None = all paths)
+    pub fn http_route_redirects(
+        &self,
+        name: &str,
+        gateway_name: &str,
+        redirects: Vec<(Option<String>, RequestRedirect)>,
+    ) -> HttpRouteManifest {
+        let mut route = self.http_route(name, gateway_name);
+        let rules = redirects
+            .into_iter()
+            .map(|(prefix, redirect)| HttpRouteRule {
+                matches: prefix.map(|prefix| {
+                    vec![HttpRouteMatch {
+                        path: Some(PathMatch {
+                            match_type: "PathPrefix".to_string(),
+                            value: prefix,
+                        }),
+                        headers: None,
+                        query_params: None,
+                        method: None,
+                    }]
                 }),
-                url_rewrite: None,
-            }]),
-            backend_refs: None,
-        }]);
+                filters: Some(vec![HttpRouteFilter {
+                    filter_type: "RequestRedirect".to_string(),
+                    request_header_modifier: None,
+                    response_header_modifier: None,
+                    request_redirect: Some(redirect),
+                    url_rewrite: None,
+                    extension_ref: None,
+                }]),
+                backend_refs: None,
+            })
+            .collect();
+        route.spec.rules = Some(rules);
         route
     }
 
@@ -573,6 +736,7 @@ impl ManifestGenerator {
                         replace_full_path: None,
                     }),
                 }),
+                extension_ref: None,
             }]),
             backend_refs: Some(vec![BackendRef {
                 name: backend.to_string(),
@@ -584,6 +748,601 @@ impl ManifestGenerator {
         route
     }
 
+    /// Generate HTTPRoute rewriting the Host header toward the backend
+    pub fn http_route_hostname_rewrite(
+        &self,
+        name: &str,
+        gateway_name: &str,
+        path_prefix: &str,
+        hostname: &str,
+        backend: &str,
+        port: u16,
+    ) -> HttpRouteManifest {
+        let mut route = self.http_route_path(name, gateway_name, path_prefix, backend, port);
+        if let Some(rule) = route
+            .spec
+            .rules
+            .as_mut()
+            .and_then(|rules| rules.first_mut())
+        {
+            rule.filters = Some(vec![HttpRouteFilter {
+                filter_type: "URLRewrite".to_string(),
+                request_header_modifier: None,
+                response_header_modifier: None,
+                request_redirect: None,
+                url_rewrite: Some(UrlRewrite {
+                    hostname: Some(hostname.to_string()),
+                    path: None,
+                }),
+                extension_ref: None,
+            }]);
+        }
+        route
+    }
+
+    /// Generate the implementation's consistent-hash policy keyed on a request
+    /// header; None if the implementation has no such policy
+    pub fn consistent_hash_policy(
+        &self,
+        name: &str,
+        route_name: &str,
+        service: &str,
+        header: &str,
+    ) -> Option<PolicyManifest> {
+        let service_ref = serde_json::json!([{ "group": "", "kind": "Service", "name": service }]);
+        let (api_version, spec) = match self.gateway_impl {
+            GatewayImpl::Envoy => (
+                "gateway.envoyproxy.io/v1alpha1",
+                serde_json::json!({
+                    "targetRefs": [{
+                        "group": "gateway.networking.k8s.io",
+                        "kind": "HTTPRoute",
+                        "name": route_name,
+                    }],
+                    "loadBalancer": {
+                        "type": "ConsistentHash",
+                        "consistentHash": { "type": "Header", "header": { "name": header } },
+                    },
+                }),
+            ),
+            GatewayImpl::Istio => (
+                "networking.istio.io/v1",
+                serde_json::json!({
+                    "host": format!("{service}.{}.svc.cluster.local", self.namespace),
+                    "trafficPolicy": {
+                        "loadBalancer": { "consistentHash": { "httpHeaderName": header } },
+                    },
+                }),
+            ),
+            GatewayImpl::Nginx => (
+                "gateway.nginx.org/v1alpha1",
+                serde_json::json!({
+                    "targetRefs": service_ref,
+                    "loadBalancingMethod": "hash consistent",
+                    "hashMethodKey": format!("$http_{}", header.to_lowercase().replace('-', "_")),
+                }),
+            ),
+            // Attached through the Service's konghq.com/upstream-policy annotation
+            GatewayImpl::Kong => (
+                "configuration.konghq.com/v1beta1",
+                serde_json::json!({
+                    "algorithm": "consistent-hashing",
+                    "hashOn": { "header": header },
+                }),
+            ),
+            GatewayImpl::Kgateway => (
+                "gateway.kgateway.dev/v1alpha1",
+                serde_json::json!({
+                    "targetRefs": service_ref,
+                    "loadBalancer": {
+                        "ringHash": { "hashPolicies": [{ "header": { "name": header } }] },
+                    },
+                }),
+            ),
+            // Policies of user-defined implementations are not known here
+            GatewayImpl::Cilium
+            | GatewayImpl::Traefik
+            | GatewayImpl::Contour
+            | GatewayImpl::Apisix
+            | GatewayImpl::Custom(_) => return None,
+        };
+
+        Some(PolicyManifest {
+            api_version: api_version.to_string(),
+            kind: self.gateway_impl.consistent_hash_policy()?.to_string(),
+            metadata: Metadata {
+                name: name.to_string(),
+                namespace: Some(self.namespace.clone()),
+                labels: self.default_labels(),
+                annotations: BTreeMap::new(),
+            },
+            spec,
+            fields: serde_json::Map::new(),
+        })
+    }
+
+    /// Generate the implementation's backend TLS policy for `service`: the
+    /// gateway verifies the backend certificate for `hostname` against the CA
+    /// in ConfigMap `ca_config_map` (key `ca.crt`) and, where the
+    /// implementation supports it, presents the client certificate in
+    /// `client_secret`; None if the implementation has no such policy
+    pub fn backend_tls_policy(
+        &self,
+        name: &str,
+        service: &str,
+        hostname: &str,
+        ca_config_map: &str,
+        client_secret: &str,
+    ) -> Option<PolicyManifest> {
+        let kind = self.gateway_impl.backend_tls_policy()?;
+        let (api_version, name, spec) = match kind {
+            "BackendTLSPolicy" => (
+                "gateway.networking.k8s.io/v1alpha3",
+                name,
+                serde_json::json!({
+                    "targetRefs": [{ "group": "", "kind": "Service", "name": service }],
+                    "validation": {
+                        "caCertificateRefs": [{
+                            "group": "",
+                            "kind": "ConfigMap",
+                            "name": ca_config_map,
+                        }],
+                        "hostname": hostname,
+                    },
+                }),
+            ),
+            // Applies to the Service of the same name
+            "ApisixUpstream" => (
+                "apisix.apache.org/v2",
+                service,
+                serde_json::json!({
+                    "scheme": "https",
+                    "tlsSecret": { "name": client_secret, "namespace": self.namespace },
+                }),
+            ),
+            // Policies of user-defined implementations are not known here
+            _ => return None,
+        };
+
+        Some(PolicyManifest {
+            api_version: api_version.to_string(),
+            kind: kind.to_string(),
+            metadata: Metadata {
+                name: name.to_string(),
+                namespace: Some(self.namespace.clone()),
+                labels: self.default_labels(),
+                annotations: BTreeMap::new(),
+            },
+            spec,
+            fields: serde_json::Map::new(),
+        })
+    }
+
+    /// Generate the implementation's policy pinning clients of `service` to
+    /// one backend with the session cookie `cookie` (source-IP hashing on
+    /// NGINX, where cookie persistence needs NGINX Plus); None if the
+    /// implementation has no such policy
+    pub fn session_affinity_policy(
+        &self,
+        name: &str,
+        route_name: &str,
+        service: &str,
+        cookie: &str,
+    ) -> Option<PolicyManifest> {
+        self.affinity_policy(name, route_name, service, Some(cookie))
+    }
+
+    /// Generate the implementation's policy pinning each client IP address
+    /// to one backend of `service`; None if the implementation has no such
+    /// policy
+    pub fn source_ip_affinity_policy(
+        &self,
+        name: &str,
+        route_name: &str,
+        service: &str,
+    ) -> Option<PolicyManifest> {
+        self.affinity_policy(name, route_name, service, None)
+    }
+
+    /// Affinity policy hashing on `cookie`, or on the client IP when None
+    fn affinity_policy(
+        &self,
+        name: &str,
+        route_name: &str,
+        service: &str,
+        cookie: Option<&str>,
+    ) -> Option<PolicyManifest> {
+        let kind = self.gateway_impl.session_affinity_policy()?;
+        let service_ref = serde_json::json!([{ "group": "", "kind": "Service", "name": service }]);
+        let (api_version, name, spec) = match self.gateway_impl {
+            GatewayImpl::Envoy => (
+                "gateway.envoyproxy.io/v1alpha1",
+                name,
+                serde_json::json!({
+                    "targetRefs": [{
+                        "group": "gateway.networking.k8s.io",
+                        "kind": "HTTPRoute",
+                        "name": route_name,
+                    }],
+                    "loadBalancer": {
+                        "type": "ConsistentHash",
+                        "consistentHash": match cookie {
+                            Some(cookie) => serde_json::json!({
+                                "type": "Cookie",
+                                "cookie": { "name": cookie, "ttl": "1h" },
+                            }),
+                            None => serde_json::json!({ "type": "SourceIP" }),
+                        },
+                    },
+                }),
+            ),
+            GatewayImpl::Istio => (
+                "networking.istio.io/v1",
+                name,
+                serde_json::json!({
+                    "host": format!("{service}.{}.svc.cluster.local", self.namespace),
+                    "trafficPolicy": {
+                        "loadBalancer": {
+                            "consistentHash": match cookie {
+                                Some(cookie) => serde_json::json!({
+                                    "httpCookie": { "name": cookie, "path": "/", "ttl": "3600s" },
+                                }),
+                                None => serde_json::json!({ "useSourceIp": true }),
+                            },
+                        },
+                    },
+                }),
+            ),
+            GatewayImpl::Nginx => (
+                "gateway.nginx.org/v1alpha1",
+                name,
+                serde_json::json!({
+                    "targetRefs": service_ref,
+                    "loadBalancingMethod": "ip_hash",
+                }),
+            ),
+            // Attached through the Service's konghq.com/upstream-policy annotation
+            GatewayImpl::Kong => (
+                "configuration.konghq.com/v1beta1",
+                name,
+                serde_json::json!({
+                    "algorithm": "consistent-hashing",
+                    "hashOn": match cookie {
+                        Some(cookie) => serde_json::json!({ "cookie": cookie, "cookiePath": "/" }),
+                        None => serde_json::json!({ "input": "ip" }),
+                    },
+                }),
+            ),
+            GatewayImpl::Kgateway => (
+                "gateway.kgateway.dev/v1alpha1",
+                name,
+                serde_json::json!({
+                    "targetRefs": service_ref,
+                    "loadBalancer": {
+                        "ringHash": {
+                            "hashPolicies": [match cookie {
+                                Some(cookie) => serde_json::json!({
+                                    "cookie": { "name": cookie, "ttl": "1h" },
+                                }),
+                                None => serde_json::json!({ "sourceIP": true }),
+                            }],
+                        },
+                    },
+                }),
+            ),
+            // Applies to the Service of the same name
+            GatewayImpl::Apisix => (
+                "apisix.apache.org/v2",
+                service,
+                serde_json::json!({
+                    "loadbalancer": match cookie {
+                        Some(cookie) => {
+                            serde_json::json!({ "type": "chash", "hashOn": "cookie", "key": cookie })
+                        }
+                        None => {
+                            serde_json::json!({ "type": "chash", "hashOn": "vars", "key": "remote_addr" })
+                        }
+                    },
+                }),
+            ),
+            // Policies of user-defined implementations are not known here
+            GatewayImpl::Cilium
+            | GatewayImpl::Traefik
+            | GatewayImpl::Contour
+            | GatewayImpl::Custom(_) => return None,
+        };
+
+        Some(PolicyManifest {
+            api_version: api_version.to_string(),
+            kind: kind.to_string(),
+            metadata: Metadata {
+                name: name.to_string(),
+                namespace: Some(self.namespace.clone()),
+                labels: self.default_labels(),
+                annotations: BTreeMap::new(),
+            },
+            spec,
+            fields: serde_json::Map::new(),
+        })
+    }
+
+    /// Service annotation that attaches `policy` to the backend Service, for
+    /// policies the implementation only picks up from there
+    pub fn policy_service_annotation(&self, policy: &PolicyManifest) -> Option<(String, String)> {
+        (policy.kind == "KongUpstreamPolicy").then(|| {
+            (
+                "konghq.com/upstream-policy".to_string(),
+                policy.metadata.name.clone(),
+            )
+        })
+    }
+
+    /// Generate the implementation's policy limiting `route_name` to
+    /// `requests_per_second` with bursts of up to `burst`; None if the
+    /// implementation has no such policy
+    ///
+    /// Kong plugins and Traefik middlewares only apply once the route refers
+    /// to them (see [`attach_policy`](Self::attach_policy)). Istio's
+    /// local rate limit covers every route of `gateway_name`.
+    pub fn rate_limit_policy(
+        &self,
+        name: &str,
+        route_name: &str,
+        gateway_name: &str,
+        requests_per_second: u32,
+        burst: u32,
+    ) -> Option<PolicyManifest> {
+        let route_ref = serde_json::json!([{
+            "group": "gateway.networking.k8s.io",
+            "kind": "HTTPRoute",
+            "name": route_name,
+        }]);
+        let mut fields = serde_json::Map::new();
+        let (api_version, spec) = match self.gateway_impl {
+            GatewayImpl::Envoy => (
+                "gateway.envoyproxy.io/v1alpha1",
+                serde_json::json!({
+                    "targetRefs": route_ref,
+                    "rateLimit": {
+                        "type": "Local",
+                        "local": {
+                            "rules": [{
+                                "limit": { "requests": requests_per_second, "unit": "Second" },
+                            }],
+                        },
+                    },
+                }),
+            ),
+            GatewayImpl::Istio => (
+                "networking.istio.io/v1alpha3",
+                serde_json::json!({
+                    "targetRefs": [{
+                        "group": "gateway.networking.k8s.io",
+                        "kind": "Gateway",
+                        "name": gateway_name,
+                    }],
+                    "configPatches": [{
+                        "applyTo": "HTTP_FILTER",
+                        "match": {
+                            "context": "GATEWAY",
+                            "listener": {
+                                "filterChain": {
+                                    "filter": {
+                                        "name": "envoy.filters.network.http_connection_manager",
+                                        "subFilter": { "name": "envoy.filters.http.router" },
+                                    },
+                                },
+                            },
+                        },
+                        "patch": {
+                            "operation": "INSERT_BEFORE",
+                            "value": {
+                                "name": "envoy.filters.http.local_ratelimit",
+                                "typed_config": {
+                                    "@type": "type.googleapis.com/udpa.type.v1.TypedStruct",
+                                    "type_url": "type.googleapis.com/envoy.extensions.filters.http.local_ratelimit.v3.LocalRateLimit",
+                                    "value": {
+                                        "stat_prefix": "http_local_rate_limiter",
+                                        "token_bucket": {
+                                            "max_tokens": burst,
+                                            "tokens_per_fill": requests_per_second,
+                                            "fill_interval": "1s",
+                                        },
+                                        "filter_enabled": {
+                                            "runtime_key": "local_rate_limit_enabled",
+                                            "default_value": { "numerator": 100, "denominator": "HUNDRED" },
+                                        },
+                                        "filter_enforced": {
+                                            "runtime_key": "local_rate_limit_enforced",
+                                            "default_value": { "numerator": 100, "denominator": "HUNDRED" },
+                                        },
+                                    },
+                                },
+                            },
+                        },
+                    }],
+                }),
+            ),
+            GatewayImpl::Kong => {
+                fields.insert("plugin".to_string(), "rate-limiting".into());
+                fields.insert(
+                    "config".to_string(),
+                    serde_json::json!({ "second": requests_per_second, "policy": "local" }),
+                );
+                ("configuration.konghq.com/v1", serde_json::Value::Null)
+            }
+            GatewayImpl::Traefik => (
+                "traefik.io/v1alpha1",
+                serde_json::json!({
+                    "rateLimit": { "average": requests_per_second, "period": "1s", "burst": burst },
+                }),
+            ),
+            GatewayImpl::Kgateway => (
+                "gateway.kgateway.dev/v1alpha1",
+                serde_json::json!({
+                    "targetRefs": route_ref,
+                    "rateLimit": {
+                        "local": {
+                            "tokenBucket": {
+                                "maxTokens": burst,
+                                "tokensPerFill": requests_per_second,
+                                "fillInterval": "1s",
+                            },
+                        },
+                    },
+                }),
+            ),
+            // Policies of user-defined implementations are not known here
+            GatewayImpl::Nginx
+            | GatewayImpl::Cilium
+            | GatewayImpl::Contour
+            | GatewayImpl::Apisix
+            | GatewayImpl::Custom(_) => return None,
+        };
+
+        Some(PolicyManifest {
+            api_version: api_version.to_string(),
+            kind: self.gateway_impl.rate_limit_policy()?.to_string(),
+            metadata: Metadata {
+                name: name.to_string(),
+                namespace: Some(self.namespace.clone()),
+                labels: self.default_labels(),
+                annotations: BTreeMap::new(),
+            },
+            spec,
+            fields,
+        })
+    }
+
+    /// Generate the implementation's CORS policy for `route_name`, allowing
+    /// `origin` to send `methods` with `headers`; None if the implementation
+    /// has no such policy
+    ///
+    /// Kong plugins and Traefik middlewares only apply once the route refers
+    /// to them (see [`attach_policy`](Self::attach_policy)).
+    pub fn cors_policy(
+        &self,
+        name: &str,
+        route_name: &str,
+        origin: &str,
+        methods: &[&str],
+        headers: &[&str],
+    ) -> Option<PolicyManifest> {
+        let route_ref = serde_json::json!([{
+            "group": "gateway.networking.k8s.io",
+            "kind": "HTTPRoute",
+            "name": route_name,
+        }]);
+        let mut fields = serde_json::Map::new();
+        let (api_version, spec) = match self.gateway_impl {
+            GatewayImpl::Envoy => (
+                "gateway.envoyproxy.io/v1alpha1",
+                serde_json::json!({
+                    "targetRefs": route_ref,
+                    "cors": {
+                        "allowOrigins": [origin],
+                        "allowMethods": methods,
+                        "allowHeaders": headers,
+                        "maxAge": "600s",
+                        "allowCredentials": true,
+                    },
+                }),
+            ),
+            GatewayImpl::Kong => {
+                fields.insert("plugin".to_string(), "cors".into());
+                fields.insert(
+                    "config".to_string(),
+                    serde_json::json!({
+                        "origins": [origin],
+                        "methods": methods,
+                        "headers": headers,
+                        "max_age": 600,
+                        "credentials": true,
+                    }),
+                );
+                ("configuration.konghq.com/v1", serde_json::Value::Null)
+            }
+            GatewayImpl::Traefik => (
+                "traefik.io/v1alpha1",
+                serde_json::json!({
+                    "headers": {
+                        "accessControlAllowOriginList": [origin],
+                        "accessControlAllowMethods": methods,
+                        "accessControlAllowHeaders": headers,
+                        "accessControlMaxAge": 600,
+                        "accessControlAllowCredentials": true,
+                        "addVaryHeader": true,
+                    },
+                }),
+            ),
+            GatewayImpl::Kgateway => (
+                "gateway.kgateway.dev/v1alpha1",
+                serde_json::json!({
+                    "targetRefs": route_ref,
+                    "cors": {
+                        "allowOrigins": [origin],
+                        "allowMethods": methods,
+                        "allowHeaders": headers,
+                        "maxAge": 600,
+                        "allowCredentials": true,
+                    },
+                }),
+            ),
+            // Policies of user-defined implementations are not known here
+            GatewayImpl::Nginx
+            | GatewayImpl::Istio
+            | GatewayImpl::Cilium
+            | GatewayImpl::Contour
+            | GatewayImpl::Apisix
+            | GatewayImpl::Custom(_) => return None,
+        };
+
+        Some(PolicyManifest {
+            api_version: api_version.to_string(),
+            kind: self.gateway_impl.cors_policy()?.to_string(),
+            metadata: Metadata {
+                name: name.to_string(),
+                namespace: Some(self.namespace.clone()),
+                labels: self.default_labels(),
+                annotations: BTreeMap::new(),
+            },
+            spec,
+            fields,
+        })
+    }
+
+    /// Make `route` refer to the plugin or middleware `policy_name`, for
+    /// implementations that attach policies from the route
+    pub fn attach_policy(&self, route: &mut HttpRouteManifest, policy_name: &str) {
+        match self.gateway_impl {
+            GatewayImpl::Kong => {
+                route
+                    .metadata
+                    .annotations
+                    .insert("konghq.com/plugins".to_string(), policy_name.to_string());
+            }
+            GatewayImpl::Traefik => {
+                for rule in route.spec.rules.iter_mut().flatten() {
+                    rule.filters
+                        .get_or_insert_with(Vec::new)
+                        .push(HttpRouteFilter {
+                            filter_type: "ExtensionRef".to_string(),
+                            request_header_modifier: None,
+                            response_header_modifier: None,
+                            request_redirect: None,
+                            url_rewrite: None,
+                            extension_ref: Some(ExtensionRef {
+                                group: "traefik.io".to_string(),
+                                kind: "Middleware".to_string(),
+                                name: policy_name.to_string(),
+                            }),
+                        });
+                }
+            }
+            _ => {}
+        }
+    }
+
     /// Convert manifest to YAML
     pub fn to_yaml<T: Serialize>(manifest: &T) -> String {
         serde_yaml::to_string(manifest).unwrap_or_default()
@@ -594,11 +1353,11 @@ impl ManifestGenerator {
         serde_json::to_string_pretty(manifest).unwrap_or_default()
     }
 
-    fn default_labels(&self) -> BTreeMap<String, String> {
-        let mut labels = BTreeMap::new();
+    pub(super) fn default_labels(&self) -> BTreeMap<String, String> {
+        let mut labels = owner_labels();
         labels.insert(
-            "app.kubernetes.io/managed-by".to_string(),
-            "gateway-poc".to_string(),
+            GATEWAY_LABEL.to_string(),
+            self.gateway_impl.short_name().to_string(),
         );
         labels
     }
@@ -639,6 +1398,249 @@ mod tests {
         assert_eq!(backends[1].weight, Some(20));
     }
 
+    #[test]
+    fn test_hostname_rewrite_route() {
+        let gen = ManifestGenerator::new(GatewayImpl::Cilium);
+        let route = gen.http_route_hostname_rewrite(
+            "rewrite",
+            "gateway",
+            "/rewrite-host",
+            "rewritten.example.com",
+            "echo",
+            8080,
+        );
+
+        let yaml = ManifestGenerator::to_yaml(&route);
+        assert!(yaml.contains("type: URLRewrite"));
+        assert!(yaml.contains("hostname: rewritten.example.com"));
+        assert!(!yaml.contains("replacePrefixMatch"));
+    }
+
+    #[test]
+    fn test_cross_namespace_route_and_grant() {
+        let gen = ManifestGenerator::new(GatewayImpl::Envoy).namespace("gw-tests");
+        let route = gen.http_route_cross_namespace(
+            "cross-ns",
+            "gateway",
+            "/ns-a",
+            "service-a",
+            "namespace-a",
+            8080,
+        );
+        let rules = route.spec.rules.unwrap();
+        let backend = &rules[0].backend_refs.as_ref().unwrap()[0];
+        assert_eq!(backend.namespace.as_deref(), Some("namespace-a"));
+
+        let grant = gen.reference_grant("allow", "namespace-a", &["service-a"]);
+        assert_eq!(grant.metadata.namespace.as_deref(), Some("namespace-a"));
+        assert_eq!(grant.spec.from[0].namespace, "gw-tests");
+        let yaml = ManifestGenerator::to_yaml(&grant);
+        assert!(yaml.contains("kind: ReferenceGrant"));
+        assert!(yaml.contains("group: ''"));
+        assert!(yaml.contains("name: service-a"));
+
+        let open = gen.reference_grant("allow-all", "namespace-b", &[]);
+        assert!(open.spec.to[0].name.is_none());
+    }
+
+    #[test]
+    fn test_consistent_hash_policy() {
+        let envoy = ManifestGenerator::new(GatewayImpl::Envoy)
+            .consistent_hash_policy("hash", "hash-route", "hash-backend", "X-User-Id")
+            .unwrap();
+        assert_eq!(envoy.kind, "BackendTrafficPolicy");
+        let yaml = ManifestGenerator::to_yaml(&envoy);
+        assert!(yaml.contains("type: ConsistentHash"));
+        assert!(yaml.contains("name: hash-route"));
+
+        let nginx = ManifestGenerator::new(GatewayImpl::Nginx)
+            .consistent_hash_policy("hash", "hash-route", "hash-backend", "X-User-Id")
+            .unwrap();
+        assert_eq!(nginx.spec["hashMethodKey"], "$http_x_user_id");
+
+        assert!(ManifestGenerator::new(GatewayImpl::Traefik)
+            .consistent_hash_policy("hash", "hash-route", "hash-backend", "X-User-Id")
+            .is_none());
+    }
+
+    #[test]
+    fn test_backend_tls_policy() {
+        let nginx = ManifestGenerator::new(GatewayImpl::Nginx)
+            .namespace("gw-tests")
+            .backend_tls_policy(
+                "tls",
+                "tls-backend",
+                "tls-backend.example.com",
+                "tls-ca",
+                "tls-client",
+            )
+            .unwrap();
+        assert_eq!(nginx.kind, "BackendTLSPolicy");
+        assert_eq!(nginx.metadata.name, "tls");
+        assert_eq!(nginx.spec["targetRefs"][0]["name"], "tls-backend");
+        assert_eq!(
+            nginx.spec["validation"]["caCertificateRefs"][0]["name"],
+            "tls-ca"
+        );
+
+        let apisix = ManifestGenerator::new(GatewayImpl::Apisix)
+            .namespace("gw-tests")
+            .backend_tls_policy(
+                "tls",
+                "tls-backend",
+                "tls-backend.example.com",
+                "tls-ca",
+                "tls-client",
+            )
+            .unwrap();
+        assert_eq!(apisix.metadata.name, "tls-backend");
+        assert_eq!(apisix.spec["scheme"], "https");
+        assert_eq!(apisix.spec["tlsSecret"]["namespace"], "gw-tests");
+
+        assert!(ManifestGenerator::new(GatewayImpl::Cilium)
+            .backend_tls_policy("tls", "svc", "host", "ca", "client")
+            .is_none());
+    }
+
+    #[test]
+    fn test_session_affinity_policy() {
+        let envoy = ManifestGenerator::new(GatewayImpl::Envoy)
+            .session_affinity_policy("sticky", "session", "session-backend", "session-id")
+            .unwrap();
+        assert_eq!(
+            envoy.spec["loadBalancer"]["consistentHash"]["cookie"]["name"],
+            "session-id"
+        );
+
+        let kong = ManifestGenerator::new(GatewayImpl::Kong);
+        let policy = kong
+            .session_affinity_policy("sticky", "session", "session-backend", "session-id")
+            .unwrap();
+        assert_eq!(policy.spec["hashOn"]["cookie"], "session-id");
+        assert_eq!(
+            kong.policy_service_annotation(&policy),
+            Some((
+                "konghq.com/upstream-policy".to_string(),
+                "sticky".to_string()
+            ))
+        );
+
+        let apisix = ManifestGenerator::new(GatewayImpl::Apisix)
+            .session_affinity_policy("sticky", "session", "session-backend", "session-id")
+            .unwrap();
+        assert_eq!(apisix.metadata.name, "session-backend");
+        assert!(ManifestGenerator::new(GatewayImpl::Apisix)
+            .policy_service_annotation(&apisix)
+            .is_none());
+
+        assert!(ManifestGenerator::new(GatewayImpl::Contour)
+            .session_affinity_policy("sticky", "session", "session-backend", "session-id")
+            .is_none());
+
+        let envoy = ManifestGenerator::new(GatewayImpl::Envoy)
+            .source_ip_affinity_policy("sticky", "session", "session-backend")
+            .unwrap();
+        assert_eq!(
+            envoy.spec["loadBalancer"]["consistentHash"]["type"],
+            "SourceIP"
+        );
+        let istio = ManifestGenerator::new(GatewayImpl::Istio)
+            .source_ip_affinity_policy("sticky", "session", "session-backend")
+            .unwrap();
+        assert_eq!(
+            istio.spec["trafficPolicy"]["loadBalancer"]["consistentHash"]["useSourceIp"],
+            true
+        );
+    }
+
+    #[test]
+    fn test_rate_limit_policy() {
+        let envoy = ManifestGenerator::new(GatewayImpl::Envoy)
+            .rate_limit_policy("limit", "limited", "gw", 10, 5)
+            .unwrap();
+        assert_eq!(envoy.kind, "BackendTrafficPolicy");
+        assert_eq!(envoy.spec["targetRefs"][0]["name"], "limited");
+        assert_eq!(
+            envoy.spec["rateLimit"]["local"]["rules"][0]["limit"]["requests"],
+            10
+        );
+
+        let kong = ManifestGenerator::new(GatewayImpl::Kong);
+        let plugin = kong
+            .rate_limit_policy("limit", "limited", "gw", 10, 5)
+            .unwrap();
+        let yaml = ManifestGenerator::to_yaml(&plugin);
+        assert!(yaml.contains("plugin: rate-limiting"));
+        assert!(!yaml.contains("spec:"));
+        let mut route = kong.http_route_path("limited", "gw", "/rate-limited", "echo", 8080);
+        kong.attach_policy(&mut route, "limit");
+        assert_eq!(route.metadata.annotations["konghq.com/plugins"], "limit");
+
+        let traefik = ManifestGenerator::new(GatewayImpl::Traefik);
+        let mut route = traefik.http_route_path("limited", "gw", "/rate-limited", "echo", 8080);
+        traefik.attach_policy(&mut route, "limit");
+        let yaml = ManifestGenerator::to_yaml(&route);
+        assert!(yaml.contains("type: ExtensionRef"));
+        assert!(yaml.contains("kind: Middleware"));
+
+        assert!(ManifestGenerator::new(GatewayImpl::Cilium)
+            .rate_limit_policy("limit", "limited", "gw", 10, 5)
+            .is_none());
+    }
+
+    #[test]
+    fn test_cors_policy() {
+        let methods = ["GET", "PUT"];
+        let envoy = ManifestGenerator::new(GatewayImpl::Envoy)
+            .cors_policy(
+                "cors",
+                "cors-route",
+                "https://a.example",
+                &methods,
+                &["X-A"],
+            )
+            .unwrap();
+        assert_eq!(envoy.kind, "SecurityPolicy");
+        assert_eq!(envoy.spec["targetRefs"][0]["name"], "cors-route");
+        assert_eq!(envoy.spec["cors"]["allowOrigins"][0], "https://a.example");
+
+        let kong = ManifestGenerator::new(GatewayImpl::Kong)
+            .cors_policy(
+                "cors",
+                "cors-route",
+                "https://a.example",
+                &methods,
+                &["X-A"],
+            )
+            .unwrap();
+        assert!(ManifestGenerator::to_yaml(&kong).contains("plugin: cors"));
+
+        let traefik = ManifestGenerator::new(GatewayImpl::Traefik)
+            .cors_policy(
+                "cors",
+                "cors-route",
+                "https://a.example",
+                &methods,
+                &["X-A"],
+            )
+            .unwrap();
+        assert_eq!(traefik.kind, "Middleware");
+        assert_eq!(
+            traefik.spec["headers"]["accessControlAllowMethods"][1],
+            "PUT"
+        );
+
+        assert!(ManifestGenerator::new(GatewayImpl::Cilium)
+            .cors_policy(
+                "cors",
+                "cors-route",
+                "https://a.example",
+                &methods,
+                &["X-A"]
+            )
+            .is_none());
+    }
+
     #[test]
     fn test_to_yaml() {
         let gen = ManifestGenerator::new(GatewayImpl::Nginx);
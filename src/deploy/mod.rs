@@ -6,15 +6,29 @@
 #![allow(dead_code)]
 #![allow(unused_imports)]
 
+mod bundle;
+mod cleanup;
+mod detect;
+mod doctor;
 mod health;
 mod installer;
+mod kubectl;
 mod manifest;
+mod setup;
 
+pub use bundle::{BundleFile, TestBundle};
+pub use cleanup::{count_by_kind, Cleanup, ManagedResource};
+pub use detect::{detect_gateways, select_gateway, DetectedGateway};
+pub use doctor::{CheckStatus, Doctor, DoctorCheck, DoctorReport};
 pub use health::{
     HealthCheck, HealthCheckConfig, HealthChecker, HealthStatus, PreFlightChecker, PreFlightResult,
 };
-pub use installer::{GatewayInstaller, InstallResult, InstallStatus, InstallerConfig};
+pub use installer::{
+    installed_gateway_api, GatewayInstaller, InstallResult, InstallStatus, InstallerConfig,
+    PlannedStep,
+};
 pub use manifest::{
     BackendRef, GatewayManifest, HttpRouteManifest, HttpRouteRule, Listener, ManifestGenerator,
-    Metadata, ParentRef,
+    Metadata, ParentRef, PathRewrite, PolicyManifest, ReferenceGrantManifest, RequestRedirect,
 };
+pub use setup::capture_setup;
@@ -0,0 +1,180 @@
+//! Post-install configuration capture
+//!
+//! Reads back what an install is actually running with: computed Helm
+//! values, the GatewayClass and its parameters resource, and the gateway's
+//! ConfigMaps. Each source is best effort; installs that did not go through
+//! Helm simply have no release recorded.
+
+use serde_json::Value;
+use std::collections::BTreeMap;
+use tokio::process::Command;
+use tracing::{debug, info};
+
+use super::installer::InstallResult;
+use super::kubectl::kubectl;
+use crate::models::{redact, redact_text, GatewayImpl, GatewaySetup, HelmRelease};
+
+/// ConfigMaps holding controller configuration outside the Helm release labels
+fn known_config_maps(gateway: GatewayImpl) -> &'static [&'static str] {
+    match gateway {
+        GatewayImpl::Envoy => &["envoy-gateway-config"],
+        GatewayImpl::Istio => &["istio", "istio-sidecar-injector"],
+        GatewayImpl::Cilium => &["cilium-config"],
+        GatewayImpl::Contour => &["contour"],
+        _ => &[],
+    }
+}
+
+/// Capture the effective configuration of an installed gateway
+pub async fn capture_setup(install: &InstallResult) -> GatewaySetup {
+    info!("Capturing {} configuration", install.gateway.name());
+    let mut setup = GatewaySetup::new(install.gateway.name());
+
+    setup.helm = helm_release(&install.release_name, &install.namespace).await;
+
+    if let Some(class) = kubectl_json(&["get", "gatewayclass", &install.gateway_class]).await {
+        let spec = class["spec"].clone();
+        setup.class_parameters = class_parameters(&spec).await;
+        setup.gateway_class = Some(spec);
+    }
+
+    let selector = format!("app.kubernetes.io/instance={}", install.release_name);
+    if let Some(list) = kubectl_json(&[
+        "get",
+        "configmaps",
+        "-n",
+        &install.namespace,
+        "-l",
+        &selector,
+    ])
+    .await
+    {
+        for item in list["items"].as_array().into_iter().flatten() {
+            add_config_map(&mut setup, item);
+        }
+    }
+    for name in known_config_maps(install.gateway) {
+        if let Some(item) =
+            kubectl_json(&["get", "configmap", name, "-n", &install.namespace]).await
+        {
+            add_config_map(&mut setup, &item);
+        }
+    }
+
+    setup
+}
+
+/// Computed values and chart of a Helm release (None if not installed by Helm)
+async fn helm_release(name: &str, namespace: &str) -> Option<HelmRelease> {
+    let mut values = command_json(
+        "helm",
+        &[
+            "get", "values", name, "-n", namespace, "--all", "-o", "json",
+        ],
+    )
+    .await?;
+    redact(&mut values);
+
+    let filter = format!("^{name}$");
+    let listed = command_json(
+        "helm",
+        &["list", "-n", namespace, "--filter", &filter, "-o", "json"],
+    )
+    .await;
+    let entry = listed.as_ref().and_then(|l| l.get(0));
+    let field = |key: &str| entry.and_then(|e| e[key].as_str()).map(str::to_string);
+
+    Some(HelmRelease {
+        name: name.to_string(),
+        namespace: namespace.to_string(),
+        chart: field("chart"),
+        app_version: field("app_version"),
+        values,
+    })
+}
+
+/// Spec of the resource a GatewayClass `parametersRef` points at
+async fn class_parameters(class_spec: &Value) -> Option<Value> {
+    let reference = &class_spec["parametersRef"];
+    let kind = reference["kind"].as_str()?;
+    let name = reference["name"].as_str()?;
+    let resource = match reference["group"].as_str() {
+        Some(group) if !group.is_empty() => format!("{kind}.{group}"),
+        _ => kind.to_lowercase(),
+    };
+
+    let mut args = vec!["get", resource.as_str(), name];
+    if let Some(namespace) = reference["namespace"].as_str() {
+        args.extend(["-n", namespace]);
+    }
+    let object = kubectl_json(&args).await?;
+    object.get("spec").or_else(|| object.get("data")).cloned()
+}
+
+fn add_config_map(setup: &mut GatewaySetup, item: &Value) {
+    let metadata = &item["metadata"];
+    let key = format!(
+        "{}/{}",
+        metadata["namespace"].as_str().unwrap_or_default(),
+        metadata["name"].as_str().unwrap_or_default()
+    );
+    let data: BTreeMap<String, String> = item["data"]
+        .as_object()
+        .map(|map| {
+            map.iter()
+                .filter_map(|(k, v)| Some((k.clone(), redact_text(k, v.as_str()?))))
+                .collect()
+        })
+        .unwrap_or_default();
+    setup.config_maps.insert(key, data);
+}
+
+async fn kubectl_json(args: &[&str]) -> Option<Value> {
+    let mut args = args.to_vec();
+    args.extend(["-o", "json"]);
+    match kubectl(&args).await {
+        Ok(output) if output.success => serde_json::from_str(&output.stdout).ok(),
+        Ok(output) => {
+            debug!("kubectl {}: {}", args.join(" "), output.stderr.trim());
+            None
+        }
+        Err(e) => {
+            debug!("kubectl {}: {}", args.join(" "), e);
+            None
+        }
+    }
+}
+
+async fn command_json(program: &str, args: &[&str]) -> Option<Value> {
+    let output = Command::new(program).args(args).output().await.ok()?;
+    if !output.status.success() {
+        debug!(
+            "{program} {}: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+        return None;
+    }
+    serde_json::from_slice(&output.stdout).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_add_config_map() {
+        let mut setup = GatewaySetup::new("Cilium Gateway");
+        add_config_map(
+            &mut setup,
+            &json!({
+                "metadata": { "name": "cilium-config", "namespace": "kube-system" },
+                "data": { "enable-gateway-api": "true", "kube-proxy-replacement": "true" },
+            }),
+        );
+        let data = &setup.config_maps["kube-system/cilium-config"];
+        assert_eq!(data["enable-gateway-api"], "true");
+        assert_eq!(data.len(), 2);
+    }
+}
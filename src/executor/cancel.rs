@@ -0,0 +1,199 @@
+//! Suite cancellation
+//!
+//! Stops a whole test run early, either because its time budget is spent
+//! (`--suite-timeout`) or because the user pressed Ctrl-C. A timeout
+//! aborts tests still running; an interrupt lets them finish, and a second
+//! one aborts them. Either way their fixtures are torn down and tests not
+//! yet started are skipped.
+
+use std::fmt;
+use std::future::Future;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+use tracing::warn;
+
+use crate::models::{TestCase, TestResult};
+
+/// Exit code after an interrupted run
+pub const EXIT_INTERRUPTED: i32 = 130;
+
+/// Why a suite stopped early
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StopReason {
+    /// Suite timeout (seconds) exceeded
+    Timeout(u64),
+    /// Ctrl-C
+    Interrupted,
+}
+
+impl fmt::Display for StopReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StopReason::Timeout(secs) => write!(f, "suite timeout of {secs}s exceeded"),
+            StopReason::Interrupted => write!(f, "interrupted"),
+        }
+    }
+}
+
+/// Cancellation shared by the runners of one suite
+#[derive(Clone, Debug, Default)]
+pub struct SuiteCancel {
+    /// Cancelled once no further tests should start
+    stop: CancellationToken,
+    /// Cancelled once running tests should be aborted
+    abort: CancellationToken,
+    reason: Arc<OnceLock<StopReason>>,
+}
+
+impl SuiteCancel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stop the suite; the first reason given is kept
+    pub fn cancel(&self, reason: StopReason) {
+        let _ = self.reason.set(reason);
+        self.stop.cancel();
+        if matches!(reason, StopReason::Timeout(_)) {
+            self.abort.cancel();
+        }
+    }
+
+    /// Abort the tests still running (their teardown still runs)
+    pub fn abort_running(&self) {
+        let _ = self.reason.set(StopReason::Interrupted);
+        self.stop.cancel();
+        self.abort.cancel();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.stop.is_cancelled()
+    }
+
+    /// Why the suite stopped, if it did
+    pub fn reason(&self) -> Option<StopReason> {
+        self.reason.get().copied()
+    }
+
+    /// Cancel the suite once `budget` has elapsed
+    pub fn timeout_after(&self, budget: Duration) {
+        let cancel = self.clone();
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = tokio::time::sleep(budget) => {
+                    warn!("Suite timeout of {}s exceeded; cancelling remaining tests", budget.as_secs());
+                    cancel.cancel(StopReason::Timeout(budget.as_secs()));
+                }
+                _ = cancel.abort.cancelled() => {}
+            }
+        });
+    }
+
+    /// Stop the suite on Ctrl-C; a second Ctrl-C aborts the running tests
+    pub fn cancel_on_ctrl_c(&self) {
+        let cancel = self.clone();
+        tokio::spawn(async move {
+            if tokio::signal::ctrl_c().await.is_err() {
+                return;
+            }
+            warn!("Interrupted; finishing running tests (press Ctrl-C again to abort them)");
+            cancel.cancel(StopReason::Interrupted);
+
+            if tokio::signal::ctrl_c().await.is_ok() {
+                warn!("Aborting running tests; tearing down their fixtures");
+                cancel.abort_running();
+            }
+        });
+    }
+}
+
+/// Run a test unless the suite was stopped, aborting it on suite timeout
+pub(crate) async fn run_cancellable<F>(
+    cancel: &SuiteCancel,
+    test_case: TestCase,
+    test: F,
+) -> TestResult
+where
+    F: Future<Output = TestResult>,
+{
+    if let Some(reason) = cancel.reason() {
+        return TestResult::skip(test_case, format!("Not started: {reason}"));
+    }
+
+    tokio::select! {
+        biased;
+        _ = cancel.abort.cancelled() => {
+            let reason = cancel.reason().unwrap_or(StopReason::Interrupted);
+            TestResult::skip(test_case, format!("Aborted: {reason}"))
+        }
+        result = test => result,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TestStatus;
+
+    #[tokio::test]
+    async fn test_timeout_aborts() {
+        let cancel = SuiteCancel::new();
+        let result = run_cancellable(&cancel, TestCase::HostRouting, async {
+            TestResult::pass(TestCase::HostRouting, 1)
+        })
+        .await;
+        assert_eq!(result.status, TestStatus::Pass);
+
+        cancel.timeout_after(Duration::from_millis(20));
+        let result = run_cancellable(&cancel, TestCase::LoadTest, async {
+            tokio::time::sleep(Duration::from_secs(10)).await;
+            TestResult::pass(TestCase::LoadTest, 10_000)
+        })
+        .await;
+        assert_eq!(result.status, TestStatus::Skip);
+        assert_eq!(
+            result.message.as_deref(),
+            Some("Aborted: suite timeout of 0s exceeded")
+        );
+
+        let result = run_cancellable(&cancel, TestCase::PathRouting, async {
+            TestResult::pass(TestCase::PathRouting, 1)
+        })
+        .await;
+        assert_eq!(result.status, TestStatus::Skip);
+    }
+
+    #[tokio::test]
+    async fn test_interrupt_finishes_running_test() {
+        let cancel = SuiteCancel::new();
+        let running = cancel.clone();
+        let result = run_cancellable(&cancel, TestCase::LoadTest, async move {
+            running.cancel(StopReason::Interrupted);
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            TestResult::pass(TestCase::LoadTest, 10)
+        })
+        .await;
+        assert_eq!(result.status, TestStatus::Pass);
+
+        cancel.cancel(StopReason::Timeout(30));
+        assert_eq!(cancel.reason(), Some(StopReason::Interrupted));
+        let result = run_cancellable(&cancel, TestCase::PathRouting, async {
+            TestResult::pass(TestCase::PathRouting, 1)
+        })
+        .await;
+        assert_eq!(result.message.as_deref(), Some("Not started: interrupted"));
+
+        // A second interrupt aborts what is still running
+        let cancel = SuiteCancel::new();
+        let running = cancel.clone();
+        let result = run_cancellable(&cancel, TestCase::LoadTest, async move {
+            running.cancel(StopReason::Interrupted);
+            running.abort_running();
+            tokio::time::sleep(Duration::from_secs(10)).await;
+            TestResult::pass(TestCase::LoadTest, 10_000)
+        })
+        .await;
+        assert_eq!(result.message.as_deref(), Some("Aborted: interrupted"));
+    }
+}
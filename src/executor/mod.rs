@@ -2,8 +2,13 @@
 //!
 //! Provides sequential and parallel test execution capabilities.
 
+mod cancel;
 mod parallel;
+mod remote;
+mod retry;
 mod runner;
 
+pub use cancel::{StopReason, SuiteCancel, EXIT_INTERRUPTED};
 pub use parallel::{AggregateResult, BatchRunner, ParallelExecutor};
+pub use remote::{forwarded_args, RemoteExecutor};
 pub use runner::TestRunner;
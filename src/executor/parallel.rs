@@ -5,24 +5,49 @@
 #![allow(dead_code)]
 
 use anyhow::Result;
+use chrono::Utc;
 use futures::future::join_all;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Instant;
 use tokio::sync::Semaphore;
-use tracing::{debug, info};
+use tracing::{debug, info, info_span, warn, Instrument};
 
-use crate::http::HttpClient;
+use crate::http::{
+    AuthHeaders, BackendTracker, ConnectionSettings, DnsOverrides, HttpClient, HttpClientConfig,
+    HttpMetrics, TrafficWindow,
+};
+use crate::k8s::{ClusterProbe, CrashWatcher, CrashWindow, DiagnosticsCollector};
 
+use super::cancel::{self, SuiteCancel};
+use super::retry;
 use crate::models::{
-    GatewayConfig, GatewayImpl, TestCase, TestResult, TestRoundSummary, TestStatus,
+    CanarySettings, FailoverSettings, GatewayApiSpec, GatewayConfig, GatewayImpl, HttpTraffic,
+    LoadTestSettings, RetryPolicy, RoundPlan, RoundVariant, SoakReport, SoakSettings, TestCase,
+    TestFilter, TestResult, TestRoundSummary, TestStatus, MAX_CONSECUTIVE_ROUND_ERRORS,
 };
 use crate::tests;
+use crate::tui::{self, ProgressEvent, ProgressReporter};
+use crate::utils::Teardown;
 
 /// Parallel test executor
 pub struct ParallelExecutor {
     max_concurrent: usize,
     timeout_secs: u64,
+    load_test: LoadTestSettings,
+    canary: CanarySettings,
+    failover: FailoverSettings,
+    gateway_api: Option<GatewayApiSpec>,
+    filter: TestFilter,
+    dns_overrides: DnsOverrides,
+    connection: ConnectionSettings,
+    retry: RetryPolicy,
+    progress: Option<ProgressReporter>,
+    crashes: Option<CrashWatcher>,
+    diagnostics: Option<DiagnosticsCollector>,
+    metrics: Option<HttpMetrics>,
+    auth: Option<AuthHeaders>,
+    cancel: SuiteCancel,
 }
 
 impl ParallelExecutor {
@@ -30,14 +55,117 @@ impl ParallelExecutor {
         Self {
             max_concurrent,
             timeout_secs: 30,
+            load_test: LoadTestSettings::default(),
+            canary: CanarySettings::default(),
+            failover: FailoverSettings::default(),
+            gateway_api: None,
+            filter: TestFilter::default(),
+            dns_overrides: DnsOverrides::default(),
+            connection: ConnectionSettings::default(),
+            retry: RetryPolicy::default(),
+            progress: None,
+            crashes: None,
+            diagnostics: None,
+            metrics: None,
+            auth: None,
+            cancel: SuiteCancel::default(),
         }
     }
 
+    /// Run only the tests the filter selects
+    pub fn with_filter(mut self, filter: TestFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Stop the run when the suite is cancelled (suite timeout or Ctrl-C)
+    pub fn with_cancellation(mut self, cancel: SuiteCancel) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// Whether the run was cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+
+    /// Publish per-test progress (e.g. to the dashboard)
+    pub fn with_progress(mut self, reporter: ProgressReporter) -> Self {
+        self.progress = Some(reporter);
+        self
+    }
+
+    /// Resolve test hostnames to fixed addresses (e.g. the gateway IP)
+    pub fn with_dns_overrides(mut self, overrides: DnsOverrides) -> Self {
+        self.dns_overrides = overrides;
+        self
+    }
+
+    /// Reuse connections or open one per request
+    pub fn with_connection(mut self, connection: ConnectionSettings) -> Self {
+        self.connection = connection;
+        self
+    }
+
+    /// Flag rounds during which a gateway pod crashed
+    pub fn with_crash_watcher(mut self, watcher: CrashWatcher) -> Self {
+        self.crashes = Some(watcher);
+        self
+    }
+
+    /// Capture cluster diagnostics for every failed test
+    pub fn with_diagnostics(mut self, collector: DiagnosticsCollector) -> Self {
+        self.diagnostics = Some(collector);
+        self
+    }
+
+    /// Count the HTTP traffic of every test and report it per round
+    pub fn with_http_metrics(mut self, metrics: HttpMetrics) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Authenticate test requests with tokens from a provider
+    pub fn with_auth(mut self, auth: AuthHeaders) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// Re-run failed tests, classifying passes on retry as flaky
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
     pub fn with_timeout(mut self, secs: u64) -> Self {
         self.timeout_secs = secs;
         self
     }
 
+    /// Set Load Test (test 16) parameters
+    pub fn with_load_test(mut self, settings: LoadTestSettings) -> Self {
+        self.load_test = settings;
+        self
+    }
+
+    /// Set Canary Traffic (test 7) parameters
+    pub fn with_canary(mut self, settings: CanarySettings) -> Self {
+        self.canary = settings;
+        self
+    }
+
+    /// Set Failover Recovery (test 17) parameters
+    pub fn with_failover(mut self, settings: FailoverSettings) -> Self {
+        self.failover = settings;
+        self
+    }
+
+    /// Skip tests the given Gateway API spec level does not define
+    pub fn with_gateway_api(mut self, spec: Option<GatewayApiSpec>) -> Self {
+        self.gateway_api = spec;
+        self
+    }
+
     /// Run tests in parallel for a single gateway
     pub async fn run_tests_parallel(
         &self,
@@ -45,58 +173,159 @@ impl ParallelExecutor {
         gateway_config: &GatewayConfig,
         test_cases: Vec<TestCase>,
     ) -> Result<Vec<TestResult>> {
-        let semaphore = Arc::new(Semaphore::new(self.max_concurrent));
-        let client = Arc::new(HttpClient::with_timeout(self.timeout_secs)?);
+        self.run_variant(gateway_ip, gateway_config, test_cases, None)
+            .await
+    }
+
+    /// Run tests in parallel with a round variant's parameter overrides
+    async fn run_variant(
+        &self,
+        gateway_ip: &str,
+        gateway_config: &GatewayConfig,
+        test_cases: Vec<TestCase>,
+        variant: Option<&RoundVariant>,
+    ) -> Result<Vec<TestResult>> {
+        let max_concurrent = variant
+            .and_then(|v| v.concurrency)
+            .unwrap_or(self.max_concurrent);
+        let timeout_secs = variant
+            .and_then(|v| v.timeout_secs)
+            .unwrap_or(self.timeout_secs);
+        let load_test = match variant {
+            Some(v) => v.apply_load_test(&self.load_test),
+            None => self.load_test.clone(),
+        };
+
+        let semaphore = Arc::new(Semaphore::new(max_concurrent));
+        let mut client = HttpClient::from_config(
+            HttpClientConfig::default()
+                .with_timeout(timeout_secs)
+                .with_dns_overrides(self.dns_overrides.clone())
+                .with_connection(&self.connection),
+        )?;
+        if let Some(metrics) = &self.metrics {
+            client = client.with_metrics(metrics.clone());
+        }
+        if let Some(auth) = &self.auth {
+            client = client.with_auth(auth.clone());
+        }
+        let client = Arc::new(client);
 
         let gateway_ip = gateway_ip.to_string();
-        let http_port = gateway_config.http_port;
-        let https_port = gateway_config.https_port;
-        let grpc_port = gateway_config.grpc_port.unwrap_or(9090);
-        let hostname = gateway_config.hostname.clone();
+        let gateway = Arc::new(gateway_config.clone());
 
         let mut handles = Vec::new();
+        let mut unsupported = Vec::new();
 
         for test_case in test_cases {
+            let unsupported_result = match self
+                .gateway_api
+                .and_then(|spec| spec.unsupported_reason(test_case))
+            {
+                Some(reason) => Some(TestResult::skip(test_case, reason)),
+                None => gateway_config
+                    .implementation
+                    .unsupported_reason(test_case)
+                    .map(|reason| TestResult::not_supported(test_case, reason)),
+            };
+            if let Some(result) = unsupported_result {
+                tui::emit(
+                    &self.progress,
+                    ProgressEvent::TestFinished {
+                        result: result.clone(),
+                    },
+                );
+                unsupported.push(result);
+                continue;
+            }
+
             let semaphore = semaphore.clone();
-            let _client = client.clone();
+            let client = client.clone();
             let gateway_ip = gateway_ip.clone();
-            let hostname = hostname.clone();
+            let gateway = gateway.clone();
+            let load_test = load_test.clone();
+            let canary = self.canary.clone();
+            let failover = self.failover.clone();
+            let retry = self.retry.clone();
+            let progress = self.progress.clone();
+            let cancel = self.cancel.clone();
+            let diagnostics = self.diagnostics.clone();
+            let span = info_span!(
+                "test",
+                number = test_case.number(),
+                test = test_case.name(),
+                gateway = gateway_config.implementation.name()
+            );
 
-            let handle = tokio::spawn(async move {
-                let _permit = semaphore.acquire().await.unwrap();
-
-                debug!("Starting parallel execution of {}", test_case);
-                let _start = Instant::now();
-
-                let result = tests::run_test(
-                    test_case,
-                    &gateway_ip,
-                    http_port,
-                    https_port,
-                    grpc_port,
-                    &hostname,
-                )
-                .await;
-
-                match result {
-                    Ok(r) => r,
-                    Err(e) => TestResult::error(test_case, e.to_string()),
+            let handle = tokio::spawn(
+                async move {
+                    // Exclusive tests hold every slot, so nothing overlaps them
+                    let permits = if test_case.exclusive() {
+                        max_concurrent as u32
+                    } else {
+                        1
+                    };
+                    let _permit = semaphore.acquire_many(permits).await.unwrap();
+                    let teardown = Teardown::new();
+                    let started = chrono::Utc::now();
+                    let backends = BackendTracker::new();
+                    let client = (*client).clone().with_backend_tracker(backends.clone());
+                    let run = async {
+                        debug!("Starting parallel execution of {}", test_case);
+                        tui::emit(&progress, ProgressEvent::TestStarted { test: test_case });
+
+                        let (client, gateway_ip, gateway, load_test, canary, failover, teardown) = (
+                            &client,
+                            &gateway_ip,
+                            &gateway,
+                            &load_test,
+                            &canary,
+                            &failover,
+                            &teardown,
+                        );
+                        retry::run_with_retries(&retry, || async move {
+                            tests::run_test(
+                                test_case, client, gateway_ip, gateway, load_test, canary,
+                                failover, teardown,
+                            )
+                            .await
+                            .unwrap_or_else(|e| TestResult::error(test_case, e.to_string()))
+                        })
+                        .await
+                    };
+                    let result = cancel::run_cancellable(&cancel, test_case, run)
+                        .await
+                        .with_backends(backends.hits());
+                    let result = match &diagnostics {
+                        Some(collector) => collector.attach(result, started).await,
+                        None => result,
+                    };
+                    teardown.unwind().await;
+                    tui::emit(
+                        &progress,
+                        ProgressEvent::TestFinished {
+                            result: result.clone(),
+                        },
+                    );
+                    result
                 }
-            });
+                .instrument(span),
+            );
 
             handles.push(handle);
         }
 
-        let results: Vec<TestResult> = join_all(handles)
+        let mut results: Vec<TestResult> = join_all(handles)
             .await
             .into_iter()
             .filter_map(|r| r.ok())
             .collect();
+        results.extend(unsupported);
 
         Ok(results)
     }
 
-    /// Run all 17 tests in parallel
+    /// Run all tests in parallel
     pub async fn run_all_parallel(
         &self,
         gateway_ip: &str,
@@ -108,8 +337,12 @@ impl ParallelExecutor {
         );
 
         let start = Instant::now();
+        let test_cases = self.filter.select(TestCase::all());
+        self.round_started(1, 1, &test_cases);
+        let window = CrashWindow::open(self.crashes.as_ref()).await;
+        let traffic = TrafficWindow::open(self.metrics.as_ref());
         let results = self
-            .run_tests_parallel(gateway_ip, gateway_config, TestCase::all())
+            .run_tests_parallel(gateway_ip, gateway_config, test_cases)
             .await?;
 
         // Sort results by test number
@@ -117,7 +350,12 @@ impl ParallelExecutor {
         sorted_results.sort_by_key(|r| r.test_case.number());
 
         let summary =
-            TestRoundSummary::new(1, gateway_config.implementation.name(), sorted_results);
+            TestRoundSummary::new(1, gateway_config.implementation.name(), sorted_results)
+                .with_gateway_api(self.gateway_api)
+                .with_filter(&self.filter)
+                .with_crashes(window.finish().await)
+                .with_traffic(traffic.finish());
+        self.round_finished(&summary);
 
         info!(
             "Parallel execution completed in {}ms - Pass: {}/{} ({:.1}%)",
@@ -130,6 +368,28 @@ impl ParallelExecutor {
         Ok(summary)
     }
 
+    fn round_started(&self, round: u32, total_rounds: u32, tests: &[TestCase]) {
+        tui::emit(
+            &self.progress,
+            ProgressEvent::RoundStarted {
+                round,
+                total_rounds,
+                tests: tests.to_vec(),
+            },
+        );
+    }
+
+    fn round_finished(&self, summary: &TestRoundSummary) {
+        tui::emit(
+            &self.progress,
+            ProgressEvent::RoundFinished {
+                round: summary.round,
+                passed: summary.passed,
+                total: summary.total,
+            },
+        );
+    }
+
     /// Run tests across multiple gateways in parallel
     pub async fn run_multi_gateway(
         &self,
@@ -143,9 +403,28 @@ impl ParallelExecutor {
         for (implementation, gateway_ip) in gateways {
             let max_concurrent = self.max_concurrent;
             let timeout_secs = self.timeout_secs;
+            let load_test = self.load_test.clone();
+            let canary = self.canary.clone();
+            let failover = self.failover.clone();
+            let gateway_api = self.gateway_api;
+            let filter = self.filter.clone();
+            let dns_overrides = self.dns_overrides.clone();
+            let retry = self.retry.clone();
+            let auth = self.auth.clone();
 
             let handle = tokio::spawn(async move {
-                let executor = ParallelExecutor::new(max_concurrent).with_timeout(timeout_secs);
+                let mut executor = ParallelExecutor::new(max_concurrent)
+                    .with_timeout(timeout_secs)
+                    .with_load_test(load_test)
+                    .with_canary(canary)
+                    .with_failover(failover)
+                    .with_gateway_api(gateway_api)
+                    .with_filter(filter)
+                    .with_dns_overrides(dns_overrides)
+                    .with_retry(retry);
+                if let Some(auth) = auth {
+                    executor = executor.with_auth(auth);
+                }
                 let config = GatewayConfig::new(implementation);
 
                 let result = executor.run_all_parallel(&gateway_ip, &config).await;
@@ -185,6 +464,8 @@ impl Default for ParallelExecutor {
 pub struct BatchRunner {
     executor: ParallelExecutor,
     rounds: u32,
+    probe: Option<ClusterProbe>,
+    plan: RoundPlan,
 }
 
 impl BatchRunner {
@@ -192,9 +473,116 @@ impl BatchRunner {
         Self {
             executor: ParallelExecutor::new(max_concurrent),
             rounds,
+            probe: None,
+            plan: RoundPlan::default(),
         }
     }
 
+    /// Vary parameters across rounds; the plan sets the number of rounds
+    pub fn with_round_plan(mut self, plan: RoundPlan) -> Self {
+        if !plan.is_empty() {
+            self.rounds = plan.total_rounds();
+        }
+        self.plan = plan;
+        self
+    }
+
+    /// Capture cluster health at the start of each round
+    pub fn with_cluster_probe(mut self, probe: ClusterProbe) -> Self {
+        self.probe = Some(probe);
+        self
+    }
+
+    /// Set Load Test (test 16) parameters
+    pub fn with_load_test(mut self, settings: LoadTestSettings) -> Self {
+        self.executor = self.executor.with_load_test(settings);
+        self
+    }
+
+    /// Set Canary Traffic (test 7) parameters
+    pub fn with_canary(mut self, settings: CanarySettings) -> Self {
+        self.executor = self.executor.with_canary(settings);
+        self
+    }
+
+    /// Set Failover Recovery (test 17) parameters
+    pub fn with_failover(mut self, settings: FailoverSettings) -> Self {
+        self.executor = self.executor.with_failover(settings);
+        self
+    }
+
+    /// Skip tests the given Gateway API spec level does not define
+    pub fn with_gateway_api(mut self, spec: Option<GatewayApiSpec>) -> Self {
+        self.executor = self.executor.with_gateway_api(spec);
+        self
+    }
+
+    /// Publish per-test progress (e.g. to the dashboard)
+    pub fn with_progress(mut self, reporter: ProgressReporter) -> Self {
+        self.executor = self.executor.with_progress(reporter);
+        self
+    }
+
+    /// Run only the tests the filter selects
+    pub fn with_filter(mut self, filter: TestFilter) -> Self {
+        self.executor = self.executor.with_filter(filter);
+        self
+    }
+
+    /// Set the per-request HTTP timeout
+    pub fn with_timeout(mut self, secs: u64) -> Self {
+        self.executor = self.executor.with_timeout(secs);
+        self
+    }
+
+    /// Resolve test hostnames to fixed addresses (e.g. the gateway IP)
+    pub fn with_dns_overrides(mut self, overrides: DnsOverrides) -> Self {
+        self.executor = self.executor.with_dns_overrides(overrides);
+        self
+    }
+
+    /// Reuse connections or open one per request
+    pub fn with_connection(mut self, connection: ConnectionSettings) -> Self {
+        self.executor = self.executor.with_connection(connection);
+        self
+    }
+
+    /// Authenticate test requests with tokens from a provider
+    pub fn with_auth(mut self, auth: AuthHeaders) -> Self {
+        self.executor = self.executor.with_auth(auth);
+        self
+    }
+
+    /// Re-run failed tests, classifying passes on retry as flaky
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.executor = self.executor.with_retry(retry);
+        self
+    }
+
+    /// Flag rounds during which a gateway pod crashed
+    pub fn with_crash_watcher(mut self, watcher: CrashWatcher) -> Self {
+        self.executor = self.executor.with_crash_watcher(watcher);
+        self
+    }
+
+    /// Capture cluster diagnostics for every failed test
+    pub fn with_diagnostics(mut self, collector: DiagnosticsCollector) -> Self {
+        self.executor = self.executor.with_diagnostics(collector);
+        self
+    }
+
+    /// Count the HTTP traffic of every test and report it per round
+    pub fn with_http_metrics(mut self, metrics: HttpMetrics) -> Self {
+        self.executor = self.executor.with_http_metrics(metrics);
+        self
+    }
+
+    /// Stop the run when the suite is cancelled (suite timeout or Ctrl-C)
+    pub fn with_cancellation(mut self, cancel: SuiteCancel) -> Self {
+        self.executor = self.executor.with_cancellation(cancel);
+        self
+    }
+
     /// Run multiple rounds of parallel tests
     pub async fn run_rounds(
         &self,
@@ -209,18 +597,139 @@ impl BatchRunner {
         let mut summaries = Vec::new();
 
         for round in 1..=self.rounds {
-            info!("=== Round {}/{} ===", round, self.rounds);
+            if self.executor.is_cancelled() {
+                info!("Suite cancelled; skipping rounds {}-{}", round, self.rounds);
+                break;
+            }
+            let summary = self
+                .run_round(round, self.rounds, gateway_ip, gateway_config)
+                .await?;
+            summaries.push(summary);
+        }
+
+        Ok(summaries)
+    }
+
+    /// Run rounds back to back until the soak duration elapses, folding each
+    /// into a report as it finishes. `checkpoint` is called with the report
+    /// and the latest round every checkpoint interval. Rounds that fail to run
+    /// are noted in the report; after [`MAX_CONSECUTIVE_ROUND_ERRORS`] in a
+    /// row the soak stops with what it has. Returns the report and the last
+    /// round.
+    pub async fn run_soak(
+        &self,
+        gateway_ip: &str,
+        gateway_config: &GatewayConfig,
+        soak: &SoakSettings,
+        mut checkpoint: impl FnMut(&SoakReport, &TestRoundSummary),
+    ) -> Result<(SoakReport, Option<TestRoundSummary>)> {
+        info!(
+            "Soaking {} for {}s (checkpoint every {}s)",
+            gateway_config.implementation,
+            soak.duration.as_secs(),
+            soak.checkpoint_interval.as_secs()
+        );
+
+        let started = Instant::now();
+        let mut report = SoakReport::new(soak, Utc::now());
+        let mut last_checkpoint = Instant::now();
+        let mut latest = None;
+        let mut consecutive_errors = 0;
+
+        while started.elapsed() < soak.duration {
+            if self.executor.is_cancelled() {
+                info!(
+                    "Suite cancelled; ending soak after {} rounds",
+                    report.rounds
+                );
+                break;
+            }
+            let round = report.next_round();
+            let summary = match self.run_round(round, 0, gateway_ip, gateway_config).await {
+                Ok(summary) => summary,
+                // One broken round must not throw away hours of results
+                Err(e) => {
+                    warn!("Soak round {} failed to run: {:#}", round, e);
+                    report.record_error(round, format!("{e:#}"), Utc::now());
+                    consecutive_errors += 1;
+                    if consecutive_errors >= MAX_CONSECUTIVE_ROUND_ERRORS {
+                        report.stopped = Some(format!(
+                            "{consecutive_errors} rounds in a row failed to run: {e:#}"
+                        ));
+                        break;
+                    }
+                    continue;
+                }
+            };
+            consecutive_errors = 0;
+            report.record(&summary, Utc::now());
+            if last_checkpoint.elapsed() >= soak.checkpoint_interval {
+                checkpoint(&report, &summary);
+                last_checkpoint = Instant::now();
+            }
+            latest = Some(summary);
+        }
+
+        if let (0, Some(reason)) = (report.rounds, &report.stopped) {
+            anyhow::bail!("Soak stopped before any round ran: {reason}");
+        }
+        Ok((report, latest))
+    }
+
+    /// Run one round of parallel tests (`total_rounds` 0 = open-ended)
+    async fn run_round(
+        &self,
+        round: u32,
+        total_rounds: u32,
+        gateway_ip: &str,
+        gateway_config: &GatewayConfig,
+    ) -> Result<TestRoundSummary> {
+        let span = info_span!(
+            "round",
+            round,
+            gateway = gateway_config.implementation.name()
+        );
+        async {
+            let variant = self.plan.variant(round);
+            match (variant, total_rounds) {
+                (Some(v), _) => info!("=== Round {}/{} ({}) ===", round, total_rounds, v),
+                (None, 0) => info!("=== Round {} ===", round),
+                (None, _) => info!("=== Round {}/{} ===", round, total_rounds),
+            }
+            let test_cases = self.executor.filter.select(TestCase::all());
+            self.executor
+                .round_started(round, total_rounds, &test_cases);
+
+            let environment = match &self.probe {
+                Some(probe) => probe.try_snapshot().await,
+                None => None,
+            };
+            if let Some(env) = &environment {
+                info!("Cluster: {}", env);
+            }
+            let window = CrashWindow::open(self.executor.crashes.as_ref()).await;
+            let traffic = TrafficWindow::open(self.executor.metrics.as_ref());
 
             let results = self
                 .executor
-                .run_tests_parallel(gateway_ip, gateway_config, TestCase::all())
+                .run_variant(gateway_ip, gateway_config, test_cases, variant)
                 .await?;
 
             let mut sorted_results = results;
             sorted_results.sort_by_key(|r| r.test_case.number());
+            if let Some(probe) = &self.probe {
+                probe.locate_backends(&mut sorted_results).await;
+            }
 
             let summary =
-                TestRoundSummary::new(round, gateway_config.implementation.name(), sorted_results);
+                TestRoundSummary::new(round, gateway_config.implementation.name(), sorted_results)
+                    .with_environment(environment)
+                    .with_gateway_api(self.executor.gateway_api)
+                    .with_filter(&self.executor.filter)
+                    .with_crashes(window.finish().await)
+                    .with_traffic(traffic.finish())
+                    .with_variant(variant.map(|v| v.to_string()));
+            self.executor.round_finished(&summary);
 
             info!(
                 "Round {} completed: {}/{} passed ({:.1}%)",
@@ -230,10 +739,10 @@ impl BatchRunner {
                 summary.pass_rate()
             );
 
-            summaries.push(summary);
+            Ok(summary)
         }
-
-        Ok(summaries)
+        .instrument(span)
+        .await
     }
 
     /// Aggregate results across multiple rounds
@@ -247,9 +756,14 @@ impl BatchRunner {
 
                 match result.status {
                     TestStatus::Pass => stats.passes += 1,
+                    TestStatus::Flaky => {
+                        stats.passes += 1;
+                        stats.flakes += 1;
+                    }
                     TestStatus::Fail => stats.failures += 1,
                     TestStatus::Skip => stats.skips += 1,
                     TestStatus::Error => stats.errors += 1,
+                    TestStatus::NotSupported => stats.not_supported += 1,
                 }
                 stats.total_duration_ms += result.duration_ms;
             }
@@ -258,40 +772,115 @@ impl BatchRunner {
         // Calculate pass rates
         let test_pass_rates: HashMap<TestCase, f64> = test_stats
             .iter()
-            .map(|(tc, stats)| {
-                let total = stats.passes + stats.failures + stats.errors;
-                let rate = if total > 0 {
-                    (stats.passes as f64 / total as f64) * 100.0
-                } else {
-                    0.0
-                };
-                (*tc, rate)
-            })
+            .map(|(tc, stats)| (*tc, stats.pass_rate()))
             .collect();
 
         let overall_pass_rate =
             summaries.iter().map(|s| s.pass_rate()).sum::<f64>() / summaries.len() as f64;
 
+        let anomalous_rounds = summaries
+            .iter()
+            .filter(|s| s.is_anomalous())
+            .map(|s| s.round)
+            .collect();
+
         AggregateResult {
             total_rounds,
             test_stats,
             test_pass_rates,
             overall_pass_rate,
+            anomalous_rounds,
+            traffic: HttpTraffic::total(summaries.iter().filter_map(|s| s.traffic.as_ref())),
         }
     }
+
+    /// Aggregate of a soak run, from its incremental report
+    pub fn aggregate_soak(report: &SoakReport) -> AggregateResult {
+        let test_stats: HashMap<TestCase, TestStats> = report
+            .tests
+            .iter()
+            .filter_map(|(number, stats)| {
+                let stats = TestStats {
+                    passes: stats.passes,
+                    flakes: stats.flakes,
+                    failures: stats.failures,
+                    skips: stats.skips,
+                    errors: stats.errors,
+                    not_supported: stats.not_supported,
+                    total_duration_ms: stats.total_duration_ms,
+                };
+                Some((TestCase::from_number(*number)?, stats))
+            })
+            .collect();
+        let test_pass_rates = test_stats
+            .iter()
+            .map(|(tc, stats)| (*tc, stats.pass_rate()))
+            .collect();
+
+        AggregateResult {
+            total_rounds: report.rounds,
+            test_stats,
+            test_pass_rates,
+            overall_pass_rate: report.avg_pass_rate(),
+            // Round numbers are not kept; the soak report counts them
+            anomalous_rounds: Vec::new(),
+            traffic: report.traffic.clone(),
+        }
+    }
+
+    /// Aggregate rounds per parameter variant, in the order variants first ran
+    pub fn aggregate_by_variant(summaries: &[TestRoundSummary]) -> Vec<(String, AggregateResult)> {
+        let mut groups: Vec<(String, Vec<TestRoundSummary>)> = Vec::new();
+        for summary in summaries {
+            let Some(variant) = &summary.variant else {
+                continue;
+            };
+            match groups.iter_mut().find(|(name, _)| name == variant) {
+                Some((_, rounds)) => rounds.push(summary.clone()),
+                None => groups.push((variant.clone(), vec![summary.clone()])),
+            }
+        }
+        groups
+            .into_iter()
+            .map(|(name, rounds)| (name, Self::aggregate_results(&rounds)))
+            .collect()
+    }
+
+    /// Drop rounds that started while the cluster was unhealthy
+    pub fn exclude_anomalous(summaries: Vec<TestRoundSummary>) -> Vec<TestRoundSummary> {
+        summaries
+            .into_iter()
+            .filter(|s| !s.is_anomalous())
+            .collect()
+    }
 }
 
 /// Statistics for a single test case across rounds
 #[derive(Clone, Debug, Default)]
 pub struct TestStats {
+    /// Passing runs, including flaky ones
     pub passes: u32,
+    /// Runs that passed only on retry
+    pub flakes: u32,
     pub failures: u32,
     pub skips: u32,
     pub errors: u32,
+    /// Runs reported N/A: the implementation lacks the capability
+    pub not_supported: u32,
     pub total_duration_ms: u64,
 }
 
 impl TestStats {
+    /// Pass rate as a percentage of the runs that passed, failed or errored
+    pub fn pass_rate(&self) -> f64 {
+        let total = self.passes + self.failures + self.errors;
+        if total > 0 {
+            (self.passes as f64 / total as f64) * 100.0
+        } else {
+            0.0
+        }
+    }
+
     pub fn avg_duration_ms(&self) -> u64 {
         let total = self.passes + self.failures + self.errors;
         if total > 0 {
@@ -309,6 +898,10 @@ pub struct AggregateResult {
     pub test_stats: HashMap<TestCase, TestStats>,
     pub test_pass_rates: HashMap<TestCase, f64>,
     pub overall_pass_rate: f64,
+    /// Rounds that started while the cluster was unhealthy
+    pub anomalous_rounds: Vec<u32>,
+    /// HTTP traffic of all rounds (when a metrics registry was attached)
+    pub traffic: Option<HttpTraffic>,
 }
 
 impl AggregateResult {
@@ -323,6 +916,34 @@ impl AggregateResult {
         tests
     }
 
+    /// Tests that passed on retry in at least one round, most flakes first
+    pub fn retried_tests(&self) -> Vec<(TestCase, u32)> {
+        let mut tests: Vec<_> = self
+            .test_stats
+            .iter()
+            .filter(|(_, stats)| stats.flakes > 0)
+            .map(|(tc, stats)| (*tc, stats.flakes))
+            .collect();
+        tests.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.number().cmp(&b.0.number())));
+        tests
+    }
+
+    /// Mean test duration across all rounds
+    pub fn avg_duration_ms(&self) -> u64 {
+        let (total, runs) = self.test_stats.values().fold((0, 0), |(total, runs), s| {
+            (
+                total + s.total_duration_ms,
+                runs + (s.passes + s.failures + s.errors) as u64,
+            )
+        });
+        total.checked_div(runs).unwrap_or(0)
+    }
+
+    /// Total flaky runs across rounds
+    pub fn total_flakes(&self) -> u32 {
+        self.test_stats.values().map(|s| s.flakes).sum()
+    }
+
     /// Get tests that always pass
     pub fn stable_tests(&self) -> Vec<TestCase> {
         self.test_pass_rates
@@ -376,5 +997,108 @@ mod unit_tests {
             aggregate.test_pass_rates.get(&TestCase::PathRouting),
             Some(&50.0)
         );
+        assert!(aggregate.anomalous_rounds.is_empty());
+    }
+
+    #[test]
+    fn test_aggregate_soak_matches_rounds() {
+        let summaries = vec![
+            TestRoundSummary::new(
+                1,
+                "nginx",
+                vec![
+                    TestResult::pass(TestCase::HostRouting, 100),
+                    TestResult::fail(TestCase::PathRouting, 50, "failed"),
+                ],
+            ),
+            TestRoundSummary::new(
+                2,
+                "nginx",
+                vec![
+                    TestResult::pass(TestCase::HostRouting, 120),
+                    TestResult::pass(TestCase::PathRouting, 60),
+                ],
+            ),
+        ];
+        let settings = SoakSettings::new(std::time::Duration::from_secs(3600));
+        let mut report = SoakReport::new(&settings, Utc::now());
+        for summary in &summaries {
+            report.record(summary, Utc::now());
+        }
+
+        let rounds = BatchRunner::aggregate_results(&summaries);
+        let soak = BatchRunner::aggregate_soak(&report);
+        assert_eq!(soak.total_rounds, rounds.total_rounds);
+        assert_eq!(soak.overall_pass_rate, rounds.overall_pass_rate);
+        assert_eq!(soak.test_pass_rates, rounds.test_pass_rates);
+        assert_eq!(soak.avg_duration_ms(), rounds.avg_duration_ms());
+    }
+
+    #[test]
+    fn test_batch_runner_round_plan() {
+        let plan = RoundPlan::new(vec![
+            RoundVariant::new(2).with_concurrency(4),
+            RoundVariant::new(3).with_concurrency(16),
+        ]);
+        let runner = BatchRunner::new(4, 1).with_round_plan(plan);
+        assert_eq!(runner.rounds, 5);
+    }
+
+    #[test]
+    fn test_aggregate_by_variant() {
+        let round = |n, variant: &str, passed: bool| {
+            let result = if passed {
+                TestResult::pass(TestCase::HostRouting, 10)
+            } else {
+                TestResult::fail(TestCase::HostRouting, 10, "x")
+            };
+            TestRoundSummary::new(n, "nginx", vec![result]).with_variant(Some(variant.into()))
+        };
+        let summaries = vec![
+            round(1, "concurrency=4", true),
+            round(2, "concurrency=4", true),
+            round(3, "concurrency=16", false),
+            round(4, "concurrency=16", true),
+        ];
+
+        let groups = BatchRunner::aggregate_by_variant(&summaries);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].0, "concurrency=4");
+        assert_eq!(groups[0].1.overall_pass_rate, 100.0);
+        assert_eq!(groups[1].0, "concurrency=16");
+        assert_eq!(groups[1].1.total_rounds, 2);
+        assert_eq!(groups[1].1.overall_pass_rate, 50.0);
+    }
+
+    #[test]
+    fn test_exclude_anomalous_rounds() {
+        use crate::models::RoundEnvironment;
+
+        let unhealthy = RoundEnvironment {
+            nodes_total: 2,
+            nodes_ready: 1,
+            api_latency_ms: Some(5),
+            ..RoundEnvironment::new()
+        };
+        let summaries = vec![
+            TestRoundSummary::new(
+                1,
+                "nginx",
+                vec![TestResult::pass(TestCase::HostRouting, 10)],
+            ),
+            TestRoundSummary::new(
+                2,
+                "nginx",
+                vec![TestResult::fail(TestCase::HostRouting, 10, "x")],
+            )
+            .with_environment(Some(unhealthy)),
+        ];
+
+        let aggregate = BatchRunner::aggregate_results(&summaries);
+        assert_eq!(aggregate.anomalous_rounds, vec![2]);
+
+        let kept = BatchRunner::exclude_anomalous(summaries);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].round, 1);
     }
 }
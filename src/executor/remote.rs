@@ -0,0 +1,359 @@
+//! Running the suite inside a KubeVirt VM
+//!
+//! Some gateways are only reachable from the VM network (AMD64-only
+//! implementations running in a VM on an ARM64 host). The remote executor
+//! uploads a gateway-poc binary to the VM over SSH, runs the same `test`
+//! command there and brings the stored runs and output files back, so
+//! `results` works on them as if the suite had run locally.
+
+use anyhow::{Context, Result};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, warn};
+
+use crate::config::ConfigFile;
+use crate::kubevirt::{shell_command, shell_quote, SshClient, SshOutput};
+use crate::results::{generate_run_id, StoredTestRun};
+
+/// Outcome of a remote suite run
+#[derive(Debug)]
+pub struct RemoteRun {
+    /// Output and exit code of the remote gateway-poc
+    pub output: SshOutput,
+    /// Runs the remote gateway-poc stored
+    pub runs: Vec<StoredTestRun>,
+    /// Local copies of the output files the remote run wrote
+    pub outputs: Vec<PathBuf>,
+}
+
+/// File or directory option of the remote run copied back afterwards
+struct RemoteOutput {
+    /// Long option name, e.g. `output`
+    option: String,
+    local: PathBuf,
+    dir: bool,
+}
+
+/// Runs gateway-poc on a VM over SSH
+pub struct RemoteExecutor {
+    ssh: SshClient,
+    host: String,
+    binary: Option<PathBuf>,
+    config: Option<String>,
+    env: Vec<(String, String)>,
+    outputs: Vec<RemoteOutput>,
+}
+
+impl RemoteExecutor {
+    /// Run on `host`, uploading this executable unless
+    /// [`with_binary`](Self::with_binary) names another
+    pub fn new(ssh: SshClient, host: impl Into<String>) -> Self {
+        Self {
+            ssh,
+            host: host.into(),
+            binary: None,
+            config: None,
+            env: Vec::new(),
+            outputs: Vec::new(),
+        }
+    }
+
+    /// Upload this binary instead (e.g. a static AMD64 build from an ARM64 host)
+    pub fn with_binary(mut self, path: impl Into<PathBuf>) -> Self {
+        self.binary = Some(path.into());
+        self
+    }
+
+    /// Upload this configuration (variables already substituted) and point
+    /// the remote run at it
+    pub fn with_config(mut self, config: &ConfigFile) -> Result<Self> {
+        self.config = Some(serde_yaml::to_string(config).context("Failed to serialize config")?);
+        Ok(self)
+    }
+
+    /// Set an environment variable for the remote run
+    pub fn with_env(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.push((name.into(), value.into()));
+        self
+    }
+
+    /// Pass `--{option}` a path on the VM and copy the file written there to
+    /// `local` afterwards (`args` must not contain the option)
+    pub fn with_output_file(
+        mut self,
+        option: impl Into<String>,
+        local: impl Into<PathBuf>,
+    ) -> Self {
+        self.outputs.push(RemoteOutput {
+            option: option.into(),
+            local: local.into(),
+            dir: false,
+        });
+        self
+    }
+
+    /// Like [`with_output_file`](Self::with_output_file) for a directory;
+    /// its entries are copied into `local`
+    pub fn with_output_dir(mut self, option: impl Into<String>, local: impl Into<PathBuf>) -> Self {
+        self.outputs.push(RemoteOutput {
+            option: option.into(),
+            local: local.into(),
+            dir: true,
+        });
+        self
+    }
+
+    /// Run gateway-poc with `args` on the VM and collect the runs it stored
+    ///
+    /// The remote run keeps its results and output files in a private
+    /// scratch directory that is removed afterwards; a failing exit code is
+    /// returned, not an error.
+    pub async fn run(&self, args: &[String]) -> Result<RemoteRun> {
+        let binary = match &self.binary {
+            Some(path) => path.clone(),
+            None => {
+                let exe = std::env::current_exe().context("Failed to locate gateway-poc")?;
+                self.check_arch().await?;
+                exe
+            }
+        };
+        // mktemp creates the directory readable by the SSH user only
+        let work_dir = self
+            .checked(&format!(
+                "mktemp -d /tmp/gateway-poc-remote-{}-XXXXXX",
+                generate_run_id()
+            ))
+            .await?
+            .trim()
+            .to_string();
+        self.checked(&format!("mkdir -p {work_dir}/data {work_dir}/out"))
+            .await?;
+
+        let result = self.run_in(&work_dir, &binary, args).await;
+        if let Err(e) = self.checked(&format!("rm -rf {work_dir}")).await {
+            warn!("Failed to remove {} on {}: {}", work_dir, self.host, e);
+        }
+        result
+    }
+
+    async fn run_in(&self, work_dir: &str, binary: &Path, args: &[String]) -> Result<RemoteRun> {
+        let remote_binary = format!("{work_dir}/gateway-poc");
+        info!("Uploading {} to {}", binary.display(), self.host);
+        self.ssh.upload(&self.host, binary, &remote_binary).await?;
+        self.checked(&format!("chmod +x {remote_binary}")).await?;
+
+        let mut env = vec![("XDG_DATA_HOME".to_string(), format!("{work_dir}/data"))];
+        if let Some(config) = &self.config {
+            let remote_config = format!("{work_dir}/config.yaml");
+            // A new owner-only file, removed when dropped
+            let mut local_config = tempfile::Builder::new()
+                .prefix("gateway-poc-remote-")
+                .suffix(".yaml")
+                .tempfile()
+                .context("Failed to create the config file to upload")?;
+            local_config
+                .write_all(config.as_bytes())
+                .with_context(|| format!("Failed to write {}", local_config.path().display()))?;
+            self.ssh
+                .upload(&self.host, local_config.path(), &remote_config)
+                .await?;
+            env.push(("GATEWAY_POC_CONFIG".to_string(), remote_config));
+        }
+        env.extend(self.env.iter().cloned());
+
+        let mut args = args.to_vec();
+        for output in &self.outputs {
+            args.push(format!("--{}", output.option));
+            args.push(format!("{work_dir}/out/{}", output.option));
+        }
+
+        let command = remote_command(&remote_binary, &env, &args);
+        debug!("Running on {}: {}", self.host, command);
+        let output = self.ssh.exec(&self.host, &command).await?;
+        let runs = self.collect_runs(work_dir).await?;
+        let outputs = self.collect_outputs(work_dir).await;
+        Ok(RemoteRun {
+            output,
+            runs,
+            outputs,
+        })
+    }
+
+    /// Copy the output files the remote run wrote; a missing or failed copy
+    /// is logged, not an error
+    async fn collect_outputs(&self, work_dir: &str) -> Vec<PathBuf> {
+        let mut copied = Vec::new();
+        for output in &self.outputs {
+            let remote = format!("{work_dir}/out/{}", output.option);
+            match self.copy_output(&remote, output).await {
+                Ok(true) => copied.push(output.local.clone()),
+                Ok(false) => debug!("--{} wrote nothing on {}", output.option, self.host),
+                Err(e) => warn!("Failed to copy --{} output: {:#}", output.option, e),
+            }
+        }
+        copied
+    }
+
+    async fn copy_output(&self, remote: &str, output: &RemoteOutput) -> Result<bool> {
+        if !output.dir {
+            if !self.exists(remote).await? {
+                return Ok(false);
+            }
+            self.ssh.download(&self.host, remote, &output.local).await?;
+            return Ok(true);
+        }
+        let listing = self
+            .checked(&format!(
+                "ls -A {} 2>/dev/null || true",
+                shell_quote(remote)
+            ))
+            .await?;
+        let entries: Vec<&str> = listing.lines().filter(|l| !l.is_empty()).collect();
+        if entries.is_empty() {
+            return Ok(false);
+        }
+        std::fs::create_dir_all(&output.local)
+            .with_context(|| format!("Failed to create {}", output.local.display()))?;
+        for entry in entries {
+            let local = output.local.join(entry);
+            if local.exists() {
+                warn!("Not overwriting {}", local.display());
+                continue;
+            }
+            self.ssh
+                .download_dir(&self.host, &format!("{remote}/{entry}"), &local)
+                .await?;
+        }
+        Ok(true)
+    }
+
+    async fn exists(&self, remote: &str) -> Result<bool> {
+        let test = self
+            .checked(&format!(
+                "test -e {} && echo yes || true",
+                shell_quote(remote)
+            ))
+            .await?;
+        Ok(test.trim() == "yes")
+    }
+
+    /// Runs stored under the remote data directory
+    async fn collect_runs(&self, work_dir: &str) -> Result<Vec<StoredTestRun>> {
+        let listing = self
+            .checked(&format!(
+                "find {work_dir}/data/gateway-poc/results -mindepth 2 -maxdepth 2 \
+                 -name '*.json' -not -path '*/setups/*' 2>/dev/null || true"
+            ))
+            .await?;
+
+        let mut runs = Vec::new();
+        for path in listing.lines() {
+            let json = self.checked(&format!("cat {}", shell_quote(path))).await?;
+            let run = serde_json::from_str(&json)
+                .with_context(|| format!("Failed to parse {path} from {}", self.host))?;
+            runs.push(run);
+        }
+        Ok(runs)
+    }
+
+    /// Fail early when this executable cannot run on the VM
+    async fn check_arch(&self) -> Result<()> {
+        let machine = self.checked("uname -m").await?;
+        let machine = machine.trim();
+        if machine != std::env::consts::ARCH {
+            anyhow::bail!(
+                "{} is {machine} but this gateway-poc is built for {}; pass a static \
+                 {machine} build (e.g. the gateway-poc-linux-amd64-static release) with --vm-binary",
+                self.host,
+                std::env::consts::ARCH
+            );
+        }
+        Ok(())
+    }
+
+    async fn checked(&self, command: &str) -> Result<String> {
+        self.ssh.exec_checked(&self.host, command).await
+    }
+}
+
+/// Shell command running `binary` with `env` set
+fn remote_command(binary: &str, env: &[(String, String)], args: &[String]) -> String {
+    let mut words: Vec<String> = env
+        .iter()
+        .map(|(name, value)| format!("{name}={}", shell_quote(value)))
+        .collect();
+    words.push(shell_quote(binary));
+    words.push(shell_command(args));
+    words.join(" ")
+}
+
+/// `argv` without the program name and the options in `local_only`
+/// (long names, or letters of short options, with their values)
+pub fn forwarded_args(argv: impl IntoIterator<Item = String>, local_only: &[&str]) -> Vec<String> {
+    let mut forwarded = Vec::new();
+    let mut argv = argv.into_iter().skip(1);
+    while let Some(arg) = argv.next() {
+        let name = arg.split('=').next().unwrap_or_default();
+        match name.strip_prefix("--") {
+            Some(long) if local_only.contains(&long) => {
+                if !arg.contains('=') {
+                    argv.next();
+                }
+            }
+            Some(_) => forwarded.push(arg),
+            // `-o value` or `-ovalue`
+            None => match arg.strip_prefix('-').and_then(|s| s.get(..1)) {
+                Some(short) if local_only.contains(&short) => {
+                    if arg.len() == 2 {
+                        argv.next();
+                    }
+                }
+                _ => forwarded.push(arg),
+            },
+        }
+    }
+    forwarded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forwarded_args() {
+        let argv = [
+            "gateway-poc",
+            "--namespace",
+            "gw",
+            "test",
+            "--vm",
+            "vm-0",
+            "-g",
+            "envoy",
+            "--vm-binary=/tmp/gateway-poc",
+            "--all",
+            "-o",
+            "report.json",
+            "-q",
+        ]
+        .map(String::from);
+        assert_eq!(
+            forwarded_args(argv, &["vm", "vm-binary", "output", "o"]),
+            ["--namespace", "gw", "test", "-g", "envoy", "--all", "-q"]
+        );
+    }
+
+    #[test]
+    fn test_remote_command() {
+        let env = [("GATEWAY_POC_HOSTNAME".to_string(), "a b".to_string())];
+        let args = [
+            "test".to_string(),
+            "--resolve".to_string(),
+            "x=1.2.3.4".to_string(),
+        ];
+        assert_eq!(
+            remote_command("/tmp/w/gateway-poc", &env, &args),
+            "GATEWAY_POC_HOSTNAME='a b' /tmp/w/gateway-poc test --resolve x=1.2.3.4"
+        );
+    }
+}
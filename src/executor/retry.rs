@@ -0,0 +1,93 @@
+//! Test retries
+//!
+//! Re-runs failed tests under a retry policy and classifies tests that
+//! pass on a later attempt as flaky.
+
+use std::future::Future;
+use tracing::warn;
+
+use crate::models::{RetryPolicy, TestResult, TestStatus};
+
+/// Run `attempt` until it passes or the policy's retries are exhausted
+pub(crate) async fn run_with_retries<F, Fut>(policy: &RetryPolicy, mut attempt: F) -> TestResult
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = TestResult>,
+{
+    let mut result = attempt().await;
+    let first_failure = result.message.clone();
+
+    for retry in 1..=policy.retries {
+        if !result.status.is_retryable() {
+            break;
+        }
+        warn!(
+            "{} {} ({}), retry {}/{}",
+            result.test_case,
+            result.status,
+            result.message.as_deref().unwrap_or("no message"),
+            retry,
+            policy.retries
+        );
+        tokio::time::sleep(policy.delay()).await;
+
+        result = attempt().await;
+        result.retries = retry;
+    }
+
+    if result.retries > 0 && result.status == TestStatus::Pass {
+        result.into_flaky(first_failure.as_deref())
+    } else {
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TestCase;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    async fn run_failing(policy: &RetryPolicy, failures: u32) -> (TestResult, u32) {
+        let calls = AtomicU32::new(0);
+        let result = run_with_retries(policy, || async {
+            if calls.fetch_add(1, Ordering::SeqCst) < failures {
+                TestResult::fail(TestCase::HostRouting, 10, "503")
+            } else {
+                TestResult::pass(TestCase::HostRouting, 10)
+            }
+        })
+        .await;
+        (result, calls.load(Ordering::SeqCst))
+    }
+
+    #[tokio::test]
+    async fn test_retry_classification() {
+        let policy = RetryPolicy::new(2, 0);
+
+        let (result, calls) = run_failing(&policy, 0).await;
+        assert_eq!(
+            (result.status, result.retries, calls),
+            (TestStatus::Pass, 0, 1)
+        );
+
+        let (result, calls) = run_failing(&policy, 1).await;
+        assert_eq!(
+            (result.status, result.retries, calls),
+            (TestStatus::Flaky, 1, 2)
+        );
+        assert_eq!(
+            result.message.as_deref(),
+            Some("Passed on retry 1 (first failure: 503)")
+        );
+
+        let (result, calls) = run_failing(&policy, 5).await;
+        assert_eq!(
+            (result.status, result.retries, calls),
+            (TestStatus::Fail, 2, 3)
+        );
+
+        let (result, calls) = run_failing(&RetryPolicy::default(), 1).await;
+        assert_eq!((result.status, calls), (TestStatus::Fail, 1));
+    }
+}
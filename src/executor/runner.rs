@@ -6,70 +6,215 @@
 
 use anyhow::{Context, Result};
 use std::time::Instant;
-use tracing::{error, info};
+use tracing::{error, info, info_span, Instrument};
 
-use crate::http::HttpClient;
+use super::cancel::{self, SuiteCancel};
+use super::retry;
+use crate::http::{
+    AuthHeaders, BackendTracker, HttpClient, HttpClientConfig, HttpMetrics, TrafficWindow,
+};
+use crate::k8s::{ClusterProbe, CrashWatcher, CrashWindow, DiagnosticsCollector};
 use crate::models::{
-    GatewayConfig, GatewayImpl, TestCase, TestConfig, TestResult, TestRoundSummary,
+    GatewayConfig, GatewayImpl, RoundEnvironment, TestCase, TestConfig, TestResult,
+    TestRoundSummary,
 };
 use crate::tests;
+use crate::tui::{self, ProgressEvent, ProgressReporter};
+use crate::utils::Teardown;
 
 /// Test runner for Gateway API tests
 pub struct TestRunner {
     config: TestConfig,
     client: HttpClient,
     gateway_ip: Option<String>,
+    probe: Option<ClusterProbe>,
+    crashes: Option<CrashWatcher>,
+    diagnostics: Option<DiagnosticsCollector>,
+    metrics: Option<HttpMetrics>,
+    progress: Option<ProgressReporter>,
+    cancel: SuiteCancel,
 }
 
 impl TestRunner {
     /// Create a new test runner
     pub fn new(config: TestConfig) -> Result<Self> {
-        let client = HttpClient::with_timeout(config.timeout_secs)?;
+        let client = HttpClient::from_config(
+            HttpClientConfig::default()
+                .with_timeout(config.timeout_secs)
+                .with_dns_overrides(config.dns_overrides.clone())
+                .with_connection(&config.connection),
+        )?;
         Ok(Self {
             config,
             client,
             gateway_ip: None,
+            probe: None,
+            crashes: None,
+            diagnostics: None,
+            metrics: None,
+            progress: None,
+            cancel: SuiteCancel::default(),
         })
     }
 
+    /// Publish per-test progress (e.g. to the dashboard)
+    pub fn with_progress(mut self, reporter: ProgressReporter) -> Self {
+        self.progress = Some(reporter);
+        self
+    }
+
+    /// Stop the run when the suite is cancelled (suite timeout or Ctrl-C)
+    pub fn with_cancellation(mut self, cancel: SuiteCancel) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    /// Whether the run was cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+
     /// Set gateway IP address
     pub fn with_gateway_ip(mut self, ip: impl Into<String>) -> Self {
         self.gateway_ip = Some(ip.into());
         self
     }
 
+    /// Capture cluster health at the start of each round
+    pub fn with_cluster_probe(mut self, probe: ClusterProbe) -> Self {
+        self.probe = Some(probe);
+        self
+    }
+
+    /// Flag rounds during which a gateway pod crashed
+    pub fn with_crash_watcher(mut self, watcher: CrashWatcher) -> Self {
+        self.crashes = Some(watcher);
+        self
+    }
+
+    /// Capture cluster diagnostics for every failed test
+    pub fn with_diagnostics(mut self, collector: DiagnosticsCollector) -> Self {
+        self.diagnostics = Some(collector);
+        self
+    }
+
+    /// Count the HTTP traffic of every test and report it per round
+    pub fn with_http_metrics(mut self, metrics: HttpMetrics) -> Self {
+        self.client = self.client.with_metrics(metrics.clone());
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Authenticate test requests with tokens from a provider
+    pub fn with_auth(mut self, auth: AuthHeaders) -> Self {
+        self.client = self.client.with_auth(auth);
+        self
+    }
+
+    /// Snapshot cluster health if a probe is configured
+    async fn capture_environment(&self) -> Option<RoundEnvironment> {
+        match &self.probe {
+            Some(probe) => probe.try_snapshot().await,
+            None => None,
+        }
+    }
+
     /// Get the gateway IP (from config or discovery)
     pub fn gateway_ip(&self) -> &str {
         self.gateway_ip.as_deref().unwrap_or("127.0.0.1")
     }
 
-    /// Run a single test case
+    /// Run a single test case, undoing its fixture changes even if it is aborted
     pub async fn run_test(&self, test_case: TestCase) -> TestResult {
+        tui::emit(
+            &self.progress,
+            ProgressEvent::TestStarted { test: test_case },
+        );
+        let teardown = Teardown::new();
+        let started = chrono::Utc::now();
+        let backends = BackendTracker::new();
+        let client = self.client.clone().with_backend_tracker(backends.clone());
+        let result = cancel::run_cancellable(
+            &self.cancel,
+            test_case,
+            self.execute_test(test_case, &client, &teardown),
+        )
+        .await
+        .with_backends(backends.hits());
+        let result = match &self.diagnostics {
+            Some(collector) => collector.attach(result, started).await,
+            None => result,
+        };
+        teardown.unwind().await;
+        tui::emit(
+            &self.progress,
+            ProgressEvent::TestFinished {
+                result: result.clone(),
+            },
+        );
+        result
+    }
+
+    async fn execute_test(
+        &self,
+        test_case: TestCase,
+        client: &HttpClient,
+        teardown: &Teardown,
+    ) -> TestResult {
         let gateway_ip = self.gateway_ip();
-        let http_port = self.config.gateway.http_port;
-        let https_port = self.config.gateway.https_port;
-        let grpc_port = self.config.gateway.grpc_port.unwrap_or(9090);
-        let hostname = &self.config.gateway.hostname;
 
         // Check if test should be skipped
-        if self.config.skip_tests.contains(&test_case.number()) {
+        if self.config.filter.skip.contains(&test_case.number()) {
             return TestResult::skip(test_case, "Skipped by configuration");
         }
 
-        info!("Running {}", test_case);
-
-        let result = tests::run_test(
-            test_case, gateway_ip, http_port, https_port, grpc_port, hostname,
-        )
-        .await;
+        if let Some(reason) = self
+            .config
+            .gateway_api
+            .and_then(|spec| spec.unsupported_reason(test_case))
+        {
+            return TestResult::skip(test_case, reason);
+        }
+        if let Some(reason) = self
+            .config
+            .gateway
+            .implementation
+            .unsupported_reason(test_case)
+        {
+            return TestResult::not_supported(test_case, reason);
+        }
 
-        match result {
-            Ok(result) => result,
-            Err(e) => {
-                error!("Test {} failed with error: {}", test_case, e);
-                TestResult::error(test_case, e.to_string())
+        let span = info_span!(
+            "test",
+            number = test_case.number(),
+            test = test_case.name(),
+            gateway = self.config.gateway.implementation.name()
+        );
+        info!(parent: &span, "Running {}", test_case);
+
+        retry::run_with_retries(&self.config.retry, || async move {
+            let result = tests::run_test(
+                test_case,
+                client,
+                gateway_ip,
+                &self.config.gateway,
+                &self.config.load_test,
+                &self.config.canary,
+                &self.config.failover,
+                teardown,
+            )
+            .await;
+
+            match result {
+                Ok(result) => result,
+                Err(e) => {
+                    error!("Test {} failed with error: {}", test_case, e);
+                    TestResult::error(test_case, e.to_string())
+                }
             }
-        }
+        })
+        .instrument(span)
+        .await
     }
 
     /// Run all test cases sequentially
@@ -81,14 +226,23 @@ impl TestRunner {
 
         let start = Instant::now();
         let mut results = Vec::new();
+        let test_cases = self.selected_tests();
+        self.round_started(1, 1, &test_cases);
+        let window = CrashWindow::open(self.crashes.as_ref()).await;
+        let traffic = TrafficWindow::open(self.metrics.as_ref());
 
-        for test_case in TestCase::all() {
+        for test_case in test_cases {
             let result = self.run_test(test_case).await;
             info!("  {}", result);
             results.push(result);
         }
 
-        let summary = TestRoundSummary::new(1, self.config.gateway.implementation.name(), results);
+        let summary = TestRoundSummary::new(1, self.config.gateway.implementation.name(), results)
+            .with_gateway_api(self.config.gateway_api)
+            .with_filter(&self.config.filter)
+            .with_crashes(window.finish().await)
+            .with_traffic(traffic.finish());
+        self.round_finished(&summary);
 
         info!(
             "Test round completed in {}ms - Pass: {}/{} ({:.1}%)",
@@ -111,25 +265,61 @@ impl TestRunner {
         let mut summaries = Vec::new();
 
         for round in 1..=num_rounds {
-            info!("=== Round {}/{} ===", round, num_rounds);
-
-            let mut results = Vec::new();
-
-            for test_case in TestCase::all() {
-                let result = self.run_test(test_case).await;
-                results.push(result);
+            if self.is_cancelled() {
+                info!("Suite cancelled; skipping rounds {}-{}", round, num_rounds);
+                break;
             }
-
-            let summary =
-                TestRoundSummary::new(round, self.config.gateway.implementation.name(), results);
-
-            info!(
-                "Round {} completed: {}/{} passed ({:.1}%)",
+            let span = info_span!(
+                "round",
                 round,
-                summary.passed,
-                summary.total,
-                summary.pass_rate()
+                gateway = self.config.gateway.implementation.name()
             );
+            let summary = async {
+                info!("=== Round {}/{} ===", round, num_rounds);
+                let test_cases = self.selected_tests();
+                self.round_started(round, num_rounds, &test_cases);
+
+                let environment = self.capture_environment().await;
+                if let Some(env) = &environment {
+                    info!("Cluster: {}", env);
+                }
+                let window = CrashWindow::open(self.crashes.as_ref()).await;
+                let traffic = TrafficWindow::open(self.metrics.as_ref());
+
+                let mut results = Vec::new();
+
+                for test_case in test_cases {
+                    let result = self.run_test(test_case).await;
+                    results.push(result);
+                }
+                if let Some(probe) = &self.probe {
+                    probe.locate_backends(&mut results).await;
+                }
+
+                let summary = TestRoundSummary::new(
+                    round,
+                    self.config.gateway.implementation.name(),
+                    results,
+                )
+                .with_environment(environment)
+                .with_gateway_api(self.config.gateway_api)
+                .with_filter(&self.config.filter)
+                .with_crashes(window.finish().await)
+                .with_traffic(traffic.finish());
+                self.round_finished(&summary);
+
+                info!(
+                    "Round {} completed: {}/{} passed ({:.1}%)",
+                    round,
+                    summary.passed,
+                    summary.total,
+                    summary.pass_rate()
+                );
+
+                summary
+            }
+            .instrument(span)
+            .await;
 
             summaries.push(summary);
         }
@@ -137,6 +327,33 @@ impl TestRunner {
         Ok(summaries)
     }
 
+    /// Tests of the configured (possibly partial) suite
+    fn selected_tests(&self) -> Vec<TestCase> {
+        self.config.filter.select(TestCase::all())
+    }
+
+    fn round_started(&self, round: u32, total_rounds: u32, tests: &[TestCase]) {
+        tui::emit(
+            &self.progress,
+            ProgressEvent::RoundStarted {
+                round,
+                total_rounds,
+                tests: tests.to_vec(),
+            },
+        );
+    }
+
+    fn round_finished(&self, summary: &TestRoundSummary) {
+        tui::emit(
+            &self.progress,
+            ProgressEvent::RoundFinished {
+                round: summary.round,
+                passed: summary.passed,
+                total: summary.total,
+            },
+        );
+    }
+
     /// Run specific test cases
     pub async fn run_tests(&self, test_cases: &[TestCase]) -> Result<TestRoundSummary> {
         info!(
@@ -153,11 +370,10 @@ impl TestRunner {
             results.push(result);
         }
 
-        Ok(TestRoundSummary::new(
-            1,
-            self.config.gateway.implementation.name(),
-            results,
-        ))
+        Ok(
+            TestRoundSummary::new(1, self.config.gateway.implementation.name(), results)
+                .with_gateway_api(self.config.gateway_api),
+        )
     }
 }
 
@@ -199,8 +415,9 @@ impl MultiGatewayRunner {
         for (implementation, ip) in &self.gateways {
             info!("Testing {} Gateway at {}", implementation, ip);
 
-            let config =
-                TestConfig::new(GatewayConfig::new(*implementation)).with_rounds(self.rounds);
+            let config = TestConfig::new(GatewayConfig::new(*implementation))
+                .with_rounds(self.rounds)
+                .with_timeout(self.timeout_secs);
 
             let runner = TestRunner::new(config)?.with_gateway_ip(ip);
             let summaries = runner.run_rounds(self.rounds).await?;
@@ -251,6 +468,17 @@ mod unit_tests {
         assert!(runner.is_ok());
     }
 
+    #[test]
+    fn test_runner_uses_configured_timeout() {
+        let gateway = GatewayConfig::new(GatewayImpl::Nginx).with_ports(8080, 8443, Some(50051));
+        let config = TestConfig::new(gateway).with_timeout(5);
+        let runner = TestRunner::new(config).unwrap();
+
+        assert_eq!(runner.client.config().timeout_secs, 5);
+        assert_eq!(runner.config.gateway.http_port, 8080);
+        assert_eq!(runner.config.gateway.grpc_port, Some(50051));
+    }
+
     #[test]
     fn test_multi_gateway_builder() {
         let runner = MultiGatewayRunner::new()
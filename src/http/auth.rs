@@ -0,0 +1,284 @@
+//! Authorization header providers
+//!
+//! Gateways fronted by auth policies (JWT validation, OIDC, ext-auth) reject
+//! anonymous traffic. A provider hands the test and benchmark clients a fresh
+//! `Authorization` header: a static token, an OIDC client-credentials grant,
+//! or the output of an exec plugin. Tokens are cached and refreshed shortly
+//! before they expire, so long runs never send stale credentials.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::process::Command;
+use tokio::sync::Mutex;
+use tracing::debug;
+
+/// Environment variables holding OIDC client credentials
+pub const OIDC_CLIENT_ID_ENV: &str = "GATEWAY_POC_OIDC_CLIENT_ID";
+pub const OIDC_CLIENT_SECRET_ENV: &str = "GATEWAY_POC_OIDC_CLIENT_SECRET";
+pub const OIDC_SCOPE_ENV: &str = "GATEWAY_POC_OIDC_SCOPE";
+
+/// Tokens are refreshed this long before they expire
+const REFRESH_MARGIN: Duration = Duration::from_secs(30);
+
+/// Exec plugin tokens without an expiry are reused for this long
+const EXEC_DEFAULT_TTL: Duration = Duration::from_secs(300);
+
+/// Source of bearer tokens
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AuthProvider {
+    /// Fixed token
+    Static(String),
+    /// OAuth2 client-credentials grant against a token endpoint
+    Oidc {
+        token_url: String,
+        client_id: String,
+        client_secret: String,
+        scope: Option<String>,
+    },
+    /// Command printing a token, or JSON with `token`/`access_token` and
+    /// optional `expires_in` seconds
+    Exec { command: String, args: Vec<String> },
+}
+
+impl AuthProvider {
+    /// Parse `bearer:<token>`, `bearer-env:<VAR>`, `oidc:<token-url>` or
+    /// `exec:<command> [args...]`
+    ///
+    /// OIDC client credentials come from `GATEWAY_POC_OIDC_CLIENT_ID`,
+    /// `GATEWAY_POC_OIDC_CLIENT_SECRET` and (optionally) `GATEWAY_POC_OIDC_SCOPE`
+    /// so secrets stay off the command line.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let (kind, value) = spec
+            .split_once(':')
+            .with_context(|| format!("Invalid auth spec '{spec}' (expected KIND:VALUE)"))?;
+        if value.is_empty() {
+            anyhow::bail!("Auth spec '{spec}' has no value");
+        }
+        match kind {
+            "bearer" => Ok(Self::Static(value.to_string())),
+            "bearer-env" => std::env::var(value)
+                .map(Self::Static)
+                .with_context(|| format!("Environment variable {value} is not set")),
+            "oidc" => Ok(Self::Oidc {
+                token_url: value.to_string(),
+                client_id: std::env::var(OIDC_CLIENT_ID_ENV)
+                    .with_context(|| format!("{OIDC_CLIENT_ID_ENV} is not set"))?,
+                client_secret: std::env::var(OIDC_CLIENT_SECRET_ENV)
+                    .with_context(|| format!("{OIDC_CLIENT_SECRET_ENV} is not set"))?,
+                scope: std::env::var(OIDC_SCOPE_ENV).ok(),
+            }),
+            "exec" => {
+                let mut parts = value.split_whitespace().map(str::to_string);
+                let command = parts.next().context("Exec auth spec has no command")?;
+                Ok(Self::Exec {
+                    command,
+                    args: parts.collect(),
+                })
+            }
+            other => anyhow::bail!(
+                "Unknown auth provider '{other}' (use bearer, bearer-env, oidc or exec)"
+            ),
+        }
+    }
+
+    /// Obtain a token and how long it stays valid (None = forever)
+    async fn fetch(&self) -> Result<(String, Option<Duration>)> {
+        match self {
+            Self::Static(token) => Ok((token.clone(), None)),
+            Self::Oidc {
+                token_url,
+                client_id,
+                client_secret,
+                scope,
+            } => {
+                let mut form = vec![("grant_type", "client_credentials")];
+                if let Some(scope) = scope {
+                    form.push(("scope", scope));
+                }
+                let response = reqwest::Client::new()
+                    .post(token_url)
+                    .basic_auth(client_id, Some(client_secret))
+                    .form(&form)
+                    .send()
+                    .await
+                    .context("Failed to reach the OIDC token endpoint")?;
+                let status = response.status();
+                if !status.is_success() {
+                    anyhow::bail!("OIDC token request failed with status {status}");
+                }
+                let token: TokenResponse = response
+                    .json()
+                    .await
+                    .context("Invalid OIDC token response")?;
+                token.into_token()
+            }
+            Self::Exec { command, args } => {
+                let output = Command::new(command)
+                    .args(args)
+                    .output()
+                    .await
+                    .with_context(|| format!("Failed to run auth plugin {command}"))?;
+                if !output.status.success() {
+                    anyhow::bail!(
+                        "Auth plugin {command} failed: {}",
+                        String::from_utf8_lossy(&output.stderr).trim()
+                    );
+                }
+                let (token, ttl) = parse_plugin_output(&String::from_utf8_lossy(&output.stdout))?;
+                Ok((token, Some(ttl.unwrap_or(EXEC_DEFAULT_TTL))))
+            }
+        }
+    }
+}
+
+impl fmt::Display for AuthProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Static(_) => write!(f, "static bearer token"),
+            Self::Oidc { token_url, .. } => write!(f, "OIDC client credentials ({token_url})"),
+            Self::Exec { command, .. } => write!(f, "exec plugin ({command})"),
+        }
+    }
+}
+
+/// Token endpoint or exec plugin JSON
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    #[serde(alias = "token")]
+    access_token: String,
+    #[serde(default)]
+    expires_in: Option<u64>,
+}
+
+impl TokenResponse {
+    fn into_token(self) -> Result<(String, Option<Duration>)> {
+        if self.access_token.is_empty() {
+            anyhow::bail!("Token response has an empty token");
+        }
+        Ok((self.access_token, self.expires_in.map(Duration::from_secs)))
+    }
+}
+
+/// Token from exec plugin stdout: JSON or the bare token
+fn parse_plugin_output(stdout: &str) -> Result<(String, Option<Duration>)> {
+    let stdout = stdout.trim();
+    if stdout.starts_with('{') {
+        return serde_json::from_str::<TokenResponse>(stdout)
+            .context("Invalid auth plugin JSON")?
+            .into_token();
+    }
+    if stdout.is_empty() || stdout.contains(char::is_whitespace) {
+        anyhow::bail!("Auth plugin printed no single token");
+    }
+    Ok((stdout.to_string(), None))
+}
+
+#[derive(Debug)]
+struct CachedToken {
+    value: String,
+    expires_at: Option<Instant>,
+}
+
+impl CachedToken {
+    fn is_fresh(&self) -> bool {
+        self.expires_at
+            .is_none_or(|at| Instant::now() + REFRESH_MARGIN < at)
+    }
+}
+
+/// Authorization headers from a provider, shared by clones of a client
+#[derive(Clone, Debug)]
+pub struct AuthHeaders {
+    provider: Arc<AuthProvider>,
+    cached: Arc<Mutex<Option<CachedToken>>>,
+}
+
+impl AuthHeaders {
+    pub fn new(provider: AuthProvider) -> Self {
+        Self {
+            provider: Arc::new(provider),
+            cached: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Current `Authorization` value, fetching a new token when needed
+    pub async fn header_value(&self) -> Result<String> {
+        let mut cached = self.cached.lock().await;
+        if let Some(token) = cached.as_ref().filter(|t| t.is_fresh()) {
+            return Ok(format!("Bearer {}", token.value));
+        }
+
+        let (value, ttl) = self
+            .provider
+            .fetch()
+            .await
+            .with_context(|| format!("Failed to obtain token from {}", self.provider))?;
+        debug!("Fetched token from {}", self.provider);
+        let header = format!("Bearer {value}");
+        *cached = Some(CachedToken {
+            value,
+            expires_at: ttl.map(|ttl| Instant::now() + ttl),
+        });
+        Ok(header)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_auth_spec() {
+        assert_eq!(
+            AuthProvider::parse("bearer:abc").unwrap(),
+            AuthProvider::Static("abc".to_string())
+        );
+        assert_eq!(
+            AuthProvider::parse("exec:vault read -field=token secret/gw").unwrap(),
+            AuthProvider::Exec {
+                command: "vault".to_string(),
+                args: vec!["read".into(), "-field=token".into(), "secret/gw".into()],
+            }
+        );
+        assert!(AuthProvider::parse("bearer:").is_err());
+        assert!(AuthProvider::parse("basic:user").is_err());
+        assert!(AuthProvider::parse("token").is_err());
+        assert!(AuthProvider::parse("bearer-env:GATEWAY_POC_UNSET_TOKEN_VAR").is_err());
+    }
+
+    #[test]
+    fn test_parse_plugin_output() {
+        assert_eq!(
+            parse_plugin_output("abc\n").unwrap(),
+            ("abc".to_string(), None)
+        );
+        assert_eq!(
+            parse_plugin_output(r#"{"token": "abc", "expires_in": 60}"#).unwrap(),
+            ("abc".to_string(), Some(Duration::from_secs(60)))
+        );
+        assert!(parse_plugin_output("").is_err());
+        assert!(parse_plugin_output("not a token").is_err());
+        assert!(parse_plugin_output(r#"{"access_token": ""}"#).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_exec_token_cached() {
+        let auth = AuthHeaders::new(AuthProvider::Exec {
+            command: "echo".to_string(),
+            args: vec![r#"{"access_token":"t1","expires_in":3600}"#.to_string()],
+        });
+        assert_eq!(auth.header_value().await.unwrap(), "Bearer t1");
+        assert!(auth.cached.lock().await.as_ref().unwrap().is_fresh());
+
+        // Tokens inside the refresh margin are fetched again
+        let stale = AuthHeaders::new(AuthProvider::Static("s".to_string()));
+        *stale.cached.lock().await = Some(CachedToken {
+            value: "old".to_string(),
+            expires_at: Some(Instant::now() + Duration::from_secs(5)),
+        });
+        assert_eq!(stale.header_value().await.unwrap(), "Bearer s");
+    }
+}
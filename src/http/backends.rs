@@ -0,0 +1,43 @@
+//! Per-test backend tracking
+//!
+//! Counts which backend pods answered a test's requests, from the identity
+//! echo backends report. Each test gets its own tracker on a clone of the
+//! shared client, so concurrent tests are attributed separately.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use super::client::HttpResponse;
+use crate::models::{BackendHits, BackendIdentity};
+
+/// Backend pods seen by one test
+#[derive(Clone, Debug, Default)]
+pub struct BackendTracker {
+    seen: Arc<Mutex<BTreeMap<BackendIdentity, u64>>>,
+}
+
+impl BackendTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count the backend of a response, if it reported one
+    pub(crate) fn record(&self, response: &HttpResponse) {
+        if let Some(backend) = response.backend_identity() {
+            *self.seen.lock().unwrap().entry(backend).or_default() += 1;
+        }
+    }
+
+    /// Backends seen so far, in identity order
+    pub fn hits(&self) -> Vec<BackendHits> {
+        self.seen
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(backend, requests)| BackendHits {
+                backend: backend.clone(),
+                requests: *requests,
+            })
+            .collect()
+    }
+}
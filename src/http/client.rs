@@ -1,6 +1,8 @@
 //! HTTP client for Gateway API testing
 //!
 //! Provides a high-level HTTP client for testing Gateway implementations.
+//! Functional tests and benchmarks share this client, so connection-pool
+//! settings and latency measurement are identical across both.
 
 #![allow(dead_code)]
 
@@ -11,9 +13,20 @@ use reqwest::{
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::time::Duration;
+use std::net::{IpAddr, Ipv6Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use thiserror::Error;
-use tracing::debug;
+use tracing::{debug, debug_span, trace, Instrument};
+
+use super::auth::AuthHeaders;
+use super::backends::BackendTracker;
+use super::dns::OverrideResolver;
+use super::echo::EchoResponse;
+use super::metrics::HttpMetrics;
+use crate::models::{
+    is_sensitive, BackendIdentity, ConnectionSettings, DnsOverrides, LatencyStats, REDACTED,
+};
 
 /// HTTP client errors
 #[derive(Error, Debug)]
@@ -34,48 +47,229 @@ pub enum HttpError {
     TlsError(String),
 }
 
+/// `host:port` for a URL, bracketing IPv6 literals (`[fd00::1]:80`)
+pub fn authority(host: &str, port: u16) -> String {
+    match host.parse::<Ipv6Addr>() {
+        Ok(_) => format!("[{host}]:{port}"),
+        Err(_) => format!("{host}:{port}"),
+    }
+}
+
+/// Connection settings shared by functional tests and benchmarks
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HttpClientConfig {
+    /// Total request timeout in seconds
+    pub timeout_secs: u64,
+    /// TCP connect timeout in seconds
+    pub connect_timeout_secs: u64,
+    /// Idle keep-alive connections kept per host (0 disables keep-alive)
+    pub pool_max_idle_per_host: usize,
+    /// Seconds an idle pooled connection is kept
+    pub pool_idle_timeout_secs: u64,
+    /// Disable Nagle's algorithm
+    pub tcp_nodelay: bool,
+    /// Accept self-signed gateway certificates
+    pub accept_invalid_certs: bool,
+    /// Hostnames resolved to fixed addresses instead of system DNS
+    #[serde(skip_serializing_if = "DnsOverrides::is_empty")]
+    pub dns_overrides: DnsOverrides,
+    /// Local address connections are made from (None = chosen by the OS)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub local_address: Option<IpAddr>,
+    /// Client addresses available to tests that need several (source-IP affinity)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub source_addresses: Vec<IpAddr>,
+    /// Never negotiate HTTP/2, so a Host header is sent as given
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub http1_only: bool,
+}
+
+impl Default for HttpClientConfig {
+    fn default() -> Self {
+        Self {
+            timeout_secs: 30,
+            connect_timeout_secs: 10,
+            pool_max_idle_per_host: 32,
+            pool_idle_timeout_secs: 90,
+            tcp_nodelay: true,
+            accept_invalid_certs: true,
+            dns_overrides: DnsOverrides::default(),
+            local_address: None,
+            source_addresses: Vec::new(),
+            http1_only: false,
+        }
+    }
+}
+
+impl HttpClientConfig {
+    /// Set request timeout
+    pub fn with_timeout(mut self, timeout_secs: u64) -> Self {
+        self.timeout_secs = timeout_secs.max(1);
+        self.connect_timeout_secs = self.connect_timeout_secs.min(self.timeout_secs);
+        self
+    }
+
+    /// Set idle connections kept per host
+    pub fn with_pool_size(mut self, max_idle_per_host: usize) -> Self {
+        self.pool_max_idle_per_host = max_idle_per_host;
+        self
+    }
+
+    /// Resolve matching hostnames to fixed addresses
+    pub fn with_dns_overrides(mut self, overrides: DnsOverrides) -> Self {
+        self.dns_overrides = overrides;
+        self
+    }
+
+    /// Set TCP_NODELAY (true disables Nagle's algorithm)
+    pub fn with_tcp_nodelay(mut self, tcp_nodelay: bool) -> Self {
+        self.tcp_nodelay = tcp_nodelay;
+        self
+    }
+
+    /// Send from a fixed local address
+    pub fn with_local_address(mut self, address: IpAddr) -> Self {
+        self.local_address = Some(address);
+        self
+    }
+
+    /// Speak HTTP/1.1 only
+    pub fn with_http1_only(mut self) -> Self {
+        self.http1_only = true;
+        self
+    }
+
+    /// Apply connection reuse settings
+    pub fn with_connection(mut self, connection: &ConnectionSettings) -> Self {
+        if let Some(max_idle) = connection.max_idle_per_host {
+            self.pool_max_idle_per_host = max_idle;
+        }
+        self.source_addresses = connection.source_addresses.clone();
+        self.with_tcp_nodelay(connection.tcp_nodelay)
+            .with_keep_alive(connection.keep_alive)
+    }
+
+    /// Enable or disable connection reuse
+    pub fn with_keep_alive(mut self, keep_alive: bool) -> Self {
+        if !keep_alive {
+            self.pool_max_idle_per_host = 0;
+        }
+        self
+    }
+
+    /// Whether pooled connections are reused
+    pub fn keep_alive(&self) -> bool {
+        self.pool_max_idle_per_host > 0
+    }
+
+    /// reqwest builder with these settings applied
+    fn builder(&self) -> reqwest::ClientBuilder {
+        let mut builder = Client::builder()
+            .timeout(Duration::from_secs(self.timeout_secs))
+            .connect_timeout(Duration::from_secs(self.connect_timeout_secs))
+            .pool_max_idle_per_host(self.pool_max_idle_per_host)
+            .pool_idle_timeout(Duration::from_secs(self.pool_idle_timeout_secs))
+            .tcp_nodelay(self.tcp_nodelay)
+            .local_address(self.local_address)
+            .danger_accept_invalid_certs(self.accept_invalid_certs);
+        if self.http1_only {
+            builder = builder.http1_only();
+        }
+        if self.dns_overrides.is_empty() {
+            builder
+        } else {
+            builder.dns_resolver(Arc::new(OverrideResolver::new(self.dns_overrides.clone())))
+        }
+    }
+}
+
 /// HTTP client for testing
 #[derive(Clone)]
 pub struct HttpClient {
     client: Client,
+    config: HttpClientConfig,
     base_url: Option<String>,
     default_headers: HeaderMap,
-    timeout_secs: u64,
+    metrics: Option<HttpMetrics>,
+    backends: Option<BackendTracker>,
+    auth: Option<AuthHeaders>,
 }
 
 impl HttpClient {
     /// Create a new HTTP client
     pub fn new() -> Result<Self> {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(30))
-            .danger_accept_invalid_certs(true)
+        Self::from_config(HttpClientConfig::default())
+    }
+
+    /// Create client with custom timeout
+    pub fn with_timeout(timeout_secs: u64) -> Result<Self> {
+        Self::from_config(HttpClientConfig::default().with_timeout(timeout_secs))
+    }
+
+    /// Create client from connection settings
+    pub fn from_config(config: HttpClientConfig) -> Result<Self> {
+        let client = config
+            .builder()
             .build()
             .context("Failed to create HTTP client")?;
 
         Ok(Self {
             client,
+            config,
             base_url: None,
             default_headers: HeaderMap::new(),
-            timeout_secs: 30,
+            metrics: None,
+            backends: None,
+            auth: None,
         })
     }
 
-    /// Create client with custom timeout
-    pub fn with_timeout(timeout_secs: u64) -> Result<Self> {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(timeout_secs))
-            .danger_accept_invalid_certs(true)
+    /// Connection settings in use
+    pub fn config(&self) -> &HttpClientConfig {
+        &self.config
+    }
+
+    /// Same client opening a new connection per request, optionally from a
+    /// fixed local address, so every request is a fresh flow
+    pub fn unpooled(&self, local_address: Option<IpAddr>) -> Result<Self> {
+        let mut config = self.config.clone().with_keep_alive(false);
+        config.local_address = local_address.or(config.local_address);
+        self.reconfigured(config)
+    }
+
+    /// Same client (metrics, auth, backend tracking) with other connection settings
+    pub fn reconfigured(&self, config: HttpClientConfig) -> Result<Self> {
+        let client = config
+            .builder()
             .build()
             .context("Failed to create HTTP client")?;
-
         Ok(Self {
             client,
-            base_url: None,
-            default_headers: HeaderMap::new(),
-            timeout_secs,
+            config,
+            ..self.clone()
         })
     }
 
+    /// Count every request in a shared registry (clones share it too)
+    pub fn with_metrics(mut self, metrics: HttpMetrics) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Count the backend pods that answer this client's requests
+    pub fn with_backend_tracker(mut self, tracker: BackendTracker) -> Self {
+        self.backends = Some(tracker);
+        self
+    }
+
+    /// Send an `Authorization` header from a token provider (clones share
+    /// the cached token); requests that set their own header keep it
+    pub fn with_auth(mut self, auth: AuthHeaders) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
     /// Set base URL for requests
     pub fn base_url(mut self, url: impl Into<String>) -> Self {
         self.base_url = Some(url.into());
@@ -107,6 +301,86 @@ impl HttpClient {
 
     /// Send HTTP request
     pub async fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        self.send_timed(request).await.0
+    }
+
+    /// Send HTTP request, returning latency in milliseconds even on failure
+    ///
+    /// Latency covers connection setup through the last body byte, the same
+    /// measurement benchmarks record.
+    pub async fn send_timed(&self, request: HttpRequest) -> (Result<HttpResponse>, f64) {
+        let span = debug_span!("request", method = %request.method, url = %request.url);
+        let bytes_sent = request.body_len();
+        let (result, latency_ms) = match self.auth_header(&request).await {
+            Ok(auth) => {
+                let start = Instant::now();
+                let result = self
+                    .execute(request, auth, start, None)
+                    .instrument(span)
+                    .await;
+                (result, start.elapsed().as_secs_f64() * 1000.0)
+            }
+            Err(e) => (Err(e), 0.0),
+        };
+        self.observe(bytes_sent, &result);
+        (result, latency_ms)
+    }
+
+    /// Send HTTP request, reading the body chunk by chunk and recording when
+    /// each chunk arrives (for streamed responses such as server-sent events)
+    pub async fn send_streaming(&self, request: HttpRequest) -> Result<StreamedResponse> {
+        let span = debug_span!("stream", method = %request.method, url = %request.url);
+        let bytes_sent = request.body_len();
+        let mut chunks = Vec::new();
+        let result = match self.auth_header(&request).await {
+            Ok(auth) => {
+                self.execute(request, auth, Instant::now(), Some(&mut chunks))
+                    .instrument(span)
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+        self.observe(bytes_sent, &result);
+        Ok(StreamedResponse {
+            response: result?,
+            chunks,
+        })
+    }
+
+    /// Feed a finished request to the attached metrics and backend tracker
+    fn observe(&self, bytes_sent: usize, result: &Result<HttpResponse>) {
+        if let Some(metrics) = &self.metrics {
+            metrics.record(bytes_sent, result);
+        }
+        if let (Some(backends), Ok(response)) = (&self.backends, result) {
+            backends.record(response);
+        }
+    }
+
+    /// Provider token for the request, unless the test sets its own
+    /// credentials; resolved before the request timer starts so token
+    /// fetches don't count as latency
+    async fn auth_header(&self, request: &HttpRequest) -> Result<Option<String>> {
+        let Some(auth) = &self.auth else {
+            return Ok(None);
+        };
+        let own = request
+            .headers
+            .keys()
+            .any(|k| k.eq_ignore_ascii_case("authorization"));
+        if own {
+            return Ok(None);
+        }
+        auth.header_value().await.map(Some)
+    }
+
+    async fn execute(
+        &self,
+        request: HttpRequest,
+        auth: Option<String>,
+        start: Instant,
+        chunks: Option<&mut Vec<StreamChunk>>,
+    ) -> Result<HttpResponse> {
         let url = self.build_url(&request.url);
         debug!("Sending {} request to {}", request.method, url);
 
@@ -120,21 +394,47 @@ impl HttpClient {
             req_builder = req_builder.header(key, value);
         }
 
+        // Add provider token
+        if let Some(auth) = auth {
+            req_builder = req_builder.header("authorization", auth);
+        }
+
         // Add request headers
         for (key, value) in &request.headers {
             req_builder = req_builder.header(key.as_str(), value.as_str());
         }
+        trace!(
+            "Request headers: {:?} (defaults: {:?})",
+            loggable_headers(
+                request
+                    .headers
+                    .iter()
+                    .map(|(k, v)| (k.as_str(), v.as_str()))
+            ),
+            loggable_headers(
+                self.default_headers
+                    .iter()
+                    .map(|(k, v)| (k.as_str(), v.to_str().unwrap_or("<binary>")))
+            )
+        );
 
         // Add body if present
         if let Some(body) = &request.body {
             req_builder = req_builder.body(body.clone());
+        } else if let Some(filler) = request.filler {
+            req_builder = req_builder.body(filler.body());
         }
-
-        let start = std::time::Instant::now();
+        let timeout_secs = match request.timeout {
+            Some(timeout) => {
+                req_builder = req_builder.timeout(timeout);
+                timeout.as_secs()
+            }
+            None => self.config.timeout_secs,
+        };
 
         let response = req_builder.send().await.map_err(|e| {
             if e.is_timeout() {
-                anyhow::anyhow!(HttpError::Timeout(self.timeout_secs))
+                anyhow::anyhow!(HttpError::Timeout(timeout_secs))
             } else if e.is_connect() {
                 anyhow::anyhow!(HttpError::ConnectionRefused(url.clone()))
             } else {
@@ -142,9 +442,9 @@ impl HttpClient {
             }
         })?;
 
-        let duration_ms = start.elapsed().as_millis() as u64;
         let status = response.status();
         let headers = response.headers().clone();
+        let remote_addr = response.remote_addr();
 
         // Extract response headers
         let mut response_headers = HashMap::new();
@@ -154,23 +454,52 @@ impl HttpClient {
             }
         }
 
-        let body = response
-            .text()
-            .await
-            .context("Failed to read response body")?;
+        let body = match chunks {
+            Some(chunks) => {
+                let mut response = response;
+                let mut bytes = Vec::new();
+                while let Some(chunk) = response
+                    .chunk()
+                    .await
+                    .context("Failed to read response body")?
+                {
+                    bytes.extend_from_slice(&chunk);
+                    chunks.push(StreamChunk {
+                        end: bytes.len(),
+                        offset_ms: start.elapsed().as_secs_f64() * 1000.0,
+                    });
+                }
+                String::from_utf8_lossy(&bytes).into_owned()
+            }
+            None => response
+                .text()
+                .await
+                .context("Failed to read response body")?,
+        };
 
+        let latency_ms = start.elapsed().as_secs_f64() * 1000.0;
         debug!(
-            "Response: {} {} in {}ms",
+            "Response: {} {} in {:.2}ms",
             status.as_u16(),
             status.canonical_reason().unwrap_or(""),
-            duration_ms
+            latency_ms
+        );
+        trace!(
+            "Response headers: {:?}",
+            loggable_headers(
+                response_headers
+                    .iter()
+                    .map(|(k, v)| (k.as_str(), v.as_str()))
+            )
         );
 
         Ok(HttpResponse {
             status_code: status.as_u16(),
             headers: response_headers,
             body,
-            duration_ms,
+            duration_ms: latency_ms.round() as u64,
+            latency_ms,
+            remote_addr,
         })
     }
 
@@ -189,7 +518,15 @@ impl HttpClient {
     }
 
     /// GET with Host header
+    ///
+    /// When a DNS override resolves `hostname`, the URL's host is replaced
+    /// by it instead of sending a Host header.
     pub async fn get_with_host(&self, url: &str, hostname: &str) -> Result<HttpResponse> {
+        if self.resolves(hostname) {
+            let mut url = reqwest::Url::parse(url).context("Invalid URL")?;
+            url.set_host(Some(hostname)).context("Invalid hostname")?;
+            return self.get(url.as_str()).await;
+        }
         let mut headers = HashMap::new();
         headers.insert("Host".to_string(), hostname.to_string());
         self.get_with_headers(url, headers).await
@@ -200,14 +537,25 @@ impl HttpClient {
         self.send(HttpRequest::post(url).body(body)).await
     }
 
+    /// Whether requests to `hostname` are resolved by a DNS override
+    pub fn resolves(&self, hostname: &str) -> bool {
+        self.config.dns_overrides.lookup(hostname).is_some()
+    }
+
     /// Test host routing
+    ///
+    /// Requests the hostname itself when a DNS override resolves it,
+    /// otherwise the gateway IP with a Host header.
     pub async fn test_host_routing(
         &self,
         ip: &str,
         port: u16,
         hostname: &str,
     ) -> Result<HttpResponse> {
-        let url = format!("http://{ip}:{port}/");
+        if self.resolves(hostname) {
+            return self.get(&format!("http://{hostname}:{port}/")).await;
+        }
+        let url = format!("http://{}/", authority(ip, port));
         let mut headers = HashMap::new();
         headers.insert("Host".to_string(), hostname.to_string());
         self.get_with_headers(&url, headers).await
@@ -215,7 +563,7 @@ impl HttpClient {
 
     /// Test path routing
     pub async fn test_path_routing(&self, ip: &str, port: u16, path: &str) -> Result<HttpResponse> {
-        let url = format!("http://{ip}:{port}{path}");
+        let url = format!("http://{}{path}", authority(ip, port));
         self.get(&url).await
     }
 
@@ -227,7 +575,7 @@ impl HttpClient {
         header_name: &str,
         header_value: &str,
     ) -> Result<HttpResponse> {
-        let url = format!("http://{ip}:{port}/");
+        let url = format!("http://{}/", authority(ip, port));
         let mut headers = HashMap::new();
         headers.insert(header_name.to_string(), header_value.to_string());
         self.get_with_headers(&url, headers).await
@@ -235,21 +583,55 @@ impl HttpClient {
 
     /// Test HTTPS endpoint
     pub async fn test_https(&self, ip: &str, port: u16, path: &str) -> Result<HttpResponse> {
-        let url = format!("https://{ip}:{port}{path}");
+        let url = format!("https://{}{path}", authority(ip, port));
         self.get(&url).await
     }
 
+    /// Test HTTPS endpoint by hostname (SNI) when a DNS override resolves it
+    pub async fn test_https_host(
+        &self,
+        ip: &str,
+        port: u16,
+        hostname: &str,
+        path: &str,
+    ) -> Result<HttpResponse> {
+        if self.resolves(hostname) {
+            return self.get(&format!("https://{hostname}:{port}{path}")).await;
+        }
+        self.test_https(ip, port, path).await
+    }
+
     /// Test redirect
-    pub async fn test_redirect(&self, url: &str) -> Result<(u16, Option<String>)> {
+    ///
+    /// A hostname is sent like `get_with_host` does: in the URL when a DNS
+    /// override resolves it, otherwise as the Host header.
+    pub async fn test_redirect(
+        &self,
+        url: &str,
+        hostname: Option<&str>,
+    ) -> Result<(u16, Option<String>)> {
         // Don't follow redirects for this test
-        let client = Client::builder()
+        let client = self
+            .config
+            .builder()
             .redirect(reqwest::redirect::Policy::none())
-            .timeout(Duration::from_secs(10))
-            .danger_accept_invalid_certs(true)
             .build()
             .context("Failed to create client")?;
 
-        let response = client.get(url).send().await?;
+        let mut url = reqwest::Url::parse(url).context("Invalid URL")?;
+        let mut host_header = None;
+        match hostname {
+            Some(hostname) if self.resolves(hostname) => {
+                url.set_host(Some(hostname)).context("Invalid hostname")?
+            }
+            Some(hostname) => host_header = Some(hostname),
+            None => {}
+        }
+        let mut request = client.get(url);
+        if let Some(hostname) = host_header {
+            request = request.header("Host", hostname);
+        }
+        let response = request.send().await?;
         let status = response.status().as_u16();
         let location = response
             .headers()
@@ -270,30 +652,26 @@ impl HttpClient {
         use futures::future::join_all;
 
         let mut handles = Vec::new();
-        let requests_per_task = total / concurrent;
+        let concurrent = concurrent.clamp(1, total.max(1));
 
-        for _ in 0..concurrent {
+        for task in 0..concurrent {
             let client = self.clone();
             let url = url.to_string();
+            // Spread the remainder so exactly `total` requests are sent
+            let requests = total / concurrent + usize::from(task < total % concurrent);
 
             let handle = tokio::spawn(async move {
-                let mut successes = 0;
                 let mut failures = 0;
-                let mut total_duration = 0u64;
-
-                for _ in 0..requests_per_task {
-                    match client.get(&url).await {
-                        Ok(resp) if resp.is_success() => {
-                            successes += 1;
-                            total_duration += resp.duration_ms;
-                        }
-                        _ => {
-                            failures += 1;
-                        }
+                let mut latencies = Vec::with_capacity(requests);
+
+                for _ in 0..requests {
+                    match client.send_timed(HttpRequest::get(&url)).await {
+                        (Ok(resp), latency_ms) if resp.is_success() => latencies.push(latency_ms),
+                        _ => failures += 1,
                     }
                 }
 
-                (successes, failures, total_duration)
+                (latencies, failures)
             });
 
             handles.push(handle);
@@ -301,27 +679,22 @@ impl HttpClient {
 
         let results = join_all(handles).await;
 
-        let mut total_successes = 0;
+        let mut latencies = Vec::with_capacity(total);
         let mut total_failures = 0;
-        let mut total_duration = 0u64;
 
-        for (s, f, d) in results.into_iter().flatten() {
-            total_successes += s;
+        for (l, f) in results.into_iter().flatten() {
+            latencies.extend(l);
             total_failures += f;
-            total_duration += d;
         }
 
-        let avg_duration = if total_successes > 0 {
-            total_duration / total_successes as u64
-        } else {
-            0
-        };
+        let latency = LatencyStats::from_samples(&latencies);
 
         Ok(LoadTestResult {
             total_requests: total,
-            successes: total_successes,
+            successes: latencies.len(),
             failures: total_failures,
-            avg_duration_ms: avg_duration,
+            avg_duration_ms: latency.mean.round() as u64,
+            latency,
         })
     }
 }
@@ -339,6 +712,35 @@ pub struct HttpRequest {
     pub url: String,
     pub headers: HashMap<String, String>,
     pub body: Option<String>,
+    /// Generated body sent when `body` is unset
+    pub filler: Option<FillerBody>,
+    /// Overrides the client timeout for this request
+    pub timeout: Option<Duration>,
+}
+
+/// Body of filler bytes generated while sending, so large uploads need no
+/// buffer of their own
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FillerBody {
+    pub size: usize,
+    /// Sent in chunks without Content-Length (chunked transfer encoding)
+    pub streamed: bool,
+}
+
+impl FillerBody {
+    const CHUNK: usize = 64 * 1024;
+
+    fn body(self) -> reqwest::Body {
+        if !self.streamed {
+            return vec![b'a'; self.size].into();
+        }
+        let size = self.size;
+        let chunks = (0..size.div_ceil(Self::CHUNK)).map(move |i| {
+            let len = Self::CHUNK.min(size - i * Self::CHUNK);
+            Ok::<_, std::io::Error>(vec![b'a'; len])
+        });
+        reqwest::Body::wrap_stream(futures::stream::iter(chunks))
+    }
 }
 
 impl HttpRequest {
@@ -348,6 +750,8 @@ impl HttpRequest {
             url: url.into(),
             headers: HashMap::new(),
             body: None,
+            filler: None,
+            timeout: None,
         }
     }
 
@@ -381,6 +785,53 @@ impl HttpRequest {
         self.body = Some(body.into());
         self
     }
+
+    /// Send `size` filler bytes, streamed (chunked) or with a Content-Length
+    pub fn filler(mut self, size: usize, streamed: bool) -> Self {
+        self.filler = Some(FillerBody { size, streamed });
+        self
+    }
+
+    /// Time allowed for this request instead of the client's timeout
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Request body size in bytes
+    pub fn body_len(&self) -> usize {
+        match (&self.body, self.filler) {
+            (Some(body), _) => body.len(),
+            (None, Some(filler)) => filler.size,
+            (None, None) => 0,
+        }
+    }
+}
+
+/// Arrival of one body chunk of a streamed response
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StreamChunk {
+    /// Body length in bytes after this chunk
+    pub end: usize,
+    /// Milliseconds from sending the request until the chunk arrived
+    pub offset_ms: f64,
+}
+
+/// Response read chunk by chunk
+#[derive(Clone, Debug)]
+pub struct StreamedResponse {
+    pub response: HttpResponse,
+    pub chunks: Vec<StreamChunk>,
+}
+
+impl StreamedResponse {
+    /// When the body byte at `position` arrived
+    pub fn arrival_ms(&self, position: usize) -> Option<f64> {
+        self.chunks
+            .iter()
+            .find(|c| c.end > position)
+            .map(|c| c.offset_ms)
+    }
 }
 
 /// HTTP response
@@ -390,9 +841,32 @@ pub struct HttpResponse {
     pub headers: HashMap<String, String>,
     pub body: String,
     pub duration_ms: u64,
+    /// Full request latency in milliseconds
+    #[serde(default)]
+    pub latency_ms: f64,
+    /// Address the connection was made to (shows the IPv4/IPv6 path taken)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_addr: Option<SocketAddr>,
 }
 
 impl HttpResponse {
+    /// Response as a test double, with a 1 ms latency
+    #[cfg(test)]
+    pub(crate) fn fixture(
+        status_code: u16,
+        headers: HashMap<String, String>,
+        body: impl Into<String>,
+    ) -> Self {
+        HttpResponse {
+            status_code,
+            headers,
+            body: body.into(),
+            duration_ms: 1,
+            latency_ms: 1.0,
+            remote_addr: None,
+        }
+    }
+
     pub fn is_success(&self) -> bool {
         (200..300).contains(&self.status_code)
     }
@@ -416,8 +890,91 @@ impl HttpResponse {
     pub fn body_contains(&self, text: &str) -> bool {
         self.body.contains(text)
     }
+
+    /// Request and backend as reported by an echo backend
+    pub fn echo(&self) -> Option<EchoResponse> {
+        EchoResponse::parse(&self.body)
+    }
+
+    /// Host header the backend reports receiving, if it echoes requests
+    ///
+    /// Understands JSON echo servers (`host`, `headers.host`,
+    /// `request.headers.host`) and plain-text `Host: ...` dumps.
+    pub fn echoed_host(&self) -> Option<String> {
+        if let Some(echo) = self.echo().filter(|echo| !echo.host.is_empty()) {
+            return Some(echo.host);
+        }
+        self.echoed_header("host")
+    }
+
+    /// Request header the backend reports receiving, if it echoes requests
+    pub fn echoed_header(&self, name: &str) -> Option<String> {
+        self.echoed_headers()
+            .into_iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value)
+    }
+
+    /// Request headers the backend reports receiving, if it echoes requests
+    ///
+    /// Reads JSON echo bodies (`headers` or `request.headers`, one entry per
+    /// listed value) and plain-text `Name: value` dumps. Names keep the case
+    /// the backend reports.
+    pub fn echoed_headers(&self) -> Vec<(String, String)> {
+        if let Ok(json) = serde_json::from_str::<serde_json::Value>(&self.body) {
+            let headers = json
+                .get("headers")
+                .or_else(|| json.pointer("/request/headers"))
+                .and_then(|h| h.as_object());
+            return headers
+                .into_iter()
+                .flatten()
+                .flat_map(|(name, value)| {
+                    let values = match value {
+                        serde_json::Value::Array(values) => values.clone(),
+                        value => vec![value.clone()],
+                    };
+                    values
+                        .into_iter()
+                        .filter_map(|v| v.as_str().map(str::to_string))
+                        .map(move |v| (name.clone(), v))
+                })
+                .collect();
+        }
+
+        self.body
+            .lines()
+            .filter_map(|line| {
+                let (name, value) = line.split_once(':')?;
+                Some((name.trim().to_string(), value.trim().to_string()))
+            })
+            .collect()
+    }
+
+    /// Backend pod that served the request, if the backend reports it
+    ///
+    /// Reads `x-pod-name`-style identity headers, falling back to the
+    /// identity fields of echo bodies.
+    pub fn backend_identity(&self) -> Option<BackendIdentity> {
+        if let Some(pod) = self.get_header(POD_NAME_HEADER) {
+            return Some(BackendIdentity {
+                pod: pod.clone(),
+                namespace: self.get_header(POD_NAMESPACE_HEADER).cloned(),
+                service: self.get_header(SERVICE_NAME_HEADER).cloned(),
+                node: self.get_header(NODE_NAME_HEADER).cloned(),
+            });
+        }
+
+        self.echo()?.identity()
+    }
 }
 
+/// Response headers echo backends set from the downward API
+const POD_NAME_HEADER: &str = "x-pod-name";
+const POD_NAMESPACE_HEADER: &str = "x-pod-namespace";
+const SERVICE_NAME_HEADER: &str = "x-service-name";
+const NODE_NAME_HEADER: &str = "x-node-name";
+
 /// Load test result
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct LoadTestResult {
@@ -425,6 +982,9 @@ pub struct LoadTestResult {
     pub successes: usize,
     pub failures: usize,
     pub avg_duration_ms: u64,
+    /// Latency distribution of successful requests
+    #[serde(default)]
+    pub latency: LatencyStats,
 }
 
 impl LoadTestResult {
@@ -437,6 +997,26 @@ impl LoadTestResult {
     }
 }
 
+/// Headers as written to trace logs, with credentials and cookies redacted
+fn loggable_headers<'a>(
+    headers: impl Iterator<Item = (&'a str, &'a str)>,
+) -> Vec<(&'a str, &'a str)> {
+    headers
+        .map(|(name, value)| {
+            let hidden = [
+                "authorization",
+                "proxy-authorization",
+                "cookie",
+                "set-cookie",
+            ]
+            .iter()
+            .any(|h| name.eq_ignore_ascii_case(h))
+                || is_sensitive(name);
+            (name, if hidden { REDACTED } else { value })
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -458,11 +1038,57 @@ mod tests {
             headers: HashMap::new(),
             body: "Hello World".to_string(),
             duration_ms: 100,
+            latency_ms: 100.0,
+            remote_addr: None,
         };
 
         assert!(resp.is_success());
         assert!(!resp.is_redirect());
         assert!(resp.body_contains("Hello"));
+        assert_eq!(resp.echoed_host(), None);
+    }
+
+    #[test]
+    fn test_echoed_host() {
+        let mut resp = HttpResponse::fixture(
+            200,
+            HashMap::new(),
+            r#"{"path":"/","host":"backend.example.com"}"#,
+        );
+        assert_eq!(resp.echoed_host().as_deref(), Some("backend.example.com"));
+
+        resp.body = r#"{"request":{"headers":{"Host":["a.example.com"]}}}"#.to_string();
+        assert_eq!(resp.echoed_host().as_deref(), Some("a.example.com"));
+
+        resp.body = "GET / HTTP/1.1\nHost: b.example.com\nAccept: */*".to_string();
+        assert_eq!(resp.echoed_host().as_deref(), Some("b.example.com"));
+        assert_eq!(resp.echoed_header("accept").as_deref(), Some("*/*"));
+
+        resp.body = r#"{"headers":{"X-Dup":["one","two"]}}"#.to_string();
+        assert_eq!(resp.echoed_headers().len(), 2);
+    }
+
+    #[test]
+    fn test_backend_identity() {
+        let mut resp = HttpResponse::fixture(
+            200,
+            HashMap::new(),
+            r#"{"path":"/","namespace":"default","service":"echo","pod":"echo-7d9f-abc"}"#,
+        );
+        let backend = resp.backend_identity().unwrap();
+        assert_eq!(backend.pod, "echo-7d9f-abc");
+        assert_eq!(backend.service.as_deref(), Some("echo"));
+        assert_eq!(backend.node, None);
+
+        resp.headers.insert("x-pod-name".into(), "echo-1".into());
+        resp.headers.insert("x-node-name".into(), "node-a".into());
+        let backend = resp.backend_identity().unwrap();
+        assert_eq!(backend.pod, "echo-1");
+        assert_eq!(backend.node.as_deref(), Some("node-a"));
+
+        resp.headers.clear();
+        resp.body = "plain text".to_string();
+        assert!(resp.backend_identity().is_none());
     }
 
     #[test]
@@ -472,8 +1098,76 @@ mod tests {
             successes: 90,
             failures: 10,
             avg_duration_ms: 50,
+            latency: LatencyStats::default(),
         };
 
         assert_eq!(result.success_rate(), 90.0);
     }
+
+    #[test]
+    fn test_client_config() {
+        let config = HttpClientConfig::default().with_timeout(5);
+        assert_eq!(config.timeout_secs, 5);
+        assert_eq!(config.connect_timeout_secs, 5);
+        assert!(config.keep_alive());
+
+        let config = config.with_keep_alive(false);
+        assert!(!config.keep_alive());
+
+        let client = HttpClient::from_config(config.clone()).unwrap();
+        assert_eq!(client.config(), &config);
+    }
+
+    #[test]
+    fn test_connection_settings() {
+        let settings = ConnectionSettings::new(true, Some(4), false);
+        let config = HttpClientConfig::default()
+            .with_pool_size(32)
+            .with_connection(&settings);
+        assert_eq!(config.pool_max_idle_per_host, 4);
+        assert!(!config.tcp_nodelay);
+        assert_eq!(
+            settings.to_string(),
+            "keep-alive (max 4 idle per host), Nagle enabled"
+        );
+
+        let config = config.with_connection(&ConnectionSettings::new(false, None, true));
+        assert!(!config.keep_alive());
+
+        let settings: ConnectionSettings =
+            serde_json::from_str(r#"{"keep_alive": false}"#).unwrap();
+        assert!(!settings.keep_alive && settings.tcp_nodelay);
+        assert!(ConnectionSettings::default().is_default());
+    }
+
+    #[test]
+    fn test_loggable_headers() {
+        let headers = [
+            ("Authorization", "Bearer abc"),
+            ("cookie", "session=1"),
+            ("Set-Cookie", "session=2"),
+            ("X-Api-Token", "t0k3n"),
+            ("Host", "example.com"),
+        ];
+        let logged = loggable_headers(headers.into_iter());
+        assert!(logged[..4].iter().all(|(_, v)| *v == REDACTED));
+        assert_eq!(logged[4], ("Host", "example.com"));
+    }
+
+    #[test]
+    fn test_authority() {
+        assert_eq!(authority("10.0.0.1", 80), "10.0.0.1:80");
+        assert_eq!(authority("fd00::1", 8080), "[fd00::1]:8080");
+        assert_eq!(authority("gw.example.com", 443), "gw.example.com:443");
+    }
+
+    #[test]
+    fn test_unpooled_client() {
+        let address: IpAddr = "127.0.0.2".parse().unwrap();
+        let client = HttpClient::new().unwrap();
+        let unpooled = client.unpooled(Some(address)).unwrap();
+        assert_eq!(unpooled.config().local_address, Some(address));
+        assert!(!unpooled.config().keep_alive());
+        assert!(client.config().keep_alive());
+    }
 }
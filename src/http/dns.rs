@@ -0,0 +1,119 @@
+//! DNS resolution overrides
+//!
+//! Resolves test hostnames (e.g. `app1.example.com` or `*.example.com`) to
+//! the gateway address inside the tool's HTTP clients, so requests can use
+//! real hostnames end to end without editing /etc/hosts. Other names go to
+//! the configured nameservers (or the system resolver), and the address
+//! family setting decides whether dual-stack names are raced happy-eyeballs
+//! style or pinned to IPv4 or IPv6.
+
+#![allow(dead_code)]
+
+use hyper::client::connect::dns::Name;
+use reqwest::dns::{Addrs, Resolve, Resolving};
+use std::io;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+
+use super::resolver::DnsClient;
+use crate::models::{DnsOverrides, IpFamily};
+
+/// Every address of `host` in both families, for checks that must reach
+/// each of them; an IP literal or overridden name has just the one
+pub async fn resolve_all(overrides: &DnsOverrides, host: &str) -> io::Result<Vec<IpAddr>> {
+    if let Some(ip) = host
+        .parse::<IpAddr>()
+        .ok()
+        .or_else(|| overrides.lookup(host))
+    {
+        return Ok(vec![ip]);
+    }
+    if !overrides.nameservers.is_empty() {
+        return DnsClient::new(overrides.nameservers.clone())
+            .lookup(host, IpFamily::Dual)
+            .await;
+    }
+    let mut addrs: Vec<IpAddr> = tokio::net::lookup_host((host, 0))
+        .await?
+        .map(|addr| addr.ip())
+        .collect();
+    addrs.sort();
+    addrs.dedup();
+    Ok(addrs)
+}
+
+/// reqwest resolver answering overridden names and sending the rest to the
+/// configured nameservers (or the system resolver), ordered by address family
+pub struct OverrideResolver {
+    overrides: Arc<DnsOverrides>,
+    client: Option<DnsClient>,
+}
+
+impl OverrideResolver {
+    pub fn new(overrides: DnsOverrides) -> Self {
+        let client = (!overrides.nameservers.is_empty())
+            .then(|| DnsClient::new(overrides.nameservers.clone()));
+        Self {
+            overrides: Arc::new(overrides),
+            client,
+        }
+    }
+}
+
+impl Resolve for OverrideResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let overrides = self.overrides.clone();
+        let client = self.client.clone();
+        Box::pin(async move {
+            // The port is ignored; reqwest connects to the URL's port
+            if let Some(ip) = overrides.lookup(name.as_str()) {
+                let addrs: Addrs = Box::new(std::iter::once(SocketAddr::new(ip, 0)));
+                return Ok(addrs);
+            }
+            let resolved = match &client {
+                Some(client) => client.lookup(name.as_str(), overrides.family).await?,
+                None => tokio::net::lookup_host((name.as_str(), 0))
+                    .await?
+                    .map(|addr| addr.ip())
+                    .collect(),
+            };
+            let ordered = overrides.family.order(resolved);
+            if ordered.is_empty() {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("No {} address for {}", overrides.family, name.as_str()),
+                )
+                .into());
+            }
+            let addrs: Addrs = Box::new(ordered.into_iter().map(|ip| SocketAddr::new(ip, 0)));
+            Ok(addrs)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_resolver_override() {
+        let resolver = OverrideResolver::new(
+            DnsOverrides::new().add("app1.example.com", "127.0.0.2".parse().unwrap()),
+        );
+        let addrs: Vec<SocketAddr> = resolver
+            .resolve("app1.example.com".parse().unwrap())
+            .await
+            .unwrap()
+            .collect();
+        assert_eq!(addrs, vec!["127.0.0.2:0".parse().unwrap()]);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_all() {
+        let overrides = DnsOverrides::new().add("gw.example.com", "10.0.0.1".parse().unwrap());
+        let addrs = resolve_all(&overrides, "fd00::1").await.unwrap();
+        assert_eq!(addrs, vec!["fd00::1".parse::<IpAddr>().unwrap()]);
+        let addrs = resolve_all(&overrides, "gw.example.com").await.unwrap();
+        assert_eq!(addrs, vec!["10.0.0.1".parse::<IpAddr>().unwrap()]);
+    }
+}
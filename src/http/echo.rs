@@ -0,0 +1,206 @@
+//! Echo backend protocol
+//!
+//! Fixture backends run Gateway API's echo-basic server, which answers
+//! every request with a JSON description of the request it received and
+//! of itself:
+//!
+//! ```json
+//! {"path": "/api", "host": "app1.example.com", "method": "GET",
+//!  "proto": "HTTP/1.1", "headers": {"Accept": ["*/*"]},
+//!  "namespace": "gateway-tests", "service": "app1",
+//!  "pod": "app1-6d4f9c7b8-x2x4q"}
+//! ```
+//!
+//! The identity comes from the `POD_NAME`, `NAMESPACE` and `SERVICE_NAME`
+//! variables the fixture sets on the container. HTTPS backends add a `tls`
+//! object describing the connection from the gateway.
+
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
+
+use crate::models::BackendIdentity;
+
+/// What an echo backend received, and which backend it is
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EchoResponse {
+    /// Request target, including any query string
+    pub path: String,
+    pub host: String,
+    pub method: String,
+    pub proto: String,
+    #[serde(deserialize_with = "header_values")]
+    pub headers: BTreeMap<String, Vec<String>>,
+    pub namespace: String,
+    pub ingress: String,
+    pub service: String,
+    pub pod: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls: Option<EchoTls>,
+}
+
+/// Connection from the gateway to an HTTPS echo backend
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct EchoTls {
+    pub version: String,
+    pub server_name: String,
+    pub negotiated_protocol: String,
+    pub cipher_suite: String,
+    /// Client certificates the gateway presented (PEM)
+    pub peer_certificates: Vec<String>,
+}
+
+impl EchoResponse {
+    /// Parse a response body; None unless it is a JSON object naming the
+    /// backend or describing the request
+    pub fn parse(body: &str) -> Option<Self> {
+        let echo: Self = serde_json::from_str(body).ok()?;
+        (!echo.pod.is_empty() || !echo.path.is_empty()).then_some(echo)
+    }
+
+    /// First value of a received request header (names are case-insensitive)
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .and_then(|(_, values)| values.first())
+            .map(String::as_str)
+    }
+
+    /// Received Host without a port
+    pub fn hostname(&self) -> &str {
+        match self.host.rsplit_once(':') {
+            Some((host, port)) if port.parse::<u16>().is_ok() && !host.ends_with(':') => host,
+            _ => &self.host,
+        }
+    }
+
+    /// Name of the backend that answered: its Service, or the Deployment
+    /// its pod belongs to when the Service is not reported
+    pub fn backend(&self) -> Option<&str> {
+        if !self.service.is_empty() {
+            return Some(&self.service);
+        }
+        // Deployment pods are named <deployment>-<template hash>-<suffix>
+        let mut parts = self.pod.rsplitn(3, '-');
+        let (_, _, deployment) = (parts.next()?, parts.next()?, parts.next()?);
+        Some(deployment)
+    }
+
+    /// Whether `backend` answered; unlike a substring match, `app` is not
+    /// mistaken for `app-canary`
+    pub fn served_by(&self, backend: &str) -> bool {
+        self.backend() == Some(backend)
+    }
+
+    /// Pod identity for backend tracking
+    pub fn identity(&self) -> Option<BackendIdentity> {
+        let field = |value: &str| (!value.is_empty()).then(|| value.to_string());
+        Some(BackendIdentity {
+            pod: field(&self.pod)?,
+            namespace: field(&self.namespace),
+            service: field(&self.service),
+            node: None,
+        })
+    }
+}
+
+/// Header values as lists; some echo servers report single values as strings
+fn header_values<'de, D>(deserializer: D) -> Result<BTreeMap<String, Vec<String>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Values {
+        Many(Vec<String>),
+        One(String),
+    }
+
+    let headers = Option::<BTreeMap<String, Values>>::deserialize(deserializer)?;
+    Ok(headers
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(name, values)| match values {
+            Values::Many(values) => (name, values),
+            Values::One(value) => (name, vec![value]),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BODY: &str = r#"{
+        "path": "/api?x=1", "host": "app1.example.com:8080", "method": "GET",
+        "proto": "HTTP/1.1",
+        "headers": {"Accept": ["*/*"], "X-Version": ["v1", "v2"], "User-Agent": "curl"},
+        "namespace": "gateway-tests", "ingress": "", "service": "app1",
+        "pod": "app1-6d4f9c7b8-x2x4q"
+    }"#;
+
+    #[test]
+    fn test_parse() {
+        let echo = EchoResponse::parse(BODY).unwrap();
+        assert_eq!(echo.path, "/api?x=1");
+        assert_eq!(echo.hostname(), "app1.example.com");
+        assert_eq!(echo.header("x-version"), Some("v1"));
+        assert_eq!(echo.header("user-agent"), Some("curl"));
+        assert_eq!(echo.headers["X-Version"], ["v1", "v2"]);
+        assert!(echo.tls.is_none());
+
+        let identity = echo.identity().unwrap();
+        assert_eq!(identity.pod, "app1-6d4f9c7b8-x2x4q");
+        assert_eq!(identity.namespace.as_deref(), Some("gateway-tests"));
+
+        assert!(EchoResponse::parse("app1").is_none());
+        assert!(EchoResponse::parse(r#"{"error": "not found"}"#).is_none());
+        assert!(EchoResponse::parse(r#"{"path": "/"}"#)
+            .unwrap()
+            .identity()
+            .is_none());
+    }
+
+    #[test]
+    fn test_served_by() {
+        let echo = EchoResponse::parse(BODY).unwrap();
+        assert!(echo.served_by("app1"));
+        assert!(!echo.served_by("app"));
+
+        // Without SERVICE_NAME the Deployment is read from the pod name
+        let canary = EchoResponse {
+            pod: "app-canary-6d4f9c7b8-x2x4q".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(canary.backend(), Some("app-canary"));
+        assert!(canary.served_by("app-canary"));
+        assert!(!canary.served_by("app"));
+        assert_eq!(EchoResponse::default().backend(), None);
+    }
+
+    #[test]
+    fn test_tls() {
+        let echo = EchoResponse::parse(
+            r#"{"path": "/", "pod": "tls-backend-1-2", "tls": {"version": "TLSv1.3",
+                "serverName": "tls.example.com", "negotiatedProtocol": "http/1.1",
+                "cipherSuite": "TLS_AES_128_GCM_SHA256", "peerCertificates": ["-----BEGIN"]}}"#,
+        )
+        .unwrap();
+        let tls = echo.tls.unwrap();
+        assert_eq!(tls.server_name, "tls.example.com");
+        assert_eq!(tls.peer_certificates.len(), 1);
+    }
+
+    #[test]
+    fn test_hostname() {
+        let echo = |host: &str| EchoResponse {
+            host: host.to_string(),
+            ..Default::default()
+        };
+        assert_eq!(echo("a.example.com").hostname(), "a.example.com");
+        assert_eq!(echo("[::1]:80").hostname(), "[::1]");
+        assert_eq!(echo("::1").hostname(), "::1");
+    }
+}
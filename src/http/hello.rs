@@ -0,0 +1,481 @@
+//! Raw TLS ClientHello probing
+//!
+//! rustls only speaks TLS 1.2 and 1.3 with modern cipher suites, so it cannot
+//! tell whether a server would also accept TLS 1.0 or RC4. These probes send
+//! a hand-built ClientHello offering exactly one protocol version and a given
+//! set of cipher suites, and read the server's first answer: a ServerHello
+//! selecting the offered version means it is accepted, an alert or a lower
+//! version means it is not. No handshake is completed.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::fmt;
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+const HANDSHAKE: u8 = 22;
+const ALERT: u8 = 21;
+const CLIENT_HELLO: u8 = 1;
+const SERVER_HELLO: u8 = 2;
+const SUPPORTED_VERSIONS: u16 = 0x002b;
+
+/// Protocol version offered by a probe
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub enum TlsVersion {
+    #[serde(rename = "TLSv1.0")]
+    Tls10,
+    #[serde(rename = "TLSv1.1")]
+    Tls11,
+    #[serde(rename = "TLSv1.2")]
+    Tls12,
+    #[serde(rename = "TLSv1.3")]
+    Tls13,
+}
+
+impl TlsVersion {
+    pub fn all() -> [TlsVersion; 4] {
+        [
+            TlsVersion::Tls10,
+            TlsVersion::Tls11,
+            TlsVersion::Tls12,
+            TlsVersion::Tls13,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TlsVersion::Tls10 => "TLSv1.0",
+            TlsVersion::Tls11 => "TLSv1.1",
+            TlsVersion::Tls12 => "TLSv1.2",
+            TlsVersion::Tls13 => "TLSv1.3",
+        }
+    }
+
+    /// Whether current guidance (RFC 8996) forbids the version
+    pub fn is_deprecated(&self) -> bool {
+        *self < TlsVersion::Tls12
+    }
+
+    fn wire(&self) -> u16 {
+        match self {
+            TlsVersion::Tls10 => 0x0301,
+            TlsVersion::Tls11 => 0x0302,
+            TlsVersion::Tls12 => 0x0303,
+            TlsVersion::Tls13 => 0x0304,
+        }
+    }
+
+    /// Suites a client of this version would typically offer
+    pub fn default_suites(&self) -> &'static [u16] {
+        match self {
+            TlsVersion::Tls10 | TlsVersion::Tls11 => {
+                &[0xc013, 0xc014, 0xc009, 0xc00a, 0x002f, 0x0035, 0x000a]
+            }
+            TlsVersion::Tls12 => &[
+                0xc02b, 0xc02f, 0xc02c, 0xc030, 0xcca9, 0xcca8, 0xc013, 0xc014, 0xc009, 0xc00a,
+                0x009c, 0x009d, 0x002f, 0x0035,
+            ],
+            TlsVersion::Tls13 => &[0x1301, 0x1302, 0x1303],
+        }
+    }
+}
+
+impl fmt::Display for TlsVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Cipher suites that share a weakness, probed together over TLS 1.2
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SuiteGroup {
+    pub name: &'static str,
+    pub suites: &'static [u16],
+    /// Broken outright, rather than merely lacking forward secrecy
+    pub insecure: bool,
+}
+
+/// Weak suite groups, most severe first
+pub const WEAK_SUITES: [SuiteGroup; 5] = [
+    SuiteGroup {
+        name: "NULL",
+        suites: &[0x0001, 0x0002, 0x003b, 0xc006, 0xc010],
+        insecure: true,
+    },
+    SuiteGroup {
+        name: "EXPORT",
+        suites: &[0x0003, 0x0006, 0x0008, 0x000e, 0x0014],
+        insecure: true,
+    },
+    SuiteGroup {
+        name: "RC4",
+        suites: &[0x0004, 0x0005, 0xc007, 0xc011],
+        insecure: true,
+    },
+    SuiteGroup {
+        name: "3DES",
+        suites: &[0x000a, 0x0016, 0xc008, 0xc012],
+        insecure: true,
+    },
+    SuiteGroup {
+        name: "static RSA",
+        suites: &[0x002f, 0x0035, 0x009c, 0x009d],
+        insecure: false,
+    },
+];
+
+/// Server's answer to a probe
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HelloOutcome {
+    /// ServerHello with the offered version
+    Accepted { cipher_suite: u16 },
+    /// Alert, a lower version, or the connection closed
+    Rejected { reason: String },
+}
+
+impl HelloOutcome {
+    pub fn is_accepted(&self) -> bool {
+        matches!(self, HelloOutcome::Accepted { .. })
+    }
+}
+
+/// ClientHello probe against one endpoint
+#[derive(Clone, Debug)]
+pub struct HelloProbe {
+    server_name: String,
+    timeout: Duration,
+}
+
+impl HelloProbe {
+    /// Probe sending `server_name` as SNI
+    pub fn new(server_name: impl Into<String>) -> Self {
+        Self {
+            server_name: server_name.into(),
+            timeout: Duration::from_secs(10),
+        }
+    }
+
+    pub fn with_timeout(mut self, secs: u64) -> Self {
+        self.timeout = Duration::from_secs(secs);
+        self
+    }
+
+    /// Offer `version` with `suites` and report whether the server accepts
+    pub async fn offer(
+        &self,
+        ip: IpAddr,
+        port: u16,
+        version: TlsVersion,
+        suites: &[u16],
+    ) -> Result<HelloOutcome> {
+        let address = SocketAddr::new(ip, port);
+        let hello = client_hello(&self.server_name, version, suites);
+        let exchange = async {
+            let mut stream = TcpStream::connect(address)
+                .await
+                .with_context(|| format!("Failed to connect to {address}"))?;
+            stream
+                .write_all(&hello)
+                .await
+                .with_context(|| format!("Failed to send ClientHello to {address}"))?;
+            read_answer(&mut stream, version).await
+        };
+        tokio::time::timeout(self.timeout, exchange)
+            .await
+            .with_context(|| {
+                format!(
+                    "No answer to ClientHello from {address} within {}s",
+                    self.timeout.as_secs()
+                )
+            })?
+    }
+}
+
+/// ClientHello record offering only `version`
+fn client_hello(server_name: &str, version: TlsVersion, suites: &[u16]) -> Vec<u8> {
+    let tls13 = version == TlsVersion::Tls13;
+    let mut body = Vec::new();
+    put_u16(&mut body, version.wire().min(0x0303));
+    body.extend(rand::random::<[u8; 32]>());
+    if tls13 {
+        // Middlebox compatibility mode
+        body.push(32);
+        body.extend(rand::random::<[u8; 32]>());
+    } else {
+        body.push(0);
+    }
+    put_u16(&mut body, (suites.len() * 2) as u16);
+    for suite in suites {
+        put_u16(&mut body, *suite);
+    }
+    body.extend([1, 0]);
+
+    let mut extensions = Vec::new();
+    if server_name.parse::<IpAddr>().is_err() {
+        let mut names = vec![0];
+        put_u16(&mut names, server_name.len() as u16);
+        names.extend(server_name.as_bytes());
+        let mut list = Vec::new();
+        put_u16(&mut list, names.len() as u16);
+        list.extend(names);
+        put_extension(&mut extensions, 0x0000, &list);
+    }
+    // x25519, secp256r1, secp384r1
+    put_extension(&mut extensions, 0x000a, &[0, 6, 0, 0x1d, 0, 0x17, 0, 0x18]);
+    put_extension(&mut extensions, 0x000b, &[1, 0]);
+    if version >= TlsVersion::Tls12 {
+        let algorithms: [u16; 10] = [
+            0x0403, 0x0804, 0x0401, 0x0503, 0x0805, 0x0501, 0x0806, 0x0601, 0x0201, 0x0203,
+        ];
+        let mut list = Vec::new();
+        put_u16(&mut list, (algorithms.len() * 2) as u16);
+        for algorithm in algorithms {
+            put_u16(&mut list, algorithm);
+        }
+        put_extension(&mut extensions, 0x000d, &list);
+    }
+    if tls13 {
+        put_extension(&mut extensions, SUPPORTED_VERSIONS, &[2, 0x03, 0x04]);
+        // Any 32 bytes are a valid x25519 public key
+        let mut share = vec![0, 0x1d, 0, 32];
+        share.extend(rand::random::<[u8; 32]>());
+        let mut shares = Vec::new();
+        put_u16(&mut shares, share.len() as u16);
+        shares.extend(share);
+        put_extension(&mut extensions, 0x0033, &shares);
+    } else {
+        // Secure renegotiation, which older servers insist on
+        put_extension(&mut extensions, 0xff01, &[0]);
+    }
+    put_u16(&mut body, extensions.len() as u16);
+    body.extend(extensions);
+
+    let mut handshake = vec![CLIENT_HELLO];
+    handshake.extend(&(body.len() as u32).to_be_bytes()[1..]);
+    handshake.extend(body);
+
+    let mut record = vec![HANDSHAKE];
+    put_u16(&mut record, 0x0301);
+    put_u16(&mut record, handshake.len() as u16);
+    record.extend(handshake);
+    record
+}
+
+/// Read records until the ServerHello or an alert
+async fn read_answer(stream: &mut TcpStream, version: TlsVersion) -> Result<HelloOutcome> {
+    let mut handshake = Vec::new();
+    loop {
+        let mut header = [0u8; 5];
+        if stream.read_exact(&mut header).await.is_err() {
+            return Ok(rejected("connection closed"));
+        }
+        let length = u16::from_be_bytes([header[3], header[4]]) as usize;
+        let mut fragment = vec![0u8; length];
+        if stream.read_exact(&mut fragment).await.is_err() {
+            return Ok(rejected("connection closed"));
+        }
+        match header[0] {
+            ALERT => {
+                let description = fragment.get(1).copied().unwrap_or_default();
+                return Ok(rejected(&format!("alert {}", alert_name(description))));
+            }
+            HANDSHAKE => {
+                handshake.extend(fragment);
+                if let Some(outcome) = parse_server_hello(&handshake, version)? {
+                    return Ok(outcome);
+                }
+            }
+            other => anyhow::bail!("Unexpected TLS record type {other}"),
+        }
+    }
+}
+
+/// Outcome from the handshake bytes read so far (None: incomplete)
+fn parse_server_hello(handshake: &[u8], offered: TlsVersion) -> Result<Option<HelloOutcome>> {
+    if handshake.len() < 4 {
+        return Ok(None);
+    }
+    if handshake[0] != SERVER_HELLO {
+        anyhow::bail!(
+            "Expected ServerHello, got handshake message {}",
+            handshake[0]
+        );
+    }
+    let length = u32::from_be_bytes([0, handshake[1], handshake[2], handshake[3]]) as usize;
+    let Some(body) = handshake.get(4..4 + length) else {
+        return Ok(None);
+    };
+
+    let mut reader = Reader(body);
+    let legacy_version = reader.u16()?;
+    reader.skip(32)?;
+    let session_id = reader.u8()? as usize;
+    reader.skip(session_id)?;
+    let cipher_suite = reader.u16()?;
+    reader.skip(1)?;
+
+    let mut selected = legacy_version;
+    if let Ok(extensions_length) = reader.u16() {
+        let mut extensions = Reader(reader.take(extensions_length as usize)?);
+        while let Ok(kind) = extensions.u16() {
+            let length = extensions.u16()? as usize;
+            let data = extensions.take(length)?;
+            if kind == SUPPORTED_VERSIONS && data.len() == 2 {
+                selected = u16::from_be_bytes([data[0], data[1]]);
+            }
+        }
+    }
+
+    Ok(Some(if selected == offered.wire() {
+        HelloOutcome::Accepted { cipher_suite }
+    } else {
+        rejected(&format!("server chose version 0x{selected:04x}"))
+    }))
+}
+
+fn rejected(reason: &str) -> HelloOutcome {
+    HelloOutcome::Rejected {
+        reason: reason.to_string(),
+    }
+}
+
+fn alert_name(description: u8) -> String {
+    match description {
+        40 => "handshake_failure".to_string(),
+        47 => "illegal_parameter".to_string(),
+        70 => "protocol_version".to_string(),
+        71 => "insufficient_security".to_string(),
+        80 => "internal_error".to_string(),
+        112 => "unrecognized_name".to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn put_u16(buf: &mut Vec<u8>, value: u16) {
+    buf.extend(value.to_be_bytes());
+}
+
+fn put_extension(buf: &mut Vec<u8>, kind: u16, data: &[u8]) {
+    put_u16(buf, kind);
+    put_u16(buf, data.len() as u16);
+    buf.extend(data);
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8]> {
+        if self.0.len() < n {
+            anyhow::bail!("Truncated ServerHello");
+        }
+        let (head, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Ok(head)
+    }
+
+    fn skip(&mut self, n: usize) -> Result<()> {
+        self.take(n).map(|_| ())
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::tls::tests::tls_server;
+
+    #[test]
+    fn test_client_hello_framing() {
+        let hello = client_hello("gateway.test", TlsVersion::Tls12, &[0xc02f]);
+        assert_eq!(hello[0], HANDSHAKE);
+        assert_eq!(
+            u16::from_be_bytes([hello[3], hello[4]]) as usize,
+            hello.len() - 5
+        );
+        assert_eq!(hello[5], CLIENT_HELLO);
+        assert_eq!(&hello[9..11], &[0x03, 0x03]);
+        assert!(hello.windows(12).any(|w| w == b"gateway.test"));
+
+        // TLS 1.3 goes in supported_versions, the legacy field stays 1.2
+        let hello = client_hello("10.0.0.1", TlsVersion::Tls13, &[0x1301]);
+        assert_eq!(&hello[9..11], &[0x03, 0x03]);
+        assert!(hello.windows(5).any(|w| w == [0x00, 0x2b, 0, 3, 2]));
+        assert!(!hello.windows(8).any(|w| w == b"10.0.0.1"));
+    }
+
+    #[test]
+    fn test_parse_server_hello() {
+        let server_hello = |version: u16, extensions: &[u8]| {
+            let mut body = version.to_be_bytes().to_vec();
+            body.extend([0u8; 32]);
+            body.push(0);
+            body.extend([0x13, 0x01, 0]);
+            body.extend(extensions);
+            let mut message = vec![SERVER_HELLO, 0, 0, body.len() as u8];
+            message.extend(body);
+            message
+        };
+
+        let tls12 = server_hello(0x0303, &[]);
+        assert_eq!(
+            parse_server_hello(&tls12, TlsVersion::Tls12).unwrap(),
+            Some(HelloOutcome::Accepted {
+                cipher_suite: 0x1301
+            })
+        );
+        assert!(!parse_server_hello(&tls12, TlsVersion::Tls13)
+            .unwrap()
+            .unwrap()
+            .is_accepted());
+        assert_eq!(
+            parse_server_hello(&tls12[..20], TlsVersion::Tls12).unwrap(),
+            None
+        );
+
+        let tls13 = server_hello(0x0303, &[0, 6, 0x00, 0x2b, 0, 2, 0x03, 0x04]);
+        assert!(parse_server_hello(&tls13, TlsVersion::Tls13)
+            .unwrap()
+            .unwrap()
+            .is_accepted());
+    }
+
+    #[tokio::test]
+    async fn test_offer_against_rustls() {
+        // rustls serves TLS 1.2 and 1.3 with AEAD suites only
+        let address = tls_server("gateway.test", &[]).await;
+        let probe = HelloProbe::new("gateway.test");
+        let offer = |version: TlsVersion, suites: &'static [u16]| {
+            let probe = probe.clone();
+            async move {
+                probe
+                    .offer(address.ip(), address.port(), version, suites)
+                    .await
+                    .unwrap()
+            }
+        };
+
+        for version in TlsVersion::all() {
+            let outcome = offer(version, version.default_suites()).await;
+            assert_eq!(
+                outcome.is_accepted(),
+                !version.is_deprecated(),
+                "{version}: {outcome:?}"
+            );
+        }
+        for group in WEAK_SUITES {
+            let outcome = offer(TlsVersion::Tls12, group.suites).await;
+            assert!(!outcome.is_accepted(), "{}: {outcome:?}", group.name);
+        }
+    }
+}
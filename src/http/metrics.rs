@@ -0,0 +1,177 @@
+//! Shared HTTP metrics registry
+//!
+//! Functional tests share the runner's client; when a registry is attached
+//! every request it sends is counted, so a round summary can report the
+//! traffic of the whole suite. Counters are atomics, safe to update from
+//! concurrent tests.
+
+#![allow(dead_code)]
+
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use super::client::{HttpError, HttpResponse};
+use crate::models::{HttpErrorClass, HttpTraffic};
+
+#[derive(Debug, Default)]
+struct Counters {
+    requests: AtomicU64,
+    bytes_sent: AtomicU64,
+    bytes_received: AtomicU64,
+    /// Indexed like `HttpErrorClass::ALL`
+    errors: [AtomicU64; HttpErrorClass::ALL.len()],
+    ipv4: AtomicU64,
+    ipv6: AtomicU64,
+}
+
+/// Run-wide HTTP request counters
+#[derive(Clone, Debug, Default)]
+pub struct HttpMetrics {
+    counters: Arc<Counters>,
+}
+
+impl HttpMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count one request and its outcome
+    pub(crate) fn record(&self, bytes_sent: usize, result: &Result<HttpResponse>) {
+        let counters = &self.counters;
+        counters.requests.fetch_add(1, Ordering::Relaxed);
+        counters
+            .bytes_sent
+            .fetch_add(bytes_sent as u64, Ordering::Relaxed);
+
+        let class = match result {
+            Ok(response) => {
+                counters
+                    .bytes_received
+                    .fetch_add(response.body.len() as u64, Ordering::Relaxed);
+                match response.remote_addr {
+                    Some(addr) if addr.is_ipv6() => counters.ipv6.fetch_add(1, Ordering::Relaxed),
+                    Some(_) => counters.ipv4.fetch_add(1, Ordering::Relaxed),
+                    None => 0,
+                };
+                HttpErrorClass::from_status(response.status_code)
+            }
+            Err(e) => Some(match e.downcast_ref::<HttpError>() {
+                Some(HttpError::Timeout(_)) => HttpErrorClass::Timeout,
+                Some(HttpError::ConnectionRefused(_)) => HttpErrorClass::Connect,
+                _ => HttpErrorClass::Request,
+            }),
+        };
+        if let Some(class) = class {
+            let index = HttpErrorClass::ALL
+                .iter()
+                .position(|c| *c == class)
+                .unwrap_or_default();
+            counters.errors[index].fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Totals so far
+    pub fn snapshot(&self) -> HttpTraffic {
+        let counters = &self.counters;
+        let errors: BTreeMap<HttpErrorClass, u64> = HttpErrorClass::ALL
+            .iter()
+            .zip(&counters.errors)
+            .map(|(class, count)| (*class, count.load(Ordering::Relaxed)))
+            .filter(|(_, count)| *count > 0)
+            .collect();
+        HttpTraffic {
+            requests: counters.requests.load(Ordering::Relaxed),
+            bytes_sent: counters.bytes_sent.load(Ordering::Relaxed),
+            bytes_received: counters.bytes_received.load(Ordering::Relaxed),
+            errors,
+            ipv4: counters.ipv4.load(Ordering::Relaxed),
+            ipv6: counters.ipv6.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Registry totals at the start of a test round
+pub struct TrafficWindow {
+    start: Option<(HttpMetrics, HttpTraffic)>,
+}
+
+impl TrafficWindow {
+    /// Start a window (a no-op without a registry)
+    pub fn open(metrics: Option<&HttpMetrics>) -> Self {
+        Self {
+            start: metrics.map(|m| (m.clone(), m.snapshot())),
+        }
+    }
+
+    /// Traffic since the window opened
+    pub fn finish(self) -> Option<HttpTraffic> {
+        self.start
+            .map(|(metrics, start)| metrics.snapshot().since(&start))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn response(status_code: u16, body: &str) -> Result<HttpResponse> {
+        Ok(HttpResponse {
+            status_code,
+            headers: HashMap::new(),
+            body: body.to_string(),
+            duration_ms: 1,
+            latency_ms: 1.0,
+            remote_addr: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_recording() {
+        let metrics = HttpMetrics::new();
+        metrics.record(0, &response(200, "warmup"));
+        let window = TrafficWindow::open(Some(&metrics));
+
+        let tasks: Vec<_> = (0..8)
+            .map(|_| {
+                let metrics = metrics.clone();
+                tokio::spawn(async move {
+                    for _ in 0..100 {
+                        metrics.record(10, &response(200, "ok"));
+                    }
+                    metrics.record(0, &response(503, ""));
+                    metrics.record(0, &Err(anyhow::anyhow!(HttpError::Timeout(5))));
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        let traffic = window.finish().unwrap();
+        assert_eq!(traffic.requests, 816);
+        assert_eq!(traffic.bytes_sent, 8000);
+        assert_eq!(traffic.bytes_received, 1600);
+        assert_eq!(traffic.errors[&HttpErrorClass::ServerError], 8);
+        assert_eq!(traffic.errors[&HttpErrorClass::Timeout], 8);
+        assert_eq!(metrics.snapshot().requests, 817);
+        assert!(TrafficWindow::open(None).finish().is_none());
+    }
+
+    #[test]
+    fn test_address_family() {
+        let metrics = HttpMetrics::new();
+        for addr in ["[fd00::5]:80", "[fd00::6]:80", "10.0.0.5:80"] {
+            let mut ok = response(200, "").unwrap();
+            ok.remote_addr = Some(addr.parse().unwrap());
+            metrics.record(0, &Ok(ok));
+        }
+        metrics.record(0, &response(200, ""));
+
+        let traffic = metrics.snapshot();
+        assert_eq!((traffic.ipv4, traffic.ipv6), (1, 2));
+        assert!(traffic.to_string().ends_with(", IPv4 1 / IPv6 2"));
+    }
+}
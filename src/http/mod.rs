@@ -2,6 +2,33 @@
 //!
 //! Provides HTTP client functionality for testing Gateway API implementations.
 
+mod auth;
+mod backends;
 mod client;
+mod dns;
+mod echo;
+mod hello;
+mod metrics;
+pub(crate) mod multiplex;
+mod raw;
+mod resolver;
+pub(crate) mod tls;
+mod wait;
 
-pub use client::HttpClient;
+pub use crate::models::{ConnectionSettings, DnsOverrides, IpFamily};
+pub use auth::{
+    AuthHeaders, AuthProvider, OIDC_CLIENT_ID_ENV, OIDC_CLIENT_SECRET_ENV, OIDC_SCOPE_ENV,
+};
+pub use backends::BackendTracker;
+pub use client::{
+    authority, HttpClient, HttpClientConfig, HttpRequest, HttpResponse, StreamedResponse,
+};
+pub use dns::resolve_all;
+pub use echo::EchoResponse;
+pub use hello::{HelloOutcome, HelloProbe, SuiteGroup, TlsVersion, WEAK_SUITES};
+pub use metrics::{HttpMetrics, TrafficWindow};
+pub use multiplex::TlsConnection;
+pub use raw::RawResponse;
+pub use resolver::parse_nameserver;
+pub use tls::{TlsInfo, TlsProbe};
+pub use wait::{poll_until, EndpointWait};
@@ -0,0 +1,275 @@
+//! HTTP over one TLS connection
+//!
+//! The shared client pools connections and does not say which protocol a
+//! request went over. The HTTP/2 test needs both under its control: it
+//! negotiates ALPN itself, then sends requests as concurrent streams on a
+//! single HTTP/2 connection, or over HTTP/1.1 when that is what was agreed.
+
+use anyhow::{Context, Result};
+use bytes::Bytes;
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::time::Instant;
+use tokio::sync::Mutex;
+use tracing::debug;
+
+use super::client::HttpResponse;
+use super::tls::{TlsInfo, TlsProbe};
+
+enum Sender {
+    Http2(h2::client::SendRequest<Bytes>),
+    Http1(Mutex<hyper::client::conn::SendRequest<hyper::Body>>),
+}
+
+/// TLS connection speaking the protocol ALPN selected
+pub struct TlsConnection {
+    tls: TlsInfo,
+    address: SocketAddr,
+    sender: Sender,
+}
+
+impl TlsConnection {
+    /// Handshake with `probe` (its ALPN offer decides the protocol) and
+    /// start HTTP/2 if the server selected `h2`, HTTP/1.1 otherwise
+    pub async fn open(probe: &TlsProbe, ip: IpAddr, port: u16) -> Result<Self> {
+        let address = SocketAddr::new(ip, port);
+        let (stream, tls) = probe.handshake(ip, port).await?;
+        let sender = if tls.alpn.as_deref() == Some("h2") {
+            let (sender, connection) = h2::client::handshake(stream)
+                .await
+                .with_context(|| format!("HTTP/2 handshake with {address} failed"))?;
+            tokio::spawn(async move {
+                if let Err(e) = connection.await {
+                    debug!("HTTP/2 connection to {} ended: {}", address, e);
+                }
+            });
+            Sender::Http2(sender)
+        } else {
+            let (sender, connection) = hyper::client::conn::handshake(stream)
+                .await
+                .with_context(|| format!("HTTP/1.1 handshake with {address} failed"))?;
+            tokio::spawn(async move {
+                if let Err(e) = connection.await {
+                    debug!("HTTP/1.1 connection to {} ended: {}", address, e);
+                }
+            });
+            Sender::Http1(Mutex::new(sender))
+        };
+        Ok(Self {
+            tls,
+            address,
+            sender,
+        })
+    }
+
+    pub fn tls(&self) -> &TlsInfo {
+        &self.tls
+    }
+
+    pub fn is_http2(&self) -> bool {
+        matches!(self.sender, Sender::Http2(_))
+    }
+
+    /// GET `path` from `host`; on HTTP/2 every call is a new stream, so
+    /// concurrent calls are multiplexed, on HTTP/1.1 they queue
+    pub async fn get(
+        &self,
+        host: &str,
+        path: &str,
+        headers: &[(&str, &str)],
+    ) -> Result<HttpResponse> {
+        let start = Instant::now();
+        let (status, response_headers, body) = match &self.sender {
+            Sender::Http2(sender) => {
+                let mut request = http::Request::get(format!("https://{host}{path}"));
+                for (name, value) in headers {
+                    request = request.header(*name, *value);
+                }
+                let request = request.body(()).context("Invalid request")?;
+                let mut sender = sender
+                    .clone()
+                    .ready()
+                    .await
+                    .with_context(|| format!("HTTP/2 connection to {} closed", self.address))?;
+                let (response, _) = sender
+                    .send_request(request, true)
+                    .context("Failed to open HTTP/2 stream")?;
+                let response = response.await.context("HTTP/2 stream failed")?;
+                let (parts, mut body) = response.into_parts();
+                let mut bytes = Vec::new();
+                while let Some(chunk) = body.data().await {
+                    let chunk = chunk.context("HTTP/2 stream failed")?;
+                    let _ = body.flow_control().release_capacity(chunk.len());
+                    bytes.extend_from_slice(&chunk);
+                }
+                (parts.status, parts.headers, bytes)
+            }
+            Sender::Http1(sender) => {
+                let mut request = http::Request::get(path).header(http::header::HOST, host);
+                for (name, value) in headers {
+                    request = request.header(*name, *value);
+                }
+                let request = request
+                    .body(hyper::Body::empty())
+                    .context("Invalid request")?;
+                let mut sender = sender.lock().await;
+                futures::future::poll_fn(|cx| sender.poll_ready(cx))
+                    .await
+                    .with_context(|| format!("HTTP/1.1 connection to {} closed", self.address))?;
+                let response = sender
+                    .send_request(request)
+                    .await
+                    .context("HTTP/1.1 request failed")?;
+                let (parts, body) = response.into_parts();
+                let bytes = hyper::body::to_bytes(body)
+                    .await
+                    .context("HTTP/1.1 request failed")?;
+                (parts.status, parts.headers, bytes.to_vec())
+            }
+        };
+
+        let elapsed = start.elapsed();
+        Ok(HttpResponse {
+            status_code: status.as_u16(),
+            headers: response_headers
+                .iter()
+                .filter_map(|(name, value)| {
+                    Some((name.to_string(), value.to_str().ok()?.to_string()))
+                })
+                .collect::<HashMap<_, _>>(),
+            body: String::from_utf8_lossy(&body).to_string(),
+            duration_ms: elapsed.as_millis() as u64,
+            latency_ms: elapsed.as_secs_f64() * 1000.0,
+            remote_addr: Some(self.address),
+        })
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::http::tls::tests::server_config;
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use tokio_rustls::TlsAcceptor;
+
+    /// Body echoing the request's `x-stream-id` header, like the echo backends
+    fn echo_body(stream_id: Option<&str>) -> String {
+        serde_json::json!({ "headers": { "x-stream-id": stream_id.unwrap_or_default() } })
+            .to_string()
+    }
+
+    /// HTTPS echo server for `name` offering the given ALPN protocols
+    pub(crate) async fn https_echo_server(name: &str, alpn: &[&str]) -> SocketAddr {
+        let acceptor = TlsAcceptor::from(Arc::new(server_config(name, alpn)));
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((tcp, _)) = listener.accept().await {
+                let acceptor = acceptor.clone();
+                tokio::spawn(async move {
+                    let Ok(tls) = acceptor.accept(tcp).await else {
+                        return;
+                    };
+                    if tls.get_ref().1.alpn_protocol() == Some(b"h2") {
+                        serve_h2(tls).await;
+                    } else {
+                        serve_http1(tls).await;
+                    }
+                });
+            }
+        });
+        address
+    }
+
+    async fn serve_h2(tls: tokio_rustls::server::TlsStream<tokio::net::TcpStream>) {
+        let Ok(mut connection) = h2::server::handshake(tls).await else {
+            return;
+        };
+        while let Some(Ok((request, mut respond))) = connection.accept().await {
+            tokio::spawn(async move {
+                let stream_id = request
+                    .headers()
+                    .get("x-stream-id")
+                    .and_then(|v| v.to_str().ok());
+                let body = echo_body(stream_id);
+                // Answer out of order, so mixed-up streams would show
+                let delay = stream_id.and_then(|id| id.parse::<u64>().ok()).unwrap_or(0);
+                tokio::time::sleep(std::time::Duration::from_millis(20 - delay % 20)).await;
+                let response = http::Response::builder().status(200).body(()).unwrap();
+                if let Ok(mut send) = respond.send_response(response, false) {
+                    let _ = send.send_data(Bytes::from(body), true);
+                }
+            });
+        }
+    }
+
+    async fn serve_http1(mut tls: tokio_rustls::server::TlsStream<tokio::net::TcpStream>) {
+        let mut request = Vec::new();
+        let mut buf = [0u8; 1024];
+        while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+            match tls.read(&mut buf).await {
+                Ok(0) | Err(_) => return,
+                Ok(n) => request.extend_from_slice(&buf[..n]),
+            }
+        }
+        let request = String::from_utf8_lossy(&request);
+        let stream_id = request
+            .lines()
+            .find_map(|line| line.strip_prefix("x-stream-id: "));
+        let body = echo_body(stream_id);
+        let response = format!(
+            "HTTP/1.1 200 OK\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        let _ = tls.write_all(response.as_bytes()).await;
+        let _ = tls.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn test_http2_streams() {
+        let address = https_echo_server("gateway.test", &["h2", "http/1.1"]).await;
+        let probe = TlsProbe::new("gateway.test").with_accept_invalid_certs(true);
+        let connection = TlsConnection::open(&probe, address.ip(), address.port())
+            .await
+            .unwrap();
+        assert!(connection.is_http2());
+
+        let ids: Vec<String> = (0..10).map(|i| i.to_string()).collect();
+        let responses = futures::future::join_all(ids.iter().map(|id| async {
+            connection
+                .get("gateway.test", "/", &[("x-stream-id", id.as_str())])
+                .await
+        }))
+        .await;
+        for (id, response) in ids.iter().zip(responses) {
+            let response = response.unwrap();
+            assert_eq!(response.status_code, 200);
+            assert!(response
+                .echoed_headers()
+                .contains(&("x-stream-id".to_string(), id.clone())));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_http1_fallback() {
+        let address = https_echo_server("gateway.test", &["h2", "http/1.1"]).await;
+        let probe = TlsProbe::new("gateway.test")
+            .with_accept_invalid_certs(true)
+            .with_alpn(vec!["http/1.1".to_string()]);
+        let connection = TlsConnection::open(&probe, address.ip(), address.port())
+            .await
+            .unwrap();
+        assert!(!connection.is_http2());
+        assert_eq!(connection.tls().alpn.as_deref(), Some("http/1.1"));
+
+        let response = connection
+            .get("gateway.test", "/", &[("x-stream-id", "7")])
+            .await
+            .unwrap();
+        assert_eq!(response.status_code, 200);
+        assert!(response.body.contains(r#""x-stream-id":"7""#));
+    }
+}
@@ -0,0 +1,174 @@
+//! Raw HTTP/1.x requests
+//!
+//! Some tests need requests an HTTP client will not send: no Host header,
+//! duplicated or mixed-case header names, hop-by-hop headers. They are
+//! written to the socket exactly as given, and the response is parsed just
+//! far enough for the checks.
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+use super::client::{HttpClient, HttpResponse};
+use super::dns::resolve_all;
+
+/// Response to a raw request
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RawResponse {
+    pub status: u16,
+    /// Header fields in order, names as sent by the server
+    pub headers: Vec<(String, String)>,
+    /// Body as received (dechunked, but not decoded)
+    pub body: Vec<u8>,
+    /// From sending the request until the server closed
+    pub duration_ms: u64,
+}
+
+impl RawResponse {
+    /// Parse a complete HTTP/1.x response (None = no status line)
+    pub fn parse(bytes: &[u8]) -> Option<Self> {
+        let head_end = bytes
+            .windows(4)
+            .position(|w| w == b"\r\n\r\n")
+            .unwrap_or(bytes.len());
+        let head = String::from_utf8_lossy(&bytes[..head_end]);
+        let mut lines = head.split("\r\n");
+
+        let mut status_line = lines.next()?.split_whitespace();
+        status_line
+            .next()
+            .filter(|version| version.starts_with("HTTP/"))?;
+        let status = status_line.next()?.parse().ok()?;
+
+        let headers: Vec<(String, String)> = lines
+            .filter_map(|line| {
+                let (name, value) = line.split_once(':')?;
+                Some((name.trim().to_string(), value.trim().to_string()))
+            })
+            .collect();
+        let raw_body = bytes.get(head_end + 4..).unwrap_or_default();
+        let chunked = headers.iter().any(|(name, value)| {
+            name.eq_ignore_ascii_case("transfer-encoding")
+                && value.to_ascii_lowercase().contains("chunked")
+        });
+        let body = if chunked {
+            dechunk(raw_body)
+        } else {
+            raw_body.to_vec()
+        };
+
+        Some(Self {
+            status,
+            headers,
+            body,
+            duration_ms: 0,
+        })
+    }
+
+    /// First value of a header (case-insensitive)
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// As an `HttpResponse`, to use its echo parsing
+    pub fn into_response(self) -> HttpResponse {
+        let mut headers = HashMap::new();
+        for (name, value) in self.headers {
+            headers.entry(name.to_ascii_lowercase()).or_insert(value);
+        }
+        HttpResponse {
+            status_code: self.status,
+            headers,
+            body: String::from_utf8_lossy(&self.body).into_owned(),
+            duration_ms: self.duration_ms,
+            latency_ms: self.duration_ms as f64,
+            remote_addr: None,
+        }
+    }
+}
+
+/// Body of a chunked response (stops at the last chunk or malformed input)
+fn dechunk(mut bytes: &[u8]) -> Vec<u8> {
+    let mut body = Vec::new();
+    while let Some(line_end) = bytes.windows(2).position(|w| w == b"\r\n") {
+        let size_field = String::from_utf8_lossy(&bytes[..line_end]);
+        let size_hex = size_field.split(';').next().unwrap_or_default().trim();
+        let Ok(size) = usize::from_str_radix(size_hex, 16) else {
+            break;
+        };
+        let start = line_end + 2;
+        if size == 0 || bytes.len() < start + size {
+            break;
+        }
+        body.extend_from_slice(&bytes[start..start + size]);
+        bytes = bytes.get(start + size + 2..).unwrap_or_default();
+    }
+    body
+}
+
+impl HttpClient {
+    /// Write `request` to `host:port` as given and read until the server
+    /// closes (None = closed without a response)
+    ///
+    /// `host` is resolved through the client's DNS overrides; the request
+    /// should ask for `Connection: close`.
+    pub async fn send_raw(
+        &self,
+        host: &str,
+        port: u16,
+        request: &str,
+    ) -> Result<Option<RawResponse>> {
+        let addrs = resolve_all(&self.config().dns_overrides, host)
+            .await
+            .with_context(|| format!("Could not resolve {host}"))?;
+        let addr = *addrs
+            .first()
+            .with_context(|| format!("No address for {host}"))?;
+        let timeout = Duration::from_secs(self.config().timeout_secs);
+
+        let start = Instant::now();
+        tokio::time::timeout(timeout, async {
+            let mut stream = TcpStream::connect((addr, port))
+                .await
+                .with_context(|| format!("Failed to connect to {addr}:{port}"))?;
+            stream.write_all(request.as_bytes()).await?;
+            let mut response = Vec::new();
+            // A reset after an error response still leaves the status line
+            let _ = stream.read_to_end(&mut response).await;
+            let duration_ms = start.elapsed().as_millis() as u64;
+            Ok(RawResponse::parse(&response).map(|r| RawResponse { duration_ms, ..r }))
+        })
+        .await
+        .context("Timed out waiting for a response")?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_raw_response() {
+        let response = RawResponse::parse(
+            b"HTTP/1.1 200 OK\r\nX-Mixed-Case: a\r\nTransfer-Encoding: chunked\r\n\r\n\
+              5\r\nhello\r\n6\r\n world\r\n0\r\n\r\n",
+        )
+        .unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(
+            response.headers[0],
+            ("X-Mixed-Case".to_string(), "a".to_string())
+        );
+        assert_eq!(response.body, b"hello world");
+        assert_eq!(response.header("x-mixed-case"), Some("a"));
+
+        let bad_request = RawResponse::parse(b"HTTP/1.1 400 Bad Request\r\n").unwrap();
+        assert_eq!(bad_request.status, 400);
+        assert_eq!(RawResponse::parse(b""), None);
+    }
+}
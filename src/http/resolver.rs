@@ -0,0 +1,291 @@
+//! Async DNS client for configured nameservers
+//!
+//! Sends A/AAAA queries over UDP straight to the given servers instead of
+//! going through the system resolver (getaddrinfo on a blocking thread), so
+//! tests can resolve gateway names against cluster or split-horizon DNS.
+
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
+use tokio::net::UdpSocket;
+use tokio::time::timeout;
+use tracing::debug;
+
+use crate::models::IpFamily;
+
+/// Standard DNS port, used when a server is given without one
+const DNS_PORT: u16 = 53;
+
+const TYPE_A: u16 = 1;
+const TYPE_AAAA: u16 = 28;
+const CLASS_IN: u16 = 1;
+
+/// DNS client querying a fixed list of nameservers in order
+#[derive(Clone, Debug)]
+pub struct DnsClient {
+    servers: Vec<SocketAddr>,
+    timeout: Duration,
+}
+
+impl DnsClient {
+    /// Query `servers` in order, 2s per attempt
+    pub fn new(servers: Vec<SocketAddr>) -> Self {
+        Self {
+            servers,
+            timeout: Duration::from_secs(2),
+        }
+    }
+
+    /// Addresses of `host` in the requested family; A and AAAA are queried
+    /// concurrently for [`IpFamily::Auto`] and [`IpFamily::Dual`]
+    pub async fn lookup(&self, host: &str, family: IpFamily) -> io::Result<Vec<IpAddr>> {
+        let (v6, v4) = match family {
+            IpFamily::Auto | IpFamily::Dual => {
+                let (v6, v4) = tokio::join!(self.query(host, TYPE_AAAA), self.query(host, TYPE_A));
+                // One family failing is fine as long as the other answered
+                match (v6, v4) {
+                    (Err(e), Err(_)) => return Err(e),
+                    (v6, v4) => (v6.unwrap_or_default(), v4.unwrap_or_default()),
+                }
+            }
+            IpFamily::Ipv4 => (Vec::new(), self.query(host, TYPE_A).await?),
+            IpFamily::Ipv6 => (self.query(host, TYPE_AAAA).await?, Vec::new()),
+        };
+        Ok(v6.into_iter().chain(v4).collect())
+    }
+
+    /// Ask each server in turn until one answers
+    async fn query(&self, host: &str, qtype: u16) -> io::Result<Vec<IpAddr>> {
+        let mut last_error = io::Error::new(io::ErrorKind::InvalidInput, "No nameservers");
+        for server in &self.servers {
+            match self.query_server(*server, host, qtype).await {
+                Ok(addrs) => return Ok(addrs),
+                Err(e) => {
+                    debug!("Nameserver {} failed for {}: {}", server, host, e);
+                    last_error = e;
+                }
+            }
+        }
+        Err(last_error)
+    }
+
+    async fn query_server(
+        &self,
+        server: SocketAddr,
+        host: &str,
+        qtype: u16,
+    ) -> io::Result<Vec<IpAddr>> {
+        let id: u16 = rand::random();
+        let query = encode_query(id, host, qtype)?;
+
+        let bind: SocketAddr = match server {
+            SocketAddr::V4(_) => (Ipv4Addr::UNSPECIFIED, 0).into(),
+            SocketAddr::V6(_) => (Ipv6Addr::UNSPECIFIED, 0).into(),
+        };
+        let socket = UdpSocket::bind(bind).await?;
+        socket.connect(server).await?;
+        socket.send(&query).await?;
+
+        let mut buf = [0u8; 1232];
+        timeout(self.timeout, async {
+            loop {
+                let len = socket.recv(&mut buf).await?;
+                // Stray datagrams (late answers to an earlier query) are skipped
+                if let Some(addrs) = parse_response(id, &buf[..len])? {
+                    return Ok(addrs);
+                }
+            }
+        })
+        .await
+        .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, format!("{server} timed out")))?
+    }
+}
+
+/// Parse `IP` or `IP:PORT` (IPv6 with a port as `[IP]:PORT`)
+pub fn parse_nameserver(spec: &str) -> Result<SocketAddr, String> {
+    let spec = spec.trim();
+    spec.parse::<SocketAddr>()
+        .or_else(|_| {
+            spec.parse::<IpAddr>()
+                .map(|ip| SocketAddr::new(ip, DNS_PORT))
+        })
+        .map_err(|_| format!("Invalid nameserver '{spec}' (expected IP or IP:PORT)"))
+}
+
+/// Recursive query for one name and record type
+fn encode_query(id: u16, host: &str, qtype: u16) -> io::Result<Vec<u8>> {
+    let mut packet = Vec::with_capacity(host.len() + 18);
+    packet.extend_from_slice(&id.to_be_bytes());
+    // Standard query, recursion desired; one question
+    packet.extend_from_slice(&[0x01, 0x00, 0x00, 0x01, 0, 0, 0, 0, 0, 0]);
+    for label in host.trim_end_matches('.').split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Invalid hostname '{host}'"),
+            ));
+        }
+        packet.push(label.len() as u8);
+        packet.extend_from_slice(label.as_bytes());
+    }
+    packet.push(0);
+    packet.extend_from_slice(&qtype.to_be_bytes());
+    packet.extend_from_slice(&CLASS_IN.to_be_bytes());
+    Ok(packet)
+}
+
+/// Addresses in a response to query `id` (None if the packet answers another query)
+fn parse_response(id: u16, packet: &[u8]) -> io::Result<Option<Vec<IpAddr>>> {
+    let malformed = || io::Error::new(io::ErrorKind::InvalidData, "Malformed DNS response");
+    let read_u16 = |at: usize| -> io::Result<u16> {
+        packet
+            .get(at..at + 2)
+            .map(|b| u16::from_be_bytes([b[0], b[1]]))
+            .ok_or_else(malformed)
+    };
+
+    let flags = read_u16(2)?;
+    if read_u16(0)? != id || flags & 0x8000 == 0 {
+        return Ok(None);
+    }
+    match flags & 0x000f {
+        0 => {}
+        // NXDOMAIN: the name has no addresses
+        3 => return Ok(Some(Vec::new())),
+        rcode => {
+            return Err(io::Error::other(format!(
+                "Nameserver returned error code {rcode}"
+            )))
+        }
+    }
+    let questions = read_u16(4)?;
+    let answers = read_u16(6)?;
+
+    let mut at = 12;
+    for _ in 0..questions {
+        at = skip_name(packet, at).ok_or_else(malformed)? + 4;
+    }
+
+    let mut addrs = Vec::new();
+    for _ in 0..answers {
+        at = skip_name(packet, at).ok_or_else(malformed)?;
+        let rtype = read_u16(at)?;
+        let len = read_u16(at + 8)? as usize;
+        let data = packet.get(at + 10..at + 10 + len).ok_or_else(malformed)?;
+        match (rtype, len) {
+            (TYPE_A, 4) => addrs.push(IpAddr::from(<[u8; 4]>::try_from(data).unwrap())),
+            (TYPE_AAAA, 16) => addrs.push(IpAddr::from(<[u8; 16]>::try_from(data).unwrap())),
+            // CNAMEs are followed by the recursive server in the same answer
+            _ => {}
+        }
+        at += 10 + len;
+    }
+    Ok(Some(addrs))
+}
+
+/// Offset just past a (possibly compressed) name
+fn skip_name(packet: &[u8], mut at: usize) -> Option<usize> {
+    loop {
+        let len = *packet.get(at)?;
+        match len {
+            0 => return Some(at + 1),
+            // Compression pointer ends the name
+            l if l & 0xc0 == 0xc0 => return Some(at + 2),
+            l => at += 1 + l as usize,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Response to `query` with the given (type, rdata) answers
+    fn response(query: &[u8], answers: &[(u16, &[u8])]) -> Vec<u8> {
+        let mut packet = query.to_vec();
+        packet[2] |= 0x80;
+        packet[6..8].copy_from_slice(&(answers.len() as u16).to_be_bytes());
+        for (rtype, data) in answers {
+            // Pointer to the question name
+            packet.extend_from_slice(&[0xc0, 12]);
+            packet.extend_from_slice(&rtype.to_be_bytes());
+            packet.extend_from_slice(&CLASS_IN.to_be_bytes());
+            packet.extend_from_slice(&300u32.to_be_bytes());
+            packet.extend_from_slice(&(data.len() as u16).to_be_bytes());
+            packet.extend_from_slice(data);
+        }
+        packet
+    }
+
+    #[test]
+    fn test_encode_query() {
+        let query = encode_query(0x1234, "app.example.com.", TYPE_AAAA).unwrap();
+        assert_eq!(&query[..2], &[0x12, 0x34]);
+        assert_eq!(&query[12..17], b"\x03app\x07");
+        assert_eq!(&query[query.len() - 4..], &[0, 28, 0, 1]);
+        assert!(encode_query(1, "bad..name", TYPE_A).is_err());
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let query = encode_query(7, "gw.example.com", TYPE_A).unwrap();
+        let cname = b"\x02lb\xc0\x0c";
+        let packet = response(&query, &[(5, cname), (TYPE_A, &[10, 0, 0, 5])]);
+        assert_eq!(
+            parse_response(7, &packet).unwrap(),
+            Some(vec!["10.0.0.5".parse().unwrap()])
+        );
+        assert_eq!(parse_response(8, &packet).unwrap(), None);
+
+        let mut nxdomain = response(&query, &[]);
+        nxdomain[3] |= 3;
+        assert_eq!(parse_response(7, &nxdomain).unwrap(), Some(vec![]));
+        assert!(parse_response(7, &packet[..packet.len() - 2]).is_err());
+    }
+
+    #[test]
+    fn test_parse_nameserver() {
+        assert_eq!(
+            parse_nameserver("10.96.0.10").unwrap(),
+            "10.96.0.10:53".parse().unwrap()
+        );
+        assert_eq!(
+            parse_nameserver("[fd00::a]:5353").unwrap(),
+            "[fd00::a]:5353".parse().unwrap()
+        );
+        assert!(parse_nameserver("dns.local").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_lookup_against_local_server() {
+        let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let addr = server.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut buf = [0u8; 512];
+            for _ in 0..2 {
+                let (len, peer) = server.recv_from(&mut buf).await.unwrap();
+                let query = &buf[..len];
+                let answer: &[u8] = if query[len - 3] == TYPE_AAAA as u8 {
+                    &[0xfd, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 5]
+                } else {
+                    &[10, 0, 0, 5]
+                };
+                let qtype = if answer.len() == 4 { TYPE_A } else { TYPE_AAAA };
+                let reply = response(query, &[(qtype, answer)]);
+                server.send_to(&reply, peer).await.unwrap();
+            }
+        });
+
+        let addrs = DnsClient::new(vec![addr])
+            .lookup("gw.example.com", IpFamily::Auto)
+            .await
+            .unwrap();
+        assert_eq!(
+            addrs,
+            vec![
+                "fd00::5".parse::<IpAddr>().unwrap(),
+                "10.0.0.5".parse().unwrap()
+            ]
+        );
+    }
+}
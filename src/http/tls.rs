@@ -0,0 +1,385 @@
+//! TLS endpoint inspection
+//!
+//! Performs a TLS handshake with a gateway listener and reports what was
+//! negotiated and which certificate was served, without an HTTP request
+//! and without external tools. The connection goes to a given IP with the
+//! hostname as SNI, so the hostname need not resolve. Gateways under test
+//! mostly serve self-signed certificates; the probe can accept any
+//! certificate while still recording why it would not be trusted.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, TimeZone, Utc};
+use rustls::client::{ServerCertVerified, ServerCertVerifier, WebPkiVerifier};
+use rustls::{Certificate, ClientConfig, OwnedTrustAnchor, RootCertStore, ServerName};
+use serde::Serialize;
+use std::fmt;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use tokio::net::TcpStream;
+use tokio_rustls::client::TlsStream;
+use tokio_rustls::TlsConnector;
+use x509_parser::extensions::GeneralName;
+
+/// Certificate served by a TLS endpoint
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct CertificateInfo {
+    pub subject: String,
+    pub issuer: String,
+    /// DNS names and IP addresses of the subjectAltName extension
+    pub names: Vec<String>,
+    pub not_before: DateTime<Utc>,
+    pub not_after: DateTime<Utc>,
+}
+
+impl CertificateInfo {
+    /// Parse a DER-encoded X.509 certificate
+    pub fn parse(der: &[u8]) -> Result<Self> {
+        let (_, cert) = x509_parser::parse_x509_certificate(der)
+            .map_err(|e| anyhow::anyhow!("Invalid certificate: {e}"))?;
+        let timestamp = |t: i64| {
+            Utc.timestamp_opt(t, 0)
+                .single()
+                .context("Certificate validity out of range")
+        };
+
+        let mut names = Vec::new();
+        if let Ok(Some(san)) = cert.subject_alternative_name() {
+            for name in &san.value.general_names {
+                match name {
+                    GeneralName::DNSName(dns) => names.push(dns.to_string()),
+                    GeneralName::IPAddress(bytes) => {
+                        let ip = match bytes.len() {
+                            4 => <[u8; 4]>::try_from(*bytes).ok().map(IpAddr::from),
+                            16 => <[u8; 16]>::try_from(*bytes).ok().map(IpAddr::from),
+                            _ => None,
+                        };
+                        names.extend(ip.map(|ip| ip.to_string()));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(Self {
+            subject: cert.subject().to_string(),
+            issuer: cert.issuer().to_string(),
+            names,
+            not_before: timestamp(cert.validity().not_before.timestamp())?,
+            not_after: timestamp(cert.validity().not_after.timestamp())?,
+        })
+    }
+
+    /// Whether `hostname` is one of the names, `*.` wildcards matching a
+    /// single label
+    pub fn matches(&self, hostname: &str) -> bool {
+        let hostname = hostname.trim_end_matches('.').to_ascii_lowercase();
+        self.names.iter().any(|name| {
+            let name = name.to_ascii_lowercase();
+            match name.strip_prefix("*.") {
+                Some(suffix) => hostname
+                    .split_once('.')
+                    .is_some_and(|(label, rest)| !label.is_empty() && rest == suffix),
+                None => name == hostname,
+            }
+        })
+    }
+
+    /// Whether subject and issuer are the same
+    pub fn is_self_signed(&self) -> bool {
+        self.subject == self.issuer
+    }
+
+    /// Time left until the certificate expires (negative once expired)
+    pub fn expires_in(&self, now: DateTime<Utc>) -> chrono::Duration {
+        self.not_after - now
+    }
+}
+
+/// Outcome of a TLS handshake
+#[derive(Clone, Debug, Serialize)]
+pub struct TlsInfo {
+    /// Negotiated protocol version, e.g. `TLSv1.3`
+    pub protocol: String,
+    pub cipher_suite: String,
+    /// Negotiated ALPN protocol, e.g. `h2`
+    pub alpn: Option<String>,
+    /// Leaf certificate
+    pub certificate: Option<CertificateInfo>,
+    /// Certificates the server sent, leaf included
+    pub chain_length: usize,
+    /// Why the chain is not trusted by the public roots, if it is not
+    pub verification_error: Option<String>,
+}
+
+impl fmt::Display for TlsInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.protocol)?;
+        if let Some(alpn) = &self.alpn {
+            write!(f, ", {alpn}")?;
+        }
+        if let Some(cert) = &self.certificate {
+            write!(
+                f,
+                ", {} (expires {})",
+                cert.subject,
+                cert.not_after.format("%Y-%m-%d")
+            )?;
+        }
+        if self.verification_error.is_some() {
+            write!(f, ", untrusted")?;
+        }
+        Ok(())
+    }
+}
+
+/// TLS handshake against an endpoint
+#[derive(Clone, Debug)]
+pub struct TlsProbe {
+    server_name: String,
+    timeout: Duration,
+    alpn: Vec<String>,
+    accept_invalid_certs: bool,
+}
+
+impl TlsProbe {
+    /// Probe sending `server_name` as SNI, offering h2 and http/1.1
+    pub fn new(server_name: impl Into<String>) -> Self {
+        Self {
+            server_name: server_name.into(),
+            timeout: Duration::from_secs(10),
+            alpn: vec!["h2".to_string(), "http/1.1".to_string()],
+            accept_invalid_certs: false,
+        }
+    }
+
+    pub fn with_timeout(mut self, secs: u64) -> Self {
+        self.timeout = Duration::from_secs(secs);
+        self
+    }
+
+    /// ALPN protocols to offer, most preferred first (none: no ALPN)
+    pub fn with_alpn(mut self, protocols: Vec<String>) -> Self {
+        self.alpn = protocols;
+        self
+    }
+
+    /// Complete the handshake with untrusted certificates too; the reason is
+    /// kept in [`TlsInfo::verification_error`]
+    pub fn with_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.accept_invalid_certs = accept;
+        self
+    }
+
+    /// Handshake with `ip:port`
+    pub async fn connect(&self, ip: IpAddr, port: u16) -> Result<TlsInfo> {
+        Ok(self.handshake(ip, port).await?.1)
+    }
+
+    /// Handshake with `ip:port`, keeping the connection open
+    pub async fn handshake(
+        &self,
+        ip: IpAddr,
+        port: u16,
+    ) -> Result<(TlsStream<TcpStream>, TlsInfo)> {
+        let server_name = ServerName::try_from(self.server_name.as_str())
+            .with_context(|| format!("Invalid TLS server name: {}", self.server_name))?;
+        let verifier = Arc::new(InspectingVerifier::new(self.accept_invalid_certs));
+        let mut config = ClientConfig::builder()
+            .with_safe_defaults()
+            .with_custom_certificate_verifier(verifier.clone())
+            .with_no_client_auth();
+        config.alpn_protocols = self.alpn.iter().map(|p| p.as_bytes().to_vec()).collect();
+
+        let address = SocketAddr::new(ip, port);
+        let handshake = async {
+            let tcp = TcpStream::connect(address)
+                .await
+                .with_context(|| format!("Failed to connect to {address}"))?;
+            TlsConnector::from(Arc::new(config))
+                .connect(server_name, tcp)
+                .await
+                .with_context(|| format!("TLS handshake with {address} failed"))
+        };
+        let stream = tokio::time::timeout(self.timeout, handshake)
+            .await
+            .with_context(|| {
+                format!(
+                    "TLS handshake with {address} timed out after {}s",
+                    self.timeout.as_secs()
+                )
+            })??;
+
+        let (_, connection) = stream.get_ref();
+        let chain = connection.peer_certificates().unwrap_or_default();
+        let info = TlsInfo {
+            protocol: connection
+                .protocol_version()
+                .map(protocol_name)
+                .unwrap_or_else(|| "unknown".to_string()),
+            cipher_suite: connection
+                .negotiated_cipher_suite()
+                .map(|suite| format!("{:?}", suite.suite()))
+                .unwrap_or_else(|| "unknown".to_string()),
+            alpn: connection
+                .alpn_protocol()
+                .map(|p| String::from_utf8_lossy(p).to_string()),
+            certificate: chain
+                .first()
+                .and_then(|cert| CertificateInfo::parse(&cert.0).ok()),
+            chain_length: chain.len(),
+            verification_error: verifier.error(),
+        };
+        Ok((stream, info))
+    }
+}
+
+/// `TLSv1_3` as `TLSv1.3`
+fn protocol_name(version: rustls::ProtocolVersion) -> String {
+    format!("{version:?}").replace('_', ".")
+}
+
+/// Verifies against the public roots, optionally accepting failures
+struct InspectingVerifier {
+    inner: WebPkiVerifier,
+    accept_invalid: bool,
+    error: Mutex<Option<String>>,
+}
+
+impl InspectingVerifier {
+    fn new(accept_invalid: bool) -> Self {
+        let mut roots = RootCertStore::empty();
+        roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|anchor| {
+            OwnedTrustAnchor::from_subject_spki_name_constraints(
+                anchor.subject,
+                anchor.spki,
+                anchor.name_constraints,
+            )
+        }));
+        Self {
+            inner: WebPkiVerifier::new(roots, None),
+            accept_invalid,
+            error: Mutex::new(None),
+        }
+    }
+
+    fn error(&self) -> Option<String> {
+        self.error.lock().ok().and_then(|e| e.clone())
+    }
+}
+
+impl ServerCertVerifier for InspectingVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &Certificate,
+        intermediates: &[Certificate],
+        server_name: &ServerName,
+        scts: &mut dyn Iterator<Item = &[u8]>,
+        ocsp_response: &[u8],
+        now: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        match self.inner.verify_server_cert(
+            end_entity,
+            intermediates,
+            server_name,
+            scts,
+            ocsp_response,
+            now,
+        ) {
+            Ok(verified) => Ok(verified),
+            Err(e) if self.accept_invalid => {
+                if let Ok(mut error) = self.error.lock() {
+                    *error = Some(e.to_string());
+                }
+                Ok(ServerCertVerified::assertion())
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use tokio::io::AsyncWriteExt;
+    use tokio::net::TcpListener;
+    use tokio_rustls::TlsAcceptor;
+
+    /// Server configuration with a self-signed certificate for `name`
+    pub(crate) fn server_config(name: &str, alpn: &[&str]) -> rustls::ServerConfig {
+        let cert = rcgen::generate_simple_self_signed(vec![name.to_string()]).unwrap();
+        let chain = vec![Certificate(cert.serialize_der().unwrap())];
+        let key = rustls::PrivateKey(cert.serialize_private_key_der());
+        let mut config = rustls::ServerConfig::builder()
+            .with_safe_defaults()
+            .with_no_client_auth()
+            .with_single_cert(chain, key)
+            .unwrap();
+        config.alpn_protocols = alpn.iter().map(|p| p.as_bytes().to_vec()).collect();
+        config
+    }
+
+    /// Self-signed TLS listener for `name`, answering one handshake per
+    /// connection with the given ALPN protocols
+    pub(crate) async fn tls_server(name: &str, alpn: &[&str]) -> SocketAddr {
+        let acceptor = TlsAcceptor::from(Arc::new(server_config(name, alpn)));
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((tcp, _)) = listener.accept().await {
+                let acceptor = acceptor.clone();
+                tokio::spawn(async move {
+                    if let Ok(mut tls) = acceptor.accept(tcp).await {
+                        let _ = tls.shutdown().await;
+                    }
+                });
+            }
+        });
+        address
+    }
+
+    #[test]
+    fn test_certificate_matches() {
+        let cert = rcgen::generate_simple_self_signed(vec![
+            "*.example.com".to_string(),
+            "example.com".to_string(),
+            "10.0.0.1".to_string(),
+        ])
+        .unwrap();
+        let info = CertificateInfo::parse(&cert.serialize_der().unwrap()).unwrap();
+        assert_eq!(info.names, ["*.example.com", "example.com", "10.0.0.1"]);
+        assert!(info.matches("example.com"));
+        assert!(info.matches("API.example.com."));
+        assert!(info.matches("10.0.0.1"));
+        assert!(!info.matches("a.b.example.com"));
+        assert!(!info.matches("example.org"));
+    }
+
+    #[tokio::test]
+    async fn test_probe_self_signed() {
+        let address = tls_server("gateway.test", &["h2", "http/1.1"]).await;
+
+        let info = TlsProbe::new("gateway.test")
+            .with_accept_invalid_certs(true)
+            .connect(address.ip(), address.port())
+            .await
+            .unwrap();
+        assert_eq!(info.protocol, "TLSv1.3");
+        assert_eq!(info.alpn.as_deref(), Some("h2"));
+        assert_eq!(info.chain_length, 1);
+        let cert = info.certificate.as_ref().unwrap();
+        assert_eq!(cert.names, ["gateway.test"]);
+        assert!(cert.is_self_signed());
+        assert!(cert.expires_in(Utc::now()) > chrono::Duration::days(365));
+        assert!(info.verification_error.is_some());
+        assert!(info.to_string().ends_with("untrusted"), "{info}");
+
+        // Rejected unless untrusted certificates are accepted
+        let err = TlsProbe::new("gateway.test")
+            .connect(address.ip(), address.port())
+            .await
+            .unwrap_err();
+        assert!(format!("{err:#}").contains("handshake"), "{err:#}");
+    }
+}
@@ -0,0 +1,183 @@
+//! Waiting for an endpoint to come up
+//!
+//! Replaces the curl loops users script between deploy and test steps:
+//! polls a URL until it answers with the expected status or a deadline
+//! passes. The polling loop is shared with the installer's readiness checks.
+
+use anyhow::Result;
+use std::future::Future;
+use std::time::{Duration, Instant};
+use tokio::time::sleep;
+use tracing::debug;
+
+use super::client::HttpClient;
+
+/// A successful poll
+#[derive(Clone, Debug)]
+pub struct Polled<T> {
+    pub value: T,
+    pub attempts: u32,
+    pub elapsed: Duration,
+}
+
+/// Run `check` every `interval` until it succeeds or `timeout` passes
+///
+/// The error on timeout carries the reason the last attempt failed.
+pub async fn poll_until<T, F, Fut>(
+    timeout: Duration,
+    interval: Duration,
+    mut check: F,
+) -> Result<Polled<T>>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, String>>,
+{
+    let start = Instant::now();
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        match check().await {
+            Ok(value) => {
+                return Ok(Polled {
+                    value,
+                    attempts,
+                    elapsed: start.elapsed(),
+                })
+            }
+            Err(reason) => {
+                debug!("Attempt {} not ready: {}", attempts, reason);
+                if start.elapsed() + interval > timeout {
+                    anyhow::bail!(
+                        "Timed out after {}s ({} attempts): {}",
+                        timeout.as_secs(),
+                        attempts,
+                        reason
+                    );
+                }
+            }
+        }
+        sleep(interval).await;
+    }
+}
+
+/// Wait for a URL to answer with an expected status
+#[derive(Clone, Debug)]
+pub struct EndpointWait {
+    url: String,
+    status: Option<u16>,
+    host: Option<String>,
+    timeout: Duration,
+    interval: Duration,
+}
+
+impl EndpointWait {
+    /// Wait up to 120s for any 2xx, polling every 2s
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            status: None,
+            host: None,
+            timeout: Duration::from_secs(120),
+            interval: Duration::from_secs(2),
+        }
+    }
+
+    /// Require an exact status instead of any 2xx
+    pub fn with_status(mut self, status: u16) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Send a Host header
+    pub fn with_host(mut self, host: impl Into<String>) -> Self {
+        self.host = Some(host.into());
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    fn accepts(&self, status: u16) -> bool {
+        match self.status {
+            Some(expected) => status == expected,
+            None => (200..300).contains(&status),
+        }
+    }
+
+    /// Poll until the endpoint is ready, returning the status it answered
+    pub async fn wait(&self, client: &HttpClient) -> Result<Polled<u16>> {
+        poll_until(self.timeout, self.interval, || async {
+            let response = match &self.host {
+                Some(host) => client.get_with_host(&self.url, host).await,
+                None => client.get(&self.url).await,
+            }
+            .map_err(|e| format!("{e:#}"))?;
+            if self.accepts(response.status_code) {
+                Ok(response.status_code)
+            } else {
+                Err(format!("{} returned {}", self.url, response.status_code))
+            }
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_poll_until_retries() {
+        let mut calls = 0;
+        let polled = poll_until(Duration::from_secs(5), Duration::from_millis(1), || {
+            calls += 1;
+            let ready = calls >= 3;
+            async move {
+                if ready {
+                    Ok("up")
+                } else {
+                    Err("down".to_string())
+                }
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(polled.value, "up");
+        assert_eq!(polled.attempts, 3);
+
+        let err = poll_until(Duration::ZERO, Duration::from_millis(1), || async {
+            Err::<(), _>("connection refused".to_string())
+        })
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("(1 attempts): connection refused"));
+    }
+
+    #[test]
+    fn test_expected_status() {
+        let any = EndpointWait::new("http://10.0.0.1/health");
+        assert!(any.accepts(204));
+        assert!(!any.accepts(503));
+        let exact = any.with_status(404);
+        assert!(exact.accepts(404));
+        assert!(!exact.accepts(200));
+    }
+
+    #[tokio::test]
+    async fn test_wait_times_out() {
+        let client = HttpClient::with_timeout(1).unwrap();
+        let err = EndpointWait::new("http://127.0.0.1:1/health")
+            .with_timeout(Duration::ZERO)
+            .wait(&client)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().starts_with("Timed out"), "{err}");
+    }
+}
@@ -0,0 +1,161 @@
+//! Test certificates
+//!
+//! A throwaway CA with one server and one client certificate, for fixtures
+//! that need TLS between the gateway and a backend. Generated with the
+//! `openssl` CLI in a scratch directory that is removed afterwards.
+
+#![allow(dead_code)]
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+use tokio::process::Command;
+use tracing::debug;
+
+/// Days the certificates stay valid (fixtures are short-lived)
+const VALIDITY_DAYS: &str = "7";
+
+/// PEM-encoded CA, server and client certificates and keys
+#[derive(Clone, Debug)]
+pub struct TestCertificates {
+    pub ca_cert: String,
+    pub server_cert: String,
+    pub server_key: String,
+    pub client_cert: String,
+    pub client_key: String,
+}
+
+impl TestCertificates {
+    /// Generate a CA, a server certificate for `hostname` and a client
+    /// certificate, both signed by the CA
+    pub async fn generate(hostname: &str) -> Result<Self> {
+        let dir = ScratchDir::new()?;
+        let path = |name: &str| dir.path().join(name);
+
+        openssl(&[
+            "req",
+            "-x509",
+            "-newkey",
+            "rsa:2048",
+            "-nodes",
+            "-days",
+            VALIDITY_DAYS,
+            "-subj",
+            "/CN=gateway-poc test CA",
+            "-keyout",
+            &path("ca.key").to_string_lossy(),
+            "-out",
+            &path("ca.crt").to_string_lossy(),
+        ])
+        .await?;
+        sign(
+            dir.path(),
+            "server",
+            hostname,
+            &format!("subjectAltName=DNS:{hostname}\nextendedKeyUsage=serverAuth\n"),
+        )
+        .await?;
+        sign(
+            dir.path(),
+            "client",
+            "gateway-poc client",
+            "extendedKeyUsage=clientAuth\n",
+        )
+        .await?;
+
+        let read = |name: &str| {
+            fs::read_to_string(path(name)).with_context(|| format!("Failed to read {name}"))
+        };
+        Ok(Self {
+            ca_cert: read("ca.crt")?,
+            server_cert: read("server.crt")?,
+            server_key: read("server.key")?,
+            client_cert: read("client.crt")?,
+            client_key: read("client.key")?,
+        })
+    }
+}
+
+/// Create `<name>.key` and `<name>.crt` signed by `ca.crt`/`ca.key` in `dir`
+async fn sign(dir: &Path, name: &str, common_name: &str, extensions: &str) -> Result<()> {
+    let path = |file: String| dir.join(file).to_string_lossy().into_owned();
+    let extfile = path(format!("{name}.ext"));
+    fs::write(&extfile, extensions).with_context(|| format!("Failed to write {extfile}"))?;
+
+    openssl(&[
+        "req",
+        "-newkey",
+        "rsa:2048",
+        "-nodes",
+        "-subj",
+        &format!("/CN={common_name}"),
+        "-keyout",
+        &path(format!("{name}.key")),
+        "-out",
+        &path(format!("{name}.csr")),
+    ])
+    .await?;
+    openssl(&[
+        "x509",
+        "-req",
+        "-days",
+        VALIDITY_DAYS,
+        "-in",
+        &path(format!("{name}.csr")),
+        "-CA",
+        &path("ca.crt".to_string()),
+        "-CAkey",
+        &path("ca.key".to_string()),
+        "-CAcreateserial",
+        "-extfile",
+        &extfile,
+        "-out",
+        &path(format!("{name}.crt")),
+    ])
+    .await
+}
+
+async fn openssl(args: &[&str]) -> Result<()> {
+    debug!("Running: openssl {}", args.join(" "));
+    let output = Command::new("openssl")
+        .args(args)
+        .output()
+        .await
+        .context("Failed to run openssl (is it installed?)")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "openssl {} failed: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// Temporary directory removed on drop
+struct ScratchDir(PathBuf);
+
+impl ScratchDir {
+    fn new() -> Result<Self> {
+        static NEXT: AtomicU32 = AtomicU32::new(0);
+        let path = std::env::temp_dir().join(format!(
+            "gateway-poc-certs-{}-{}",
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&path)
+            .with_context(|| format!("Failed to create {}", path.display()))?;
+        Ok(Self(path))
+    }
+
+    fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
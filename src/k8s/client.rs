@@ -9,10 +9,14 @@ use k8s_openapi::api::core::v1::{Namespace, Pod, Service};
 use k8s_openapi::apiextensions_apiserver::pkg::apis::apiextensions::v1::CustomResourceDefinition;
 use kube::{
     api::{Api, ListParams},
+    client::ClientBuilder,
     Client, Config,
 };
 use tracing::{info, warn};
 
+use super::throttle::ApiRateLimitLayer;
+use crate::models::GatewayApiSpec;
+
 /// Kubernetes client wrapper
 #[derive(Clone)]
 pub struct K8sClient {
@@ -23,20 +27,20 @@ pub struct K8sClient {
 impl K8sClient {
     /// Create a new Kubernetes client
     pub async fn new(namespace: impl Into<String>) -> Result<Self> {
-        let client = Client::try_default()
+        let config = Config::infer()
             .await
             .context("Failed to create Kubernetes client")?;
-
-        Ok(Self {
-            client,
-            namespace: namespace.into(),
-        })
+        Self::with_config(config, namespace).await
     }
 
     /// Create client with custom config
+    ///
+    /// Requests go through the process-wide API rate limit.
     pub async fn with_config(config: Config, namespace: impl Into<String>) -> Result<Self> {
-        let client =
-            Client::try_from(config).context("Failed to create Kubernetes client from config")?;
+        let client = ClientBuilder::try_from(config)
+            .context("Failed to create Kubernetes client from config")?
+            .with_layer(&ApiRateLimitLayer)
+            .build();
 
         Ok(Self {
             client,
@@ -44,6 +48,14 @@ impl K8sClient {
         })
     }
 
+    /// Same connection, scoped to another namespace
+    pub fn in_namespace(&self, namespace: impl Into<String>) -> Self {
+        Self {
+            client: self.client.clone(),
+            namespace: namespace.into(),
+        }
+    }
+
     /// Get the underlying kube client
     pub fn client(&self) -> &Client {
         &self.client
@@ -94,6 +106,24 @@ impl K8sClient {
         Ok(installed)
     }
 
+    /// Detect the installed Gateway API release and channel
+    ///
+    /// Reads the bundle annotations on the GatewayClass CRD; returns None if
+    /// the CRDs are missing or carry no bundle version.
+    pub async fn gateway_api_spec(&self) -> Result<Option<GatewayApiSpec>> {
+        let crds: Api<CustomResourceDefinition> = Api::all(self.client.clone());
+
+        match crds.get("gatewayclasses.gateway.networking.k8s.io").await {
+            Ok(crd) => Ok(crd
+                .metadata
+                .annotations
+                .as_ref()
+                .and_then(GatewayApiSpec::from_annotations)),
+            Err(kube::Error::Api(e)) if e.code == 404 => Ok(None),
+            Err(e) => Err(e).context("Failed to read Gateway API CRD"),
+        }
+    }
+
     /// Check if a specific CRD exists
     pub async fn crd_exists(&self, group: &str, _version: &str, kind: &str) -> Result<bool> {
         let crds: Api<CustomResourceDefinition> = Api::all(self.client.clone());
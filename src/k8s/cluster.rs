@@ -0,0 +1,159 @@
+//! Cluster health probing
+//!
+//! Snapshots node conditions, pending pods, warning events and API server
+//! latency before each test round.
+
+#![allow(dead_code)]
+
+use anyhow::{Context, Result};
+use k8s_openapi::api::core::v1::{Event, Node, Pod};
+use kube::api::{Api, ListParams};
+use std::collections::HashMap;
+use std::time::Instant;
+use tracing::{debug, warn};
+
+use super::K8sClient;
+use crate::models::{RoundEnvironment, TestResult, DEFAULT_API_LATENCY_THRESHOLD_MS};
+
+/// Node conditions that indicate resource pressure when True
+const PRESSURE_CONDITIONS: [&str; 4] = [
+    "MemoryPressure",
+    "DiskPressure",
+    "PIDPressure",
+    "NetworkUnavailable",
+];
+
+/// Window for counting recent Warning events
+const EVENT_WINDOW_SECS: i64 = 300;
+
+/// Captures cluster health snapshots
+#[derive(Clone)]
+pub struct ClusterProbe {
+    client: K8sClient,
+    api_latency_threshold_ms: u64,
+}
+
+impl ClusterProbe {
+    pub fn new(client: K8sClient) -> Self {
+        Self {
+            client,
+            api_latency_threshold_ms: DEFAULT_API_LATENCY_THRESHOLD_MS,
+        }
+    }
+
+    /// Set API latency threshold for anomaly detection
+    pub fn with_latency_threshold(mut self, ms: u64) -> Self {
+        self.api_latency_threshold_ms = ms;
+        self
+    }
+
+    /// Take a snapshot of cluster health
+    pub async fn snapshot(&self) -> Result<RoundEnvironment> {
+        let mut env = RoundEnvironment::new();
+        env.api_latency_threshold_ms = self.api_latency_threshold_ms;
+
+        let start = Instant::now();
+        env.api_latency_ms = match self.client.client().apiserver_version().await {
+            Ok(_) => Some(start.elapsed().as_millis() as u64),
+            Err(e) => {
+                warn!("API server probe failed: {}", e);
+                None
+            }
+        };
+
+        let nodes: Api<Node> = self.client.cluster_api();
+        let node_list = nodes
+            .list(&ListParams::default())
+            .await
+            .context("Failed to list nodes")?;
+
+        env.nodes_total = node_list.items.len();
+        for node in &node_list.items {
+            let name = node.metadata.name.as_deref().unwrap_or("unknown");
+            let conditions = node
+                .status
+                .as_ref()
+                .and_then(|s| s.conditions.as_ref())
+                .map(|c| c.as_slice())
+                .unwrap_or_default();
+
+            for condition in conditions {
+                if condition.status != "True" {
+                    continue;
+                }
+                if condition.type_ == "Ready" {
+                    env.nodes_ready += 1;
+                } else if PRESSURE_CONDITIONS.contains(&condition.type_.as_str()) {
+                    env.node_pressure
+                        .push(format!("{}: {}", name, condition.type_));
+                }
+            }
+        }
+
+        let pods: Api<Pod> = Api::all(self.client.client().clone());
+        let pending = pods
+            .list(&ListParams::default().fields("status.phase=Pending"))
+            .await
+            .context("Failed to list pending pods")?;
+        env.pending_pods = pending.items.len();
+
+        let events: Api<Event> = Api::all(self.client.client().clone());
+        let warnings = events
+            .list(&ListParams::default().fields("type=Warning"))
+            .await
+            .context("Failed to list events")?;
+        let cutoff = chrono::Utc::now() - chrono::Duration::seconds(EVENT_WINDOW_SECS);
+        env.warning_events = warnings
+            .items
+            .iter()
+            .filter(|e| {
+                e.last_timestamp
+                    .as_ref()
+                    .map(|t| t.0 >= cutoff)
+                    .unwrap_or(false)
+            })
+            .count();
+
+        debug!("Cluster snapshot: {}", env);
+        Ok(env)
+    }
+
+    /// Fill in the node of backends that did not report one, by pod lookup
+    pub async fn locate_backends(&self, results: &mut [TestResult]) {
+        let mut nodes: HashMap<(String, String), Option<String>> = HashMap::new();
+        for backend in results
+            .iter_mut()
+            .flat_map(|r| r.backends.iter_mut())
+            .map(|hit| &mut hit.backend)
+            .filter(|b| b.node.is_none())
+        {
+            let Some(namespace) = backend.namespace.clone() else {
+                continue;
+            };
+            let key = (namespace, backend.pod.clone());
+            if !nodes.contains_key(&key) {
+                let pods: Api<Pod> = Api::namespaced(self.client.client().clone(), &key.0);
+                let node = match pods.get_opt(&key.1).await {
+                    Ok(pod) => pod.and_then(|p| p.spec).and_then(|s| s.node_name),
+                    Err(e) => {
+                        warn!("Failed to look up backend pod {}/{}: {}", key.0, key.1, e);
+                        None
+                    }
+                };
+                nodes.insert(key.clone(), node);
+            }
+            backend.node = nodes[&key].clone();
+        }
+    }
+
+    /// Take a snapshot, logging and discarding failures
+    pub async fn try_snapshot(&self) -> Option<RoundEnvironment> {
+        match self.snapshot().await {
+            Ok(env) => Some(env),
+            Err(e) => {
+                warn!("Failed to capture cluster environment: {:#}", e);
+                None
+            }
+        }
+    }
+}
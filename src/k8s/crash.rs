@@ -0,0 +1,149 @@
+//! Gateway pod crash detection
+//!
+//! Snapshots the restart counts of the gateway's controller and data-plane
+//! pods before and after a test round or benchmark, so results measured
+//! across a crash can be flagged.
+
+#![allow(dead_code)]
+
+use anyhow::{Context, Result};
+use k8s_openapi::api::core::v1::{ContainerStatus, Pod};
+use kube::api::{Api, ListParams};
+use tracing::warn;
+
+use super::K8sClient;
+use crate::models::{ContainerSnapshot, GatewayImpl, PodCrash, PodSnapshot};
+
+/// Watches gateway pods for restarts
+#[derive(Clone)]
+pub struct CrashWatcher {
+    client: K8sClient,
+    selectors: Vec<String>,
+    namespace: Option<String>,
+}
+
+impl CrashWatcher {
+    /// Watch the controller and data-plane pods of a gateway in all namespaces
+    pub fn new(client: K8sClient, gateway: GatewayImpl) -> Self {
+        Self {
+            client,
+            selectors: gateway.all_pod_selectors(&gateway.gateway_name()),
+            namespace: None,
+        }
+    }
+
+    /// Restrict watching to one namespace
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    fn api(&self) -> Api<Pod> {
+        let client = self.client.client().clone();
+        match &self.namespace {
+            Some(ns) => Api::namespaced(client, ns),
+            None => Api::all(client),
+        }
+    }
+
+    /// Current state of every watched pod
+    pub async fn snapshot(&self) -> Result<Vec<PodSnapshot>> {
+        let api = self.api();
+        let mut pods: Vec<PodSnapshot> = Vec::new();
+        for selector in &self.selectors {
+            let list = api
+                .list(&ListParams::default().labels(selector))
+                .await
+                .with_context(|| format!("Failed to list pods matching {selector}"))?;
+            for pod in list.items {
+                let snapshot = pod_snapshot(&pod);
+                if !pods.iter().any(|p| p.uid == snapshot.uid) {
+                    pods.push(snapshot);
+                }
+            }
+        }
+        Ok(pods)
+    }
+}
+
+/// Pod restart counts at the start of a test round or benchmark
+pub struct CrashWindow {
+    baseline: Option<(CrashWatcher, Vec<PodSnapshot>)>,
+}
+
+impl CrashWindow {
+    /// Start a window (a no-op without a watcher)
+    pub async fn open(watcher: Option<&CrashWatcher>) -> Self {
+        let Some(watcher) = watcher else {
+            return Self { baseline: None };
+        };
+        let baseline = match watcher.snapshot().await {
+            Ok(pods) => Some((watcher.clone(), pods)),
+            Err(e) => {
+                warn!("Failed to snapshot gateway pods: {:#}", e);
+                None
+            }
+        };
+        Self { baseline }
+    }
+
+    /// Crashes since the window opened (empty if the pods could not be read)
+    pub async fn finish(self) -> Vec<PodCrash> {
+        let Some((watcher, baseline)) = self.baseline else {
+            return Vec::new();
+        };
+        let crashes = match watcher.snapshot().await {
+            Ok(pods) => PodCrash::detect(&baseline, &pods),
+            Err(e) => {
+                warn!("Failed to snapshot gateway pods: {:#}", e);
+                return Vec::new();
+            }
+        };
+        for crash in &crashes {
+            warn!("Gateway pod crashed: {}", crash);
+        }
+        crashes
+    }
+}
+
+fn pod_snapshot(pod: &Pod) -> PodSnapshot {
+    let statuses = pod
+        .status
+        .as_ref()
+        .and_then(|s| s.container_statuses.as_ref())
+        .map(|c| c.as_slice())
+        .unwrap_or_default();
+
+    PodSnapshot {
+        namespace: pod.metadata.namespace.clone().unwrap_or_default(),
+        name: pod.metadata.name.clone().unwrap_or_default(),
+        uid: pod.metadata.uid.clone().unwrap_or_default(),
+        containers: statuses.iter().map(container_snapshot).collect(),
+    }
+}
+
+fn container_snapshot(status: &ContainerStatus) -> ContainerSnapshot {
+    let last_termination = status
+        .last_state
+        .as_ref()
+        .and_then(|s| s.terminated.as_ref())
+        .map(|t| {
+            format!(
+                "{} (exit {})",
+                t.reason.as_deref().unwrap_or("Terminated"),
+                t.exit_code
+            )
+        });
+    let waiting = status
+        .state
+        .as_ref()
+        .and_then(|s| s.waiting.as_ref())
+        .and_then(|w| w.reason.clone());
+
+    ContainerSnapshot {
+        name: status.name.clone(),
+        restarts: status.restart_count.max(0) as u32,
+        last_termination,
+        waiting,
+    }
+}
@@ -0,0 +1,86 @@
+//! Deployment scaling
+//!
+//! Scales backend Deployments for tests that verify behaviour across
+//! scaling events (e.g. consistent-hash key remapping).
+
+#![allow(dead_code)]
+
+use anyhow::{Context, Result};
+use k8s_openapi::api::apps::v1::Deployment;
+use kube::api::{Api, Patch, PatchParams};
+use std::time::{Duration, Instant};
+use tracing::{debug, info};
+
+use super::K8sClient;
+
+/// Interval between readiness checks while waiting for a rollout
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Scales Deployments in the client's namespace
+#[derive(Clone)]
+pub struct DeploymentScaler {
+    client: K8sClient,
+}
+
+impl DeploymentScaler {
+    pub fn new(client: K8sClient) -> Self {
+        Self { client }
+    }
+
+    fn api(&self) -> Api<Deployment> {
+        self.client.namespaced_api()
+    }
+
+    /// Desired replica count of a Deployment
+    pub async fn replicas(&self, name: &str) -> Result<i32> {
+        let deployment = self
+            .api()
+            .get(name)
+            .await
+            .with_context(|| format!("Failed to get deployment {name}"))?;
+        Ok(deployment.spec.and_then(|spec| spec.replicas).unwrap_or(1))
+    }
+
+    /// Set the replica count of a Deployment
+    pub async fn scale(&self, name: &str, replicas: i32) -> Result<()> {
+        info!("Scaling deployment {} to {} replicas", name, replicas);
+        let patch = serde_json::json!({ "spec": { "replicas": replicas } });
+        self.api()
+            .patch(name, &PatchParams::default(), &Patch::Merge(&patch))
+            .await
+            .with_context(|| format!("Failed to scale deployment {name}"))?;
+        Ok(())
+    }
+
+    /// Wait until `replicas` pods of a Deployment are available
+    pub async fn wait_available(&self, name: &str, replicas: i32, timeout: Duration) -> Result<()> {
+        let start = Instant::now();
+        loop {
+            let deployment = self
+                .api()
+                .get(name)
+                .await
+                .with_context(|| format!("Failed to get deployment {name}"))?;
+            let status = deployment.status.unwrap_or_default();
+            let available = status.available_replicas.unwrap_or(0);
+            let total = status.replicas.unwrap_or(0);
+            debug!("Deployment {}: {}/{} available", name, available, replicas);
+
+            if available == replicas && total == replicas {
+                return Ok(());
+            }
+            if start.elapsed() >= timeout {
+                anyhow::bail!(
+                    "Timeout waiting for deployment {name} ({available}/{replicas} available)"
+                );
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    /// Scale and wait for the new replica count to become available
+    pub async fn scale_and_wait(&self, name: &str, replicas: i32, timeout: Duration) -> Result<()> {
+        self.scale(name, replicas).await?;
+        self.wait_available(name, replicas, timeout).await
+    }
+}
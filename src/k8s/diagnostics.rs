@@ -0,0 +1,263 @@
+//! Failure diagnostics capture
+//!
+//! When a test fails, snapshots the cluster state needed to debug it after
+//! the fact: events, the gateway controller and data-plane pod logs for the
+//! failure window, and HTTPRoute status conditions. Each bundle is written
+//! to its own directory, whose path is recorded in the test result details.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use k8s_openapi::api::core::v1::{Event, Pod};
+use kube::api::{Api, ListParams, LogParams};
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tracing::{info, warn};
+
+use super::httproute::HTTPRoute;
+use super::K8sClient;
+use crate::models::{GatewayImpl, TestCase, TestResult, TestStatus};
+
+/// Logs and events from this long before a test started are included
+const WINDOW_SLACK_SECS: i64 = 5;
+
+/// Collects cluster diagnostics for failed tests
+#[derive(Clone)]
+pub struct DiagnosticsCollector {
+    client: K8sClient,
+    selectors: Vec<String>,
+    namespace: Option<String>,
+    dir: PathBuf,
+}
+
+impl DiagnosticsCollector {
+    /// Collect from the gateway's pods in all namespaces into `dir`
+    pub fn new(client: K8sClient, gateway: GatewayImpl, dir: impl Into<PathBuf>) -> Self {
+        Self {
+            client,
+            selectors: gateway.all_pod_selectors(&gateway.gateway_name()),
+            namespace: None,
+            dir: dir.into(),
+        }
+    }
+
+    /// Restrict gateway pods to one namespace
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    fn pods(&self) -> Api<Pod> {
+        let client = self.client.client().clone();
+        match &self.namespace {
+            Some(ns) => Api::namespaced(client, ns),
+            None => Api::all(client),
+        }
+    }
+
+    /// Attach a diagnostics bundle to a failed result (others pass through)
+    pub async fn attach(&self, result: TestResult, started: DateTime<Utc>) -> TestResult {
+        if !matches!(result.status, TestStatus::Fail | TestStatus::Error) {
+            return result;
+        }
+        match self.collect(result.test_case, started).await {
+            Ok(path) => {
+                info!(
+                    "Diagnostics for {} saved to {}",
+                    result.test_case,
+                    path.display()
+                );
+                let details = with_diagnostics(result.details.clone(), &path);
+                result.with_details(details)
+            }
+            Err(e) => {
+                warn!(
+                    "Failed to collect diagnostics for {}: {:#}",
+                    result.test_case, e
+                );
+                result
+            }
+        }
+    }
+
+    /// Write a bundle for a test that started at `started`
+    pub async fn collect(&self, test_case: TestCase, started: DateTime<Utc>) -> Result<PathBuf> {
+        let since = started - chrono::Duration::seconds(WINDOW_SLACK_SECS);
+        let path = self.dir.join(bundle_name(test_case, Utc::now()));
+        fs::create_dir_all(&path)
+            .await
+            .with_context(|| format!("Failed to create {}", path.display()))?;
+
+        // Each part is best effort so one unreadable source keeps the rest
+        if let Err(e) = self.write_events(&path, since).await {
+            warn!("Failed to capture events: {:#}", e);
+        }
+        if let Err(e) = self.write_logs(&path, since).await {
+            warn!("Failed to capture gateway logs: {:#}", e);
+        }
+        if let Err(e) = self.write_routes(&path).await {
+            warn!("Failed to capture HTTPRoute status: {:#}", e);
+        }
+        Ok(path)
+    }
+
+    async fn write_events(&self, path: &Path, since: DateTime<Utc>) -> Result<()> {
+        let events: Api<Event> = Api::all(self.client.client().clone());
+        let list = events
+            .list(&ListParams::default())
+            .await
+            .context("Failed to list events")?;
+        let mut recent: Vec<&Event> = list
+            .items
+            .iter()
+            .filter(|e| event_time(e).is_some_and(|t| t >= since))
+            .collect();
+        recent.sort_by_key(|e| event_time(e));
+        let text: String = recent.into_iter().map(format_event).collect();
+        fs::write(path.join("events.txt"), text)
+            .await
+            .context("Failed to write events")
+    }
+
+    async fn write_logs(&self, path: &Path, since: DateTime<Utc>) -> Result<()> {
+        let logs_dir = path.join("logs");
+        fs::create_dir_all(&logs_dir).await?;
+        for selector in &self.selectors {
+            let pods = self
+                .pods()
+                .list(&ListParams::default().labels(selector))
+                .await
+                .with_context(|| format!("Failed to list pods matching {selector}"))?;
+            for pod in pods.items {
+                let namespace = pod.metadata.namespace.clone().unwrap_or_default();
+                let name = pod.metadata.name.clone().unwrap_or_default();
+                let api: Api<Pod> = Api::namespaced(self.client.client().clone(), &namespace);
+                let containers = pod
+                    .spec
+                    .map(|s| s.containers.into_iter().map(|c| c.name).collect())
+                    .unwrap_or_else(Vec::new);
+                for container in containers {
+                    let params = LogParams {
+                        container: Some(container.clone()),
+                        since_time: Some(since),
+                        timestamps: true,
+                        ..LogParams::default()
+                    };
+                    let text = match api.logs(&name, &params).await {
+                        Ok(text) => text,
+                        Err(e) => format!("failed to read logs: {e}\n"),
+                    };
+                    let file = logs_dir.join(format!("{namespace}_{name}_{container}.log"));
+                    fs::write(&file, text)
+                        .await
+                        .with_context(|| format!("Failed to write {}", file.display()))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn write_routes(&self, path: &Path) -> Result<()> {
+        let routes: Api<HTTPRoute> = Api::all(self.client.client().clone());
+        let list = routes
+            .list(&ListParams::default())
+            .await
+            .context("Failed to list HTTPRoutes")?;
+        let conditions: Vec<_> = list
+            .items
+            .iter()
+            .map(|route| {
+                json!({
+                    "namespace": route.metadata.namespace,
+                    "name": route.metadata.name,
+                    "parents": route.status.as_ref().map(|s| &s.parents),
+                })
+            })
+            .collect();
+        fs::write(
+            path.join("httproutes.json"),
+            serde_json::to_string_pretty(&conditions)?,
+        )
+        .await
+        .context("Failed to write HTTPRoute status")
+    }
+}
+
+/// Directory name of a bundle, e.g. `02-path-routing-20260101T120000Z`
+fn bundle_name(test_case: TestCase, at: DateTime<Utc>) -> String {
+    format!(
+        "{:02}-{}-{}",
+        test_case.number(),
+        test_case.name().to_lowercase().replace(' ', "-"),
+        at.format("%Y%m%dT%H%M%SZ")
+    )
+}
+
+/// Result details with the bundle path added, keeping existing details
+fn with_diagnostics(details: Option<serde_json::Value>, path: &Path) -> serde_json::Value {
+    let path = path.display().to_string();
+    match details {
+        Some(serde_json::Value::Object(mut map)) => {
+            map.insert("diagnostics".to_string(), path.into());
+            serde_json::Value::Object(map)
+        }
+        None => json!({ "diagnostics": path }),
+        Some(other) => json!({ "details": other, "diagnostics": path }),
+    }
+}
+
+fn event_time(event: &Event) -> Option<DateTime<Utc>> {
+    event
+        .last_timestamp
+        .as_ref()
+        .map(|t| t.0)
+        .or_else(|| event.event_time.as_ref().map(|t| t.0))
+}
+
+/// One `kubectl get events`-style line
+fn format_event(event: &Event) -> String {
+    format!(
+        "{} {} {} {}/{} {}: {}\n",
+        event_time(event)
+            .map(|t| t.to_rfc3339())
+            .unwrap_or_default(),
+        event.type_.as_deref().unwrap_or("-"),
+        event.reason.as_deref().unwrap_or("-"),
+        event.involved_object.kind.as_deref().unwrap_or("-"),
+        event.involved_object.name.as_deref().unwrap_or("-"),
+        event.metadata.namespace.as_deref().unwrap_or("-"),
+        event.message.as_deref().unwrap_or("").trim()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_bundle_name() {
+        let at = Utc.with_ymd_and_hms(2026, 1, 1, 12, 0, 0).unwrap();
+        let name = bundle_name(TestCase::PathRouting, at);
+        assert!(name.starts_with(&format!("{:02}-", TestCase::PathRouting.number())));
+        assert!(name.ends_with("-20260101T120000Z"));
+        assert!(!name.contains(' '));
+    }
+
+    #[test]
+    fn test_with_diagnostics_keeps_details() {
+        let path = Path::new("/tmp/diag");
+        assert_eq!(
+            with_diagnostics(None, path),
+            json!({ "diagnostics": "/tmp/diag" })
+        );
+        assert_eq!(
+            with_diagnostics(Some(json!({ "status": 503 })), path),
+            json!({ "status": 503, "diagnostics": "/tmp/diag" })
+        );
+        assert_eq!(
+            with_diagnostics(Some(json!([1, 2])), path),
+            json!({ "details": [1, 2], "diagnostics": "/tmp/diag" })
+        );
+    }
+}
@@ -0,0 +1,224 @@
+//! Backend fault injection
+//!
+//! Takes backends down on purpose so failover can be timed from a known
+//! instant instead of waiting for something to fail.
+
+use anyhow::{Context, Result};
+use k8s_openapi::api::core::v1::{Node, Pod};
+use kube::api::{Api, DeleteParams, ListParams, Patch, PatchParams};
+use std::time::{Duration, Instant};
+use tracing::info;
+
+use super::{DeploymentScaler, K8sClient};
+
+/// Image of the ephemeral container that shapes a pod's traffic with `tc`
+const NETEM_IMAGE: &str = "nicolaka/netshoot:v0.13";
+
+/// How long the netem container may take to start, image pull included
+const NETEM_START_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How long the netem container must stay up for `tc` to have succeeded
+const NETEM_SETTLE: Duration = Duration::from_secs(1);
+
+/// Injects faults into backends in the client's namespace
+#[derive(Clone)]
+pub struct FaultInjector {
+    client: K8sClient,
+}
+
+impl FaultInjector {
+    pub fn new(client: K8sClient) -> Self {
+        Self { client }
+    }
+
+    fn pods(&self) -> Api<Pod> {
+        self.client.namespaced_api()
+    }
+
+    /// Scaler for the backend Deployments
+    pub fn scaler(&self) -> DeploymentScaler {
+        DeploymentScaler::new(self.client.clone())
+    }
+
+    /// Delete a pod without a grace period, as if it crashed
+    pub async fn kill_pod(&self, name: &str) -> Result<()> {
+        info!("Killing pod {}", name);
+        self.pods()
+            .delete(name, &DeleteParams::default().grace_period(0))
+            .await
+            .with_context(|| format!("Failed to delete pod {name}"))?;
+        Ok(())
+    }
+
+    /// Node a pod is scheduled on
+    pub async fn pod_node(&self, name: &str) -> Result<String> {
+        let pod = self
+            .pods()
+            .get(name)
+            .await
+            .with_context(|| format!("Failed to get pod {name}"))?;
+        pod.spec
+            .and_then(|spec| spec.node_name)
+            .with_context(|| format!("Pod {name} is not scheduled on a node"))
+    }
+
+    /// Running pods matching a label selector
+    pub async fn running_pods(&self, selector: &str) -> Result<Vec<String>> {
+        let list = self
+            .pods()
+            .list(&ListParams::default().labels(selector))
+            .await
+            .with_context(|| format!("Failed to list pods matching {selector}"))?;
+        Ok(list
+            .items
+            .into_iter()
+            .filter(|pod| {
+                pod.metadata.deletion_timestamp.is_none()
+                    && pod.status.as_ref().and_then(|s| s.phase.as_deref()) == Some("Running")
+            })
+            .filter_map(|pod| pod.metadata.name)
+            .collect())
+    }
+
+    /// Gracefully delete every pod matching a selector in a namespace so
+    /// their controllers restart them; returns the deleted pods as
+    /// `namespace/name`
+    pub async fn restart_pods(&self, selector: &str, namespace: &str) -> Result<Vec<String>> {
+        // An empty selector matches every pod in the namespace
+        if selector.trim().is_empty() {
+            anyhow::bail!("Refusing to restart pods without a label selector");
+        }
+        let api: Api<Pod> = Api::namespaced(self.client.client().clone(), namespace);
+        let list = api
+            .list(&ListParams::default().labels(selector))
+            .await
+            .with_context(|| format!("Failed to list pods matching {selector}"))?;
+
+        let mut restarted = Vec::new();
+        for pod in list.items {
+            let (Some(ns), Some(name)) = (pod.metadata.namespace, pod.metadata.name) else {
+                continue;
+            };
+            info!("Restarting pod {}/{}", ns, name);
+            Api::<Pod>::namespaced(self.client.client().clone(), &ns)
+                .delete(&name, &DeleteParams::default())
+                .await
+                .with_context(|| format!("Failed to delete pod {ns}/{name}"))?;
+            restarted.push(format!("{ns}/{name}"));
+        }
+        Ok(restarted)
+    }
+
+    /// Delay a pod's egress traffic for a while with `tc netem`
+    ///
+    /// Runs in an ephemeral container sharing the pod's network namespace,
+    /// which removes the qdisc itself once `duration` has passed. Ephemeral
+    /// containers cannot be removed, so the container stays in the pod spec
+    /// (exited) until the pod is replaced. Returns the container's name once
+    /// the qdisc is in place, or an error when the container fails.
+    pub async fn netem_delay(
+        &self,
+        pod: &str,
+        delay_ms: u64,
+        duration: Duration,
+    ) -> Result<String> {
+        let name = format!("netem-{}", chrono::Utc::now().timestamp_millis());
+        let script = format!(
+            "tc qdisc add dev eth0 root netem delay {delay_ms}ms && sleep {}; tc qdisc del dev eth0 root",
+            duration.as_secs().max(1)
+        );
+        let patch = serde_json::json!({
+            "spec": {
+                "ephemeralContainers": [{
+                    "name": name,
+                    "image": NETEM_IMAGE,
+                    "command": ["sh", "-c", script],
+                    "securityContext": { "capabilities": { "add": ["NET_ADMIN"] } },
+                }]
+            }
+        });
+        info!("Delaying traffic of pod {} by {}ms", pod, delay_ms);
+        self.pods()
+            .patch_ephemeral_containers(pod, &PatchParams::default(), &Patch::Strategic(&patch))
+            .await
+            .with_context(|| format!("Failed to add a netem container to pod {pod}"))?;
+        self.wait_netem_started(pod, &name).await?;
+        Ok(name)
+    }
+
+    /// Wait until the netem container got past `tc qdisc add`: it keeps
+    /// running (sleeping) or exited cleanly, and fails otherwise
+    async fn wait_netem_started(&self, pod: &str, name: &str) -> Result<()> {
+        let started = Instant::now();
+        let mut running_since = None;
+        loop {
+            let status = self
+                .pods()
+                .get(pod)
+                .await
+                .with_context(|| format!("Failed to read pod {pod}"))?
+                .status;
+            let state = status
+                .and_then(|s| s.ephemeral_container_statuses)
+                .unwrap_or_default()
+                .into_iter()
+                .find(|c| c.name == name)
+                .and_then(|c| c.state);
+            if let Some(state) = state {
+                if let Some(terminated) = state.terminated {
+                    if terminated.exit_code == 0 {
+                        return Ok(());
+                    }
+                    anyhow::bail!(
+                        "netem container {name} in pod {pod} exited with {}: {}",
+                        terminated.exit_code,
+                        terminated
+                            .message
+                            .or(terminated.reason)
+                            .unwrap_or_else(|| "no message".to_string())
+                    );
+                }
+                if state.running.is_some() {
+                    let since = *running_since.get_or_insert_with(Instant::now);
+                    if since.elapsed() >= NETEM_SETTLE {
+                        return Ok(());
+                    }
+                }
+                if let Some(reason) = state.waiting.and_then(|w| w.reason) {
+                    if matches!(
+                        reason.as_str(),
+                        "ErrImagePull"
+                            | "ImagePullBackOff"
+                            | "InvalidImageName"
+                            | "CreateContainerError"
+                    ) {
+                        anyhow::bail!("netem container {name} in pod {pod} cannot start: {reason}");
+                    }
+                }
+            }
+            if started.elapsed() >= NETEM_START_TIMEOUT {
+                anyhow::bail!(
+                    "netem container {name} in pod {pod} did not start within {}s",
+                    NETEM_START_TIMEOUT.as_secs()
+                );
+            }
+            tokio::time::sleep(Duration::from_millis(250)).await;
+        }
+    }
+
+    /// Mark a node (un)schedulable
+    pub async fn cordon(&self, node: &str, cordoned: bool) -> Result<()> {
+        info!(
+            "{} node {}",
+            if cordoned { "Cordoning" } else { "Uncordoning" },
+            node
+        );
+        let nodes: Api<Node> = self.client.cluster_api();
+        let patch = serde_json::json!({ "spec": { "unschedulable": cordoned } });
+        nodes
+            .patch(node, &PatchParams::default(), &Patch::Merge(&patch))
+            .await
+            .with_context(|| format!("Failed to cordon node {node}"))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,1027 @@
+//! Test fixtures created in the cluster
+//!
+//! Echo backends, the cross-namespace topology (namespaces, Services,
+//! routes, and ReferenceGrants), the TLS backend with its certificates
+//! and backend TLS policy, and implementation-specific policies that a test
+//! needs before it can run. Every created object carries the ownership
+//! label and registers its deletion with the test's teardown. Objects that
+//! already exist under a fixture's names are used as they are and never
+//! deleted. Namespaces are left in place so the next round does not race
+//! their termination; `cleanup --namespaces` removes them.
+
+#![allow(dead_code)]
+
+use anyhow::{Context, Result};
+use k8s_openapi::api::apps::v1::{Deployment, DeploymentSpec};
+use k8s_openapi::api::core::v1::{
+    ConfigMap, Container, ContainerPort, EnvVar, EnvVarSource, KeyToPath, LocalObjectReference,
+    Namespace, ObjectFieldSelector, PodSpec, PodTemplateSpec, Secret, SecretVolumeSource, Service,
+    ServicePort, ServiceSpec, Volume, VolumeMount,
+};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{LabelSelector, ObjectMeta};
+use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
+use k8s_openapi::ByteString;
+use kube::api::{
+    Api, ApiResource, DeleteParams, DynamicObject, GroupVersionKind, Patch, PatchParams, PostParams,
+};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tracing::{debug, info};
+
+use super::certs::TestCertificates;
+use super::httproute::{HTTPRoute, HTTPRouteBuilder, HTTPRouteManager, RuleBuilder};
+use super::owner::owner_labels;
+use super::referencegrant::ReferenceGrant;
+use super::{DeploymentScaler, K8sClient};
+use crate::utils::Teardown;
+
+/// Echo server answering with the request it received and its pod, namespace
+/// and Service (see `crate::http::EchoResponse`), which the tests match on
+pub const ECHO_IMAGE: &str =
+    "gcr.io/k8s-staging-gateway-api/echo-basic:v20231214-v1.0.0-140-gf544a46e";
+pub const ECHO_PORT: i32 = 3000;
+
+/// Port the echo server serves HTTPS on when given a certificate
+pub const ECHO_TLS_PORT: i32 = 8443;
+
+/// Where the TLS echo backend mounts its serving certificate
+const ECHO_CERT_DIR: &str = "/etc/secret-tls";
+
+/// Replicas of each echo backend, so affinity, hashing and failover have a choice
+const ECHO_REPLICAS: i32 = 2;
+
+/// `imagePullSecrets` of a pod spec pulling with the Secrets `names`
+pub(crate) fn image_pull_secrets(names: &[String]) -> Option<Vec<LocalObjectReference>> {
+    if names.is_empty() {
+        return None;
+    }
+    Some(
+        names
+            .iter()
+            .map(|name| LocalObjectReference {
+                name: Some(name.clone()),
+            })
+            .collect(),
+    )
+}
+
+/// Pull a Deployment's images with the Secrets `names` (config file
+/// `secrets.image_pull_secrets`)
+pub fn set_image_pull_secrets(deployment: &mut Deployment, names: &[String]) {
+    if let Some(pod) = deployment
+        .spec
+        .as_mut()
+        .and_then(|spec| spec.template.spec.as_mut())
+    {
+        pod.image_pull_secrets = image_pull_secrets(names);
+    }
+}
+
+/// Deployment and Service of an echo backend; `labels` are added to both
+pub fn echo_backend(
+    name: &str,
+    namespace: &str,
+    port: u16,
+    mut labels: BTreeMap<String, String>,
+) -> (Deployment, Service) {
+    let selector = BTreeMap::from([("app".to_string(), name.to_string())]);
+    labels.extend(selector.clone());
+    let meta = ObjectMeta {
+        name: Some(name.to_string()),
+        namespace: Some(namespace.to_string()),
+        labels: Some(labels.clone()),
+        ..Default::default()
+    };
+    let field_env = |name: &str, path: &str| EnvVar {
+        name: name.to_string(),
+        value_from: Some(EnvVarSource {
+            field_ref: Some(ObjectFieldSelector {
+                field_path: path.to_string(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let deployment = Deployment {
+        metadata: meta.clone(),
+        spec: Some(DeploymentSpec {
+            replicas: Some(ECHO_REPLICAS),
+            selector: LabelSelector {
+                match_labels: Some(selector.clone()),
+                ..Default::default()
+            },
+            template: PodTemplateSpec {
+                metadata: Some(ObjectMeta {
+                    labels: Some(labels),
+                    ..Default::default()
+                }),
+                spec: Some(PodSpec {
+                    containers: vec![Container {
+                        name: "echo".to_string(),
+                        image: Some(ECHO_IMAGE.to_string()),
+                        ports: Some(vec![ContainerPort {
+                            container_port: ECHO_PORT,
+                            ..Default::default()
+                        }]),
+                        env: Some(vec![
+                            field_env("POD_NAME", "metadata.name"),
+                            field_env("NAMESPACE", "metadata.namespace"),
+                            EnvVar {
+                                name: "SERVICE_NAME".to_string(),
+                                value: Some(name.to_string()),
+                                ..Default::default()
+                            },
+                        ]),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }),
+            },
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let service = Service {
+        metadata: meta,
+        spec: Some(ServiceSpec {
+            selector: Some(selector),
+            ports: Some(vec![ServicePort {
+                port: port as i32,
+                target_port: Some(IntOrString::Int(ECHO_PORT)),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    (deployment, service)
+}
+
+/// Certificate objects of a TLS echo backend, named after its Service
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TlsBackendRefs {
+    /// `kubernetes.io/tls` Secret the backend serves with
+    pub server_secret: String,
+    /// ConfigMap holding the CA under `ca.crt`, for the gateway to verify with
+    pub ca_config_map: String,
+    /// `kubernetes.io/tls` Secret the gateway can present as client certificate
+    pub client_secret: String,
+}
+
+impl TlsBackendRefs {
+    pub fn for_service(service: &str) -> Self {
+        Self {
+            server_secret: format!("{service}-cert"),
+            ca_config_map: format!("{service}-ca"),
+            client_secret: format!("{service}-client"),
+        }
+    }
+}
+
+/// Echo backend serving HTTPS on `port` with the certificate in
+/// [`TlsBackendRefs::server_secret`]; `labels` are added to both objects
+pub fn tls_echo_backend(
+    name: &str,
+    namespace: &str,
+    port: u16,
+    labels: BTreeMap<String, String>,
+) -> (Deployment, Service) {
+    let (mut deployment, mut service) = echo_backend(name, namespace, port, labels);
+    let refs = TlsBackendRefs::for_service(name);
+
+    if let Some(pod) = deployment
+        .spec
+        .as_mut()
+        .and_then(|spec| spec.template.spec.as_mut())
+    {
+        for container in &mut pod.containers {
+            container.ports = Some(vec![ContainerPort {
+                container_port: ECHO_TLS_PORT,
+                ..Default::default()
+            }]);
+            let env = container.env.get_or_insert_with(Vec::new);
+            for (key, file) in [("TLS_SERVER_CERT", "crt"), ("TLS_SERVER_PRIVKEY", "key")] {
+                env.push(EnvVar {
+                    name: key.to_string(),
+                    value: Some(format!("{ECHO_CERT_DIR}/{file}")),
+                    ..Default::default()
+                });
+            }
+            container.volume_mounts = Some(vec![VolumeMount {
+                name: "tls".to_string(),
+                mount_path: ECHO_CERT_DIR.to_string(),
+                read_only: Some(true),
+                ..Default::default()
+            }]);
+        }
+        let item = |key: &str, path: &str| KeyToPath {
+            key: key.to_string(),
+            path: path.to_string(),
+            ..Default::default()
+        };
+        pod.volumes = Some(vec![Volume {
+            name: "tls".to_string(),
+            secret: Some(SecretVolumeSource {
+                secret_name: Some(refs.server_secret),
+                items: Some(vec![item("tls.crt", "crt"), item("tls.key", "key")]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }]);
+    }
+    for service_port in service
+        .spec
+        .iter_mut()
+        .flat_map(|spec| spec.ports.iter_mut().flatten())
+    {
+        service_port.name = Some("https".to_string());
+        service_port.target_port = Some(IntOrString::Int(ECHO_TLS_PORT));
+    }
+    (deployment, service)
+}
+
+/// Route from the gateway to a Service in another namespace
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CrossNamespaceTarget {
+    pub path: String,
+    pub namespace: String,
+    pub service: String,
+}
+
+impl CrossNamespaceTarget {
+    /// Name of the HTTPRoute serving this target
+    pub fn route_name(&self) -> String {
+        format!("cross-ns-{}-{}", self.namespace, self.service)
+    }
+}
+
+/// Namespaces, echo Services, HTTPRoutes and ReferenceGrants of the
+/// cross-namespace test (13)
+///
+/// Routes live in the client's namespace and attach to `gateway_name` there.
+#[derive(Clone)]
+pub struct CrossNamespaceFixture {
+    client: K8sClient,
+    gateway_name: String,
+    port: u16,
+    targets: Vec<CrossNamespaceTarget>,
+    image_pull_secrets: Vec<String>,
+    ready_timeout: Duration,
+}
+
+impl CrossNamespaceFixture {
+    pub fn new(client: K8sClient, gateway_name: impl Into<String>) -> Self {
+        Self {
+            client,
+            gateway_name: gateway_name.into(),
+            port: 8080,
+            targets: Vec::new(),
+            image_pull_secrets: Vec::new(),
+            ready_timeout: Duration::from_secs(120),
+        }
+    }
+
+    /// Route `path` to `service` in `namespace`
+    pub fn with_target(
+        mut self,
+        path: impl Into<String>,
+        namespace: impl Into<String>,
+        service: impl Into<String>,
+    ) -> Self {
+        self.targets.push(CrossNamespaceTarget {
+            path: path.into(),
+            namespace: namespace.into(),
+            service: service.into(),
+        });
+        self
+    }
+
+    /// Service port the routes point at
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    /// Secrets the backends pull their images with
+    pub fn with_image_pull_secrets(mut self, names: Vec<String>) -> Self {
+        self.image_pull_secrets = names;
+        self
+    }
+
+    /// How long to wait for backends and route status
+    pub fn with_ready_timeout(mut self, timeout: Duration) -> Self {
+        self.ready_timeout = timeout;
+        self
+    }
+
+    pub fn targets(&self) -> &[CrossNamespaceTarget] {
+        &self.targets
+    }
+
+    /// Namespace the routes are created in
+    pub fn route_namespace(&self) -> &str {
+        self.client.namespace()
+    }
+
+    /// Target namespaces in first-use order
+    fn target_namespaces(&self) -> Vec<&str> {
+        let mut namespaces: Vec<&str> = Vec::new();
+        for target in &self.targets {
+            if !namespaces.contains(&target.namespace.as_str()) {
+                namespaces.push(&target.namespace);
+            }
+        }
+        namespaces
+    }
+
+    /// Services a grant in `namespace` must allow
+    fn services_in(&self, namespace: &str) -> Vec<&str> {
+        self.targets
+            .iter()
+            .filter(|t| t.namespace == namespace)
+            .map(|t| t.service.as_str())
+            .collect()
+    }
+
+    /// Create everything and wait until the backends are available and the
+    /// routes resolve their cross-namespace references
+    pub async fn setup(&self, teardown: &Teardown) -> Result<()> {
+        let route_namespace = self.route_namespace().to_string();
+        info!(
+            "Setting up cross-namespace fixture: {} target(s) from {}",
+            self.targets.len(),
+            route_namespace
+        );
+
+        for namespace in self.target_namespaces() {
+            self.ensure_namespace(namespace).await?;
+            let grant = ReferenceGrant::for_services(
+                &format!("allow-routes-from-{route_namespace}"),
+                namespace,
+                &route_namespace,
+                &self.services_in(namespace),
+            );
+            self.create_grant(grant, namespace, teardown).await?;
+        }
+
+        for target in &self.targets {
+            self.create_backend(target, teardown).await?;
+            self.create_route(target, teardown).await?;
+        }
+
+        for target in &self.targets {
+            let client = self.client.in_namespace(&target.namespace);
+            DeploymentScaler::new(client)
+                .wait_available(&target.service, ECHO_REPLICAS, self.ready_timeout)
+                .await?;
+        }
+        for target in &self.targets {
+            self.wait_resolved(target).await?;
+        }
+        Ok(())
+    }
+
+    async fn ensure_namespace(&self, name: &str) -> Result<()> {
+        if self.client.namespace_exists(name).await? {
+            return Ok(());
+        }
+        debug!("Creating namespace {}", name);
+        let namespace = Namespace {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                labels: Some(owner_labels()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let api: Api<Namespace> = self.client.cluster_api();
+        match api.create(&PostParams::default(), &namespace).await {
+            Err(e) if !already_exists(&e) => {
+                Err(e).with_context(|| format!("Failed to create namespace {name}"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    async fn create_grant(
+        &self,
+        grant: ReferenceGrant,
+        namespace: &str,
+        teardown: &Teardown,
+    ) -> Result<()> {
+        let name = grant.metadata.name.clone().unwrap_or_default();
+        let api: Api<ReferenceGrant> = Api::namespaced(self.client.client().clone(), namespace);
+        create_if_absent(api, &grant, "ReferenceGrant", &name, teardown).await?;
+        Ok(())
+    }
+
+    async fn create_backend(
+        &self,
+        target: &CrossNamespaceTarget,
+        teardown: &Teardown,
+    ) -> Result<()> {
+        let (mut deployment, service) = echo_backend(
+            &target.service,
+            &target.namespace,
+            self.port,
+            owner_labels(),
+        );
+        set_image_pull_secrets(&mut deployment, &self.image_pull_secrets);
+        let deployments: Api<Deployment> =
+            Api::namespaced(self.client.client().clone(), &target.namespace);
+        let services: Api<Service> =
+            Api::namespaced(self.client.client().clone(), &target.namespace);
+        create_if_absent(
+            deployments,
+            &deployment,
+            "Deployment",
+            &target.service,
+            teardown,
+        )
+        .await?;
+        create_if_absent(services, &service, "Service", &target.service, teardown).await?;
+        Ok(())
+    }
+
+    async fn create_route(&self, target: &CrossNamespaceTarget, teardown: &Teardown) -> Result<()> {
+        let namespace = self.route_namespace();
+        let route = HTTPRouteBuilder::new(target.route_name(), namespace)
+            .parent_ref_namespaced(&self.gateway_name, namespace)
+            .rule(
+                RuleBuilder::new()
+                    .path_prefix(&target.path)
+                    .backend_in_namespace(&target.service, &target.namespace, self.port)
+                    .build(),
+            )
+            .build();
+        create_route(&self.client, &route, teardown).await
+    }
+
+    /// Wait for the gateway to report ResolvedRefs=True on a target's route
+    async fn wait_resolved(&self, target: &CrossNamespaceTarget) -> Result<()> {
+        wait_route_resolved(
+            &self.client,
+            &target.route_name(),
+            self.route_namespace(),
+            self.ready_timeout,
+        )
+        .await
+    }
+}
+
+/// TLS echo backend of the backend TLS test (6), with a throwaway CA, its
+/// certificates, an HTTPRoute to the backend, and the implementation's
+/// backend TLS policy
+///
+/// Everything lives in the client's namespace; the route attaches to
+/// `gateway_name` there.
+#[derive(Clone)]
+pub struct BackendTlsFixture {
+    client: K8sClient,
+    gateway_name: String,
+    service: String,
+    hostname: String,
+    path: String,
+    port: u16,
+    policy: Option<serde_json::Value>,
+    image_pull_secrets: Vec<String>,
+    ready_timeout: Duration,
+}
+
+impl BackendTlsFixture {
+    /// Backend `service` serving a certificate for `hostname`
+    pub fn new(
+        client: K8sClient,
+        gateway_name: impl Into<String>,
+        service: impl Into<String>,
+        hostname: impl Into<String>,
+    ) -> Self {
+        Self {
+            client,
+            gateway_name: gateway_name.into(),
+            service: service.into(),
+            hostname: hostname.into(),
+            path: "/".to_string(),
+            port: 443,
+            policy: None,
+            image_pull_secrets: Vec::new(),
+            ready_timeout: Duration::from_secs(120),
+        }
+    }
+
+    /// Path prefix routed to the backend
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    /// Service port the route points at
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    /// Backend TLS policy to apply (any policy manifest, e.g. from
+    /// `ManifestGenerator::backend_tls_policy`)
+    pub fn with_policy<T: Serialize>(mut self, policy: &T) -> Self {
+        self.policy = serde_json::to_value(policy).ok();
+        self
+    }
+
+    /// Secrets the backend pulls its image with
+    pub fn with_image_pull_secrets(mut self, names: Vec<String>) -> Self {
+        self.image_pull_secrets = names;
+        self
+    }
+
+    /// How long to wait for the backend and route status
+    pub fn with_ready_timeout(mut self, timeout: Duration) -> Self {
+        self.ready_timeout = timeout;
+        self
+    }
+
+    /// Secrets and ConfigMap the fixture creates
+    pub fn refs(&self) -> TlsBackendRefs {
+        TlsBackendRefs::for_service(&self.service)
+    }
+
+    /// Name of the HTTPRoute to the backend
+    pub fn route_name(&self) -> String {
+        self.service.clone()
+    }
+
+    /// Create everything and wait until the backend is available and the
+    /// route resolves it
+    pub async fn setup(&self, teardown: &Teardown) -> Result<()> {
+        let policy = self
+            .policy
+            .as_ref()
+            .context("No backend TLS policy to apply")?;
+        let namespace = self.client.namespace().to_string();
+        info!(
+            "Setting up backend TLS fixture: {}/{} ({})",
+            namespace, self.service, self.hostname
+        );
+
+        let certs = TestCertificates::generate(&self.hostname).await?;
+        let refs = self.refs();
+        self.create_tls_secret(
+            &refs.server_secret,
+            &certs.server_cert,
+            &certs.server_key,
+            teardown,
+        )
+        .await?;
+        self.create_tls_secret(
+            &refs.client_secret,
+            &certs.client_cert,
+            &certs.client_key,
+            teardown,
+        )
+        .await?;
+        self.create_ca(&refs.ca_config_map, &certs.ca_cert, teardown)
+            .await?;
+        self.create_backend(teardown).await?;
+        create_manifest(&self.client, policy, teardown).await?;
+
+        let route = HTTPRouteBuilder::new(self.route_name(), &namespace)
+            .parent_ref_namespaced(&self.gateway_name, &namespace)
+            .rule(
+                RuleBuilder::new()
+                    .path_prefix(&self.path)
+                    .backend(&self.service, self.port)
+                    .build(),
+            )
+            .build();
+        create_route(&self.client, &route, teardown).await?;
+
+        DeploymentScaler::new(self.client.clone())
+            .wait_available(&self.service, ECHO_REPLICAS, self.ready_timeout)
+            .await?;
+        wait_route_resolved(
+            &self.client,
+            &self.route_name(),
+            &namespace,
+            self.ready_timeout,
+        )
+        .await
+    }
+
+    async fn create_tls_secret(
+        &self,
+        name: &str,
+        cert: &str,
+        key: &str,
+        teardown: &Teardown,
+    ) -> Result<()> {
+        let secret = Secret {
+            metadata: self.metadata(name),
+            type_: Some("kubernetes.io/tls".to_string()),
+            data: Some(BTreeMap::from([
+                ("tls.crt".to_string(), ByteString(cert.as_bytes().to_vec())),
+                ("tls.key".to_string(), ByteString(key.as_bytes().to_vec())),
+            ])),
+            ..Default::default()
+        };
+        let api: Api<Secret> = self.client.namespaced_api();
+        create_if_absent(api, &secret, "Secret", name, teardown).await?;
+        Ok(())
+    }
+
+    async fn create_ca(&self, name: &str, ca_cert: &str, teardown: &Teardown) -> Result<()> {
+        let config_map = ConfigMap {
+            metadata: self.metadata(name),
+            data: Some(BTreeMap::from([(
+                "ca.crt".to_string(),
+                ca_cert.to_string(),
+            )])),
+            ..Default::default()
+        };
+        let api: Api<ConfigMap> = self.client.namespaced_api();
+        create_if_absent(api, &config_map, "ConfigMap", name, teardown).await?;
+        Ok(())
+    }
+
+    async fn create_backend(&self, teardown: &Teardown) -> Result<()> {
+        let (mut deployment, service) = tls_echo_backend(
+            &self.service,
+            self.client.namespace(),
+            self.port,
+            owner_labels(),
+        );
+        set_image_pull_secrets(&mut deployment, &self.image_pull_secrets);
+        let deployments: Api<Deployment> = self.client.namespaced_api();
+        let services: Api<Service> = self.client.namespaced_api();
+        create_if_absent(
+            deployments,
+            &deployment,
+            "Deployment",
+            &self.service,
+            teardown,
+        )
+        .await?;
+        create_if_absent(services, &service, "Service", &self.service, teardown).await?;
+        Ok(())
+    }
+
+    fn metadata(&self, name: &str) -> ObjectMeta {
+        ObjectMeta {
+            name: Some(name.to_string()),
+            namespace: Some(self.client.namespace().to_string()),
+            labels: Some(owner_labels()),
+            ..Default::default()
+        }
+    }
+}
+
+/// Implementation-specific policies for a test, e.g. the rate-limit policy of
+/// the rate limiting test (8), applied with the route they govern
+///
+/// The manifests (any kind, e.g. from `ManifestGenerator`) are created as
+/// given in the client's namespace unless an object of that name exists.
+#[derive(Clone)]
+pub struct PolicyFixture {
+    client: K8sClient,
+    manifests: Vec<serde_json::Value>,
+    /// (Service, annotation, value) attaching a policy to an existing Service
+    annotations: Vec<(String, String, String)>,
+    route_name: Option<String>,
+    ready_timeout: Duration,
+    settle_time: Duration,
+}
+
+impl PolicyFixture {
+    pub fn new(client: K8sClient) -> Self {
+        Self {
+            client,
+            manifests: Vec::new(),
+            annotations: Vec::new(),
+            route_name: None,
+            ready_timeout: Duration::from_secs(60),
+            settle_time: Duration::from_secs(3),
+        }
+    }
+
+    /// Add a manifest to apply, in order
+    pub fn with_manifest<T: Serialize>(mut self, manifest: &T) -> Self {
+        self.manifests.extend(serde_json::to_value(manifest).ok());
+        self
+    }
+
+    /// Annotate an existing Service (e.g. to attach a Kong upstream policy);
+    /// the previous value, or its absence, is restored at teardown
+    pub fn with_service_annotation(
+        mut self,
+        service: impl Into<String>,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        self.annotations
+            .push((service.into(), key.into(), value.into()));
+        self
+    }
+
+    /// HTTPRoute (one of the manifests) to wait for before the test
+    pub fn with_route(mut self, name: impl Into<String>) -> Self {
+        self.route_name = Some(name.into());
+        self
+    }
+
+    /// How long to wait for the route status
+    pub fn with_ready_timeout(mut self, timeout: Duration) -> Self {
+        self.ready_timeout = timeout;
+        self
+    }
+
+    /// Time the data plane gets to pick up the policies once the route resolves
+    pub fn with_settle_time(mut self, settle_time: Duration) -> Self {
+        self.settle_time = settle_time;
+        self
+    }
+
+    /// Kinds and names of the manifests, for test details
+    pub fn describe(&self) -> Vec<String> {
+        self.manifests
+            .iter()
+            .map(|m| {
+                format!(
+                    "{} {}",
+                    m["kind"].as_str().unwrap_or("?"),
+                    m["metadata"]["name"].as_str().unwrap_or("?")
+                )
+            })
+            .chain(
+                self.annotations
+                    .iter()
+                    .map(|(service, key, _)| format!("Service {service} annotation {key}")),
+            )
+            .collect()
+    }
+
+    /// Apply every manifest and wait until the route resolves
+    pub async fn setup(&self, teardown: &Teardown) -> Result<()> {
+        info!(
+            "Applying {} in {}",
+            self.describe().join(", "),
+            self.client.namespace()
+        );
+        for manifest in &self.manifests {
+            create_manifest(&self.client, manifest, teardown).await?;
+        }
+        for (service, key, value) in &self.annotations {
+            self.annotate_service(service, key, value, teardown).await?;
+        }
+        if let Some(route) = &self.route_name {
+            wait_route_resolved(
+                &self.client,
+                route,
+                self.client.namespace(),
+                self.ready_timeout,
+            )
+            .await?;
+        }
+        tokio::time::sleep(self.settle_time).await;
+        Ok(())
+    }
+
+    async fn annotate_service(
+        &self,
+        service: &str,
+        key: &str,
+        value: &str,
+        teardown: &Teardown,
+    ) -> Result<()> {
+        let annotate = |value: serde_json::Value| {
+            Patch::Merge(serde_json::json!({ "metadata": { "annotations": { key: value } } }))
+        };
+        let api: Api<Service> = self.client.namespaced_api();
+        let previous = api
+            .get(service)
+            .await
+            .with_context(|| format!("Failed to read service {service}"))?
+            .metadata
+            .annotations
+            .and_then(|mut annotations| annotations.remove(key));
+        if previous.as_deref() == Some(value) {
+            // Already attached, e.g. by hand: nothing to change or undo
+            return Ok(());
+        }
+        api.patch(service, &PatchParams::default(), &annotate(value.into()))
+            .await
+            .with_context(|| format!("Failed to annotate service {service}"))?;
+
+        let description = match &previous {
+            Some(_) => format!("restore annotation {key} on Service {service}"),
+            None => format!("remove annotation {key} from Service {service}"),
+        };
+        let patch = annotate(previous.map_or(serde_json::Value::Null, serde_json::Value::from));
+        let service = service.to_string();
+        teardown.register(description, move || {
+            let (api, patch, service) = (api.clone(), patch.clone(), service.clone());
+            async move {
+                api.patch(&service, &PatchParams::default(), &patch)
+                    .await
+                    .with_context(|| format!("Failed to restore service {service}"))?;
+                Ok(())
+            }
+        });
+        Ok(())
+    }
+}
+
+/// Create a manifest of any kind in the client's namespace unless it exists,
+/// registering its deletion if created
+async fn create_manifest(
+    client: &K8sClient,
+    manifest: &serde_json::Value,
+    teardown: &Teardown,
+) -> Result<()> {
+    let mut object: DynamicObject =
+        serde_json::from_value(manifest.clone()).context("Invalid manifest")?;
+    let types = object
+        .types
+        .clone()
+        .context("Manifest has no apiVersion/kind")?;
+    let (group, version) = types
+        .api_version
+        .split_once('/')
+        .unwrap_or(("", &types.api_version));
+    let resource = ApiResource::from_gvk(&GroupVersionKind::gvk(group, version, &types.kind));
+    let name = object
+        .metadata
+        .name
+        .clone()
+        .with_context(|| format!("{} manifest has no name", types.kind))?;
+    object.metadata.namespace = Some(client.namespace().to_string());
+
+    let api: Api<DynamicObject> =
+        Api::namespaced_with(client.client().clone(), client.namespace(), &resource);
+    create_if_absent(api, &object, &types.kind, &name, teardown).await?;
+    Ok(())
+}
+
+/// Create an object unless one of that name exists, registering its
+/// deletion only when created; returns whether it was created
+///
+/// An existing object, e.g. one deployed by hand, is used as it is and
+/// left in place afterwards.
+async fn create_if_absent<K>(
+    api: Api<K>,
+    object: &K,
+    kind: &str,
+    name: &str,
+    teardown: &Teardown,
+) -> Result<bool>
+where
+    K: Clone + Serialize + serde::de::DeserializeOwned + std::fmt::Debug + Send + Sync + 'static,
+{
+    match api.create(&PostParams::default(), object).await {
+        Ok(_) => {
+            register_delete(teardown, api, kind, name);
+            Ok(true)
+        }
+        Err(e) if already_exists(&e) => {
+            info!("Using existing {} {}", kind, name);
+            Ok(false)
+        }
+        Err(e) => Err(e).with_context(|| format!("Failed to create {kind} {name}")),
+    }
+}
+
+/// Whether an API error reports that the object already exists
+fn already_exists(error: &kube::Error) -> bool {
+    matches!(error, kube::Error::Api(response) if response.code == 409)
+}
+
+/// Register deletion of a namespaced object through its API
+fn register_delete<K>(teardown: &Teardown, api: Api<K>, kind: &str, name: &str)
+where
+    K: Clone + serde::de::DeserializeOwned + std::fmt::Debug + Send + Sync + 'static,
+{
+    let (kind, name) = (kind.to_string(), name.to_string());
+    teardown.register(format!("delete {kind} {name}"), move || {
+        let (api, kind, name) = (api.clone(), kind.clone(), name.clone());
+        async move {
+            api.delete(&name, &DeleteParams::default())
+                .await
+                .with_context(|| format!("Failed to delete {kind} {name}"))?;
+            Ok(())
+        }
+    });
+}
+
+/// Create an HTTPRoute unless it exists, registering its deletion if created
+async fn create_route(client: &K8sClient, route: &HTTPRoute, teardown: &Teardown) -> Result<()> {
+    let name = route.metadata.name.clone().unwrap_or_default();
+    let namespace = route
+        .metadata
+        .namespace
+        .clone()
+        .unwrap_or_else(|| client.namespace().to_string());
+    let api: Api<HTTPRoute> = Api::namespaced(client.client().clone(), &namespace);
+    create_if_absent(api, route, "HTTPRoute", &name, teardown).await?;
+    Ok(())
+}
+
+/// Wait for the gateway to report ResolvedRefs=True on a route
+async fn wait_route_resolved(
+    client: &K8sClient,
+    name: &str,
+    namespace: &str,
+    timeout: Duration,
+) -> Result<()> {
+    let routes = HTTPRouteManager::new(client.clone());
+    let start = std::time::Instant::now();
+    loop {
+        let route = routes.get(name, namespace).await?;
+        let condition = route
+            .status
+            .iter()
+            .flat_map(|s| &s.parents)
+            .flat_map(|p| &p.conditions)
+            .find(|c| c.condition_type == "ResolvedRefs");
+        match condition {
+            Some(c) if c.status == "True" => return Ok(()),
+            Some(c) if start.elapsed() >= timeout => anyhow::bail!(
+                "HTTPRoute {name} references not resolved: {}",
+                c.reason.as_deref().unwrap_or("unknown reason")
+            ),
+            None if start.elapsed() >= timeout => {
+                anyhow::bail!("HTTPRoute {name} has no status from the gateway")
+            }
+            _ => tokio::time::sleep(Duration::from_secs(1)).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_echo_backend() {
+        let (deployment, service) = echo_backend("service-a", "namespace-a", 8080, owner_labels());
+        assert_eq!(
+            deployment.metadata.namespace.as_deref(),
+            Some("namespace-a")
+        );
+        assert!(crate::k8s::is_managed(deployment.metadata.labels.as_ref()));
+        let container = &deployment.spec.unwrap().template.spec.unwrap().containers[0];
+        let env = container.env.as_ref().unwrap();
+        assert!(env
+            .iter()
+            .any(|e| e.name == "SERVICE_NAME" && e.value.as_deref() == Some("service-a")));
+
+        let spec = service.spec.unwrap();
+        assert_eq!(
+            spec.selector.unwrap().get("app").map(String::as_str),
+            Some("service-a")
+        );
+        let port = &spec.ports.unwrap()[0];
+        assert_eq!(port.port, 8080);
+        assert_eq!(port.target_port, Some(IntOrString::Int(ECHO_PORT)));
+    }
+
+    #[test]
+    fn test_image_pull_secrets() {
+        let (mut deployment, _) = echo_backend("service-a", "namespace-a", 8080, owner_labels());
+        set_image_pull_secrets(&mut deployment, &["regcred".to_string()]);
+        let pod = deployment.spec.unwrap().template.spec.unwrap();
+        assert_eq!(
+            pod.image_pull_secrets.unwrap()[0].name.as_deref(),
+            Some("regcred")
+        );
+        assert!(image_pull_secrets(&[]).is_none());
+    }
+
+    #[test]
+    fn test_tls_echo_backend() {
+        let (deployment, service) =
+            tls_echo_backend("tls-backend", "gw-tests", 443, owner_labels());
+        let pod = deployment.spec.unwrap().template.spec.unwrap();
+        let volume = &pod.volumes.unwrap()[0];
+        assert_eq!(
+            volume.secret.as_ref().unwrap().secret_name.as_deref(),
+            Some("tls-backend-cert")
+        );
+        let env = pod.containers[0].env.as_ref().unwrap();
+        assert!(env.iter().any(|e| e.name == "TLS_SERVER_CERT"));
+
+        let port = &service.spec.unwrap().ports.unwrap()[0];
+        assert_eq!(port.port, 443);
+        assert_eq!(port.target_port, Some(IntOrString::Int(ECHO_TLS_PORT)));
+    }
+
+    #[test]
+    fn test_cross_namespace_target_route_name() {
+        let target = CrossNamespaceTarget {
+            path: "/ns-a".to_string(),
+            namespace: "namespace-a".to_string(),
+            service: "service-a".to_string(),
+        };
+        assert_eq!(target.route_name(), "cross-ns-namespace-a-service-a");
+    }
+}
@@ -5,11 +5,13 @@
 #![allow(dead_code)]
 
 use anyhow::{Context, Result};
-use kube::api::{Api, ListParams, PostParams};
+use kube::api::{Api, ListParams, Patch, PatchParams, PostParams};
 use kube::CustomResource;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
+use super::owner::owner_labels;
 use super::K8sClient;
 
 /// HTTPRoute custom resource specification
@@ -326,6 +328,7 @@ pub struct HTTPRouteBuilder {
     parent_refs: Vec<ParentRef>,
     hostnames: Vec<String>,
     rules: Vec<HTTPRouteRule>,
+    labels: BTreeMap<String, String>,
 }
 
 impl HTTPRouteBuilder {
@@ -337,9 +340,16 @@ impl HTTPRouteBuilder {
             parent_refs: Vec::new(),
             hostnames: Vec::new(),
             rules: Vec::new(),
+            labels: owner_labels(),
         }
     }
 
+    /// Add a label (the ownership label is always set)
+    pub fn label(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.labels.insert(key.into(), value.into());
+        self
+    }
+
     /// Add parent reference (gateway)
     pub fn parent_ref(mut self, gateway_name: impl Into<String>) -> Self {
         self.parent_refs.push(ParentRef {
@@ -377,14 +387,16 @@ impl HTTPRouteBuilder {
 
     /// Build the HTTPRoute
     pub fn build(self) -> HTTPRoute {
-        HTTPRoute::new(
+        let mut route = HTTPRoute::new(
             &self.name,
             HTTPRouteSpec {
                 parent_refs: self.parent_refs,
                 hostnames: self.hostnames,
                 rules: self.rules,
             },
-        )
+        );
+        route.metadata.labels = Some(self.labels);
+        route
     }
 }
 
@@ -466,6 +478,22 @@ impl RuleBuilder {
         self
     }
 
+    /// Add a backend Service in another namespace (needs a ReferenceGrant there)
+    pub fn backend_in_namespace(
+        mut self,
+        name: impl Into<String>,
+        namespace: impl Into<String>,
+        port: u16,
+    ) -> Self {
+        self.backend_refs.push(HTTPBackendRef {
+            name: name.into(),
+            port: Some(port),
+            namespace: Some(namespace.into()),
+            ..Default::default()
+        });
+        self
+    }
+
     /// Add backend with weight
     pub fn backend_with_weight(mut self, name: impl Into<String>, port: u16, weight: i32) -> Self {
         self.backend_refs.push(HTTPBackendRef {
@@ -491,6 +519,22 @@ impl RuleBuilder {
         self
     }
 
+    /// Set a request header before forwarding to the backend
+    pub fn set_request_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.filters.push(HTTPRouteFilter {
+            filter_type: "RequestHeaderModifier".to_string(),
+            request_header_modifier: Some(HTTPHeaderModifier {
+                set: vec![HTTPHeader {
+                    name: name.into(),
+                    value: value.into(),
+                }],
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        self
+    }
+
     /// Add URL rewrite
     pub fn url_rewrite(mut self, path: impl Into<String>) -> Self {
         self.filters.push(HTTPRouteFilter {
@@ -508,6 +552,19 @@ impl RuleBuilder {
         self
     }
 
+    /// Rewrite the Host header sent to the backend
+    pub fn hostname_rewrite(mut self, hostname: impl Into<String>) -> Self {
+        self.filters.push(HTTPRouteFilter {
+            filter_type: "URLRewrite".to_string(),
+            url_rewrite: Some(HTTPURLRewrite {
+                hostname: Some(hostname.into()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        self
+    }
+
     /// Set request timeout
     pub fn timeout(mut self, duration: impl Into<String>) -> Self {
         self.timeouts = Some(HTTPRouteTimeouts {
@@ -555,6 +612,52 @@ impl HTTPRouteManager {
             .context("Failed to create HTTPRoute")
     }
 
+    /// Create or update a route via server-side apply
+    pub async fn apply(&self, route: &HTTPRoute, namespace: &str) -> Result<HTTPRoute> {
+        let name = route
+            .metadata
+            .name
+            .as_deref()
+            .context("HTTPRoute has no name")?;
+        let api = self.api(namespace);
+        api.patch(
+            name,
+            &PatchParams::apply("gateway-poc").force(),
+            &Patch::Apply(route),
+        )
+        .await
+        .with_context(|| format!("Failed to apply HTTPRoute {name}"))
+    }
+
+    /// Set backend weights on one rule of an existing route
+    pub async fn set_weights(
+        &self,
+        name: &str,
+        namespace: &str,
+        rule: usize,
+        weights: &[(&str, i32)],
+    ) -> Result<HTTPRoute> {
+        let mut route = self.get(name, namespace).await?;
+        let rule_ref = route
+            .spec
+            .rules
+            .get_mut(rule)
+            .with_context(|| format!("HTTPRoute {name} has no rule {rule}"))?;
+        for (backend, weight) in weights {
+            let backend_ref = rule_ref
+                .backend_refs
+                .iter_mut()
+                .find(|b| b.name == *backend)
+                .with_context(|| format!("Backend {backend} not found in rule {rule} of {name}"))?;
+            backend_ref.weight = Some(*weight);
+        }
+
+        let api = self.api(namespace);
+        api.replace(name, &PostParams::default(), &route)
+            .await
+            .with_context(|| format!("Failed to update weights on HTTPRoute {name}"))
+    }
+
     pub async fn get(&self, name: &str, namespace: &str) -> Result<HTTPRoute> {
         let api = self.api(namespace);
         api.get(name).await.context("Failed to get HTTPRoute")
@@ -596,6 +699,7 @@ mod tests {
             .build();
 
         assert_eq!(route.metadata.name, Some("test-route".to_string()));
+        assert!(crate::k8s::is_managed(route.metadata.labels.as_ref()));
         assert_eq!(route.spec.hostnames, vec!["example.com"]);
     }
 
@@ -609,5 +713,12 @@ mod tests {
 
         assert_eq!(rule.backend_refs.len(), 2);
         assert_eq!(rule.backend_refs[0].weight, Some(90));
+
+        let rule = RuleBuilder::new()
+            .set_request_header("X-Probe", "1")
+            .build();
+        let modifier = rule.filters[0].request_header_modifier.as_ref().unwrap();
+        assert_eq!(rule.filters[0].filter_type, "RequestHeaderModifier");
+        assert_eq!(modifier.set[0].name, "X-Probe");
     }
 }
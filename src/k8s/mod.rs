@@ -2,9 +2,38 @@
 //!
 //! Provides Kubernetes resource management for Gateway API testing.
 
+mod certs;
 mod client;
+mod cluster;
+mod crash;
+mod deployment;
+mod diagnostics;
+mod fault;
+mod fixture;
 mod gateway;
 mod httproute;
+mod owner;
 mod pod;
+mod referencegrant;
+mod resources;
+mod rollout;
+mod secret;
+mod throttle;
 
 pub use client::K8sClient;
+pub use cluster::ClusterProbe;
+pub use crash::{CrashWatcher, CrashWindow};
+pub use deployment::DeploymentScaler;
+pub use diagnostics::DiagnosticsCollector;
+pub use fault::FaultInjector;
+pub use fixture::{
+    echo_backend, set_image_pull_secrets, tls_echo_backend, BackendTlsFixture,
+    CrossNamespaceFixture, PolicyFixture, TlsBackendRefs,
+};
+pub use httproute::{HTTPRoute, HTTPRouteBuilder, HTTPRouteManager, RuleBuilder};
+pub use owner::{gateway_selector, is_managed, managed_selector, owner_labels, GATEWAY_LABEL};
+pub use pod::{PodManager, TestPodConfig};
+pub use resources::ResourceCollector;
+pub use rollout::{RolloutRestarter, Workload};
+pub use secret::{resolve_secret, resolve_secret_to_file, SecretFile};
+pub use throttle::{acquire_api_permit, set_api_rate_limit, throttle_stats, ApiRateLimit};
@@ -0,0 +1,66 @@
+//! Ownership labels
+//!
+//! Everything the tool creates (VMs, test and load-generator pods, HTTPRoutes,
+//! generated manifests) carries `app.kubernetes.io/managed-by=gateway-poc`.
+//! Bulk cleanup selects on that label only, so it never touches workloads
+//! that merely share the namespace or an `app` label. Resources made for one
+//! implementation also carry [`GATEWAY_LABEL`] so they can be cleaned up per
+//! gateway.
+
+use std::collections::BTreeMap;
+
+/// Standard label naming the tool that manages a resource
+pub const MANAGED_BY_LABEL: &str = "app.kubernetes.io/managed-by";
+
+/// Value of [`MANAGED_BY_LABEL`] on resources this tool created
+pub const MANAGED_BY: &str = "gateway-poc";
+
+/// Gateway implementation (short name) a resource was created for
+pub const GATEWAY_LABEL: &str = "gateway-poc.io/gateway";
+
+/// Labels marking a resource as created by this tool
+pub fn owner_labels() -> BTreeMap<String, String> {
+    BTreeMap::from([(MANAGED_BY_LABEL.to_string(), MANAGED_BY.to_string())])
+}
+
+/// Label selector matching resources this tool created
+pub fn managed_selector() -> String {
+    format!("{MANAGED_BY_LABEL}={MANAGED_BY}")
+}
+
+/// Label selector matching resources this tool created for one gateway
+pub fn gateway_selector(gateway: &str) -> String {
+    format!("{},{GATEWAY_LABEL}={gateway}", managed_selector())
+}
+
+/// Whether a resource's labels mark it as created by this tool
+pub fn is_managed(labels: Option<&BTreeMap<String, String>>) -> bool {
+    labels
+        .and_then(|l| l.get(MANAGED_BY_LABEL))
+        .is_some_and(|v| v == MANAGED_BY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_managed() {
+        assert!(is_managed(Some(&owner_labels())));
+        assert!(!is_managed(None));
+
+        let foreign = BTreeMap::from([
+            ("app".to_string(), "gateway-test".to_string()),
+            (MANAGED_BY_LABEL.to_string(), "Helm".to_string()),
+        ]);
+        assert!(!is_managed(Some(&foreign)));
+        assert_eq!(
+            managed_selector(),
+            "app.kubernetes.io/managed-by=gateway-poc"
+        );
+        assert_eq!(
+            gateway_selector("envoy"),
+            "app.kubernetes.io/managed-by=gateway-poc,gateway-poc.io/gateway=envoy"
+        );
+    }
+}
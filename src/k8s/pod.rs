@@ -13,9 +13,11 @@ use std::collections::{BTreeMap, HashMap};
 use std::time::Duration;
 use tracing::debug;
 
+use super::owner::owner_labels;
 use super::K8sClient;
 
 /// Pod manager for test operations
+#[derive(Clone)]
 pub struct PodManager {
     client: K8sClient,
 }
@@ -46,6 +48,7 @@ impl PodManager {
                     ..Default::default()
                 }],
                 restart_policy: Some("Never".to_string()),
+                image_pull_secrets: super::fixture::image_pull_secrets(&config.image_pull_secrets),
                 ..Default::default()
             }),
             ..Default::default()
@@ -193,11 +196,13 @@ pub struct TestPodConfig {
     pub namespace: String,
     pub image: String,
     pub labels: BTreeMap<String, String>,
+    /// Secrets the pod pulls its image with
+    pub image_pull_secrets: Vec<String>,
 }
 
 impl TestPodConfig {
     pub fn new(name: impl Into<String>, namespace: impl Into<String>) -> Self {
-        let mut labels = BTreeMap::new();
+        let mut labels = owner_labels();
         labels.insert("app".to_string(), "gateway-test".to_string());
 
         Self {
@@ -205,6 +210,7 @@ impl TestPodConfig {
             namespace: namespace.into(),
             image: "curlimages/curl:latest".to_string(),
             labels,
+            image_pull_secrets: Vec::new(),
         }
     }
 
@@ -217,6 +223,11 @@ impl TestPodConfig {
         self.labels.insert(key.into(), value.into());
         self
     }
+
+    pub fn with_image_pull_secrets(mut self, names: Vec<String>) -> Self {
+        self.image_pull_secrets = names;
+        self
+    }
 }
 
 /// Curl command options
@@ -0,0 +1,140 @@
+//! ReferenceGrant resource management
+//!
+//! Lets routes reference Services in other namespaces (cross-namespace
+//! routing needs a grant in the Service's namespace).
+
+#![allow(dead_code)]
+
+use anyhow::{Context, Result};
+use kube::api::{Api, DeleteParams, Patch, PatchParams};
+use kube::CustomResource;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::owner::owner_labels;
+use super::K8sClient;
+
+/// ReferenceGrant custom resource specification
+#[derive(CustomResource, Clone, Debug, Serialize, Deserialize, Default, JsonSchema)]
+#[kube(
+    group = "gateway.networking.k8s.io",
+    version = "v1beta1",
+    kind = "ReferenceGrant",
+    namespaced
+)]
+pub struct ReferenceGrantSpec {
+    /// Resources in other namespaces allowed to make references
+    pub from: Vec<ReferenceGrantFrom>,
+
+    /// Resources in this namespace that may be referenced
+    pub to: Vec<ReferenceGrantTo>,
+}
+
+/// Referencing resource
+#[derive(Clone, Debug, Serialize, Deserialize, Default, JsonSchema)]
+pub struct ReferenceGrantFrom {
+    pub group: String,
+    pub kind: String,
+    pub namespace: String,
+}
+
+/// Referenced resource
+#[derive(Clone, Debug, Serialize, Deserialize, Default, JsonSchema)]
+pub struct ReferenceGrantTo {
+    pub group: String,
+    pub kind: String,
+
+    /// Name of the resource (None = all of this kind)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+impl ReferenceGrant {
+    /// Grant letting HTTPRoutes in `route_namespace` reference the named Services
+    pub fn for_services(
+        name: &str,
+        namespace: &str,
+        route_namespace: &str,
+        services: &[&str],
+    ) -> Self {
+        let mut grant = ReferenceGrant::new(
+            name,
+            ReferenceGrantSpec {
+                from: vec![ReferenceGrantFrom {
+                    group: "gateway.networking.k8s.io".to_string(),
+                    kind: "HTTPRoute".to_string(),
+                    namespace: route_namespace.to_string(),
+                }],
+                to: services
+                    .iter()
+                    .map(|service| ReferenceGrantTo {
+                        group: String::new(),
+                        kind: "Service".to_string(),
+                        name: Some(service.to_string()),
+                    })
+                    .collect(),
+            },
+        );
+        grant.metadata.namespace = Some(namespace.to_string());
+        grant.metadata.labels = Some(owner_labels());
+        grant
+    }
+}
+
+/// ReferenceGrant manager
+pub struct ReferenceGrantManager {
+    client: K8sClient,
+}
+
+impl ReferenceGrantManager {
+    pub fn new(client: K8sClient) -> Self {
+        Self { client }
+    }
+
+    fn api(&self, namespace: &str) -> Api<ReferenceGrant> {
+        Api::namespaced(self.client.client().clone(), namespace)
+    }
+
+    /// Create or update a grant via server-side apply
+    pub async fn apply(&self, grant: &ReferenceGrant, namespace: &str) -> Result<ReferenceGrant> {
+        let name = grant
+            .metadata
+            .name
+            .as_deref()
+            .context("ReferenceGrant has no name")?;
+        self.api(namespace)
+            .patch(
+                name,
+                &PatchParams::apply("gateway-poc").force(),
+                &Patch::Apply(grant),
+            )
+            .await
+            .with_context(|| format!("Failed to apply ReferenceGrant {name}"))
+    }
+
+    pub async fn delete(&self, name: &str, namespace: &str) -> Result<()> {
+        self.api(namespace)
+            .delete(name, &DeleteParams::default())
+            .await
+            .with_context(|| format!("Failed to delete ReferenceGrant {name}"))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grant_for_services() {
+        let grant = ReferenceGrant::for_services("allow", "namespace-a", "default", &["service-a"]);
+        assert_eq!(grant.metadata.namespace.as_deref(), Some("namespace-a"));
+        assert!(crate::k8s::is_managed(grant.metadata.labels.as_ref()));
+        assert_eq!(grant.spec.from[0].namespace, "default");
+        assert_eq!(grant.spec.to[0].name.as_deref(), Some("service-a"));
+
+        let json = serde_json::to_value(&grant).unwrap();
+        assert_eq!(json["apiVersion"], "gateway.networking.k8s.io/v1beta1");
+        assert_eq!(json["spec"]["to"][0]["group"], "");
+    }
+}
@@ -0,0 +1,198 @@
+//! Gateway pod resource collection
+//!
+//! Polls metrics-server (`metrics.k8s.io`) for the CPU and memory of the
+//! gateway's pods while a benchmark runs.
+
+#![allow(dead_code)]
+
+use anyhow::{Context, Result};
+use kube::api::{Api, ApiResource, DynamicObject, GroupVersionKind, ListParams};
+use std::time::{Duration, Instant};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+
+use super::K8sClient;
+use crate::models::{ResourceSample, ResourceUsage};
+
+/// Default interval between samples (metrics-server resolution is ~15s)
+pub const DEFAULT_SAMPLE_INTERVAL_SECS: u64 = 5;
+
+/// Samples CPU/memory of pods matching a label selector
+#[derive(Clone)]
+pub struct ResourceCollector {
+    client: K8sClient,
+    selector: String,
+    namespace: Option<String>,
+    interval: Duration,
+}
+
+impl ResourceCollector {
+    /// Collect from pods matching `selector` in all namespaces
+    pub fn new(client: K8sClient, selector: impl Into<String>) -> Self {
+        Self {
+            client,
+            selector: selector.into(),
+            namespace: None,
+            interval: Duration::from_secs(DEFAULT_SAMPLE_INTERVAL_SECS),
+        }
+    }
+
+    /// Restrict collection to one namespace
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    /// Set the sampling interval
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    fn api(&self) -> Api<DynamicObject> {
+        let gvk = GroupVersionKind::gvk("metrics.k8s.io", "v1beta1", "PodMetrics");
+        let resource = ApiResource::from_gvk_with_plural(&gvk, "pods");
+        let client = self.client.client().clone();
+        match &self.namespace {
+            Some(ns) => Api::namespaced_with(client, ns, &resource),
+            None => Api::all_with(client, &resource),
+        }
+    }
+
+    /// Take one sample summed over all matching pods
+    pub async fn sample(&self) -> Result<ResourceSample> {
+        let list = self
+            .api()
+            .list(&ListParams::default().labels(&self.selector))
+            .await
+            .context("Failed to query metrics.k8s.io (is metrics-server installed?)")?;
+
+        let mut sample = ResourceSample {
+            pods: list.items.len(),
+            ..Default::default()
+        };
+        for pod in &list.items {
+            let containers = pod.data["containers"]
+                .as_array()
+                .cloned()
+                .unwrap_or_default();
+            for container in &containers {
+                let usage = &container["usage"];
+                sample.cpu_cores += usage["cpu"].as_str().and_then(parse_cpu).unwrap_or(0.0);
+                sample.memory_bytes += usage["memory"].as_str().and_then(parse_memory).unwrap_or(0);
+            }
+        }
+
+        Ok(sample)
+    }
+
+    /// Start sampling in the background until the monitor is stopped
+    pub fn start(self) -> ResourceMonitor {
+        let (stop_tx, mut stop_rx) = oneshot::channel();
+
+        let handle = tokio::spawn(async move {
+            let start = Instant::now();
+            let mut usage = ResourceUsage::new(&self.selector);
+            let mut ticker = tokio::time::interval(self.interval);
+
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {}
+                    _ = &mut stop_rx => break,
+                }
+                match self.sample().await {
+                    Ok(mut sample) => {
+                        sample.elapsed_secs = start.elapsed().as_secs_f64();
+                        debug!(
+                            "Resources: {} pods, {:.2} cores, {} bytes",
+                            sample.pods, sample.cpu_cores, sample.memory_bytes
+                        );
+                        usage.samples.push(sample);
+                    }
+                    Err(e) => warn!("Resource sample failed: {:#}", e),
+                }
+            }
+
+            usage
+        });
+
+        ResourceMonitor {
+            stop: stop_tx,
+            handle,
+        }
+    }
+}
+
+/// Handle to a running resource collection
+pub struct ResourceMonitor {
+    stop: oneshot::Sender<()>,
+    handle: JoinHandle<ResourceUsage>,
+}
+
+impl ResourceMonitor {
+    /// Stop sampling and return the collected usage
+    pub async fn stop(self) -> ResourceUsage {
+        let _ = self.stop.send(());
+        self.handle.await.unwrap_or_default()
+    }
+}
+
+/// Parse a Kubernetes CPU quantity into cores ("250m", "1", "123456n")
+pub fn parse_cpu(quantity: &str) -> Option<f64> {
+    let q = quantity.trim();
+    let (number, scale) = match q.char_indices().last()? {
+        (i, 'n') => (&q[..i], 1e-9),
+        (i, 'u') => (&q[..i], 1e-6),
+        (i, 'm') => (&q[..i], 1e-3),
+        _ => (q, 1.0),
+    };
+    number.parse::<f64>().ok().map(|n| n * scale)
+}
+
+/// Parse a Kubernetes memory quantity into bytes ("128Mi", "1G", "4096")
+pub fn parse_memory(quantity: &str) -> Option<u64> {
+    const SUFFIXES: [(&str, f64); 12] = [
+        ("Ki", 1024.0),
+        ("Mi", 1048576.0),
+        ("Gi", 1073741824.0),
+        ("Ti", 1099511627776.0),
+        ("Pi", 1125899906842624.0),
+        ("Ei", 1152921504606846976.0),
+        ("k", 1e3),
+        ("M", 1e6),
+        ("G", 1e9),
+        ("T", 1e12),
+        ("P", 1e15),
+        ("E", 1e18),
+    ];
+
+    let q = quantity.trim();
+    let (number, scale) = SUFFIXES
+        .iter()
+        .find_map(|(suffix, scale)| q.strip_suffix(suffix).map(|n| (n, *scale)))
+        .unwrap_or((q, 1.0));
+    number.parse::<f64>().ok().map(|n| (n * scale) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cpu() {
+        assert_eq!(parse_cpu("250m"), Some(0.25));
+        assert_eq!(parse_cpu("2"), Some(2.0));
+        assert!((parse_cpu("1500000n").unwrap() - 0.0015).abs() < 1e-12);
+        assert_eq!(parse_cpu("abc"), None);
+    }
+
+    #[test]
+    fn test_parse_memory() {
+        assert_eq!(parse_memory("128Mi"), Some(128 * 1024 * 1024));
+        assert_eq!(parse_memory("2048Ki"), Some(2 * 1024 * 1024));
+        assert_eq!(parse_memory("1G"), Some(1_000_000_000));
+        assert_eq!(parse_memory("4096"), Some(4096));
+        assert_eq!(parse_memory(""), None);
+    }
+}
@@ -0,0 +1,333 @@
+//! Rollout restarts of gateway workloads
+//!
+//! Finds the Deployments, DaemonSets and StatefulSets that own a gateway's
+//! controller and data-plane pods and restarts them the way
+//! `kubectl rollout restart` does: by stamping the pod template with a
+//! `restartedAt` annotation so the controller replaces pods gradually.
+
+#![allow(dead_code)]
+
+use anyhow::{Context, Result};
+use k8s_openapi::api::apps::v1::{DaemonSet, Deployment, ReplicaSet, StatefulSet};
+use k8s_openapi::api::core::v1::Pod;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference;
+use kube::api::{Api, ListParams, Patch, PatchParams};
+use std::fmt;
+use std::time::{Duration, Instant};
+use tracing::{debug, info};
+
+use super::K8sClient;
+
+/// Pod template annotation `kubectl rollout restart` sets
+const RESTARTED_AT_ANNOTATION: &str = "kubectl.kubernetes.io/restartedAt";
+
+/// Interval between status checks while waiting for a rollout
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Kind of workload that owns pods
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum WorkloadKind {
+    Deployment,
+    DaemonSet,
+    StatefulSet,
+}
+
+impl WorkloadKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Deployment => "deployment",
+            Self::DaemonSet => "daemonset",
+            Self::StatefulSet => "statefulset",
+        }
+    }
+}
+
+/// A workload that can be rollout-restarted
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Workload {
+    pub kind: WorkloadKind,
+    pub namespace: String,
+    pub name: String,
+}
+
+impl fmt::Display for Workload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{} in {}",
+            self.kind.name(),
+            self.name,
+            self.namespace
+        )
+    }
+}
+
+/// Rollout progress of a workload, from its status
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RolloutStatus {
+    pub generation: i64,
+    pub observed_generation: i64,
+    pub desired: i32,
+    /// Pods running the latest template
+    pub updated: i32,
+    pub available: i32,
+    /// All pods, old and new
+    pub total: i32,
+}
+
+impl RolloutStatus {
+    /// Whether every pod runs the latest template and is available, with
+    /// no old pods left
+    pub fn complete(&self) -> bool {
+        self.observed_generation >= self.generation
+            && self.updated == self.desired
+            && self.available == self.desired
+            && self.total == self.desired
+    }
+}
+
+/// Restarts and watches gateway workloads
+#[derive(Clone)]
+pub struct RolloutRestarter {
+    client: K8sClient,
+}
+
+impl RolloutRestarter {
+    pub fn new(client: K8sClient) -> Self {
+        Self { client }
+    }
+
+    fn api<K>(&self, namespace: &str) -> Api<K>
+    where
+        K: kube::Resource<Scope = k8s_openapi::NamespaceResourceScope>,
+        <K as kube::Resource>::DynamicType: Default,
+    {
+        Api::namespaced(self.client.client().clone(), namespace)
+    }
+
+    /// Workloads owning the pods that match any selector, in one namespace
+    /// (all namespaces if None)
+    pub async fn find(
+        &self,
+        selectors: &[String],
+        namespace: Option<&str>,
+    ) -> Result<Vec<Workload>> {
+        let pods: Api<Pod> = match namespace {
+            Some(ns) => self.api(ns),
+            None => Api::all(self.client.client().clone()),
+        };
+
+        let mut workloads = Vec::new();
+        for selector in selectors {
+            let list = pods
+                .list(&ListParams::default().labels(selector))
+                .await
+                .with_context(|| format!("Failed to list pods matching {selector}"))?;
+            for pod in list.items {
+                if let Some(workload) = self.owner(&pod).await? {
+                    if !workloads.contains(&workload) {
+                        workloads.push(workload);
+                    }
+                }
+            }
+        }
+        workloads.sort();
+        Ok(workloads)
+    }
+
+    /// Workload controlling a pod (through its ReplicaSet for Deployments)
+    async fn owner(&self, pod: &Pod) -> Result<Option<Workload>> {
+        let Some(namespace) = pod.metadata.namespace.clone() else {
+            return Ok(None);
+        };
+        let Some(owner) = controller_of(pod.metadata.owner_references.as_deref()) else {
+            return Ok(None);
+        };
+        let workload = |kind, name: &str| Workload {
+            kind,
+            namespace: namespace.clone(),
+            name: name.to_string(),
+        };
+        Ok(match owner.kind.as_str() {
+            "DaemonSet" => Some(workload(WorkloadKind::DaemonSet, &owner.name)),
+            "StatefulSet" => Some(workload(WorkloadKind::StatefulSet, &owner.name)),
+            "ReplicaSet" => {
+                let replica_set = self
+                    .api::<ReplicaSet>(&namespace)
+                    .get(&owner.name)
+                    .await
+                    .with_context(|| format!("Failed to get replicaset {}", owner.name))?;
+                controller_of(replica_set.metadata.owner_references.as_deref())
+                    .filter(|o| o.kind == "Deployment")
+                    .map(|o| workload(WorkloadKind::Deployment, &o.name))
+            }
+            _ => None,
+        })
+    }
+
+    /// Restart a workload's pods like `kubectl rollout restart`
+    pub async fn restart(&self, workload: &Workload) -> Result<()> {
+        info!("Restarting {}", workload);
+        let patch = serde_json::json!({
+            "spec": { "template": { "metadata": { "annotations": {
+                RESTARTED_AT_ANNOTATION: chrono::Utc::now().to_rfc3339(),
+            }}}}
+        });
+        let params = PatchParams::default();
+        let patch = Patch::Merge(&patch);
+        let ns = &workload.namespace;
+        let name = &workload.name;
+        match workload.kind {
+            WorkloadKind::Deployment => self
+                .api::<Deployment>(ns)
+                .patch(name, &params, &patch)
+                .await
+                .map(|_| ()),
+            WorkloadKind::DaemonSet => self
+                .api::<DaemonSet>(ns)
+                .patch(name, &params, &patch)
+                .await
+                .map(|_| ()),
+            WorkloadKind::StatefulSet => self
+                .api::<StatefulSet>(ns)
+                .patch(name, &params, &patch)
+                .await
+                .map(|_| ()),
+        }
+        .with_context(|| format!("Failed to restart {workload}"))
+    }
+
+    /// Current rollout progress of a workload
+    pub async fn status(&self, workload: &Workload) -> Result<RolloutStatus> {
+        let ns = &workload.namespace;
+        let name = &workload.name;
+        let context = || format!("Failed to get {workload}");
+        Ok(match workload.kind {
+            WorkloadKind::Deployment => {
+                let d = self
+                    .api::<Deployment>(ns)
+                    .get(name)
+                    .await
+                    .with_context(context)?;
+                let status = d.status.unwrap_or_default();
+                RolloutStatus {
+                    generation: d.metadata.generation.unwrap_or(0),
+                    observed_generation: status.observed_generation.unwrap_or(0),
+                    desired: d.spec.and_then(|s| s.replicas).unwrap_or(1),
+                    updated: status.updated_replicas.unwrap_or(0),
+                    available: status.available_replicas.unwrap_or(0),
+                    total: status.replicas.unwrap_or(0),
+                }
+            }
+            WorkloadKind::DaemonSet => {
+                let d = self
+                    .api::<DaemonSet>(ns)
+                    .get(name)
+                    .await
+                    .with_context(context)?;
+                let status = d.status.unwrap_or_default();
+                RolloutStatus {
+                    generation: d.metadata.generation.unwrap_or(0),
+                    observed_generation: status.observed_generation.unwrap_or(0),
+                    desired: status.desired_number_scheduled,
+                    updated: status.updated_number_scheduled.unwrap_or(0),
+                    available: status.number_available.unwrap_or(0),
+                    total: status.current_number_scheduled,
+                }
+            }
+            WorkloadKind::StatefulSet => {
+                let s = self
+                    .api::<StatefulSet>(ns)
+                    .get(name)
+                    .await
+                    .with_context(context)?;
+                let status = s.status.unwrap_or_default();
+                RolloutStatus {
+                    generation: s.metadata.generation.unwrap_or(0),
+                    observed_generation: status.observed_generation.unwrap_or(0),
+                    desired: s.spec.and_then(|s| s.replicas).unwrap_or(1),
+                    updated: status.updated_replicas.unwrap_or(0),
+                    available: status.available_replicas.unwrap_or(0),
+                    total: status.replicas,
+                }
+            }
+        })
+    }
+
+    /// Wait until a restarted workload has rolled out completely
+    pub async fn wait_rolled_out(&self, workload: &Workload, timeout: Duration) -> Result<()> {
+        let start = Instant::now();
+        // Give the controller a moment to observe the new template
+        tokio::time::sleep(POLL_INTERVAL).await;
+        loop {
+            let status = self.status(workload).await?;
+            debug!(
+                "{}: {}/{} updated, {}/{} available",
+                workload, status.updated, status.desired, status.available, status.desired
+            );
+            if status.complete() {
+                return Ok(());
+            }
+            if start.elapsed() >= timeout {
+                anyhow::bail!(
+                    "Timeout waiting for {workload} to roll out ({}/{} updated, {}/{} available)",
+                    status.updated,
+                    status.desired,
+                    status.available,
+                    status.desired
+                );
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+}
+
+/// The owner reference marked as controller
+fn controller_of(owners: Option<&[OwnerReference]>) -> Option<&OwnerReference> {
+    owners?.iter().find(|o| o.controller == Some(true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rollout_complete() {
+        let done = RolloutStatus {
+            generation: 3,
+            observed_generation: 3,
+            desired: 2,
+            updated: 2,
+            available: 2,
+            total: 2,
+        };
+        assert!(done.complete());
+        // New template not seen yet
+        assert!(!RolloutStatus {
+            observed_generation: 2,
+            ..done
+        }
+        .complete());
+        // An old pod is still terminating
+        assert!(!RolloutStatus { total: 3, ..done }.complete());
+        // A new pod is not ready yet
+        assert!(!RolloutStatus {
+            available: 1,
+            ..done
+        }
+        .complete());
+    }
+
+    #[test]
+    fn test_workload_display() {
+        let workload = Workload {
+            kind: WorkloadKind::Deployment,
+            namespace: "envoy-gateway-system".to_string(),
+            name: "envoy-gateway".to_string(),
+        };
+        assert_eq!(
+            workload.to_string(),
+            "deployment/envoy-gateway in envoy-gateway-system"
+        );
+    }
+}
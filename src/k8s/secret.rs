@@ -0,0 +1,128 @@
+//! Reading [`SecretRef`] values
+//!
+//! References name an environment variable, a file, or a key of a
+//! Kubernetes Secret; the latter is read through the cluster API.
+
+use anyhow::{Context, Result};
+use k8s_openapi::api::core::v1::Secret;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tempfile::TempPath;
+
+use super::K8sClient;
+use crate::config::SecretRef;
+
+/// Read the value; Secrets without a namespace are looked up in `namespace`
+pub async fn resolve_secret(reference: &SecretRef, namespace: &str) -> Result<String> {
+    reference.validate()?;
+    if let Some(var) = &reference.env {
+        return std::env::var(var).with_context(|| format!("{var} is not set"));
+    }
+    if let Some(path) = reference.file_path() {
+        return tokio::fs::read_to_string(&path)
+            .await
+            .with_context(|| format!("Failed to read secret file: {}", path.display()));
+    }
+    let secret_ref = reference.secret.as_ref().expect("validated");
+    let namespace = secret_ref.namespace.as_deref().unwrap_or(namespace);
+    let client = K8sClient::new(namespace).await?;
+    let secret = client
+        .namespaced_api::<Secret>()
+        .get(&secret_ref.name)
+        .await
+        .with_context(|| format!("Failed to read Secret {namespace}/{}", secret_ref.name))?;
+    secret_value(&secret, &secret_ref.key)
+        .with_context(|| format!("Secret {namespace}/{}", secret_ref.name))
+}
+
+/// Read the value into a private file that is removed with the returned
+/// [`SecretFile`]; `file` references are used in place
+pub async fn resolve_secret_to_file(
+    reference: &SecretRef,
+    namespace: &str,
+    name: &str,
+) -> Result<SecretFile> {
+    if let Some(path) = reference.file_path() {
+        reference.validate()?;
+        return Ok(SecretFile::Existing(path));
+    }
+    let mut value = resolve_secret(reference, namespace).await?;
+    // ssh rejects keys without the final newline
+    if !value.ends_with('\n') {
+        value.push('\n');
+    }
+    Ok(SecretFile::Private(write_private(name, &value)?))
+}
+
+/// Value under `key` of a Secret (`data`, else `stringData`)
+fn secret_value(secret: &Secret, key: &str) -> Result<String> {
+    if let Some(bytes) = secret.data.as_ref().and_then(|d| d.get(key)) {
+        return String::from_utf8(bytes.0.clone())
+            .with_context(|| format!("Key {key} is not UTF-8"));
+    }
+    secret
+        .string_data
+        .as_ref()
+        .and_then(|d| d.get(key))
+        .cloned()
+        .with_context(|| format!("No key {key}"))
+}
+
+/// Write `value` to a new file with a random name, readable by the owner only
+fn write_private(name: &str, value: &str) -> Result<TempPath> {
+    let mut file = tempfile::Builder::new()
+        .prefix(&format!("gateway-poc-{name}-"))
+        .tempfile()
+        .context("Failed to create a private file")?;
+    file.write_all(value.as_bytes())
+        .with_context(|| format!("Failed to write {}", file.path().display()))?;
+    Ok(file.into_temp_path())
+}
+
+/// Secret value on disk for tools that take a path (ssh `-i`)
+#[derive(Debug)]
+pub enum SecretFile {
+    /// The referenced file, used in place
+    Existing(PathBuf),
+    /// Written by us, so removed on drop
+    Private(TempPath),
+}
+
+impl SecretFile {
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::Existing(path) => path,
+            Self::Private(path) => path,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_resolve_to_file() {
+        std::env::set_var("GATEWAY_POC_TEST_SSH_KEY", "-----BEGIN KEY-----");
+        let file = resolve_secret_to_file(
+            &SecretRef::env("GATEWAY_POC_TEST_SSH_KEY"),
+            "default",
+            "test-key",
+        )
+        .await
+        .unwrap();
+        let path = file.path().to_path_buf();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "-----BEGIN KEY-----\n"
+        );
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        drop(file);
+        assert!(!path.exists());
+    }
+}
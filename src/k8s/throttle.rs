@@ -0,0 +1,249 @@
+//! Client-side Kubernetes API rate limiting
+//!
+//! Route-scaling and endpoint-churn scenarios issue many API calls in a
+//! short time. Like client-go, every request (kube client calls and kubectl
+//! invocations alike) takes a token from one shared bucket refilled at `qps`
+//! tokens per second and holding at most `burst`; requests beyond that wait.
+//! Time spent waiting is recorded so a run can report that it was throttled.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Mutex, OnceLock};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tokio::time::Sleep;
+use tower::{Layer, Service};
+
+/// QPS/burst settings for the tool's own API requests
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ApiRateLimit {
+    /// Sustained requests per second (0 disables limiting)
+    #[serde(default = "default_qps")]
+    pub qps: f64,
+    /// Requests allowed at once before the QPS limit applies
+    #[serde(default = "default_burst")]
+    pub burst: u32,
+}
+
+fn default_qps() -> f64 {
+    50.0
+}
+
+fn default_burst() -> u32 {
+    100
+}
+
+impl Default for ApiRateLimit {
+    fn default() -> Self {
+        Self {
+            qps: default_qps(),
+            burst: default_burst(),
+        }
+    }
+}
+
+impl ApiRateLimit {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    fn enabled(&self) -> bool {
+        self.qps.is_finite() && self.qps > 0.0
+    }
+}
+
+impl fmt::Display for ApiRateLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.enabled() {
+            write!(f, "{} qps, burst {}", self.qps, self.burst)
+        } else {
+            write!(f, "unlimited")
+        }
+    }
+}
+
+/// Requests that had to wait for a token
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ThrottleStats {
+    pub requests: u64,
+    pub throttled: u64,
+    pub waited: Duration,
+    pub limit: ApiRateLimit,
+}
+
+impl ThrottleStats {
+    pub fn was_throttled(&self) -> bool {
+        self.throttled > 0
+    }
+}
+
+impl fmt::Display for ThrottleStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} of {} Kubernetes API requests throttled ({:.1}s waiting at {})",
+            self.throttled,
+            self.requests,
+            self.waited.as_secs_f64(),
+            self.limit
+        )
+    }
+}
+
+/// Token bucket handing out reservations
+#[derive(Debug)]
+struct TokenBucket {
+    limit: ApiRateLimit,
+    tokens: f64,
+    updated: Instant,
+    stats: ThrottleStats,
+}
+
+impl TokenBucket {
+    fn new(limit: ApiRateLimit, now: Instant) -> Self {
+        Self {
+            limit,
+            tokens: f64::from(limit.burst.max(1)),
+            updated: now,
+            stats: ThrottleStats {
+                limit,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Take a token, returning how long to wait before using it
+    fn reserve(&mut self, now: Instant) -> Duration {
+        self.stats.requests += 1;
+        if !self.limit.enabled() {
+            return Duration::ZERO;
+        }
+        let burst = f64::from(self.limit.burst.max(1));
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.limit.qps).min(burst) - 1.0;
+        self.updated = now;
+        if self.tokens >= 0.0 {
+            return Duration::ZERO;
+        }
+        let wait = Duration::from_secs_f64(-self.tokens / self.limit.qps);
+        self.stats.throttled += 1;
+        self.stats.waited += wait;
+        wait
+    }
+}
+
+fn bucket() -> std::sync::MutexGuard<'static, TokenBucket> {
+    static BUCKET: OnceLock<Mutex<TokenBucket>> = OnceLock::new();
+    BUCKET
+        .get_or_init(|| Mutex::new(TokenBucket::new(ApiRateLimit::default(), Instant::now())))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+/// Replace the limit (from the config file) before any API calls are made
+pub fn set_api_rate_limit(limit: ApiRateLimit) {
+    *bucket() = TokenBucket::new(limit, Instant::now());
+}
+
+/// Throttling so far in this process
+pub fn throttle_stats() -> ThrottleStats {
+    bucket().stats
+}
+
+/// Wait for a token before an API request
+pub async fn acquire_api_permit() {
+    let wait = bucket().reserve(Instant::now());
+    if !wait.is_zero() {
+        tokio::time::sleep(wait).await;
+    }
+}
+
+/// Tower layer applying the shared limit to a kube client
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ApiRateLimitLayer;
+
+impl<S> Layer<S> for ApiRateLimitLayer {
+    type Service = ApiRateLimited<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ApiRateLimited {
+            inner,
+            reserved: false,
+            delay: None,
+        }
+    }
+}
+
+/// Service waiting for a token in `poll_ready`
+pub struct ApiRateLimited<S> {
+    inner: S,
+    reserved: bool,
+    delay: Option<Pin<Box<Sleep>>>,
+}
+
+impl<S, Req> Service<Req> for ApiRateLimited<S>
+where
+    S: Service<Req>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        if !self.reserved {
+            self.reserved = true;
+            let wait = bucket().reserve(Instant::now());
+            if !wait.is_zero() {
+                self.delay = Some(Box::pin(tokio::time::sleep(wait)));
+            }
+        }
+        if let Some(delay) = self.delay.as_mut() {
+            if delay.as_mut().poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+            self.delay = None;
+        }
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Req) -> Self::Future {
+        self.reserved = false;
+        self.inner.call(req)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_bucket() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(
+            ApiRateLimit {
+                qps: 10.0,
+                burst: 3,
+            },
+            start,
+        );
+        for _ in 0..3 {
+            assert_eq!(bucket.reserve(start), Duration::ZERO);
+        }
+        assert_eq!(bucket.reserve(start), Duration::from_millis(100));
+        assert_eq!(bucket.reserve(start), Duration::from_millis(200));
+
+        // Refilled at 10 per second, up to the burst
+        let later = start + Duration::from_secs(1);
+        assert_eq!(bucket.reserve(later), Duration::ZERO);
+        assert_eq!(bucket.stats.requests, 6);
+        assert_eq!(bucket.stats.throttled, 2);
+        assert!(bucket.stats.to_string().starts_with("2 of 6"));
+
+        let mut unlimited = TokenBucket::new(ApiRateLimit { qps: 0.0, burst: 1 }, start);
+        assert_eq!(unlimited.reserve(start), Duration::ZERO);
+        assert_eq!(unlimited.reserve(start), Duration::ZERO);
+        assert!(!unlimited.stats.was_throttled());
+    }
+}
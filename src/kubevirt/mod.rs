@@ -5,10 +5,19 @@
 
 #![allow(dead_code)]
 
+mod pool;
+mod prepull;
+mod provision;
 mod ssh;
 mod vm;
 mod vmi;
 
-pub use ssh::{SshClient, SshConfig};
-pub use vm::{VirtualMachineManager, VmConfig};
+pub use pool::{plan as plan_pool, PoolChange, PoolSpec};
+pub use prepull::{
+    is_prepulled, puller_daemon_set, readiness_summary, ImagePrePuller, PREPULL_ANNOTATION,
+};
+pub use provision::{Provision, PROVISIONED_MARKER, PROVISION_LOG};
+pub(crate) use ssh::shell_quote;
+pub use ssh::{shell_command, SshClient, SshConfig, SshOutput};
+pub use vm::{VirtualMachine, VirtualMachineManager, VmConfig};
 pub use vmi::VmiManager;
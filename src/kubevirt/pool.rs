@@ -0,0 +1,392 @@
+//! Declarative VM pools
+//!
+//! A pool spec declares groups of identical VMs. `vm apply` compares it with
+//! the VMs in the namespace and creates, resizes, relabels, replaces or
+//! deletes VMs until they match. VMs belong to a pool through
+//! [`POOL_LABEL`], so VMs created any other way are never touched.
+//!
+//! ```yaml
+//! pools:
+//!   - name: worker        # VMs worker-0 .. worker-2
+//!     count: 3
+//!     cpu: 4
+//!     memory: 8           # GB
+//!     image: quay.io/containerdisks/fedora:latest
+//!     labels: { role: loadgen }
+//!     provision: [k3s]
+//! ```
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::Path;
+
+use super::provision::Provision;
+use super::vm::{VirtualMachine, VmConfig};
+
+/// Label naming the pool a VM belongs to
+pub const POOL_LABEL: &str = "gateway-poc.io/pool";
+
+/// Set of VM pools, as read from `vm apply --spec`
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PoolSpec {
+    pub pools: Vec<VmPool>,
+}
+
+/// Group of identical VMs named `<name>-<index>`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VmPool {
+    pub name: String,
+
+    /// Number of VMs (0 removes them all)
+    #[serde(default = "default_count")]
+    pub count: u32,
+
+    /// CPU cores per VM
+    #[serde(default = "default_cpu")]
+    pub cpu: u32,
+
+    /// Memory per VM in GB
+    #[serde(default = "default_memory")]
+    pub memory: u32,
+
+    /// Container disk image (default: Fedora)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, String>,
+
+    /// Provisioning presets run at first boot (k3s, containerd); not applied
+    /// to VMs that already exist
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub provision: Vec<String>,
+}
+
+fn default_count() -> u32 {
+    1
+}
+
+fn default_cpu() -> u32 {
+    4
+}
+
+fn default_memory() -> u32 {
+    8
+}
+
+impl PoolSpec {
+    /// Read and validate a spec file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read VM spec: {}", path.display()))?;
+        let spec: PoolSpec = serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse VM spec: {}", path.display()))?;
+        spec.validate()
+            .with_context(|| format!("Invalid VM spec: {}", path.display()))?;
+        Ok(spec)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        let mut names = BTreeSet::new();
+        for pool in &self.pools {
+            let valid = !pool.name.is_empty()
+                && pool.name.len() <= 50
+                && pool
+                    .name
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+                && !pool.name.starts_with('-')
+                && !pool.name.ends_with('-');
+            if !valid {
+                anyhow::bail!(
+                    "Pool name '{}' must be lowercase letters, digits and '-' (at most 50)",
+                    pool.name
+                );
+            }
+            if !names.insert(pool.name.as_str()) {
+                anyhow::bail!("Pool '{}' is declared twice", pool.name);
+            }
+            if pool.cpu == 0 || pool.memory == 0 {
+                anyhow::bail!("Pool '{}' needs at least 1 CPU and 1 GB memory", pool.name);
+            }
+            pool.provisioning()
+                .map_err(|e| anyhow::anyhow!("Pool '{}': {e}", pool.name))?;
+        }
+        Ok(())
+    }
+
+    /// VMs the spec declares, authorizing `ssh_public_key` in new ones
+    pub fn desired(&self, namespace: &str, ssh_public_key: Option<&str>) -> Vec<VirtualMachine> {
+        self.pools
+            .iter()
+            .flat_map(|pool| (0..pool.count).map(move |i| pool.vm(i, namespace, ssh_public_key)))
+            .collect()
+    }
+}
+
+impl VmPool {
+    fn provisioning(&self) -> Result<Vec<Provision>, String> {
+        self.provision.iter().map(|p| Provision::parse(p)).collect()
+    }
+
+    fn vm(&self, index: u32, namespace: &str, ssh_public_key: Option<&str>) -> VirtualMachine {
+        let mut config = VmConfig::new(format!("{}-{index}", self.name), namespace)
+            .cpu(self.cpu)
+            .memory(format!("{}Gi", self.memory))
+            .label("app", "gateway-test")
+            .label(POOL_LABEL, &self.name)
+            .label("instance", index.to_string());
+        if let Some(image) = &self.image {
+            config = config.image(image);
+        }
+        for (key, value) in &self.labels {
+            config = config.label(key, value);
+        }
+        for step in self.provisioning().unwrap_or_default() {
+            config = config.provision(step);
+        }
+        if let Some(key) = ssh_public_key {
+            config = config.ssh_key(key);
+        }
+        config.build()
+    }
+}
+
+/// One step towards the declared state
+#[derive(Clone, Debug)]
+pub enum PoolChange {
+    Create(Box<VirtualMachine>),
+    /// Size differs; `None` keeps that part
+    Resize {
+        name: String,
+        cpu: Option<u32>,
+        memory: Option<String>,
+    },
+    /// Declared labels missing or different
+    Relabel {
+        name: String,
+        labels: BTreeMap<String, String>,
+    },
+    /// Image differs, which needs a new VM
+    Replace(Box<VirtualMachine>),
+    /// Pool VM no longer declared
+    Delete(String),
+}
+
+impl PoolChange {
+    pub fn name(&self) -> &str {
+        match self {
+            PoolChange::Create(vm) | PoolChange::Replace(vm) => vm_name(vm),
+            PoolChange::Resize { name, .. }
+            | PoolChange::Relabel { name, .. }
+            | PoolChange::Delete(name) => name,
+        }
+    }
+}
+
+impl fmt::Display for PoolChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PoolChange::Create(vm) => {
+                let (cpu, memory) = size(vm);
+                write!(
+                    f,
+                    "+ create {} ({} CPU, {})",
+                    vm_name(vm),
+                    cpu.unwrap_or_default(),
+                    memory.unwrap_or("-")
+                )
+            }
+            PoolChange::Resize { name, cpu, memory } => {
+                let parts: Vec<String> = [
+                    cpu.map(|c| format!("{c} CPU")),
+                    memory.as_ref().map(|m| m.to_string()),
+                ]
+                .into_iter()
+                .flatten()
+                .collect();
+                write!(f, "~ resize {name} to {}", parts.join(", "))
+            }
+            PoolChange::Relabel { name, labels } => {
+                let labels: Vec<String> = labels.iter().map(|(k, v)| format!("{k}={v}")).collect();
+                write!(f, "~ label {name} {}", labels.join(","))
+            }
+            PoolChange::Replace(vm) => write!(
+                f,
+                "± replace {} (image {})",
+                vm_name(vm),
+                vm.container_disk_images().join(", ")
+            ),
+            PoolChange::Delete(name) => write!(f, "- delete {name}"),
+        }
+    }
+}
+
+fn vm_name(vm: &VirtualMachine) -> &str {
+    vm.metadata.name.as_deref().unwrap_or_default()
+}
+
+fn size(vm: &VirtualMachine) -> (Option<u32>, Option<&str>) {
+    let domain = &vm.spec.template.spec.domain;
+    (
+        domain.cpu.as_ref().and_then(|c| c.cores),
+        domain.memory.as_ref().and_then(|m| m.guest.as_deref()),
+    )
+}
+
+/// Changes turning the pool VMs in `existing` into `desired`: deletions
+/// first (freeing capacity), then replacements, resizes, relabels and
+/// creations. VMs without [`POOL_LABEL`] are ignored.
+pub fn plan(desired: &[VirtualMachine], existing: &[VirtualMachine]) -> Vec<PoolChange> {
+    let existing: BTreeMap<&str, &VirtualMachine> = existing
+        .iter()
+        .filter(|vm| {
+            vm.metadata
+                .labels
+                .as_ref()
+                .is_some_and(|l| l.contains_key(POOL_LABEL))
+        })
+        .map(|vm| (vm_name(vm), vm))
+        .collect();
+    let declared: BTreeSet<&str> = desired.iter().map(vm_name).collect();
+
+    let mut deletes = Vec::new();
+    let mut replaces = Vec::new();
+    let mut resizes = Vec::new();
+    let mut relabels = Vec::new();
+    let mut creates = Vec::new();
+
+    for name in existing.keys().filter(|name| !declared.contains(*name)) {
+        deletes.push(PoolChange::Delete(name.to_string()));
+    }
+    for vm in desired {
+        let name = vm_name(vm);
+        let Some(current) = existing.get(name) else {
+            creates.push(PoolChange::Create(Box::new(vm.clone())));
+            continue;
+        };
+        if current.container_disk_images() != vm.container_disk_images() {
+            replaces.push(PoolChange::Replace(Box::new(vm.clone())));
+            continue;
+        }
+
+        let (cpu, memory) = size(vm);
+        let (current_cpu, current_memory) = size(current);
+        if cpu != current_cpu || memory != current_memory {
+            resizes.push(PoolChange::Resize {
+                name: name.to_string(),
+                cpu: cpu.filter(|_| cpu != current_cpu),
+                memory: memory
+                    .filter(|_| memory != current_memory)
+                    .map(str::to_string),
+            });
+        }
+
+        let current_labels = current.metadata.labels.clone().unwrap_or_default();
+        let labels: BTreeMap<String, String> = vm
+            .metadata
+            .labels
+            .iter()
+            .flatten()
+            .filter(|(k, v)| current_labels.get(*k) != Some(*v))
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        if !labels.is_empty() {
+            relabels.push(PoolChange::Relabel {
+                name: name.to_string(),
+                labels,
+            });
+        }
+    }
+
+    [deletes, replaces, resizes, relabels, creates]
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(yaml: &str) -> PoolSpec {
+        let spec: PoolSpec = serde_yaml::from_str(yaml).unwrap();
+        spec.validate().unwrap();
+        spec
+    }
+
+    #[test]
+    fn test_pool_spec() {
+        let spec = spec("pools:\n  - name: worker\n    count: 2\n    provision: [k3s]\n");
+        let vms = spec.desired("default", None);
+        assert_eq!(vms.len(), 2);
+        assert_eq!(vm_name(&vms[1]), "worker-1");
+        assert_eq!(size(&vms[0]), (Some(4), Some("8Gi")));
+        assert_eq!(
+            vms[0].metadata.labels.as_ref().unwrap()[POOL_LABEL],
+            "worker"
+        );
+
+        for invalid in [
+            "pools:\n  - name: Worker\n",
+            "pools:\n  - name: a\n  - name: a\n",
+            "pools:\n  - name: a\n    cpu: 0\n",
+            "pools:\n  - name: a\n    provision: [docker]\n",
+        ] {
+            let spec: PoolSpec = serde_yaml::from_str(invalid).unwrap();
+            assert!(spec.validate().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_plan() {
+        let current = spec(
+            "pools:\n  - name: worker\n    count: 3\n    cpu: 2\n\
+             \n  - name: old\n",
+        )
+        .desired("default", None);
+        let mut existing = current.clone();
+        // Not created by a pool, so never deleted
+        existing.push(VmConfig::new("manual", "default").build());
+
+        let desired = spec(
+            "pools:\n  - name: worker\n    count: 2\n    cpu: 4\n    labels: { role: lb }\n\
+             \n  - name: edge\n    image: quay.io/containerdisks/ubuntu:latest\n",
+        )
+        .desired("default", None);
+
+        let changes: Vec<String> = plan(&desired, &existing)
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                "- delete old-0",
+                "- delete worker-2",
+                "~ resize worker-0 to 4 CPU",
+                "~ resize worker-1 to 4 CPU",
+                "~ label worker-0 role=lb",
+                "~ label worker-1 role=lb",
+                "+ create edge-0 (4 CPU, 8Gi)",
+            ]
+        );
+
+        // Applied state needs no changes
+        assert!(plan(&desired, &desired).is_empty());
+
+        let mut moved = desired.clone();
+        moved[2] = VmConfig::new("edge-0", "default")
+            .cpu(4)
+            .memory("8Gi")
+            .label(POOL_LABEL, "edge")
+            .build();
+        let changes = plan(&desired, &moved);
+        assert!(
+            matches!(changes[..], [PoolChange::Replace(_), ..]),
+            "{changes:?}"
+        );
+    }
+}
@@ -0,0 +1,346 @@
+//! Container disk pre-pulling
+//!
+//! VM boots are dominated by pulling the container disk image on the node
+//! the VMI lands on. The pre-puller runs a short-lived DaemonSet whose init
+//! containers start from each image, so every node has the images cached
+//! before the VMs are created. Container disk images ship no shell, so a
+//! static busybox copied in from a helper init container serves as the
+//! entrypoint.
+
+use anyhow::{Context, Result};
+use k8s_openapi::api::apps::v1::{DaemonSet, DaemonSetSpec};
+use k8s_openapi::api::core::v1::{
+    Container, EmptyDirVolumeSource, PodSpec, PodTemplateSpec, Volume, VolumeMount,
+};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::LabelSelector;
+use kube::api::{Api, DeleteParams, ObjectMeta, PostParams};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::{Duration, Instant};
+use tokio::time::sleep;
+use tracing::{debug, info, warn};
+
+use super::VirtualMachine;
+use crate::k8s::{owner_labels, K8sClient};
+
+/// Annotation marking VMs created after their images were pre-pulled
+pub const PREPULL_ANNOTATION: &str = "gateway-poc.io/prepulled";
+
+/// DaemonSet running the pulls
+const PULLER_NAME: &str = "gateway-poc-image-puller";
+
+/// Statically linked shell copied into the image containers
+const HELPER_IMAGE: &str = "busybox:1.36";
+
+/// Keeps the pod running once all images are pulled
+const PAUSE_IMAGE: &str = "registry.k8s.io/pause:3.9";
+
+/// Shared volume holding the helper binary
+const BIN_VOLUME: &str = "puller-bin";
+const BIN_PATH: &str = "/puller-bin";
+
+/// Pre-pulls container disk images on every (selected) node
+pub struct ImagePrePuller {
+    client: K8sClient,
+    namespace: String,
+    node_selector: BTreeMap<String, String>,
+}
+
+impl ImagePrePuller {
+    pub fn new(client: K8sClient, namespace: impl Into<String>) -> Self {
+        Self {
+            client,
+            namespace: namespace.into(),
+            node_selector: BTreeMap::new(),
+        }
+    }
+
+    /// Only pull on nodes carrying these labels
+    pub fn with_node_selector(mut self, selector: BTreeMap<String, String>) -> Self {
+        self.node_selector = selector;
+        self
+    }
+
+    fn api(&self) -> Api<DaemonSet> {
+        Api::namespaced(self.client.client().clone(), &self.namespace)
+    }
+
+    /// DaemonSet pulling `images` on each node
+    pub fn daemon_set(&self, images: &[String]) -> DaemonSet {
+        puller_daemon_set(&self.namespace, images, &self.node_selector)
+    }
+
+    /// Pull `images` on every node, then remove the DaemonSet again
+    pub async fn pull(&self, images: &[String], timeout: Duration) -> Result<PrePullReport> {
+        let start = Instant::now();
+        info!(
+            "Pre-pulling {} image(s) in {}",
+            images.len(),
+            self.namespace
+        );
+
+        // A leftover puller from an interrupted run would hold the name
+        self.remove().await;
+        self.api()
+            .create(&PostParams::default(), &self.daemon_set(images))
+            .await
+            .context("Failed to create image puller DaemonSet")?;
+
+        let waited = self.wait_pulled(timeout).await;
+        self.remove().await;
+        let nodes = waited?;
+
+        Ok(PrePullReport {
+            images: images.to_vec(),
+            nodes,
+            duration: start.elapsed(),
+        })
+    }
+
+    /// Wait until the puller pod is ready on every scheduled node
+    async fn wait_pulled(&self, timeout: Duration) -> Result<i32> {
+        let start = Instant::now();
+        loop {
+            let status = self
+                .api()
+                .get(PULLER_NAME)
+                .await
+                .context("Failed to get image puller DaemonSet")?
+                .status
+                .unwrap_or_default();
+            debug!(
+                "Image puller: {}/{} nodes ready",
+                status.number_ready, status.desired_number_scheduled
+            );
+            if status.desired_number_scheduled > 0
+                && status.number_ready == status.desired_number_scheduled
+            {
+                return Ok(status.number_ready);
+            }
+            if start.elapsed() >= timeout {
+                anyhow::bail!(
+                    "Timeout pre-pulling images ({}/{} nodes done)",
+                    status.number_ready,
+                    status.desired_number_scheduled
+                );
+            }
+            sleep(Duration::from_secs(2)).await;
+        }
+    }
+
+    async fn remove(&self) {
+        match self
+            .api()
+            .delete(PULLER_NAME, &DeleteParams::background())
+            .await
+        {
+            Ok(_) => debug!("Removed image puller DaemonSet"),
+            Err(kube::Error::Api(e)) if e.code == 404 => {}
+            Err(e) => warn!("Failed to remove image puller DaemonSet: {}", e),
+        }
+    }
+}
+
+/// DaemonSet pulling `images` on each (selected) node
+pub fn puller_daemon_set(
+    namespace: &str,
+    images: &[String],
+    node_selector: &BTreeMap<String, String>,
+) -> DaemonSet {
+    let mut labels = owner_labels();
+    labels.insert("app".to_string(), PULLER_NAME.to_string());
+
+    let bin_mount = VolumeMount {
+        name: BIN_VOLUME.to_string(),
+        mount_path: BIN_PATH.to_string(),
+        ..Default::default()
+    };
+    let mut init_containers = vec![Container {
+        name: "install-helper".to_string(),
+        image: Some(HELPER_IMAGE.to_string()),
+        command: Some(vec![
+            "cp".to_string(),
+            "/bin/busybox".to_string(),
+            format!("{BIN_PATH}/busybox"),
+        ]),
+        volume_mounts: Some(vec![bin_mount.clone()]),
+        ..Default::default()
+    }];
+    init_containers.extend(images.iter().enumerate().map(|(i, image)| Container {
+        name: format!("pull-{i}"),
+        image: Some(image.clone()),
+        image_pull_policy: Some("IfNotPresent".to_string()),
+        command: Some(vec![format!("{BIN_PATH}/busybox"), "true".to_string()]),
+        volume_mounts: Some(vec![bin_mount.clone()]),
+        ..Default::default()
+    }));
+
+    DaemonSet {
+        metadata: ObjectMeta {
+            name: Some(PULLER_NAME.to_string()),
+            namespace: Some(namespace.to_string()),
+            labels: Some(labels.clone()),
+            ..Default::default()
+        },
+        spec: Some(DaemonSetSpec {
+            selector: LabelSelector {
+                match_labels: Some(labels.clone()),
+                ..Default::default()
+            },
+            template: PodTemplateSpec {
+                metadata: Some(ObjectMeta {
+                    labels: Some(labels),
+                    ..Default::default()
+                }),
+                spec: Some(PodSpec {
+                    init_containers: Some(init_containers),
+                    containers: vec![Container {
+                        name: "pause".to_string(),
+                        image: Some(PAUSE_IMAGE.to_string()),
+                        ..Default::default()
+                    }],
+                    volumes: Some(vec![Volume {
+                        name: BIN_VOLUME.to_string(),
+                        empty_dir: Some(EmptyDirVolumeSource::default()),
+                        ..Default::default()
+                    }]),
+                    node_selector: (!node_selector.is_empty()).then(|| node_selector.clone()),
+                    termination_grace_period_seconds: Some(0),
+                    ..Default::default()
+                }),
+            },
+            ..Default::default()
+        }),
+        status: None,
+    }
+}
+
+/// Outcome of a pre-pull
+#[derive(Clone, Debug)]
+pub struct PrePullReport {
+    pub images: Vec<String>,
+    pub nodes: i32,
+    pub duration: Duration,
+}
+
+impl fmt::Display for PrePullReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Pre-pulled {} image(s) on {} node(s) in {:.1}s",
+            self.images.len(),
+            self.nodes,
+            self.duration.as_secs_f64()
+        )
+    }
+}
+
+/// Whether a VM was created after its images were pre-pulled
+pub fn is_prepulled(vm: &VirtualMachine) -> bool {
+    vm.metadata
+        .annotations
+        .as_ref()
+        .and_then(|a| a.get(PREPULL_ANNOTATION))
+        .is_some_and(|v| v == "true")
+}
+
+/// Average time-to-ready of pre-pulled vs. cold-started VMs, e.g.
+/// "pre-pulled 21.0s avg (2 VMs) vs cold 84.0s avg (1 VM), 75% faster"
+pub fn readiness_summary(vms: &[VirtualMachine]) -> Option<String> {
+    let average = |prepulled: bool| {
+        let times: Vec<f64> = vms
+            .iter()
+            .filter(|vm| is_prepulled(vm) == prepulled)
+            .filter_map(|vm| vm.time_to_ready())
+            .map(|d| d.as_secs_f64())
+            .collect();
+        (!times.is_empty()).then(|| (times.iter().sum::<f64>() / times.len() as f64, times.len()))
+    };
+    let plural = |n: usize| if n == 1 { "VM" } else { "VMs" };
+
+    match (average(true), average(false)) {
+        (Some((warm, w)), Some((cold, c))) => {
+            let mut summary = format!(
+                "pre-pulled {warm:.1}s avg ({w} {}) vs cold {cold:.1}s avg ({c} {})",
+                plural(w),
+                plural(c)
+            );
+            if cold > 0.0 && warm < cold {
+                summary.push_str(&format!(", {:.0}% faster", (1.0 - warm / cold) * 100.0));
+            }
+            Some(summary)
+        }
+        (Some((warm, w)), None) => Some(format!("pre-pulled {warm:.1}s avg ({w} {})", plural(w))),
+        (None, Some((cold, c))) => Some(format!("cold {cold:.1}s avg ({c} {})", plural(c))),
+        (None, None) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kubevirt::VmConfig;
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::Time;
+
+    fn ready_vm(name: &str, prepulled: bool, secs: i64) -> VirtualMachine {
+        let mut config = VmConfig::new(name, "default");
+        if prepulled {
+            config = config.annotation(PREPULL_ANNOTATION, "true");
+        }
+        let mut vm = config.build();
+        let created: chrono::DateTime<chrono::Utc> = "2025-01-01T12:00:00Z".parse().unwrap();
+        vm.metadata.creation_timestamp = Some(Time(created));
+        let ready_at = created + chrono::Duration::seconds(secs);
+        vm.status = Some(
+            serde_json::from_value(serde_json::json!({
+                "ready": true,
+                "conditions": [{
+                    "type": "Ready",
+                    "status": "True",
+                    "lastTransitionTime": ready_at.to_rfc3339(),
+                }],
+            }))
+            .unwrap(),
+        );
+        vm
+    }
+
+    #[test]
+    fn test_puller_daemon_set() {
+        let images = vec!["quay.io/containerdisks/fedora:latest".to_string()];
+        let selector =
+            BTreeMap::from([("kubevirt.io/schedulable".to_string(), "true".to_string())]);
+        let ds = puller_daemon_set("vms", &images, &selector);
+
+        let pod = ds.spec.unwrap().template.spec.unwrap();
+        let init = pod.init_containers.unwrap();
+        assert_eq!(init.len(), 2);
+        assert_eq!(init[1].image.as_deref(), Some(images[0].as_str()));
+        assert_eq!(
+            init[1].command.as_ref().unwrap()[0],
+            format!("{BIN_PATH}/busybox")
+        );
+        assert_eq!(pod.node_selector, Some(selector));
+        assert!(crate::k8s::is_managed(ds.metadata.labels.as_ref()));
+    }
+
+    #[test]
+    fn test_readiness_summary() {
+        assert_eq!(readiness_summary(&[]), None);
+
+        let vms = vec![
+            ready_vm("a", true, 20),
+            ready_vm("b", true, 22),
+            ready_vm("c", false, 84),
+        ];
+        assert!(is_prepulled(&vms[0]) && !is_prepulled(&vms[2]));
+        assert_eq!(
+            readiness_summary(&vms).unwrap(),
+            "pre-pulled 21.0s avg (2 VMs) vs cold 84.0s avg (1 VM), 75% faster"
+        );
+        assert_eq!(
+            readiness_summary(&vms[2..]).unwrap(),
+            "cold 84.0s avg (1 VM)"
+        );
+    }
+}
@@ -1,14 +1,21 @@
 //! SSH connectivity for KubeVirt VMs
 //!
 //! Provides SSH client for connecting to and executing commands in VMs.
+//! Commands go through the system OpenSSH client (`ssh`, `scp`, `ssh-keygen`),
+//! so keys, agents and `~/.ssh/config` work as they do on the command line.
+//! It runs in batch mode, so authentication must be key-based (a private key
+//! or an agent); password logins are not supported.
 
 use anyhow::{Context, Result};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
 use std::time::Duration;
 use tokio::process::Command;
 use tokio::time::sleep;
 use tracing::{debug, info, warn};
 
+use crate::http::authority;
+
 /// SSH client configuration
 #[derive(Clone, Debug)]
 pub struct SshConfig {
@@ -18,9 +25,6 @@ pub struct SshConfig {
     /// SSH private key path
     pub private_key_path: Option<PathBuf>,
 
-    /// SSH password (if not using key)
-    pub password: Option<String>,
-
     /// SSH port
     pub port: u16,
 
@@ -42,7 +46,6 @@ impl Default for SshConfig {
         Self {
             username: "fedora".to_string(),
             private_key_path: None,
-            password: None,
             port: 22,
             timeout_secs: 30,
             strict_host_key_checking: false,
@@ -67,12 +70,6 @@ impl SshConfig {
         self
     }
 
-    /// Set password
-    pub fn password(mut self, password: impl Into<String>) -> Self {
-        self.password = Some(password.into());
-        self
-    }
-
     /// Set port
     pub fn port(mut self, port: u16) -> Self {
         self.port = port;
@@ -110,8 +107,8 @@ impl SshClient {
         }
     }
 
-    /// Build SSH command arguments
-    fn build_ssh_args(&self, host: &str) -> Vec<String> {
+    /// Options shared by `ssh` and `scp`
+    fn common_args(&self) -> Vec<String> {
         let mut args = vec![
             "-o".to_string(),
             format!(
@@ -130,19 +127,38 @@ impl SshClient {
             "BatchMode=yes".to_string(),
             "-o".to_string(),
             "LogLevel=ERROR".to_string(),
-            "-p".to_string(),
-            self.config.port.to_string(),
         ];
 
         if let Some(ref key_path) = self.config.private_key_path {
             args.push("-i".to_string());
             args.push(key_path.to_string_lossy().to_string());
         }
+        args
+    }
 
+    /// Build SSH command arguments
+    fn build_ssh_args(&self, host: &str) -> Vec<String> {
+        let mut args = self.common_args();
+        args.push("-p".to_string());
+        args.push(self.config.port.to_string());
         args.push(format!("{}@{}", self.config.username, host));
         args
     }
 
+    /// Build SCP command arguments; `scp` takes the port as `-P`
+    fn build_scp_args(&self, from: String, to: String) -> Vec<String> {
+        let mut args = self.common_args();
+        args.push("-P".to_string());
+        args.push(self.config.port.to_string());
+        args.push(from);
+        args.push(to);
+        args
+    }
+
+    fn remote_path(&self, host: &str, path: &str) -> String {
+        format!("{}@{}:{}", self.config.username, host, path)
+    }
+
     /// Test SSH connectivity
     pub async fn test_connection(&self, host: &str) -> Result<bool> {
         debug!(
@@ -256,39 +272,88 @@ impl SshClient {
         }
     }
 
-    /// Copy file to remote host via SCP
-    pub async fn scp_to(&self, host: &str, local_path: &str, remote_path: &str) -> Result<()> {
+    /// Execute a command over SSH, failing unless it exits with 0
+    pub async fn exec_checked(&self, host: &str, command: &str) -> Result<String> {
+        let output = self.exec(host, command).await?;
+        if !output.is_success() {
+            anyhow::bail!(
+                "`{command}` failed on {host} (exit code {}): {}",
+                output.exit_code,
+                output.stderr.trim()
+            );
+        }
+        Ok(output.stdout)
+    }
+
+    /// Execute a command over SSH with its output going to this terminal;
+    /// returns the remote exit code
+    pub async fn exec_passthrough(&self, host: &str, command: &str) -> Result<i32> {
+        debug!("Executing SSH command on {}: {}", host, command);
+
+        let mut args = self.build_ssh_args(host);
+        args.push(command.to_string());
+
+        let status = Command::new("ssh")
+            .args(&args)
+            .stdin(Stdio::null())
+            .status()
+            .await
+            .context("Failed to execute SSH command")?;
+        Ok(status.code().unwrap_or(-1))
+    }
+
+    /// Copy a local file to the remote host
+    pub async fn upload(&self, host: &str, local: &Path, remote: &str) -> Result<()> {
         debug!(
-            "SCP {} -> {}@{}:{}",
-            local_path, self.config.username, host, remote_path
+            "SCP {} -> {}",
+            local.display(),
+            self.remote_path(host, remote)
         );
+        let args = self.build_scp_args(
+            local.to_string_lossy().to_string(),
+            self.remote_path(host, remote),
+        );
+        self.scp(&args)
+            .await
+            .with_context(|| format!("Failed to upload {} to {host}", local.display()))
+    }
 
-        let mut args = vec![
-            "-o".to_string(),
-            format!(
-                "StrictHostKeyChecking={}",
-                if self.config.strict_host_key_checking {
-                    "yes"
-                } else {
-                    "no"
-                }
-            ),
-            "-o".to_string(),
-            "UserKnownHostsFile=/dev/null".to_string(),
-            "-P".to_string(),
-            self.config.port.to_string(),
-        ];
-
-        if let Some(ref key_path) = self.config.private_key_path {
-            args.push("-i".to_string());
-            args.push(key_path.to_string_lossy().to_string());
-        }
+    /// Copy a file from the remote host
+    pub async fn download(&self, host: &str, remote: &str, local: &Path) -> Result<()> {
+        debug!(
+            "SCP {} -> {}",
+            self.remote_path(host, remote),
+            local.display()
+        );
+        let args = self.build_scp_args(
+            self.remote_path(host, remote),
+            local.to_string_lossy().to_string(),
+        );
+        self.scp(&args)
+            .await
+            .with_context(|| format!("Failed to download {remote} from {host}"))
+    }
 
-        args.push(local_path.to_string());
-        args.push(format!("{}@{}:{}", self.config.username, host, remote_path));
+    /// Copy a directory from the remote host to `local`, which must not exist
+    pub async fn download_dir(&self, host: &str, remote: &str, local: &Path) -> Result<()> {
+        debug!(
+            "SCP -r {} -> {}",
+            self.remote_path(host, remote),
+            local.display()
+        );
+        let mut args = vec!["-r".to_string()];
+        args.extend(self.build_scp_args(
+            self.remote_path(host, remote),
+            local.to_string_lossy().to_string(),
+        ));
+        self.scp(&args)
+            .await
+            .with_context(|| format!("Failed to download {remote} from {host}"))
+    }
 
+    async fn scp(&self, args: &[String]) -> Result<()> {
         let output = Command::new("scp")
-            .args(&args)
+            .args(args)
             .output()
             .await
             .context("Failed to execute SCP command")?;
@@ -297,53 +362,54 @@ impl SshClient {
             Ok(())
         } else {
             let stderr = String::from_utf8_lossy(&output.stderr);
-            anyhow::bail!("SCP failed: {stderr}")
+            anyhow::bail!("SCP failed: {}", stderr.trim())
         }
     }
 
-    /// Copy file from remote host via SCP
-    pub async fn scp_from(&self, host: &str, remote_path: &str, local_path: &str) -> Result<()> {
-        debug!(
-            "SCP {}@{}:{} -> {}",
-            self.config.username, host, remote_path, local_path
-        );
+    /// Wait for a file to exist on the remote host
+    pub async fn wait_for_file(&self, host: &str, path: &str, timeout_secs: u64) -> Result<bool> {
+        let start = std::time::Instant::now();
+        let timeout = Duration::from_secs(timeout_secs);
+        let command = format!("test -e {}", shell_quote(path));
 
-        let mut args = vec![
-            "-o".to_string(),
-            format!(
-                "StrictHostKeyChecking={}",
-                if self.config.strict_host_key_checking {
-                    "yes"
-                } else {
-                    "no"
-                }
-            ),
-            "-o".to_string(),
-            "UserKnownHostsFile=/dev/null".to_string(),
-            "-P".to_string(),
-            self.config.port.to_string(),
-        ];
+        loop {
+            if start.elapsed() > timeout {
+                warn!("Timeout waiting for {} on {}", path, host);
+                return Ok(false);
+            }
 
-        if let Some(ref key_path) = self.config.private_key_path {
-            args.push("-i".to_string());
-            args.push(key_path.to_string_lossy().to_string());
+            match self.exec(host, &command).await {
+                Ok(output) if output.is_success() => return Ok(true),
+                Ok(_) => debug!("{} not yet on {}", path, host),
+                Err(e) => debug!("SSH exec error: {}", e),
+            }
+            sleep(Duration::from_secs(self.config.retry_delay_secs)).await;
         }
+    }
 
-        args.push(format!("{}@{}:{}", self.config.username, host, remote_path));
-        args.push(local_path.to_string());
-
-        let output = Command::new("scp")
-            .args(&args)
+    /// Public half of the configured private key, for authorizing it in VMs
+    pub async fn public_key(&self) -> Result<Option<String>> {
+        let Some(key_path) = &self.config.private_key_path else {
+            return Ok(None);
+        };
+        let output = Command::new("ssh-keygen")
+            .arg("-y")
+            .arg("-f")
+            .arg(key_path)
             .output()
             .await
-            .context("Failed to execute SCP command")?;
-
-        if output.status.success() {
-            Ok(())
-        } else {
+            .context("Failed to execute ssh-keygen")?;
+        if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
-            anyhow::bail!("SCP failed: {stderr}")
+            anyhow::bail!(
+                "Failed to derive the public key of {}: {}",
+                key_path.display(),
+                stderr.trim()
+            );
         }
+        Ok(Some(
+            String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        ))
     }
 
     /// Create an interactive SSH session
@@ -404,13 +470,13 @@ impl SshSession {
     }
 
     /// Copy file to remote
-    pub async fn upload(&self, local: &str, remote: &str) -> Result<()> {
-        self.client.scp_to(&self.host, local, remote).await
+    pub async fn upload(&self, local: &Path, remote: &str) -> Result<()> {
+        self.client.upload(&self.host, local, remote).await
     }
 
     /// Copy file from remote
-    pub async fn download(&self, remote: &str, local: &str) -> Result<()> {
-        self.client.scp_from(&self.host, remote, local).await
+    pub async fn download(&self, remote: &str, local: &Path) -> Result<()> {
+        self.client.download(&self.host, remote, local).await
     }
 
     /// Run curl command on remote host
@@ -468,7 +534,7 @@ impl SshSession {
         path: &str,
         hostname: Option<&str>,
     ) -> Result<GatewayTestResult> {
-        let url = format!("http://{gateway_ip}:{port}{path}");
+        let url = format!("http://{}{path}", authority(gateway_ip, port));
 
         let curl_cmd = if let Some(host) = hostname {
             format!(
@@ -502,6 +568,24 @@ impl SshSession {
     }
 }
 
+/// Shell command line running `args`, quoted for the remote shell
+pub fn shell_command(args: &[String]) -> String {
+    args.iter()
+        .map(|a| shell_quote(a))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Quote an argument for POSIX shells when it contains special characters
+pub(crate) fn shell_quote(arg: &str) -> String {
+    let safe = |c: char| c.is_ascii_alphanumeric() || "-_./:=,@%+".contains(c);
+    if !arg.is_empty() && arg.chars().all(safe) {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
 /// Result of a gateway test from VM
 #[derive(Clone, Debug)]
 pub struct GatewayTestResult {
@@ -542,6 +626,42 @@ mod tests {
         assert!(args.contains(&"fedora@192.168.1.100".to_string()));
     }
 
+    #[test]
+    fn test_scp_args() {
+        let client = SshClient::new(
+            SshConfig::new("fedora")
+                .port(2222)
+                .private_key("/tmp/id_ed25519"),
+        );
+        let args = client.build_scp_args(
+            "/tmp/a".to_string(),
+            client.remote_path("10.0.0.5", "/tmp/b"),
+        );
+
+        // scp takes the port as -P; -p would mean "preserve times"
+        let port = args.iter().position(|a| a == "-P").unwrap();
+        assert_eq!(args[port + 1], "2222");
+        assert!(!args.contains(&"-p".to_string()));
+        assert!(args.contains(&"/tmp/id_ed25519".to_string()));
+        assert_eq!(args[args.len() - 2..], ["/tmp/a", "fedora@10.0.0.5:/tmp/b"]);
+    }
+
+    #[test]
+    fn test_shell_command() {
+        let args = ["ls", "-la", "/var/lib/gateway poc", "it's"].map(String::from);
+        assert_eq!(
+            shell_command(&args),
+            r"ls -la '/var/lib/gateway poc' 'it'\''s'"
+        );
+    }
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("/api"), "/api");
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+        assert_eq!(shell_quote(""), "''");
+    }
+
     #[test]
     fn test_ssh_output() {
         let output = SshOutput {
@@ -3,8 +3,9 @@
 //! Provides CRUD operations for KubeVirt VirtualMachine resources.
 
 use anyhow::{Context, Result};
+use k8s_openapi::api::core::v1::Secret;
 use kube::api::{Api, DeleteParams, ListParams, Patch, PatchParams, PostParams};
-use kube::CustomResource;
+use kube::{CustomResource, Resource};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
@@ -12,7 +13,8 @@ use std::time::Duration;
 use tokio::time::sleep;
 use tracing::{debug, info, warn};
 
-use crate::k8s::K8sClient;
+use super::provision::{CloudConfig, Provision, PROVISION_ANNOTATION};
+use crate::k8s::{owner_labels, K8sClient};
 
 /// VirtualMachine custom resource specification
 #[derive(CustomResource, Clone, Debug, Serialize, Deserialize, Default, JsonSchema)]
@@ -506,7 +508,10 @@ pub struct VmConfig {
     pub image: String,
     pub ssh_public_key: Option<String>,
     pub labels: BTreeMap<String, String>,
+    pub annotations: BTreeMap<String, String>,
     pub network_type: NetworkType,
+    pub provision: Vec<Provision>,
+    pub run_commands: Vec<String>,
 }
 
 /// Network type for VM
@@ -528,8 +533,11 @@ impl VmConfig {
             memory: "1Gi".to_string(),
             image: "quay.io/containerdisks/fedora:latest".to_string(),
             ssh_public_key: None,
-            labels: BTreeMap::new(),
+            labels: owner_labels(),
+            annotations: BTreeMap::new(),
             network_type: NetworkType::Masquerade,
+            provision: Vec::new(),
+            run_commands: Vec::new(),
         }
     }
 
@@ -563,28 +571,51 @@ impl VmConfig {
         self
     }
 
+    /// Add annotation
+    pub fn annotation(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.annotations.insert(key.into(), value.into());
+        self
+    }
+
     /// Set network type
     pub fn network(mut self, network_type: NetworkType) -> Self {
         self.network_type = network_type;
         self
     }
 
+    /// Install `step` at first boot (see [`Provision`])
+    pub fn provision(mut self, step: Provision) -> Self {
+        self.provision.push(step);
+        self
+    }
+
+    /// Run a shell command as root at first boot, after the provisioning
+    /// steps
+    pub fn run_command(mut self, command: impl Into<String>) -> Self {
+        self.run_commands.push(command.into());
+        self
+    }
+
     /// Build the VirtualMachine resource
-    pub fn build(self) -> VirtualMachine {
-        let has_ssh_key = self.ssh_public_key.is_some();
-        let cloud_init = self.ssh_public_key.map(|key| {
-            let user_data = format!(
-                r#"#cloud-config
-user: fedora
-password: fedora
-chpasswd:
-  expire: false
-ssh_authorized_keys:
-  - {key}
-"#
-            );
+    pub fn build(mut self) -> VirtualMachine {
+        let has_cloud_init = self.ssh_public_key.is_some()
+            || !self.provision.is_empty()
+            || !self.run_commands.is_empty();
+        if !self.provision.is_empty() {
+            let steps: Vec<_> = self.provision.iter().map(Provision::name).collect();
+            self.annotations
+                .insert(PROVISION_ANNOTATION.to_string(), steps.join(","));
+        }
+        let cloud_init = has_cloud_init.then(|| {
+            let mut config = CloudConfig {
+                user: "fedora".to_string(),
+                password: "fedora".to_string(),
+                ssh_authorized_keys: self.ssh_public_key.into_iter().collect(),
+                ..Default::default()
+            };
+            config.provision(&self.provision, &self.run_commands);
             CloudInitNoCloud {
-                user_data: Some(user_data),
+                user_data: Some(config.to_user_data()),
                 network_data: None,
                 secret_ref: None,
             }
@@ -649,7 +680,7 @@ ssh_authorized_keys:
             ..Default::default()
         }];
 
-        if has_ssh_key {
+        if has_cloud_init {
             disks.push(Disk {
                 name: "cloudinit".to_string(),
                 disk: Some(DiskTarget {
@@ -668,6 +699,11 @@ ssh_authorized_keys:
                 } else {
                     Some(self.labels)
                 },
+                annotations: if self.annotations.is_empty() {
+                    None
+                } else {
+                    Some(self.annotations)
+                },
                 ..Default::default()
             },
             spec: VirtualMachineSpec {
@@ -704,7 +740,93 @@ ssh_authorized_keys:
     }
 }
 
+impl VirtualMachine {
+    /// Container disk images the VM boots from
+    pub fn container_disk_images(&self) -> Vec<&str> {
+        self.spec
+            .template
+            .spec
+            .volumes
+            .iter()
+            .filter_map(|v| v.container_disk.as_ref())
+            .map(|d| d.image.as_str())
+            .collect()
+    }
+
+    /// Time from creation until the Ready condition last became true
+    pub fn time_to_ready(&self) -> Option<Duration> {
+        let created = self.metadata.creation_timestamp.as_ref()?.0;
+        let ready = self
+            .status
+            .as_ref()?
+            .conditions
+            .iter()
+            .find(|c| c.condition_type == "Ready" && c.status == "True")?;
+        let ready_at = chrono::DateTime::parse_from_rfc3339(ready.last_transition_time.as_ref()?)
+            .ok()?
+            .with_timezone(&chrono::Utc);
+        (ready_at - created).to_std().ok()
+    }
+}
+
+/// Merge patch setting the VMI template's CPU cores and guest memory
+fn resize_patch(cpu: Option<u32>, memory: Option<&str>) -> serde_json::Value {
+    let mut domain = serde_json::Map::new();
+    if let Some(cores) = cpu {
+        domain.insert("cpu".to_string(), serde_json::json!({ "cores": cores }));
+    }
+    if let Some(guest) = memory {
+        domain.insert("memory".to_string(), serde_json::json!({ "guest": guest }));
+    }
+    serde_json::json!({
+        "spec": {
+            "template": {
+                "spec": {
+                    "domain": domain
+                }
+            }
+        }
+    })
+}
+
+/// Key of the cloud-init user data in a VM's user-data Secret
+const USER_DATA_KEY: &str = "userdata";
+
+/// Name of the Secret holding the cloud-init user data of VM `name`
+fn user_data_secret_name(name: &str) -> String {
+    format!("{name}-cloudinit")
+}
+
+/// Move the VM's inline cloud-init user data into a Secret the volume
+/// references, so it is not readable by everyone who can read VMs
+fn user_data_secret(vm: &mut VirtualMachine) -> Option<Secret> {
+    let name = user_data_secret_name(vm.metadata.name.as_deref()?);
+    let user_data = vm
+        .spec
+        .template
+        .spec
+        .volumes
+        .iter_mut()
+        .filter_map(|volume| volume.cloud_init_no_cloud.as_mut())
+        .find_map(|ci| {
+            let user_data = ci.user_data.take()?;
+            ci.secret_ref = Some(SecretRef { name: name.clone() });
+            Some(user_data)
+        })?;
+    Some(Secret {
+        metadata: kube::api::ObjectMeta {
+            name: Some(name),
+            namespace: vm.metadata.namespace.clone(),
+            labels: vm.metadata.labels.clone(),
+            ..Default::default()
+        },
+        string_data: Some(BTreeMap::from([(USER_DATA_KEY.to_string(), user_data)])),
+        ..Default::default()
+    })
+}
+
 /// VirtualMachine manager
+#[derive(Clone)]
 pub struct VirtualMachineManager {
     client: K8sClient,
 }
@@ -719,12 +841,63 @@ impl VirtualMachineManager {
         Api::namespaced(self.client.client().clone(), namespace)
     }
 
+    fn secret_api(&self, namespace: &str) -> Api<Secret> {
+        Api::namespaced(self.client.client().clone(), namespace)
+    }
+
     /// Create a VirtualMachine
+    ///
+    /// Inline cloud-init user data goes into a Secret first (see
+    /// [`user_data_secret`]), owned by the VM so it is deleted with it.
     pub async fn create(&self, vm: &VirtualMachine, namespace: &str) -> Result<VirtualMachine> {
-        let api = self.api(namespace);
-        api.create(&PostParams::default(), vm)
+        let mut vm = vm.clone();
+        let secret = user_data_secret(&mut vm);
+        let secrets = self.secret_api(namespace);
+        if let Some(secret) = &secret {
+            let name = secret.metadata.name.as_deref().unwrap_or_default();
+            secrets
+                .patch(
+                    name,
+                    &PatchParams::apply("gateway-poc").force(),
+                    &Patch::Apply(secret),
+                )
+                .await
+                .with_context(|| {
+                    format!("Failed to create cloud-init Secret {namespace}/{name}")
+                })?;
+        }
+
+        let created = match self
+            .api(namespace)
+            .create(&PostParams::default(), &vm)
             .await
-            .context("Failed to create VirtualMachine")
+        {
+            Ok(created) => created,
+            Err(e) => {
+                if let Some(name) = secret.as_ref().and_then(|s| s.metadata.name.as_deref()) {
+                    if let Err(e) = secrets.delete(name, &DeleteParams::default()).await {
+                        warn!("Failed to delete cloud-init Secret {namespace}/{name}: {e}");
+                    }
+                }
+                return Err(e).context("Failed to create VirtualMachine");
+            }
+        };
+
+        if let (Some(secret), Some(owner)) = (&secret, created.controller_owner_ref(&())) {
+            let name = secret.metadata.name.as_deref().unwrap_or_default();
+            let patch = serde_json::json!({
+                "metadata": {
+                    "ownerReferences": [owner]
+                }
+            });
+            secrets
+                .patch(name, &PatchParams::default(), &Patch::Merge(&patch))
+                .await
+                .with_context(|| {
+                    format!("Failed to set owner of cloud-init Secret {namespace}/{name}")
+                })?;
+        }
+        Ok(created)
     }
 
     /// Get a VirtualMachine
@@ -749,6 +922,17 @@ impl VirtualMachineManager {
         api.delete(name, &DeleteParams::default())
             .await
             .context("Failed to delete VirtualMachine")?;
+        // Owned by the VM, but garbage collection is asynchronous and a VM
+        // recreated under the same name must not find the old Secret
+        let secret = user_data_secret_name(name);
+        match self
+            .secret_api(namespace)
+            .delete(&secret, &DeleteParams::default())
+            .await
+        {
+            Ok(_) | Err(kube::Error::Api(kube::error::ErrorResponse { code: 404, .. })) => {}
+            Err(e) => warn!("Failed to delete cloud-init Secret {namespace}/{secret}: {e}"),
+        }
         info!("Deleted VirtualMachine {}/{}", namespace, name);
         Ok(())
     }
@@ -783,14 +967,129 @@ impl VirtualMachineManager {
         Ok(())
     }
 
-    /// Restart a VirtualMachine
-    pub async fn restart(&self, name: &str, namespace: &str) -> Result<()> {
+    /// Restart a VirtualMachine, waiting for the old VMI to go away before
+    /// starting it again
+    pub async fn restart(&self, name: &str, namespace: &str, timeout_secs: u64) -> Result<()> {
         self.stop(name, namespace).await?;
-        sleep(Duration::from_secs(2)).await;
+        if !self.wait_stopped(name, namespace, timeout_secs).await? {
+            anyhow::bail!("VirtualMachine {namespace}/{name} did not stop within {timeout_secs}s");
+        }
         self.start(name, namespace).await?;
         Ok(())
     }
 
+    /// Change the CPU cores and/or guest memory of a VirtualMachine
+    ///
+    /// Only the template changes; a running VM keeps its old size until it
+    /// is restarted.
+    pub async fn resize(
+        &self,
+        name: &str,
+        namespace: &str,
+        cpu: Option<u32>,
+        memory: Option<&str>,
+    ) -> Result<()> {
+        let api = self.api(namespace);
+        let patch = resize_patch(cpu, memory);
+        api.patch(name, &PatchParams::default(), &Patch::Merge(&patch))
+            .await
+            .context("Failed to resize VirtualMachine")?;
+        info!("Resized VirtualMachine {}/{}", namespace, name);
+        Ok(())
+    }
+
+    /// Whether the VirtualMachine has a running instance
+    pub async fn is_running(&self, name: &str, namespace: &str) -> Result<bool> {
+        let vm = self.get(name, namespace).await?;
+        Ok(vm.status.is_some_and(|s| s.created))
+    }
+
+    /// Add or change labels of a VirtualMachine
+    pub async fn label(
+        &self,
+        name: &str,
+        namespace: &str,
+        labels: &BTreeMap<String, String>,
+    ) -> Result<()> {
+        let api = self.api(namespace);
+        let patch = serde_json::json!({
+            "metadata": {
+                "labels": labels
+            }
+        });
+        api.patch(name, &PatchParams::default(), &Patch::Merge(&patch))
+            .await
+            .context("Failed to label VirtualMachine")?;
+        Ok(())
+    }
+
+    /// Wait for a deleted VirtualMachine to be gone
+    pub async fn wait_deleted(
+        &self,
+        name: &str,
+        namespace: &str,
+        timeout_secs: u64,
+    ) -> Result<bool> {
+        let start = std::time::Instant::now();
+        let timeout = Duration::from_secs(timeout_secs);
+
+        loop {
+            if start.elapsed() > timeout {
+                warn!(
+                    "Timeout waiting for VirtualMachine {}/{} to be deleted",
+                    namespace, name
+                );
+                return Ok(false);
+            }
+
+            let vm = self
+                .api(namespace)
+                .get_opt(name)
+                .await
+                .context("Failed to get VirtualMachine")?;
+            if vm.is_none() {
+                return Ok(true);
+            }
+
+            sleep(Duration::from_secs(2)).await;
+        }
+    }
+
+    /// Wait for the VM's instance to be gone
+    pub async fn wait_stopped(
+        &self,
+        name: &str,
+        namespace: &str,
+        timeout_secs: u64,
+    ) -> Result<bool> {
+        let start = std::time::Instant::now();
+        let timeout = Duration::from_secs(timeout_secs);
+
+        loop {
+            if start.elapsed() > timeout {
+                warn!(
+                    "Timeout waiting for VirtualMachine {}/{} to stop",
+                    namespace, name
+                );
+                return Ok(false);
+            }
+
+            let vm = self.get(name, namespace).await?;
+            match &vm.status {
+                Some(status) if status.created => debug!(
+                    "VirtualMachine {}/{} status: {:?}",
+                    namespace, name, status.printable_status
+                ),
+                _ => {
+                    info!("VirtualMachine {}/{} is stopped", namespace, name);
+                    return Ok(true);
+                }
+            }
+
+            sleep(Duration::from_secs(2)).await;
+        }
+    }
+
     /// Wait for VM to be ready
     pub async fn wait_ready(&self, name: &str, namespace: &str, timeout_secs: u64) -> Result<bool> {
         let start = std::time::Instant::now();
@@ -838,6 +1137,7 @@ impl VirtualMachineManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::kubevirt::provision::HELM_VERSION;
 
     #[test]
     fn test_vm_config_builder() {
@@ -851,6 +1151,33 @@ mod tests {
         assert_eq!(vm.metadata.name.as_deref(), Some("test-vm"));
         assert_eq!(vm.metadata.namespace.as_deref(), Some("default"));
         assert!(vm.spec.running);
+        assert!(vm.metadata.annotations.is_none());
+        assert_eq!(
+            vm.container_disk_images(),
+            vec!["quay.io/containerdisks/ubuntu:latest"]
+        );
+    }
+
+    #[test]
+    fn test_time_to_ready() {
+        let mut vm = VmConfig::new("test-vm", "default").build();
+        assert_eq!(vm.time_to_ready(), None);
+
+        vm.metadata.creation_timestamp =
+            Some(k8s_openapi::apimachinery::pkg::apis::meta::v1::Time(
+                "2025-01-01T12:00:00Z".parse().unwrap(),
+            ));
+        vm.status = Some(VirtualMachineStatus {
+            ready: true,
+            conditions: vec![VmCondition {
+                condition_type: "Ready".to_string(),
+                status: "True".to_string(),
+                last_transition_time: Some("2025-01-01T12:00:42Z".to_string()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        });
+        assert_eq!(vm.time_to_ready(), Some(Duration::from_secs(42)));
     }
 
     #[test]
@@ -865,6 +1192,63 @@ mod tests {
             .is_some());
     }
 
+    #[test]
+    fn test_resize_patch() {
+        let patch = resize_patch(Some(8), Some("16Gi"));
+        let domain = &patch["spec"]["template"]["spec"]["domain"];
+        assert_eq!(domain["cpu"]["cores"], 8);
+        assert_eq!(domain["memory"]["guest"], "16Gi");
+
+        // Unset sizes are left alone rather than cleared
+        let patch = resize_patch(None, Some("4Gi"));
+        let domain = &patch["spec"]["template"]["spec"]["domain"];
+        assert!(domain.get("cpu").is_none());
+        assert_eq!(domain["memory"]["guest"], "4Gi");
+    }
+
+    #[test]
+    fn test_vm_config_with_provision() {
+        let vm = VmConfig::new("k3s-vm", "default")
+            .provision(Provision::K3s)
+            .run_command("echo done")
+            .build();
+
+        let annotations = vm.metadata.annotations.unwrap();
+        assert_eq!(annotations[PROVISION_ANNOTATION], "k3s");
+        let spec = &vm.spec.template.spec;
+        assert_eq!(spec.domain.devices.disks.len(), 2);
+        let user_data = spec.volumes[1]
+            .cloud_init_no_cloud
+            .as_ref()
+            .and_then(|ci| ci.user_data.as_deref())
+            .unwrap();
+        assert!(user_data.contains(HELM_VERSION));
+        assert!(user_data.contains("echo done"));
+        assert!(!user_data.contains("ssh_authorized_keys"));
+    }
+
+    #[test]
+    fn test_user_data_secret() {
+        let mut vm = VmConfig::new("k3s-vm", "tests")
+            .provision(Provision::K3s)
+            .build();
+        let secret = user_data_secret(&mut vm).unwrap();
+
+        assert_eq!(secret.metadata.name.as_deref(), Some("k3s-vm-cloudinit"));
+        assert_eq!(secret.metadata.namespace.as_deref(), Some("tests"));
+        assert!(secret.string_data.unwrap()[USER_DATA_KEY].starts_with("#cloud-config\n"));
+        let ci = vm.spec.template.spec.volumes[1]
+            .cloud_init_no_cloud
+            .as_ref()
+            .unwrap();
+        assert!(ci.user_data.is_none());
+        assert_eq!(ci.secret_ref.as_ref().unwrap().name, "k3s-vm-cloudinit");
+
+        // Nothing to move out of VMs without cloud-init
+        let mut plain = VmConfig::new("plain-vm", "tests").build();
+        assert!(user_data_secret(&mut plain).is_none());
+    }
+
     #[test]
     fn test_network_types() {
         let masq_vm = VmConfig::new("masq-vm", "default")
@@ -1,12 +1,13 @@
 //! Gateway API PoC - Kubernetes Gateway Implementation Comparison Tool
 //!
-//! A CLI tool for testing and comparing 7 Gateway API implementations
+//! A CLI tool for testing and comparing Gateway API implementations
 //! with KubeVirt virtualization support for AMD64 components on ARM64 hosts.
 //!
 //! ## Features
 //!
-//! - 17 comprehensive test cases covering routing, TLS, traffic management
-//! - Support for 7 Gateway implementations (NGINX, Envoy, Istio, Cilium, Kong, Traefik, kgateway)
+//! - Test catalog covering routing, TLS, traffic management, protocol handling and resilience
+//! - Support for 9 Gateway implementations (NGINX, Envoy, Istio, Cilium, Kong, Traefik, kgateway,
+//!   Contour, APISIX), plus user-defined ones
 //! - Parallel test execution
 //! - Multiple output formats (Table, JSON, CSV)
 //! - KubeVirt VM management for AMD64 testing
@@ -31,12 +32,12 @@
 //! gateway-poc vm status
 //! ```
 
-use anyhow::Result;
-use clap::Parser;
-use tracing::{info, Level};
-use tracing_subscriber::FmtSubscriber;
+use anyhow::{Context, Result};
+use std::io::IsTerminal;
+use tracing::{debug, info, warn};
 
 mod benchmark;
+mod chaos;
 mod cli;
 mod config;
 mod deploy;
@@ -47,7 +48,9 @@ mod kubevirt;
 mod models;
 mod output;
 mod results;
+mod serve;
 mod tests;
+mod tui;
 mod utils;
 
 use cli::Args;
@@ -56,131 +59,1349 @@ use models::{GatewayConfig, GatewayImpl, TestCase, TestConfig};
 use output::{OutputFormat, ResultFormatter};
 
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() -> Result<std::process::ExitCode> {
+    let args = Args::parse_with_sources();
+
     // Initialize logging
-    FmtSubscriber::builder()
-        .with_max_level(Level::INFO)
-        .with_target(false)
-        .compact()
-        .init();
+    let log_format = utils::LogFormat::from_str(&args.log_format)
+        .ok_or_else(|| anyhow::anyhow!("Unknown log format: {}", args.log_format))?;
+    // Console logs would garble the dashboard; keep errors only unless logging to a file
+    let quiet_logs = args.quiet
+        || (args.uses_tui() && args.log_file.is_none() && std::io::stdout().is_terminal());
+    // Replays log everything, by default into a transcript next to the results
+    let replay = args.replay_run();
+    let mut log_config = match replay {
+        Some(_) => utils::LogConfig::from_verbosity(utils::MAX_VERBOSITY, false),
+        None => utils::LogConfig::from_verbosity(args.verbose, quiet_logs),
+    }
+    .with_format(log_format);
+    let log_file = match (&args.log_file, replay) {
+        (Some(path), _) => Some(std::path::PathBuf::from(path)),
+        (None, Some(run_id)) => {
+            Some(results::ResultsStorage::default_dir()?.replay_transcript(run_id)?)
+        }
+        (None, None) => None,
+    };
+    if let Some(path) = &log_file {
+        log_config = log_config.with_file(path);
+    }
+    utils::init_logging(&log_config)?;
 
-    let args = Args::parse();
+    let config_file = match load_config_file() {
+        // The config commands are how a broken file gets fixed
+        Err(e) if matches!(args.command, cli::Command::Config(_)) => {
+            warn!("{:#}", e);
+            None
+        }
+        result => result?,
+    };
+    if let Some(file) = &config_file {
+        k8s::set_api_rate_limit(file.app.kube_api);
+    }
+    let namespace = configured_namespace(args.namespace, config_file.as_ref());
 
+    // Commands that fail without an error (e.g. interrupted runs) set this
+    let mut exit_code = 0;
     match args.command {
         cli::Command::Test(test_args) => {
-            run_tests(test_args).await?;
+            let replay = test_args.replay_failures.is_some();
+            exit_code = run_tests(
+                test_args,
+                &args.given,
+                config_file.as_ref(),
+                namespace.as_deref(),
+                args.quiet,
+            )
+            .await?;
+            if let Some(path) = log_file.filter(|_| replay) {
+                println!("Transcript: {}", path.display());
+            }
         }
         cli::Command::List(list_args) => {
             list_tests(list_args);
         }
         cli::Command::Vm(vm_args) => {
-            manage_vm(vm_args).await?;
+            manage_vm(
+                vm_args,
+                config_file.as_ref(),
+                namespace.as_deref().unwrap_or("default"),
+            )
+            .await?;
         }
         cli::Command::Results(results_args) => {
             show_results(results_args)?;
         }
         cli::Command::Deploy(deploy_args) => {
-            manage_deploy(deploy_args).await?;
+            manage_deploy(
+                deploy_args,
+                &args.given,
+                config_file.as_ref(),
+                namespace.as_deref(),
+            )
+            .await?;
         }
         cli::Command::Benchmark(benchmark_args) => {
-            run_benchmark(benchmark_args).await?;
+            exit_code = run_benchmark(
+                benchmark_args,
+                &args.given,
+                config_file.as_ref(),
+                namespace.as_deref(),
+                args.quiet,
+            )
+            .await?;
         }
         cli::Command::Config(config_args) => {
-            manage_config(config_args)?;
+            manage_config(config_args, config_file.as_ref())?;
+        }
+        cli::Command::Serve(serve_args) => {
+            run_serve(serve_args).await?;
+        }
+        cli::Command::Canary(canary_args) => {
+            exit_code = run_canary(canary_args, namespace.as_deref().unwrap_or("default")).await?;
+        }
+        cli::Command::Docs(docs_args) => {
+            generate_docs(docs_args)?;
+        }
+        cli::Command::Doctor(doctor_args) => {
+            exit_code = run_doctor(doctor_args).await?;
+        }
+        cli::Command::Wait(wait_args) => {
+            wait_for_endpoint(wait_args, args.quiet).await?;
+        }
+        cli::Command::Cleanup(cleanup_args) => {
+            run_cleanup(cleanup_args, namespace.as_deref()).await?;
+        }
+        cli::Command::Headers(headers_args) => {
+            compare_headers(headers_args).await?;
+        }
+        cli::Command::Chaos(chaos_args) => {
+            exit_code = run_chaos(
+                chaos_args,
+                &args.given,
+                config_file.as_ref(),
+                namespace.as_deref(),
+            )
+            .await?;
         }
     }
 
-    Ok(())
+    let throttle = k8s::throttle_stats();
+    if throttle.was_throttled() {
+        warn!(
+            "{}; raise app.kube_api.qps/burst in the config file if runs are slowed down",
+            throttle
+        );
+    }
+
+    Ok(std::process::ExitCode::from(exit_code.clamp(0, 255) as u8))
+}
+
+/// Config file found in the usual locations, if any; one that is found but
+/// does not load is an error rather than silently replaced by defaults
+fn load_config_file() -> Result<Option<config::ConfigFile>> {
+    let Some(path) = config::ConfigFile::find() else {
+        return Ok(None);
+    };
+    // User-defined gateways must be known before any gateway name is parsed
+    let gateways = config::ConfigFile::register_gateways(&path)?;
+    if !gateways.is_empty() {
+        debug!("Registered {} custom gateways", gateways.len());
+    }
+    config::ConfigFile::load(&path)
+        .map(Some)
+        .with_context(|| format!("Invalid config file {}", path.display()))
 }
 
-async fn run_tests(args: cli::TestArgs) -> Result<()> {
-    let implementation = GatewayImpl::from_str(&args.gateway)
-        .ok_or_else(|| anyhow::anyhow!("Unknown gateway: {}", args.gateway))?;
+/// Namespace from `--namespace` (or GATEWAY_POC_NAMESPACE), else the config file
+fn configured_namespace(
+    flag: Option<String>,
+    config_file: Option<&config::ConfigFile>,
+) -> Option<String> {
+    flag.or_else(|| config_file.and_then(|file| file.app.namespace.clone()))
+}
+
+async fn run_serve(args: cli::ServeArgs) -> Result<()> {
+    let token = args.token.filter(|t| !t.is_empty()).ok_or_else(|| {
+        anyhow::anyhow!("An API token is required (--token or GATEWAY_POC_SERVE_TOKEN)")
+    })?;
+
+    let targets = args
+        .targets
+        .iter()
+        .map(|t| serve::ServeTarget::parse(t))
+        .collect::<Result<Vec<_>>>()?;
+
+    let server = serve::Server::new(serve::ServeConfig {
+        bind: args.bind,
+        token,
+        interval_secs: args.interval,
+        concurrent: args.concurrent,
+        targets,
+        public_badges: args.public_badges,
+    });
+    server.run().await
+}
+
+async fn run_canary(args: cli::CanaryArgs, namespace: &str) -> Result<i32> {
+    let scenario =
+        tests::ProgressiveCanary::new(&args.ip, args.port, &args.route, &args.stable, &args.canary)
+            .with_path(&args.path)
+            .with_hostname(&args.hostname)
+            .with_namespace(namespace)
+            .with_rule(args.rule)
+            .with_steps(tests::parse_steps(&args.steps)?)
+            .with_samples(args.samples, args.probe_size)
+            .with_confidence(args.confidence)
+            .with_propagation_timeout(std::time::Duration::from_secs(args.propagation_timeout))
+            .with_rollback(!args.no_rollback);
+
+    let client = http::HttpClient::new()?;
+    let routes = k8s::HTTPRouteManager::new(k8s::K8sClient::new(namespace).await?);
+    let result = scenario.run(&client, &routes).await?;
+
+    match args.format.as_str() {
+        "json" => println!("{}", serde_json::to_string_pretty(&result)?),
+        _ => println!("{}", result.format_table()),
+    }
+
+    Ok(if result.promoted { 0 } else { 1 })
+}
 
-    let gateway_config = GatewayConfig::new(implementation).with_hostname(&args.hostname);
+async fn run_tests(
+    mut args: cli::TestArgs,
+    given: &cli::GivenArgs,
+    config_file: Option<&config::ConfigFile>,
+    namespace: Option<&str>,
+    quiet: bool,
+) -> Result<i32> {
+    if let Some(vm) = args.vm.clone() {
+        return run_tests_in_vm(&vm, args, given, config_file, namespace).await;
+    }
 
-    let config = TestConfig::new(gateway_config).with_rounds(args.rounds);
+    let mut replay_tests = Vec::new();
+    if let Some(run_id) = &args.replay_failures {
+        let storage = results::ResultsStorage::default_dir()?;
+        let run = storage.find(run_id)?;
+        replay_tests = run.failed_tests();
+        if replay_tests.is_empty() {
+            println!("Run {run_id} has no failed tests; nothing to replay");
+            return Ok(0);
+        }
 
-    let gateway_ip = args.ip.as_deref().unwrap_or("127.0.0.1");
+        // Same target as the original run, one sequential round
+        let implementation = run
+            .implementation()
+            .ok_or_else(|| anyhow::anyhow!("Unknown gateway in run {run_id}: {}", run.gateway))?;
+        args.gateway = implementation.short_name().to_string();
+        args.ip = Some(run.gateway_ip.clone());
+        args.hostname = run.config.hostname.clone();
+        args.http_port = run.config.http_port;
+        args.https_port = run.config.https_port;
+        args.timeout = run.config.timeout_secs;
+        args.rounds = 1;
+        args.parallel = false;
+        for group in replay_tests
+            .iter()
+            .filter_map(|n| TestCase::from_number(*n)?.experimental())
+        {
+            if !args.experimental.contains(&group) {
+                args.experimental.push(group);
+            }
+        }
+        info!(
+            "Replaying {} failed test(s) from run {}",
+            replay_tests.len(),
+            run_id
+        );
+    }
+
+    let mut resolved = config::ResolvedConfig::default();
+    if args.replay_failures.is_none() {
+        resolve_gateway(&mut args.gateway, given, config_file, &mut resolved);
+    }
+    let implementation = if args.gateway == "auto" {
+        let gateway = deploy::select_gateway().await?;
+        info!("Detected {} in the cluster", gateway.name());
+        gateway
+    } else {
+        GatewayImpl::from_str(&args.gateway)
+            .ok_or_else(|| anyhow::anyhow!("Unknown gateway: {}", args.gateway))?
+    };
+    let connection = config_file.and_then(|f| f.connection(implementation));
+    if args.replay_failures.is_none() {
+        resolve_connection(&mut args, given, connection, &mut resolved);
+    }
+
+    let gateway_ip = args.ip.clone().unwrap_or_else(|| "127.0.0.1".to_string());
+    let gateway_ip = gateway_ip.as_str();
+    let run_id = results::generate_run_id();
+    let vars = runtime_vars(implementation, gateway_ip, namespace, &run_id);
+    let profile = selected_profile(&args, &vars)?;
+    if args.replay_failures.is_none() {
+        resolve_run(
+            &mut args,
+            given,
+            config_file,
+            connection,
+            profile.as_ref(),
+            &mut resolved,
+        );
+    }
+    if let Some(profile) = &profile {
+        info!("Using test profile {}", profile.name);
+    }
+    debug!("Effective configuration:\n{resolved}");
+
+    let namespace = namespace.unwrap_or("default");
+    let gateway_config = GatewayConfig::new(implementation)
+        .with_namespace(namespace)
+        .with_hostname(&args.hostname)
+        .with_ports(args.http_port, args.https_port, Some(args.grpc_port))
+        .with_policy_setup(args.setup_policies)
+        .with_pod_namespace(args.gateway_namespace.clone())
+        .with_image_pull_secrets(image_pull_secrets(config_file));
+
+    let filter = models::TestFilter::new()
+        .with_tests(match &profile {
+            // --replay-failures and --profile are exclusive
+            Some(profile) => profile.tests.clone(),
+            None => replay_tests,
+        })
+        .with_skip(args.skip.clone())
+        .with_categories(args.only.clone())
+        .with_tags(args.tags.clone())
+        .with_experimental(args.experimental.clone());
+    filter.validate().map_err(|e| anyhow::anyhow!(e))?;
+    if filter.select(TestCase::all()).is_empty() {
+        anyhow::bail!("No tests match the selection ({filter})");
+    }
+    if let Some(test_case) = args.test.and_then(TestCase::from_number) {
+        if let Some(group) = test_case.experimental().filter(|g| !filter.enables(*g)) {
+            anyhow::bail!("{test_case} is experimental; enable it with --experimental {group}");
+        }
+    }
+
+    let dns_overrides = http::DnsOverrides::from_specs(&args.resolve, gateway_ip.parse().ok())
+        .map_err(|e| anyhow::anyhow!(e))?;
+    let dns_overrides = dns_settings(dns_overrides, &args.dns_server, &args.ip_family)?;
+    let connection = http::ConnectionSettings::new(
+        !args.no_keep_alive,
+        args.max_idle_per_host,
+        !args.no_tcp_nodelay,
+    )
+    .with_source_addresses(args.source_address.clone());
+    if !connection.is_default() {
+        info!("Connections: {}", connection);
+    }
+
+    let retry = models::RetryPolicy::new(args.retries, args.retry_delay);
+
+    let load_test = load_test_settings(&args, profile.as_ref())?;
+    let canary = models::CanarySettings::new(args.confidence);
+    let failover = models::FailoverSettings::new(args.failover_fault);
+    let round_plan = profile.map(|p| p.round_plan()).unwrap_or_default();
+    if !round_plan.is_empty() {
+        round_plan.validate()?;
+        if args.test.is_some() {
+            anyhow::bail!("Round variants run the full selection; drop --test");
+        }
+        // Variants set the concurrency of parallel rounds
+        if !args.parallel {
+            anyhow::bail!(
+                "Round variants run in parallel; use --parallel or set `parallel: true` in the profile"
+            );
+        }
+        args.rounds = round_plan.total_rounds();
+        info!(
+            "Round plan: {}",
+            round_plan
+                .variants()
+                .iter()
+                .map(|v| format!("{} x{}", v, v.rounds))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+    let soak = args.duration.map(|duration| {
+        models::SoakSettings::new(duration).with_checkpoint_interval(
+            std::time::Duration::from_secs(args.checkpoint_interval * 60),
+        )
+    });
+    if soak.is_some() {
+        if !round_plan.is_empty() {
+            anyhow::bail!("Round variants need a fixed number of rounds; drop --duration");
+        }
+        args.rounds = 1;
+        args.parallel = true;
+    }
+    let gateway_api = gateway_api_spec(&args).await?;
+    let config = TestConfig::new(gateway_config)
+        .with_rounds(args.rounds)
+        .with_timeout(args.timeout)
+        .with_load_test(load_test.clone())
+        .with_canary(canary.clone())
+        .with_failover(failover.clone())
+        .with_gateway_api(gateway_api)
+        .with_filter(filter.clone())
+        .with_dns_overrides(dns_overrides.clone())
+        .with_connection(connection.clone())
+        .with_retry(retry.clone());
 
     info!(
         "Testing {} Gateway at {} ({} rounds)",
         implementation, gateway_ip, args.rounds
     );
+    debug!(
+        "Ports: http {}, https {}, grpc {}; timeout {}s",
+        args.http_port, args.https_port, args.grpc_port, args.timeout
+    );
+    if !filter.is_empty() {
+        info!("Partial suite: {}", filter);
+    }
+    if !dns_overrides.is_empty() {
+        info!("Resolving {}", dns_overrides);
+    }
+    if retry.is_enabled() {
+        info!(
+            "Retrying failed tests up to {} times ({}ms apart)",
+            retry.retries, retry.delay_ms
+        );
+    }
 
     let formatter =
         ResultFormatter::new(OutputFormat::from_str(&args.format).unwrap_or(OutputFormat::Table));
 
-    if args.parallel {
-        let executor = ParallelExecutor::new(args.concurrent);
+    let probe = if args.capture_env {
+        Some(k8s::ClusterProbe::new(
+            k8s::K8sClient::new(namespace).await?,
+        ))
+    } else {
+        None
+    };
+    let crashes = if args.watch_crashes {
+        Some(crash_watcher(implementation, args.gateway_namespace.as_deref()).await?)
+    } else {
+        None
+    };
+    let diagnostics = match &args.diagnostics {
+        Some(dir) => Some(
+            diagnostics_collector(implementation, args.gateway_namespace.as_deref(), dir).await?,
+        ),
+        None => None,
+    };
+
+    let http_metrics = args.http_metrics.then(http::HttpMetrics::new);
+    let auth = args.auth.clone().map(|provider| {
+        info!("Authenticating requests with {}", provider);
+        http::AuthHeaders::new(provider)
+    });
+
+    // The setup is the one installed when the run starts, not when it is saved
+    let record = new_stored_run(&args, implementation, gateway_ip, &run_id);
+    let cancel = executor::SuiteCancel::new();
+    cancel.cancel_on_ctrl_c();
+
+    let dashboard = if args.tui {
+        start_dashboard(
+            format!("{} Gateway tests", implementation.name()),
+            Some(cancel.clone()),
+        )
+    } else {
+        None
+    };
+    let progress = dashboard.as_ref().map(|d| d.reporter());
+    let tui_active = dashboard.is_some();
+    let quiet = quiet || tui_active;
 
-        if args.rounds > 1 {
-            let batch_runner = BatchRunner::new(args.concurrent, args.rounds);
-            let summaries = batch_runner.run_rounds(gateway_ip, &config.gateway).await?;
+    if let Some(secs) = args.suite_timeout {
+        cancel.timeout_after(std::time::Duration::from_secs(secs));
+    }
+
+    let gate_code;
+    if args.parallel {
+        let mut executor = ParallelExecutor::new(args.concurrent)
+            .with_timeout(args.timeout)
+            .with_load_test(load_test.clone())
+            .with_canary(canary.clone())
+            .with_failover(failover.clone())
+            .with_gateway_api(gateway_api)
+            .with_filter(filter.clone())
+            .with_dns_overrides(dns_overrides.clone())
+            .with_connection(connection.clone())
+            .with_retry(retry.clone())
+            .with_cancellation(cancel.clone());
+        if let Some(progress) = &progress {
+            executor = executor.with_progress(progress.clone());
+        }
+        if let Some(watcher) = &crashes {
+            executor = executor.with_crash_watcher(watcher.clone());
+        }
+        if let Some(collector) = &diagnostics {
+            executor = executor.with_diagnostics(collector.clone());
+        }
+        if let Some(metrics) = &http_metrics {
+            executor = executor.with_http_metrics(metrics.clone());
+        }
+        if let Some(auth) = &auth {
+            executor = executor.with_auth(auth.clone());
+        }
 
-            for summary in &summaries {
-                println!("{}", formatter.format_summary(summary));
+        if soak.is_some() || args.rounds > 1 || !round_plan.is_empty() {
+            let mut batch_runner = BatchRunner::new(args.concurrent, args.rounds)
+                .with_round_plan(round_plan.clone())
+                .with_timeout(args.timeout)
+                .with_load_test(load_test)
+                .with_canary(canary)
+                .with_failover(failover)
+                .with_gateway_api(gateway_api)
+                .with_filter(filter)
+                .with_dns_overrides(dns_overrides)
+                .with_connection(connection)
+                .with_retry(retry)
+                .with_cancellation(cancel.clone());
+            if let Some(probe) = probe {
+                batch_runner = batch_runner.with_cluster_probe(probe);
+            }
+            if let Some(watcher) = crashes {
+                batch_runner = batch_runner.with_crash_watcher(watcher);
             }
+            if let Some(collector) = diagnostics {
+                batch_runner = batch_runner.with_diagnostics(collector);
+            }
+            if let Some(metrics) = http_metrics {
+                batch_runner = batch_runner.with_http_metrics(metrics);
+            }
+            if let Some(auth) = auth {
+                batch_runner = batch_runner.with_auth(auth);
+            }
+            if let Some(progress) = progress {
+                batch_runner = batch_runner.with_progress(progress);
+            }
+            if let Some(soak) = &soak {
+                let (report, latest) = batch_runner
+                    .run_soak(gateway_ip, &config.gateway, soak, |report, latest| {
+                        save_soak(
+                            &record,
+                            report,
+                            Some(latest),
+                            Some("Soak in progress".to_string()),
+                        )
+                    })
+                    .await?;
+                save_soak(
+                    &record,
+                    &report,
+                    latest.as_ref(),
+                    cancel
+                        .reason()
+                        .map(|reason| reason.to_string())
+                        .or_else(|| report.stopped.clone())
+                        .map(|reason| {
+                            warn!("Soak stopped early ({}); saving partial results", reason);
+                            format!("Stopped early: {reason}")
+                        }),
+                );
 
-            let aggregate = BatchRunner::aggregate_results(&summaries);
-            println!(
-                "{}",
-                formatter.format_aggregate(&aggregate, implementation.name())
-            );
+                let aggregate = BatchRunner::aggregate_soak(&report);
+                let mut soak_report = formatter.format_aggregate(&aggregate, implementation.name());
+                soak_report.push('\n');
+                soak_report.push_str(&formatter.format_soak(&report, implementation.name()));
+                close_dashboard(dashboard, &soak_report).await?;
+                println!("{soak_report}");
+                gate_code = enforce_gates(&args.fail_on, &aggregate);
+            } else {
+                let summaries = batch_runner.run_rounds(gateway_ip, &config.gateway).await?;
+                save_run(&record, &summaries, &cancel, args.save);
+
+                if !quiet {
+                    for summary in &summaries {
+                        println!("{}", formatter.format_summary(summary));
+                    }
+                }
+
+                let summaries = if args.exclude_anomalous {
+                    let kept = BatchRunner::exclude_anomalous(summaries);
+                    if kept.is_empty() {
+                        anyhow::bail!("All rounds were anomalous; nothing to aggregate");
+                    }
+                    kept
+                } else {
+                    summaries
+                };
+
+                let aggregate = BatchRunner::aggregate_results(&summaries);
+                let mut report = formatter.format_aggregate(&aggregate, implementation.name());
+                if !round_plan.is_empty() {
+                    let variants = BatchRunner::aggregate_by_variant(&summaries);
+                    report.push('\n');
+                    report.push_str(&formatter.format_variants(&variants, implementation.name()));
+                }
+                close_dashboard(dashboard, &report).await?;
+                println!("{report}");
+                gate_code = enforce_gates(&args.fail_on, &aggregate);
+            }
         } else {
             let summary = executor
                 .run_all_parallel(gateway_ip, &config.gateway)
                 .await?;
-            println!("{}", formatter.format_summary(&summary));
+            save_run(&record, std::slice::from_ref(&summary), &cancel, args.save);
+            let report = formatter.format_summary(&summary);
+            close_dashboard(dashboard, &report).await?;
+            println!("{report}");
+            gate_code = enforce_gates(&args.fail_on, &summary);
         }
     } else {
-        let runner = TestRunner::new(config)?.with_gateway_ip(gateway_ip);
+        let mut runner = TestRunner::new(config)?
+            .with_gateway_ip(gateway_ip)
+            .with_cancellation(cancel.clone());
+        if let Some(probe) = probe {
+            runner = runner.with_cluster_probe(probe);
+        }
+        if let Some(watcher) = crashes {
+            runner = runner.with_crash_watcher(watcher);
+        }
+        if let Some(collector) = diagnostics {
+            runner = runner.with_diagnostics(collector);
+        }
+        if let Some(metrics) = http_metrics {
+            runner = runner.with_http_metrics(metrics);
+        }
+        if let Some(auth) = auth {
+            runner = runner.with_auth(auth);
+        }
+        if let Some(progress) = progress {
+            runner = runner.with_progress(progress);
+        }
 
         if let Some(test_num) = args.test {
             let test_case = TestCase::from_number(test_num)
                 .ok_or_else(|| anyhow::anyhow!("Invalid test number: {test_num}"))?;
             let result = runner.run_test(test_case).await;
-            println!("{}", formatter.format_result(&result));
+            let report = formatter.format_result(&result);
+            close_dashboard(dashboard, &report).await?;
+            println!("{report}");
+            let summary = models::TestRoundSummary::new(1, implementation.name(), vec![result]);
+            gate_code = enforce_gates(&args.fail_on, &summary);
         } else if args.rounds > 1 {
             let summaries = runner.run_rounds(args.rounds).await?;
-            for summary in summaries {
-                println!("{}", formatter.format_summary(&summary));
+            save_run(&record, &summaries, &cancel, args.save);
+            let aggregate = if args.exclude_anomalous {
+                let kept = BatchRunner::exclude_anomalous(summaries.clone());
+                if kept.is_empty() {
+                    anyhow::bail!("All rounds were anomalous; nothing to aggregate");
+                }
+                BatchRunner::aggregate_results(&kept)
+            } else {
+                BatchRunner::aggregate_results(&summaries)
+            };
+            let report = formatter.format_aggregate(&aggregate, implementation.name());
+            close_dashboard(dashboard, &report).await?;
+            if tui_active {
+                println!("{report}");
+            } else if !quiet {
+                for summary in &summaries {
+                    println!("{}", formatter.format_summary(summary));
+                }
             }
+            gate_code = enforce_gates(&args.fail_on, &aggregate);
         } else {
             let summary = runner.run_all().await?;
-            println!("{}", formatter.format_summary(&summary));
+            save_run(&record, std::slice::from_ref(&summary), &cancel, args.save);
+            let report = formatter.format_summary(&summary);
+            close_dashboard(dashboard, &report).await?;
+            println!("{report}");
+            gate_code = enforce_gates(&args.fail_on, &summary);
         }
     }
 
-    Ok(())
+    if gate_code != 0 {
+        return Ok(gate_code);
+    }
+    if cancel.reason() == Some(executor::StopReason::Interrupted) {
+        return Ok(executor::EXIT_INTERRUPTED);
+    }
+    Ok(0)
 }
 
-fn list_tests(args: cli::ListArgs) {
-    println!("\nGateway API Test Cases (17 total)\n");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+/// Options that only make sense on this machine, not on the VM running the
+/// suite; output paths are replaced by paths on the VM copied back later
+const LOCAL_ONLY_TEST_OPTIONS: [&str; 8] = [
+    "vm",
+    "vm-binary",
+    "ssh-user",
+    "ssh-key",
+    "log-file",
+    "output",
+    "o",
+    "diagnostics",
+];
+
+/// Run the `test` command inside a KubeVirt VM and store its runs locally;
+/// returns the remote exit code
+async fn run_tests_in_vm(
+    vm: &str,
+    args: cli::TestArgs,
+    given: &cli::GivenArgs,
+    config_file: Option<&config::ConfigFile>,
+    namespace: Option<&str>,
+) -> Result<i32> {
+    let namespace = namespace.unwrap_or("default");
+    let vmis = kubevirt::VmiManager::new(k8s::K8sClient::new(namespace).await?);
+    let ip = vmis
+        .get_ip(vm, namespace)
+        .await?
+        .with_context(|| format!("VM {vm} has no IP address"))?;
+
+    let key_file = vm_ssh_key(args.ssh_key.as_deref(), config_file, namespace).await?;
+    let mut ssh = kubevirt::SshConfig::new(&args.ssh_user);
+    if let Some(key) = &key_file {
+        ssh = ssh.private_key(key.path());
+    }
 
-    let mut current_category = "";
+    let mut executor = executor::RemoteExecutor::new(kubevirt::SshClient::new(ssh), &ip);
+    if let Some(binary) = &args.vm_binary {
+        executor = executor.with_binary(binary);
+    }
+    if let Some(file) = config_file {
+        executor = executor.with_config(file)?;
+    }
+    if let Some(path) = &args.output {
+        executor = executor.with_output_file("output", path);
+    }
+    if let Some(dir) = &args.diagnostics {
+        executor = executor.with_output_dir("diagnostics", dir);
+    }
+    // Settings taken from GATEWAY_POC_* variables apply on the VM as well
+    for (id, var) in given.env_vars() {
+        if LOCAL_ONLY_TEST_OPTIONS.contains(&id.replace('_', "-").as_str()) {
+            continue;
+        }
+        if let Ok(value) = std::env::var(var) {
+            executor = executor.with_env(var, value);
+        }
+    }
 
-    for test_case in TestCase::all() {
-        let category = test_case.category();
-        if category != current_category {
-            if !current_category.is_empty() {
-                println!();
+    let remote_args = executor::forwarded_args(std::env::args(), &LOCAL_ONLY_TEST_OPTIONS);
+    println!("Running the suite on VM {vm} ({ip})...");
+    let remote = executor.run(&remote_args).await?;
+    print!("{}", remote.output.stdout);
+    eprint!("{}", remote.output.stderr);
+
+    let storage = results::ResultsStorage::default_dir()?;
+    for run in &remote.runs {
+        let path = storage.save(run)?;
+        println!("✓ Stored run {} from {vm} in {}", run.id, path.display());
+    }
+    for path in &remote.outputs {
+        println!("✓ Copied {} from {vm}", path.display());
+    }
+    if !remote.output.is_success() {
+        return Ok(remote.output.exit_code.max(1));
+    }
+    Ok(0)
+}
+
+/// Store suite rounds for `results` with `--save`; runs stopped early are
+/// always stored, marked as partial
+fn save_run(
+    record: &results::StoredTestRun,
+    summaries: &[models::TestRoundSummary],
+    cancel: &executor::SuiteCancel,
+    save: bool,
+) {
+    if !save && cancel.reason().is_none() {
+        return;
+    }
+    let mut run = record.clone();
+    for summary in summaries {
+        run.add_round(summary.round, summary);
+    }
+    run.calculate_aggregate();
+
+    if let Some(reason) = cancel.reason() {
+        warn!("Run stopped early ({}); saving partial results", reason);
+        run.mark_partial(format!("Stopped early: {reason}"));
+    }
+    store_run(&run);
+}
+
+/// Save a soak run's report with its latest round, overwriting the previous
+/// checkpoint; `partial` says why the soak is not complete
+fn save_soak(
+    record: &results::StoredTestRun,
+    report: &models::SoakReport,
+    latest: Option<&models::TestRoundSummary>,
+    partial: Option<String>,
+) {
+    let mut run = record.clone();
+    run.started_at = report.started_at;
+    run.record_soak(report, latest);
+    if let Some(reason) = partial {
+        run.mark_partial(reason);
+    }
+    store_run(&run);
+}
+
+/// Record for a run starting now, with the gateway setup currently saved
+fn new_stored_run(
+    args: &cli::TestArgs,
+    implementation: GatewayImpl,
+    gateway_ip: &str,
+    run_id: &str,
+) -> results::StoredTestRun {
+    let mut run = results::StoredTestRun::new(implementation, gateway_ip).with_config(
+        results::TestRunConfig {
+            hostname: args.hostname.clone(),
+            http_port: args.http_port,
+            https_port: args.https_port,
+            timeout_secs: args.timeout,
+            parallel: args.parallel,
+            concurrency: args.concurrent,
+            profile: args.profile.clone(),
+        },
+    );
+    run.id = run_id.to_string();
+    match results::ResultsStorage::default_dir()
+        .and_then(|storage| storage.load_setup(implementation.name()))
+    {
+        Ok(setup) => run.gateway_setup = setup,
+        Err(e) => warn!("Failed to load gateway configuration: {}", e),
+    }
+    run
+}
+
+fn store_run(run: &results::StoredTestRun) {
+    let saved = results::ResultsStorage::default_dir().and_then(|storage| storage.save(run));
+    if let Err(e) = saved {
+        warn!("Failed to save results: {}", e);
+    }
+}
+
+/// Write an OpenMetrics snapshot next to the `--output` report, or into the
+/// results store without one
+fn save_benchmark_metrics(results: &[benchmark::BenchmarkResult], output: Option<&str>) {
+    let saved = match output {
+        Some(output) => {
+            let path = std::path::Path::new(output).with_extension(results::OPENMETRICS_EXTENSION);
+            if path == std::path::Path::new(output) {
+                return;
             }
-            println!("\n{category} Tests:");
-            println!("──────────────────────────────────────────────────────────────────────");
-            current_category = category;
+            std::fs::write(
+                &path,
+                results::OpenMetrics::from_benchmarks(results).render(),
+            )
+            .map(|_| path)
+            .map_err(anyhow::Error::from)
         }
+        None => results::ResultsStorage::default_dir()
+            .and_then(|storage| storage.save_benchmark_metrics(results)),
+    };
+    match saved {
+        Ok(path) => info!("Saved OpenMetrics snapshot to {}", path.display()),
+        Err(e) => warn!("Failed to save OpenMetrics snapshot: {}", e),
+    }
+}
 
-        if args.detailed {
-            println!(
-                "  {:2}. {:20} [{}]",
-                test_case.number(),
-                test_case.name(),
-                test_case.category()
+/// Load settings of one `benchmark compare` phase
+fn compare_config(
+    preset: Option<&config::ScenarioPreset>,
+    implementation: GatewayImpl,
+    ip: &str,
+    port: u16,
+    duration: u64,
+    rps: Option<u32>,
+    concurrency: Option<u32>,
+) -> benchmark::BenchmarkConfig {
+    let mut config = match preset {
+        Some(preset) => preset.benchmark_config(implementation, ip, port),
+        None => {
+            let mut config = benchmark::BenchmarkConfig::new(implementation, ip)
+                .with_pattern(benchmark::LoadPattern::Constant { rps: 100 })
+                .with_concurrency(10);
+            config.port = port;
+            config
+        }
+    };
+    config = config.with_duration(duration);
+    if let Some(rps) = rps {
+        config = config.with_pattern(benchmark::LoadPattern::Constant { rps });
+    }
+    if let Some(concurrency) = concurrency {
+        config = config.with_concurrency(concurrency);
+    }
+    config
+}
+
+/// Run one `benchmark compare` load phase; None if the benchmark failed
+async fn compare_phase(
+    config: benchmark::BenchmarkConfig,
+    resources: bool,
+    watch_crashes: bool,
+    gateway_namespace: Option<&str>,
+    quiet: bool,
+) -> Result<Option<benchmark::BenchmarkResult>> {
+    let implementation = config.gateway;
+    let mut runner = benchmark::BenchmarkRunner::new(config);
+    if resources {
+        let collector = resource_collector(implementation, gateway_namespace).await?;
+        runner = runner.with_resource_collector(collector);
+    }
+    if watch_crashes {
+        let watcher = crash_watcher(implementation, gateway_namespace).await?;
+        runner = runner.with_crash_watcher(watcher);
+    }
+    match runner.run().await {
+        Ok(result) => {
+            if !quiet {
+                println!(
+                    "  ✓ {}: {:.1} RPS, p99={:.2}ms",
+                    implementation.name(),
+                    result.metrics.throughput.rps,
+                    result.metrics.latency.percentiles.p99
+                );
+                for crash in &result.crashes {
+                    println!("  ⚠ {}: tainted, {}", implementation.name(), crash);
+                }
+            }
+            Ok(Some(result))
+        }
+        Err(e) => {
+            println!("  ✗ {}: Failed - {}", implementation.name(), e);
+            Ok(None)
+        }
+    }
+}
+
+/// Start the `--tui` dashboard, falling back to plain output without a terminal
+fn start_dashboard(title: String, cancel: Option<executor::SuiteCancel>) -> Option<tui::Dashboard> {
+    let dashboard = tui::Dashboard::start(title, cancel);
+    if dashboard.is_none() {
+        warn!("--tui requires an interactive terminal; using plain output");
+    }
+    dashboard
+}
+
+/// Show the final report on the dashboard (if any) and wait for it to close
+async fn close_dashboard(dashboard: Option<tui::Dashboard>, report: &str) -> Result<()> {
+    match dashboard {
+        Some(dashboard) => dashboard.finish(report).await,
+        None => Ok(()),
+    }
+}
+
+/// Print `--fail-on` results; the exit code is non-zero if a gate tripped
+fn enforce_gates(conditions: &[results::GateCondition], source: &impl results::GateSource) -> i32 {
+    if conditions.is_empty() {
+        return 0;
+    }
+
+    let report = results::GateReport::evaluate(conditions, source);
+    println!("{report}");
+    report.exit_code()
+}
+
+/// Gateway API spec level from `--gateway-api-version`, or detected from the cluster
+async fn gateway_api_spec(args: &cli::TestArgs) -> Result<Option<models::GatewayApiSpec>> {
+    if let Some(version) = args.gateway_api_version {
+        let channel =
+            models::GatewayApiChannel::from_str(&args.gateway_api_channel).ok_or_else(|| {
+                anyhow::anyhow!("Unknown Gateway API channel: {}", args.gateway_api_channel)
+            })?;
+        return Ok(Some(models::GatewayApiSpec::new(version, channel)));
+    }
+
+    let detected = match k8s::K8sClient::new("default").await {
+        Ok(client) => client.gateway_api_spec().await,
+        Err(e) => Err(e),
+    };
+    match detected {
+        Ok(Some(spec)) => {
+            info!("Detected Gateway API {spec}");
+            Ok(Some(spec))
+        }
+        Ok(None) => {
+            warn!("Gateway API CRD version not found; version-specific tests are not gated");
+            Ok(None)
+        }
+        Err(e) => {
+            warn!(
+                "Could not detect Gateway API version ({e}); version-specific tests are not gated"
             );
-        } else {
-            println!("  {:2}. {}", test_case.number(), test_case.name());
+            Ok(None)
+        }
+    }
+}
+
+/// DNS overrides extended with `--dns-server` and `--ip-family`
+fn dns_settings(
+    overrides: http::DnsOverrides,
+    servers: &[String],
+    family: &str,
+) -> Result<http::DnsOverrides> {
+    let family = http::IpFamily::from_str(family)
+        .ok_or_else(|| anyhow::anyhow!("Unknown IP family: {family} (auto, v4, v6, dual)"))?;
+    let nameservers = servers
+        .iter()
+        .map(|s| http::parse_nameserver(s))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| anyhow::anyhow!(e))?;
+    Ok(overrides.with_nameservers(nameservers).with_family(family))
+}
+
+/// Values profiles and scenario files can reference as `${GATEWAY_IP}` etc.
+fn runtime_vars(
+    implementation: GatewayImpl,
+    gateway_ip: &str,
+    namespace: Option<&str>,
+    run_id: &str,
+) -> config::VarResolver {
+    config::VarResolver::from_env()
+        .with_var("GATEWAY", implementation.short_name())
+        .with_var("GATEWAY_IP", gateway_ip)
+        .with_var("NAMESPACE", namespace.unwrap_or("default"))
+        .with_var("RUN_ID", run_id)
+}
+
+/// Test profile named by `--profile` (config file first, then built-in)
+fn selected_profile(
+    args: &cli::TestArgs,
+    vars: &config::VarResolver,
+) -> Result<Option<config::TestProfile>> {
+    let Some(name) = &args.profile else {
+        return Ok(None);
+    };
+    let file = config::ConfigFile::load_default_with(vars)?;
+    file.test_profile(name)
+        .cloned()
+        .or_else(|| config::TestProfile::find(name))
+        .map(Some)
+        .ok_or_else(|| anyhow::anyhow!("Test profile not found: {name}"))
+}
+
+/// Gateway from the command line or environment, else the config file's
+/// `app.default_gateway`
+fn resolve_gateway(
+    gateway: &mut String,
+    given: &cli::GivenArgs,
+    file: Option<&config::ConfigFile>,
+    resolved: &mut config::ResolvedConfig,
+) {
+    use config::SettingSource;
+
+    resolved.resolve(
+        "gateway",
+        gateway,
+        given.source("gateway"),
+        vec![(
+            SettingSource::ConfigFile,
+            file.map(|f| f.app.default_gateway.clone()),
+        )],
+    );
+}
+
+/// Gateway IP and pod namespace not given on the command line or in the
+/// environment from the gateway's `connections` entry
+fn resolve_connection(
+    args: &mut cli::TestArgs,
+    given: &cli::GivenArgs,
+    connection: Option<&config::GatewayConnection>,
+    resolved: &mut config::ResolvedConfig,
+) {
+    use config::SettingSource;
+
+    let mut ip = args.ip.clone().unwrap_or_else(|| "127.0.0.1".to_string());
+    resolved.resolve(
+        "ip",
+        &mut ip,
+        given.source("ip"),
+        vec![(
+            SettingSource::ConfigFile,
+            connection.and_then(|c| c.ip.clone()),
+        )],
+    );
+    args.ip = Some(ip);
+
+    let mut namespace = args.gateway_namespace.clone().unwrap_or_default();
+    resolved.resolve(
+        "gateway_namespace",
+        &mut namespace,
+        given.source("gateway_namespace"),
+        vec![(
+            SettingSource::ConfigFile,
+            connection.and_then(|c| c.namespace.clone()),
+        )],
+    );
+    args.gateway_namespace = Some(namespace).filter(|n| !n.is_empty());
+}
+
+/// IP and port of `implementation`, from its `connections` entry in the
+/// config file where not given on the command line or in the environment
+fn gateway_address(
+    implementation: GatewayImpl,
+    ip: Option<String>,
+    port: u16,
+    given: &cli::GivenArgs,
+    file: Option<&config::ConfigFile>,
+) -> Result<(String, u16)> {
+    let connection = file.and_then(|f| f.connection(implementation));
+    let port = match connection.and_then(|c| c.http_port) {
+        Some(configured) if given.source("port").is_none() => configured,
+        _ => port,
+    };
+    let ip = ip
+        .or_else(|| connection.and_then(|c| c.ip.clone()))
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "No address for {implementation}: pass --ip or set connections.{}.ip in the config file",
+                implementation.short_name()
+            )
+        })?;
+    Ok((ip, port))
+}
+
+/// Handshake details of a TLS health check
+fn print_tls_info(info: &http::TlsInfo) {
+    println!("TLS: {} ({})", info.protocol, info.cipher_suite);
+    println!("  ALPN:       {}", info.alpn.as_deref().unwrap_or("none"));
+    if let Some(cert) = &info.certificate {
+        let days = cert.expires_in(chrono::Utc::now()).num_days();
+        println!("  Subject:    {}", cert.subject);
+        println!(
+            "  Issuer:     {}{}",
+            cert.issuer,
+            if cert.is_self_signed() {
+                " (self-signed)"
+            } else {
+                ""
+            }
+        );
+        println!("  Names:      {}", cert.names.join(", "));
+        println!(
+            "  Expires:    {} ({days} days)",
+            cert.not_after.format("%Y-%m-%d %H:%M UTC")
+        );
+    }
+    println!("  Chain:      {} certificate(s)", info.chain_length);
+    if let Some(error) = &info.verification_error {
+        println!("  Untrusted:  {error}");
+    }
+}
+
+/// Fill the test options not given on the command line or in the
+/// environment from the config file (`app` section, the gateway's
+/// `connections` entry), then from `profile`
+fn resolve_run(
+    args: &mut cli::TestArgs,
+    given: &cli::GivenArgs,
+    file: Option<&config::ConfigFile>,
+    connection: Option<&config::GatewayConnection>,
+    profile: Option<&config::TestProfile>,
+    resolved: &mut config::ResolvedConfig,
+) {
+    use config::SettingSource;
+
+    let format_source = given.source("format").cloned();
+    resolved.record(
+        "format",
+        &args.format,
+        format_source.unwrap_or(SettingSource::Default),
+    );
+
+    let app = file.map(|f| &f.app);
+    // Without a profile its layer has no values, so its source never shows
+    let from_profile = profile
+        .map(|p| SettingSource::Profile(p.name.clone()))
+        .unwrap_or(SettingSource::Default);
+
+    resolved.resolve(
+        "rounds",
+        &mut args.rounds,
+        given.source("rounds"),
+        vec![
+            (SettingSource::ConfigFile, app.map(|a| a.default_rounds)),
+            (from_profile.clone(), profile.map(|p| p.rounds)),
+        ],
+    );
+    resolved.resolve(
+        "parallel",
+        &mut args.parallel,
+        given.source("parallel"),
+        vec![
+            (SettingSource::ConfigFile, app.map(|a| a.parallel)),
+            (from_profile.clone(), profile.map(|p| p.parallel)),
+        ],
+    );
+    resolved.resolve(
+        "timeout",
+        &mut args.timeout,
+        given.source("timeout"),
+        vec![
+            (SettingSource::ConfigFile, app.map(|a| a.timeout_secs)),
+            (from_profile.clone(), profile.map(|p| p.timeout_secs)),
+        ],
+    );
+    resolved.resolve(
+        "concurrent",
+        &mut args.concurrent,
+        given.source("concurrent"),
+        vec![
+            (SettingSource::ConfigFile, app.map(|a| a.max_concurrent)),
+            (from_profile.clone(), profile.and_then(|p| p.concurrency)),
+        ],
+    );
+    resolved.resolve(
+        "hostname",
+        &mut args.hostname,
+        given.source("hostname"),
+        vec![
+            (
+                SettingSource::ConfigFile,
+                connection.and_then(|c| c.hostname.clone()),
+            ),
+            (
+                from_profile.clone(),
+                profile.and_then(|p| p.hostname.clone()),
+            ),
+        ],
+    );
+    resolved.resolve(
+        "http_port",
+        &mut args.http_port,
+        given.source("http_port"),
+        vec![
+            (
+                SettingSource::ConfigFile,
+                connection.and_then(|c| c.http_port),
+            ),
+            (from_profile.clone(), profile.and_then(|p| p.http_port)),
+        ],
+    );
+    resolved.resolve(
+        "https_port",
+        &mut args.https_port,
+        given.source("https_port"),
+        vec![
+            (
+                SettingSource::ConfigFile,
+                connection.and_then(|c| c.https_port),
+            ),
+            (from_profile, profile.and_then(|p| p.https_port)),
+        ],
+    );
+}
+
+/// Settings a plain `gateway-poc test` would run with here, and their sources
+fn resolved_test_config(file: Option<&config::ConfigFile>) -> Result<config::ResolvedConfig> {
+    use config::SettingSource;
+
+    let args = Args::try_parse_with_env(["gateway-poc", "test"], |var| std::env::var(var).ok())?;
+    let cli::Command::Test(mut test_args) = args.command else {
+        unreachable!("parsed the test command");
+    };
+    let given = &args.given;
+    let mut resolved = config::ResolvedConfig::default();
+
+    let config_var = config::env::config_var();
+    let config_source = match std::env::var(&config_var) {
+        Ok(_) => SettingSource::Env(config_var),
+        Err(_) => SettingSource::Default,
+    };
+    let config_path = config::ConfigFile::find()
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|| "(none)".to_string());
+    resolved.record("config", config_path, config_source);
+
+    let (namespace, source) = match (&args.namespace, file.and_then(|f| f.app.namespace.as_ref())) {
+        (Some(namespace), _) => (namespace.clone(), given.source("namespace").cloned()),
+        (None, Some(namespace)) => (namespace.clone(), Some(SettingSource::ConfigFile)),
+        (None, None) => ("default".to_string(), None),
+    };
+    resolved.record(
+        "namespace",
+        namespace,
+        source.unwrap_or(SettingSource::Default),
+    );
+
+    resolve_gateway(&mut test_args.gateway, given, file, &mut resolved);
+    // "auto" is only known in the cluster
+    let connection = GatewayImpl::from_str(&test_args.gateway)
+        .and_then(|implementation| file.and_then(|f| f.connection(implementation)));
+    resolve_connection(&mut test_args, given, connection, &mut resolved);
+    let profile = selected_profile(&test_args, &config::VarResolver::from_env())?;
+    resolve_run(
+        &mut test_args,
+        given,
+        file,
+        connection,
+        profile.as_ref(),
+        &mut resolved,
+    );
+    Ok(resolved)
+}
+
+/// Load Test settings from the selected profile, overridden by CLI flags
+fn load_test_settings(
+    args: &cli::TestArgs,
+    profile: Option<&config::TestProfile>,
+) -> Result<models::LoadTestSettings> {
+    let mut settings = profile
+        .and_then(|p| p.load_test.clone())
+        .unwrap_or_default();
+
+    if let Some(engine) = &args.load_engine {
+        settings.engine = models::LoadTestEngine::from_str(engine)
+            .ok_or_else(|| anyhow::anyhow!("Unknown load engine: {engine}"))?;
+    }
+    if let Some(users) = args.load_users {
+        settings.concurrent_users = users;
+    }
+    if let Some(requests) = args.load_requests {
+        settings.total_requests = requests;
+    }
+    if let Some(duration) = args.load_duration {
+        settings.duration_secs = duration;
+    }
+
+    Ok(settings)
+}
+
+fn list_tests(args: cli::ListArgs) {
+    println!(
+        "\nGateway API Test Cases ({} total)\n",
+        TestCase::all().len()
+    );
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+
+    // Group by category; later additions are numbered after the original 17
+    for (i, category) in models::TestCategory::all().into_iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        match TestCase::all()
+            .into_iter()
+            .find(|t| t.category() == category)
+            .and_then(|t| t.experimental())
+        {
+            Some(group) => println!("\n{category} Tests (experimental, --experimental {group}):"),
+            None => println!("\n{category} Tests:"),
+        }
+        println!("──────────────────────────────────────────────────────────────────────");
+
+        for test_case in TestCase::all()
+            .into_iter()
+            .filter(|t| t.category() == category)
+        {
+            if args.detailed {
+                println!(
+                    "  {:2}. {:20} [{}] {}",
+                    test_case.number(),
+                    test_case.name(),
+                    test_case.category(),
+                    test_case.tags().join(", ")
+                );
+            } else {
+                println!("  {:2}. {}", test_case.number(), test_case.name());
+            }
         }
     }
 
@@ -196,14 +1417,167 @@ fn list_tests(args: cli::ListArgs) {
             };
             println!("  - {:25} [{}]", gateway.name(), arm64_status);
         }
+        println!("\nOptional capabilities (tests needing a missing one report N/A):\n");
+        print!("{}", models::capability_matrix(&GatewayImpl::all()));
         println!();
     }
 }
 
-async fn manage_vm(args: cli::VmArgs) -> Result<()> {
-    use kubevirt::{SshClient, SshConfig, VirtualMachineManager, VmConfig, VmiManager};
+/// Worker VM `i` as created by `vm create`
+fn worker_vm(
+    i: u32,
+    namespace: &str,
+    cpu: u32,
+    memory: u32,
+    prepulled: bool,
+    provision: &[kubevirt::Provision],
+    ssh_public_key: Option<&str>,
+) -> kubevirt::VirtualMachine {
+    let mut config = kubevirt::VmConfig::new(format!("gateway-test-vm-{i}"), namespace)
+        .cpu(cpu)
+        .memory(format!("{memory}Gi"))
+        .label("app", "gateway-test")
+        .label("instance", i.to_string());
+    if prepulled {
+        config = config.annotation(kubevirt::PREPULL_ANNOTATION, "true");
+    }
+    for step in provision {
+        config = config.provision(step.clone());
+    }
+    if let Some(key) = ssh_public_key {
+        config = config.ssh_key(key);
+    }
+    config.build()
+}
+
+/// Public half of the VM SSH key, authorized in the VMs `vm create` makes
+async fn vm_public_key(key_file: Option<&k8s::SecretFile>) -> Result<Option<String>> {
+    let Some(key) = key_file else {
+        return Ok(None);
+    };
+    kubevirt::SshClient::new(kubevirt::SshConfig::default().private_key(key.path()))
+        .public_key()
+        .await
+}
+
+/// `--provision` presets followed by the `--provision-script`
+fn provision_steps(
+    presets: Vec<kubevirt::Provision>,
+    script: Option<&std::path::Path>,
+) -> Result<Vec<kubevirt::Provision>> {
+    let mut steps = presets;
+    if let Some(path) = script {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read provisioning script: {}", path.display()))?;
+        steps.push(kubevirt::Provision::Script(content));
+    }
+    Ok(steps)
+}
+
+/// Distinct container disk images of `vms`
+fn vm_images(vms: &[kubevirt::VirtualMachine]) -> Vec<String> {
+    let mut images: Vec<String> = vms
+        .iter()
+        .flat_map(|vm| vm.container_disk_images())
+        .map(String::from)
+        .collect();
+    images.sort();
+    images.dedup();
+    images
+}
+
+/// Print dry-run steps as a reviewable shell script
+fn print_plan(steps: &[deploy::PlannedStep]) {
+    println!("# Dry run: nothing was changed in the cluster");
+    for step in steps {
+        println!("{step}");
+    }
+}
+
+/// Secrets pods created by the tool pull their images with (config file
+/// `secrets.image_pull_secrets`)
+fn image_pull_secrets(config_file: Option<&config::ConfigFile>) -> Vec<String> {
+    config_file
+        .map(|f| f.secrets.image_pull_secrets.clone())
+        .unwrap_or_default()
+}
 
-    let k8s_client = k8s::K8sClient::new("default").await?;
+/// SSH private key for VMs: `flag`, else the config file's
+/// `app.kubevirt.ssh_key` reference or `ssh_key_path`
+async fn vm_ssh_key(
+    flag: Option<&str>,
+    config_file: Option<&config::ConfigFile>,
+    namespace: &str,
+) -> Result<Option<k8s::SecretFile>> {
+    let kubevirt = config_file.map(|f| &f.app.kubevirt);
+    let reference = match (flag, kubevirt.and_then(|k| k.ssh_key.clone())) {
+        (Some(path), _) => config::SecretRef::file(path),
+        (None, Some(reference)) => reference,
+        (None, None) => match kubevirt.and_then(|k| k.ssh_key_path.as_deref()) {
+            Some(path) => config::SecretRef::file(path),
+            None => return Ok(None),
+        },
+    };
+    let key = k8s::resolve_secret_to_file(&reference, namespace, "ssh-key")
+        .await
+        .with_context(|| format!("Failed to read the VM SSH key ({reference})"))?;
+    Ok(Some(key))
+}
+
+async fn manage_vm(
+    args: cli::VmArgs,
+    config_file: Option<&config::ConfigFile>,
+    namespace: &str,
+) -> Result<()> {
+    use kubevirt::{SshClient, SshConfig, VirtualMachineManager, VmiManager};
+
+    // Rendering VMs needs no KubeVirt (an SSH key held in a Secret is still
+    // read from the cluster)
+    if let cli::VmAction::Create {
+        workers,
+        cpu,
+        memory,
+        prepull,
+        provision,
+        provision_script,
+        dry_run: true,
+        ..
+    } = args.action
+    {
+        let provision = provision_steps(provision, provision_script.as_deref())?;
+        let key_file = vm_ssh_key(None, config_file, namespace).await?;
+        let public_key = vm_public_key(key_file.as_ref()).await?;
+        let vms: Vec<_> = (0..workers)
+            .map(|i| {
+                worker_vm(
+                    i,
+                    namespace,
+                    cpu,
+                    memory,
+                    prepull,
+                    &provision,
+                    public_key.as_deref(),
+                )
+            })
+            .collect();
+        if prepull {
+            let puller =
+                kubevirt::puller_daemon_set(namespace, &vm_images(&vms), &Default::default());
+            print!("---\n{}", serde_yaml::to_string(&puller)?);
+        }
+        for vm in &vms {
+            print!("---\n{}", serde_yaml::to_string(vm)?);
+        }
+        return Ok(());
+    }
+
+    // A broken pool spec is reported before touching the cluster
+    let pool_spec = match &args.action {
+        cli::VmAction::Apply { spec, .. } => Some(kubevirt::PoolSpec::load(spec)?),
+        _ => None,
+    };
+
+    let k8s_client = k8s::K8sClient::new(namespace).await?;
     let vm_manager = VirtualMachineManager::new(k8s_client.clone());
     let vmi_manager = VmiManager::new(k8s_client.clone());
 
@@ -222,66 +1596,146 @@ async fn manage_vm(args: cli::VmArgs) -> Result<()> {
             cpu,
             memory,
             disk: _,
+            prepull,
+            prepull_timeout,
+            provision,
+            provision_script,
+            provision_timeout,
+            dry_run: _,
         } => {
-            info!("Creating {} KubeVirt VM(s)...", workers);
+            let provision = provision_steps(provision, provision_script.as_deref())?;
+            // VMs authorize the configured key, so `vm exec` and the
+            // provisioning wait below can log in
+            let key_file = vm_ssh_key(None, config_file, namespace).await?;
+            let public_key = vm_public_key(key_file.as_ref()).await?;
+            let ssh = key_file
+                .as_ref()
+                .map(|key| SshClient::new(SshConfig::new("fedora").private_key(key.path())));
+            // Only VMs whose image actually made it onto the nodes count as
+            // pre-pulled in `vm status`
+            let prepulled = if prepull {
+                let images = vm_images(&[worker_vm(0, namespace, cpu, memory, false, &[], None)]);
+                let puller = kubevirt::ImagePrePuller::new(k8s_client.clone(), namespace);
+                println!("Pre-pulling {}...", images.join(", "));
+                match puller
+                    .pull(&images, std::time::Duration::from_secs(prepull_timeout))
+                    .await
+                {
+                    Ok(report) => {
+                        println!("  ✓ {report}");
+                        true
+                    }
+                    Err(e) => {
+                        println!("  ⚠ Pre-pull failed, VMs will pull on boot: {e:#}");
+                        false
+                    }
+                }
+            } else {
+                false
+            };
 
-            for i in 0..workers {
-                let vm_name = format!("gateway-test-vm-{i}");
-                println!("Creating VM: {vm_name}");
+            info!("Creating {} KubeVirt VM(s)...", workers);
 
-                let vm = VmConfig::new(&vm_name, "default")
-                    .cpu(cpu)
-                    .memory(format!("{memory}Gi"))
-                    .label("app", "gateway-test")
-                    .label("instance", i.to_string())
-                    .build();
+            // VMs that fail to come up (or are interrupted) are deleted again
+            let teardown = utils::Teardown::new();
+            teardown
+                .guard(async {
+                    for i in 0..workers {
+                        let vm_name = format!("gateway-test-vm-{i}");
+                        println!("Creating VM: {vm_name}");
+
+                        let vm = worker_vm(
+                            i,
+                            namespace,
+                            cpu,
+                            memory,
+                            prepulled,
+                            &provision,
+                            public_key.as_deref(),
+                        );
+                        let started = std::time::Instant::now();
 
-                match vm_manager.create(&vm, "default").await {
-                    Ok(_) => {
+                        if let Err(e) = vm_manager.create(&vm, namespace).await {
+                            println!("  ✗ Failed to create VM {vm_name}: {e}");
+                            continue;
+                        }
                         println!("  ✓ VM {vm_name} created successfully");
+                        let created = {
+                            let (vm_manager, vm_name) = (vm_manager.clone(), vm_name.clone());
+                            let namespace = namespace.to_string();
+                            teardown.register(format!("delete VM {vm_name}"), move || {
+                                let (vm_manager, vm_name, namespace) =
+                                    (vm_manager.clone(), vm_name.clone(), namespace.clone());
+                                async move { vm_manager.delete(&vm_name, &namespace).await }
+                            })
+                        };
 
                         // Wait for VM to be ready
                         println!("  ⏳ Waiting for VM to be ready...");
-                        if vm_manager.wait_ready(&vm_name, "default", 300).await? {
-                            println!("  ✓ VM {vm_name} is ready");
+                        if vm_manager.wait_ready(&vm_name, namespace, 300).await? {
+                            println!(
+                                "  ✓ VM {vm_name} is ready ({:.1}s)",
+                                started.elapsed().as_secs_f64()
+                            );
+                            created.commit();
 
                             // Wait for IP
-                            if let Some(ip) =
-                                vmi_manager.wait_for_ip(&vm_name, "default", 120).await?
-                            {
+                            let ip = vmi_manager.wait_for_ip(&vm_name, namespace, 120).await?;
+                            if let Some(ip) = &ip {
                                 println!("  ✓ VM {vm_name} has IP: {ip}");
                             }
+                            if !provision.is_empty() {
+                                wait_provisioned(
+                                    &vm_name,
+                                    ip.as_deref(),
+                                    ssh.as_ref(),
+                                    provision_timeout,
+                                )
+                                .await;
+                            }
                         } else {
-                            println!("  ⚠ VM {vm_name} did not become ready in time");
+                            println!("  ⚠ VM {vm_name} did not become ready in time; deleting it");
                         }
                     }
-                    Err(e) => {
-                        println!("  ✗ Failed to create VM {vm_name}: {e}");
-                    }
-                }
-            }
+                    Ok(())
+                })
+                .await?;
         }
 
-        cli::VmAction::Delete { all, name } => {
+        cli::VmAction::Delete { all, name, dry_run } => {
+            let plan = |vm_name: &str| {
+                println!("kubectl delete virtualmachines.kubevirt.io/{vm_name} -n {namespace}")
+            };
+            if dry_run {
+                println!("# Dry run: nothing was changed in the cluster");
+            }
+
             if all {
-                info!("Deleting all gateway-test VMs...");
-                let vms = vm_manager.list("default").await?;
+                info!("Deleting all gateway-poc VMs in {}...", namespace);
+                let vms = vm_manager.list(namespace).await?;
 
                 for vm in vms {
-                    if let Some(labels) = &vm.metadata.labels {
-                        if labels.get("app").map(|s| s.as_str()) == Some("gateway-test") {
-                            if let Some(vm_name) = &vm.metadata.name {
-                                match vm_manager.delete(vm_name, "default").await {
-                                    Ok(_) => println!("  ✓ Deleted VM: {vm_name}"),
-                                    Err(e) => println!("  ✗ Failed to delete {vm_name}: {e}"),
-                                }
-                            }
+                    if !k8s::is_managed(vm.metadata.labels.as_ref()) {
+                        continue;
+                    }
+                    if let Some(vm_name) = &vm.metadata.name {
+                        if dry_run {
+                            plan(vm_name);
+                            continue;
+                        }
+                        match vm_manager.delete(vm_name, namespace).await {
+                            Ok(_) => println!("  ✓ Deleted VM: {vm_name}"),
+                            Err(e) => println!("  ✗ Failed to delete {vm_name}: {e}"),
                         }
                     }
                 }
             } else if let Some(vm_name) = name {
+                if dry_run {
+                    plan(&vm_name);
+                    return Ok(());
+                }
                 info!("Deleting VM: {}", vm_name);
-                match vm_manager.delete(&vm_name, "default").await {
+                match vm_manager.delete(&vm_name, namespace).await {
                     Ok(_) => println!("✓ Deleted VM: {vm_name}"),
                     Err(e) => println!("✗ Failed to delete {vm_name}: {e}"),
                 }
@@ -292,15 +1746,20 @@ async fn manage_vm(args: cli::VmArgs) -> Result<()> {
 
         cli::VmAction::Status => {
             info!("Fetching VM status...");
-            let vms = vm_manager.list("default").await?;
+            let vms = vm_manager.list(namespace).await?;
 
-            println!("\n┌─────────────────────────────────────────────────────────────┐");
-            println!("│ KubeVirt VMs in 'default' namespace                          │");
-            println!("├─────────────────────────┬──────────┬─────────────────────────┤");
-            println!("│ Name                    │ Status   │ IP Address              │");
-            println!("├─────────────────────────┼──────────┼─────────────────────────┤");
+            println!(
+                "\n┌─────────────────────────────────────────────────────────────────────────┐"
+            );
+            println!(
+                "│ {:<72}│",
+                format!("KubeVirt VMs in '{namespace}' namespace")
+            );
+            println!("├─────────────────────────┬──────────┬─────────────────────────┬──────────┤");
+            println!("│ Name                    │ Status   │ IP Address              │ Ready in │");
+            println!("├─────────────────────────┼──────────┼─────────────────────────┼──────────┤");
 
-            for vm in vms {
+            for vm in &vms {
                 let name = vm.metadata.name.as_deref().unwrap_or("unknown");
                 let status = vm
                     .status
@@ -309,22 +1768,35 @@ async fn manage_vm(args: cli::VmArgs) -> Result<()> {
                     .unwrap_or_else(|| "Unknown".to_string());
 
                 // Try to get IP from VMI
-                let ip = match vmi_manager.get_ip(name, "default").await {
+                let ip = match vmi_manager.get_ip(name, namespace).await {
                     Ok(Some(ip)) => ip,
                     _ => "N/A".to_string(),
                 };
 
-                println!("│ {name:23} │ {status:8} │ {ip:23} │");
+                // "*" marks VMs whose image was pre-pulled
+                let ready_in = vm
+                    .time_to_ready()
+                    .map(|d| {
+                        let mark = if kubevirt::is_prepulled(vm) { "*" } else { "" };
+                        format!("{}s{mark}", d.as_secs())
+                    })
+                    .unwrap_or_else(|| "-".to_string());
+
+                println!("│ {name:23} │ {status:8} │ {ip:23} │ {ready_in:>8} │");
             }
 
-            println!("└─────────────────────────┴──────────┴─────────────────────────┘\n");
+            println!("└─────────────────────────┴──────────┴─────────────────────────┴──────────┘");
+            if let Some(summary) = kubevirt::readiness_summary(&vms) {
+                println!("Time to ready: {summary}");
+            }
+            println!();
         }
 
         cli::VmAction::Ssh { name } => {
             info!("Connecting to VM via SSH: {}", name);
 
             // Get VM IP
-            let ip = match vmi_manager.get_ip(&name, "default").await? {
+            let ip = match vmi_manager.get_ip(&name, namespace).await? {
                 Some(ip) => ip,
                 None => {
                     println!("❌ Could not find IP address for VM: {name}");
@@ -334,7 +1806,12 @@ async fn manage_vm(args: cli::VmArgs) -> Result<()> {
 
             println!("Connecting to {name} ({ip})...");
 
-            let ssh = SshClient::new(SshConfig::new("fedora").port(22));
+            let key_file = vm_ssh_key(None, config_file, namespace).await?;
+            let mut ssh_config = SshConfig::new("fedora").port(22);
+            if let Some(key) = &key_file {
+                ssh_config = ssh_config.private_key(key.path());
+            }
+            let ssh = SshClient::new(ssh_config);
 
             // Test connection
             if ssh.wait_for_ssh(&ip, 60).await? {
@@ -343,26 +1820,279 @@ async fn manage_vm(args: cli::VmArgs) -> Result<()> {
 
                 // Or use virtctl:
                 println!("Alternatively, use virtctl:");
-                println!("\n  virtctl ssh --namespace default {name}\n");
+                println!("\n  virtctl ssh --namespace {namespace} {name}\n");
             } else {
                 println!("❌ Could not establish SSH connection to VM");
             }
         }
+
+        cli::VmAction::Apply {
+            spec: spec_path,
+            dry_run,
+            timeout,
+        } => {
+            let Some(spec) = pool_spec else {
+                unreachable!("loaded the pool spec");
+            };
+            let key_file = vm_ssh_key(None, config_file, namespace).await?;
+            let public_key = vm_public_key(key_file.as_ref()).await?;
+            let desired = spec.desired(namespace, public_key.as_deref());
+            let existing = vm_manager.list(namespace).await?;
+            let changes = kubevirt::plan_pool(&desired, &existing);
+
+            if changes.is_empty() {
+                println!("✓ VMs in {namespace} match {}", spec_path.display());
+                return Ok(());
+            }
+            if dry_run {
+                println!("# Dry run: nothing was changed in the cluster");
+                for change in &changes {
+                    println!("{change}");
+                }
+                return Ok(());
+            }
+
+            let mut failed = 0;
+            for change in &changes {
+                println!("{change}");
+                if let Err(e) =
+                    apply_pool_change(&vm_manager, &vmi_manager, change, namespace, timeout).await
+                {
+                    println!("  ✗ {}: {e:#}", change.name());
+                    failed += 1;
+                }
+            }
+            if failed > 0 {
+                anyhow::bail!("{failed} of {} VM change(s) failed", changes.len());
+            }
+            println!("✓ Applied {} VM change(s)", changes.len());
+        }
+
+        cli::VmAction::Exec {
+            name,
+            ssh_user,
+            ssh_key,
+            command,
+        } => {
+            let ip = vmi_manager
+                .get_ip(&name, namespace)
+                .await?
+                .with_context(|| format!("VM {name} has no IP address"))?;
+            let key_file = vm_ssh_key(ssh_key.as_deref(), config_file, namespace).await?;
+            let mut ssh_config = SshConfig::new(ssh_user);
+            if let Some(key) = &key_file {
+                ssh_config = ssh_config.private_key(key.path());
+            }
+            let code = SshClient::new(ssh_config)
+                .exec_passthrough(&ip, &kubevirt::shell_command(&command))
+                .await?;
+            if code != 0 {
+                // The key file is removed on drop, which exit() would skip
+                drop(key_file);
+                std::process::exit(code.max(1));
+            }
+        }
+
+        cli::VmAction::Start { name, timeout } => {
+            println!("Starting VM: {name}");
+            vm_manager.start(&name, namespace).await?;
+            wait_vm_ready(&vm_manager, &vmi_manager, &name, namespace, timeout).await?;
+        }
+
+        cli::VmAction::Stop { name, timeout } => {
+            println!("Stopping VM: {name}");
+            vm_manager.stop(&name, namespace).await?;
+            println!("  ⏳ Waiting for VM to stop...");
+            if !vm_manager.wait_stopped(&name, namespace, timeout).await? {
+                anyhow::bail!("VM {name} did not stop within {timeout}s");
+            }
+            println!("  ✓ VM {name} stopped");
+        }
+
+        cli::VmAction::Restart { name, timeout } => {
+            println!("Restarting VM: {name}");
+            vm_manager.restart(&name, namespace, timeout).await?;
+            println!("  ✓ VM {name} stopped and started again");
+            wait_vm_ready(&vm_manager, &vmi_manager, &name, namespace, timeout).await?;
+        }
+
+        cli::VmAction::Resize {
+            name,
+            cpu,
+            memory,
+            no_restart,
+            timeout,
+        } => {
+            let memory = memory.map(|gb| format!("{gb}Gi"));
+            let size = [
+                cpu.map(|cores| format!("{cores} CPU")),
+                memory.as_ref().map(|guest| format!("{guest} memory")),
+            ]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(", ");
+            println!("Resizing VM {name} to {size}");
+            vm_manager
+                .resize(&name, namespace, cpu, memory.as_deref())
+                .await?;
+            println!("  ✓ VM spec updated");
+
+            if no_restart || !vm_manager.is_running(&name, namespace).await? {
+                println!("  The new size applies the next time {name} starts");
+                return Ok(());
+            }
+            println!("  ⏳ Restarting VM to apply the new size...");
+            vm_manager.restart(&name, namespace, timeout).await?;
+            wait_vm_ready(&vm_manager, &vmi_manager, &name, namespace, timeout).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Wait for the first-boot provisioning of a VM, when it can be checked
+/// over SSH
+async fn wait_provisioned(
+    name: &str,
+    ip: Option<&str>,
+    ssh: Option<&kubevirt::SshClient>,
+    timeout: u64,
+) {
+    let (Some(ip), Some(ssh)) = (ip, ssh) else {
+        println!(
+            "  ⏳ Provisioning continues in the VM; done once {} exists (log: {})",
+            kubevirt::PROVISIONED_MARKER,
+            kubevirt::PROVISION_LOG
+        );
+        return;
+    };
+    println!("  ⏳ Waiting for provisioning to finish...");
+    let started = std::time::Instant::now();
+    match ssh
+        .wait_for_file(ip, kubevirt::PROVISIONED_MARKER, timeout)
+        .await
+    {
+        Ok(true) => println!(
+            "  ✓ VM {name} provisioned ({:.1}s)",
+            started.elapsed().as_secs_f64()
+        ),
+        Ok(false) => println!(
+            "  ⚠ VM {name} did not finish provisioning within {timeout}s; see {} \
+             (gateway-poc vm exec {name} -- sudo cat {})",
+            kubevirt::PROVISION_LOG,
+            kubevirt::PROVISION_LOG
+        ),
+        Err(e) => println!("  ⚠ Could not check provisioning of {name}: {e:#}"),
+    }
+}
+
+/// Make one `vm apply` change, waiting for the VMs it (re)starts
+async fn apply_pool_change(
+    vm_manager: &kubevirt::VirtualMachineManager,
+    vmi_manager: &kubevirt::VmiManager,
+    change: &kubevirt::PoolChange,
+    namespace: &str,
+    timeout: u64,
+) -> Result<()> {
+    use kubevirt::PoolChange;
+
+    let name = change.name();
+    match change {
+        PoolChange::Create(vm) => {
+            vm_manager.create(vm, namespace).await?;
+            wait_vm_ready(vm_manager, vmi_manager, name, namespace, timeout).await
+        }
+        PoolChange::Replace(vm) => {
+            vm_manager.delete(name, namespace).await?;
+            if !vm_manager.wait_deleted(name, namespace, timeout).await? {
+                anyhow::bail!("VM {name} was not deleted within {timeout}s");
+            }
+            vm_manager.create(vm, namespace).await?;
+            wait_vm_ready(vm_manager, vmi_manager, name, namespace, timeout).await
+        }
+        PoolChange::Resize { cpu, memory, .. } => {
+            vm_manager
+                .resize(name, namespace, *cpu, memory.as_deref())
+                .await?;
+            if !vm_manager.is_running(name, namespace).await? {
+                return Ok(());
+            }
+            vm_manager.restart(name, namespace, timeout).await?;
+            wait_vm_ready(vm_manager, vmi_manager, name, namespace, timeout).await
+        }
+        PoolChange::Relabel { labels, .. } => vm_manager.label(name, namespace, labels).await,
+        PoolChange::Delete(_) => vm_manager.delete(name, namespace).await,
     }
+}
 
+/// Wait for a (re)started VM to be ready and report its IP
+async fn wait_vm_ready(
+    vm_manager: &kubevirt::VirtualMachineManager,
+    vmi_manager: &kubevirt::VmiManager,
+    name: &str,
+    namespace: &str,
+    timeout: u64,
+) -> Result<()> {
+    let started = std::time::Instant::now();
+    println!("  ⏳ Waiting for VM to be ready...");
+    if !vm_manager.wait_ready(name, namespace, timeout).await? {
+        anyhow::bail!("VM {name} did not become ready within {timeout}s");
+    }
+    println!(
+        "  ✓ VM {name} is ready ({:.1}s)",
+        started.elapsed().as_secs_f64()
+    );
+    if let Some(ip) = vmi_manager.wait_for_ip(name, namespace, 120).await? {
+        println!("  ✓ VM {name} has IP: {ip}");
+    }
     Ok(())
 }
 
 fn show_results(args: cli::ResultsArgs) -> Result<()> {
     use results::{
-        ComparisonFormatter, GatewayComparator, ReportFormat, ReportGenerator, ResultsStorage,
+        catalog_warning, ComparisonFormatter, GatewayComparator, ReportFormat, ReportGenerator,
+        ResultsStorage,
     };
     use std::path::PathBuf;
 
-    info!("Results viewer - displaying stored results");
-
     let storage = ResultsStorage::default_dir()?;
 
+    match args.action {
+        Some(cli::ResultsAction::Annotate {
+            run_id,
+            test,
+            note,
+            author,
+        }) => return annotate_run(&storage, &run_id, test, note, author),
+        Some(cli::ResultsAction::ExportBundle {
+            output,
+            runs,
+            gateway,
+            all,
+            no_benchmarks,
+        }) => return export_bundle(&storage, &output, &runs, &gateway, all, !no_benchmarks),
+        Some(cli::ResultsAction::SetupDiff { left, right }) => {
+            return diff_setups(&storage, &left, &right)
+        }
+        Some(cli::ResultsAction::ImportBundle { path, force }) => {
+            let import = storage.import_bundle(&path, force)?;
+            for (gateway, id) in &import.imported {
+                println!("✓ Imported run {id} ({gateway})");
+            }
+            for (gateway, id) in &import.skipped {
+                println!("- Skipped existing run {id} ({gateway}); use --force to replace");
+            }
+            if import.benchmarks > 0 {
+                println!("✓ Imported {} benchmark snapshots", import.benchmarks);
+            }
+            return Ok(());
+        }
+        None => {}
+    }
+
+    info!("Results viewer - displaying stored results");
+
     // List gateways if no specific gateway requested
     if args.gateway.is_none() && !args.summary {
         let gateways = storage.list_gateways()?;
@@ -414,6 +2144,9 @@ fn show_results(args: cli::ResultsArgs) -> Result<()> {
         }
 
         let comparison = GatewayComparator::compare(&runs);
+        if let Some(warning) = catalog_warning(runs.iter().map(|r| r.catalog_version)) {
+            eprintln!("⚠ Warning: {warning}");
+        }
 
         match args.format.as_str() {
             "json" => {
@@ -425,7 +2158,9 @@ fn show_results(args: cli::ResultsArgs) -> Result<()> {
         }
 
         // Export if requested
-        if let Some(export_path) = &args.export {
+        if let Some(export_path) = args.export.as_deref().filter(|p| is_chart_dir(p)) {
+            export_chart_files(&runs, export_path)?;
+        } else if let Some(export_path) = &args.export {
             let path = PathBuf::from(export_path);
             let format =
                 ReportFormat::from_str(path.extension().and_then(|e| e.to_str()).unwrap_or("md"))
@@ -462,6 +2197,13 @@ fn show_results(args: cli::ResultsArgs) -> Result<()> {
                 println!("│ Run ID: {:50} │", latest.id);
                 println!("│ IP: {:54} │", latest.gateway_ip);
                 println!("│ Rounds: {:50} │", latest.rounds);
+                println!("│ Test Catalog: {:44} │", latest.catalog_label());
+                if let Some(profile) = &latest.config.profile {
+                    println!("│ Profile: {:49} │", profile);
+                }
+                if let Some(reason) = &latest.partial {
+                    println!("│ Partial: {:49} │", reason);
+                }
 
                 if let Some(agg) = &latest.aggregate {
                     println!("├─────────────────────────────────────────────────────────────┤");
@@ -488,6 +2230,18 @@ fn show_results(args: cli::ResultsArgs) -> Result<()> {
 
                 println!("└─────────────────────────────────────────────────────────────┘");
 
+                if let Some(soak) = &latest.soak {
+                    let formatter = ResultFormatter::new(OutputFormat::Table);
+                    println!("{}", formatter.format_soak(soak, &latest.gateway));
+                }
+
+                if !latest.annotations.is_empty() {
+                    println!("\nReviewer notes:");
+                    for annotation in &latest.annotations {
+                        println!("  - {annotation}");
+                    }
+                }
+
                 // Show other runs
                 if runs.len() > 1 {
                     println!("\nOther runs ({}):", runs.len() - 1);
@@ -497,14 +2251,27 @@ fn show_results(args: cli::ResultsArgs) -> Result<()> {
                             .as_ref()
                             .map(|a| format!("{:.1}%", a.avg_pass_rate * 100.0))
                             .unwrap_or_else(|| "N/A".to_string());
-                        println!("  - {} | {} | {}", run.id, run.rounds, pass_rate);
+                        println!(
+                            "  - {} | {} | {} | catalog {}",
+                            run.id,
+                            run.rounds,
+                            pass_rate,
+                            run.catalog_label()
+                        );
+                    }
+                    if let Some(warning) =
+                        catalog_warning(runs.iter().take(6).map(|r| r.catalog_version))
+                    {
+                        println!("\n⚠ Warning: {warning}");
                     }
                 }
             }
         }
 
         // Export if requested
-        if let Some(export_path) = &args.export {
+        if let Some(export_path) = args.export.as_deref().filter(|p| is_chart_dir(p)) {
+            export_chart_files(std::slice::from_ref(latest), export_path)?;
+        } else if let Some(export_path) = &args.export {
             let path = PathBuf::from(export_path);
             let format =
                 ReportFormat::from_str(path.extension().and_then(|e| e.to_str()).unwrap_or("md"))
@@ -520,30 +2287,481 @@ fn show_results(args: cli::ResultsArgs) -> Result<()> {
     Ok(())
 }
 
-async fn manage_deploy(args: cli::DeployArgs) -> Result<()> {
+/// Whether `--export` names a chart directory (trailing slash or existing directory)
+fn is_chart_dir(path: &str) -> bool {
+    path.ends_with('/') || std::path::Path::new(path).is_dir()
+}
+
+/// Write PNG/SVG charts for `runs` into a directory
+fn export_chart_files(runs: &[results::StoredTestRun], dir: &str) -> Result<()> {
+    let written = results::export_charts(runs, std::path::Path::new(dir))?;
+    if written.is_empty() {
+        println!("\nNo chart data in the selected runs.");
+    } else {
+        println!("\n✓ Charts exported to: {dir}");
+        for path in written {
+            println!("  - {}", path.display());
+        }
+    }
+    Ok(())
+}
+
+fn diff_setups(storage: &results::ResultsStorage, left: &str, right: &str) -> Result<()> {
+    let setup = |run_id: &str| -> Result<models::GatewaySetup> {
+        let run = storage.find(run_id)?;
+        run.gateway_setup.ok_or_else(|| {
+            anyhow::anyhow!(
+                "Run {run_id} has no captured gateway setup (install with `deploy install` first)"
+            )
+        })
+    };
+    let (a, b) = (setup(left)?, setup(right)?);
+    println!(
+        "{left}: {} captured {}",
+        a.gateway,
+        a.captured_at.format("%Y-%m-%d %H:%M")
+    );
+    println!(
+        "{right}: {} captured {}",
+        b.gateway,
+        b.captured_at.format("%Y-%m-%d %H:%M")
+    );
+
+    let diff = a.diff(&b);
+    if diff.is_empty() {
+        println!("\n✓ Equivalent gateway setups");
+    } else {
+        println!("\n{} setting(s) differ:", diff.len());
+        for difference in &diff {
+            println!("  {difference}");
+        }
+    }
+    Ok(())
+}
+
+fn annotate_run(
+    storage: &results::ResultsStorage,
+    run_id: &str,
+    test: Option<u8>,
+    note: String,
+    author: Option<String>,
+) -> Result<()> {
+    if note.trim().is_empty() {
+        anyhow::bail!("Note must not be empty");
+    }
+    if let Some(n) = test {
+        if TestCase::from_number(n).is_none() {
+            anyhow::bail!(
+                "Invalid test number: {n} (valid: 1-{})",
+                TestCase::all().len()
+            );
+        }
+    }
+
+    let mut run = storage.find(run_id)?;
+    if let Some(n) = test.filter(|n| !run.has_test(*n)) {
+        warn!("Run {} did not execute test {}", run_id, n);
+    }
+
+    let annotation = results::Annotation::new(test, note.trim())
+        .with_author(author.or_else(|| std::env::var("USER").ok()));
+    println!(
+        "✓ Annotated run {} ({}): {}",
+        run.id, run.gateway, annotation
+    );
+    run.annotate(annotation);
+    storage.save(&run)?;
+
+    Ok(())
+}
+
+fn generate_docs(args: cli::DocsArgs) -> Result<()> {
+    let cli::DocsAction::Features { format, output } = args.action;
+
+    let storage = results::ResultsStorage::default_dir()?;
+    let mut runs = Vec::new();
+    for gateway in storage.list_gateways()? {
+        if let Some(run) = storage.latest(&gateway)? {
+            runs.push(run);
+        }
+    }
+
+    let catalog = results::FeatureCatalog::build(&runs);
+    let rendered = match format.as_str() {
+        "markdown" | "md" => catalog.to_markdown(),
+        "json" => catalog.to_json()?,
+        other => anyhow::bail!("Unknown docs format: {other} (valid: markdown, json)"),
+    };
+
+    match output {
+        Some(path) => {
+            std::fs::write(&path, rendered)?;
+            println!("✓ Feature catalog written to: {}", path.display());
+        }
+        None => println!("{rendered}"),
+    }
+    Ok(())
+}
+
+async fn run_doctor(args: cli::DoctorArgs) -> Result<i32> {
+    let report = deploy::Doctor::new().run().await;
+    let rendered = match args.format.as_str() {
+        "table" => report.format_table(),
+        "json" => report.to_json()?,
+        other => anyhow::bail!("Unknown doctor format: {other} (valid: table, json)"),
+    };
+    println!("{rendered}");
+
+    if let Some(path) = args.output {
+        std::fs::write(&path, &rendered)?;
+        println!("✓ Report written to: {}", path.display());
+    }
+
+    Ok(if report.passed() { 0 } else { 1 })
+}
+
+async fn wait_for_endpoint(args: cli::WaitArgs, quiet: bool) -> Result<()> {
+    let mut wait = http::EndpointWait::new(&args.url)
+        .with_timeout(std::time::Duration::from_secs(args.timeout))
+        .with_interval(std::time::Duration::from_secs(args.interval.max(1)));
+    if let Some(status) = args.status {
+        wait = wait.with_status(status);
+    }
+    if let Some(hostname) = &args.hostname {
+        wait = wait.with_host(hostname);
+    }
+
+    if !quiet {
+        println!("Waiting for {} (timeout: {}s)...", args.url, args.timeout);
+    }
+    let client = http::HttpClient::with_timeout(args.interval.clamp(1, 10))?;
+    let polled = wait.wait(&client).await?;
+    if !quiet {
+        println!(
+            "✓ {} returned {} after {} attempt(s) ({:.1}s)",
+            args.url,
+            polled.value,
+            polled.attempts,
+            polled.elapsed.as_secs_f64()
+        );
+    }
+    Ok(())
+}
+
+async fn compare_headers(args: cli::HeadersArgs) -> Result<()> {
+    let targets = args
+        .targets
+        .iter()
+        .map(|t| results::HeaderTarget::parse(t))
+        .collect::<Result<Vec<_>>>()?;
+    if targets.len() < 2 {
+        anyhow::bail!("Comparing headers needs at least two --target gateways");
+    }
+    let port = args.port.unwrap_or(if args.https { 443 } else { 80 });
+    let probe = results::HeaderProbe::new(targets, &args.hostname, port)
+        .with_paths(args.paths)
+        .with_https(args.https);
+    let comparison = probe.run(&http::HttpClient::new()?).await;
+
+    let rendered = match args.format.as_str() {
+        "text" => comparison.format_text(args.all),
+        "markdown" => comparison.format_markdown(args.all),
+        "json" => comparison.to_json()?,
+        other => anyhow::bail!("Unknown headers format: {other} (valid: text, json, markdown)"),
+    };
+    println!("{rendered}");
+
+    if let Some(path) = args.output {
+        std::fs::write(&path, &rendered)?;
+        println!("✓ Comparison written to: {}", path.display());
+    }
+    Ok(())
+}
+
+async fn run_chaos(
+    args: cli::ChaosArgs,
+    given: &cli::GivenArgs,
+    config_file: Option<&config::ConfigFile>,
+    namespace: Option<&str>,
+) -> Result<i32> {
+    let storage = results::ResultsStorage::default_dir()?;
+    let print = |result: &chaos::ChaosResult, format: &str| -> Result<()> {
+        match format {
+            "json" => println!("{}", serde_json::to_string_pretty(result)?),
+            "table" => println!("{}", result.format_table()),
+            other => anyhow::bail!("Unknown chaos format: {other} (valid: table, json)"),
+        }
+        Ok(())
+    };
+
+    match args.action {
+        cli::ChaosAction::Run {
+            mut gateway,
+            ip,
+            port,
+            path,
+            hostname,
+            fault,
+            during_load,
+            duration,
+            fault_at,
+            concurrency,
+            rps,
+            timeout,
+            slo,
+            backend,
+            gateway_namespace,
+            delay_ms,
+            delay_duration,
+            format,
+        } => {
+            if !(slo > 0.0 && slo < 100.0) {
+                anyhow::bail!("--slo must be between 0 and 100 (exclusive), got {slo}");
+            }
+            resolve_gateway(
+                &mut gateway,
+                given,
+                config_file,
+                &mut config::ResolvedConfig::default(),
+            );
+            let implementation = GatewayImpl::from_str(&gateway)
+                .ok_or_else(|| anyhow::anyhow!("Unknown gateway: {gateway}"))?;
+            let (ip, port) = gateway_address(implementation, ip, port, given, config_file)?;
+
+            let experiment = chaos::ChaosExperiment::new(implementation, &ip, port)
+                .with_path(&path)
+                .with_hostname(&hostname)
+                .with_fault(fault)
+                .with_backend(&backend)
+                .with_gateway_namespace(
+                    gateway_namespace
+                        .as_deref()
+                        .or(namespace)
+                        .unwrap_or("default"),
+                )
+                .with_schedule(duration, fault_at)
+                .with_delay(delay_ms, delay_duration)
+                .with_load(during_load, concurrency, rps)
+                .with_timeout(timeout)
+                .with_slo(slo);
+            let client = k8s::K8sClient::new(namespace.unwrap_or("default")).await?;
+            let injector = k8s::FaultInjector::new(client);
+            let result = experiment
+                .run(&injector, &results::generate_run_id())
+                .await?;
+
+            print(&result, &format)?;
+            let path = storage.save_chaos(&result)?;
+            println!("✓ Result saved to: {}", path.display());
+
+            if !result.passed() {
+                return Ok(1);
+            }
+        }
+        cli::ChaosAction::List => {
+            let results = storage.list_chaos()?;
+            if results.is_empty() {
+                println!("No chaos experiments stored");
+                return Ok(0);
+            }
+            println!(
+                "{:<22} {:<10} {:<12} {:>10} {:>12} {:>8}",
+                "ID", "Gateway", "Fault", "Recovery", "Budget used", "Result"
+            );
+            for result in &results {
+                let recovery = match (result.recovered, result.recovery_ms) {
+                    (true, Some(ms)) => format!("{ms}ms"),
+                    (true, None) => "-".to_string(),
+                    (false, _) => "none".to_string(),
+                };
+                println!(
+                    "{:<22} {:<10} {:<12} {:>10} {:>11.1}% {:>8}",
+                    result.id,
+                    result.gateway,
+                    result.fault.name(),
+                    recovery,
+                    result.error_budget_consumed,
+                    if result.passed() { "✓" } else { "✗" }
+                );
+            }
+        }
+        cli::ChaosAction::Show { id, format } => {
+            print(&storage.load_chaos(&id)?, &format)?;
+        }
+    }
+    Ok(0)
+}
+
+async fn run_cleanup(args: cli::CleanupArgs, namespace: Option<&str>) -> Result<()> {
+    let mut cleanup = deploy::Cleanup::new()
+        .with_fixtures(args.fixtures || args.all)
+        .with_vms(args.vms || args.all)
+        .with_namespaces(args.all);
+    if let Some(gateway) = &args.gateway {
+        let implementation = GatewayImpl::from_str(gateway)
+            .ok_or_else(|| anyhow::anyhow!("Unknown gateway: {gateway}"))?;
+        cleanup = cleanup.with_gateway(implementation);
+    }
+    if let Some(namespace) = namespace {
+        cleanup = cleanup.with_namespace(namespace);
+    }
+
+    let resources = cleanup.find().await?;
+    if resources.is_empty() {
+        println!("Nothing to clean up");
+        return Ok(());
+    }
+
+    for resource in &resources {
+        println!("  {resource}");
+    }
+    let summary: Vec<String> = deploy::count_by_kind(&resources)
+        .iter()
+        .map(|(kind, count)| format!("{count} {kind}"))
+        .collect();
+    if args.dry_run {
+        println!("Would delete {}", summary.join(", "));
+        return Ok(());
+    }
+
+    cleanup.delete(&resources).await?;
+    println!("✓ Deleted {}", summary.join(", "));
+    Ok(())
+}
+
+fn export_bundle(
+    storage: &results::ResultsStorage,
+    output: &std::path::Path,
+    run_ids: &[String],
+    gateways: &[String],
+    all: bool,
+    include_benchmarks: bool,
+) -> Result<()> {
+    let mut runs = Vec::new();
+    if all {
+        for gateway in storage.list_gateways()? {
+            runs.extend(storage.load_gateway(&gateway)?);
+        }
+    } else {
+        for gateway in gateways {
+            let found = storage.load_gateway(gateway)?;
+            if found.is_empty() {
+                anyhow::bail!("No results found for gateway: {gateway}");
+            }
+            runs.extend(found);
+        }
+        for run_id in run_ids {
+            if !runs.iter().any(|r| &r.id == run_id) {
+                runs.push(storage.find(run_id)?);
+            }
+        }
+    }
+    if runs.is_empty() {
+        anyhow::bail!("No runs selected (pass run IDs, --gateway, or --all)");
+    }
+
+    let manifest = storage.export_bundle(&runs, include_benchmarks, output)?;
+    println!(
+        "✓ Bundled {} runs and {} benchmark snapshots into {}",
+        manifest.runs.len(),
+        manifest.benchmarks.len(),
+        output.display()
+    );
+    Ok(())
+}
+
+async fn manage_deploy(
+    args: cli::DeployArgs,
+    given: &cli::GivenArgs,
+    config_file: Option<&config::ConfigFile>,
+    namespace: Option<&str>,
+) -> Result<()> {
     use deploy::{
         GatewayInstaller, HealthCheckConfig, HealthChecker, InstallerConfig, ManifestGenerator,
-        PreFlightChecker,
+        PreFlightChecker, TestBundle,
     };
 
     match args.action {
         cli::DeployAction::Install {
             gateway,
-            namespace,
             timeout,
+            wait_url,
+            dry_run,
         } => {
             let implementation = GatewayImpl::from_str(&gateway)
                 .ok_or_else(|| anyhow::anyhow!("Unknown gateway: {gateway}"))?;
 
-            let config = InstallerConfig::new()
-                .namespace(&namespace)
-                .timeout(timeout);
+            let mut config = InstallerConfig::new()
+                .namespace(namespace.unwrap_or("gateway-system"))
+                .timeout(timeout)
+                .dry_run(dry_run);
+            for credential in config_file.iter().flat_map(|f| &f.secrets.registries) {
+                // Dry runs show the login without reading the password
+                let password = if dry_run {
+                    String::new()
+                } else {
+                    k8s::resolve_secret(&credential.password, namespace.unwrap_or("default"))
+                        .await
+                        .with_context(|| {
+                            format!(
+                                "Failed to read the password for {} ({})",
+                                credential.registry, credential.password
+                            )
+                        })?
+                };
+                config =
+                    config.registry_login(&credential.registry, &credential.username, password);
+            }
+            if let Some(url) = &wait_url {
+                config = config.ready_url(url);
+            }
 
-            let installer = GatewayInstaller::new(config);
+            if dry_run {
+                let installer = GatewayInstaller::new(config);
+                installer.install(implementation).await?;
+                print_plan(&installer.planned_steps());
+                return Ok(());
+            }
+
+            let installer = std::sync::Arc::new(GatewayInstaller::new(config));
 
             println!("Installing {} gateway...", implementation.name());
 
-            match installer.install(implementation).await {
+            // A failed or interrupted install is rolled back, unless the
+            // gateway was already installed before (or that is unknown)
+            let teardown = utils::Teardown::new();
+            let preinstalled = match installer.check_installed(implementation).await {
+                Ok(status) => status.is_installed(),
+                Err(e) => {
+                    warn!(
+                        "Could not check for an existing {} installation, so a failed install will not be rolled back: {:#}",
+                        implementation.name(),
+                        e
+                    );
+                    true
+                }
+            };
+            let outcome = teardown
+                .guard(async {
+                    let rollback = (!preinstalled).then(|| {
+                        let installer = installer.clone();
+                        teardown.register(
+                            format!("uninstall {}", implementation.name()),
+                            move || {
+                                let installer = installer.clone();
+                                async move { installer.uninstall(implementation).await }
+                            },
+                        )
+                    });
+                    let result = installer.install(implementation).await?;
+                    if let Some(rollback) = rollback {
+                        rollback.commit();
+                    }
+                    Ok(result)
+                })
+                .await;
+
+            match outcome {
                 Ok(result) => {
                     println!("\n✓ Installation complete!");
                     println!("  Gateway: {}", result.gateway.name());
@@ -551,6 +2769,23 @@ async fn manage_deploy(args: cli::DeployArgs) -> Result<()> {
                     println!("  Namespace: {}", result.namespace);
                     println!("  GatewayClass: {}", result.gateway_class);
                     println!("  Status: {}", result.status.as_str());
+
+                    let setup = deploy::capture_setup(&result).await;
+                    match results::ResultsStorage::default_dir()
+                        .and_then(|storage| storage.save_setup(&setup))
+                    {
+                        Ok(path) => println!("  Configuration: {}", path.display()),
+                        Err(e) => warn!("Failed to save gateway configuration: {:#}", e),
+                    }
+
+                    if let Some(url) = wait_url {
+                        println!("\nWaiting for {url}...");
+                        if installer.wait_ready(implementation).await? {
+                            println!("✓ {} is ready", implementation.name());
+                        } else {
+                            anyhow::bail!("{} did not become ready", implementation.name());
+                        }
+                    }
                 }
                 Err(e) => {
                     println!("✗ Installation failed: {e}");
@@ -558,13 +2793,21 @@ async fn manage_deploy(args: cli::DeployArgs) -> Result<()> {
             }
         }
 
-        cli::DeployAction::Uninstall { gateway, namespace } => {
+        cli::DeployAction::Uninstall { gateway, dry_run } => {
             let implementation = GatewayImpl::from_str(&gateway)
                 .ok_or_else(|| anyhow::anyhow!("Unknown gateway: {gateway}"))?;
 
-            let config = InstallerConfig::new().namespace(&namespace);
+            let config = InstallerConfig::new()
+                .namespace(namespace.unwrap_or("gateway-system"))
+                .dry_run(dry_run);
             let installer = GatewayInstaller::new(config);
 
+            if dry_run {
+                installer.uninstall(implementation).await?;
+                print_plan(&installer.planned_steps());
+                return Ok(());
+            }
+
             println!("Uninstalling {} gateway...", implementation.name());
 
             match installer.uninstall(implementation).await {
@@ -609,20 +2852,42 @@ async fn manage_deploy(args: cli::DeployArgs) -> Result<()> {
             println!("└─────────────────────────────────────────────────────────────┘\n");
         }
 
-        cli::DeployAction::Health { gateway, ip, port } => {
+        cli::DeployAction::Health {
+            gateway,
+            ip,
+            port,
+            tls,
+            tls_port,
+            cert_warning_days,
+        } => {
             let implementation = GatewayImpl::from_str(&gateway)
                 .ok_or_else(|| anyhow::anyhow!("Unknown gateway: {gateway}"))?;
+            let (ip, port) = gateway_address(implementation, ip, port, given, config_file)?;
 
-            let config = HealthCheckConfig::default();
+            let config = HealthCheckConfig::default().cert_warning(cert_warning_days);
             let checker = HealthChecker::new(config)?;
 
-            let status = checker.check_gateway(implementation, &ip, port).await;
+            let mut status = checker.check_gateway(implementation, &ip, port).await;
+            if let Some(hostname) = tls {
+                let tls_port = match config_file
+                    .and_then(|f| f.connection(implementation))
+                    .and_then(|c| c.https_port)
+                {
+                    Some(configured) if given.source("tls_port").is_none() => configured,
+                    _ => tls_port,
+                };
+                status.add_check(checker.check_certificate(&ip, tls_port, &hostname).await);
+            }
             println!("{}", status.format_table());
+            for info in status.checks.iter().filter_map(|c| c.tls.as_ref()) {
+                print_tls_info(info);
+            }
         }
 
         cli::DeployAction::Preflight { gateway, ip, port } => {
             let implementation = GatewayImpl::from_str(&gateway)
                 .ok_or_else(|| anyhow::anyhow!("Unknown gateway: {gateway}"))?;
+            let (ip, port) = gateway_address(implementation, ip, port, given, config_file)?;
 
             let config = HealthCheckConfig::default();
             let checker = PreFlightChecker::new(config)?;
@@ -635,15 +2900,32 @@ async fn manage_deploy(args: cli::DeployArgs) -> Result<()> {
             }
         }
 
-        cli::DeployAction::Crds { experimental } => {
-            let config = InstallerConfig::new();
+        cli::DeployAction::Crds {
+            experimental,
+            gateway_api_version,
+        } => {
+            let config = InstallerConfig::new().gateway_api_version(gateway_api_version);
             let installer = GatewayInstaller::new(config);
 
+            match deploy::installed_gateway_api().await {
+                Ok(Some(installed)) => {
+                    println!("Currently installed: Gateway API {installed}");
+                    let downgrade = installed
+                        .spec
+                        .is_some_and(|spec| spec.version > gateway_api_version);
+                    if downgrade {
+                        warn!("Installing {gateway_api_version} downgrades the installed CRDs");
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => warn!("Could not detect installed Gateway API CRDs: {:#}", e),
+            }
+
             if experimental {
-                println!("Installing experimental Gateway API CRDs...");
+                println!("Installing experimental Gateway API {gateway_api_version} CRDs...");
                 installer.install_gateway_api_experimental().await?;
             } else {
-                println!("Installing standard Gateway API CRDs...");
+                println!("Installing standard Gateway API {gateway_api_version} CRDs...");
                 installer.install_gateway_api_crds().await?;
             }
 
@@ -655,11 +2937,32 @@ async fn manage_deploy(args: cli::DeployArgs) -> Result<()> {
             resource,
             name,
             format,
+            bundle,
         } => {
             let implementation = GatewayImpl::from_str(&gateway)
                 .ok_or_else(|| anyhow::anyhow!("Unknown gateway: {gateway}"))?;
 
-            let generator = ManifestGenerator::new(implementation);
+            if let Some(dir) = bundle {
+                let written = TestBundle::new(implementation)
+                    .with_namespace(namespace.unwrap_or("default"))
+                    .with_gateway_name(&name)
+                    .with_image_pull_secrets(image_pull_secrets(config_file))
+                    .write(&dir)?;
+                println!(
+                    "Wrote {} bundle ({} files) to {}:",
+                    implementation.name(),
+                    written.len(),
+                    dir.display()
+                );
+                for path in &written {
+                    println!("  {}", path.strip_prefix(&dir).unwrap_or(path).display());
+                }
+                println!("\nApply with: kubectl apply -k {}", dir.display());
+                return Ok(());
+            }
+
+            let generator =
+                ManifestGenerator::new(implementation).namespace(namespace.unwrap_or("default"));
 
             let output = match resource.to_lowercase().as_str() {
                 "gateway" => {
@@ -678,9 +2981,196 @@ async fn manage_deploy(args: cli::DeployArgs) -> Result<()> {
                         ManifestGenerator::to_yaml(&manifest)
                     }
                 }
+                "hostname-rewrite" => {
+                    let manifest = generator.http_route_hostname_rewrite(
+                        &name,
+                        "test-gateway",
+                        tests::HOSTNAME_REWRITE_PATH,
+                        tests::HOSTNAME_REWRITE_TARGET,
+                        "echo",
+                        8080,
+                    );
+                    if format == "json" {
+                        ManifestGenerator::to_json(&manifest)
+                    } else {
+                        ManifestGenerator::to_yaml(&manifest)
+                    }
+                }
+                "consistent-hash" => {
+                    let route = generator.http_route_path(
+                        &name,
+                        "test-gateway",
+                        tests::CONSISTENT_HASH_PATH,
+                        tests::CONSISTENT_HASH_BACKEND,
+                        8080,
+                    );
+                    let policy = generator
+                        .consistent_hash_policy(
+                            &format!("{name}-hash"),
+                            &name,
+                            tests::CONSISTENT_HASH_BACKEND,
+                            tests::CONSISTENT_HASH_HEADER,
+                        )
+                        .ok_or_else(|| {
+                            anyhow::anyhow!(
+                                "{} has no header-keyed consistent-hash policy",
+                                implementation.name()
+                            )
+                        })?;
+                    if let Some((key, value)) = generator.policy_service_annotation(&policy) {
+                        eprintln!(
+                            "⚠ Annotate Service {} with {key}={value} to attach the policy",
+                            tests::CONSISTENT_HASH_BACKEND
+                        );
+                    }
+                    if format == "json" {
+                        ManifestGenerator::to_json(&(route, policy))
+                    } else {
+                        format!(
+                            "{}---\n{}",
+                            ManifestGenerator::to_yaml(&route),
+                            ManifestGenerator::to_yaml(&policy)
+                        )
+                    }
+                }
+                "session-affinity" => {
+                    let route = generator.http_route_path(
+                        &name,
+                        "test-gateway",
+                        tests::SESSION_AFFINITY_PATH,
+                        tests::SESSION_AFFINITY_BACKEND,
+                        8080,
+                    );
+                    let policy = generator
+                        .session_affinity_policy(
+                            &format!("{name}-affinity"),
+                            &name,
+                            tests::SESSION_AFFINITY_BACKEND,
+                            tests::SESSION_COOKIE,
+                        )
+                        .ok_or_else(|| {
+                            anyhow::anyhow!(
+                                "{} has no session affinity policy",
+                                implementation.name()
+                            )
+                        })?;
+                    if let Some((key, value)) = generator.policy_service_annotation(&policy) {
+                        eprintln!(
+                            "⚠ Annotate Service {} with {key}={value} to attach the policy",
+                            tests::SESSION_AFFINITY_BACKEND
+                        );
+                    }
+                    if format == "json" {
+                        ManifestGenerator::to_json(&(route, policy))
+                    } else {
+                        format!(
+                            "{}---\n{}",
+                            ManifestGenerator::to_yaml(&route),
+                            ManifestGenerator::to_yaml(&policy)
+                        )
+                    }
+                }
+                "rate-limit" => {
+                    let mut route = generator.http_route_path(
+                        &name,
+                        "test-gateway",
+                        tests::RATE_LIMIT_PATH,
+                        "echo",
+                        8080,
+                    );
+                    let policy = generator
+                        .rate_limit_policy(&format!("{name}-limit"), &name, "test-gateway", 10, 5)
+                        .ok_or_else(|| {
+                            anyhow::anyhow!("{} has no rate-limit policy", implementation.name())
+                        })?;
+                    generator.attach_policy(&mut route, &policy.metadata.name);
+                    if format == "json" {
+                        ManifestGenerator::to_json(&(route, policy))
+                    } else {
+                        format!(
+                            "{}---\n{}",
+                            ManifestGenerator::to_yaml(&route),
+                            ManifestGenerator::to_yaml(&policy)
+                        )
+                    }
+                }
+                "cors" => {
+                    let mut route = generator.http_route_path(
+                        &name,
+                        "test-gateway",
+                        tests::CORS_PATH,
+                        "echo",
+                        8080,
+                    );
+                    let policy = generator
+                        .cors_policy(
+                            &format!("{name}-cors"),
+                            &name,
+                            tests::CORS_ORIGIN,
+                            &tests::CORS_METHODS,
+                            &[tests::CORS_HEADER],
+                        )
+                        .ok_or_else(|| {
+                            anyhow::anyhow!("{} has no CORS policy", implementation.name())
+                        })?;
+                    generator.attach_policy(&mut route, &policy.metadata.name);
+                    if format == "json" {
+                        ManifestGenerator::to_json(&(route, policy))
+                    } else {
+                        format!(
+                            "{}---\n{}",
+                            ManifestGenerator::to_yaml(&route),
+                            ManifestGenerator::to_yaml(&policy)
+                        )
+                    }
+                }
+                "backend-tls" => {
+                    let route = generator.http_route_path(
+                        &name,
+                        "test-gateway",
+                        tests::BACKEND_TLS_PATH,
+                        tests::BACKEND_TLS_SERVICE,
+                        tests::BACKEND_TLS_PORT,
+                    );
+                    let refs = k8s::TlsBackendRefs::for_service(tests::BACKEND_TLS_SERVICE);
+                    let policy = generator
+                        .backend_tls_policy(
+                            &format!("{name}-tls"),
+                            tests::BACKEND_TLS_SERVICE,
+                            tests::BACKEND_TLS_HOSTNAME,
+                            &refs.ca_config_map,
+                            &refs.client_secret,
+                        )
+                        .ok_or_else(|| {
+                            anyhow::anyhow!("{} has no backend TLS policy", implementation.name())
+                        })?;
+                    if format == "json" {
+                        ManifestGenerator::to_json(&(route, policy))
+                    } else {
+                        format!(
+                            "{}---\n{}",
+                            ManifestGenerator::to_yaml(&route),
+                            ManifestGenerator::to_yaml(&policy)
+                        )
+                    }
+                }
+                "ai" => {
+                    let manifest = generator.http_route_path(
+                        &name,
+                        "test-gateway",
+                        tests::AI_ROUTE_PATH,
+                        tests::AI_BACKEND,
+                        8080,
+                    );
+                    if format == "json" {
+                        ManifestGenerator::to_json(&manifest)
+                    } else {
+                        ManifestGenerator::to_yaml(&manifest)
+                    }
+                }
                 _ => {
                     anyhow::bail!(
-                        "Unknown resource type: {resource}. Use 'gateway' or 'httproute'"
+                        "Unknown resource type: {resource}. Use 'gateway', 'httproute', 'hostname-rewrite', 'consistent-hash', 'session-affinity', 'rate-limit', 'cors', 'backend-tls' or 'ai'"
                     );
                 }
             };
@@ -692,76 +3182,278 @@ async fn manage_deploy(args: cli::DeployArgs) -> Result<()> {
     Ok(())
 }
 
-async fn run_benchmark(args: cli::BenchmarkArgs) -> Result<()> {
+/// Collector for the gateway's pods, selected by the implementation's labels
+async fn resource_collector(
+    implementation: GatewayImpl,
+    namespace: Option<&str>,
+) -> Result<k8s::ResourceCollector> {
+    let client = k8s::K8sClient::new(namespace.unwrap_or("default")).await?;
+    let collector = k8s::ResourceCollector::new(client, implementation.pod_selector());
+    Ok(match namespace {
+        Some(ns) => collector.with_namespace(ns),
+        None => collector,
+    })
+}
+
+async fn crash_watcher(
+    implementation: GatewayImpl,
+    namespace: Option<&str>,
+) -> Result<k8s::CrashWatcher> {
+    let client = k8s::K8sClient::new(namespace.unwrap_or("default")).await?;
+    let watcher = k8s::CrashWatcher::new(client, implementation);
+    Ok(match namespace {
+        Some(ns) => watcher.with_namespace(ns),
+        None => watcher,
+    })
+}
+
+async fn diagnostics_collector(
+    implementation: GatewayImpl,
+    namespace: Option<&str>,
+    dir: &std::path::Path,
+) -> Result<k8s::DiagnosticsCollector> {
+    let client = k8s::K8sClient::new(namespace.unwrap_or("default")).await?;
+    let collector = k8s::DiagnosticsCollector::new(client, implementation, dir);
+    Ok(match namespace {
+        Some(ns) => collector.with_namespace(ns),
+        None => collector,
+    })
+}
+
+async fn run_benchmark(
+    args: cli::BenchmarkArgs,
+    given: &cli::GivenArgs,
+    config_file: Option<&config::ConfigFile>,
+    namespace: Option<&str>,
+    quiet: bool,
+) -> Result<i32> {
     use benchmark::{
-        BenchmarkConfig, BenchmarkReport, BenchmarkReportFormat, BenchmarkRunner, LoadPattern,
+        parse_latency_ms, BenchmarkConfig, BenchmarkReport, BenchmarkReportFormat, BenchmarkRunner,
+        CapacitySearch, ConcurrencyTuner, DistributedRunner, Interleave, InterleavedRun,
+        LoadPattern, SteadyState, TrafficMix, WorkerBackend,
     };
     use std::fs;
 
+    let mut exit_code = 0;
     match args.action {
         cli::BenchmarkAction::Run {
-            gateway,
+            mut gateway,
             ip,
             port,
             path,
             hostname,
+            dns_server,
+            ip_family,
+            no_keep_alive,
+            max_idle_per_host,
+            no_tcp_nodelay,
             duration,
             concurrency,
             rps,
             pattern,
             warmup,
+            drain,
+            steady_state,
+            steady_window,
+            pause_file,
+            distributed,
+            workers,
+            worker_image,
+            worker_namespace,
+            worker_vms,
+            ssh_user,
+            ssh_key,
+            targets,
+            mix,
+            mix_json,
+            auth,
+            resources,
+            watch_crashes,
+            gateway_namespace,
+            fail_on,
+            tui,
             format,
             output,
         } => {
+            let worker_namespace = worker_namespace
+                .as_deref()
+                .or(namespace)
+                .unwrap_or("default");
+            resolve_gateway(
+                &mut gateway,
+                given,
+                config_file,
+                &mut config::ResolvedConfig::default(),
+            );
             let implementation = GatewayImpl::from_str(&gateway)
                 .ok_or_else(|| anyhow::anyhow!("Unknown gateway: {gateway}"))?;
+            let (ip, port) = gateway_address(implementation, ip, port, given, config_file)?;
+            let connection = config_file.and_then(|f| f.connection(implementation));
+            let hostname = connection
+                .and_then(|c| c.hostname.clone())
+                .filter(|_| given.source("hostname").is_none())
+                .unwrap_or(hostname);
+
+            let dashboard = if tui {
+                start_dashboard(format!("{} benchmark", implementation.name()), None)
+            } else {
+                None
+            };
+            let quiet = quiet || dashboard.is_some();
 
             // Parse load pattern
-            let load_pattern = match pattern.to_lowercase().as_str() {
-                "constant" => LoadPattern::Constant { rps },
-                "ramp" => LoadPattern::Ramp {
-                    start_rps: rps / 2,
-                    end_rps: rps,
-                    duration_secs: duration,
-                },
-                "step" => LoadPattern::Step {
-                    start_rps: rps / 4,
-                    step_rps: rps / 4,
-                    step_interval_secs: duration / 4,
-                    max_rps: rps,
-                },
-                "spike" => LoadPattern::Spike {
-                    base_rps: rps / 2,
-                    spike_rps: rps * 2,
-                    spike_duration_secs: duration / 6,
-                },
-                "max" => LoadPattern::Max { concurrency },
-                _ => LoadPattern::Constant { rps },
-            };
+            let load_pattern = LoadPattern::from_name(&pattern, rps, duration, concurrency);
 
             let config = BenchmarkConfig::new(implementation, &ip)
                 .with_pattern(load_pattern)
                 .with_duration(duration)
                 .with_concurrency(concurrency)
                 .with_path(&path)
-                .with_hostname(&hostname);
+                .with_hostname(&hostname)
+                .with_dns(dns_settings(
+                    http::DnsOverrides::new(),
+                    &dns_server,
+                    &ip_family,
+                )?)
+                .with_connection(http::ConnectionSettings::new(
+                    !no_keep_alive,
+                    max_idle_per_host,
+                    !no_tcp_nodelay,
+                ));
 
             // Update config with warmup and port
-            let mut config = config;
-            config.warmup_secs = warmup;
+            let mut config = config.with_warmup(warmup).with_drain(drain);
             config.port = port;
 
-            println!(
-                "Starting benchmark for {} at http://{}:{}{}",
-                implementation.name(),
-                ip,
-                port,
-                path
-            );
-            println!("Duration: {duration}s, Concurrency: {concurrency}, Pattern: {pattern:?}");
+            if steady_state {
+                config = config.with_steady_state(SteadyState {
+                    window_secs: steady_window,
+                    ..Default::default()
+                });
+            }
+
+            // Weighted traffic mix from file or --target specs
+            let traffic_mix = match mix {
+                Some(mix_path) => {
+                    let run_id = results::generate_run_id();
+                    let vars = runtime_vars(implementation, &ip, namespace, &run_id);
+                    Some(TrafficMix::load(&mix_path, &vars)?)
+                }
+                None if !targets.is_empty() => Some(TrafficMix::from_specs(&targets)?),
+                None => match mix_json {
+                    Some(json) => {
+                        let mix: TrafficMix =
+                            serde_json::from_str(&json).context("Invalid --mix-json")?;
+                        mix.validate()?;
+                        Some(mix)
+                    }
+                    None => None,
+                },
+            };
+            if let Some(traffic_mix) = traffic_mix {
+                if !quiet {
+                    println!("Traffic mix:");
+                    for target in &traffic_mix.targets {
+                        println!(
+                            "  {} ({:.0}%)",
+                            target.key(),
+                            traffic_mix.share(target) * 100.0
+                        );
+                    }
+                }
+                config = config.with_mix(traffic_mix);
+            }
+
+            if !quiet {
+                println!(
+                    "Starting benchmark for {} at http://{}{}",
+                    implementation.name(),
+                    http::authority(&ip, port),
+                    path
+                );
+                println!("Duration: {duration}s, Concurrency: {concurrency}, Pattern: {pattern:?}");
+            }
+
+            let key_file = match &worker_vms {
+                Some(_) if distributed => {
+                    vm_ssh_key(ssh_key.as_deref(), config_file, worker_namespace).await?
+                }
+                _ => None,
+            };
+            let result = if distributed {
+                let backend = match &worker_vms {
+                    Some(vms) => {
+                        let mut ssh = kubevirt::SshConfig::new(&ssh_user);
+                        if let Some(key) = &key_file {
+                            ssh = ssh.private_key(key.path());
+                        }
+                        WorkerBackend::Vms {
+                            names: vms.split(',').map(|s| s.trim().to_string()).collect(),
+                            namespace: worker_namespace.to_string(),
+                            ssh,
+                        }
+                    }
+                    None => WorkerBackend::Pods {
+                        namespace: worker_namespace.to_string(),
+                        image: worker_image.clone(),
+                        image_pull_secrets: image_pull_secrets(config_file),
+                    },
+                };
+                let runner = DistributedRunner::new(config, rps, workers, backend);
+                if !quiet {
+                    println!("Distributing load across {} workers", runner.workers());
+                }
+                runner.run().await?
+            } else {
+                let mut runner = BenchmarkRunner::new(config);
+                if let Some(dashboard) = &dashboard {
+                    runner = runner.with_progress(dashboard.reporter());
+                }
+                if let Some(provider) = auth {
+                    if !quiet {
+                        println!("Authenticating requests with {provider}");
+                    }
+                    runner = runner.with_auth(http::AuthHeaders::new(provider));
+                }
+                if resources {
+                    let collector =
+                        resource_collector(implementation, gateway_namespace.as_deref()).await?;
+                    runner = runner.with_resource_collector(collector);
+                }
+                if watch_crashes {
+                    let watcher =
+                        crash_watcher(implementation, gateway_namespace.as_deref()).await?;
+                    runner = runner.with_crash_watcher(watcher);
+                }
+
+                // Pause/resume controls for long runs
+                let control = runner.control();
+                let mut listeners = Vec::new();
+                #[cfg(unix)]
+                {
+                    listeners.push(control.listen_signal()?);
+                    if !quiet {
+                        println!(
+                            "Send SIGUSR1 to pid {} to pause/resume load",
+                            std::process::id()
+                        );
+                    }
+                }
+                if let Some(path) = &pause_file {
+                    listeners.push(control.watch_file(path));
+                    if !quiet {
+                        println!("Create {path} to pause load, remove it to resume");
+                    }
+                }
 
-            let runner = BenchmarkRunner::new(config);
-            let result = runner.run().await?;
+                let result = runner.run().await;
+                for listener in listeners {
+                    listener.abort();
+                }
+                let result = result?;
+                close_dashboard(dashboard, &result.format_summary()).await?;
+                result
+            };
 
             // Generate report
             let report_format =
@@ -771,10 +3463,13 @@ async fn run_benchmark(args: cli::BenchmarkArgs) -> Result<()> {
             println!("{report}");
 
             // Save to file if specified
-            if let Some(output_path) = output {
-                fs::write(&output_path, &report)?;
+            if let Some(output_path) = &output {
+                fs::write(output_path, &report)?;
                 println!("Report saved to: {output_path}");
             }
+            save_benchmark_metrics(std::slice::from_ref(&result), output.as_deref());
+
+            exit_code = enforce_gates(&fail_on, &result);
         }
 
         cli::BenchmarkAction::Compare {
@@ -784,43 +3479,226 @@ async fn run_benchmark(args: cli::BenchmarkArgs) -> Result<()> {
             duration,
             concurrency,
             rps,
+            preset,
+            apply_routes,
+            route_namespace,
+            resources,
+            watch_crashes,
+            gateway_namespace,
+            slo_p99,
+            interleave,
+            gap,
+            seed,
+            window,
+            parallel,
             format,
             output,
         } => {
+            let route_namespace = route_namespace
+                .as_deref()
+                .or(namespace)
+                .unwrap_or("default");
+            let preset = match &preset {
+                Some(name) => Some(config::ScenarioPreset::find(name).ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Unknown preset: {name} (available: {})",
+                        config::ScenarioPreset::names().join(", ")
+                    )
+                })?),
+                None => None,
+            };
+            let route_manager = if apply_routes {
+                Some(k8s::HTTPRouteManager::new(
+                    k8s::K8sClient::new(route_namespace).await?,
+                ))
+            } else {
+                None
+            };
+            let capacity_search = match &slo_p99 {
+                Some(slo) => Some(CapacitySearch::new(
+                    parse_latency_ms(slo)
+                        .ok_or_else(|| anyhow::anyhow!("Invalid --slo-p99: {slo}"))?,
+                )),
+                None => None,
+            };
+            let mut capacity_results = Vec::new();
             let gateway_list: Vec<&str> = gateways.split(',').map(|s| s.trim()).collect();
             let mut results = Vec::new();
 
-            println!("Comparing {} gateways...\n", gateway_list.len());
+            match &preset {
+                _ if quiet => {}
+                Some(preset) => println!(
+                    "Comparing {} gateways with preset '{}'...\n",
+                    gateway_list.len(),
+                    preset.name
+                ),
+                None => println!("Comparing {} gateways...\n", gateway_list.len()),
+            }
 
+            let mut implementations = Vec::new();
             for gateway_name in gateway_list {
-                if let Some(implementation) = GatewayImpl::from_str(gateway_name) {
-                    println!("Benchmarking {}...", implementation.name());
+                match GatewayImpl::from_str(gateway_name) {
+                    Some(implementation) => implementations.push(implementation),
+                    None => println!("  ⚠ Unknown gateway: {gateway_name}"),
+                }
+            }
+            let mut addresses = std::collections::HashMap::new();
+            for &implementation in &implementations {
+                let address =
+                    gateway_address(implementation, ip.clone(), port, given, config_file)?;
+                addresses.insert(implementation, address);
+            }
+            if parallel {
+                let mut seen = std::collections::HashSet::new();
+                for implementation in &implementations {
+                    let (ip, port) = &addresses[implementation];
+                    if !seen.insert((ip, port)) {
+                        anyhow::bail!(
+                            "--parallel needs a separate address per gateway, but {} shares {ip}:{port}; set connections.<gateway>.ip in the config file",
+                            implementation.name()
+                        );
+                    }
+                }
+            }
+            let build_config = |implementation| {
+                let (ip, port) = &addresses[&implementation];
+                compare_config(
+                    preset.as_ref(),
+                    implementation,
+                    ip,
+                    *port,
+                    duration,
+                    rps,
+                    concurrency,
+                )
+            };
 
-                    let config = BenchmarkConfig::new(implementation, &ip)
-                        .with_pattern(LoadPattern::Constant { rps })
-                        .with_duration(duration)
-                        .with_concurrency(concurrency);
+            // Gateways measured side by side get their routes up front
+            if interleave.is_some() || parallel {
+                if let (Some(preset), Some(manager)) = (&preset, &route_manager) {
+                    let mut applied = Vec::new();
+                    for implementation in implementations {
+                        let route = preset.http_route(implementation, route_namespace);
+                        match manager.apply(&route, route_namespace).await {
+                            Ok(_) => applied.push(implementation),
+                            Err(e) => println!("  ✗ {}: {:#}", implementation.name(), e),
+                        }
+                    }
+                    implementations = applied;
+                }
+            }
 
-                    let mut config = config;
-                    config.port = port;
+            let mut interleaved = None;
+            if let Some(rounds) = interleave {
+                let mut schedule = Interleave::new(rounds).with_gap(gap);
+                if let Some(seed) = seed {
+                    schedule = schedule.with_seed(seed);
+                }
+                let phases = schedule.schedule(&implementations);
+                let window = window.unwrap_or(duration);
+                if !quiet {
+                    println!(
+                        "Interleaving {} rounds of {}s windows, {}s apart (seed {})\n",
+                        schedule.rounds, window, schedule.gap_secs, schedule.seed
+                    );
+                }
 
-                    let runner = BenchmarkRunner::new(config);
-                    match runner.run().await {
-                        Ok(result) => {
-                            println!(
-                                "  ✓ {}: {:.1} RPS, p99={:.2}ms",
-                                implementation.name(),
-                                result.metrics.throughput.rps,
-                                result.metrics.latency.percentiles.p99
-                            );
-                            results.push(result);
+                let mut run = InterleavedRun::default();
+                for (i, phase) in phases.into_iter().enumerate() {
+                    if i > 0 {
+                        tokio::time::sleep(schedule.gap()).await;
+                    }
+                    if !quiet {
+                        println!(
+                            "Round {}/{}, #{}: benchmarking {}...",
+                            phase.round,
+                            schedule.rounds,
+                            phase.position,
+                            phase.gateway.name()
+                        );
+                    }
+                    let config = build_config(phase.gateway).with_duration(window);
+                    if let Some(result) = compare_phase(
+                        config,
+                        resources,
+                        watch_crashes,
+                        gateway_namespace.as_deref(),
+                        quiet,
+                    )
+                    .await?
+                    {
+                        run.push(phase, result);
+                    }
+                }
+                results = run.combined();
+                interleaved = Some(run);
+            } else if parallel {
+                if !quiet {
+                    println!(
+                        "Benchmarking {} gateways in parallel...",
+                        implementations.len()
+                    );
+                }
+                let phases = implementations.iter().map(|implementation| {
+                    compare_phase(
+                        build_config(*implementation),
+                        resources,
+                        watch_crashes,
+                        gateway_namespace.as_deref(),
+                        quiet,
+                    )
+                });
+                for result in futures::future::join_all(phases).await {
+                    results.extend(result?);
+                }
+            } else {
+                for implementation in implementations {
+                    if !quiet {
+                        println!("Benchmarking {}...", implementation.name());
+                    }
+
+                    if let (Some(preset), Some(manager)) = (&preset, &route_manager) {
+                        let route = preset.http_route(implementation, route_namespace);
+                        if let Err(e) = manager.apply(&route, route_namespace).await {
+                            println!("  ✗ {}: {:#}", implementation.name(), e);
+                            continue;
+                        }
+                    }
+
+                    let config = build_config(implementation);
+                    let capacity_config = config.clone();
+                    if let Some(result) = compare_phase(
+                        config,
+                        resources,
+                        watch_crashes,
+                        gateway_namespace.as_deref(),
+                        quiet,
+                    )
+                    .await?
+                    {
+                        results.push(result);
+                    }
+
+                    if let Some(search) = &capacity_search {
+                        if !quiet {
+                            println!("Searching {} capacity...", implementation.name());
                         }
-                        Err(e) => {
-                            println!("  ✗ {}: Failed - {}", implementation.name(), e);
+                        match search.run(&capacity_config).await {
+                            Ok(capacity) => {
+                                if !quiet {
+                                    println!("  ✓ {}", capacity.format_summary());
+                                }
+                                capacity_results.push(capacity);
+                            }
+                            Err(e) => {
+                                println!(
+                                    "  ✗ {}: Capacity search failed - {}",
+                                    implementation.name(),
+                                    e
+                                );
+                            }
                         }
                     }
-                } else {
-                    println!("  ⚠ Unknown gateway: {gateway_name}");
                 }
             }
 
@@ -828,11 +3706,190 @@ async fn run_benchmark(args: cli::BenchmarkArgs) -> Result<()> {
                 // Generate comparison report
                 let report_format =
                     BenchmarkReportFormat::from_str(&format).unwrap_or(BenchmarkReportFormat::Text);
-                let report = BenchmarkReport::comparison(&results, report_format);
+                let mut report = BenchmarkReport::comparison(&results, report_format);
+                if !capacity_results.is_empty() {
+                    report = match report_format {
+                        BenchmarkReportFormat::Json | BenchmarkReportFormat::JsonPretty => {
+                            let combined = serde_json::json!({
+                                "benchmarks": results,
+                                "capacity": capacity_results,
+                            });
+                            if report_format == BenchmarkReportFormat::Json {
+                                combined.to_string()
+                            } else {
+                                serde_json::to_string_pretty(&combined)?
+                            }
+                        }
+                        _ => format!(
+                            "{report}\n{}",
+                            BenchmarkReport::capacity(&capacity_results, report_format)
+                        ),
+                    };
+                }
+                if let Some(run) = &interleaved {
+                    report = match report_format {
+                        BenchmarkReportFormat::Json | BenchmarkReportFormat::JsonPretty => {
+                            let combined = serde_json::json!({
+                                "benchmarks": results,
+                                "phases": run.phases,
+                                "ordering": run.ordering_effects(),
+                            });
+                            if report_format == BenchmarkReportFormat::Json {
+                                combined.to_string()
+                            } else {
+                                serde_json::to_string_pretty(&combined)?
+                            }
+                        }
+                        BenchmarkReportFormat::Text => {
+                            format!("{report}\n{}", run.format_ordering())
+                        }
+                        // The ordering table is plain text; keep other formats parseable
+                        _ => report,
+                    };
+                }
 
                 println!("\n{report}");
+                if report_format == BenchmarkReportFormat::Text {
+                    if let Some(slo) = preset.as_ref().and_then(|p| p.slo_report(&results)) {
+                        println!("{slo}");
+                    }
+                }
 
                 // Save to file if specified
+                if let Some(output_path) = &output {
+                    fs::write(output_path, &report)?;
+                    println!("Report saved to: {output_path}");
+                }
+                save_benchmark_metrics(&results, output.as_deref());
+            }
+        }
+
+        cli::BenchmarkAction::Capacity {
+            gateways,
+            ip,
+            port,
+            path,
+            hostname,
+            slo_p99,
+            max_errors,
+            min_rps,
+            max_rps,
+            precision,
+            duration,
+            concurrency,
+            warmup,
+            format,
+            output,
+        } => {
+            let slo_ms = parse_latency_ms(&slo_p99)
+                .ok_or_else(|| anyhow::anyhow!("Invalid --slo-p99: {slo_p99}"))?;
+            let search = CapacitySearch::new(slo_ms)
+                .with_range(min_rps, max_rps)
+                .with_max_error_rate(max_errors / 100.0)
+                .with_precision(precision);
+
+            println!(
+                "Capacity search: p99 <= {slo_ms:.1}ms, {min_rps}-{max_rps} RPS, {duration}s per probe\n"
+            );
+
+            let mut results = Vec::new();
+            for gateway_name in gateways.split(',').map(|s| s.trim()) {
+                let Some(implementation) = GatewayImpl::from_str(gateway_name) else {
+                    println!("  ⚠ Unknown gateway: {gateway_name}");
+                    continue;
+                };
+
+                println!("Searching {}...", implementation.name());
+                let mut config = BenchmarkConfig::new(implementation, &ip)
+                    .with_duration(duration)
+                    .with_concurrency(concurrency)
+                    .with_path(&path)
+                    .with_hostname(&hostname)
+                    .with_warmup(warmup);
+                config.port = port;
+
+                match search.run(&config).await {
+                    Ok(result) => {
+                        println!("  ✓ {}", result.format_summary());
+                        results.push(result);
+                    }
+                    Err(e) => println!("  ✗ {}: Failed - {}", implementation.name(), e),
+                }
+            }
+
+            if !results.is_empty() {
+                let report_format =
+                    BenchmarkReportFormat::from_str(&format).unwrap_or(BenchmarkReportFormat::Text);
+                let report = BenchmarkReport::capacity(&results, report_format);
+
+                println!("\n{report}");
+
+                if let Some(output_path) = output {
+                    fs::write(&output_path, &report)?;
+                    println!("Report saved to: {output_path}");
+                }
+            }
+        }
+
+        cli::BenchmarkAction::Autotune {
+            gateways,
+            ip,
+            port,
+            path,
+            hostname,
+            target_p95,
+            max_errors,
+            min_concurrency,
+            max_concurrency,
+            max_steps,
+            duration,
+            warmup,
+            format,
+            output,
+        } => {
+            let target_ms = parse_latency_ms(&target_p95)
+                .ok_or_else(|| anyhow::anyhow!("Invalid --target-p95: {target_p95}"))?;
+            let tuner = ConcurrencyTuner::new(target_ms)
+                .with_range(min_concurrency, max_concurrency)
+                .with_max_error_rate(max_errors / 100.0)
+                .with_max_steps(max_steps);
+
+            println!(
+                "Autotuning: p95 <= {target_ms:.1}ms, concurrency {}-{}, {duration}s per step\n",
+                tuner.min_concurrency, tuner.max_concurrency
+            );
+
+            let mut results = Vec::new();
+            for gateway_name in gateways.split(',').map(|s| s.trim()) {
+                let Some(implementation) = GatewayImpl::from_str(gateway_name) else {
+                    println!("  ⚠ Unknown gateway: {gateway_name}");
+                    continue;
+                };
+
+                println!("Tuning {}...", implementation.name());
+                let mut config = BenchmarkConfig::new(implementation, &ip)
+                    .with_duration(duration)
+                    .with_path(&path)
+                    .with_hostname(&hostname)
+                    .with_warmup(warmup);
+                config.port = port;
+
+                match tuner.run(&config).await {
+                    Ok(result) => {
+                        println!("  ✓ {}", result.format_summary());
+                        results.push(result);
+                    }
+                    Err(e) => println!("  ✗ {}: Failed - {}", implementation.name(), e),
+                }
+            }
+
+            if !results.is_empty() {
+                let report_format =
+                    BenchmarkReportFormat::from_str(&format).unwrap_or(BenchmarkReportFormat::Text);
+                let report = BenchmarkReport::autotune(&results, report_format);
+
+                println!("\n{report}");
+
                 if let Some(output_path) = output {
                     fs::write(&output_path, &report)?;
                     println!("Report saved to: {output_path}");
@@ -895,11 +3952,45 @@ async fn run_benchmark(args: cli::BenchmarkArgs) -> Result<()> {
         }
     }
 
-    Ok(())
+    Ok(exit_code)
+}
+
+/// `GATEWAY_POC_*` variables that are set, with the option each one sets
+fn print_env_vars() {
+    let prefix = format!("{}_", config::env::ENV_PREFIX);
+    let mut vars: Vec<_> = std::env::vars()
+        .filter(|(var, _)| var.starts_with(&prefix))
+        .collect();
+    vars.sort();
+    if vars.is_empty() {
+        println!("No {prefix}* variables are set (see `gateway-poc config env`)");
+        return;
+    }
+    let options = cli::env_options();
+    let config_var = config::env::config_var();
+    println!("Environment Configuration:");
+    for (var, value) in &vars {
+        let target = match options.iter().find(|option| &option.var == var) {
+            Some(option) if option.command.is_empty() => option.flag.clone(),
+            Some(option) => format!("{} {}", option.command, option.flag),
+            None if *var == config_var => "config file".to_string(),
+            None if [
+                http::OIDC_CLIENT_ID_ENV,
+                http::OIDC_CLIENT_SECRET_ENV,
+                http::OIDC_SCOPE_ENV,
+            ]
+            .contains(&var.as_str()) =>
+            {
+                "--auth oidc".to_string()
+            }
+            None => "unknown, ignored".to_string(),
+        };
+        println!("  {var}={value}  ({target})");
+    }
 }
 
-fn manage_config(args: cli::ConfigArgs) -> Result<()> {
-    use config::{ConfigFile, EnvConfig, ProfileManager, TestProfile};
+fn manage_config(args: cli::ConfigArgs, config_file: Option<&config::ConfigFile>) -> Result<()> {
+    use config::{ConfigFile, ProfileManager, TestProfile};
     use std::path::Path;
 
     match args.action {
@@ -917,10 +4008,20 @@ fn manage_config(args: cli::ConfigArgs) -> Result<()> {
             println!("\nEdit the file to customize your settings.");
         }
 
-        cli::ConfigAction::Show { env, format } => {
-            if env {
-                let env_config = EnvConfig::load();
-                env_config.print_summary();
+        cli::ConfigAction::Show {
+            env,
+            resolved,
+            format,
+        } => {
+            if resolved {
+                let resolved = resolved_test_config(config_file)?;
+                if format == "json" {
+                    println!("{}", serde_json::to_string_pretty(&resolved)?);
+                } else {
+                    print!("{resolved}");
+                }
+            } else if env {
+                print_env_vars();
             } else {
                 let config = ConfigFile::load_default()?;
                 let output = if format == "json" {
@@ -993,6 +4094,9 @@ fn manage_config(args: cli::ConfigArgs) -> Result<()> {
                             "    Rounds: {}, Parallel: {}",
                             profile.rounds, profile.parallel
                         );
+                        for variant in &profile.round_variants {
+                            println!("    Variant: {} x{}", variant, variant.rounds);
+                        }
                         println!("    Tags: {:?}", profile.tags);
                         println!();
                     } else {
@@ -1007,6 +4111,35 @@ fn manage_config(args: cli::ConfigArgs) -> Result<()> {
             }
         }
 
+        cli::ConfigAction::Presets { detailed } => {
+            println!("Scenario Presets:");
+            println!("{:-<60}", "");
+            for preset in config::ScenarioPreset::predefined() {
+                if detailed {
+                    println!("  {} ({})", preset.name, preset.hostname);
+                    println!("    Description: {}", preset.description);
+                    println!(
+                        "    Load: {} RPS, {} connections",
+                        preset.rps, preset.concurrency
+                    );
+                    if let Some(slo) = preset.slo_p99_ms {
+                        println!("    SLO: p99 <= {slo:.0}ms");
+                    }
+                    println!("    Routes:");
+                    for route in &preset.routes {
+                        println!("      {} -> {}:{}", route.path, route.backend, route.port);
+                    }
+                    println!("    Mix:");
+                    for target in &preset.mix.targets {
+                        println!("      {:3}  {}", target.weight, target.key());
+                    }
+                    println!();
+                } else {
+                    println!("  {:20} - {}", preset.name, preset.description);
+                }
+            }
+        }
+
         cli::ConfigAction::Profile { name, profile_type } => {
             let manager = ProfileManager::new();
 
@@ -1047,22 +4180,9 @@ fn manage_config(args: cli::ConfigArgs) -> Result<()> {
                 ConfigFile::default()
             };
 
-            let value_display = value.clone();
-
-            // Set value based on key
-            match key.as_str() {
-                "app.default_gateway" => config.app.default_gateway = value,
-                "app.default_rounds" => config.app.default_rounds = value.parse()?,
-                "app.timeout_secs" => config.app.timeout_secs = value.parse()?,
-                "app.parallel" => config.app.parallel = value.parse()?,
-                "app.max_concurrent" => config.app.max_concurrent = value.parse()?,
-                _ => {
-                    anyhow::bail!("Unknown configuration key: {key}");
-                }
-            }
-
+            config.set_key(&key, &value)?;
             config.save(&path)?;
-            println!("✓ Set {key} = {value_display} in {path}");
+            println!("✓ Set {key} = {value} in {path}");
         }
 
         cli::ConfigAction::Get { key, file } => {
@@ -1072,18 +4192,10 @@ fn manage_config(args: cli::ConfigArgs) -> Result<()> {
                 ConfigFile::load_default()?
             };
 
-            let value = match key.as_str() {
-                "app.default_gateway" => config.app.default_gateway.clone(),
-                "app.default_rounds" => config.app.default_rounds.to_string(),
-                "app.timeout_secs" => config.app.timeout_secs.to_string(),
-                "app.parallel" => config.app.parallel.to_string(),
-                "app.max_concurrent" => config.app.max_concurrent.to_string(),
-                _ => {
-                    anyhow::bail!("Unknown configuration key: {key}");
-                }
-            };
-
-            println!("{value}");
+            match config.get_key(&key)? {
+                serde_yaml::Value::String(value) => println!("{value}"),
+                value => print!("{}", serde_yaml::to_string(&value)?),
+            }
         }
 
         cli::ConfigAction::Env => {
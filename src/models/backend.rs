@@ -0,0 +1,157 @@
+//! Backend workload identity
+//!
+//! Echo backends report which pod (and Service, namespace, node) served a
+//! request. Recording them per test shows whether two gateways were measured
+//! against the same backend placement, so differences caused by replica
+//! count or node spread are not blamed on the gateway.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// Backend pod that served a request
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct BackendIdentity {
+    pub pod: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node: Option<String>,
+}
+
+/// Requests one backend pod served during a test
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackendHits {
+    #[serde(flatten)]
+    pub backend: BackendIdentity,
+    pub requests: u64,
+}
+
+/// Replicas and nodes observed behind one Service
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServiceTopology {
+    /// Service name ("unknown" when the backend does not report it)
+    pub service: String,
+    /// Distinct pods that served traffic
+    pub replicas: usize,
+    /// Distinct nodes those pods ran on (empty when unknown)
+    pub nodes: Vec<String>,
+    pub requests: u64,
+}
+
+impl fmt::Display for ServiceTopology {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} replica(s)", self.service, self.replicas)?;
+        if !self.nodes.is_empty() {
+            write!(f, " on {} node(s)", self.nodes.len())?;
+        }
+        Ok(())
+    }
+}
+
+/// Backend placement observed during a round or run
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackendTopology {
+    pub services: Vec<ServiceTopology>,
+}
+
+impl BackendTopology {
+    /// Group backend hits by Service (None if no backend reported identity)
+    pub fn from_hits<'a>(hits: impl IntoIterator<Item = &'a BackendHits>) -> Option<Self> {
+        let mut services: BTreeMap<String, (BTreeSet<&str>, BTreeSet<&str>, u64)> = BTreeMap::new();
+        for hit in hits {
+            let service = hit.backend.service.as_deref().unwrap_or("unknown");
+            let (pods, nodes, requests) = services.entry(service.to_string()).or_default();
+            pods.insert(&hit.backend.pod);
+            nodes.extend(hit.backend.node.as_deref());
+            *requests += hit.requests;
+        }
+        if services.is_empty() {
+            return None;
+        }
+        Some(Self {
+            services: services
+                .into_iter()
+                .map(|(service, (pods, nodes, requests))| ServiceTopology {
+                    service,
+                    replicas: pods.len(),
+                    nodes: nodes.into_iter().map(str::to_string).collect(),
+                    requests,
+                })
+                .collect(),
+        })
+    }
+
+    /// Whether two topologies differ in replica count or node spread
+    pub fn differs_from(&self, other: &BackendTopology) -> bool {
+        let shape = |t: &BackendTopology| -> Vec<(String, usize, usize)> {
+            t.services
+                .iter()
+                .map(|s| (s.service.clone(), s.replicas, s.nodes.len()))
+                .collect()
+        };
+        shape(self) != shape(other)
+    }
+}
+
+impl fmt::Display for BackendTopology {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let services: Vec<String> = self.services.iter().map(|s| s.to_string()).collect();
+        f.write_str(&services.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hit(pod: &str, service: Option<&str>, node: Option<&str>, requests: u64) -> BackendHits {
+        BackendHits {
+            backend: BackendIdentity {
+                pod: pod.to_string(),
+                namespace: Some("default".to_string()),
+                service: service.map(str::to_string),
+                node: node.map(str::to_string),
+            },
+            requests,
+        }
+    }
+
+    #[test]
+    fn test_topology_from_hits() {
+        let hits = [
+            hit("echo-a", Some("echo"), Some("node-1"), 5),
+            hit("echo-b", Some("echo"), Some("node-2"), 3),
+            hit("echo-a", Some("echo"), Some("node-1"), 2),
+            hit("other-x", None, None, 1),
+        ];
+        let topology = BackendTopology::from_hits(&hits).unwrap();
+        assert_eq!(topology.services.len(), 2);
+        assert_eq!(topology.services[0].service, "echo");
+        assert_eq!(topology.services[0].replicas, 2);
+        assert_eq!(topology.services[0].requests, 10);
+        assert_eq!(
+            topology.to_string(),
+            "echo: 2 replica(s) on 2 node(s), unknown: 1 replica(s)"
+        );
+        assert!(BackendTopology::from_hits(&[]).is_none());
+    }
+
+    #[test]
+    fn test_topology_differs() {
+        let spread = BackendTopology::from_hits(&[
+            hit("a", Some("echo"), Some("n1"), 1),
+            hit("b", Some("echo"), Some("n2"), 1),
+        ])
+        .unwrap();
+        let packed = BackendTopology::from_hits(&[
+            hit("a", Some("echo"), Some("n1"), 9),
+            hit("b", Some("echo"), Some("n1"), 9),
+        ])
+        .unwrap();
+        assert!(spread.differs_from(&packed));
+        assert!(!spread.differs_from(&spread.clone()));
+    }
+}
@@ -0,0 +1,157 @@
+//! Implementation capabilities
+//!
+//! Optional features a test needs beyond core Gateway API. A gateway without
+//! the capability gets its tests marked not supported (N/A) instead of
+//! failed, so pass rates only count what the implementation offers.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use super::gateway::GatewayImpl;
+use super::test_filter::TestCategory;
+use super::TestCase;
+
+/// Optional feature provided through an implementation-specific policy
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Capability {
+    /// TLS (and client certificates) from the gateway to a backend
+    BackendTls,
+    RateLimiting,
+    SessionAffinity,
+    /// Header-keyed consistent-hash load balancing
+    ConsistentHashing,
+    /// CORS preflight handling
+    Cors,
+    /// AI/LLM inference routing (experimental AI tests)
+    AiRouting,
+}
+
+impl Capability {
+    pub fn all() -> [Capability; 6] {
+        [
+            Capability::BackendTls,
+            Capability::RateLimiting,
+            Capability::SessionAffinity,
+            Capability::ConsistentHashing,
+            Capability::Cors,
+            Capability::AiRouting,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Capability::BackendTls => "Backend TLS",
+            Capability::RateLimiting => "Rate Limiting",
+            Capability::SessionAffinity => "Session Affinity",
+            Capability::ConsistentHashing => "Consistent Hashing",
+            Capability::Cors => "CORS",
+            Capability::AiRouting => "AI Routing",
+        }
+    }
+
+    /// Capability a test needs, if it goes beyond core Gateway API
+    pub fn required_by(test_case: TestCase) -> Option<Capability> {
+        match test_case {
+            TestCase::BackendTls => Some(Capability::BackendTls),
+            TestCase::RateLimiting => Some(Capability::RateLimiting),
+            TestCase::SessionAffinity => Some(Capability::SessionAffinity),
+            TestCase::ConsistentHashing => Some(Capability::ConsistentHashing),
+            TestCase::Cors => Some(Capability::Cors),
+            _ if test_case.category() == TestCategory::Ai => Some(Capability::AiRouting),
+            _ => None,
+        }
+    }
+
+    /// Policy or extension providing the capability on `gateway`
+    pub fn provider(&self, gateway: GatewayImpl) -> Option<&'static str> {
+        match self {
+            Capability::BackendTls => gateway.backend_tls_policy(),
+            Capability::RateLimiting => gateway.rate_limit_policy(),
+            Capability::SessionAffinity => gateway.session_affinity_policy(),
+            Capability::ConsistentHashing => gateway.consistent_hash_policy(),
+            Capability::Cors => gateway.cors_policy(),
+            Capability::AiRouting => gateway.ai_extension(),
+        }
+    }
+
+    /// What `gateway` lacks, as in "Cilium Gateway has no ..."
+    fn missing(&self) -> &'static str {
+        match self {
+            Capability::BackendTls => "backend TLS policy",
+            Capability::RateLimiting => "rate-limit policy",
+            Capability::SessionAffinity => "session affinity policy",
+            Capability::ConsistentHashing => "header-keyed consistent-hash policy",
+            Capability::Cors => "CORS policy",
+            Capability::AiRouting => "AI routing extension",
+        }
+    }
+
+    /// Why `gateway` cannot run tests needing this capability
+    pub fn unsupported_reason(&self, gateway: GatewayImpl) -> Option<String> {
+        self.provider(gateway)
+            .is_none()
+            .then(|| format!("{} has no {}", gateway.name(), self.missing()))
+    }
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Capability table: which gateways provide which optional features
+pub fn capability_matrix(gateways: &[GatewayImpl]) -> String {
+    let width = gateways
+        .iter()
+        .map(|g| g.name().len())
+        .max()
+        .unwrap_or(0)
+        .max("Gateway".len());
+    let mut out = format!("{:<width$}", "Gateway");
+    for capability in Capability::all() {
+        out.push_str(&format!("  {}", capability.name()));
+    }
+    out.push('\n');
+    for gateway in gateways {
+        out.push_str(&format!("{:<width$}", gateway.name()));
+        for capability in Capability::all() {
+            let cell = if capability.provider(*gateway).is_some() {
+                "✓"
+            } else {
+                "N/A"
+            };
+            out.push_str(&format!("  {cell:<len$}", len = capability.name().len()));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_required_by() {
+        assert_eq!(
+            Capability::required_by(TestCase::RateLimiting),
+            Some(Capability::RateLimiting)
+        );
+        assert_eq!(
+            Capability::required_by(TestCase::AiTokenLatency),
+            Some(Capability::AiRouting)
+        );
+        assert_eq!(Capability::required_by(TestCase::HostRouting), None);
+    }
+
+    #[test]
+    fn test_capability_matrix() {
+        let matrix = capability_matrix(&[GatewayImpl::Envoy, GatewayImpl::Cilium]);
+        let cilium = matrix.lines().find(|l| l.starts_with("Cilium")).unwrap();
+        assert!(!cilium.contains('✓'));
+        let envoy = matrix.lines().find(|l| l.starts_with("Envoy")).unwrap();
+        assert_eq!(envoy.matches('✓').count(), Capability::all().len());
+    }
+}
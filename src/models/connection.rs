@@ -0,0 +1,85 @@
+//! Connection settings
+//!
+//! How the tool's HTTP clients open and reuse connections to the gateway.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::net::IpAddr;
+
+/// Connection reuse settings chosen per test run or benchmark
+///
+/// Whether connections are reused changes what the gateway sees (one
+/// source port per worker vs one per request), which matters for
+/// source-based affinity tests and for benchmark numbers alike.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConnectionSettings {
+    /// Reuse pooled connections (false = a new connection per request)
+    #[serde(default = "default_true")]
+    pub keep_alive: bool,
+    /// Idle connections kept per host (None = the client's default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_idle_per_host: Option<usize>,
+    /// Disable Nagle's algorithm
+    #[serde(default = "default_true")]
+    pub tcp_nodelay: bool,
+    /// Local addresses source-IP affinity tests send from, one client per address
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub source_addresses: Vec<IpAddr>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for ConnectionSettings {
+    fn default() -> Self {
+        Self {
+            keep_alive: true,
+            max_idle_per_host: None,
+            tcp_nodelay: true,
+            source_addresses: Vec::new(),
+        }
+    }
+}
+
+impl ConnectionSettings {
+    /// Settings from `--no-keep-alive`, `--max-idle-per-host` and `--no-tcp-nodelay`
+    pub fn new(keep_alive: bool, max_idle_per_host: Option<usize>, tcp_nodelay: bool) -> Self {
+        Self {
+            keep_alive,
+            max_idle_per_host,
+            tcp_nodelay,
+            source_addresses: Vec::new(),
+        }
+    }
+
+    /// Client addresses from `--source-address`
+    pub fn with_source_addresses(mut self, addresses: Vec<IpAddr>) -> Self {
+        self.source_addresses = addresses;
+        self
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl fmt::Display for ConnectionSettings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.keep_alive {
+            write!(f, "keep-alive")?;
+            if let Some(max_idle) = self.max_idle_per_host {
+                write!(f, " (max {max_idle} idle per host)")?;
+            }
+        } else {
+            write!(f, "new connection per request")?;
+        }
+        if !self.tcp_nodelay {
+            write!(f, ", Nagle enabled")?;
+        }
+        if !self.source_addresses.is_empty() {
+            write!(f, ", {} source addresses", self.source_addresses.len())?;
+        }
+        Ok(())
+    }
+}
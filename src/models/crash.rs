@@ -0,0 +1,154 @@
+//! Gateway pod crash models
+//!
+//! Container restarts and lost pods of the gateway observed during a test
+//! round or benchmark. Results measured across a crash are tainted.
+
+#![allow(dead_code)]
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Container state of a gateway pod at one point in time
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ContainerSnapshot {
+    pub name: String,
+    /// Container restartCount
+    pub restarts: u32,
+    /// Reason of the last termination, e.g. "OOMKilled (exit 137)"
+    pub last_termination: Option<String>,
+    /// Current waiting reason, e.g. "CrashLoopBackOff"
+    pub waiting: Option<String>,
+}
+
+/// Gateway pod state at one point in time
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PodSnapshot {
+    pub namespace: String,
+    pub name: String,
+    pub uid: String,
+    pub containers: Vec<ContainerSnapshot>,
+}
+
+/// A gateway container restart or pod loss during a window
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PodCrash {
+    /// Pod as namespace/name
+    pub pod: String,
+    /// Container (None = the whole pod went away)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub container: Option<String>,
+    /// Restarts during the window
+    pub restarts: u32,
+    /// Termination or waiting reason
+    pub reason: String,
+}
+
+impl PodCrash {
+    /// Crashes between two snapshots of the same pods
+    pub fn detect(before: &[PodSnapshot], after: &[PodSnapshot]) -> Vec<PodCrash> {
+        let mut crashes = Vec::new();
+
+        for pod in after {
+            let previous = before.iter().find(|p| p.uid == pod.uid);
+            for container in &pod.containers {
+                let baseline = previous
+                    .and_then(|p| p.containers.iter().find(|c| c.name == container.name))
+                    .map_or(0, |c| c.restarts);
+                if container.restarts <= baseline {
+                    continue;
+                }
+                crashes.push(PodCrash {
+                    pod: format!("{}/{}", pod.namespace, pod.name),
+                    container: Some(container.name.clone()),
+                    restarts: container.restarts - baseline,
+                    reason: container
+                        .last_termination
+                        .clone()
+                        .or_else(|| container.waiting.clone())
+                        .unwrap_or_else(|| "Restarted".to_string()),
+                });
+            }
+        }
+
+        for pod in before {
+            if !after.iter().any(|p| p.uid == pod.uid) {
+                crashes.push(PodCrash {
+                    pod: format!("{}/{}", pod.namespace, pod.name),
+                    container: None,
+                    restarts: 0,
+                    reason: "Pod terminated".to_string(),
+                });
+            }
+        }
+
+        crashes
+    }
+}
+
+impl fmt::Display for PodCrash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.pod)?;
+        if let Some(container) = &self.container {
+            write!(f, " [{container}]")?;
+        }
+        write!(f, ": {}", self.reason)?;
+        if self.restarts > 1 {
+            write!(f, " ({} restarts)", self.restarts)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pod(name: &str, restarts: u32, last_termination: Option<&str>) -> PodSnapshot {
+        PodSnapshot {
+            namespace: "envoy-gateway-system".to_string(),
+            name: name.to_string(),
+            uid: format!("uid-{name}"),
+            containers: vec![ContainerSnapshot {
+                name: "envoy".to_string(),
+                restarts,
+                last_termination: last_termination.map(String::from),
+                waiting: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_detect_restarts() {
+        let before = vec![
+            pod("proxy-a", 1, Some("Error (exit 1)")),
+            pod("proxy-b", 0, None),
+        ];
+        assert!(PodCrash::detect(&before, &before).is_empty());
+
+        let after = vec![
+            pod("proxy-a", 3, Some("OOMKilled (exit 137)")),
+            pod("proxy-b", 0, None),
+        ];
+        let crashes = PodCrash::detect(&before, &after);
+        assert_eq!(crashes.len(), 1);
+        assert_eq!(crashes[0].restarts, 2);
+        assert_eq!(
+            crashes[0].to_string(),
+            "envoy-gateway-system/proxy-a [envoy]: OOMKilled (exit 137) (2 restarts)"
+        );
+    }
+
+    #[test]
+    fn test_detect_replaced_pod() {
+        let before = vec![pod("proxy-a", 0, None)];
+        let after = vec![pod("proxy-c", 1, None)];
+        let crashes = PodCrash::detect(&before, &after);
+        assert_eq!(crashes.len(), 2);
+        assert_eq!(crashes[0].reason, "Restarted");
+        assert_eq!(crashes[1].container, None);
+        assert_eq!(
+            crashes[1].to_string(),
+            "envoy-gateway-system/proxy-a: Pod terminated"
+        );
+    }
+}
@@ -0,0 +1,283 @@
+//! DNS override settings
+//!
+//! Hostname → address overrides, nameservers and the address family used
+//! when the tool's HTTP clients resolve names (see `http::dns`).
+
+#![allow(dead_code)]
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::net::{IpAddr, SocketAddr};
+
+/// Which addresses of a dual-stack name connections use
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IpFamily {
+    /// Happy eyeballs (RFC 8305): IPv6 first, IPv4 raced after a short delay
+    #[default]
+    Auto,
+    Ipv4,
+    Ipv6,
+    /// Connect like `Auto`, but the dual-stack test requires both families
+    Dual,
+}
+
+impl IpFamily {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "auto" | "happy-eyeballs" => Some(IpFamily::Auto),
+            "ipv4" | "v4" | "4" => Some(IpFamily::Ipv4),
+            "ipv6" | "v6" | "6" => Some(IpFamily::Ipv6),
+            "dual" | "dual-stack" => Some(IpFamily::Dual),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IpFamily::Auto => "auto",
+            IpFamily::Ipv4 => "ipv4",
+            IpFamily::Ipv6 => "ipv6",
+            IpFamily::Dual => "dual",
+        }
+    }
+
+    pub(crate) fn is_auto(&self) -> bool {
+        *self == IpFamily::Auto
+    }
+
+    /// Connection order for resolved addresses
+    ///
+    /// `Auto` and `Dual` interleave families starting with IPv6; the connector tries
+    /// the first family and starts the other if it has not connected within
+    /// its fallback delay (300ms).
+    pub fn order(&self, addrs: Vec<IpAddr>) -> Vec<IpAddr> {
+        let (v6, v4): (Vec<IpAddr>, Vec<IpAddr>) = addrs.into_iter().partition(IpAddr::is_ipv6);
+        match self {
+            IpFamily::Ipv4 => v4,
+            IpFamily::Ipv6 => v6,
+            IpFamily::Auto | IpFamily::Dual => {
+                let mut ordered = Vec::with_capacity(v6.len() + v4.len());
+                let (mut v6, mut v4) = (v6.into_iter(), v4.into_iter());
+                loop {
+                    match (v6.next(), v4.next()) {
+                        (None, None) => break,
+                        (a, b) => ordered.extend(a.into_iter().chain(b)),
+                    }
+                }
+                ordered
+            }
+        }
+    }
+}
+
+impl fmt::Display for IpFamily {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A hostname pattern resolved to a fixed address
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HostOverride {
+    /// Exact hostname or `*.suffix` wildcard
+    pub host: String,
+    pub ip: IpAddr,
+}
+
+impl HostOverride {
+    fn matches(&self, hostname: &str) -> bool {
+        match self.host.strip_prefix("*.") {
+            Some(suffix) => hostname
+                .strip_suffix(suffix)
+                .is_some_and(|label| label.len() > 1 && label.ends_with('.')),
+            None => self.host == hostname,
+        }
+    }
+}
+
+impl fmt::Display for HostOverride {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", self.host, self.ip)
+    }
+}
+
+/// Hostname → address overrides applied before DNS, plus how the rest resolve
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DnsOverrides {
+    pub entries: Vec<HostOverride>,
+    /// Nameservers queried instead of the system resolver
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub nameservers: Vec<SocketAddr>,
+    /// Address family for dual-stack names
+    #[serde(default, skip_serializing_if = "IpFamily::is_auto")]
+    pub family: IpFamily,
+}
+
+impl DnsOverrides {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolve `host` (exact or `*.suffix`) to `ip`
+    pub fn add(mut self, host: &str, ip: IpAddr) -> Self {
+        self.entries.push(HostOverride {
+            host: normalize(host),
+            ip,
+        });
+        self
+    }
+
+    /// Parse `HOST=IP`; a bare `HOST` resolves to `default_ip`
+    pub fn parse_entry(spec: &str, default_ip: Option<IpAddr>) -> Result<HostOverride, String> {
+        let (host, ip) = match spec.split_once('=') {
+            Some((host, ip)) => (
+                host,
+                ip.trim()
+                    .parse::<IpAddr>()
+                    .map_err(|_| format!("Invalid IP address in '{spec}'"))?,
+            ),
+            None => (
+                spec,
+                default_ip.ok_or_else(|| format!("'{spec}' needs an address (HOST=IP)"))?,
+            ),
+        };
+
+        let host = normalize(host);
+        let name = host.strip_prefix("*.").unwrap_or(&host);
+        if name.is_empty() || name.contains(['*', '/', ':', ' ']) {
+            return Err(format!("Invalid hostname in '{spec}'"));
+        }
+        Ok(HostOverride { host, ip })
+    }
+
+    /// Parse `--resolve` specs
+    pub fn from_specs(specs: &[String], default_ip: Option<IpAddr>) -> Result<Self, String> {
+        let entries = specs
+            .iter()
+            .map(|spec| Self::parse_entry(spec, default_ip))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            entries,
+            ..Self::default()
+        })
+    }
+
+    /// Query these nameservers instead of the system resolver
+    pub fn with_nameservers(mut self, nameservers: Vec<SocketAddr>) -> Self {
+        self.nameservers = nameservers;
+        self
+    }
+
+    pub fn with_family(mut self, family: IpFamily) -> Self {
+        self.family = family;
+        self
+    }
+
+    /// Whether resolution is left entirely to the system defaults
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty() && self.nameservers.is_empty() && self.family.is_auto()
+    }
+
+    /// Address for a hostname; exact entries win over wildcards
+    pub fn lookup(&self, hostname: &str) -> Option<IpAddr> {
+        let hostname = normalize(hostname);
+        self.entries
+            .iter()
+            .find(|e| !e.host.starts_with("*.") && e.matches(&hostname))
+            .or_else(|| self.entries.iter().find(|e| e.matches(&hostname)))
+            .map(|e| e.ip)
+    }
+}
+
+impl fmt::Display for DnsOverrides {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts: Vec<String> = self.entries.iter().map(|e| e.to_string()).collect();
+        if !self.nameservers.is_empty() {
+            let servers: Vec<String> = self.nameservers.iter().map(|s| s.to_string()).collect();
+            parts.push(format!("via {}", servers.join(", ")));
+        }
+        match self.family {
+            IpFamily::Auto => {}
+            IpFamily::Dual => parts.push("dual-stack".to_string()),
+            family => parts.push(format!("{family} only")),
+        }
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+fn normalize(host: &str) -> String {
+    host.trim().trim_end_matches('.').to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup() {
+        let gateway: IpAddr = "10.0.0.1".parse().unwrap();
+        let other: IpAddr = "10.0.0.2".parse().unwrap();
+        let overrides = DnsOverrides::new()
+            .add("*.example.com", gateway)
+            .add("api.example.com", other);
+
+        assert_eq!(overrides.lookup("app1.example.com"), Some(gateway));
+        assert_eq!(overrides.lookup("APP1.Example.com."), Some(gateway));
+        assert_eq!(overrides.lookup("api.example.com"), Some(other));
+        assert_eq!(overrides.lookup("example.com"), None);
+        assert_eq!(overrides.lookup("badexample.com"), None);
+    }
+
+    #[test]
+    fn test_parse_entries() {
+        let gateway: IpAddr = "10.0.0.1".parse().unwrap();
+        let overrides = DnsOverrides::from_specs(
+            &[
+                "*.example.com".to_string(),
+                "db.test=192.168.1.5".to_string(),
+            ],
+            Some(gateway),
+        )
+        .unwrap();
+        assert_eq!(overrides.entries[0].ip, gateway);
+        assert_eq!(
+            overrides.to_string(),
+            "*.example.com=10.0.0.1, db.test=192.168.1.5"
+        );
+
+        assert!(DnsOverrides::parse_entry("app.example.com", None).is_err());
+        assert!(DnsOverrides::parse_entry("app=not-an-ip", None).is_err());
+        assert!(DnsOverrides::parse_entry("*.*.com=10.0.0.1", None).is_err());
+    }
+
+    #[test]
+    fn test_family_order() {
+        let addrs: Vec<IpAddr> = ["10.0.0.1", "10.0.0.2", "fd00::1", "fd00::2", "fd00::3"]
+            .iter()
+            .map(|a| a.parse().unwrap())
+            .collect();
+        let order = |family: IpFamily| -> Vec<String> {
+            family
+                .order(addrs.clone())
+                .iter()
+                .map(|a| a.to_string())
+                .collect()
+        };
+        assert_eq!(
+            order(IpFamily::Auto),
+            ["fd00::1", "10.0.0.1", "fd00::2", "10.0.0.2", "fd00::3"]
+        );
+        assert_eq!(order(IpFamily::Ipv4), ["10.0.0.1", "10.0.0.2"]);
+        assert_eq!(order(IpFamily::Ipv6).len(), 3);
+        assert_eq!(IpFamily::from_str("IPv6"), Some(IpFamily::Ipv6));
+        assert_eq!(IpFamily::from_str("v4"), Some(IpFamily::Ipv4));
+        assert_eq!(order(IpFamily::Dual), order(IpFamily::Auto));
+
+        let pinned = DnsOverrides::new().with_family(IpFamily::Ipv4);
+        assert!(!pinned.is_empty());
+        assert_eq!(pinned.to_string(), "ipv4 only");
+        let dual = DnsOverrides::new().with_family(IpFamily::Dual);
+        assert_eq!(dual.to_string(), "dual-stack");
+    }
+}
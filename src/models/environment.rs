@@ -0,0 +1,133 @@
+//! Cluster environment snapshot models
+//!
+//! Captures basic cluster health at the start of a test round.
+
+#![allow(dead_code)]
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// API server latency above which a round is considered anomalous
+pub const DEFAULT_API_LATENCY_THRESHOLD_MS: u64 = 1000;
+
+/// Cluster health snapshot taken before a test round
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RoundEnvironment {
+    /// Capture time (RFC 3339)
+    pub captured_at: String,
+    /// Total number of nodes
+    pub nodes_total: usize,
+    /// Nodes reporting Ready
+    pub nodes_ready: usize,
+    /// Active node pressure conditions (e.g. "node-1: MemoryPressure")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub node_pressure: Vec<String>,
+    /// Pods in Pending phase across all namespaces
+    pub pending_pods: usize,
+    /// Recent Warning events across all namespaces
+    pub warning_events: usize,
+    /// API server round-trip latency in milliseconds
+    pub api_latency_ms: Option<u64>,
+    /// Latency threshold used for anomaly detection
+    #[serde(default = "default_api_latency_threshold")]
+    pub api_latency_threshold_ms: u64,
+}
+
+fn default_api_latency_threshold() -> u64 {
+    DEFAULT_API_LATENCY_THRESHOLD_MS
+}
+
+impl RoundEnvironment {
+    /// Create an empty snapshot stamped with the current time
+    pub fn new() -> Self {
+        Self {
+            captured_at: chrono::Utc::now().to_rfc3339(),
+            api_latency_threshold_ms: DEFAULT_API_LATENCY_THRESHOLD_MS,
+            ..Default::default()
+        }
+    }
+
+    /// Reasons this snapshot looks unhealthy
+    pub fn anomalies(&self) -> Vec<String> {
+        let mut reasons = Vec::new();
+
+        if self.nodes_ready < self.nodes_total {
+            reasons.push(format!(
+                "{}/{} nodes ready",
+                self.nodes_ready, self.nodes_total
+            ));
+        }
+        reasons.extend(self.node_pressure.iter().cloned());
+        if self.pending_pods > 0 {
+            reasons.push(format!("{} pending pods", self.pending_pods));
+        }
+        match self.api_latency_ms {
+            Some(ms) if ms > self.api_latency_threshold_ms => {
+                reasons.push(format!("API server latency {ms}ms"));
+            }
+            None => reasons.push("API server unreachable".to_string()),
+            _ => {}
+        }
+
+        reasons
+    }
+
+    /// Whether the cluster was unhealthy when the round started
+    pub fn is_anomalous(&self) -> bool {
+        !self.anomalies().is_empty()
+    }
+}
+
+impl fmt::Display for RoundEnvironment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "nodes {}/{} ready, {} pending pods, {} warnings",
+            self.nodes_ready, self.nodes_total, self.pending_pods, self.warning_events
+        )?;
+        match self.api_latency_ms {
+            Some(ms) => write!(f, ", API {ms}ms"),
+            None => write!(f, ", API unreachable"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn healthy() -> RoundEnvironment {
+        RoundEnvironment {
+            nodes_total: 3,
+            nodes_ready: 3,
+            api_latency_ms: Some(20),
+            ..RoundEnvironment::new()
+        }
+    }
+
+    #[test]
+    fn test_healthy_environment() {
+        let env = healthy();
+        assert!(!env.is_anomalous());
+        assert_eq!(
+            env.to_string(),
+            "nodes 3/3 ready, 0 pending pods, 0 warnings, API 20ms"
+        );
+    }
+
+    #[test]
+    fn test_anomalous_environment() {
+        let env = RoundEnvironment {
+            nodes_ready: 2,
+            pending_pods: 4,
+            node_pressure: vec!["node-1: MemoryPressure".to_string()],
+            api_latency_ms: Some(2500),
+            ..healthy()
+        };
+
+        let reasons = env.anomalies();
+        assert_eq!(reasons.len(), 4);
+        assert!(reasons.contains(&"2/3 nodes ready".to_string()));
+        assert!(env.is_anomalous());
+    }
+}
@@ -1,15 +1,21 @@
 //! Gateway implementation models
 //!
-//! Defines the 7 Gateway implementations being tested.
+//! Defines the 9 built-in Gateway implementations being tested; further
+//! implementations come from the registry.
 
 #![allow(dead_code)]
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
 
+use super::capability::Capability;
+use super::gateway_api::{GatewayApiChannel, GatewayApiSpec, GatewayApiVersion};
+use super::registry::{self, GatewayDefinition};
+use super::test_filter::TestFilter;
+use super::{ConnectionSettings, DnsOverrides, TestCase};
+
 /// Supported Gateway implementations
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum GatewayImpl {
     Nginx,
     Envoy,
@@ -18,9 +24,21 @@ pub enum GatewayImpl {
     Kong,
     Traefik,
     Kgateway,
+    Contour,
+    Apisix,
+    /// User-defined implementation (index into the registry)
+    Custom(u16),
 }
 
 impl GatewayImpl {
+    /// Definition of a user-defined implementation
+    pub fn definition(&self) -> Option<&'static GatewayDefinition> {
+        match self {
+            GatewayImpl::Custom(index) => registry::definition(*index),
+            _ => None,
+        }
+    }
+
     /// Get gateway display name
     pub fn name(&self) -> &'static str {
         match self {
@@ -31,12 +49,150 @@ impl GatewayImpl {
             GatewayImpl::Kong => "Kong Gateway",
             GatewayImpl::Traefik => "Traefik Gateway",
             GatewayImpl::Kgateway => "kgateway",
+            GatewayImpl::Contour => "Contour",
+            GatewayImpl::Apisix => "Apache APISIX",
+            GatewayImpl::Custom(_) => self
+                .definition()
+                .map(|d| d.display_name.as_deref().unwrap_or(&d.name))
+                .unwrap_or("unknown"),
         }
     }
 
     /// Check if ARM64 is supported
     pub fn supports_arm64(&self) -> bool {
-        !matches!(self, GatewayImpl::Kgateway)
+        match self {
+            GatewayImpl::Kgateway => false,
+            GatewayImpl::Custom(_) => self.definition().is_some_and(|d| d.arm64),
+            _ => true,
+        }
+    }
+
+    /// Implementation policy providing header-keyed consistent-hash load balancing
+    pub fn consistent_hash_policy(&self) -> Option<&'static str> {
+        match self {
+            GatewayImpl::Nginx => Some("UpstreamSettingsPolicy"),
+            GatewayImpl::Envoy => Some("BackendTrafficPolicy"),
+            GatewayImpl::Istio => Some("DestinationRule"),
+            GatewayImpl::Kong => Some("KongUpstreamPolicy"),
+            GatewayImpl::Kgateway => Some("BackendConfigPolicy"),
+            GatewayImpl::Cilium
+            | GatewayImpl::Traefik
+            | GatewayImpl::Contour
+            | GatewayImpl::Apisix => None,
+            GatewayImpl::Custom(_) => self
+                .definition()
+                .and_then(|d| d.consistent_hash_policy.as_deref()),
+        }
+    }
+
+    /// Policy configuring TLS from the gateway to a backend Service
+    /// (BackendTLSPolicy or the implementation's equivalent)
+    pub fn backend_tls_policy(&self) -> Option<&'static str> {
+        match self {
+            GatewayImpl::Nginx
+            | GatewayImpl::Envoy
+            | GatewayImpl::Istio
+            | GatewayImpl::Traefik
+            | GatewayImpl::Kgateway
+            | GatewayImpl::Contour => Some("BackendTLSPolicy"),
+            GatewayImpl::Apisix => Some("ApisixUpstream"),
+            GatewayImpl::Cilium | GatewayImpl::Kong => None,
+            GatewayImpl::Custom(_) => self
+                .definition()
+                .and_then(|d| d.backend_tls_policy.as_deref()),
+        }
+    }
+
+    /// Policy pinning a client to one backend (cookie-based where the
+    /// implementation supports it)
+    pub fn session_affinity_policy(&self) -> Option<&'static str> {
+        match self {
+            GatewayImpl::Nginx => Some("UpstreamSettingsPolicy"),
+            GatewayImpl::Envoy => Some("BackendTrafficPolicy"),
+            GatewayImpl::Istio => Some("DestinationRule"),
+            GatewayImpl::Kong => Some("KongUpstreamPolicy"),
+            GatewayImpl::Kgateway => Some("BackendConfigPolicy"),
+            GatewayImpl::Apisix => Some("ApisixUpstream"),
+            GatewayImpl::Cilium | GatewayImpl::Traefik | GatewayImpl::Contour => None,
+            GatewayImpl::Custom(_) => self
+                .definition()
+                .and_then(|d| d.session_affinity_policy.as_deref()),
+        }
+    }
+
+    /// Policy limiting the request rate on a route (rate limiting is not part
+    /// of core Gateway API)
+    pub fn rate_limit_policy(&self) -> Option<&'static str> {
+        match self {
+            GatewayImpl::Envoy => Some("BackendTrafficPolicy"),
+            GatewayImpl::Istio => Some("EnvoyFilter"),
+            GatewayImpl::Kong => Some("KongPlugin"),
+            GatewayImpl::Traefik => Some("Middleware"),
+            GatewayImpl::Kgateway => Some("TrafficPolicy"),
+            GatewayImpl::Nginx
+            | GatewayImpl::Cilium
+            | GatewayImpl::Contour
+            | GatewayImpl::Apisix => None,
+            GatewayImpl::Custom(_) => self
+                .definition()
+                .and_then(|d| d.rate_limit_policy.as_deref()),
+        }
+    }
+
+    /// Policy answering CORS preflights and adding Access-Control-* headers
+    pub fn cors_policy(&self) -> Option<&'static str> {
+        match self {
+            GatewayImpl::Envoy => Some("SecurityPolicy"),
+            GatewayImpl::Kong => Some("KongPlugin"),
+            GatewayImpl::Traefik => Some("Middleware"),
+            GatewayImpl::Kgateway => Some("TrafficPolicy"),
+            GatewayImpl::Nginx
+            | GatewayImpl::Istio
+            | GatewayImpl::Cilium
+            | GatewayImpl::Contour
+            | GatewayImpl::Apisix => None,
+            GatewayImpl::Custom(_) => self.definition().and_then(|d| d.cors_policy.as_deref()),
+        }
+    }
+
+    /// Extension routing AI/LLM inference traffic (experimental AI tests)
+    pub fn ai_extension(&self) -> Option<&'static str> {
+        match self {
+            GatewayImpl::Kgateway => Some("AI Backend (agentgateway)"),
+            GatewayImpl::Envoy => Some("Envoy AI Gateway"),
+            GatewayImpl::Kong => Some("AI Proxy plugin"),
+            GatewayImpl::Apisix => Some("ai-proxy plugin"),
+            GatewayImpl::Nginx
+            | GatewayImpl::Istio
+            | GatewayImpl::Cilium
+            | GatewayImpl::Traefik
+            | GatewayImpl::Contour => None,
+            GatewayImpl::Custom(_) => self.definition().and_then(|d| d.ai_extension.as_deref()),
+        }
+    }
+
+    /// Oldest Gateway API release and channel the implementation works with
+    pub fn gateway_api_requirement(&self) -> Option<GatewayApiSpec> {
+        let (version, channel) = match self {
+            GatewayImpl::Nginx | GatewayImpl::Kong | GatewayImpl::Contour | GatewayImpl::Apisix => {
+                (GatewayApiVersion::V1_0, GatewayApiChannel::Standard)
+            }
+            GatewayImpl::Istio | GatewayImpl::Traefik => {
+                (GatewayApiVersion::V1_1, GatewayApiChannel::Standard)
+            }
+            // Both watch TLSRoute, which only the experimental channel ships
+            GatewayImpl::Envoy | GatewayImpl::Cilium => {
+                (GatewayApiVersion::V1_1, GatewayApiChannel::Experimental)
+            }
+            GatewayImpl::Kgateway => (GatewayApiVersion::V1_2, GatewayApiChannel::Standard),
+            GatewayImpl::Custom(_) => return self.definition().and_then(|d| d.gateway_api),
+        };
+        Some(GatewayApiSpec::new(version, channel))
+    }
+
+    /// Why a test cannot run against this implementation, if it cannot
+    pub fn unsupported_reason(&self, test_case: TestCase) -> Option<String> {
+        Capability::required_by(test_case)?.unsupported_reason(*self)
     }
 
     /// Get GatewayClass name
@@ -49,9 +205,41 @@ impl GatewayImpl {
             GatewayImpl::Kong => "kong",
             GatewayImpl::Traefik => "traefik",
             GatewayImpl::Kgateway => "kgateway",
+            GatewayImpl::Contour => "contour",
+            GatewayImpl::Apisix => "apisix",
+            GatewayImpl::Custom(_) => self
+                .definition()
+                .map(|d| d.gateway_class.as_str())
+                .unwrap_or("unknown"),
         }
     }
 
+    /// GatewayClass `controllerName` of the implementation's controller
+    pub fn controller_name(&self) -> Option<&'static str> {
+        match self {
+            GatewayImpl::Nginx => Some("gateway.nginx.org/nginx-gateway-controller"),
+            GatewayImpl::Envoy => Some("gateway.envoyproxy.io/gatewayclass-controller"),
+            GatewayImpl::Istio => Some("istio.io/gateway-controller"),
+            GatewayImpl::Cilium => Some("io.cilium/gateway-controller"),
+            GatewayImpl::Kong => Some("konghq.com/kic-gateway-controller"),
+            GatewayImpl::Traefik => Some("traefik.io/gateway-controller"),
+            GatewayImpl::Kgateway => Some("kgateway.dev/kgateway"),
+            GatewayImpl::Contour => Some("projectcontour.io/gateway-controller"),
+            GatewayImpl::Apisix => Some("apisix.apache.org/apisix-ingress-controller"),
+            GatewayImpl::Custom(_) => self.definition().and_then(|d| d.controller_name.as_deref()),
+        }
+    }
+
+    /// Implementation running a GatewayClass, by controller name and then
+    /// by class name
+    pub fn from_gateway_class(class_name: &str, controller_name: &str) -> Option<Self> {
+        let all = Self::all();
+        all.iter()
+            .copied()
+            .find(|g| g.controller_name() == Some(controller_name))
+            .or_else(|| all.into_iter().find(|g| g.gateway_class() == class_name))
+    }
+
     /// Get short name for Helm releases
     pub fn short_name(&self) -> &'static str {
         match self {
@@ -62,6 +250,12 @@ impl GatewayImpl {
             GatewayImpl::Kong => "kong",
             GatewayImpl::Traefik => "traefik",
             GatewayImpl::Kgateway => "kgateway",
+            GatewayImpl::Contour => "contour",
+            GatewayImpl::Apisix => "apisix",
+            GatewayImpl::Custom(_) => self
+                .definition()
+                .map(|d| d.name.as_str())
+                .unwrap_or("unknown"),
         }
     }
 
@@ -75,11 +269,74 @@ impl GatewayImpl {
             GatewayImpl::Kong => "app.kubernetes.io/name=kong",
             GatewayImpl::Traefik => "app.kubernetes.io/name=traefik",
             GatewayImpl::Kgateway => "app.kubernetes.io/name=kgateway",
+            GatewayImpl::Contour => "control-plane=contour-gateway-provisioner",
+            GatewayImpl::Apisix => "app.kubernetes.io/name=apisix-ingress-controller",
+            GatewayImpl::Custom(_) => self
+                .definition()
+                .map(|d| d.pod_selector.as_str())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Name of the Gateway resource the test topology creates
+    pub fn gateway_name(&self) -> String {
+        format!("{}-gateway", self.gateway_class())
+    }
+
+    /// Label selector of the data-plane pods serving the Gateway named
+    /// `gateway`, if separate from the controller
+    ///
+    /// Implementations provisioning a data plane per Gateway label it with
+    /// the Gateway's name, so pods of other Gateways never match. Cilium
+    /// and APISIX run one data plane per installation.
+    pub fn data_plane_selector(&self, gateway: &str) -> Option<String> {
+        match self {
+            GatewayImpl::Envoy => Some(format!(
+                "app.kubernetes.io/managed-by=envoy-gateway,app.kubernetes.io/component=proxy,\
+                 gateway.envoyproxy.io/owning-gateway-name={gateway}"
+            )),
+            GatewayImpl::Cilium => Some("app.kubernetes.io/name=cilium-envoy".to_string()),
+            GatewayImpl::Contour => Some(format!(
+                "app.kubernetes.io/managed-by=contour-gateway-provisioner,\
+                 projectcontour.io/owning-gateway-name={gateway}"
+            )),
+            GatewayImpl::Apisix => Some("app.kubernetes.io/name=apisix".to_string()),
+            GatewayImpl::Nginx | GatewayImpl::Istio | GatewayImpl::Kgateway => {
+                Some(format!("gateway.networking.k8s.io/gateway-name={gateway}"))
+            }
+            GatewayImpl::Kong | GatewayImpl::Traefik => None,
+            GatewayImpl::Custom(_) => self
+                .definition()
+                .and_then(|d| d.data_plane_selector.clone())
+                .filter(|s| !s.is_empty()),
         }
     }
 
-    /// Get all gateway implementations
+    /// Selector of the pods proxying the Gateway's traffic: its data plane,
+    /// or the controller where that proxies itself (None = unknown)
+    pub fn serving_pod_selector(&self, gateway: &str) -> Option<String> {
+        self.data_plane_selector(gateway)
+            .or_else(|| Some(self.pod_selector().to_string()))
+            .filter(|s| !s.is_empty())
+    }
+
+    /// Selectors of the controller pods and the Gateway's data-plane pods
+    pub fn all_pod_selectors(&self, gateway: &str) -> Vec<String> {
+        std::iter::once(self.pod_selector().to_string())
+            .chain(self.data_plane_selector(gateway))
+            .filter(|s| !s.is_empty())
+            .collect()
+    }
+
+    /// Get all gateway implementations (built-in, then registered)
     pub fn all() -> Vec<GatewayImpl> {
+        let mut all = Self::builtin();
+        all.extend(registry::custom());
+        all
+    }
+
+    /// Get the built-in gateway implementations
+    pub fn builtin() -> Vec<GatewayImpl> {
         vec![
             GatewayImpl::Nginx,
             GatewayImpl::Envoy,
@@ -88,6 +345,8 @@ impl GatewayImpl {
             GatewayImpl::Kong,
             GatewayImpl::Traefik,
             GatewayImpl::Kgateway,
+            GatewayImpl::Contour,
+            GatewayImpl::Apisix,
         ]
     }
 
@@ -109,9 +368,32 @@ impl GatewayImpl {
             "kong" | "kong-gateway" => Some(GatewayImpl::Kong),
             "traefik" | "traefik-gateway" => Some(GatewayImpl::Traefik),
             "kgateway" | "gloo" => Some(GatewayImpl::Kgateway),
-            _ => None,
+            "contour" | "contour-gateway" => Some(GatewayImpl::Contour),
+            "apisix" | "apache-apisix" => Some(GatewayImpl::Apisix),
+            name => registry::find(name),
         }
     }
+
+    pub fn is_custom(&self) -> bool {
+        matches!(self, GatewayImpl::Custom(_))
+    }
+}
+
+// Serialized by short name, so custom implementations round-trip by name
+impl Serialize for GatewayImpl {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.short_name())
+    }
+}
+
+impl<'de> Deserialize<'de> for GatewayImpl {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        // Results of a custom gateway stay readable without its definition
+        GatewayImpl::from_str(&name)
+            .or_else(|| registry::unregistered(&name))
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown gateway: {name}")))
+    }
 }
 
 impl fmt::Display for GatewayImpl {
@@ -130,6 +412,17 @@ pub struct GatewayConfig {
     pub https_port: u16,
     pub grpc_port: Option<u16>,
     pub hostname: String,
+    /// Apply implementation-specific policies (rate limit, session affinity)
+    /// before the tests that need them
+    #[serde(default)]
+    pub setup_policies: bool,
+    /// Namespace of the gateway's controller and data-plane pods (None =
+    /// the Gateway's namespace)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pod_namespace: Option<String>,
+    /// Secrets the test fixtures pull their images with
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub image_pull_secrets: Vec<String>,
 }
 
 impl GatewayConfig {
@@ -137,11 +430,14 @@ impl GatewayConfig {
         Self {
             implementation,
             namespace: "default".to_string(),
-            name: format!("{}-gateway", implementation.gateway_class()),
+            name: implementation.gateway_name(),
             http_port: 80,
             https_port: 443,
             grpc_port: Some(9090),
             hostname: "example.com".to_string(),
+            setup_policies: false,
+            pod_namespace: None,
+            image_pull_secrets: Vec::new(),
         }
     }
 
@@ -166,6 +462,228 @@ impl GatewayConfig {
         self.grpc_port = grpc;
         self
     }
+
+    pub fn with_policy_setup(mut self, setup_policies: bool) -> Self {
+        self.setup_policies = setup_policies;
+        self
+    }
+
+    pub fn with_pod_namespace(mut self, namespace: Option<String>) -> Self {
+        self.pod_namespace = namespace;
+        self
+    }
+
+    pub fn with_image_pull_secrets(mut self, names: Vec<String>) -> Self {
+        self.image_pull_secrets = names;
+        self
+    }
+
+    /// Namespace the gateway's pods run in
+    pub fn pods_namespace(&self) -> &str {
+        self.pod_namespace.as_deref().unwrap_or(&self.namespace)
+    }
+}
+
+/// Engine used by the functional Load Test (test 16)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LoadTestEngine {
+    /// Fixed number of requests split across concurrent users
+    #[default]
+    Simple,
+    /// Time-based run on the benchmark engine (percentile metrics)
+    Benchmark,
+}
+
+impl LoadTestEngine {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "simple" => Some(Self::Simple),
+            "benchmark" => Some(Self::Benchmark),
+            _ => None,
+        }
+    }
+}
+
+/// Load Test (test 16) parameters and pass thresholds
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoadTestSettings {
+    pub engine: LoadTestEngine,
+    pub concurrent_users: usize,
+    /// Total requests (simple engine)
+    pub total_requests: usize,
+    /// Run duration in seconds (benchmark engine)
+    pub duration_secs: u64,
+    /// Target RPS, 0 for maximum throughput (benchmark engine)
+    pub rps: u32,
+    /// Minimum success rate in percent
+    pub expected_success_rate: f64,
+    pub max_avg_latency_ms: u64,
+    pub max_p99_latency_ms: Option<u64>,
+}
+
+impl Default for LoadTestSettings {
+    fn default() -> Self {
+        Self {
+            engine: LoadTestEngine::Simple,
+            concurrent_users: 10,
+            total_requests: 100,
+            duration_secs: 10,
+            rps: 0,
+            expected_success_rate: 95.0,
+            max_avg_latency_ms: 1000,
+            max_p99_latency_ms: None,
+        }
+    }
+}
+
+/// Canary Traffic (test 7) parameters
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CanarySettings {
+    /// Confidence level of the distribution check, as a fraction (0.99)
+    pub confidence: f64,
+}
+
+impl Default for CanarySettings {
+    fn default() -> Self {
+        Self { confidence: 0.99 }
+    }
+}
+
+impl CanarySettings {
+    pub fn new(confidence: f64) -> Self {
+        Self { confidence }
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Parse a confidence level given as a fraction ("0.99") or in percent
+    /// ("99", "99%")
+    pub fn parse_confidence(s: &str) -> Result<f64, String> {
+        let trimmed = s.trim();
+        let (digits, percent) = match trimmed.strip_suffix('%') {
+            Some(digits) => (digits, true),
+            None => (trimmed, false),
+        };
+        let value: f64 = digits
+            .trim()
+            .parse()
+            .map_err(|_| format!("Invalid confidence level '{s}'"))?;
+        let confidence = if percent || value > 1.0 {
+            value / 100.0
+        } else {
+            value
+        };
+        if !(0.5..=0.9999).contains(&confidence) {
+            return Err(format!(
+                "Confidence level '{s}' must be between 0.5 and 0.9999"
+            ));
+        }
+        Ok(confidence)
+    }
+}
+
+/// Fault the Failover Recovery test (test 17) injects into its backend
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BackendFault {
+    /// Delete the pod that served the baseline request
+    DeletePod,
+    /// Cordon the pod's node, then delete the pod so it is not replaced there
+    CordonNode,
+    /// Scale the backend Deployment to zero replicas, then back
+    ScaleToZero,
+    /// Inject nothing; only watch for recovery (needs no cluster access)
+    #[default]
+    None,
+}
+
+impl BackendFault {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::DeletePod => "delete-pod",
+            Self::CordonNode => "cordon-node",
+            Self::ScaleToZero => "scale-to-zero",
+            Self::None => "none",
+        }
+    }
+
+    pub fn all() -> [Self; 4] {
+        [
+            Self::DeletePod,
+            Self::CordonNode,
+            Self::ScaleToZero,
+            Self::None,
+        ]
+    }
+
+    /// Parse a fault name ("delete-pod", "cordon-node", "scale-to-zero", "none")
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let name = s.trim().to_lowercase();
+        Self::all()
+            .into_iter()
+            .find(|fault| fault.name() == name)
+            .ok_or_else(|| {
+                let names: Vec<_> = Self::all().iter().map(|f| f.name()).collect();
+                format!("Unknown fault '{s}' (expected {})", names.join(", "))
+            })
+    }
+
+    /// Whether the fault takes down one pod rather than the whole backend
+    pub fn targets_pod(&self) -> bool {
+        matches!(self, Self::DeletePod | Self::CordonNode)
+    }
+}
+
+impl fmt::Display for BackendFault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Failover Recovery (test 17) parameters
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FailoverSettings {
+    pub fault: BackendFault,
+}
+
+impl FailoverSettings {
+    pub fn new(fault: BackendFault) -> Self {
+        Self { fault }
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Re-runs of failed tests
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// Retries after a failed or errored attempt (0 = no retries)
+    pub retries: u32,
+    /// Delay between attempts in milliseconds
+    pub delay_ms: u64,
+}
+
+impl RetryPolicy {
+    pub fn new(retries: u32, delay_ms: u64) -> Self {
+        Self { retries, delay_ms }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.retries > 0
+    }
+
+    pub fn delay(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.delay_ms)
+    }
 }
 
 /// Gateway test configuration
@@ -175,7 +693,26 @@ pub struct TestConfig {
     pub rounds: u32,
     pub parallel: bool,
     pub timeout_secs: u64,
-    pub skip_tests: Vec<u8>,
+    /// Partial suite selection (skip list, categories, tags)
+    #[serde(default)]
+    pub filter: TestFilter,
+    #[serde(default)]
+    pub load_test: LoadTestSettings,
+    #[serde(default, skip_serializing_if = "CanarySettings::is_default")]
+    pub canary: CanarySettings,
+    #[serde(default, skip_serializing_if = "FailoverSettings::is_default")]
+    pub failover: FailoverSettings,
+    /// Gateway API spec level the cluster provides (None = run every test)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gateway_api: Option<GatewayApiSpec>,
+    /// Hostnames resolved to fixed addresses by the test clients
+    #[serde(default, skip_serializing_if = "DnsOverrides::is_empty")]
+    pub dns_overrides: DnsOverrides,
+    /// Connection reuse of the test clients
+    #[serde(default, skip_serializing_if = "ConnectionSettings::is_default")]
+    pub connection: ConnectionSettings,
+    #[serde(default)]
+    pub retry: RetryPolicy,
 }
 
 impl TestConfig {
@@ -185,22 +722,80 @@ impl TestConfig {
             rounds: 1,
             parallel: false,
             timeout_secs: 30,
-            skip_tests: Vec::new(),
+            filter: TestFilter::default(),
+            load_test: LoadTestSettings::default(),
+            canary: CanarySettings::default(),
+            failover: FailoverSettings::default(),
+            gateway_api: None,
+            dns_overrides: DnsOverrides::default(),
+            connection: ConnectionSettings::default(),
+            retry: RetryPolicy::default(),
         }
     }
 
+    /// Re-run failed tests, classifying passes on retry as flaky
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Resolve test hostnames to fixed addresses (e.g. the gateway IP)
+    pub fn with_dns_overrides(mut self, overrides: DnsOverrides) -> Self {
+        self.dns_overrides = overrides;
+        self
+    }
+
+    /// Reuse connections or open one per request
+    pub fn with_connection(mut self, connection: ConnectionSettings) -> Self {
+        self.connection = connection;
+        self
+    }
+
+    /// Skip tests the given Gateway API spec level does not define
+    pub fn with_gateway_api(mut self, spec: Option<GatewayApiSpec>) -> Self {
+        self.gateway_api = spec;
+        self
+    }
+
+    pub fn with_load_test(mut self, load_test: LoadTestSettings) -> Self {
+        self.load_test = load_test;
+        self
+    }
+
+    pub fn with_canary(mut self, canary: CanarySettings) -> Self {
+        self.canary = canary;
+        self
+    }
+
+    pub fn with_failover(mut self, failover: FailoverSettings) -> Self {
+        self.failover = failover;
+        self
+    }
+
     pub fn with_rounds(mut self, rounds: u32) -> Self {
         self.rounds = rounds;
         self
     }
 
+    /// Set the per-request HTTP timeout
+    pub fn with_timeout(mut self, secs: u64) -> Self {
+        self.timeout_secs = secs;
+        self
+    }
+
     pub fn parallel(mut self, parallel: bool) -> Self {
         self.parallel = parallel;
         self
     }
 
     pub fn skip_test(mut self, test_number: u8) -> Self {
-        self.skip_tests.push(test_number);
+        self.filter.skip.push(test_number);
+        self
+    }
+
+    /// Run only the tests the filter selects
+    pub fn with_filter(mut self, filter: TestFilter) -> Self {
+        self.filter = filter;
         self
     }
 }
@@ -216,23 +811,169 @@ mod tests {
         assert!(!GatewayImpl::Kgateway.supports_arm64());
     }
 
+    #[test]
+    fn test_pod_selectors() {
+        let nginx = GatewayImpl::Nginx.gateway_name();
+        assert_eq!(nginx, "nginx-gateway");
+        // Other Gateways' data planes never match
+        assert_eq!(
+            GatewayImpl::Nginx.data_plane_selector(&nginx).as_deref(),
+            Some("gateway.networking.k8s.io/gateway-name=nginx-gateway")
+        );
+        assert!(GatewayImpl::Envoy
+            .data_plane_selector("eg-gateway")
+            .unwrap()
+            .ends_with(",gateway.envoyproxy.io/owning-gateway-name=eg-gateway"));
+        assert_eq!(
+            GatewayImpl::Kong
+                .serving_pod_selector("kong-gateway")
+                .as_deref(),
+            Some("app.kubernetes.io/name=kong")
+        );
+        // Cilium's data plane, not its CNI agent
+        assert_eq!(
+            GatewayImpl::Cilium
+                .serving_pod_selector("cilium-gateway")
+                .as_deref(),
+            Some("app.kubernetes.io/name=cilium-envoy")
+        );
+        assert_eq!(
+            GatewayImpl::Traefik
+                .all_pod_selectors("traefik-gateway")
+                .len(),
+            1
+        );
+    }
+
     #[test]
     fn test_gateway_from_str() {
         assert_eq!(GatewayImpl::from_str("nginx"), Some(GatewayImpl::Nginx));
         assert_eq!(GatewayImpl::from_str("ENVOY"), Some(GatewayImpl::Envoy));
+        assert_eq!(
+            GatewayImpl::from_str("apache-apisix"),
+            Some(GatewayImpl::Apisix)
+        );
+        assert_eq!(GatewayImpl::Contour.gateway_class(), "contour");
         assert_eq!(GatewayImpl::from_str("unknown"), None);
     }
 
+    #[test]
+    fn test_consistent_hash_support() {
+        assert!(GatewayImpl::Envoy
+            .unsupported_reason(TestCase::ConsistentHashing)
+            .is_none());
+        assert!(GatewayImpl::Traefik
+            .unsupported_reason(TestCase::ConsistentHashing)
+            .is_some());
+        assert!(GatewayImpl::Traefik
+            .unsupported_reason(TestCase::HostRouting)
+            .is_none());
+    }
+
+    #[test]
+    fn test_backend_tls_policy() {
+        assert_eq!(
+            GatewayImpl::Envoy.backend_tls_policy(),
+            Some("BackendTLSPolicy")
+        );
+        assert_eq!(
+            GatewayImpl::Apisix.backend_tls_policy(),
+            Some("ApisixUpstream")
+        );
+        assert!(GatewayImpl::Cilium.backend_tls_policy().is_none());
+    }
+
+    #[test]
+    fn test_session_affinity_support() {
+        assert_eq!(
+            GatewayImpl::Kong.session_affinity_policy(),
+            Some("KongUpstreamPolicy")
+        );
+        assert!(GatewayImpl::Contour
+            .unsupported_reason(TestCase::SessionAffinity)
+            .is_some());
+        assert!(GatewayImpl::Istio
+            .unsupported_reason(TestCase::SessionAffinity)
+            .is_none());
+    }
+
+    #[test]
+    fn test_rate_limit_policy() {
+        assert_eq!(GatewayImpl::Kong.rate_limit_policy(), Some("KongPlugin"));
+        assert_eq!(GatewayImpl::Istio.rate_limit_policy(), Some("EnvoyFilter"));
+        assert!(GatewayImpl::Cilium.rate_limit_policy().is_none());
+    }
+
+    #[test]
+    fn test_ai_extension_support() {
+        assert!(GatewayImpl::Kgateway
+            .unsupported_reason(TestCase::AiStreaming)
+            .is_none());
+        assert_eq!(
+            GatewayImpl::Cilium.unsupported_reason(TestCase::AiBodyLimit),
+            Some("Cilium Gateway has no AI routing extension".to_string())
+        );
+    }
+
     #[test]
     fn test_all_gateways() {
-        let all = GatewayImpl::all();
-        assert_eq!(all.len(), 7);
+        let builtin = GatewayImpl::builtin();
+        assert_eq!(builtin.len(), 9);
+        assert!(builtin
+            .iter()
+            .all(|g| GatewayImpl::from_str(g.short_name()) == Some(*g)));
+        assert_eq!(
+            serde_json::to_string(&GatewayImpl::Envoy).unwrap(),
+            "\"envoy\""
+        );
+    }
+
+    #[test]
+    fn test_load_test_settings_partial() {
+        let settings: LoadTestSettings =
+            serde_yaml::from_str("engine: benchmark\nconcurrent_users: 50\n").unwrap();
+        assert_eq!(settings.engine, LoadTestEngine::Benchmark);
+        assert_eq!(settings.concurrent_users, 50);
+        assert_eq!(settings.total_requests, 100);
+        assert_eq!(
+            LoadTestEngine::from_str("simple"),
+            Some(LoadTestEngine::Simple)
+        );
+    }
+
+    #[test]
+    fn test_parse_confidence() {
+        assert_eq!(CanarySettings::parse_confidence("0.99"), Ok(0.99));
+        assert_eq!(CanarySettings::parse_confidence("95"), Ok(0.95));
+        let percent = CanarySettings::parse_confidence("99.9%").unwrap();
+        assert!((percent - 0.999).abs() < 1e-9);
+        assert!(CanarySettings::parse_confidence("0.3").is_err());
+        assert!(CanarySettings::parse_confidence("100").is_err());
+        assert!(CanarySettings::parse_confidence("high").is_err());
+        assert!(CanarySettings::default().is_default());
+    }
+
+    #[test]
+    fn test_parse_backend_fault() {
+        for fault in BackendFault::all() {
+            assert_eq!(BackendFault::parse(fault.name()), Ok(fault));
+        }
+        assert_eq!(
+            BackendFault::parse("Scale-To-Zero"),
+            Ok(BackendFault::ScaleToZero)
+        );
+        assert!(BackendFault::parse("kill").is_err());
+        assert!(BackendFault::DeletePod.targets_pod());
+        assert!(!BackendFault::ScaleToZero.targets_pod());
     }
 
     #[test]
     fn test_arm64_compatible() {
-        let arm64 = GatewayImpl::arm64_compatible();
-        assert_eq!(arm64.len(), 6);
+        let arm64: Vec<_> = GatewayImpl::arm64_compatible()
+            .into_iter()
+            .filter(|g| !g.is_custom())
+            .collect();
+        assert_eq!(arm64.len(), 8);
         assert!(!arm64.contains(&GatewayImpl::Kgateway));
     }
 
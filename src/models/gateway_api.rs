@@ -0,0 +1,499 @@
+//! Gateway API versions and feature levels
+//!
+//! Tracks which Gateway API release (and release channel) is installed so
+//! version-specific tests are only run against a spec level that defines them.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+use super::TestCase;
+
+/// CRD annotation carrying the Gateway API bundle version
+pub const BUNDLE_VERSION_ANNOTATION: &str = "gateway.networking.k8s.io/bundle-version";
+
+/// CRD annotation carrying the Gateway API release channel
+pub const CHANNEL_ANNOTATION: &str = "gateway.networking.k8s.io/channel";
+
+/// Gateway API release channel
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GatewayApiChannel {
+    #[default]
+    Standard,
+    Experimental,
+}
+
+impl GatewayApiChannel {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "standard" => Some(Self::Standard),
+            "experimental" => Some(Self::Experimental),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Standard => "standard",
+            Self::Experimental => "experimental",
+        }
+    }
+}
+
+impl fmt::Display for GatewayApiChannel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Gateway API release version (e.g. v1.2.1)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct GatewayApiVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl GatewayApiVersion {
+    pub const V1_0: Self = Self::new(1, 0, 0);
+    pub const V1_1: Self = Self::new(1, 1, 0);
+    pub const V1_2: Self = Self::new(1, 2, 1);
+
+    /// Release installed by `deploy crds` when no version is given
+    pub const DEFAULT: Self = Self::V1_2;
+
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Releases this tool has been validated against
+    pub fn supported() -> Vec<Self> {
+        vec![Self::V1_0, Self::V1_1, Self::V1_2]
+    }
+
+    /// Parse `v1.2.1`, `1.2.1` or `1.2` (usable as a clap value parser)
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let trimmed = s.trim();
+        let digits = trimmed.strip_prefix('v').unwrap_or(trimmed);
+        let parts: Vec<&str> = digits.split('.').collect();
+        if !(2..=3).contains(&parts.len()) {
+            return Err(format!(
+                "Invalid Gateway API version '{s}' (expected vX.Y[.Z])"
+            ));
+        }
+
+        let number = |part: &str| {
+            part.parse::<u32>()
+                .map_err(|_| format!("Invalid Gateway API version '{s}'"))
+        };
+        Ok(Self {
+            major: number(parts[0])?,
+            minor: number(parts[1])?,
+            patch: parts.get(2).map(|p| number(p)).transpose()?.unwrap_or(0),
+        })
+    }
+
+    /// Whether this release is at least `major.minor`
+    pub fn at_least(&self, other: &Self) -> bool {
+        (self.major, self.minor) >= (other.major, other.minor)
+    }
+
+    /// `vX.Y` form used in requirement messages
+    fn short(&self) -> String {
+        format!("v{}.{}", self.major, self.minor)
+    }
+
+    /// Release manifest URL for a channel
+    pub fn crd_url(&self, channel: GatewayApiChannel) -> String {
+        format!(
+            "https://github.com/kubernetes-sigs/gateway-api/releases/download/{}/{}-install.yaml",
+            self,
+            channel.name()
+        )
+    }
+}
+
+impl Default for GatewayApiVersion {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl fmt::Display for GatewayApiVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "v{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl TryFrom<String> for GatewayApiVersion {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::parse(&s)
+    }
+}
+
+impl From<GatewayApiVersion> for String {
+    fn from(version: GatewayApiVersion) -> Self {
+        version.to_string()
+    }
+}
+
+/// Spec feature whose availability depends on the Gateway API release
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GatewayApiFeature {
+    /// HTTPRoute `timeouts` field
+    HttpRouteTimeouts,
+    /// BackendTLSPolicy resource
+    BackendTlsPolicy,
+    /// GRPCRoute resource
+    GrpcRoute,
+}
+
+impl GatewayApiFeature {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::HttpRouteTimeouts => "HTTPRoute timeouts",
+            Self::BackendTlsPolicy => "BackendTLSPolicy",
+            Self::GrpcRoute => "GRPCRoute",
+        }
+    }
+
+    /// First release shipping the feature in the standard channel
+    pub fn standard_since(&self) -> Option<GatewayApiVersion> {
+        match self {
+            Self::HttpRouteTimeouts => Some(GatewayApiVersion::V1_2),
+            Self::GrpcRoute => Some(GatewayApiVersion::V1_1),
+            Self::BackendTlsPolicy => None,
+        }
+    }
+
+    /// First release shipping the feature in the experimental channel
+    pub fn experimental_since(&self) -> GatewayApiVersion {
+        GatewayApiVersion::V1_0
+    }
+
+    /// Whether a release/channel provides the feature
+    pub fn available_in(&self, version: &GatewayApiVersion, channel: GatewayApiChannel) -> bool {
+        let since = match channel {
+            GatewayApiChannel::Standard => match self.standard_since() {
+                Some(since) => since,
+                None => return false,
+            },
+            GatewayApiChannel::Experimental => self.experimental_since(),
+        };
+        version.at_least(&since)
+    }
+
+    /// Human readable requirement, e.g. "v1.2+ (standard) or v1.0+ (experimental)"
+    pub fn requirement(&self) -> String {
+        let experimental = format!("{}+ (experimental)", self.experimental_since().short());
+        match self.standard_since() {
+            Some(since) => format!("{}+ (standard) or {experimental}", since.short()),
+            None => experimental,
+        }
+    }
+}
+
+impl TestCase {
+    /// Spec features a test exercises beyond the v1.0 standard channel
+    pub fn required_features(&self) -> &'static [GatewayApiFeature] {
+        match self {
+            TestCase::TimeoutRetry => &[GatewayApiFeature::HttpRouteTimeouts],
+            TestCase::BackendTls => &[GatewayApiFeature::BackendTlsPolicy],
+            TestCase::GrpcRouting => &[GatewayApiFeature::GrpcRoute],
+            _ => &[],
+        }
+    }
+
+    /// Gateway API resources and fields a test exercises
+    pub fn api_fields(&self) -> &'static [&'static str] {
+        match self {
+            TestCase::HostRouting => &["HTTPRoute.spec.hostnames"],
+            TestCase::PathRouting => &["HTTPRoute.spec.rules.matches.path"],
+            TestCase::HeaderRouting => &["HTTPRoute.spec.rules.matches.headers"],
+            TestCase::TlsTermination => &["Gateway.spec.listeners.tls"],
+            TestCase::HttpsRedirect => &[
+                "HTTPRoute.spec.rules.filters.requestRedirect",
+                "HTTPRoute.spec.rules.filters.requestRedirect.path",
+            ],
+            TestCase::BackendTls => &["BackendTLSPolicy.spec.validation"],
+            TestCase::CanaryTraffic => &["HTTPRoute.spec.rules.backendRefs.weight"],
+            TestCase::RateLimiting => &["Implementation rate limit policy"],
+            TestCase::TimeoutRetry => &["HTTPRoute.spec.rules.timeouts"],
+            TestCase::SessionAffinity => &["Implementation session persistence policy"],
+            TestCase::UrlRewrite => &["HTTPRoute.spec.rules.filters.urlRewrite"],
+            TestCase::HeaderModifier => &[
+                "HTTPRoute.spec.rules.filters.requestHeaderModifier",
+                "HTTPRoute.spec.rules.filters.responseHeaderModifier",
+            ],
+            TestCase::CrossNamespace => &[
+                "HTTPRoute.spec.rules.backendRefs.namespace",
+                "ReferenceGrant",
+            ],
+            TestCase::GrpcRouting => &["GRPCRoute.spec.rules.matches.method"],
+            TestCase::HealthCheck => &["HTTPRoute.spec.rules.matches.path"],
+            TestCase::LoadTest | TestCase::FailoverRecovery => {
+                &["HTTPRoute.spec.rules.backendRefs"]
+            }
+            TestCase::ConsistentHashing => &["Implementation load balancer policy"],
+            TestCase::AiStreaming | TestCase::AiTokenLatency => {
+                &["Implementation AI backend / route extension"]
+            }
+            TestCase::AiBodyLimit => &["Implementation request body limit policy"],
+            TestCase::DualStack => &["Gateway.status.addresses"],
+            TestCase::WildcardHostname => &["HTTPRoute.spec.hostnames"],
+            TestCase::MissingHost => &["HTTPRoute.spec.rules.matches.path"],
+            TestCase::SniMismatch => &[
+                "Gateway.spec.listeners.hostname",
+                "HTTPRoute.spec.hostnames",
+            ],
+            TestCase::LargeBody => &["Implementation request body limit policy"],
+            TestCase::HeaderHandling => &["HTTPRoute.spec.rules.backendRefs"],
+            TestCase::ForwardedHeaders => &["Implementation client address handling"],
+            TestCase::Compression => &["Implementation response compression"],
+            TestCase::Cors => &["Implementation CORS policy"],
+            TestCase::TlsPolicy => &["Gateway.spec.listeners.tls.options"],
+            TestCase::Http2 => &["Gateway.spec.listeners.protocol (HTTPS)"],
+            TestCase::ZeroDowntimeReload => &["Implementation controller and data-plane rollout"],
+            TestCase::RoutePropagation => {
+                &["HTTPRoute.spec.hostnames", "HTTPRoute.spec.rules.filters"]
+            }
+        }
+    }
+}
+
+/// Installed (or declared) Gateway API spec level
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GatewayApiSpec {
+    pub version: GatewayApiVersion,
+    #[serde(default)]
+    pub channel: GatewayApiChannel,
+}
+
+impl GatewayApiSpec {
+    pub fn new(version: GatewayApiVersion, channel: GatewayApiChannel) -> Self {
+        Self { version, channel }
+    }
+
+    /// Read the spec level from Gateway API CRD annotations
+    pub fn from_annotations(annotations: &BTreeMap<String, String>) -> Option<Self> {
+        let version = GatewayApiVersion::parse(annotations.get(BUNDLE_VERSION_ANNOTATION)?).ok()?;
+        let channel = annotations
+            .get(CHANNEL_ANNOTATION)
+            .and_then(|c| GatewayApiChannel::from_str(c))
+            .unwrap_or_default();
+        Some(Self::new(version, channel))
+    }
+
+    pub fn supports(&self, feature: GatewayApiFeature) -> bool {
+        feature.available_in(&self.version, self.channel)
+    }
+
+    /// Why a test cannot run at this spec level, if it cannot
+    pub fn unsupported_reason(&self, test_case: TestCase) -> Option<String> {
+        let missing = test_case
+            .required_features()
+            .iter()
+            .find(|feature| !self.supports(**feature))?;
+        Some(format!(
+            "Requires {} in Gateway API {}; cluster has {}",
+            missing.name(),
+            missing.requirement(),
+            self
+        ))
+    }
+
+    /// Why this spec level falls short of `required`, if it does
+    ///
+    /// The experimental channel is a superset of the standard one.
+    pub fn shortfall(&self, required: &GatewayApiSpec) -> Option<String> {
+        if !self.version.at_least(&required.version) {
+            return Some(format!(
+                "needs {}+, cluster has {}",
+                required.version.short(),
+                self.version
+            ));
+        }
+        if required.channel == GatewayApiChannel::Experimental
+            && self.channel == GatewayApiChannel::Standard
+        {
+            return Some("needs the experimental channel, cluster has standard".to_string());
+        }
+        None
+    }
+}
+
+/// Gateway API CRDs as installed in a cluster
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InstalledGatewayApi {
+    /// Bundle release and channel (None if not annotated)
+    pub spec: Option<GatewayApiSpec>,
+    /// API versions the CRD serves, e.g. `["v1", "v1beta1"]`
+    pub served_versions: Vec<String>,
+}
+
+impl InstalledGatewayApi {
+    /// Read a CustomResourceDefinition as JSON (`kubectl get crd -o json`)
+    pub fn from_crd(crd: &serde_json::Value) -> Self {
+        let annotations: BTreeMap<String, String> = crd["metadata"]["annotations"]
+            .as_object()
+            .map(|map| {
+                map.iter()
+                    .filter_map(|(k, v)| Some((k.clone(), v.as_str()?.to_string())))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let served_versions = crd["spec"]["versions"]
+            .as_array()
+            .map(|versions| {
+                versions
+                    .iter()
+                    .filter(|v| v["served"].as_bool().unwrap_or(false))
+                    .filter_map(|v| v["name"].as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            spec: GatewayApiSpec::from_annotations(&annotations),
+            served_versions,
+        }
+    }
+
+    /// Whether the GA `v1` API is served (CRDs older than v1.0 only serve v1beta1)
+    pub fn serves_v1(&self) -> bool {
+        self.served_versions.iter().any(|v| v == "v1")
+    }
+}
+
+impl fmt::Display for InstalledGatewayApi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.spec {
+            Some(spec) => write!(f, "{spec}")?,
+            None => f.write_str("unknown release")?,
+        }
+        if !self.served_versions.is_empty() {
+            write!(f, ", serves {}", self.served_versions.join("/"))?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for GatewayApiSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.version, self.channel)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::GatewayImpl;
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(
+            GatewayApiVersion::parse("v1.2.1").unwrap(),
+            GatewayApiVersion::new(1, 2, 1)
+        );
+        assert_eq!(
+            GatewayApiVersion::parse("1.1").unwrap(),
+            GatewayApiVersion::V1_1
+        );
+        assert!(GatewayApiVersion::parse("v1").is_err());
+        assert!(GatewayApiVersion::parse("latest").is_err());
+        assert_eq!(GatewayApiVersion::V1_0.to_string(), "v1.0.0");
+        assert!(GatewayApiVersion::V1_0
+            .crd_url(GatewayApiChannel::Experimental)
+            .ends_with("/v1.0.0/experimental-install.yaml"));
+    }
+
+    #[test]
+    fn test_feature_availability() {
+        let v1_0 = GatewayApiSpec::new(GatewayApiVersion::V1_0, GatewayApiChannel::Standard);
+        let v1_1 = GatewayApiSpec::new(GatewayApiVersion::V1_1, GatewayApiChannel::Standard);
+        let v1_2 = GatewayApiSpec::new(GatewayApiVersion::V1_2, GatewayApiChannel::Standard);
+        let exp = GatewayApiSpec::new(GatewayApiVersion::V1_0, GatewayApiChannel::Experimental);
+
+        assert!(!v1_0.supports(GatewayApiFeature::GrpcRoute));
+        assert!(v1_1.supports(GatewayApiFeature::GrpcRoute));
+        assert!(!v1_1.supports(GatewayApiFeature::HttpRouteTimeouts));
+        assert!(v1_2.supports(GatewayApiFeature::HttpRouteTimeouts));
+        assert!(!v1_2.supports(GatewayApiFeature::BackendTlsPolicy));
+        assert!(exp.supports(GatewayApiFeature::BackendTlsPolicy));
+        assert!(exp.supports(GatewayApiFeature::HttpRouteTimeouts));
+    }
+
+    #[test]
+    fn test_unsupported_reason() {
+        let v1_1 = GatewayApiSpec::new(GatewayApiVersion::V1_1, GatewayApiChannel::Standard);
+        assert!(v1_1.unsupported_reason(TestCase::HostRouting).is_none());
+        assert!(v1_1.unsupported_reason(TestCase::GrpcRouting).is_none());
+
+        let reason = v1_1.unsupported_reason(TestCase::TimeoutRetry).unwrap();
+        assert!(reason.contains("HTTPRoute timeouts"));
+        assert!(reason.contains("v1.1.0 (standard)"));
+    }
+
+    #[test]
+    fn test_shortfall() {
+        let installed = GatewayApiSpec::new(GatewayApiVersion::V1_1, GatewayApiChannel::Standard);
+        assert!(installed
+            .shortfall(&GatewayImpl::Nginx.gateway_api_requirement().unwrap())
+            .is_none());
+        assert!(installed
+            .shortfall(&GatewayImpl::Kgateway.gateway_api_requirement().unwrap())
+            .unwrap()
+            .contains("needs v1.2+"));
+        assert!(installed
+            .shortfall(&GatewayImpl::Cilium.gateway_api_requirement().unwrap())
+            .unwrap()
+            .contains("experimental"));
+    }
+
+    #[test]
+    fn test_installed_from_crd() {
+        let crd = serde_json::json!({
+            "metadata": {"annotations": {
+                BUNDLE_VERSION_ANNOTATION: "v1.0.0",
+                CHANNEL_ANNOTATION: "standard",
+            }},
+            "spec": {"versions": [
+                {"name": "v1", "served": true},
+                {"name": "v1beta1", "served": true},
+                {"name": "v1alpha2", "served": false},
+            ]},
+        });
+        let installed = InstalledGatewayApi::from_crd(&crd);
+        assert!(installed.serves_v1());
+        assert_eq!(
+            installed.to_string(),
+            "v1.0.0 (standard), serves v1/v1beta1"
+        );
+
+        let legacy = InstalledGatewayApi::from_crd(&serde_json::json!({
+            "spec": {"versions": [{"name": "v1beta1", "served": true}]},
+        }));
+        assert!(!legacy.serves_v1());
+        assert!(legacy.spec.is_none());
+    }
+
+    #[test]
+    fn test_from_annotations() {
+        let mut annotations = BTreeMap::new();
+        assert!(GatewayApiSpec::from_annotations(&annotations).is_none());
+
+        annotations.insert(BUNDLE_VERSION_ANNOTATION.to_string(), "v1.1.0".to_string());
+        annotations.insert(CHANNEL_ANNOTATION.to_string(), "experimental".to_string());
+        let spec = GatewayApiSpec::from_annotations(&annotations).unwrap();
+        assert_eq!(spec.version, GatewayApiVersion::V1_1);
+        assert_eq!(spec.channel, GatewayApiChannel::Experimental);
+    }
+}
@@ -0,0 +1,188 @@
+//! Latency statistics
+//!
+//! Percentiles and summary statistics over latency samples, shared by
+//! functional tests, the HTTP client metrics and benchmarks.
+
+#![allow(dead_code)]
+
+use serde::{Deserialize, Serialize};
+
+/// Latency percentiles (p50, p90, p95, p99, p999)
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Percentiles {
+    /// 50th percentile (median)
+    pub p50: f64,
+    /// 90th percentile
+    pub p90: f64,
+    /// 95th percentile
+    pub p95: f64,
+    /// 99th percentile
+    pub p99: f64,
+    /// 99.9th percentile
+    pub p999: f64,
+}
+
+impl Percentiles {
+    /// Calculate percentiles from sorted latencies (in milliseconds)
+    pub fn from_sorted(latencies: &[f64]) -> Self {
+        if latencies.is_empty() {
+            return Self::default();
+        }
+
+        Self {
+            p50: percentile(latencies, 50.0),
+            p90: percentile(latencies, 90.0),
+            p95: percentile(latencies, 95.0),
+            p99: percentile(latencies, 99.0),
+            p999: percentile(latencies, 99.9),
+        }
+    }
+
+    /// Format as table row
+    pub fn format_row(&self) -> String {
+        format!(
+            "{:>8.2} {:>8.2} {:>8.2} {:>8.2} {:>8.2}",
+            self.p50, self.p90, self.p95, self.p99, self.p999
+        )
+    }
+}
+
+/// Calculate percentile value from sorted array
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    if sorted.len() == 1 {
+        return sorted[0];
+    }
+
+    let idx = (p / 100.0) * (sorted.len() - 1) as f64;
+    let lower = idx.floor() as usize;
+    let upper = idx.ceil() as usize;
+    let fraction = idx - lower as f64;
+
+    if upper >= sorted.len() {
+        sorted[sorted.len() - 1]
+    } else {
+        sorted[lower] * (1.0 - fraction) + sorted[upper] * fraction
+    }
+}
+
+/// Latency statistics
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LatencyStats {
+    /// Minimum latency in milliseconds
+    pub min: f64,
+    /// Maximum latency in milliseconds
+    pub max: f64,
+    /// Mean latency in milliseconds
+    pub mean: f64,
+    /// Standard deviation in milliseconds
+    pub std_dev: f64,
+    /// Latency percentiles
+    pub percentiles: Percentiles,
+    /// Total number of samples
+    pub count: usize,
+}
+
+impl LatencyStats {
+    /// Calculate statistics from latency samples (in milliseconds)
+    pub fn from_samples(samples: &[f64]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+        let min = sorted[0];
+        let max = sorted[sorted.len() - 1];
+        let sum: f64 = sorted.iter().sum();
+        let mean = sum / sorted.len() as f64;
+
+        let variance: f64 =
+            sorted.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / sorted.len() as f64;
+        let std_dev = variance.sqrt();
+
+        let percentiles = Percentiles::from_sorted(&sorted);
+
+        Self {
+            min,
+            max,
+            mean,
+            std_dev,
+            percentiles,
+            count: sorted.len(),
+        }
+    }
+
+    /// Combine stats from independent sample sets
+    ///
+    /// Mean and standard deviation are exact; percentiles are approximated
+    /// by a sample-weighted average since raw samples are not available.
+    pub fn merge(parts: &[LatencyStats]) -> Self {
+        let parts: Vec<&LatencyStats> = parts.iter().filter(|p| p.count > 0).collect();
+        let count: usize = parts.iter().map(|p| p.count).sum();
+        if count == 0 {
+            return Self::default();
+        }
+
+        let n = count as f64;
+        let weighted = |f: &dyn Fn(&LatencyStats) -> f64| {
+            parts.iter().map(|p| f(p) * p.count as f64).sum::<f64>() / n
+        };
+
+        let mean = weighted(&|p| p.mean);
+        let variance = weighted(&|p| p.std_dev.powi(2) + (p.mean - mean).powi(2));
+
+        Self {
+            min: parts.iter().map(|p| p.min).fold(f64::INFINITY, f64::min),
+            max: parts.iter().map(|p| p.max).fold(0.0, f64::max),
+            mean,
+            std_dev: variance.sqrt(),
+            percentiles: Percentiles {
+                p50: weighted(&|p| p.percentiles.p50),
+                p90: weighted(&|p| p.percentiles.p90),
+                p95: weighted(&|p| p.percentiles.p95),
+                p99: weighted(&|p| p.percentiles.p99),
+                p999: weighted(&|p| p.percentiles.p999),
+            },
+            count,
+        }
+    }
+
+    /// Format as summary string
+    pub fn format_summary(&self) -> String {
+        format!(
+            "min={:.2}ms max={:.2}ms mean={:.2}ms std={:.2}ms p95={:.2}ms p99={:.2}ms",
+            self.min, self.max, self.mean, self.std_dev, self.percentiles.p95, self.percentiles.p99
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentiles() {
+        let data: Vec<f64> = (1..=100).map(|x| x as f64).collect();
+        let p = Percentiles::from_sorted(&data);
+
+        assert!((p.p50 - 50.0).abs() < 1.0);
+        assert!((p.p90 - 90.0).abs() < 1.0);
+        assert!((p.p95 - 95.0).abs() < 1.0);
+        assert!((p.p99 - 99.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_latency_stats() {
+        let samples: Vec<f64> = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        let stats = LatencyStats::from_samples(&samples);
+
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.max, 5.0);
+        assert_eq!(stats.mean, 3.0);
+        assert_eq!(stats.count, 5);
+    }
+}
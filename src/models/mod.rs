@@ -2,8 +2,43 @@
 //!
 //! This module contains all data structures used throughout the application.
 
+mod backend;
+mod capability;
+mod connection;
+mod crash;
+mod dns;
+mod environment;
 mod gateway;
+mod gateway_api;
+mod latency;
+pub mod registry;
+mod resources;
+mod round_plan;
+mod setup;
+mod soak;
+mod test_filter;
 mod test_result;
+mod traffic;
 
-pub use gateway::{GatewayConfig, GatewayImpl, TestConfig};
-pub use test_result::{TestCase, TestResult, TestRoundSummary, TestStatus};
+pub use backend::{BackendHits, BackendIdentity, BackendTopology};
+pub use capability::capability_matrix;
+pub use connection::ConnectionSettings;
+pub use crash::{ContainerSnapshot, PodCrash, PodSnapshot};
+pub use dns::{DnsOverrides, IpFamily};
+pub use environment::{RoundEnvironment, DEFAULT_API_LATENCY_THRESHOLD_MS};
+pub use gateway::{
+    BackendFault, CanarySettings, FailoverSettings, GatewayConfig, GatewayImpl, LoadTestEngine,
+    LoadTestSettings, RetryPolicy, TestConfig,
+};
+pub use gateway_api::{GatewayApiChannel, GatewayApiSpec, GatewayApiVersion, InstalledGatewayApi};
+pub use latency::{LatencyStats, Percentiles};
+pub use registry::GatewayDefinition;
+pub use resources::{ResourceSample, ResourceUsage};
+pub use round_plan::{RoundPlan, RoundVariant};
+pub use setup::{is_sensitive, redact, redact_text, GatewaySetup, HelmRelease, REDACTED};
+pub use soak::{SoakReport, SoakSettings, MAX_CONSECUTIVE_ROUND_ERRORS};
+pub use test_filter::{Experimental, TestCategory, TestFilter};
+pub use test_result::{
+    TestCase, TestResult, TestRoundSummary, TestStatus, BEHAVIOR_DETAIL, TEST_CATALOG_VERSION,
+};
+pub use traffic::{HttpErrorClass, HttpTraffic};
@@ -0,0 +1,346 @@
+//! Gateway implementation registry
+//!
+//! The built-in implementations are the fixed variants of `GatewayImpl`.
+//! Further implementations (APISIX, HAProxy, Contour, ...) are described in
+//! the `gateways` section of the config file and registered at startup; they
+//! become `GatewayImpl::Custom` values that the installer, health checks,
+//! tests and reports treat like any built-in one.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{OnceLock, RwLock};
+
+use super::gateway::GatewayImpl;
+use super::gateway_api::GatewayApiSpec;
+
+/// Helm chart of a user-defined implementation
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HelmChart {
+    /// Chart repository URL (omit for `oci://` or local charts)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo: Option<String>,
+    /// Chart name in the repository, or a full chart reference
+    pub chart: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Values passed with `--set`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub values: BTreeMap<String, String>,
+}
+
+/// User-defined gateway implementation
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GatewayDefinition {
+    /// Short name used on the command line and for Helm releases
+    pub name: String,
+    /// Display name in reports (default: the short name)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    /// Other names accepted on the command line
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    pub gateway_class: String,
+    /// GatewayClass `controllerName` (for `test --gateway auto`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub controller_name: Option<String>,
+    pub helm: HelmChart,
+    /// Label selector of controller pods
+    pub pod_selector: String,
+    /// Label selector of data-plane pods, if separate from the controller
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_plane_selector: Option<String>,
+    #[serde(default = "default_arm64")]
+    pub arm64: bool,
+    /// Policy kind providing header-keyed consistent hashing, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub consistent_hash_policy: Option<String>,
+    /// Policy kind configuring TLS to backends, if any (`BackendTLSPolicy`
+    /// gets the standard Gateway API resource generated)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backend_tls_policy: Option<String>,
+    /// Policy kind pinning a client to one backend (sticky sessions), if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_affinity_policy: Option<String>,
+    /// Policy kind limiting request rate on a route, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit_policy: Option<String>,
+    /// Policy kind answering CORS preflights, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cors_policy: Option<String>,
+    /// Extension routing AI/LLM traffic, if any (enables the AI tests)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ai_extension: Option<String>,
+    /// Oldest Gateway API release and channel the implementation works with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gateway_api: Option<GatewayApiSpec>,
+}
+
+fn default_arm64() -> bool {
+    true
+}
+
+impl GatewayDefinition {
+    /// Whether a command-line name refers to this implementation
+    pub fn matches(&self, name: &str) -> bool {
+        self.name.eq_ignore_ascii_case(name)
+            || self.aliases.iter().any(|a| a.eq_ignore_ascii_case(name))
+    }
+
+    /// Stand-in for a name found in stored results but not registered (see
+    /// [`unregistered`]); registered definitions always have a chart
+    pub fn is_placeholder(&self) -> bool {
+        self.helm.chart.is_empty()
+    }
+
+    fn validate(&self) -> Result<()> {
+        if !valid_name(&self.name) {
+            anyhow::bail!(
+                "Invalid gateway name '{}' (use lowercase letters, digits and '-')",
+                self.name
+            );
+        }
+        if self.gateway_class.is_empty() {
+            anyhow::bail!("Gateway '{}' has no gateway_class", self.name);
+        }
+        if self.pod_selector.is_empty() {
+            anyhow::bail!("Gateway '{}' has no pod_selector", self.name);
+        }
+        if self.helm.chart.is_empty() {
+            anyhow::bail!("Gateway '{}' has no Helm chart", self.name);
+        }
+        Ok(())
+    }
+}
+
+fn valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+/// Registered user-defined implementations, indexed by `GatewayImpl::Custom`
+fn registry() -> &'static RwLock<Vec<&'static GatewayDefinition>> {
+    static REGISTRY: OnceLock<RwLock<Vec<&'static GatewayDefinition>>> = OnceLock::new();
+    REGISTRY.get_or_init(RwLock::default)
+}
+
+/// Register a user-defined implementation
+///
+/// Registering the same definition again returns the existing value; a
+/// different definition under a taken name (built-in or custom) is an error.
+pub fn register(definition: GatewayDefinition) -> Result<GatewayImpl> {
+    definition.validate()?;
+    for name in std::iter::once(&definition.name).chain(&definition.aliases) {
+        if name.eq_ignore_ascii_case("auto") {
+            anyhow::bail!("Gateway name 'auto' is reserved for `test --gateway auto`");
+        }
+        if let Some(builtin) = GatewayImpl::builtin()
+            .into_iter()
+            .find(|g| GatewayImpl::from_str(name) == Some(*g))
+        {
+            anyhow::bail!("Gateway name '{name}' is taken by {builtin}");
+        }
+    }
+
+    let mut definitions = registry().write().unwrap_or_else(|e| e.into_inner());
+    if let Some(index) = definitions.iter().position(|d| {
+        std::iter::once(&definition.name)
+            .chain(&definition.aliases)
+            .any(|name| d.matches(name))
+    }) {
+        // Results naming the gateway were read before its definition
+        if definitions[index].is_placeholder() && definitions[index].name == definition.name {
+            definitions[index] = Box::leak(Box::new(definition));
+            return Ok(GatewayImpl::Custom(index as u16));
+        }
+        if *definitions[index] != definition {
+            anyhow::bail!(
+                "Gateway '{}' clashes with the definition of '{}'",
+                definition.name,
+                definitions[index].name
+            );
+        }
+        return Ok(GatewayImpl::Custom(index as u16));
+    }
+
+    let index = u16::try_from(definitions.len())?;
+    // Definitions live for the whole process, so accessors can hand out
+    // `&'static str` like the built-in variants do
+    definitions.push(Box::leak(Box::new(definition)));
+    Ok(GatewayImpl::Custom(index))
+}
+
+/// Register every definition, stopping at the first invalid one
+pub fn register_all(definitions: &[GatewayDefinition]) -> Result<Vec<GatewayImpl>> {
+    definitions.iter().cloned().map(register).collect()
+}
+
+/// Definition of a registered implementation
+pub fn definition(index: u16) -> Option<&'static GatewayDefinition> {
+    registry()
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(index as usize)
+        .copied()
+}
+
+/// All registered implementations, in registration order
+pub fn custom() -> Vec<GatewayImpl> {
+    registry()
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .enumerate()
+        .filter(|(_, d)| !d.is_placeholder())
+        .map(|(index, _)| GatewayImpl::Custom(index as u16))
+        .collect()
+}
+
+/// Implementation named in stored results whose definition is not in the
+/// config file
+///
+/// The results still load and show under that name; the placeholder is left
+/// out of [`custom`], cannot be installed, and is replaced if the definition
+/// is registered later. None for names no definition could have.
+pub fn unregistered(name: &str) -> Option<GatewayImpl> {
+    let name = name.to_lowercase();
+    if !valid_name(&name) {
+        return None;
+    }
+    if let Some(gateway) = find(&name) {
+        return Some(gateway);
+    }
+    let mut definitions = registry().write().unwrap_or_else(|e| e.into_inner());
+    let index = u16::try_from(definitions.len()).ok()?;
+    definitions.push(Box::leak(Box::new(GatewayDefinition {
+        name,
+        display_name: None,
+        aliases: Vec::new(),
+        gateway_class: String::new(),
+        controller_name: None,
+        helm: HelmChart {
+            repo: None,
+            chart: String::new(),
+            version: None,
+            values: BTreeMap::new(),
+        },
+        pod_selector: String::new(),
+        data_plane_selector: None,
+        arm64: false,
+        consistent_hash_policy: None,
+        backend_tls_policy: None,
+        session_affinity_policy: None,
+        rate_limit_policy: None,
+        cors_policy: None,
+        ai_extension: None,
+        gateway_api: None,
+    })));
+    Some(GatewayImpl::Custom(index))
+}
+
+/// Look up a registered implementation by name or alias
+pub fn find(name: &str) -> Option<GatewayImpl> {
+    registry()
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .position(|d| d.matches(name))
+        .map(|index| GatewayImpl::Custom(index as u16))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TestCase;
+
+    fn apisix() -> GatewayDefinition {
+        serde_yaml::from_str(
+            r#"
+name: apisix-test
+display_name: Apache APISIX
+aliases: [apisix-gw-test]
+gateway_class: apisix
+helm:
+  repo: https://charts.apiseven.com
+  chart: apisix
+  values:
+    ingress-controller.enabled: "true"
+pod_selector: app.kubernetes.io/name=apisix
+arm64: false
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_register_custom_gateway() {
+        let gateway = register(apisix()).unwrap();
+        assert_eq!(register(apisix()).unwrap(), gateway);
+        assert_eq!(GatewayImpl::from_str("APISIX-GW-TEST"), Some(gateway));
+
+        assert_eq!(gateway.name(), "Apache APISIX");
+        assert_eq!(gateway.short_name(), "apisix-test");
+        assert_eq!(gateway.gateway_class(), "apisix");
+        assert_eq!(gateway.pod_selector(), "app.kubernetes.io/name=apisix");
+        assert!(!gateway.supports_arm64());
+        assert!(gateway
+            .unsupported_reason(TestCase::ConsistentHashing)
+            .is_some());
+        assert!(GatewayImpl::all().contains(&gateway));
+
+        let json = serde_json::to_string(&gateway).unwrap();
+        assert_eq!(json, "\"apisix-test\"");
+        assert_eq!(serde_json::from_str::<GatewayImpl>(&json).unwrap(), gateway);
+    }
+
+    #[test]
+    fn test_register_conflicts() {
+        let mut clash = apisix();
+        clash.name = "envoy".to_string();
+        assert!(register(clash).is_err());
+
+        let mut alias = apisix();
+        alias.name = "apisix-alias-test".to_string();
+        alias.aliases = vec!["eg".to_string()];
+        assert!(register(alias).is_err());
+
+        let mut invalid = apisix();
+        invalid.name = "My Gateway".to_string();
+        assert!(register(invalid).is_err());
+
+        let mut changed = apisix();
+        changed.name = "apisix-changed-test".to_string();
+        changed.aliases.clear();
+        register(changed.clone()).unwrap();
+        changed.gateway_class = "other".to_string();
+        assert!(register(changed).is_err());
+
+        let mut taken_alias = apisix();
+        taken_alias.name = "apisix-other-test".to_string();
+        taken_alias.aliases = vec!["apisix-changed-test".to_string()];
+        assert!(register(taken_alias).is_err());
+    }
+
+    #[test]
+    fn test_unregistered_gateway() {
+        let placeholder = unregistered("Haproxy-Test").unwrap();
+        assert_eq!(placeholder.short_name(), "haproxy-test");
+        assert!(placeholder.definition().unwrap().is_placeholder());
+        assert!(!custom().contains(&placeholder));
+        assert_eq!(unregistered("haproxy-test"), Some(placeholder));
+        assert!(unregistered("not a gateway").is_none());
+
+        // Stored results naming it deserialize instead of failing
+        let parsed: GatewayImpl = serde_json::from_str("\"haproxy-test\"").unwrap();
+        assert_eq!(parsed, placeholder);
+
+        let mut haproxy = apisix();
+        haproxy.name = "haproxy-test".to_string();
+        haproxy.aliases.clear();
+        assert_eq!(register(haproxy).unwrap(), placeholder);
+        assert!(!placeholder.definition().unwrap().is_placeholder());
+        assert!(custom().contains(&placeholder));
+    }
+}
@@ -0,0 +1,123 @@
+//! Gateway resource usage models
+//!
+//! CPU and memory samples of the gateway's pods recorded during a benchmark.
+
+#![allow(dead_code)]
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Aggregate CPU/memory of the gateway pods at one point in time
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResourceSample {
+    /// Seconds since the benchmark started
+    pub elapsed_secs: f64,
+    /// Number of pods included in the sample
+    pub pods: usize,
+    /// CPU usage in cores
+    pub cpu_cores: f64,
+    /// Memory working set in bytes
+    pub memory_bytes: u64,
+}
+
+/// Resource usage time series for a benchmark
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResourceUsage {
+    /// Label selector used to find the gateway pods
+    pub selector: String,
+    /// Samples in collection order
+    pub samples: Vec<ResourceSample>,
+}
+
+impl ResourceUsage {
+    pub fn new(selector: impl Into<String>) -> Self {
+        Self {
+            selector: selector.into(),
+            samples: Vec::new(),
+        }
+    }
+
+    /// Whether any samples were collected
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Mean CPU usage in cores
+    pub fn avg_cpu_cores(&self) -> f64 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+        self.samples.iter().map(|s| s.cpu_cores).sum::<f64>() / self.samples.len() as f64
+    }
+
+    /// Highest CPU usage in cores
+    pub fn peak_cpu_cores(&self) -> f64 {
+        self.samples.iter().map(|s| s.cpu_cores).fold(0.0, f64::max)
+    }
+
+    /// Highest memory working set in bytes
+    pub fn peak_memory_bytes(&self) -> u64 {
+        self.samples
+            .iter()
+            .map(|s| s.memory_bytes)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Highest memory working set in MiB
+    pub fn peak_memory_mib(&self) -> f64 {
+        self.peak_memory_bytes() as f64 / (1024.0 * 1024.0)
+    }
+
+    /// Throughput per CPU core, if any CPU usage was recorded
+    pub fn rps_per_core(&self, rps: f64) -> Option<f64> {
+        let cores = self.avg_cpu_cores();
+        (cores > 0.0).then(|| rps / cores)
+    }
+}
+
+impl fmt::Display for ResourceUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cpu avg={:.2} peak={:.2} cores, memory peak={:.1} MiB ({} samples)",
+            self.avg_cpu_cores(),
+            self.peak_cpu_cores(),
+            self.peak_memory_mib(),
+            self.samples.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(cpu_cores: f64, memory_mib: u64) -> ResourceSample {
+        ResourceSample {
+            elapsed_secs: 0.0,
+            pods: 1,
+            cpu_cores,
+            memory_bytes: memory_mib * 1024 * 1024,
+        }
+    }
+
+    #[test]
+    fn test_usage_stats() {
+        let mut usage = ResourceUsage::new("app=gw");
+        usage.samples = vec![sample(0.5, 100), sample(1.5, 300), sample(1.0, 200)];
+
+        assert!((usage.avg_cpu_cores() - 1.0).abs() < 1e-9);
+        assert!((usage.peak_cpu_cores() - 1.5).abs() < 1e-9);
+        assert!((usage.peak_memory_mib() - 300.0).abs() < 1e-9);
+        assert_eq!(usage.rps_per_core(2000.0), Some(2000.0));
+    }
+
+    #[test]
+    fn test_empty_usage() {
+        let usage = ResourceUsage::new("app=gw");
+        assert!(usage.is_empty());
+        assert_eq!(usage.peak_memory_bytes(), 0);
+        assert_eq!(usage.rps_per_core(1000.0), None);
+    }
+}
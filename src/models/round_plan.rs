@@ -0,0 +1,184 @@
+//! Round plans
+//!
+//! Vary a run parameter across the rounds of a single run (e.g. rounds 1-10
+//! at concurrency 4, rounds 11-20 at concurrency 16) so results can be
+//! grouped by parameter value for sensitivity analysis.
+
+#![allow(dead_code)]
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use super::LoadTestSettings;
+
+/// A block of consecutive rounds run with the same parameter overrides
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RoundVariant {
+    /// Rounds in this block
+    pub rounds: u32,
+    /// Maximum concurrent tests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub concurrency: Option<usize>,
+    /// Load Test (test 16) concurrent users
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub load_users: Option<usize>,
+    /// Per-request HTTP timeout in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+}
+
+impl RoundVariant {
+    pub fn new(rounds: u32) -> Self {
+        Self {
+            rounds,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = Some(concurrency);
+        self
+    }
+
+    pub fn with_load_users(mut self, users: usize) -> Self {
+        self.load_users = Some(users);
+        self
+    }
+
+    pub fn with_timeout(mut self, secs: u64) -> Self {
+        self.timeout_secs = Some(secs);
+        self
+    }
+
+    /// Apply the Load Test overrides to the run's settings
+    pub fn apply_load_test(&self, settings: &LoadTestSettings) -> LoadTestSettings {
+        let mut settings = settings.clone();
+        if let Some(users) = self.load_users {
+            settings.concurrent_users = users;
+        }
+        settings
+    }
+}
+
+impl fmt::Display for RoundVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(concurrency) = self.concurrency {
+            parts.push(format!("concurrency={}", concurrency));
+        }
+        if let Some(users) = self.load_users {
+            parts.push(format!("load-users={}", users));
+        }
+        if let Some(secs) = self.timeout_secs {
+            parts.push(format!("timeout={}s", secs));
+        }
+        if parts.is_empty() {
+            write!(f, "baseline")
+        } else {
+            write!(f, "{}", parts.join(","))
+        }
+    }
+}
+
+/// Per-round parameter variants of a run, in round order
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RoundPlan {
+    variants: Vec<RoundVariant>,
+}
+
+impl RoundPlan {
+    pub fn new(variants: Vec<RoundVariant>) -> Self {
+        Self { variants }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.variants.is_empty()
+    }
+
+    pub fn variants(&self) -> &[RoundVariant] {
+        &self.variants
+    }
+
+    /// Rounds across all variants
+    pub fn total_rounds(&self) -> u32 {
+        self.variants.iter().map(|v| v.rounds).sum()
+    }
+
+    /// Variant in effect for a 1-based round number
+    pub fn variant(&self, round: u32) -> Option<&RoundVariant> {
+        let mut last = 0;
+        for variant in &self.variants {
+            last += variant.rounds;
+            if round >= 1 && round <= last {
+                return Some(variant);
+            }
+        }
+        None
+    }
+
+    /// Reject empty blocks and zero-valued overrides
+    pub fn validate(&self) -> Result<()> {
+        for (i, variant) in self.variants.iter().enumerate() {
+            if variant.rounds == 0 {
+                bail!("Round variant {} ({}) has no rounds", i + 1, variant);
+            }
+            if variant.concurrency == Some(0)
+                || variant.load_users == Some(0)
+                || variant.timeout_secs == Some(0)
+            {
+                bail!("Round variant {} ({}) has a zero override", i + 1, variant);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sweep() -> RoundPlan {
+        RoundPlan::new(vec![
+            RoundVariant::new(10).with_concurrency(4),
+            RoundVariant::new(10).with_concurrency(16),
+        ])
+    }
+
+    #[test]
+    fn test_variant_for_round() {
+        let plan = sweep();
+        assert_eq!(plan.total_rounds(), 20);
+        assert_eq!(plan.variant(1).unwrap().concurrency, Some(4));
+        assert_eq!(plan.variant(10).unwrap().concurrency, Some(4));
+        assert_eq!(plan.variant(11).unwrap().concurrency, Some(16));
+        assert!(plan.variant(0).is_none());
+        assert!(plan.variant(21).is_none());
+    }
+
+    #[test]
+    fn test_variant_label() {
+        assert_eq!(RoundVariant::new(1).to_string(), "baseline");
+        let variant = RoundVariant::new(1).with_concurrency(8).with_load_users(50);
+        assert_eq!(variant.to_string(), "concurrency=8,load-users=50");
+        assert_eq!(
+            variant
+                .apply_load_test(&LoadTestSettings::default())
+                .concurrent_users,
+            50
+        );
+    }
+
+    #[test]
+    fn test_validate_plan() {
+        assert!(sweep().validate().is_ok());
+        assert!(RoundPlan::new(vec![RoundVariant::new(0)])
+            .validate()
+            .is_err());
+        assert!(
+            RoundPlan::new(vec![RoundVariant::new(2).with_concurrency(0)])
+                .validate()
+                .is_err()
+        );
+    }
+}
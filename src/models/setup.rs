@@ -0,0 +1,247 @@
+//! Effective gateway configuration
+//!
+//! What an install actually runs with: the Helm chart and values, the
+//! GatewayClass and its parameters, and the gateway's ConfigMaps. Stored with
+//! each run so two results taken months apart can be checked for an
+//! equivalent setup before their numbers are compared.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Placeholder for values whose key looks like a credential
+pub const REDACTED: &str = "<redacted>";
+
+/// Key fragments whose values are never stored
+const SENSITIVE_KEYS: [&str; 4] = ["password", "secret", "token", "credential"];
+
+/// Helm release an implementation was installed from
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HelmRelease {
+    pub name: String,
+    pub namespace: String,
+    /// Chart and version, e.g. `nginx-gateway-fabric-1.4.0`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chart: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub app_version: Option<String>,
+    /// Computed values (chart defaults merged with overrides)
+    #[serde(default)]
+    pub values: Value,
+}
+
+/// Effective configuration of an installed gateway
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GatewaySetup {
+    pub gateway: String,
+    pub captured_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub helm: Option<HelmRelease>,
+    /// GatewayClass spec
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gateway_class: Option<Value>,
+    /// Spec (or data) of the resource the GatewayClass `parametersRef` points at
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class_parameters: Option<Value>,
+    /// ConfigMap data by `namespace/name`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub config_maps: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+/// One setting that differs between two setups
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SetupDifference {
+    pub path: String,
+    pub left: Option<String>,
+    pub right: Option<String>,
+}
+
+impl fmt::Display for SetupDifference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let side = |v: &Option<String>| v.clone().unwrap_or_else(|| "(unset)".to_string());
+        write!(
+            f,
+            "{}: {} → {}",
+            self.path,
+            side(&self.left),
+            side(&self.right)
+        )
+    }
+}
+
+impl GatewaySetup {
+    pub fn new(gateway: impl Into<String>) -> Self {
+        Self {
+            gateway: gateway.into(),
+            captured_at: Utc::now(),
+            helm: None,
+            gateway_class: None,
+            class_parameters: None,
+            config_maps: BTreeMap::new(),
+        }
+    }
+
+    /// Every setting as `path = value`, leaving out the capture time
+    fn settings(&self) -> BTreeMap<String, String> {
+        let mut value = serde_json::to_value(self).unwrap_or_default();
+        if let Some(map) = value.as_object_mut() {
+            map.remove("captured_at");
+        }
+        let mut settings = BTreeMap::new();
+        flatten(&value, String::new(), &mut settings);
+        settings
+    }
+
+    /// Settings that differ from `other`, sorted by path
+    pub fn diff(&self, other: &GatewaySetup) -> Vec<SetupDifference> {
+        let left = self.settings();
+        let right = other.settings();
+        let mut paths: Vec<&String> = left.keys().chain(right.keys()).collect();
+        paths.sort();
+        paths.dedup();
+        paths
+            .into_iter()
+            .filter(|path| left.get(*path) != right.get(*path))
+            .map(|path| SetupDifference {
+                path: path.clone(),
+                left: left.get(path).cloned(),
+                right: right.get(path).cloned(),
+            })
+            .collect()
+    }
+}
+
+fn flatten(value: &Value, path: String, out: &mut BTreeMap<String, String>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, item) in map {
+                let child = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                flatten(item, child, out);
+            }
+        }
+        Value::Array(items) if !items.is_empty() => {
+            for (i, item) in items.iter().enumerate() {
+                flatten(item, format!("{path}[{i}]"), out);
+            }
+        }
+        Value::String(s) => {
+            out.insert(path, s.clone());
+        }
+        other => {
+            out.insert(path, other.to_string());
+        }
+    }
+}
+
+/// Replace values under credential-like keys with [`REDACTED`]
+pub fn redact(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, item) in map.iter_mut() {
+                if is_sensitive(key) && !item.is_object() {
+                    *item = Value::from(REDACTED);
+                } else {
+                    redact(item);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+/// Redact the value stored under ConfigMap key `key`: all of it for a
+/// credential-like key, otherwise each `key: value` or `key=value` line
+/// whose key looks like a credential
+pub fn redact_text(key: &str, text: &str) -> String {
+    if is_sensitive(key) {
+        return REDACTED.to_string();
+    }
+    text.split('\n')
+        .map(|line| match line.find([':', '=']) {
+            Some(at) if is_sensitive(&line[..at]) && !line[at + 1..].trim().is_empty() => {
+                let space = if line[at..].starts_with(':') { " " } else { "" };
+                format!("{}{space}{REDACTED}", &line[..=at])
+            }
+            _ => line.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Whether `key` names a credential
+pub fn is_sensitive(key: &str) -> bool {
+    let key = key.to_lowercase();
+    SENSITIVE_KEYS.iter().any(|s| key.contains(s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn setup(replicas: u64, config: &str) -> GatewaySetup {
+        let mut setup = GatewaySetup::new("Envoy Gateway");
+        setup.helm = Some(HelmRelease {
+            name: "gateway-poc-envoy".to_string(),
+            namespace: "envoy-gateway-system".to_string(),
+            chart: Some("gateway-helm-v1.2.0".to_string()),
+            app_version: None,
+            values: json!({ "deployment": { "replicas": replicas } }),
+        });
+        setup.config_maps.insert(
+            "envoy-gateway-system/envoy-gateway-config".to_string(),
+            BTreeMap::from([("envoy-gateway.yaml".to_string(), config.to_string())]),
+        );
+        setup
+    }
+
+    #[test]
+    fn test_diff() {
+        let a = setup(1, "logging: info");
+        let mut b = setup(2, "logging: info");
+        b.captured_at = a.captured_at + chrono::Duration::days(90);
+        b.gateway_class =
+            Some(json!({ "controllerName": "gateway.envoyproxy.io/gatewayclass-controller" }));
+
+        assert!(a.diff(&a.clone()).is_empty());
+        let diff = a.diff(&b);
+        assert_eq!(diff.len(), 2, "{diff:?}");
+        assert_eq!(diff[0].path, "gateway_class.controllerName");
+        assert_eq!(diff[0].left, None);
+        assert_eq!(
+            diff[1].to_string(),
+            "helm.values.deployment.replicas: 1 → 2"
+        );
+    }
+
+    #[test]
+    fn test_redact() {
+        let mut values = json!({
+            "auth": { "adminPassword": "hunter2", "tokens": ["a", "b"] },
+            "clientSecret": { "name": "oidc" },
+            "replicas": 2,
+        });
+        redact(&mut values);
+        assert_eq!(values["auth"]["adminPassword"], REDACTED);
+        assert_eq!(values["auth"]["tokens"], REDACTED);
+        assert_eq!(values["clientSecret"]["name"], "oidc");
+        assert_eq!(values["replicas"], 2);
+    }
+
+    #[test]
+    fn test_redact_text() {
+        assert_eq!(redact_text("db-password", "hunter2"), REDACTED);
+        let config = "logging: info\nauth:\n  clientSecret: s3cr3t\napi_token=abc\n";
+        assert_eq!(
+            redact_text("config.yaml", config),
+            "logging: info\nauth:\n  clientSecret: <redacted>\napi_token=<redacted>\n"
+        );
+    }
+}
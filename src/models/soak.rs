@@ -0,0 +1,486 @@
+//! Soak runs
+//!
+//! A soak run repeats the suite for a wall-clock duration instead of a fixed
+//! number of rounds. Each round is folded into a [`SoakReport`] when it
+//! finishes and then dropped, so memory stays bounded however long the run
+//! lasts: per-test counters, at most [`MAX_SOAK_BUCKETS`] time buckets and
+//! the last [`MAX_SOAK_FAILURES`] failures.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::time::Duration;
+
+use super::{HttpTraffic, TestRoundSummary, TestStatus};
+
+/// Buckets the planned duration is divided into
+pub const SOAK_BUCKETS: u64 = 24;
+
+/// Buckets kept before neighbours are merged (a soak that overruns)
+pub const MAX_SOAK_BUCKETS: usize = 48;
+
+/// Most recent failures kept in the report
+pub const MAX_SOAK_FAILURES: usize = 20;
+
+/// Rounds in a row that may fail to run before the soak stops
+pub const MAX_CONSECUTIVE_ROUND_ERRORS: u32 = 3;
+
+/// Default minutes between checkpoints to the results store
+pub const DEFAULT_CHECKPOINT_MINS: u64 = 10;
+
+/// Duration and checkpointing of a soak run
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SoakSettings {
+    /// How long to keep starting rounds
+    pub duration: Duration,
+    /// How often the report is saved while the soak runs
+    pub checkpoint_interval: Duration,
+}
+
+impl SoakSettings {
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            checkpoint_interval: Duration::from_secs(DEFAULT_CHECKPOINT_MINS * 60),
+        }
+    }
+
+    pub fn with_checkpoint_interval(mut self, interval: Duration) -> Self {
+        self.checkpoint_interval = interval;
+        self
+    }
+
+    /// Bucket width: the duration split into [`SOAK_BUCKETS`], in whole minutes
+    pub fn bucket_secs(&self) -> u64 {
+        let secs = self.duration.as_secs().div_ceil(SOAK_BUCKETS);
+        secs.div_ceil(60).max(1) * 60
+    }
+
+    /// Parse a duration such as "90s", "30m", "6h", "1d" or "1h30m"
+    pub fn parse_duration(value: &str) -> Result<Duration, String> {
+        let value = value.trim();
+        let mut secs: u64 = 0;
+        let mut digits = String::new();
+        for c in value.chars() {
+            if c.is_ascii_digit() {
+                digits.push(c);
+                continue;
+            }
+            let unit = match c {
+                's' => 1,
+                'm' => 60,
+                'h' => 3600,
+                'd' => 86400,
+                _ => {
+                    return Err(format!(
+                        "invalid duration '{value}': unknown unit '{c}' (use s, m, h or d)"
+                    ))
+                }
+            };
+            let count: u64 = digits.parse().map_err(|_| {
+                format!("invalid duration '{value}': expected a number before '{c}'")
+            })?;
+            digits.clear();
+            secs = count
+                .checked_mul(unit)
+                .and_then(|s| secs.checked_add(s))
+                .ok_or_else(|| format!("invalid duration '{value}': too long"))?;
+        }
+        if !digits.is_empty() {
+            return Err(format!(
+                "invalid duration '{value}': missing unit (e.g. {digits}m)"
+            ));
+        }
+        if secs == 0 {
+            return Err(format!("invalid duration '{value}': must be positive"));
+        }
+        Ok(Duration::from_secs(secs))
+    }
+}
+
+/// Incremental results of a soak run
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SoakReport {
+    pub started_at: DateTime<Utc>,
+    /// When the last round finished
+    pub updated_at: DateTime<Utc>,
+    /// Planned duration in seconds
+    pub duration_secs: u64,
+    pub rounds: u32,
+    /// Bucket width; doubles whenever buckets are merged
+    pub bucket_secs: u64,
+    /// Pass rates per time bucket, oldest first (empty buckets are omitted)
+    pub buckets: Vec<SoakBucket>,
+    /// Per-test counters by test number
+    pub tests: BTreeMap<u8, SoakTestStats>,
+    /// Sum of the round pass rates (for the mean)
+    pub pass_rate_sum: f64,
+    pub min_pass_rate: f64,
+    pub max_pass_rate: f64,
+    /// Sum of the round durations
+    pub total_duration_ms: u64,
+    /// Rounds that started while the cluster was unhealthy
+    pub anomalous_rounds: u32,
+    /// HTTP traffic of all rounds (when a metrics registry was attached)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub traffic: Option<HttpTraffic>,
+    /// Most recent failed tests, oldest first
+    pub recent_failures: VecDeque<SoakFailure>,
+    /// Rounds that failed to run (not counted in `rounds`)
+    #[serde(default)]
+    pub errored_rounds: u32,
+    /// Most recent rounds that failed to run, oldest first
+    #[serde(default, skip_serializing_if = "VecDeque::is_empty")]
+    pub round_errors: VecDeque<SoakRoundError>,
+    /// Why the soak ended before its duration, if it did
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stopped: Option<String>,
+}
+
+/// Rounds that finished within one time bucket
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SoakBucket {
+    pub start: DateTime<Utc>,
+    pub rounds: u32,
+    /// Passing test runs, including flaky ones
+    pub passed: u32,
+    /// Test runs the implementation supports
+    pub applicable: u32,
+    /// Test runs that passed only on retry
+    pub flaky: u32,
+}
+
+impl SoakBucket {
+    fn add(&mut self, other: &SoakBucket) {
+        self.rounds += other.rounds;
+        self.passed += other.passed;
+        self.applicable += other.applicable;
+        self.flaky += other.flaky;
+    }
+
+    pub fn pass_rate(&self) -> f64 {
+        if self.applicable == 0 {
+            0.0
+        } else {
+            self.passed as f64 / self.applicable as f64 * 100.0
+        }
+    }
+}
+
+/// Counters for a single test across the soak
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SoakTestStats {
+    /// Passing runs, including flaky ones
+    pub passes: u32,
+    pub flakes: u32,
+    pub failures: u32,
+    pub skips: u32,
+    pub errors: u32,
+    pub not_supported: u32,
+    pub total_duration_ms: u64,
+    pub min_duration_ms: u64,
+    pub max_duration_ms: u64,
+}
+
+impl SoakTestStats {
+    /// Runs that count towards the pass rate
+    pub fn runs(&self) -> u32 {
+        self.passes + self.failures + self.errors
+    }
+}
+
+/// A failed test, kept for the report
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SoakFailure {
+    pub at: DateTime<Utc>,
+    pub round: u32,
+    pub test_number: u8,
+    pub status: TestStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// A round that failed to run, kept for the report
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SoakRoundError {
+    pub at: DateTime<Utc>,
+    pub round: u32,
+    pub message: String,
+}
+
+impl SoakReport {
+    pub fn new(settings: &SoakSettings, started_at: DateTime<Utc>) -> Self {
+        Self {
+            started_at,
+            updated_at: started_at,
+            duration_secs: settings.duration.as_secs(),
+            rounds: 0,
+            bucket_secs: settings.bucket_secs(),
+            buckets: Vec::new(),
+            tests: BTreeMap::new(),
+            pass_rate_sum: 0.0,
+            min_pass_rate: 0.0,
+            max_pass_rate: 0.0,
+            total_duration_ms: 0,
+            anomalous_rounds: 0,
+            traffic: None,
+            recent_failures: VecDeque::new(),
+            errored_rounds: 0,
+            round_errors: VecDeque::new(),
+            stopped: None,
+        }
+    }
+
+    /// Number of the next round, counting rounds that failed to run
+    pub fn next_round(&self) -> u32 {
+        self.rounds + self.errored_rounds + 1
+    }
+
+    /// Note a round that failed to run at `at`
+    pub fn record_error(&mut self, round: u32, message: String, at: DateTime<Utc>) {
+        self.errored_rounds += 1;
+        self.updated_at = at;
+        if self.round_errors.len() == MAX_SOAK_FAILURES {
+            self.round_errors.pop_front();
+        }
+        self.round_errors
+            .push_back(SoakRoundError { at, round, message });
+    }
+
+    /// Fold in a round that finished at `at`
+    pub fn record(&mut self, summary: &TestRoundSummary, at: DateTime<Utc>) {
+        let rate = summary.pass_rate();
+        if self.rounds == 0 {
+            self.min_pass_rate = rate;
+            self.max_pass_rate = rate;
+        } else {
+            self.min_pass_rate = self.min_pass_rate.min(rate);
+            self.max_pass_rate = self.max_pass_rate.max(rate);
+        }
+        self.rounds += 1;
+        self.updated_at = at;
+        self.pass_rate_sum += rate;
+        self.total_duration_ms += summary.total_duration_ms;
+        if summary.is_anomalous() {
+            self.anomalous_rounds += 1;
+        }
+        if let Some(traffic) = &summary.traffic {
+            self.traffic
+                .get_or_insert_with(Default::default)
+                .add(traffic);
+        }
+
+        for result in &summary.results {
+            let stats = self.tests.entry(result.test_case.number()).or_default();
+            let first = stats.runs() + stats.skips + stats.not_supported == 0;
+            match result.status {
+                TestStatus::Pass => stats.passes += 1,
+                TestStatus::Flaky => {
+                    stats.passes += 1;
+                    stats.flakes += 1;
+                }
+                TestStatus::Fail => stats.failures += 1,
+                TestStatus::Skip => stats.skips += 1,
+                TestStatus::Error => stats.errors += 1,
+                TestStatus::NotSupported => stats.not_supported += 1,
+            }
+            if first || result.duration_ms < stats.min_duration_ms {
+                stats.min_duration_ms = result.duration_ms;
+            }
+            stats.max_duration_ms = stats.max_duration_ms.max(result.duration_ms);
+            stats.total_duration_ms += result.duration_ms;
+
+            if matches!(result.status, TestStatus::Fail | TestStatus::Error) {
+                if self.recent_failures.len() == MAX_SOAK_FAILURES {
+                    self.recent_failures.pop_front();
+                }
+                self.recent_failures.push_back(SoakFailure {
+                    at,
+                    round: summary.round,
+                    test_number: result.test_case.number(),
+                    status: result.status,
+                    message: result.message.clone(),
+                });
+            }
+        }
+
+        let bucket = SoakBucket {
+            start: self.bucket_start(at),
+            rounds: 1,
+            passed: summary.passed as u32,
+            applicable: summary.applicable() as u32,
+            flaky: summary.flaky as u32,
+        };
+        self.add_bucket(bucket);
+        if self.buckets.len() > MAX_SOAK_BUCKETS {
+            self.merge_buckets();
+        }
+    }
+
+    /// Mean of the round pass rates
+    pub fn avg_pass_rate(&self) -> f64 {
+        if self.rounds == 0 {
+            0.0
+        } else {
+            self.pass_rate_sum / self.rounds as f64
+        }
+    }
+
+    /// Time between the start and the last finished round
+    pub fn elapsed(&self) -> Duration {
+        (self.updated_at - self.started_at)
+            .to_std()
+            .unwrap_or_default()
+    }
+
+    /// Start of the bucket containing `at`
+    fn bucket_start(&self, at: DateTime<Utc>) -> DateTime<Utc> {
+        let offset = (at - self.started_at).num_seconds().max(0) as u64;
+        let start = offset / self.bucket_secs * self.bucket_secs;
+        self.started_at + chrono::Duration::seconds(start as i64)
+    }
+
+    fn add_bucket(&mut self, bucket: SoakBucket) {
+        match self.buckets.last_mut() {
+            Some(last) if last.start == bucket.start => last.add(&bucket),
+            _ => self.buckets.push(bucket),
+        }
+    }
+
+    /// Double the bucket width, merging neighbours
+    fn merge_buckets(&mut self) {
+        self.bucket_secs *= 2;
+        for mut bucket in std::mem::take(&mut self.buckets) {
+            bucket.start = self.bucket_start(bucket.start);
+            self.add_bucket(bucket);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{TestCase, TestResult};
+
+    fn round(round: u32, results: Vec<TestResult>) -> TestRoundSummary {
+        TestRoundSummary::new(round, "Test", results)
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        let parse = SoakSettings::parse_duration;
+        assert_eq!(parse("90s"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse("30m"), Ok(Duration::from_secs(1800)));
+        assert_eq!(parse("6h"), Ok(Duration::from_secs(6 * 3600)));
+        assert_eq!(parse("1d"), Ok(Duration::from_secs(86400)));
+        assert_eq!(parse("1h30m"), Ok(Duration::from_secs(5400)));
+        assert!(parse("6").is_err());
+        assert!(parse("h").is_err());
+        assert!(parse("6w").is_err());
+        assert!(parse("0m").is_err());
+        assert!(parse("").is_err());
+    }
+
+    #[test]
+    fn test_bucket_secs() {
+        let bucket = |secs| SoakSettings::new(Duration::from_secs(secs)).bucket_secs();
+        assert_eq!(bucket(6 * 3600), 900);
+        assert_eq!(bucket(600), 60);
+        assert_eq!(bucket(25 * 60), 120);
+    }
+
+    #[test]
+    fn test_record() {
+        let settings = SoakSettings::new(Duration::from_secs(3600));
+        let start = Utc::now();
+        let mut report = SoakReport::new(&settings, start);
+
+        let at = |secs| start + chrono::Duration::seconds(secs);
+        report.record(
+            &round(
+                1,
+                vec![
+                    TestResult::pass(TestCase::HostRouting, 100),
+                    TestResult::pass(TestCase::PathRouting, 50),
+                ],
+            ),
+            at(30),
+        );
+        report.record(
+            &round(
+                2,
+                vec![
+                    TestResult::pass(TestCase::HostRouting, 300),
+                    TestResult::fail(TestCase::PathRouting, 70, "404"),
+                ],
+            ),
+            at(45),
+        );
+        report.record(
+            &round(3, vec![TestResult::pass(TestCase::HostRouting, 200)]),
+            at(200),
+        );
+
+        assert_eq!(report.rounds, 3);
+        assert_eq!(report.min_pass_rate, 50.0);
+        assert_eq!(report.max_pass_rate, 100.0);
+        assert!((report.avg_pass_rate() - 250.0 / 3.0).abs() < 1e-9);
+
+        let host = &report.tests[&TestCase::HostRouting.number()];
+        assert_eq!((host.passes, host.runs()), (3, 3));
+        assert_eq!((host.min_duration_ms, host.max_duration_ms), (100, 300));
+        assert_eq!(report.tests[&TestCase::PathRouting.number()].failures, 1);
+
+        // An hour in 3-minute buckets: rounds 1 and 2 share the first
+        assert_eq!(report.bucket_secs, 180);
+        assert_eq!(report.buckets.len(), 2);
+        assert_eq!(report.buckets[0].rounds, 2);
+        assert_eq!(report.buckets[0].pass_rate(), 75.0);
+        assert_eq!(report.buckets[1].rounds, 1);
+        assert_eq!(report.buckets[1].start, at(180));
+
+        assert_eq!(report.recent_failures.len(), 1);
+        assert_eq!(report.recent_failures[0].round, 2);
+        assert_eq!(report.recent_failures[0].message.as_deref(), Some("404"));
+    }
+
+    #[test]
+    fn test_record_error() {
+        let settings = SoakSettings::new(Duration::from_secs(3600));
+        let start = Utc::now();
+        let mut report = SoakReport::new(&settings, start);
+        report.record(
+            &round(1, vec![TestResult::pass(TestCase::HostRouting, 100)]),
+            start,
+        );
+        report.record_error(2, "connection refused".to_string(), start);
+        assert_eq!(report.rounds, 1);
+        assert_eq!(report.errored_rounds, 1);
+        assert_eq!(report.next_round(), 3);
+        assert_eq!(report.round_errors[0].round, 2);
+        assert_eq!(report.avg_pass_rate(), 100.0);
+    }
+
+    #[test]
+    fn test_memory_is_bounded() {
+        let settings = SoakSettings::new(Duration::from_secs(600));
+        let start = Utc::now();
+        let mut report = SoakReport::new(&settings, start);
+
+        // Ten times the planned duration, a failing round every 10 seconds
+        for i in 0..600 {
+            let summary = round(
+                i + 1,
+                vec![TestResult::fail(TestCase::HostRouting, 10, "timeout")],
+            );
+            report.record(&summary, start + chrono::Duration::seconds(i as i64 * 10));
+        }
+
+        assert_eq!(report.rounds, 600);
+        assert!(report.buckets.len() <= MAX_SOAK_BUCKETS);
+        assert_eq!(report.bucket_secs, 240);
+        assert_eq!(report.buckets.iter().map(|b| b.rounds).sum::<u32>(), 600);
+        assert_eq!(report.recent_failures.len(), MAX_SOAK_FAILURES);
+        assert_eq!(report.recent_failures.back().unwrap().round, 600);
+    }
+}
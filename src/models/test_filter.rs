@@ -0,0 +1,356 @@
+//! Test selection
+//!
+//! Narrows a run to a partial suite by test number, category and tag, and
+//! opts in to experimental test groups.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use super::TestCase;
+
+/// Test category
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TestCategory {
+    Routing,
+    Tls,
+    Traffic,
+    Advanced,
+    Ai,
+}
+
+impl TestCategory {
+    pub fn name(&self) -> &'static str {
+        match self {
+            TestCategory::Routing => "Routing",
+            TestCategory::Tls => "TLS",
+            TestCategory::Traffic => "Traffic",
+            TestCategory::Advanced => "Advanced",
+            TestCategory::Ai => "AI",
+        }
+    }
+
+    pub fn all() -> Vec<TestCategory> {
+        vec![
+            TestCategory::Routing,
+            TestCategory::Tls,
+            TestCategory::Traffic,
+            TestCategory::Advanced,
+            TestCategory::Ai,
+        ]
+    }
+
+    pub fn from_str(s: &str) -> Option<TestCategory> {
+        match s.trim().to_lowercase().as_str() {
+            "routing" => Some(TestCategory::Routing),
+            "tls" => Some(TestCategory::Tls),
+            "traffic" => Some(TestCategory::Traffic),
+            "advanced" => Some(TestCategory::Advanced),
+            "ai" => Some(TestCategory::Ai),
+            _ => None,
+        }
+    }
+
+    /// Parse a category name (usable as a clap value parser)
+    pub fn parse(s: &str) -> Result<TestCategory, String> {
+        Self::from_str(s).ok_or_else(|| {
+            format!("Unknown test category '{s}' (expected routing, tls, traffic, advanced or ai)")
+        })
+    }
+}
+
+impl fmt::Display for TestCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Opt-in test group outside the default suite
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Experimental {
+    /// AI/LLM inference routes (streaming, token latency, body limits)
+    Ai,
+    /// Tests that restart the gateway's pods (Zero-Downtime Reload)
+    Disruptive,
+}
+
+impl Experimental {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Experimental::Ai => "ai",
+            Experimental::Disruptive => "disruptive",
+        }
+    }
+
+    /// Parse a group name (usable as a clap value parser)
+    pub fn parse(s: &str) -> Result<Experimental, String> {
+        match s.trim().to_lowercase().as_str() {
+            "ai" => Ok(Experimental::Ai),
+            "disruptive" => Ok(Experimental::Disruptive),
+            _ => Err(format!(
+                "Unknown experimental test group '{s}' (expected ai or disruptive)"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Experimental {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Selects the tests of a partial suite
+///
+/// A test is selected when it is one of the listed tests (if any are
+/// given), is not skipped, belongs to one of the
+/// categories (if any are given), carries at least one of the tags
+/// (if any are given) and, if experimental, its group is enabled.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TestFilter {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tests: Vec<u8>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skip: Vec<u8>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<TestCategory>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub experimental: Vec<Experimental>,
+}
+
+impl TestFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restrict the run to these test numbers
+    pub fn with_tests(mut self, tests: Vec<u8>) -> Self {
+        self.tests = tests;
+        self
+    }
+
+    pub fn with_skip(mut self, tests: Vec<u8>) -> Self {
+        self.skip = tests;
+        self
+    }
+
+    pub fn with_categories(mut self, categories: Vec<TestCategory>) -> Self {
+        self.categories = categories;
+        self
+    }
+
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags.into_iter().map(|t| t.trim().to_lowercase()).collect();
+        self
+    }
+
+    /// Enable experimental test groups
+    pub fn with_experimental(mut self, groups: Vec<Experimental>) -> Self {
+        self.experimental = groups;
+        self
+    }
+
+    /// Whether an experimental group is enabled
+    pub fn enables(&self, group: Experimental) -> bool {
+        self.experimental.contains(&group)
+    }
+
+    /// Whether the filter selects exactly the default suite
+    pub fn is_empty(&self) -> bool {
+        self.tests.is_empty()
+            && self.skip.is_empty()
+            && self.categories.is_empty()
+            && self.tags.is_empty()
+            && self.experimental.is_empty()
+    }
+
+    /// Check skip numbers and tags refer to known tests
+    pub fn validate(&self) -> Result<(), String> {
+        for (list, numbers) in [("test", &self.tests), ("skip", &self.skip)] {
+            if let Some(n) = numbers
+                .iter()
+                .find(|n| TestCase::from_number(**n).is_none())
+            {
+                return Err(format!(
+                    "Invalid test number {n} in {list} list (valid: 1-{})",
+                    TestCase::all().len()
+                ));
+            }
+        }
+
+        let known = TestCase::known_tags();
+        if let Some(tag) = self.tags.iter().find(|t| !known.contains(&t.as_str())) {
+            return Err(format!(
+                "Unknown test tag '{tag}' (known: {})",
+                known.join(", ")
+            ));
+        }
+
+        if self.categories.contains(&TestCategory::Ai) && !self.enables(Experimental::Ai) {
+            return Err(
+                "AI tests are experimental; enable them with --experimental ai".to_string(),
+            );
+        }
+        if let Some((test, group)) = self.tests.iter().find_map(|n| {
+            let test = TestCase::from_number(*n)?;
+            test.experimental()
+                .filter(|g| !self.enables(*g))
+                .map(|g| (test, g))
+        }) {
+            return Err(format!(
+                "Test {} ({}) is experimental; enable it with --experimental {group}",
+                test.number(),
+                test.name()
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn matches(&self, test_case: TestCase) -> bool {
+        test_case.experimental().is_none_or(|g| self.enables(g))
+            && (self.tests.is_empty() || self.tests.contains(&test_case.number()))
+            && !self.skip.contains(&test_case.number())
+            && (self.categories.is_empty() || self.categories.contains(&test_case.category()))
+            && (self.tags.is_empty()
+                || test_case
+                    .tags()
+                    .iter()
+                    .any(|tag| self.tags.iter().any(|t| t == tag)))
+    }
+
+    /// Tests of `candidates` the filter selects, in order
+    pub fn select(&self, candidates: Vec<TestCase>) -> Vec<TestCase> {
+        candidates
+            .into_iter()
+            .filter(|t| self.matches(*t))
+            .collect()
+    }
+}
+
+impl fmt::Display for TestFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        if !self.tests.is_empty() {
+            let numbers: Vec<String> = self.tests.iter().map(|n| n.to_string()).collect();
+            parts.push(format!("tests {}", numbers.join(",")));
+        }
+        if !self.categories.is_empty() {
+            let names: Vec<String> = self
+                .categories
+                .iter()
+                .map(|c| c.name().to_lowercase())
+                .collect();
+            parts.push(format!("only {}", names.join(",")));
+        }
+        if !self.tags.is_empty() {
+            parts.push(format!("tags {}", self.tags.join(",")));
+        }
+        if !self.skip.is_empty() {
+            let numbers: Vec<String> = self.skip.iter().map(|n| n.to_string()).collect();
+            parts.push(format!("skip {}", numbers.join(",")));
+        }
+        if !self.experimental.is_empty() {
+            let groups: Vec<&str> = self.experimental.iter().map(|g| g.name()).collect();
+            parts.push(format!("experimental {}", groups.join(",")));
+        }
+        if parts.is_empty() {
+            write!(f, "all tests")
+        } else {
+            write!(f, "{}", parts.join("; "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_category_parse() {
+        assert_eq!(TestCategory::parse("TLS"), Ok(TestCategory::Tls));
+        assert!(TestCategory::parse("misc").is_err());
+        assert_eq!(TestCase::TlsTermination.category(), TestCategory::Tls);
+    }
+
+    #[test]
+    fn test_filter_select() {
+        let all = TestCase::all();
+        let default_suite = all.iter().filter(|t| t.experimental().is_none()).count();
+        assert_eq!(TestFilter::new().select(all.clone()).len(), default_suite);
+
+        let skip = TestFilter::new().with_skip(vec![3, 7]);
+        let selected = skip.select(all.clone());
+        assert_eq!(selected.len(), default_suite - 2);
+        assert!(!selected.contains(&TestCase::HeaderRouting));
+
+        let only =
+            TestFilter::new().with_categories(vec![TestCategory::Routing, TestCategory::Tls]);
+        assert_eq!(only.select(all.clone()).len(), 11);
+
+        let tagged = TestFilter::new()
+            .with_categories(vec![TestCategory::Advanced])
+            .with_tags(vec!["GRPC".to_string()]);
+        assert_eq!(tagged.select(all), vec![TestCase::GrpcRouting]);
+        assert_eq!(tagged.to_string(), "only advanced; tags grpc");
+
+        let replay = TestFilter::new().with_tests(vec![3, 7]).with_skip(vec![7]);
+        assert_eq!(
+            replay.select(TestCase::all()),
+            vec![TestCase::HeaderRouting]
+        );
+        assert_eq!(replay.to_string(), "tests 3,7; skip 7");
+    }
+
+    #[test]
+    fn test_filter_validate() {
+        assert!(TestFilter::new().with_skip(vec![18]).validate().is_ok());
+        assert!(TestFilter::new().with_skip(vec![0]).validate().is_err());
+        assert!(TestFilter::new().with_tests(vec![99]).validate().is_err());
+        assert!(TestFilter::new()
+            .with_tags(vec!["nope".to_string()])
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_experimental_opt_in() {
+        let ai = TestFilter::new().with_categories(vec![TestCategory::Ai]);
+        assert!(ai.validate().is_err());
+
+        let ai = ai.with_experimental(vec![Experimental::Ai]);
+        assert!(ai.validate().is_ok());
+        assert_eq!(
+            ai.select(TestCase::all()),
+            vec![
+                TestCase::AiStreaming,
+                TestCase::AiTokenLatency,
+                TestCase::AiBodyLimit
+            ]
+        );
+        assert_eq!(ai.to_string(), "only ai; experimental ai");
+
+        let everything =
+            TestFilter::new().with_experimental(vec![Experimental::Ai, Experimental::Disruptive]);
+        assert_eq!(everything.select(TestCase::all()), TestCase::all());
+        assert_eq!(Experimental::parse("AI"), Ok(Experimental::Ai));
+
+        // Restarting the gateway needs an explicit opt-in, even by number
+        let reload = TestFilter::new().with_tests(vec![33]);
+        assert!(reload.select(TestCase::all()).is_empty());
+        assert!(reload
+            .validate()
+            .unwrap_err()
+            .contains("--experimental disruptive"));
+        let reload = reload.with_experimental(vec![Experimental::Disruptive]);
+        assert!(reload.validate().is_ok());
+        assert_eq!(
+            reload.select(TestCase::all()),
+            vec![TestCase::ZeroDowntimeReload]
+        );
+        assert!(Experimental::parse("wasm").is_err());
+    }
+}
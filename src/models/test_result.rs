@@ -7,7 +7,22 @@
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-/// All 17 test cases for Gateway API
+use super::backend::{BackendHits, BackendTopology};
+use super::crash::PodCrash;
+use super::environment::RoundEnvironment;
+use super::gateway_api::GatewayApiSpec;
+use super::test_filter::{Experimental, TestCategory, TestFilter};
+use super::traffic::HttpTraffic;
+
+/// Version of the test catalog; bump when tests are added, removed or
+/// their pass criteria change so stored results stay comparable
+pub const TEST_CATALOG_VERSION: u32 = 23;
+
+/// Detail key of a one-line summary of how the gateway behaved, shown
+/// next to the pass rate in gateway comparisons
+pub const BEHAVIOR_DETAIL: &str = "behavior";
+
+/// All 34 test cases for Gateway API (19-21 and 33 are experimental)
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum TestCase {
@@ -35,10 +50,41 @@ pub enum TestCase {
     HealthCheck,
     LoadTest,
     FailoverRecovery,
+
+    // Added traffic tests (18+)
+    ConsistentHashing,
+
+    // Experimental AI route tests (19-21, `--experimental ai`)
+    AiStreaming,
+    AiTokenLatency,
+    AiBodyLimit,
+
+    // Added network tests (22+)
+    DualStack,
+
+    // Added host handling tests (23-25)
+    WildcardHostname,
+    MissingHost,
+    SniMismatch,
+
+    // Added protocol tests (26+)
+    LargeBody,
+    HeaderHandling,
+    ForwardedHeaders,
+    Compression,
+    Cors,
+    TlsPolicy,
+    Http2,
+
+    // Added resilience tests (33+, `--experimental disruptive`)
+    ZeroDowntimeReload,
+
+    // Added routing tests (34+)
+    RoutePropagation,
 }
 
 impl TestCase {
-    /// Get test case number (1-17)
+    /// Get test case number (1-34)
     pub fn number(&self) -> u8 {
         match self {
             TestCase::HostRouting => 1,
@@ -58,6 +104,23 @@ impl TestCase {
             TestCase::HealthCheck => 15,
             TestCase::LoadTest => 16,
             TestCase::FailoverRecovery => 17,
+            TestCase::ConsistentHashing => 18,
+            TestCase::AiStreaming => 19,
+            TestCase::AiTokenLatency => 20,
+            TestCase::AiBodyLimit => 21,
+            TestCase::DualStack => 22,
+            TestCase::WildcardHostname => 23,
+            TestCase::MissingHost => 24,
+            TestCase::SniMismatch => 25,
+            TestCase::LargeBody => 26,
+            TestCase::HeaderHandling => 27,
+            TestCase::ForwardedHeaders => 28,
+            TestCase::Compression => 29,
+            TestCase::Cors => 30,
+            TestCase::TlsPolicy => 31,
+            TestCase::Http2 => 32,
+            TestCase::ZeroDowntimeReload => 33,
+            TestCase::RoutePropagation => 34,
         }
     }
 
@@ -81,22 +144,202 @@ impl TestCase {
             TestCase::HealthCheck => "Health Check",
             TestCase::LoadTest => "Load Test",
             TestCase::FailoverRecovery => "Failover Recovery",
+            TestCase::ConsistentHashing => "Consistent Hashing",
+            TestCase::AiStreaming => "AI Streaming",
+            TestCase::AiTokenLatency => "AI Token Latency",
+            TestCase::AiBodyLimit => "AI Body Limit",
+            TestCase::DualStack => "Dual Stack",
+            TestCase::WildcardHostname => "Wildcard Hostname",
+            TestCase::MissingHost => "Missing Host Header",
+            TestCase::SniMismatch => "SNI/Host Mismatch",
+            TestCase::LargeBody => "Large Body",
+            TestCase::HeaderHandling => "Header Handling",
+            TestCase::ForwardedHeaders => "Forwarded Headers",
+            TestCase::Compression => "Compression",
+            TestCase::Cors => "CORS",
+            TestCase::TlsPolicy => "TLS Policy",
+            TestCase::Http2 => "HTTP/2",
+            TestCase::ZeroDowntimeReload => "Zero-Downtime Reload",
+            TestCase::RoutePropagation => "Route Propagation",
+        }
+    }
+
+    /// What the test validates, for the feature catalog
+    pub fn description(&self) -> &'static str {
+        match self {
+            TestCase::HostRouting => "Requests are routed to the backend matching the Host header",
+            TestCase::PathRouting => "Path prefix and exact matches select the right backend",
+            TestCase::HeaderRouting => "Header matches select the right backend",
+            TestCase::TlsTermination => {
+                "HTTPS listeners terminate TLS with the configured certificate"
+            }
+            TestCase::HttpsRedirect => {
+                "Redirect filters return the configured status and exact Location (scheme, \
+                 host, port, path)"
+            }
+            TestCase::BackendTls => {
+                "The gateway connects to TLS backends and validates their certificate"
+            }
+            TestCase::CanaryTraffic => {
+                "Weighted backends receive traffic in proportion to their weight"
+            }
+            TestCase::RateLimiting => "Requests beyond the configured rate are rejected with 429",
+            TestCase::TimeoutRetry => "Slow backends time out and failed requests are retried",
+            TestCase::SessionAffinity => "Requests of one session stick to the same backend",
+            TestCase::UrlRewrite => "Paths and hostnames are rewritten before reaching the backend",
+            TestCase::HeaderModifier => "Request and response headers are added, set and removed",
+            TestCase::CrossNamespace => {
+                "Routes reach backends in other namespaces only when granted"
+            }
+            TestCase::GrpcRouting => "gRPC calls are routed by service and method",
+            TestCase::HealthCheck => "The gateway serves the health endpoint with a healthy status",
+            TestCase::LoadTest => "Throughput and latency stay within thresholds under load",
+            TestCase::FailoverRecovery => {
+                "Traffic recovers within the deadline after a backend fails"
+            }
+            TestCase::ConsistentHashing => {
+                "Requests with the same key stick to one backend, and few keys move on scale-out"
+            }
+            TestCase::AiStreaming => {
+                "Long streamed completions reach the client incrementally, not buffered"
+            }
+            TestCase::AiTokenLatency => {
+                "Time to first token and inter-token latency stay within thresholds"
+            }
+            TestCase::AiBodyLimit => {
+                "Prompts above the request body limit are rejected with 413, smaller ones pass"
+            }
+            TestCase::DualStack => "The gateway routes requests arriving over both IPv4 and IPv6",
+            TestCase::WildcardHostname => {
+                "Wildcard route hostnames match subdomains but not the apex domain"
+            }
+            TestCase::MissingHost => {
+                "HTTP/1.1 requests without a Host header are rejected with 400"
+            }
+            TestCase::SniMismatch => {
+                "A Host header not matching the TLS SNI is refused rather than routed"
+            }
+            TestCase::LargeBody => {
+                "Request bodies up to 100 MiB stream through; reports the size limit and buffering"
+            }
+            TestCase::HeaderHandling => {
+                "Duplicate and mixed-case headers reach the backend, hop-by-hop headers do not"
+            }
+            TestCase::ForwardedHeaders => {
+                "X-Forwarded-For/Forwarded carry the client address; client-supplied chains are not trusted"
+            }
+            TestCase::Compression => {
+                "Responses are encoded only as the client accepts, with matching Content-Encoding and Vary"
+            }
+            TestCase::Cors => {
+                "Preflights from the allowed origin get matching Access-Control-* headers; others do not"
+            }
+            TestCase::TlsPolicy => {
+                "HTTPS listeners refuse TLS 1.0/1.1 and NULL, EXPORT, RC4 and 3DES cipher suites"
+            }
+            TestCase::Http2 => {
+                "Concurrent streams on an h2 connection each get their own response; http/1.1 clients are still served"
+            }
+            TestCase::ZeroDowntimeReload => {
+                "No request is dropped or answered with 5xx while the gateway's pods are rollout-restarted"
+            }
+            TestCase::RoutePropagation => {
+                "A newly created HTTPRoute is served, and stops being served once deleted, within a minute"
+            }
         }
     }
 
     /// Get test category
-    pub fn category(&self) -> &'static str {
+    pub fn category(&self) -> TestCategory {
         match self {
-            TestCase::HostRouting | TestCase::PathRouting | TestCase::HeaderRouting => "Routing",
-            TestCase::TlsTermination | TestCase::HttpsRedirect | TestCase::BackendTls => "TLS",
+            TestCase::HostRouting
+            | TestCase::PathRouting
+            | TestCase::HeaderRouting
+            | TestCase::WildcardHostname
+            | TestCase::MissingHost
+            | TestCase::RoutePropagation => TestCategory::Routing,
+            TestCase::TlsTermination
+            | TestCase::HttpsRedirect
+            | TestCase::BackendTls
+            | TestCase::SniMismatch
+            | TestCase::TlsPolicy => TestCategory::Tls,
             TestCase::CanaryTraffic
             | TestCase::RateLimiting
             | TestCase::TimeoutRetry
-            | TestCase::SessionAffinity => "Traffic",
-            _ => "Advanced",
+            | TestCase::SessionAffinity
+            | TestCase::ConsistentHashing => TestCategory::Traffic,
+            TestCase::AiStreaming | TestCase::AiTokenLatency | TestCase::AiBodyLimit => {
+                TestCategory::Ai
+            }
+            _ => TestCategory::Advanced,
         }
     }
 
+    /// Tags for `--tag` selection (protocol, spec level and cost)
+    pub fn tags(&self) -> &'static [&'static str] {
+        match self {
+            TestCase::HostRouting | TestCase::PathRouting | TestCase::HeaderRouting => {
+                &["http", "core"]
+            }
+            TestCase::TlsTermination => &["tls", "core"],
+            TestCase::HttpsRedirect => &["http", "tls", "core"],
+            TestCase::BackendTls => &["tls", "policy", "experimental"],
+            TestCase::CanaryTraffic => &["http", "core"],
+            TestCase::RateLimiting | TestCase::SessionAffinity => &["http", "policy"],
+            TestCase::TimeoutRetry => &["http", "policy", "extended"],
+            TestCase::UrlRewrite | TestCase::HeaderModifier => &["http", "filters", "core"],
+            TestCase::CrossNamespace => &["http", "core"],
+            TestCase::GrpcRouting => &["grpc", "extended"],
+            TestCase::HealthCheck => &["http"],
+            TestCase::LoadTest => &["http", "slow"],
+            TestCase::FailoverRecovery => &["http", "slow", "resilience"],
+            TestCase::ConsistentHashing => &["http", "policy", "slow"],
+            TestCase::AiStreaming | TestCase::AiTokenLatency => &["http", "ai", "streaming"],
+            TestCase::AiBodyLimit => &["http", "ai", "policy"],
+            TestCase::DualStack => &["http", "ipv6"],
+            TestCase::WildcardHostname => &["http", "core"],
+            TestCase::MissingHost => &["http"],
+            TestCase::SniMismatch => &["tls"],
+            TestCase::LargeBody => &["http", "slow"],
+            TestCase::HeaderHandling => &["http", "core"],
+            TestCase::ForwardedHeaders => &["http"],
+            TestCase::Compression => &["http"],
+            TestCase::Cors => &["http", "policy"],
+            TestCase::TlsPolicy => &["tls", "security"],
+            TestCase::Http2 => &["http", "tls"],
+            TestCase::ZeroDowntimeReload => &["http", "slow", "resilience"],
+            TestCase::RoutePropagation => &["http", "core"],
+        }
+    }
+
+    /// Opt-in group of an experimental test (None = default suite)
+    pub fn experimental(&self) -> Option<Experimental> {
+        match self {
+            TestCase::AiStreaming | TestCase::AiTokenLatency | TestCase::AiBodyLimit => {
+                Some(Experimental::Ai)
+            }
+            TestCase::ZeroDowntimeReload => Some(Experimental::Disruptive),
+            _ => None,
+        }
+    }
+
+    /// Whether the test disturbs the gateway for every other test, so it
+    /// must not overlap with them
+    pub fn exclusive(&self) -> bool {
+        matches!(self, TestCase::ZeroDowntimeReload)
+    }
+
+    /// Every tag used by the catalog, sorted
+    pub fn known_tags() -> Vec<&'static str> {
+        let mut tags: Vec<&'static str> = Self::all()
+            .iter()
+            .flat_map(|t| t.tags().iter().copied())
+            .collect();
+        tags.sort_unstable();
+        tags.dedup();
+        tags
+    }
+
     /// Get all test cases
     pub fn all() -> Vec<TestCase> {
         vec![
@@ -117,6 +360,23 @@ impl TestCase {
             TestCase::HealthCheck,
             TestCase::LoadTest,
             TestCase::FailoverRecovery,
+            TestCase::ConsistentHashing,
+            TestCase::AiStreaming,
+            TestCase::AiTokenLatency,
+            TestCase::AiBodyLimit,
+            TestCase::DualStack,
+            TestCase::WildcardHostname,
+            TestCase::MissingHost,
+            TestCase::SniMismatch,
+            TestCase::LargeBody,
+            TestCase::HeaderHandling,
+            TestCase::ForwardedHeaders,
+            TestCase::Compression,
+            TestCase::Cors,
+            TestCase::TlsPolicy,
+            TestCase::Http2,
+            TestCase::ZeroDowntimeReload,
+            TestCase::RoutePropagation,
         ]
     }
 
@@ -140,6 +400,23 @@ impl TestCase {
             15 => Some(TestCase::HealthCheck),
             16 => Some(TestCase::LoadTest),
             17 => Some(TestCase::FailoverRecovery),
+            18 => Some(TestCase::ConsistentHashing),
+            19 => Some(TestCase::AiStreaming),
+            20 => Some(TestCase::AiTokenLatency),
+            21 => Some(TestCase::AiBodyLimit),
+            22 => Some(TestCase::DualStack),
+            23 => Some(TestCase::WildcardHostname),
+            24 => Some(TestCase::MissingHost),
+            25 => Some(TestCase::SniMismatch),
+            26 => Some(TestCase::LargeBody),
+            27 => Some(TestCase::HeaderHandling),
+            28 => Some(TestCase::ForwardedHeaders),
+            29 => Some(TestCase::Compression),
+            30 => Some(TestCase::Cors),
+            31 => Some(TestCase::TlsPolicy),
+            32 => Some(TestCase::Http2),
+            33 => Some(TestCase::ZeroDowntimeReload),
+            34 => Some(TestCase::RoutePropagation),
             _ => None,
         }
     }
@@ -156,23 +433,35 @@ impl fmt::Display for TestCase {
 #[serde(rename_all = "lowercase")]
 pub enum TestStatus {
     Pass,
+    /// Passed only after failing at least once under a retry policy
+    Flaky,
     Fail,
     Skip,
     Error,
+    /// The implementation lacks an optional capability the test needs (N/A)
+    #[serde(rename = "not_supported")]
+    NotSupported,
 }
 
 impl TestStatus {
     pub fn symbol(&self) -> &'static str {
         match self {
             TestStatus::Pass => "✓",
+            TestStatus::Flaky => "~",
             TestStatus::Fail => "✗",
             TestStatus::Skip => "○",
             TestStatus::Error => "!",
+            TestStatus::NotSupported => "-",
         }
     }
 
     pub fn is_success(&self) -> bool {
-        matches!(self, TestStatus::Pass)
+        matches!(self, TestStatus::Pass | TestStatus::Flaky)
+    }
+
+    /// Whether a retry policy should re-run a test that ended this way
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, TestStatus::Fail | TestStatus::Error)
     }
 }
 
@@ -180,9 +469,11 @@ impl fmt::Display for TestStatus {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             TestStatus::Pass => write!(f, "PASS"),
+            TestStatus::Flaky => write!(f, "FLAKY"),
             TestStatus::Fail => write!(f, "FAIL"),
             TestStatus::Skip => write!(f, "SKIP"),
             TestStatus::Error => write!(f, "ERROR"),
+            TestStatus::NotSupported => write!(f, "N/A"),
         }
     }
 }
@@ -195,6 +486,16 @@ pub struct TestResult {
     pub duration_ms: u64,
     pub message: Option<String>,
     pub details: Option<serde_json::Value>,
+    /// Retries performed before this result (0 = first attempt)
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub retries: u32,
+    /// Backend pods that served the test's requests
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub backends: Vec<BackendHits>,
+}
+
+fn is_zero(n: &u32) -> bool {
+    *n == 0
 }
 
 impl TestResult {
@@ -205,6 +506,8 @@ impl TestResult {
             duration_ms,
             message: None,
             details: None,
+            retries: 0,
+            backends: Vec::new(),
         }
     }
 
@@ -215,6 +518,8 @@ impl TestResult {
             duration_ms,
             message: Some(message.into()),
             details: None,
+            retries: 0,
+            backends: Vec::new(),
         }
     }
 
@@ -225,6 +530,21 @@ impl TestResult {
             duration_ms: 0,
             message: Some(reason.into()),
             details: None,
+            retries: 0,
+            backends: Vec::new(),
+        }
+    }
+
+    /// Test needing a capability the implementation does not provide
+    pub fn not_supported(test_case: TestCase, reason: impl Into<String>) -> Self {
+        Self {
+            test_case,
+            status: TestStatus::NotSupported,
+            duration_ms: 0,
+            message: Some(reason.into()),
+            details: None,
+            retries: 0,
+            backends: Vec::new(),
         }
     }
 
@@ -235,6 +555,8 @@ impl TestResult {
             duration_ms: 0,
             message: Some(error.into()),
             details: None,
+            retries: 0,
+            backends: Vec::new(),
         }
     }
 
@@ -247,6 +569,21 @@ impl TestResult {
         self.details = Some(details);
         self
     }
+
+    pub fn with_backends(mut self, backends: Vec<BackendHits>) -> Self {
+        self.backends = backends;
+        self
+    }
+
+    /// Reclassify a pass on retry as flaky, keeping the first failure
+    pub fn into_flaky(mut self, first_failure: Option<&str>) -> Self {
+        self.status = TestStatus::Flaky;
+        self.message = Some(match first_failure {
+            Some(reason) => format!("Passed on retry {} (first failure: {reason})", self.retries),
+            None => format!("Passed on retry {}", self.retries),
+        });
+        self
+    }
 }
 
 impl fmt::Display for TestResult {
@@ -271,20 +608,47 @@ pub struct TestRoundSummary {
     pub round: u32,
     pub gateway: String,
     pub total: usize,
+    /// Passing tests, including flaky ones
     pub passed: usize,
+    /// Tests that passed only on retry
+    #[serde(default)]
+    pub flaky: usize,
     pub failed: usize,
     pub skipped: usize,
     pub errors: usize,
+    /// Tests the implementation does not support (excluded from the pass rate)
+    #[serde(default)]
+    pub not_supported: usize,
     pub total_duration_ms: u64,
     pub results: Vec<TestResult>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<RoundEnvironment>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gateway_api: Option<GatewayApiSpec>,
+    /// Selection of a partial suite (None = all tests)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<TestFilter>,
+    /// Gateway pod crashes during the round (results are tainted)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub crashes: Vec<PodCrash>,
+    /// HTTP traffic the round's tests sent (when a metrics registry is attached)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub traffic: Option<HttpTraffic>,
+    /// Backend replicas and nodes that served the round's tests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backend_topology: Option<BackendTopology>,
+    /// Parameter variant the round ran with (heterogeneous round plans)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variant: Option<String>,
 }
 
 impl TestRoundSummary {
     pub fn new(round: u32, gateway: impl Into<String>, results: Vec<TestResult>) -> Self {
         let total = results.len();
-        let passed = results
+        let passed = results.iter().filter(|r| r.status.is_success()).count();
+        let flaky = results
             .iter()
-            .filter(|r| r.status == TestStatus::Pass)
+            .filter(|r| r.status == TestStatus::Flaky)
             .count();
         let failed = results
             .iter()
@@ -298,31 +662,99 @@ impl TestRoundSummary {
             .iter()
             .filter(|r| r.status == TestStatus::Error)
             .count();
+        let not_supported = results
+            .iter()
+            .filter(|r| r.status == TestStatus::NotSupported)
+            .count();
         let total_duration_ms = results.iter().map(|r| r.duration_ms).sum();
+        let backend_topology = BackendTopology::from_hits(results.iter().flat_map(|r| &r.backends));
 
         Self {
             round,
             gateway: gateway.into(),
             total,
             passed,
+            flaky,
             failed,
             skipped,
             errors,
+            not_supported,
             total_duration_ms,
             results,
+            environment: None,
+            gateway_api: None,
+            filter: None,
+            crashes: Vec::new(),
+            traffic: None,
+            backend_topology,
+            variant: None,
         }
     }
 
+    /// Record the selection when the round ran a partial suite
+    pub fn with_filter(mut self, filter: &TestFilter) -> Self {
+        self.filter = (!filter.is_empty()).then(|| filter.clone());
+        self
+    }
+
+    /// Attach the cluster snapshot taken before this round
+    pub fn with_environment(mut self, environment: Option<RoundEnvironment>) -> Self {
+        self.environment = environment;
+        self
+    }
+
+    /// Record the Gateway API spec level the round was run against
+    pub fn with_gateway_api(mut self, spec: Option<GatewayApiSpec>) -> Self {
+        self.gateway_api = spec;
+        self
+    }
+
+    /// Record gateway pod crashes observed during the round
+    pub fn with_crashes(mut self, crashes: Vec<PodCrash>) -> Self {
+        self.crashes = crashes;
+        self
+    }
+
+    /// Record the HTTP traffic sent during the round
+    pub fn with_traffic(mut self, traffic: Option<HttpTraffic>) -> Self {
+        self.traffic = traffic;
+        self
+    }
+
+    /// Record the parameter variant the round ran with
+    pub fn with_variant(mut self, variant: Option<String>) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    /// Whether a gateway pod crashed while the round ran
+    pub fn is_tainted(&self) -> bool {
+        !self.crashes.is_empty()
+    }
+
+    /// Whether the cluster was unhealthy when this round started
+    pub fn is_anomalous(&self) -> bool {
+        self.environment
+            .as_ref()
+            .map(|e| e.is_anomalous())
+            .unwrap_or(false)
+    }
+
+    /// Tests the implementation supports
+    pub fn applicable(&self) -> usize {
+        self.total.saturating_sub(self.not_supported)
+    }
+
     pub fn pass_rate(&self) -> f64 {
-        if self.total == 0 {
+        if self.applicable() == 0 {
             0.0
         } else {
-            (self.passed as f64 / self.total as f64) * 100.0
+            (self.passed as f64 / self.applicable() as f64) * 100.0
         }
     }
 
     pub fn is_all_passed(&self) -> bool {
-        self.passed == self.total
+        self.passed == self.applicable()
     }
 }
 
@@ -330,15 +762,34 @@ impl fmt::Display for TestRoundSummary {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "Round {} - {} Gateway", self.round, self.gateway)?;
         writeln!(f, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
+        if let Some(env) = &self.environment {
+            writeln!(f, "  Cluster: {env}")?;
+        }
         for result in &self.results {
             writeln!(f, "  {result}")?;
         }
+        for crash in &self.crashes {
+            writeln!(f, "  Tainted - gateway pod crashed: {crash}")?;
+        }
         writeln!(f, "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━")?;
         writeln!(
             f,
             "Total: {} | Pass: {} | Fail: {} | Skip: {} | Error: {}",
             self.total, self.passed, self.failed, self.skipped, self.errors
         )?;
+        if self.flaky > 0 {
+            writeln!(f, "Flaky: {} (passed on retry)", self.flaky)?;
+        }
+        if self.not_supported > 0 {
+            writeln!(
+                f,
+                "N/A: {} (not supported, excluded from the pass rate)",
+                self.not_supported
+            )?;
+        }
+        if let Some(traffic) = &self.traffic {
+            writeln!(f, "HTTP: {traffic}")?;
+        }
         writeln!(
             f,
             "Pass Rate: {:.1}% | Duration: {}ms",
@@ -356,19 +807,41 @@ mod tests {
     fn test_case_numbers() {
         assert_eq!(TestCase::HostRouting.number(), 1);
         assert_eq!(TestCase::FailoverRecovery.number(), 17);
+        assert_eq!(TestCase::ConsistentHashing.number(), 18);
     }
 
     #[test]
     fn test_case_from_number() {
         assert_eq!(TestCase::from_number(1), Some(TestCase::HostRouting));
         assert_eq!(TestCase::from_number(17), Some(TestCase::FailoverRecovery));
-        assert_eq!(TestCase::from_number(18), None);
+        assert_eq!(TestCase::from_number(18), Some(TestCase::ConsistentHashing));
+        assert_eq!(TestCase::from_number(19), Some(TestCase::AiStreaming));
+        assert_eq!(TestCase::from_number(22), Some(TestCase::DualStack));
+        assert_eq!(TestCase::from_number(25), Some(TestCase::SniMismatch));
+        assert_eq!(TestCase::from_number(26), Some(TestCase::LargeBody));
+        assert_eq!(TestCase::from_number(27), Some(TestCase::HeaderHandling));
+        assert_eq!(TestCase::from_number(28), Some(TestCase::ForwardedHeaders));
+        assert_eq!(TestCase::from_number(29), Some(TestCase::Compression));
+        assert_eq!(TestCase::from_number(30), Some(TestCase::Cors));
+        assert_eq!(TestCase::from_number(31), Some(TestCase::TlsPolicy));
+        assert_eq!(TestCase::from_number(32), Some(TestCase::Http2));
+        assert_eq!(
+            TestCase::from_number(33),
+            Some(TestCase::ZeroDowntimeReload)
+        );
+        assert_eq!(TestCase::from_number(34), Some(TestCase::RoutePropagation));
+        assert_eq!(TestCase::from_number(35), None);
     }
 
     #[test]
     fn test_all_cases() {
         let all = TestCase::all();
-        assert_eq!(all.len(), 17);
+        assert_eq!(all.len(), 34);
+        let experimental: Vec<_> = all.iter().filter(|t| t.experimental().is_some()).collect();
+        assert_eq!(experimental.len(), 4);
+        assert!(experimental
+            .iter()
+            .all(|t| t.category() == TestCategory::Ai || t.exclusive()));
     }
 
     #[test]
@@ -389,7 +862,89 @@ mod tests {
         let summary = TestRoundSummary::new(1, "nginx", results);
         assert_eq!(summary.total, 3);
         assert_eq!(summary.passed, 1);
+        assert_eq!(summary.flaky, 0);
         assert_eq!(summary.failed, 1);
         assert_eq!(summary.skipped, 1);
+        assert!(!summary.is_anomalous());
+    }
+
+    #[test]
+    fn test_flaky_summary() {
+        let mut retried = TestResult::pass(TestCase::PathRouting, 80);
+        retried.retries = 2;
+        let flaky = retried.into_flaky(Some("Path not found"));
+        assert_eq!(
+            flaky.message.as_deref(),
+            Some("Passed on retry 2 (first failure: Path not found)")
+        );
+
+        let summary = TestRoundSummary::new(
+            1,
+            "nginx",
+            vec![TestResult::pass(TestCase::HostRouting, 100), flaky],
+        );
+        assert_eq!(summary.passed, 2);
+        assert_eq!(summary.flaky, 1);
+        assert!(summary.is_all_passed());
+
+        let json = serde_json::to_value(&summary.results[0]).unwrap();
+        assert!(json.get("retries").is_none());
+    }
+
+    #[test]
+    fn test_not_supported_summary() {
+        let summary = TestRoundSummary::new(
+            1,
+            "cilium",
+            vec![
+                TestResult::pass(TestCase::HostRouting, 100),
+                TestResult::not_supported(
+                    TestCase::SessionAffinity,
+                    "Cilium Gateway has no session affinity policy",
+                ),
+            ],
+        );
+        assert_eq!(summary.not_supported, 1);
+        assert_eq!(summary.pass_rate(), 100.0);
+        assert!(summary.is_all_passed());
+
+        let json = serde_json::to_value(&summary.results[1]).unwrap();
+        assert_eq!(json["status"], "not_supported");
+
+        // Inconsistent counts (e.g. a hand-edited stored run) don't underflow
+        let mut summary = summary;
+        summary.total = 0;
+        assert_eq!(summary.applicable(), 0);
+        assert_eq!(summary.pass_rate(), 0.0);
+    }
+
+    #[test]
+    fn test_round_summary_environment() {
+        let env = RoundEnvironment {
+            nodes_total: 3,
+            nodes_ready: 2,
+            api_latency_ms: Some(10),
+            ..RoundEnvironment::new()
+        };
+        let summary = TestRoundSummary::new(2, "envoy", vec![]).with_environment(Some(env));
+        assert!(summary.is_anomalous());
+    }
+
+    #[test]
+    fn test_round_summary_crashes() {
+        let summary = TestRoundSummary::new(1, "envoy", vec![]);
+        assert!(!summary.is_tainted());
+
+        let crash = PodCrash {
+            pod: "envoy-gateway-system/envoy-default-eg".to_string(),
+            container: Some("envoy".to_string()),
+            restarts: 1,
+            reason: "OOMKilled (exit 137)".to_string(),
+        };
+        let summary = summary.with_crashes(vec![crash]);
+        assert!(summary.is_tainted());
+        assert!(summary.to_string().contains(
+            "Tainted - gateway pod crashed: envoy-gateway-system/envoy-default-eg [envoy]: OOMKilled"
+        ));
     }
 }
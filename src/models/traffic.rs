@@ -0,0 +1,201 @@
+//! HTTP traffic models
+//!
+//! Requests, bytes, and error classes sent by the functional tests of a
+//! round, to show the traffic footprint of a full suite.
+
+#![allow(dead_code)]
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Class of a failed or unsuccessful HTTP request
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum HttpErrorClass {
+    #[serde(rename = "timeout")]
+    Timeout,
+    #[serde(rename = "connect")]
+    Connect,
+    /// Any other transport failure (TLS, protocol, body read)
+    #[serde(rename = "request")]
+    Request,
+    #[serde(rename = "4xx")]
+    ClientError,
+    #[serde(rename = "5xx")]
+    ServerError,
+}
+
+impl HttpErrorClass {
+    pub const ALL: [HttpErrorClass; 5] = [
+        HttpErrorClass::Timeout,
+        HttpErrorClass::Connect,
+        HttpErrorClass::Request,
+        HttpErrorClass::ClientError,
+        HttpErrorClass::ServerError,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HttpErrorClass::Timeout => "timeout",
+            HttpErrorClass::Connect => "connect",
+            HttpErrorClass::Request => "request",
+            HttpErrorClass::ClientError => "4xx",
+            HttpErrorClass::ServerError => "5xx",
+        }
+    }
+
+    /// Class of a response status (None for 1xx-3xx)
+    pub fn from_status(status: u16) -> Option<Self> {
+        match status {
+            400..=499 => Some(HttpErrorClass::ClientError),
+            500..=599 => Some(HttpErrorClass::ServerError),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for HttpErrorClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// HTTP traffic totals over a window
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct HttpTraffic {
+    pub requests: u64,
+    /// Request body bytes
+    pub bytes_sent: u64,
+    /// Response body bytes
+    pub bytes_received: u64,
+    /// Requests per error class
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub errors: BTreeMap<HttpErrorClass, u64>,
+    /// Responses received over IPv4
+    #[serde(default)]
+    pub ipv4: u64,
+    /// Responses received over IPv6
+    #[serde(default)]
+    pub ipv6: u64,
+}
+
+impl HttpTraffic {
+    /// Requests in any error class
+    pub fn error_count(&self) -> u64 {
+        self.errors.values().sum()
+    }
+
+    /// Add another window's traffic
+    pub fn add(&mut self, other: &HttpTraffic) {
+        self.requests += other.requests;
+        self.bytes_sent += other.bytes_sent;
+        self.bytes_received += other.bytes_received;
+        self.ipv4 += other.ipv4;
+        self.ipv6 += other.ipv6;
+        for (class, count) in &other.errors {
+            *self.errors.entry(*class).or_default() += count;
+        }
+    }
+
+    /// Total of several windows (None if none were recorded)
+    pub fn total<'a>(windows: impl IntoIterator<Item = &'a HttpTraffic>) -> Option<HttpTraffic> {
+        windows.into_iter().fold(None, |total, window| {
+            let mut total = total.unwrap_or_default();
+            total.add(window);
+            Some(total)
+        })
+    }
+
+    /// Traffic since an earlier snapshot of the same registry
+    pub fn since(&self, earlier: &HttpTraffic) -> HttpTraffic {
+        let errors = self
+            .errors
+            .iter()
+            .map(|(class, count)| {
+                let before = earlier.errors.get(class).copied().unwrap_or(0);
+                (*class, count.saturating_sub(before))
+            })
+            .filter(|(_, count)| *count > 0)
+            .collect();
+        HttpTraffic {
+            requests: self.requests.saturating_sub(earlier.requests),
+            bytes_sent: self.bytes_sent.saturating_sub(earlier.bytes_sent),
+            bytes_received: self.bytes_received.saturating_sub(earlier.bytes_received),
+            errors,
+            ipv4: self.ipv4.saturating_sub(earlier.ipv4),
+            ipv6: self.ipv6.saturating_sub(earlier.ipv6),
+        }
+    }
+}
+
+impl fmt::Display for HttpTraffic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} requests, {:.1} KiB sent, {:.1} KiB received",
+            self.requests,
+            self.bytes_sent as f64 / 1024.0,
+            self.bytes_received as f64 / 1024.0
+        )?;
+        if !self.errors.is_empty() {
+            let errors: Vec<String> = self
+                .errors
+                .iter()
+                .map(|(class, count)| format!("{class}={count}"))
+                .collect();
+            write!(f, ", errors: {}", errors.join(" "))?;
+        }
+        if self.ipv4 + self.ipv6 > 0 {
+            write!(f, ", IPv4 {} / IPv6 {}", self.ipv4, self.ipv6)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_traffic_since() {
+        let before = HttpTraffic {
+            requests: 10,
+            bytes_sent: 0,
+            bytes_received: 2048,
+            errors: BTreeMap::from([(HttpErrorClass::Timeout, 1)]),
+            ..HttpTraffic::default()
+        };
+        let after = HttpTraffic {
+            requests: 25,
+            bytes_sent: 512,
+            bytes_received: 4096,
+            errors: BTreeMap::from([
+                (HttpErrorClass::Timeout, 1),
+                (HttpErrorClass::ServerError, 3),
+            ]),
+            ..HttpTraffic::default()
+        };
+
+        let round = after.since(&before);
+        assert_eq!(round.requests, 15);
+        assert_eq!(round.error_count(), 3);
+        assert_eq!(
+            round.to_string(),
+            "15 requests, 0.5 KiB sent, 2.0 KiB received, errors: 5xx=3"
+        );
+
+        let total = HttpTraffic::total([&before, &round]).unwrap();
+        assert_eq!(total.requests, 25);
+        assert_eq!(total.errors.len(), 2);
+        assert!(HttpTraffic::total([]).is_none());
+
+        let json = serde_json::to_string(&round).unwrap();
+        assert!(json.contains("\"errors\":{\"5xx\":3}"));
+        assert_eq!(serde_json::from_str::<HttpTraffic>(&json).unwrap(), round);
+        assert_eq!(
+            HttpErrorClass::from_status(404),
+            Some(HttpErrorClass::ClientError)
+        );
+        assert_eq!(HttpErrorClass::from_status(302), None);
+    }
+}
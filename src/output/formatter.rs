@@ -9,7 +9,13 @@ use std::collections::HashMap;
 use std::io::Write;
 
 use crate::executor::AggregateResult;
-use crate::models::{GatewayImpl, TestResult, TestRoundSummary, TestStatus};
+use crate::models::{
+    GatewayImpl, HttpTraffic, SoakReport, TestCase, TestResult, TestRoundSummary, TestStatus,
+};
+use crate::results::failure_hints;
+
+/// Characters of a hint per line inside the summary box
+const HINT_WIDTH: usize = 55;
 
 /// Output format options
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -68,16 +74,20 @@ impl ResultFormatter {
         let status_str = if self.colorize {
             match result.status {
                 TestStatus::Pass => "\x1b[32m✓ PASS\x1b[0m",
+                TestStatus::Flaky => "\x1b[33m~ FLAKY\x1b[0m",
                 TestStatus::Fail => "\x1b[31m✗ FAIL\x1b[0m",
                 TestStatus::Skip => "\x1b[33m○ SKIP\x1b[0m",
                 TestStatus::Error => "\x1b[31m! ERROR\x1b[0m",
+                TestStatus::NotSupported => "\x1b[90m- N/A\x1b[0m",
             }
         } else {
             match result.status {
                 TestStatus::Pass => "✓ PASS",
+                TestStatus::Flaky => "~ FLAKY",
                 TestStatus::Fail => "✗ FAIL",
                 TestStatus::Skip => "○ SKIP",
                 TestStatus::Error => "! ERROR",
+                TestStatus::NotSupported => "- N/A",
             }
         };
 
@@ -132,9 +142,25 @@ impl ResultFormatter {
         ));
         output.push_str("╠══════════════════════════════════════════════════════════════╣\n");
 
-        // Results
+        // Results, with hints under failing tests
+        let hints = failure_hints(
+            summary
+                .results
+                .iter()
+                .filter(|r| matches!(r.status, TestStatus::Fail | TestStatus::Error))
+                .map(|r| (r.test_case.number(), r.message.as_deref().unwrap_or(""))),
+        );
         for result in &summary.results {
             output.push_str(&format!("║  {}  ║\n", self.format_result_table(result)));
+            for hint in hints
+                .iter()
+                .filter(|h| h.test_number == result.test_case.number())
+            {
+                for (i, line) in wrap(hint.hint(), HINT_WIDTH).iter().enumerate() {
+                    let marker = if i == 0 { "→" } else { " " };
+                    output.push_str(&format!("║     {marker} {line:HINT_WIDTH$}║\n"));
+                }
+            }
         }
 
         // Footer
@@ -155,11 +181,46 @@ impl ResultFormatter {
             "║  Total: {:2} | Pass: {} | Fail: {} | Skip: {:2} | Error: {:2}     ║\n",
             summary.total, pass_str, fail_str, summary.skipped, summary.errors
         ));
+        if summary.flaky > 0 {
+            output.push_str(&format!(
+                "║  Flaky: {:2} (passed on retry, counted as passed)              ║\n",
+                summary.flaky
+            ));
+        }
         output.push_str(&format!(
             "║  Pass Rate: {:5.1}% | Duration: {:6}ms                      ║\n",
             summary.pass_rate(),
             summary.total_duration_ms
         ));
+        if let Some(env) = &summary.environment {
+            let status = if env.is_anomalous() {
+                "ANOMALOUS"
+            } else {
+                "healthy"
+            };
+            output.push_str(&format!("║  Cluster: {:51}║\n", status));
+        }
+        if summary.is_tainted() {
+            let crashes = format!("{} gateway pod crash(es)", summary.crashes.len());
+            output.push_str(&format!("║  Tainted: {:51}║\n", crashes));
+        }
+        if let Some(traffic) = &summary.traffic {
+            let traffic = format!(
+                "{} requests, {} errors",
+                traffic.requests,
+                traffic.error_count()
+            );
+            output.push_str(&format!("║  HTTP: {:54}║\n", traffic));
+        }
+        if let Some(topology) = &summary.backend_topology {
+            output.push_str(&format!("║  Backends: {:50}║\n", topology.to_string()));
+        }
+        if let Some(spec) = &summary.gateway_api {
+            output.push_str(&format!("║  Gateway API: {:47}║\n", spec.to_string()));
+        }
+        if let Some(filter) = &summary.filter {
+            output.push_str(&format!("║  Partial suite: {:45}║\n", filter.to_string()));
+        }
         output.push_str("╚══════════════════════════════════════════════════════════════╝\n");
 
         output
@@ -273,6 +334,11 @@ impl ResultFormatter {
                     total_rounds: u32,
                     overall_pass_rate: f64,
                     test_pass_rates: HashMap<String, f64>,
+                    #[serde(skip_serializing_if = "HashMap::is_empty")]
+                    test_flakes: HashMap<String, u32>,
+                    anomalous_rounds: &'a [u32],
+                    #[serde(skip_serializing_if = "Option::is_none")]
+                    traffic: Option<&'a HttpTraffic>,
                 }
 
                 let json = AggregateJson {
@@ -284,6 +350,13 @@ impl ResultFormatter {
                         .iter()
                         .map(|(k, v)| (k.name().to_string(), *v))
                         .collect(),
+                    test_flakes: aggregate
+                        .retried_tests()
+                        .into_iter()
+                        .map(|(k, v)| (k.name().to_string(), v))
+                        .collect(),
+                    anomalous_rounds: &aggregate.anomalous_rounds,
+                    traffic: aggregate.traffic.as_ref(),
                 };
 
                 if self.format == OutputFormat::JsonPretty {
@@ -307,9 +380,24 @@ impl ResultFormatter {
         output.push_str("═══════════════════════════════════════════════════════════════\n");
 
         output.push_str(&format!(
-            " Overall Pass Rate: {:.1}%\n\n",
+            " Overall Pass Rate: {:.1}%\n",
             aggregate.overall_pass_rate
         ));
+        if !aggregate.anomalous_rounds.is_empty() {
+            let rounds: Vec<String> = aggregate
+                .anomalous_rounds
+                .iter()
+                .map(|r| r.to_string())
+                .collect();
+            output.push_str(&format!(
+                " Anomalous Rounds: {} (cluster unhealthy at start)\n",
+                rounds.join(", ")
+            ));
+        }
+        if let Some(traffic) = &aggregate.traffic {
+            output.push_str(&format!(" HTTP Traffic: {traffic}\n"));
+        }
+        output.push('\n');
 
         output.push_str(" Test Pass Rates:\n");
         output.push_str(" ───────────────────────────────────────────────────────────\n");
@@ -359,8 +447,168 @@ impl ResultFormatter {
             }
         }
 
+        let retried = aggregate.retried_tests();
+        if !retried.is_empty() {
+            output.push_str(&format!(
+                "\n Passed on Retry ({} flaky runs):\n",
+                aggregate.total_flakes()
+            ));
+            for (tc, flakes) in retried.iter().take(5) {
+                output.push_str(&format!(
+                    "   - {} ({} of {} rounds)\n",
+                    tc.name(),
+                    flakes,
+                    aggregate.total_rounds
+                ));
+            }
+        }
+
         output
     }
+
+    /// Format per-variant aggregates of a heterogeneous round plan
+    pub fn format_variants(&self, variants: &[(String, AggregateResult)], gateway: &str) -> String {
+        match self.format {
+            OutputFormat::Json | OutputFormat::JsonPretty => {
+                #[derive(Serialize)]
+                struct VariantJson<'a> {
+                    variant: &'a str,
+                    rounds: u32,
+                    pass_rate: f64,
+                    avg_duration_ms: u64,
+                    flaky_runs: u32,
+                }
+
+                let json: Vec<_> = variants
+                    .iter()
+                    .map(|(variant, aggregate)| VariantJson {
+                        variant,
+                        rounds: aggregate.total_rounds,
+                        pass_rate: aggregate.overall_pass_rate,
+                        avg_duration_ms: aggregate.avg_duration_ms(),
+                        flaky_runs: aggregate.total_flakes(),
+                    })
+                    .collect();
+
+                if self.format == OutputFormat::JsonPretty {
+                    serde_json::to_string_pretty(&json).unwrap_or_default()
+                } else {
+                    serde_json::to_string(&json).unwrap_or_default()
+                }
+            }
+            _ => {
+                let mut output = String::new();
+                output.push_str(&format!("\n Results by Variant: {}\n", gateway));
+                output.push_str(" ───────────────────────────────────────────────────────────\n");
+                output.push_str(&format!(
+                    " {:28} {:>6} {:>9} {:>10} {:>6}\n",
+                    "Variant", "Rounds", "Pass Rate", "Avg (ms)", "Flaky"
+                ));
+                for (variant, aggregate) in variants {
+                    output.push_str(&format!(
+                        " {:28} {:>6} {:>8.1}% {:>10} {:>6}\n",
+                        variant,
+                        aggregate.total_rounds,
+                        aggregate.overall_pass_rate,
+                        aggregate.avg_duration_ms(),
+                        aggregate.total_flakes()
+                    ));
+                }
+                output.push_str(" ───────────────────────────────────────────────────────────\n");
+                output
+            }
+        }
+    }
+
+    /// Format the time-bucketed pass rates of a soak run
+    pub fn format_soak(&self, report: &SoakReport, gateway: &str) -> String {
+        match self.format {
+            OutputFormat::Json => serde_json::to_string(report).unwrap_or_default(),
+            OutputFormat::JsonPretty => serde_json::to_string_pretty(report).unwrap_or_default(),
+            _ => {
+                let mut output = String::new();
+                output.push_str(&format!(
+                    "\n Soak Results: {} ({} rounds in {} of {})\n",
+                    gateway,
+                    report.rounds,
+                    clock(report.elapsed().as_secs()),
+                    clock(report.duration_secs)
+                ));
+                output.push_str(" ───────────────────────────────────────────────────────────\n");
+                output.push_str(&format!(
+                    " {:>8} {:>6} {:>6}  {:20} {:>6}\n",
+                    "Offset", "UTC", "Rounds", "Pass Rate", "Flaky"
+                ));
+                for bucket in &report.buckets {
+                    let offset = (bucket.start - report.started_at).num_seconds().max(0) as u64;
+                    let rate = bucket.pass_rate();
+                    let bar_len = ((rate / 10.0) as usize).min(10);
+                    output.push_str(&format!(
+                        " {:>8} {:>6} {:>6}  {}{} {:>6.1}% {:>6}\n",
+                        format!("+{}", clock(offset)),
+                        bucket.start.format("%H:%M"),
+                        bucket.rounds,
+                        "█".repeat(bar_len),
+                        "░".repeat(10 - bar_len),
+                        rate,
+                        bucket.flaky
+                    ));
+                }
+                output.push_str(" ───────────────────────────────────────────────────────────\n");
+                output.push_str(&format!(
+                    " Buckets of {}; round pass rate min {:.1}%, max {:.1}%\n",
+                    clock(report.bucket_secs),
+                    report.min_pass_rate,
+                    report.max_pass_rate
+                ));
+                if report.anomalous_rounds > 0 {
+                    output.push_str(&format!(
+                        " Anomalous Rounds: {} (cluster unhealthy at start)\n",
+                        report.anomalous_rounds
+                    ));
+                }
+
+                if let Some(error) = report.round_errors.back() {
+                    output.push_str(&format!(
+                        " Rounds Not Run: {} (last: round {}: {})\n",
+                        report.errored_rounds, error.round, error.message
+                    ));
+                }
+                if let Some(reason) = &report.stopped {
+                    output.push_str(&format!(" Stopped Early: {reason}\n"));
+                }
+
+                if !report.recent_failures.is_empty() {
+                    output.push_str("\n Recent Failures:\n");
+                    for failure in report.recent_failures.iter().rev().take(5) {
+                        let name = TestCase::from_number(failure.test_number)
+                            .map(|t| t.name())
+                            .unwrap_or("Unknown");
+                        output.push_str(&format!(
+                            "   - {} round {}: {} ({})\n",
+                            failure.at.format("%H:%M:%S"),
+                            failure.round,
+                            name,
+                            failure.message.as_deref().unwrap_or("no message")
+                        ));
+                    }
+                }
+                output
+            }
+        }
+    }
+}
+
+/// Seconds as hours and minutes ("6h00m", "15m")
+fn clock(secs: u64) -> String {
+    let (hours, minutes) = (secs / 3600, secs % 3600 / 60);
+    if hours > 0 {
+        format!("{hours}h{minutes:02}m")
+    } else if minutes > 0 {
+        format!("{minutes}m")
+    } else {
+        format!("{secs}s")
+    }
 }
 
 impl Default for ResultFormatter {
@@ -369,6 +617,25 @@ impl Default for ResultFormatter {
     }
 }
 
+/// Split text into lines of at most `width` characters at spaces
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
 /// Write results to a file
 pub fn write_results_to_file(
     path: &str,
@@ -410,4 +677,106 @@ mod tests {
         let output = formatter.format_result(&result);
         assert!(output.contains("Host Routing"));
     }
+
+    #[test]
+    fn test_summary_hints() {
+        let summary = TestRoundSummary::new(
+            1,
+            "nginx",
+            vec![
+                TestResult::pass(TestCase::HostRouting, 40),
+                TestResult::fail(
+                    TestCase::PathRouting,
+                    20,
+                    "✗ /api/v1 failed: Connection refused to http://10.0.0.1/api/v1",
+                ),
+            ],
+        );
+        let output = ResultFormatter::new(OutputFormat::Table)
+            .no_color()
+            .format_summary(&summary);
+        let lines: Vec<&str> = output.lines().collect();
+        let row = lines
+            .iter()
+            .position(|l| l.contains("Path Routing"))
+            .unwrap();
+        assert!(lines[row + 1].contains("→ Nothing is listening"));
+        assert!(lines
+            .iter()
+            .filter(|l| l.contains('→'))
+            .all(|l| l.chars().count() == lines[row + 1].chars().count()));
+    }
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(wrap("a bb ccc", 4), ["a bb", "ccc"]);
+        assert!(wrap("", 10).is_empty());
+    }
+
+    #[test]
+    fn test_format_variants() {
+        use crate::executor::BatchRunner;
+
+        let summaries = vec![
+            TestRoundSummary::new(
+                1,
+                "nginx",
+                vec![TestResult::pass(TestCase::HostRouting, 40)],
+            )
+            .with_variant(Some("concurrency=4".into())),
+            TestRoundSummary::new(
+                2,
+                "nginx",
+                vec![TestResult::pass(TestCase::HostRouting, 80)],
+            )
+            .with_variant(Some("concurrency=16".into())),
+        ];
+        let variants = BatchRunner::aggregate_by_variant(&summaries);
+        let output = ResultFormatter::new(OutputFormat::Table)
+            .no_color()
+            .format_variants(&variants, "nginx");
+        assert!(output.contains("concurrency=4"));
+        assert!(output.contains("concurrency=16"));
+        assert!(output.contains("80"));
+    }
+
+    #[test]
+    fn test_format_soak() {
+        use crate::models::SoakSettings;
+
+        let settings = SoakSettings::new(std::time::Duration::from_secs(6 * 3600));
+        let start = chrono::Utc::now();
+        let mut report = SoakReport::new(&settings, start);
+        report.record(
+            &TestRoundSummary::new(
+                1,
+                "nginx",
+                vec![TestResult::pass(TestCase::HostRouting, 40)],
+            ),
+            start + chrono::Duration::minutes(5),
+        );
+        report.record(
+            &TestRoundSummary::new(
+                2,
+                "nginx",
+                vec![TestResult::fail(TestCase::HostRouting, 40, "timed out")],
+            ),
+            start + chrono::Duration::minutes(20),
+        );
+
+        let output = ResultFormatter::new(OutputFormat::Table)
+            .no_color()
+            .format_soak(&report, "nginx");
+        assert!(output.contains("2 rounds in 20m of 6h00m"));
+        assert!(output.contains("+15m"));
+        assert!(output.contains("100.0%"));
+        assert!(output.contains("Host Routing (timed out)"));
+    }
+
+    #[test]
+    fn test_clock() {
+        assert_eq!(clock(45), "45s");
+        assert_eq!(clock(900), "15m");
+        assert_eq!(clock(6 * 3600 + 60), "6h01m");
+    }
 }
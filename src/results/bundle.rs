@@ -0,0 +1,305 @@
+//! Portable results bundles
+//!
+//! A bundle is a zstd-compressed tar archive of stored runs (with their
+//! environment metadata) and benchmark snapshots, so a comparison made on
+//! one machine can be reproduced on another. Layout:
+//!
+//! ```text
+//! manifest.json
+//! runs/<gateway>/<run_id>.json
+//! benchmarks/<snapshot>.prom
+//! ```
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use tracing::{debug, info};
+
+use super::storage::{EnvironmentInfo, ResultsStorage, StoredTestRun};
+use super::OPENMETRICS_EXTENSION;
+
+/// Bundle layout version
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+const MANIFEST: &str = "manifest.json";
+const RUNS_DIR: &str = "runs";
+const BENCHMARKS_DIR: &str = "benchmarks";
+
+/// Table of contents of a bundle
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BundleManifest {
+    pub format_version: u32,
+    pub created_at: DateTime<Utc>,
+    /// Version of the tool that wrote the bundle
+    pub tool_version: String,
+    pub runs: Vec<BundledRun>,
+    /// Benchmark snapshot file names
+    #[serde(default)]
+    pub benchmarks: Vec<String>,
+}
+
+/// A run listed in a bundle manifest
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BundledRun {
+    pub id: String,
+    pub gateway: String,
+    pub started_at: DateTime<Utc>,
+    pub environment: EnvironmentInfo,
+}
+
+/// Outcome of importing a bundle
+#[derive(Clone, Debug, Default)]
+pub struct BundleImport {
+    /// Imported runs (gateway, run ID)
+    pub imported: Vec<(String, String)>,
+    /// Runs already in the store and left untouched
+    pub skipped: Vec<(String, String)>,
+    /// Benchmark snapshots written
+    pub benchmarks: usize,
+}
+
+/// A name usable as a single path component
+fn is_safe_name(name: &str) -> bool {
+    !name.is_empty()
+        && name != "."
+        && name != ".."
+        && !name.contains(['/', '\\'])
+        && !name.contains('\0')
+}
+
+/// Archive path of a run
+fn run_entry(run: &BundledRun) -> String {
+    format!("{RUNS_DIR}/{}/{}.json", run.gateway.to_lowercase(), run.id)
+}
+
+fn append_file<W: std::io::Write>(
+    tar: &mut tar::Builder<W>,
+    name: &str,
+    data: &[u8],
+    mtime: DateTime<Utc>,
+) -> Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(data.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(mtime.timestamp().max(0) as u64);
+    header.set_cksum();
+    tar.append_data(&mut header, name, data)
+        .with_context(|| format!("Failed to add {name} to bundle"))
+}
+
+impl ResultsStorage {
+    /// Write runs and, optionally, all benchmark snapshots to a bundle
+    pub fn export_bundle(
+        &self,
+        runs: &[StoredTestRun],
+        include_benchmarks: bool,
+        path: &Path,
+    ) -> Result<BundleManifest> {
+        let mut benchmarks = Vec::new();
+        let benchmarks_dir = self.benchmarks_dir();
+        if include_benchmarks && benchmarks_dir.exists() {
+            for entry in fs::read_dir(&benchmarks_dir)? {
+                let path = entry?.path();
+                if path.extension().is_some_and(|e| e == OPENMETRICS_EXTENSION) {
+                    if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                        benchmarks.push(name.to_string());
+                    }
+                }
+            }
+            benchmarks.sort();
+        }
+
+        let manifest = BundleManifest {
+            format_version: BUNDLE_FORMAT_VERSION,
+            created_at: Utc::now(),
+            tool_version: env!("CARGO_PKG_VERSION").to_string(),
+            runs: runs
+                .iter()
+                .map(|run| BundledRun {
+                    id: run.id.clone(),
+                    gateway: run.gateway.clone(),
+                    started_at: run.started_at,
+                    environment: run.environment.clone(),
+                })
+                .collect(),
+            benchmarks,
+        };
+
+        let file = File::create(path).context("Failed to create bundle file")?;
+        let encoder = zstd::Encoder::new(file, 0)?;
+        let mut tar = tar::Builder::new(encoder);
+        let mtime = manifest.created_at;
+
+        append_file(
+            &mut tar,
+            MANIFEST,
+            &serde_json::to_vec_pretty(&manifest)?,
+            mtime,
+        )?;
+        for (run, entry) in runs.iter().zip(&manifest.runs) {
+            append_file(
+                &mut tar,
+                &run_entry(entry),
+                &serde_json::to_vec_pretty(run)?,
+                mtime,
+            )?;
+        }
+        for name in &manifest.benchmarks {
+            let data = fs::read(benchmarks_dir.join(name))
+                .with_context(|| format!("Failed to read benchmark snapshot {name}"))?;
+            append_file(&mut tar, &format!("{BENCHMARKS_DIR}/{name}"), &data, mtime)?;
+        }
+
+        tar.into_inner()?
+            .finish()
+            .context("Failed to finish bundle")?;
+        info!(
+            "Exported {} runs and {} benchmark snapshots to {}",
+            manifest.runs.len(),
+            manifest.benchmarks.len(),
+            path.display()
+        );
+        Ok(manifest)
+    }
+
+    /// Import a bundle; existing runs are kept unless `overwrite` is set
+    pub fn import_bundle(&self, path: &Path, overwrite: bool) -> Result<BundleImport> {
+        let file = File::open(path).context("Failed to open bundle file")?;
+        let decoder = zstd::Decoder::new(file).context("Failed to read bundle")?;
+        let mut archive = tar::Archive::new(decoder);
+
+        let mut entries = BTreeMap::new();
+        for entry in archive.entries().context("Failed to read bundle")? {
+            let mut entry = entry?;
+            let name = entry.path()?.into_owned();
+            if !name.components().all(|c| matches!(c, Component::Normal(_))) {
+                anyhow::bail!("Unsafe path in bundle: {}", name.display());
+            }
+            let mut data = Vec::new();
+            entry.read_to_end(&mut data)?;
+            entries.insert(name, data);
+        }
+
+        let manifest: BundleManifest = serde_json::from_slice(
+            entries
+                .get(Path::new(MANIFEST))
+                .context("Bundle has no manifest")?,
+        )
+        .context("Failed to parse bundle manifest")?;
+        if manifest.format_version > BUNDLE_FORMAT_VERSION {
+            anyhow::bail!(
+                "Bundle format version {} is newer than supported ({})",
+                manifest.format_version,
+                BUNDLE_FORMAT_VERSION
+            );
+        }
+
+        let mut import = BundleImport::default();
+        for entry in &manifest.runs {
+            if !is_safe_name(&entry.id) || !is_safe_name(&entry.gateway) {
+                anyhow::bail!("Invalid run in bundle: {} ({})", entry.id, entry.gateway);
+            }
+            let name = run_entry(entry);
+            let data = entries
+                .get(&PathBuf::from(&name))
+                .with_context(|| format!("Bundle is missing {name}"))?;
+            let run: StoredTestRun =
+                serde_json::from_slice(data).with_context(|| format!("Failed to parse {name}"))?;
+            if run.id != entry.id || run.gateway != entry.gateway {
+                anyhow::bail!("{name} does not match the bundle manifest");
+            }
+
+            let key = (run.gateway.clone(), run.id.clone());
+            if !overwrite && self.run_path(&run.gateway, &run.id).exists() {
+                debug!("Skipping existing run {}", run.id);
+                import.skipped.push(key);
+                continue;
+            }
+            self.save(&run)?;
+            import.imported.push(key);
+        }
+
+        let benchmarks_dir = self.benchmarks_dir();
+        for name in &manifest.benchmarks {
+            if !is_safe_name(name) {
+                anyhow::bail!("Invalid benchmark snapshot in bundle: {name}");
+            }
+            let entry = format!("{BENCHMARKS_DIR}/{name}");
+            let data = entries
+                .get(&PathBuf::from(&entry))
+                .with_context(|| format!("Bundle is missing {entry}"))?;
+            let target = benchmarks_dir.join(name);
+            if target.exists() && !overwrite {
+                continue;
+            }
+            fs::create_dir_all(&benchmarks_dir)?;
+            fs::write(&target, data)
+                .with_context(|| format!("Failed to write {}", target.display()))?;
+            import.benchmarks += 1;
+        }
+
+        info!(
+            "Imported {} runs ({} skipped) from {}",
+            import.imported.len(),
+            import.skipped.len(),
+            path.display()
+        );
+        Ok(import)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::GatewayImpl;
+
+    #[test]
+    fn test_bundle_round_trip() {
+        let source = tempfile::tempdir().unwrap();
+        let storage = ResultsStorage::new(source.path());
+        let mut nginx = StoredTestRun::new(GatewayImpl::Nginx, "10.0.0.1");
+        nginx.environment.k8s_version = Some("v1.29.2".to_string());
+        let envoy = StoredTestRun::new(GatewayImpl::Envoy, "10.0.0.2");
+        storage.save(&nginx).unwrap();
+        storage.save(&envoy).unwrap();
+        storage.save_benchmark_metrics(&[]).unwrap();
+
+        let bundle = source.path().join("out.tar.zst");
+        let manifest = storage
+            .export_bundle(&[nginx.clone(), envoy.clone()], true, &bundle)
+            .unwrap();
+        assert_eq!(manifest.runs.len(), 2);
+        assert_eq!(manifest.benchmarks.len(), 1);
+
+        let target = tempfile::tempdir().unwrap();
+        let imported = ResultsStorage::new(target.path());
+        imported.save(&envoy).unwrap();
+        let import = imported.import_bundle(&bundle, false).unwrap();
+        assert_eq!(import.imported, [(nginx.gateway.clone(), nginx.id.clone())]);
+        assert_eq!(import.skipped.len(), 1);
+        assert_eq!(import.benchmarks, 1);
+
+        let run = imported.find(&nginx.id).unwrap();
+        assert_eq!(run.environment.k8s_version.as_deref(), Some("v1.29.2"));
+        assert!(imported
+            .run_path(&run.gateway, &run.id)
+            .with_extension(OPENMETRICS_EXTENSION)
+            .exists());
+
+        let again = imported.import_bundle(&bundle, true).unwrap();
+        assert_eq!(again.imported.len(), 2);
+    }
+
+    #[test]
+    fn test_safe_names() {
+        assert!(is_safe_name("20240101_120000_0042"));
+        assert!(is_safe_name("NGINX Gateway Fabric"));
+        assert!(!is_safe_name(".."));
+        assert!(!is_safe_name("../etc"));
+        assert!(!is_safe_name(""));
+    }
+}
@@ -0,0 +1,440 @@
+//! Comparison charts
+//!
+//! Renders Load Test request latency CDFs, throughput bars and a per-test
+//! pass-rate heatmap for stored runs, as SVG (inline in HTML reports) or as
+//! standalone PNG/SVG files.
+
+use anyhow::{Context, Result};
+use plotters::coord::ranged1d::{KeyPointHint, NoDefaultFormatting, ValueFormatter};
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use plotters::style::text_anchor::{HPos, Pos, VPos};
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use crate::models::TestCase;
+use crate::results::storage::StoredTestRun;
+
+const CHART_SIZE: (u32, u32) = (800, 480);
+const FONT: &str = "sans-serif";
+
+/// Chart kinds
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Chart {
+    /// CDF of Load Test request latencies
+    LatencyCdf,
+    /// Load Test requests per second
+    RpsBars,
+    /// Pass rate by gateway and test
+    PassRateHeatmap,
+}
+
+impl Chart {
+    pub fn all() -> [Chart; 3] {
+        [Chart::LatencyCdf, Chart::RpsBars, Chart::PassRateHeatmap]
+    }
+
+    /// File name without extension
+    pub fn file_stem(&self) -> &'static str {
+        match self {
+            Chart::LatencyCdf => "latency-cdf",
+            Chart::RpsBars => "load-test-rps",
+            Chart::PassRateHeatmap => "pass-rate-heatmap",
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Chart::LatencyCdf => "Request Latency CDF",
+            Chart::RpsBars => "Load Test Throughput",
+            Chart::PassRateHeatmap => "Pass Rate by Test",
+        }
+    }
+
+    /// Whether the runs hold data for this chart
+    pub fn has_data(&self, runs: &[StoredTestRun]) -> bool {
+        match self {
+            Chart::LatencyCdf => runs.iter().any(|r| !latency_cdf(r).is_empty()),
+            Chart::RpsBars => runs.iter().any(|r| load_test_rps(r).is_some()),
+            Chart::PassRateHeatmap => runs.iter().any(|r| !pass_rates(r).is_empty()),
+        }
+    }
+
+    /// Render as an SVG document
+    pub fn render_svg(&self, runs: &[StoredTestRun]) -> Result<String> {
+        let mut svg = String::new();
+        {
+            let root = SVGBackend::with_string(&mut svg, CHART_SIZE).into_drawing_area();
+            self.draw(&root, runs)?;
+            root.present()
+                .map_err(|e| anyhow::anyhow!("Failed to render chart: {e}"))?;
+        }
+        Ok(svg)
+    }
+
+    /// Render to a PNG file
+    #[cfg(feature = "png-charts")]
+    pub fn render_png(&self, runs: &[StoredTestRun], path: &Path) -> Result<()> {
+        let root = BitMapBackend::new(path, CHART_SIZE).into_drawing_area();
+        self.draw(&root, runs)?;
+        root.present()
+            .map_err(|e| anyhow::anyhow!("Failed to write {}: {e}", path.display()))
+    }
+
+    fn draw<DB: DrawingBackend>(
+        &self,
+        root: &DrawingArea<DB, Shift>,
+        runs: &[StoredTestRun],
+    ) -> Result<()> {
+        let drawn = match self {
+            Chart::LatencyCdf => draw_latency_cdf(root, runs),
+            Chart::RpsBars => draw_rps_bars(root, runs),
+            Chart::PassRateHeatmap => draw_pass_rate_heatmap(root, runs),
+        };
+        drawn.map_err(|e| anyhow::anyhow!("Failed to draw {}: {e}", self.title()))
+    }
+}
+
+/// Write every chart with data as SVG, and as PNG with the `png-charts`
+/// feature, into `dir`
+pub fn export_charts(runs: &[StoredTestRun], dir: &Path) -> Result<Vec<PathBuf>> {
+    std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+
+    let mut written = Vec::new();
+    for chart in Chart::all().into_iter().filter(|c| c.has_data(runs)) {
+        let svg_path = dir.join(format!("{}.svg", chart.file_stem()));
+        std::fs::write(&svg_path, chart.render_svg(runs)?)
+            .with_context(|| format!("Failed to write {}", svg_path.display()))?;
+        written.push(svg_path);
+
+        #[cfg(feature = "png-charts")]
+        {
+            let png_path = dir.join(format!("{}.png", chart.file_stem()));
+            chart.render_png(runs, &png_path)?;
+            written.push(png_path);
+        }
+    }
+    Ok(written)
+}
+
+type DrawResult<DB> = Result<(), DrawingAreaErrorKind<<DB as DrawingBackend>::ErrorType>>;
+
+fn draw_latency_cdf<DB: DrawingBackend>(
+    root: &DrawingArea<DB, Shift>,
+    runs: &[StoredTestRun],
+) -> DrawResult<DB> {
+    root.fill(&WHITE)?;
+    let series: Vec<(&str, Vec<(f64, f64)>)> = runs
+        .iter()
+        .map(|r| (r.gateway.as_str(), latency_cdf(r)))
+        .filter(|(_, points)| !points.is_empty())
+        .collect();
+    let max_ms = series
+        .iter()
+        .flat_map(|(_, points)| points.iter().map(|p| p.0))
+        .fold(1.0, f64::max);
+
+    let mut chart = ChartBuilder::on(root)
+        .caption(Chart::LatencyCdf.title(), (FONT, 24))
+        .margin(15)
+        .x_label_area_size(40)
+        .y_label_area_size(50)
+        .build_cartesian_2d(0.0..max_ms * 1.05, 0.0..1.0)?;
+    chart
+        .configure_mesh()
+        .x_desc("Request latency (ms)")
+        .y_desc("Fraction of requests")
+        .y_label_formatter(&|y: &f64| format!("{:.0}%", y * 100.0))
+        .draw()?;
+
+    for (i, (gateway, points)) in series.into_iter().enumerate() {
+        let color = Palette99::pick(i).to_rgba();
+        chart
+            .draw_series(LineSeries::new(points, color.stroke_width(2)))?
+            .label(gateway)
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
+    }
+    chart
+        .configure_series_labels()
+        .position(SeriesLabelPosition::LowerRight)
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()?;
+    Ok(())
+}
+
+fn draw_rps_bars<DB: DrawingBackend>(
+    root: &DrawingArea<DB, Shift>,
+    runs: &[StoredTestRun],
+) -> DrawResult<DB> {
+    root.fill(&WHITE)?;
+    let bars: Vec<(&str, f64)> = runs
+        .iter()
+        .filter_map(|r| Some((r.gateway.as_str(), load_test_rps(r)?)))
+        .collect();
+    let max_rps = bars.iter().map(|b| b.1).fold(1.0, f64::max);
+    let names: Vec<&str> = bars.iter().map(|b| b.0).collect();
+
+    let mut chart = ChartBuilder::on(root)
+        .caption(Chart::RpsBars.title(), (FONT, 24))
+        .margin(15)
+        .x_label_area_size(40)
+        .y_label_area_size(60)
+        .build_cartesian_2d(Slots(bars.len()), 0.0..max_rps * 1.15)?;
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .y_desc("Requests per second")
+        .x_label_formatter(&|x| slot_label(&names, *x))
+        .draw()?;
+
+    chart.draw_series(bars.iter().enumerate().map(|(i, (_, rps))| {
+        let x = i as f64;
+        Rectangle::new(
+            [(x + 0.15, 0.0), (x + 0.85, *rps)],
+            Palette99::pick(i).filled(),
+        )
+    }))?;
+    chart.draw_series(bars.iter().enumerate().map(|(i, (_, rps))| {
+        Text::new(
+            format!("{rps:.1}"),
+            (i as f64 + 0.5, rps + max_rps * 0.03),
+            centered(14),
+        )
+    }))?;
+    Ok(())
+}
+
+fn draw_pass_rate_heatmap<DB: DrawingBackend>(
+    root: &DrawingArea<DB, Shift>,
+    runs: &[StoredTestRun],
+) -> DrawResult<DB> {
+    root.fill(&WHITE)?;
+    let rates: Vec<(&str, BTreeMap<u8, f64>)> = runs
+        .iter()
+        .map(|r| (r.gateway.as_str(), pass_rates(r)))
+        .collect();
+    let tests: Vec<u8> = rates
+        .iter()
+        .flat_map(|(_, r)| r.keys().copied())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let gateways: Vec<&str> = rates.iter().map(|r| r.0).collect();
+
+    let mut chart = ChartBuilder::on(root)
+        .caption(Chart::PassRateHeatmap.title(), (FONT, 24))
+        .margin(15)
+        .x_label_area_size(40)
+        .y_label_area_size(160)
+        .build_cartesian_2d(Slots(tests.len()), Slots(gateways.len()))?;
+    chart
+        .configure_mesh()
+        .disable_mesh()
+        .x_desc("Test")
+        .x_label_formatter(&|x| slot_label(&tests, *x))
+        .y_label_formatter(&|y| slot_label(&gateways, *y))
+        .draw()?;
+
+    for (row, (_, rates)) in rates.iter().enumerate() {
+        for (col, test) in tests.iter().enumerate() {
+            let Some(rate) = rates.get(test) else {
+                continue;
+            };
+            let (x, y) = (col as f64, row as f64);
+            let cell = [(x, y), (x + 1.0, y + 1.0)];
+            chart.draw_series(std::iter::once(Rectangle::new(
+                cell,
+                pass_rate_color(*rate).filled(),
+            )))?;
+            chart.draw_series(std::iter::once(Text::new(
+                format!("{:.0}", rate * 100.0),
+                (x + 0.5, y + 0.5),
+                centered(12),
+            )))?;
+        }
+    }
+    Ok(())
+}
+
+/// Axis with one unit-wide slot per item, labelled at slot centres
+struct Slots(usize);
+
+impl Ranged for Slots {
+    type FormatOption = NoDefaultFormatting;
+    type ValueType = f64;
+
+    fn map(&self, value: &f64, limit: (i32, i32)) -> i32 {
+        let span = (limit.1 - limit.0) as f64;
+        limit.0 + (span * value / self.0.max(1) as f64).round() as i32
+    }
+
+    fn key_points<Hint: KeyPointHint>(&self, _hint: Hint) -> Vec<f64> {
+        (0..self.0).map(|i| i as f64 + 0.5).collect()
+    }
+
+    fn range(&self) -> Range<f64> {
+        0.0..self.0.max(1) as f64
+    }
+}
+
+impl ValueFormatter<f64> for Slots {
+    fn format(value: &f64) -> String {
+        format!("{value}")
+    }
+}
+
+fn slot_label(items: &[impl ToString], x: f64) -> String {
+    items
+        .get(x.max(0.0) as usize)
+        .map(|item| item.to_string())
+        .unwrap_or_default()
+}
+
+/// Label style centred on its anchor
+fn centered(size: u32) -> TextStyle<'static> {
+    TextStyle::from((FONT, size).into_font()).pos(Pos::new(HPos::Center, VPos::Center))
+}
+
+/// Red (0%) through yellow to green (100%)
+fn pass_rate_color(rate: f64) -> HSLColor {
+    HSLColor(rate.clamp(0.0, 1.0) / 3.0, 0.65, 0.55)
+}
+
+/// Load Test latency quantiles stored per round, as CDF points
+const LATENCY_QUANTILES: [(&str, f64); 7] = [
+    ("min_latency_ms", 0.0),
+    ("p50_latency_ms", 0.5),
+    ("p90_latency_ms", 0.9),
+    ("p95_latency_ms", 0.95),
+    ("p99_latency_ms", 0.99),
+    ("p999_latency_ms", 0.999),
+    ("max_latency_ms", 1.0),
+];
+
+/// (latency, cumulative fraction) points of the Load Test's request
+/// latencies, each quantile averaged over rounds
+fn latency_cdf(run: &StoredTestRun) -> Vec<(f64, f64)> {
+    let load_tests: Vec<_> = run
+        .summaries
+        .iter()
+        .flat_map(|s| s.results.iter())
+        .filter(|r| r.test_number == TestCase::LoadTest.number())
+        .collect();
+    let mut points: Vec<(f64, f64)> = LATENCY_QUANTILES
+        .iter()
+        .filter_map(|(key, fraction)| {
+            let samples: Vec<f64> = load_tests
+                .iter()
+                .filter_map(|r| r.details.get(*key)?.parse().ok())
+                .collect();
+            (!samples.is_empty()).then(|| {
+                (
+                    samples.iter().sum::<f64>() / samples.len() as f64,
+                    *fraction,
+                )
+            })
+        })
+        .collect();
+    // Averaging can leave a quantile below the one before it
+    for i in 1..points.len() {
+        points[i].0 = points[i].0.max(points[i - 1].0);
+    }
+    points
+}
+
+/// Load Test requests per second, averaged over rounds
+pub fn load_test_rps(run: &StoredTestRun) -> Option<f64> {
+    let samples: Vec<f64> = run
+        .summaries
+        .iter()
+        .flat_map(|s| s.results.iter())
+        .filter(|r| r.test_number == TestCase::LoadTest.number() && r.duration_ms > 0)
+        .filter_map(|r| {
+            let requests: f64 = r.details.get("total_requests")?.parse().ok()?;
+            Some(requests / (r.duration_ms as f64 / 1000.0))
+        })
+        .collect();
+    (!samples.is_empty()).then(|| samples.iter().sum::<f64>() / samples.len() as f64)
+}
+
+/// Pass rate per test number across the rounds the test was supported
+fn pass_rates(run: &StoredTestRun) -> BTreeMap<u8, f64> {
+    let mut counts: BTreeMap<u8, (u32, u32)> = BTreeMap::new();
+    for result in run
+        .summaries
+        .iter()
+        .flat_map(|s| s.results.iter())
+        .filter(|r| !r.not_supported)
+    {
+        let entry = counts.entry(result.test_number).or_default();
+        entry.0 += u32::from(result.passed);
+        entry.1 += 1;
+    }
+    counts
+        .into_iter()
+        .map(|(test, (passed, total))| (test, passed as f64 / total as f64))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{GatewayImpl, TestResult, TestRoundSummary};
+
+    fn run(gateway: GatewayImpl, requests: u64) -> StoredTestRun {
+        let mut run = StoredTestRun::new(gateway, "10.0.0.1");
+        let results = vec![
+            TestResult::pass(TestCase::HostRouting, 20),
+            TestResult::fail(TestCase::PathRouting, 40, "404"),
+            TestResult::pass(TestCase::LoadTest, 2000).with_details(serde_json::json!({
+                "total_requests": requests,
+                "min_latency_ms": 1.0,
+                "p50_latency_ms": 4.0,
+                "p99_latency_ms": 30.0,
+                "max_latency_ms": 80.0,
+            })),
+        ];
+        run.add_round(1, &TestRoundSummary::new(1, gateway.name(), results));
+        run
+    }
+
+    #[test]
+    fn test_chart_data() {
+        let nginx = run(GatewayImpl::Nginx, 1000);
+        // Request latencies, not the 2s the Load Test took
+        assert_eq!(
+            latency_cdf(&nginx),
+            vec![(1.0, 0.0), (4.0, 0.5), (30.0, 0.99), (80.0, 1.0)]
+        );
+        assert_eq!(load_test_rps(&nginx), Some(500.0));
+
+        let rates = pass_rates(&nginx);
+        assert_eq!(rates.get(&1), Some(&1.0));
+        assert_eq!(rates.get(&2), Some(&0.0));
+
+        let empty = StoredTestRun::new(GatewayImpl::Envoy, "10.0.0.2");
+        assert_eq!(load_test_rps(&empty), None);
+        assert!(!Chart::LatencyCdf.has_data(std::slice::from_ref(&empty)));
+        assert!(!Chart::RpsBars.has_data(&[empty]));
+    }
+
+    #[test]
+    fn test_render_svg() {
+        let runs = vec![run(GatewayImpl::Nginx, 1000), run(GatewayImpl::Envoy, 1500)];
+        for chart in Chart::all() {
+            let svg = chart.render_svg(&runs).unwrap();
+            assert!(svg.starts_with("<svg"), "{}", chart.title());
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let written = export_charts(&runs, dir.path()).unwrap();
+        if cfg!(feature = "png-charts") {
+            assert_eq!(written.len(), 6);
+            assert!(dir.path().join("pass-rate-heatmap.png").exists());
+        } else {
+            assert_eq!(written.len(), 3);
+        }
+    }
+}
@@ -2,9 +2,10 @@
 //!
 //! Compare test results across different gateway implementations.
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
-use crate::results::storage::{StoredTestRun, TestStats};
+use crate::models::{BackendTopology, BEHAVIOR_DETAIL};
+use crate::results::storage::{catalog_label, StoredTestRun, TestStats};
 
 /// Comparison result between gateways
 #[derive(Clone, Debug)]
@@ -20,6 +21,12 @@ pub struct GatewayComparison {
 
     /// Summary statistics
     pub summary: ComparisonSummary,
+
+    /// Distinct test catalog versions among the compared runs
+    pub catalog_versions: Vec<String>,
+
+    /// Backend placement per gateway (runs whose backends reported identity)
+    pub backend_topology: BTreeMap<String, BackendTopology>,
 }
 
 /// Comparison for a single test across gateways
@@ -34,6 +41,10 @@ pub struct TestComparison {
     /// Results per gateway (gateway name -> stats)
     pub gateway_results: BTreeMap<String, TestComparisonResult>,
 
+    /// Gateways that do not support the test (left out of winner and
+    /// universal pass/fail)
+    pub not_supported: Vec<String>,
+
     /// Best performing gateway
     pub best_gateway: Option<String>,
 
@@ -58,6 +69,9 @@ pub struct TestComparisonResult {
 
     /// Relative performance score (higher is better)
     pub score: f64,
+
+    /// How the gateway behaved, from the latest result that recorded it
+    pub behavior: Option<String>,
 }
 
 /// Criteria for determining the winner
@@ -156,6 +170,11 @@ impl GatewayComparator {
             test_comparisons,
             rankings,
             summary,
+            catalog_versions: catalog_versions(runs.iter().map(|r| r.catalog_version)),
+            backend_topology: runs
+                .iter()
+                .filter_map(|r| Some((r.gateway.clone(), r.backend_topology()?)))
+                .collect(),
         }
     }
 
@@ -184,11 +203,23 @@ impl GatewayComparator {
             .into_iter()
             .map(|(test_name, category)| {
                 let mut gateway_results: BTreeMap<String, TestComparisonResult> = BTreeMap::new();
+                let mut not_supported = Vec::new();
 
                 for run in runs {
                     if let Some(agg) = &run.aggregate {
                         if let Some(stats) = agg.test_stats.get(&test_name) {
-                            let result = TestComparisonResult::from_stats(stats);
+                            if stats.is_not_supported() {
+                                not_supported.push(run.gateway.clone());
+                                continue;
+                            }
+                            let mut result = TestComparisonResult::from_stats(stats);
+                            result.behavior = run
+                                .summaries
+                                .iter()
+                                .rev()
+                                .flat_map(|s| &s.results)
+                                .filter(|r| r.test_name == test_name)
+                                .find_map(|r| r.details.get(BEHAVIOR_DETAIL).cloned());
                             gateway_results.insert(run.gateway.clone(), result);
                         }
                     }
@@ -201,6 +232,7 @@ impl GatewayComparator {
                     test_name,
                     category,
                     gateway_results,
+                    not_supported,
                     best_gateway,
                     winner_criteria,
                 }
@@ -364,6 +396,23 @@ impl GatewayComparator {
     }
 }
 
+impl TestComparison {
+    /// Table cell for one gateway: pass rate (with the observed behavior,
+    /// if recorded), N/A, or – when not run
+    pub fn cell(&self, gateway: &str) -> String {
+        if let Some(result) = self.gateway_results.get(gateway) {
+            match &result.behavior {
+                Some(behavior) => format!("{:.0}% · {}", result.pass_rate * 100.0, behavior),
+                None => format!("{:.0}%", result.pass_rate * 100.0),
+            }
+        } else if self.not_supported.iter().any(|g| g == gateway) {
+            "N/A".to_string()
+        } else {
+            "–".to_string()
+        }
+    }
+}
+
 impl TestComparisonResult {
     fn from_stats(stats: &TestStats) -> Self {
         // Calculate score: pass_rate * 100 - normalized_duration
@@ -380,11 +429,46 @@ impl TestComparisonResult {
             pass_count: stats.pass_count,
             fail_count: stats.fail_count,
             score,
+            behavior: None,
         }
     }
 }
 
+/// Distinct catalog version labels, sorted
+pub fn catalog_versions(versions: impl IntoIterator<Item = Option<u32>>) -> Vec<String> {
+    versions
+        .into_iter()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(catalog_label)
+        .collect()
+}
+
+/// Warning when results from different test catalog versions are mixed
+pub fn catalog_warning(versions: impl IntoIterator<Item = Option<u32>>) -> Option<String> {
+    let versions = catalog_versions(versions);
+    (versions.len() > 1).then(|| {
+        format!(
+            "results mix test catalog versions ({}); pass rates may not be comparable",
+            versions.join(", ")
+        )
+    })
+}
+
 impl GatewayComparison {
+    /// Whether the compared runs used different test catalogs
+    pub fn is_mixed_catalog(&self) -> bool {
+        self.catalog_versions.len() > 1
+    }
+
+    /// Whether the gateways were measured against different backend placements
+    pub fn is_mixed_topology(&self) -> bool {
+        let mut topologies = self.backend_topology.values();
+        topologies
+            .next()
+            .is_some_and(|first| topologies.any(|t| t.differs_from(first)))
+    }
+
     fn empty() -> Self {
         Self {
             gateways: Vec::new(),
@@ -405,6 +489,8 @@ impl GatewayComparison {
                 universal_fail: 0,
                 mixed_results: 0,
             },
+            catalog_versions: Vec::new(),
+            backend_topology: BTreeMap::new(),
         }
     }
 }
@@ -432,6 +518,13 @@ impl ComparisonFormatter {
             comparison.summary.best_overall.as_deref().unwrap_or("N/A")
         ));
 
+        if comparison.is_mixed_catalog() {
+            output.push_str(&format!(
+                "║ ⚠ Mixed test catalog versions: {:35} ║\n",
+                comparison.catalog_versions.join(", ")
+            ));
+        }
+
         output.push_str("╠════════════════════════════════════════════════════════════════════╣\n");
 
         // Rankings
@@ -465,6 +558,27 @@ impl ComparisonFormatter {
             ));
         }
 
+        if !comparison.backend_topology.is_empty() {
+            output.push_str(
+                "╠════════════════════════════════════════════════════════════════════╣\n",
+            );
+            output.push_str(
+                "║ Backend Topology:                                                  ║\n",
+            );
+            for (gateway, topology) in &comparison.backend_topology {
+                output.push_str(&format!(
+                    "║    {:14} {:49} ║\n",
+                    gateway,
+                    topology.to_string()
+                ));
+            }
+            if comparison.is_mixed_topology() {
+                output.push_str(
+                    "║ ⚠ Backend placement differs between gateways                       ║\n",
+                );
+            }
+        }
+
         output.push_str("╠════════════════════════════════════════════════════════════════════╣\n");
 
         // Test details (abbreviated)
@@ -491,6 +605,9 @@ impl ComparisonFormatter {
 #[derive(serde::Serialize)]
 struct ComparisonJson {
     gateways: Vec<String>,
+    catalog_versions: Vec<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    backend_topology: BTreeMap<String, BackendTopology>,
     summary: ComparisonSummaryJson,
     rankings: RankingsJson,
 }
@@ -522,6 +639,8 @@ impl From<&GatewayComparison> for ComparisonJson {
     fn from(c: &GatewayComparison) -> Self {
         Self {
             gateways: c.gateways.clone(),
+            catalog_versions: c.catalog_versions.clone(),
+            backend_topology: c.backend_topology.clone(),
             summary: ComparisonSummaryJson {
                 gateway_count: c.summary.gateway_count,
                 test_count: c.summary.test_count,
@@ -578,6 +697,7 @@ mod tests {
                 pass_count: 10,
                 fail_count: 0,
                 score: 95.0,
+                behavior: None,
             },
         );
         results.insert(
@@ -588,6 +708,7 @@ mod tests {
                 pass_count: 8,
                 fail_count: 2,
                 score: 85.0,
+                behavior: None,
             },
         );
 
@@ -595,4 +716,109 @@ mod tests {
         assert_eq!(winner, Some("Gateway A".to_string()));
         assert_eq!(criteria, WinnerCriteria::PassRate);
     }
+
+    #[test]
+    fn test_not_supported_cells() {
+        use crate::models::{GatewayImpl, TestCase, TestResult, TestRoundSummary};
+
+        let run = |gateway: GatewayImpl, result: TestResult| {
+            let mut run = StoredTestRun::new(gateway, "10.0.0.1");
+            run.add_round(1, &TestRoundSummary::new(1, gateway.name(), vec![result]));
+            run.calculate_aggregate();
+            run
+        };
+        let comparison = GatewayComparator::compare(&[
+            run(
+                GatewayImpl::Envoy,
+                TestResult::fail(TestCase::SessionAffinity, 20, "✗ 3 backends"),
+            ),
+            run(
+                GatewayImpl::Cilium,
+                TestResult::not_supported(TestCase::SessionAffinity, "no policy"),
+            ),
+        ]);
+
+        let test = &comparison.test_comparisons[0];
+        assert_eq!(test.cell("Cilium Gateway"), "N/A");
+        assert_eq!(test.cell("Envoy Gateway"), "0%");
+        assert_eq!(test.cell("Kong Gateway"), "–");
+        // Only Envoy ran the test, so it fails universally
+        assert_eq!(comparison.summary.universal_fail, 1);
+    }
+
+    #[test]
+    fn test_behavior_cell() {
+        use crate::models::{GatewayImpl, TestCase, TestResult, TestRoundSummary};
+
+        let mut run = StoredTestRun::new(GatewayImpl::Envoy, "10.0.0.1");
+        let result = TestResult::pass(TestCase::ForwardedHeaders, 10)
+            .with_details(serde_json::json!({ BEHAVIOR_DETAIL: "XFF appended, client IP kept" }));
+        run.add_round(1, &TestRoundSummary::new(1, "Envoy Gateway", vec![result]));
+        run.calculate_aggregate();
+
+        let comparison = GatewayComparator::compare(&[run]);
+        assert_eq!(
+            comparison.test_comparisons[0].cell("Envoy Gateway"),
+            "100% · XFF appended, client IP kept"
+        );
+    }
+
+    #[test]
+    fn test_catalog_warning() {
+        assert!(catalog_warning([Some(1), Some(1)]).is_none());
+
+        let warning = catalog_warning([Some(1), None, Some(2)]).unwrap();
+        assert!(warning.contains("unknown, v1, v2"));
+
+        let mut legacy = crate::results::storage::StoredTestRun::new(
+            crate::models::GatewayImpl::Nginx,
+            "10.0.0.1",
+        );
+        legacy.catalog_version = None;
+        let current = crate::results::storage::StoredTestRun::new(
+            crate::models::GatewayImpl::Envoy,
+            "10.0.0.2",
+        );
+        let comparison = GatewayComparator::compare(&[legacy, current]);
+        assert!(comparison.is_mixed_catalog());
+    }
+
+    #[test]
+    fn test_mixed_backend_topology() {
+        use crate::models::{BackendHits, BackendIdentity, TestCase, TestResult, TestRoundSummary};
+
+        let run = |gateway, pods: &[&str]| {
+            let backends = pods
+                .iter()
+                .map(|pod| BackendHits {
+                    backend: BackendIdentity {
+                        pod: pod.to_string(),
+                        namespace: None,
+                        service: Some("echo".to_string()),
+                        node: None,
+                    },
+                    requests: 1,
+                })
+                .collect();
+            let result = TestResult::pass(TestCase::HostRouting, 5).with_backends(backends);
+            let mut run = StoredTestRun::new(gateway, "10.0.0.1");
+            run.add_round(1, &TestRoundSummary::new(1, gateway.name(), vec![result]));
+            run.calculate_aggregate();
+            run
+        };
+
+        let same = GatewayComparator::compare(&[
+            run(crate::models::GatewayImpl::Nginx, &["a", "b"]),
+            run(crate::models::GatewayImpl::Envoy, &["c", "d"]),
+        ]);
+        assert_eq!(same.backend_topology.len(), 2);
+        assert!(!same.is_mixed_topology());
+
+        let mixed = GatewayComparator::compare(&[
+            run(crate::models::GatewayImpl::Nginx, &["a", "b"]),
+            run(crate::models::GatewayImpl::Envoy, &["c"]),
+        ]);
+        assert!(mixed.is_mixed_topology());
+        assert!(ComparisonFormatter::format_table(&mixed).contains("Backend placement differs"));
+    }
 }
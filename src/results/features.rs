@@ -0,0 +1,288 @@
+//! Feature catalog generation
+//!
+//! Builds stakeholder documentation from the test catalog itself: what each
+//! test validates, which Gateway API fields it exercises, and which gateways
+//! pass it in their latest stored run, so the docs follow actual behavior.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::models::{TestCase, TEST_CATALOG_VERSION};
+use crate::results::storage::{StoredTestResult, StoredTestRun};
+
+/// Rounds a test passed in a gateway's latest run
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct FeatureResult {
+    pub passed: usize,
+    /// Rounds the gateway supported the test (0 = not supported)
+    pub rounds: usize,
+}
+
+impl FeatureResult {
+    /// Whether the gateway provides the capability the test needs
+    pub fn is_supported(&self) -> bool {
+        self.rounds > 0
+    }
+
+    /// Table cell: ✓, ✗, N/A, or passed/rounds when results are mixed
+    pub fn label(&self) -> String {
+        if !self.is_supported() {
+            "N/A".to_string()
+        } else if self.passed == self.rounds {
+            "✓".to_string()
+        } else if self.passed == 0 {
+            "✗".to_string()
+        } else {
+            format!("~ {}/{}", self.passed, self.rounds)
+        }
+    }
+}
+
+/// One test case in the catalog
+#[derive(Clone, Debug, Serialize)]
+pub struct Feature {
+    pub number: u8,
+    pub name: &'static str,
+    pub category: String,
+    pub description: &'static str,
+    pub tags: &'static [&'static str],
+    pub api_fields: &'static [&'static str],
+    /// Gateway API release needed beyond v1.0 standard
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub requires: Vec<String>,
+    /// Results by gateway (absent = not run)
+    pub results: BTreeMap<String, FeatureResult>,
+}
+
+/// Run a catalog column is based on
+#[derive(Clone, Debug, Serialize)]
+pub struct FeatureSource {
+    pub gateway: String,
+    pub run_id: String,
+    pub started_at: DateTime<Utc>,
+    pub catalog: String,
+}
+
+/// Catalog of every test with per-gateway results
+#[derive(Clone, Debug, Serialize)]
+pub struct FeatureCatalog {
+    pub generated_at: DateTime<Utc>,
+    pub catalog_version: u32,
+    pub sources: Vec<FeatureSource>,
+    pub features: Vec<Feature>,
+}
+
+impl FeatureCatalog {
+    /// Build the catalog from the latest run of each gateway
+    pub fn build(runs: &[StoredTestRun]) -> Self {
+        let features = TestCase::all()
+            .into_iter()
+            .map(|test| {
+                let results = runs
+                    .iter()
+                    .filter_map(|run| {
+                        let outcomes: Vec<&StoredTestResult> = run
+                            .summaries
+                            .iter()
+                            .flat_map(|s| &s.results)
+                            .filter(|r| r.test_number == test.number())
+                            .collect();
+                        (!outcomes.is_empty()).then(|| {
+                            let result = FeatureResult {
+                                passed: outcomes.iter().filter(|r| r.passed).count(),
+                                rounds: outcomes.iter().filter(|r| !r.not_supported).count(),
+                            };
+                            (run.gateway.clone(), result)
+                        })
+                    })
+                    .collect();
+                Feature {
+                    number: test.number(),
+                    name: test.name(),
+                    category: test.category().to_string(),
+                    description: test.description(),
+                    tags: test.tags(),
+                    api_fields: test.api_fields(),
+                    requires: test
+                        .required_features()
+                        .iter()
+                        .map(|f| format!("{}: {}", f.name(), f.requirement()))
+                        .collect(),
+                    results,
+                }
+            })
+            .collect();
+
+        Self {
+            generated_at: Utc::now(),
+            catalog_version: TEST_CATALOG_VERSION,
+            sources: runs
+                .iter()
+                .map(|run| FeatureSource {
+                    gateway: run.gateway.clone(),
+                    run_id: run.id.clone(),
+                    started_at: run.started_at,
+                    catalog: run.catalog_label(),
+                })
+                .collect(),
+            features,
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut output = String::new();
+
+        writeln!(output, "# Gateway API Feature Catalog\n").unwrap();
+        writeln!(
+            output,
+            "Generated {} from test catalog v{}.\n",
+            self.generated_at.format("%Y-%m-%d %H:%M UTC"),
+            self.catalog_version
+        )
+        .unwrap();
+
+        // Support matrix
+        write!(output, "| # | Test |").unwrap();
+        for source in &self.sources {
+            write!(output, " {} |", source.gateway).unwrap();
+        }
+        write!(output, "\n|---|------|").unwrap();
+        for _ in &self.sources {
+            write!(output, ":---:|").unwrap();
+        }
+        writeln!(output).unwrap();
+        for feature in &self.features {
+            write!(output, "| {} | {} |", feature.number, feature.name).unwrap();
+            for source in &self.sources {
+                let cell = feature
+                    .results
+                    .get(&source.gateway)
+                    .map(FeatureResult::label)
+                    .unwrap_or_else(|| "–".to_string());
+                write!(output, " {cell} |").unwrap();
+            }
+            writeln!(output).unwrap();
+        }
+        if self.sources.is_empty() {
+            writeln!(
+                output,
+                "\n_No stored results; run tests to fill in support._"
+            )
+            .unwrap();
+        } else {
+            writeln!(
+                output,
+                "\n✓ passed every round · ✗ failed every round · ~ mixed · N/A not supported · – not run\n"
+            )
+            .unwrap();
+            writeln!(output, "Based on the latest run of each gateway:\n").unwrap();
+            for source in &self.sources {
+                writeln!(
+                    output,
+                    "- {}: run `{}` ({}, catalog {})",
+                    source.gateway,
+                    source.run_id,
+                    source.started_at.format("%Y-%m-%d"),
+                    source.catalog
+                )
+                .unwrap();
+            }
+        }
+
+        // Per-test details
+        writeln!(output, "\n## Tests").unwrap();
+        for feature in &self.features {
+            writeln!(output, "\n### {}. {}\n", feature.number, feature.name).unwrap();
+            writeln!(output, "{}.\n", feature.description).unwrap();
+            writeln!(output, "- **Category:** {}", feature.category).unwrap();
+            writeln!(output, "- **Tags:** {}", feature.tags.join(", ")).unwrap();
+            let fields: Vec<String> = feature
+                .api_fields
+                .iter()
+                .map(|f| format!("`{f}`"))
+                .collect();
+            writeln!(output, "- **Exercises:** {}", fields.join(", ")).unwrap();
+            for requirement in &feature.requires {
+                writeln!(output, "- **Requires:** {requirement}").unwrap();
+            }
+            let passing: Vec<&str> = feature
+                .results
+                .iter()
+                .filter(|(_, r)| r.is_supported() && r.passed == r.rounds)
+                .map(|(gateway, _)| gateway.as_str())
+                .collect();
+            if !self.sources.is_empty() {
+                let passing = if passing.is_empty() {
+                    "none".to_string()
+                } else {
+                    passing.join(", ")
+                };
+                writeln!(output, "- **Passing:** {passing}").unwrap();
+            }
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{GatewayImpl, TestResult, TestRoundSummary};
+    use crate::results::storage::StoredRoundSummary;
+
+    fn run(gateway: GatewayImpl, rounds: &[Vec<TestResult>]) -> StoredTestRun {
+        let mut run = StoredTestRun::new(gateway, "10.0.0.1");
+        run.summaries = rounds
+            .iter()
+            .enumerate()
+            .map(|(i, results)| {
+                let round = i as u32 + 1;
+                let summary = TestRoundSummary::new(round, gateway.name(), results.clone());
+                StoredRoundSummary::from_round_summary(round, &summary)
+            })
+            .collect();
+        run
+    }
+
+    #[test]
+    fn test_feature_catalog() {
+        let nginx = run(
+            GatewayImpl::Nginx,
+            &[
+                vec![
+                    TestResult::pass(TestCase::HostRouting, 5),
+                    TestResult::fail(TestCase::TimeoutRetry, 5, "no timeout"),
+                ],
+                vec![
+                    TestResult::pass(TestCase::HostRouting, 5),
+                    TestResult::pass(TestCase::TimeoutRetry, 5),
+                ],
+            ],
+        );
+        let catalog = FeatureCatalog::build(&[nginx]);
+        assert_eq!(catalog.features.len(), TestCase::all().len());
+
+        let host = &catalog.features[0];
+        assert_eq!(host.results["NGINX Gateway Fabric"].label(), "✓");
+        let timeout = &catalog.features[8];
+        assert_eq!(timeout.results["NGINX Gateway Fabric"].label(), "~ 1/2");
+        assert_eq!(timeout.requires.len(), 1);
+        assert!(catalog.features[1].results.is_empty());
+
+        let markdown = catalog.to_markdown();
+        assert!(markdown.contains("| # | Test | NGINX Gateway Fabric |"));
+        assert!(markdown.contains("| 1 | Host Routing | ✓ |"));
+        assert!(markdown.contains("| 2 | Path Routing | – |"));
+        assert!(markdown.contains("`HTTPRoute.spec.rules.timeouts`"));
+        assert!(catalog.to_json().unwrap().contains("\"api_fields\""));
+    }
+}
@@ -0,0 +1,382 @@
+//! Threshold gating for CI
+//!
+//! Evaluates `--fail-on` expressions such as `pass-rate<100%`, `p99>100ms`
+//! or `error-rate>0.1%` against a run summary and maps the outcome to a
+//! process exit code.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::benchmark::BenchmarkResult;
+use crate::executor::AggregateResult;
+use crate::models::TestRoundSummary;
+
+/// Exit code when at least one gate condition holds
+pub const EXIT_GATE_FAILED: i32 = 1;
+
+/// Exit code when a gate cannot be evaluated for the run
+pub const EXIT_GATE_INVALID: i32 = 2;
+
+/// Metric a gate condition is evaluated against
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GateMetric {
+    /// Test pass rate in percent
+    PassRate,
+    /// Number of failed or errored tests
+    Failed,
+    /// Number of tests that passed only on retry
+    Flaky,
+    /// Benchmark error rate in percent
+    ErrorRate,
+    /// Benchmark throughput in requests per second
+    Rps,
+    /// Mean latency in milliseconds
+    Mean,
+    P50,
+    P90,
+    P95,
+    P99,
+    P999,
+}
+
+impl GateMetric {
+    /// Parse from expression name
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "pass-rate" | "pass_rate" | "passrate" => Some(Self::PassRate),
+            "failed" | "failures" => Some(Self::Failed),
+            "flaky" | "flakes" => Some(Self::Flaky),
+            "error-rate" | "error_rate" | "errors" => Some(Self::ErrorRate),
+            "rps" | "throughput" => Some(Self::Rps),
+            "mean" | "avg" | "latency" => Some(Self::Mean),
+            "p50" | "median" => Some(Self::P50),
+            "p90" => Some(Self::P90),
+            "p95" => Some(Self::P95),
+            "p99" => Some(Self::P99),
+            "p999" | "p99.9" => Some(Self::P999),
+            _ => None,
+        }
+    }
+
+    /// Expression name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::PassRate => "pass-rate",
+            Self::Failed => "failed",
+            Self::Flaky => "flaky",
+            Self::ErrorRate => "error-rate",
+            Self::Rps => "rps",
+            Self::Mean => "mean",
+            Self::P50 => "p50",
+            Self::P90 => "p90",
+            Self::P95 => "p95",
+            Self::P99 => "p99",
+            Self::P999 => "p999",
+        }
+    }
+
+    /// Unit used when displaying values
+    pub fn unit(&self) -> &'static str {
+        match self {
+            Self::PassRate | Self::ErrorRate => "%",
+            Self::Failed | Self::Flaky => "",
+            Self::Rps => " RPS",
+            _ => "ms",
+        }
+    }
+
+    fn is_latency(&self) -> bool {
+        matches!(
+            self,
+            Self::Mean | Self::P50 | Self::P90 | Self::P95 | Self::P99 | Self::P999
+        )
+    }
+}
+
+/// Comparison operator
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GateOp {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl GateOp {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+        }
+    }
+
+    fn holds(&self, value: f64, threshold: f64) -> bool {
+        match self {
+            Self::Lt => value < threshold,
+            Self::Le => value <= threshold,
+            Self::Gt => value > threshold,
+            Self::Ge => value >= threshold,
+        }
+    }
+}
+
+/// A single `--fail-on` condition; the run fails when it holds
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GateCondition {
+    pub metric: GateMetric,
+    pub op: GateOp,
+    pub threshold: f64,
+}
+
+impl GateCondition {
+    /// Parse an expression like `p99>100ms` (usable as a clap value parser)
+    pub fn parse(expr: &str) -> Result<Self, String> {
+        let expr = expr.trim();
+        let (pos, op, len) = ["<=", ">=", "<", ">"]
+            .iter()
+            .find_map(|sym| expr.find(sym).map(|pos| (pos, *sym, sym.len())))
+            .ok_or_else(|| format!("'{expr}' has no comparison (<, <=, >, >=)"))?;
+        let op = match op {
+            "<=" => GateOp::Le,
+            ">=" => GateOp::Ge,
+            "<" => GateOp::Lt,
+            _ => GateOp::Gt,
+        };
+
+        let name = &expr[..pos];
+        let metric =
+            GateMetric::from_str(name).ok_or_else(|| format!("Unknown gate metric '{name}'"))?;
+        let threshold = parse_threshold(metric, expr[pos + len..].trim())
+            .ok_or_else(|| format!("Invalid threshold in '{expr}'"))?;
+
+        Ok(Self {
+            metric,
+            op,
+            threshold,
+        })
+    }
+
+    /// Whether the condition holds for a value (i.e. the gate trips)
+    pub fn trips(&self, value: f64) -> bool {
+        self.op.holds(value, self.threshold)
+    }
+}
+
+impl fmt::Display for GateCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{}{}{}",
+            self.metric.name(),
+            self.op.symbol(),
+            self.threshold,
+            self.metric.unit().trim()
+        )
+    }
+}
+
+/// Parse a threshold, honouring `%`, `ms` and `s` suffixes
+fn parse_threshold(metric: GateMetric, s: &str) -> Option<f64> {
+    let s = s.trim();
+    let value = if let Some(pct) = s.strip_suffix('%') {
+        pct.trim().parse::<f64>().ok()?
+    } else if let Some(ms) = s.strip_suffix("ms") {
+        ms.trim().parse::<f64>().ok()?
+    } else if let Some(secs) = s.strip_suffix('s').filter(|_| metric.is_latency()) {
+        secs.trim().parse::<f64>().ok()? * 1000.0
+    } else {
+        s.parse::<f64>().ok()?
+    };
+    value.is_finite().then_some(value)
+}
+
+/// Run summary a gate can be evaluated against
+pub trait GateSource {
+    /// Value of a metric, or None if the run does not report it
+    fn gate_value(&self, metric: GateMetric) -> Option<f64>;
+}
+
+impl GateSource for TestRoundSummary {
+    fn gate_value(&self, metric: GateMetric) -> Option<f64> {
+        match metric {
+            GateMetric::PassRate => Some(self.pass_rate()),
+            GateMetric::Failed => Some((self.failed + self.errors) as f64),
+            GateMetric::Flaky => Some(self.flaky as f64),
+            _ => None,
+        }
+    }
+}
+
+impl GateSource for AggregateResult {
+    fn gate_value(&self, metric: GateMetric) -> Option<f64> {
+        match metric {
+            GateMetric::PassRate => Some(self.overall_pass_rate),
+            GateMetric::Failed => Some(
+                self.test_stats
+                    .values()
+                    .map(|s| s.failures + s.errors)
+                    .sum::<u32>() as f64,
+            ),
+            GateMetric::Flaky => Some(self.total_flakes() as f64),
+            _ => None,
+        }
+    }
+}
+
+impl GateSource for BenchmarkResult {
+    fn gate_value(&self, metric: GateMetric) -> Option<f64> {
+        let latency = &self.metrics.latency;
+        let throughput = &self.metrics.throughput;
+        match metric {
+            GateMetric::ErrorRate => Some((1.0 - throughput.success_rate) * 100.0),
+            GateMetric::Failed => Some(throughput.failed_requests as f64),
+            GateMetric::Rps => Some(throughput.rps),
+            GateMetric::Mean => Some(latency.mean),
+            GateMetric::P50 => Some(latency.percentiles.p50),
+            GateMetric::P90 => Some(latency.percentiles.p90),
+            GateMetric::P95 => Some(latency.percentiles.p95),
+            GateMetric::P99 => Some(latency.percentiles.p99),
+            GateMetric::P999 => Some(latency.percentiles.p999),
+            GateMetric::PassRate | GateMetric::Flaky => None,
+        }
+    }
+}
+
+/// Result of evaluating one condition
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GateOutcome {
+    pub condition: GateCondition,
+    /// Measured value (None if the metric is not available)
+    pub value: Option<f64>,
+    pub tripped: bool,
+}
+
+/// Result of evaluating all `--fail-on` conditions
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct GateReport {
+    pub outcomes: Vec<GateOutcome>,
+}
+
+impl GateReport {
+    /// Evaluate conditions against a run summary
+    pub fn evaluate(conditions: &[GateCondition], source: &impl GateSource) -> Self {
+        let outcomes = conditions
+            .iter()
+            .map(|condition| {
+                let value = source.gate_value(condition.metric);
+                GateOutcome {
+                    condition: condition.clone(),
+                    value,
+                    tripped: value.is_some_and(|v| condition.trips(v)),
+                }
+            })
+            .collect();
+        Self { outcomes }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.outcomes.is_empty()
+    }
+
+    /// Whether any condition could not be evaluated
+    pub fn has_unavailable(&self) -> bool {
+        self.outcomes.iter().any(|o| o.value.is_none())
+    }
+
+    /// Whether any condition tripped
+    pub fn failed(&self) -> bool {
+        self.outcomes.iter().any(|o| o.tripped)
+    }
+
+    /// Process exit code (0 = pass, 1 = gate tripped, 2 = unevaluable gate)
+    pub fn exit_code(&self) -> i32 {
+        if self.has_unavailable() {
+            EXIT_GATE_INVALID
+        } else if self.failed() {
+            EXIT_GATE_FAILED
+        } else {
+            0
+        }
+    }
+}
+
+impl fmt::Display for GateReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Gates:")?;
+        for outcome in &self.outcomes {
+            let unit = outcome.condition.metric.unit();
+            match outcome.value {
+                Some(value) => writeln!(
+                    f,
+                    "  {} {:<24} (actual {:.2}{})",
+                    if outcome.tripped { "✗" } else { "✓" },
+                    outcome.condition.to_string(),
+                    value,
+                    unit
+                )?,
+                None => writeln!(
+                    f,
+                    "  ? {:<24} (metric not reported by this run)",
+                    outcome.condition.to_string()
+                )?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{TestCase, TestResult};
+
+    #[test]
+    fn test_parse_conditions() {
+        let c = GateCondition::parse("pass-rate<100%").unwrap();
+        assert_eq!(c.metric, GateMetric::PassRate);
+        assert_eq!(c.op, GateOp::Lt);
+        assert_eq!(c.threshold, 100.0);
+
+        let c = GateCondition::parse("p99>0.1s").unwrap();
+        assert_eq!(c.metric, GateMetric::P99);
+        assert_eq!(c.threshold, 100.0);
+
+        let c = GateCondition::parse("error-rate >= 0.1%").unwrap();
+        assert_eq!(c.op, GateOp::Ge);
+        assert_eq!(c.threshold, 0.1);
+
+        assert!(GateCondition::parse("p99=100ms").is_err());
+        assert!(GateCondition::parse("speed>1").is_err());
+        assert!(GateCondition::parse("p99>fast").is_err());
+    }
+
+    #[test]
+    fn test_gate_report_on_summary() {
+        let summary = TestRoundSummary::new(
+            1,
+            "envoy",
+            vec![
+                TestResult::pass(TestCase::HostRouting, 10),
+                TestResult::fail(TestCase::PathRouting, 10, "404"),
+            ],
+        );
+
+        let conditions = [GateCondition::parse("pass-rate<100%").unwrap()];
+        let report = GateReport::evaluate(&conditions, &summary);
+        assert!(report.failed());
+        assert_eq!(report.exit_code(), EXIT_GATE_FAILED);
+
+        let conditions = [GateCondition::parse("failed>1").unwrap()];
+        assert_eq!(GateReport::evaluate(&conditions, &summary).exit_code(), 0);
+
+        let conditions = [GateCondition::parse("flaky>0").unwrap()];
+        assert_eq!(GateReport::evaluate(&conditions, &summary).exit_code(), 0);
+
+        let conditions = [GateCondition::parse("p99>100ms").unwrap()];
+        let report = GateReport::evaluate(&conditions, &summary);
+        assert_eq!(report.exit_code(), EXIT_GATE_INVALID);
+    }
+}
@@ -0,0 +1,551 @@
+//! Response header comparison
+//!
+//! Sends identical requests to several gateways and compares the header sets
+//! they answer with: headers only some gateways return, and headers whose
+//! values differ. Functional tests pass or fail on routing; these
+//! differences (HSTS, caching headers, server tokens) are behaviour they do
+//! not look at.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use crate::http::{authority, HttpClient};
+
+/// Headers whose values change between responses; compared by presence only
+const VOLATILE: [&str; 12] = [
+    "date",
+    "age",
+    "expires",
+    "content-length",
+    "etag",
+    "last-modified",
+    "x-request-id",
+    "x-envoy-upstream-service-time",
+    "x-kong-proxy-latency",
+    "x-kong-upstream-latency",
+    "x-kong-request-id",
+    "traceparent",
+];
+
+/// Widest header value shown in text output
+const VALUE_WIDTH: usize = 32;
+
+/// What a header is about, for grouping differences
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HeaderCategory {
+    Security,
+    Caching,
+    ServerTokens,
+    Other,
+}
+
+impl HeaderCategory {
+    /// Category of a lowercase header name
+    pub fn of(name: &str) -> Self {
+        match name {
+            "strict-transport-security"
+            | "content-security-policy"
+            | "x-content-type-options"
+            | "x-frame-options"
+            | "x-xss-protection"
+            | "referrer-policy"
+            | "permissions-policy" => HeaderCategory::Security,
+            "cache-control" | "expires" | "etag" | "last-modified" | "age" | "vary" | "pragma" => {
+                HeaderCategory::Caching
+            }
+            "server" | "x-powered-by" | "via" => HeaderCategory::ServerTokens,
+            _ if ["x-envoy-", "x-kong-", "x-istio-", "x-apisix-"]
+                .iter()
+                .any(|prefix| name.starts_with(prefix)) =>
+            {
+                HeaderCategory::ServerTokens
+            }
+            _ => HeaderCategory::Other,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            HeaderCategory::Security => "security",
+            HeaderCategory::Caching => "caching",
+            HeaderCategory::ServerTokens => "server",
+            HeaderCategory::Other => "other",
+        }
+    }
+}
+
+impl fmt::Display for HeaderCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// A gateway to request, labelled for the comparison
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct HeaderTarget {
+    pub gateway: String,
+    pub ip: String,
+}
+
+impl HeaderTarget {
+    /// Parse from `gateway=ip`
+    pub fn parse(spec: &str) -> Result<Self> {
+        let (gateway, ip) = spec
+            .split_once('=')
+            .with_context(|| format!("Invalid target '{spec}', expected gateway=ip"))?;
+        Ok(Self {
+            gateway: gateway.trim().to_string(),
+            ip: ip.trim().to_string(),
+        })
+    }
+}
+
+/// What one gateway answered for one path
+#[derive(Clone, Debug, Serialize)]
+pub struct GatewayHeaders {
+    pub gateway: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    /// Lowercase header names
+    pub headers: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// How a header differs between gateways
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HeaderDiffKind {
+    /// Every gateway returns the same value (or, for volatile headers, returns it)
+    Same,
+    /// Some gateways return the header and others do not
+    Presence,
+    /// Every gateway returns the header, with different values
+    Value,
+}
+
+/// One header across gateways
+#[derive(Clone, Debug, Serialize)]
+pub struct HeaderRow {
+    pub path: String,
+    pub name: String,
+    pub category: HeaderCategory,
+    pub kind: HeaderDiffKind,
+    /// Value per gateway (None: not returned)
+    pub values: BTreeMap<String, Option<String>>,
+}
+
+/// Header sets of several gateways for the same requests
+#[derive(Clone, Debug, Serialize)]
+pub struct HeaderComparison {
+    pub gateways: Vec<String>,
+    /// Host header sent
+    pub hostname: String,
+    /// Responses per path
+    pub responses: BTreeMap<String, Vec<GatewayHeaders>>,
+}
+
+impl HeaderComparison {
+    pub fn new(gateways: Vec<String>, hostname: impl Into<String>) -> Self {
+        Self {
+            gateways,
+            hostname: hostname.into(),
+            responses: BTreeMap::new(),
+        }
+    }
+
+    pub fn add(&mut self, path: impl Into<String>, response: GatewayHeaders) {
+        self.responses
+            .entry(path.into())
+            .or_default()
+            .push(response);
+    }
+
+    /// Header rows per path, sorted by category and name; gateways that
+    /// failed to answer a path are left out of its rows
+    pub fn rows(&self, include_same: bool) -> Vec<HeaderRow> {
+        let mut rows = Vec::new();
+        for (path, responses) in &self.responses {
+            let answered: Vec<&GatewayHeaders> =
+                responses.iter().filter(|r| r.error.is_none()).collect();
+            let names: BTreeSet<&String> = answered.iter().flat_map(|r| r.headers.keys()).collect();
+            for name in names {
+                let values: BTreeMap<String, Option<String>> = answered
+                    .iter()
+                    .map(|r| (r.gateway.clone(), r.headers.get(name).cloned()))
+                    .collect();
+                let kind = if values.values().any(Option::is_none) {
+                    HeaderDiffKind::Presence
+                } else if VOLATILE.contains(&name.as_str())
+                    || values.values().collect::<BTreeSet<_>>().len() == 1
+                {
+                    HeaderDiffKind::Same
+                } else {
+                    HeaderDiffKind::Value
+                };
+                if kind != HeaderDiffKind::Same || include_same {
+                    rows.push(HeaderRow {
+                        path: path.clone(),
+                        name: name.clone(),
+                        category: HeaderCategory::of(name),
+                        kind,
+                        values,
+                    });
+                }
+            }
+        }
+        rows.sort_by(|a, b| (&a.path, a.category, &a.name).cmp(&(&b.path, b.category, &b.name)));
+        rows
+    }
+
+    /// Differences per category
+    pub fn summary(&self) -> BTreeMap<HeaderCategory, usize> {
+        let mut summary = BTreeMap::new();
+        for row in self.rows(false) {
+            *summary.entry(row.category).or_default() += 1;
+        }
+        summary
+    }
+
+    /// Matrix of headers (rows) by gateway (columns), one block per path
+    pub fn format_text(&self, include_same: bool) -> String {
+        let rows = self.rows(include_same);
+        let mut output = format!("\nResponse Header Comparison (Host: {})\n", self.hostname);
+        for (path, responses) in &self.responses {
+            output.push_str(&format!("\nGET {path}\n"));
+            let path_rows: Vec<&HeaderRow> = rows.iter().filter(|r| &r.path == path).collect();
+            let name_width = path_rows
+                .iter()
+                .map(|r| r.name.len())
+                .chain([6])
+                .max()
+                .unwrap_or(6);
+            let column = |gateway: &str| {
+                path_rows
+                    .iter()
+                    .filter_map(|r| r.values.get(gateway).cloned().flatten())
+                    .map(|v| v.len().min(VALUE_WIDTH))
+                    .chain([gateway.len(), 1])
+                    .max()
+                    .unwrap_or(1)
+            };
+            let widths: Vec<(&str, usize)> = self
+                .gateways
+                .iter()
+                .map(|g| (g.as_str(), column(g)))
+                .collect();
+
+            let mut line = format!("  {:name_width$}  {:8}", "Header", "Category");
+            for (gateway, width) in &widths {
+                line.push_str(&format!("  {gateway:width$}"));
+            }
+            output.push_str(line.trim_end());
+            output.push('\n');
+
+            let mut line = format!("  {:name_width$}  {:8}", "status", "");
+            for (gateway, width) in &widths {
+                let status = match responses.iter().find(|r| r.gateway == *gateway) {
+                    Some(GatewayHeaders {
+                        status: Some(status),
+                        ..
+                    }) => status.to_string(),
+                    Some(_) => "error".to_string(),
+                    None => "-".to_string(),
+                };
+                line.push_str(&format!("  {status:width$}"));
+            }
+            output.push_str(line.trim_end());
+            output.push('\n');
+
+            for row in &path_rows {
+                let marker = match row.kind {
+                    HeaderDiffKind::Same => ' ',
+                    HeaderDiffKind::Presence => '±',
+                    HeaderDiffKind::Value => '≠',
+                };
+                let mut line = format!(
+                    "{marker} {:name_width$}  {:8}",
+                    row.name,
+                    row.category.label()
+                );
+                for (gateway, width) in &widths {
+                    let value = match row.values.get(*gateway) {
+                        Some(Some(value)) => truncate(value, VALUE_WIDTH),
+                        Some(None) => "—".to_string(),
+                        None => "-".to_string(),
+                    };
+                    line.push_str(&format!("  {value:width$}"));
+                }
+                output.push_str(line.trim_end());
+                output.push('\n');
+            }
+            if path_rows.is_empty() {
+                output.push_str("  (no differences)\n");
+            }
+            for response in responses {
+                if let Some(error) = &response.error {
+                    output.push_str(&format!("  ✗ {}: {}\n", response.gateway, error));
+                }
+            }
+        }
+
+        let summary = self.summary();
+        if summary.is_empty() {
+            output.push_str("\n✓ All gateways return the same headers\n");
+        } else {
+            let parts: Vec<String> = summary
+                .iter()
+                .map(|(category, count)| format!("{count} {category}"))
+                .collect();
+            output.push_str(&format!(
+                "\n± returned by some gateways only, ≠ different values ({} differences: {})\n",
+                summary.values().sum::<usize>(),
+                parts.join(", ")
+            ));
+        }
+        output
+    }
+
+    /// Markdown table per path
+    pub fn format_markdown(&self, include_same: bool) -> String {
+        let rows = self.rows(include_same);
+        let mut output = format!(
+            "# Response Header Comparison\n\nHost: `{}`\n",
+            self.hostname
+        );
+        for path in self.responses.keys() {
+            output.push_str(&format!("\n## GET `{path}`\n\n"));
+            output.push_str(&format!(
+                "| Header | Category | {} |\n",
+                self.gateways.join(" | ")
+            ));
+            output.push_str(&format!(
+                "|---|---|{}\n",
+                "---|".repeat(self.gateways.len())
+            ));
+            let path_rows: Vec<&HeaderRow> = rows.iter().filter(|r| &r.path == path).collect();
+            for row in &path_rows {
+                let values: Vec<String> = self
+                    .gateways
+                    .iter()
+                    .map(|g| match row.values.get(g) {
+                        Some(Some(value)) => format!("`{}`", value.replace('|', "\\|")),
+                        Some(None) => "—".to_string(),
+                        None => "-".to_string(),
+                    })
+                    .collect();
+                output.push_str(&format!(
+                    "| `{}` | {} | {} |\n",
+                    row.name,
+                    row.category,
+                    values.join(" | ")
+                ));
+            }
+            if path_rows.is_empty() {
+                output.push_str("\nNo differences.\n");
+            }
+        }
+        output
+    }
+
+    /// Responses and differences as JSON
+    pub fn to_json(&self) -> Result<String> {
+        #[derive(Serialize)]
+        struct Report<'a> {
+            #[serde(flatten)]
+            comparison: &'a HeaderComparison,
+            differences: Vec<HeaderRow>,
+        }
+        serde_json::to_string_pretty(&Report {
+            comparison: self,
+            differences: self.rows(false),
+        })
+        .context("Failed to serialize header comparison")
+    }
+}
+
+/// Sends the same GET requests to every target
+#[derive(Clone, Debug)]
+pub struct HeaderProbe {
+    pub targets: Vec<HeaderTarget>,
+    pub paths: Vec<String>,
+    pub hostname: String,
+    pub port: u16,
+    pub https: bool,
+}
+
+impl HeaderProbe {
+    pub fn new(targets: Vec<HeaderTarget>, hostname: impl Into<String>, port: u16) -> Self {
+        Self {
+            targets,
+            paths: vec!["/".to_string()],
+            hostname: hostname.into(),
+            port,
+            https: false,
+        }
+    }
+
+    pub fn with_paths(mut self, paths: Vec<String>) -> Self {
+        if !paths.is_empty() {
+            self.paths = paths;
+        }
+        self
+    }
+
+    pub fn with_https(mut self, https: bool) -> Self {
+        self.https = https;
+        self
+    }
+
+    /// Request every path from every target
+    pub async fn run(&self, client: &HttpClient) -> HeaderComparison {
+        let scheme = if self.https { "https" } else { "http" };
+        let mut comparison = HeaderComparison::new(
+            self.targets.iter().map(|t| t.gateway.clone()).collect(),
+            &self.hostname,
+        );
+        for path in &self.paths {
+            for target in &self.targets {
+                let url = format!("{scheme}://{}{path}", authority(&target.ip, self.port));
+                let response = match client.get_with_host(&url, &self.hostname).await {
+                    Ok(response) => GatewayHeaders {
+                        gateway: target.gateway.clone(),
+                        status: Some(response.status_code),
+                        headers: response
+                            .headers
+                            .into_iter()
+                            .map(|(k, v)| (k.to_lowercase(), v))
+                            .collect(),
+                        error: None,
+                    },
+                    Err(e) => GatewayHeaders {
+                        gateway: target.gateway.clone(),
+                        status: None,
+                        headers: BTreeMap::new(),
+                        error: Some(format!("{e:#}")),
+                    },
+                };
+                comparison.add(path, response);
+            }
+        }
+        comparison
+    }
+}
+
+fn truncate(s: &str, max_len: usize) -> String {
+    if s.chars().count() <= max_len {
+        s.to_string()
+    } else {
+        format!("{}...", s.chars().take(max_len - 3).collect::<String>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(gateway: &str, headers: &[(&str, &str)]) -> GatewayHeaders {
+        GatewayHeaders {
+            gateway: gateway.to_string(),
+            status: Some(200),
+            headers: headers
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            error: None,
+        }
+    }
+
+    fn comparison() -> HeaderComparison {
+        let mut comparison = HeaderComparison::new(
+            vec!["nginx".to_string(), "envoy".to_string()],
+            "example.com",
+        );
+        comparison.add(
+            "/",
+            response(
+                "nginx",
+                &[
+                    ("server", "nginx"),
+                    ("date", "Mon, 01 Jan 2024 00:00:00 GMT"),
+                    ("strict-transport-security", "max-age=31536000"),
+                    ("content-type", "application/json"),
+                ],
+            ),
+        );
+        comparison.add(
+            "/",
+            response(
+                "envoy",
+                &[
+                    ("server", "envoy"),
+                    ("date", "Mon, 01 Jan 2024 00:00:01 GMT"),
+                    ("content-type", "application/json"),
+                    ("x-envoy-upstream-service-time", "1"),
+                ],
+            ),
+        );
+        comparison
+    }
+
+    #[test]
+    fn test_header_rows() {
+        let comparison = comparison();
+        let rows = comparison.rows(false);
+        let kind = |name: &str| rows.iter().find(|r| r.name == name).map(|r| r.kind);
+
+        assert_eq!(kind("server"), Some(HeaderDiffKind::Value));
+        assert_eq!(
+            kind("strict-transport-security"),
+            Some(HeaderDiffKind::Presence)
+        );
+        assert_eq!(
+            kind("x-envoy-upstream-service-time"),
+            Some(HeaderDiffKind::Presence)
+        );
+        // Volatile values and equal headers are not differences
+        assert_eq!(kind("date"), None);
+        assert_eq!(kind("content-type"), None);
+        assert_eq!(rows[0].category, HeaderCategory::Security);
+
+        assert_eq!(comparison.rows(true).len(), 5);
+        assert_eq!(comparison.summary()[&HeaderCategory::ServerTokens], 2);
+    }
+
+    #[test]
+    fn test_header_formats() {
+        let comparison = comparison();
+        let text = comparison.format_text(false);
+        let hsts = text
+            .lines()
+            .find(|l| l.starts_with("± strict-transport-security"))
+            .unwrap();
+        assert!(hsts.ends_with("security  max-age=31536000  —"));
+        assert!(text.contains("≠ server"));
+        assert!(text.contains("3 differences: 1 security, 2 server"));
+
+        let markdown = comparison.format_markdown(false);
+        assert!(markdown.contains("| Header | Category | nginx | envoy |"));
+        assert!(markdown.contains("| `server` | server | `nginx` | `envoy` |"));
+
+        let json: serde_json::Value = serde_json::from_str(&comparison.to_json().unwrap()).unwrap();
+        assert_eq!(json["differences"].as_array().unwrap().len(), 3);
+        assert_eq!(json["responses"]["/"][0]["gateway"], "nginx");
+    }
+
+    #[test]
+    fn test_header_target() {
+        let target = HeaderTarget::parse("envoy=10.0.0.2").unwrap();
+        assert_eq!(target.gateway, "envoy");
+        assert_eq!(target.ip, "10.0.0.2");
+        assert!(HeaderTarget::parse("envoy").is_err());
+        assert_eq!(
+            HeaderCategory::of("x-kong-upstream-latency"),
+            HeaderCategory::ServerTokens
+        );
+        assert_eq!(HeaderCategory::of("vary"), HeaderCategory::Caching);
+    }
+}
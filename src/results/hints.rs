@@ -0,0 +1,242 @@
+//! Failure hints
+//!
+//! Maps common failure signatures in test messages (connection refused,
+//! 404 on every route, TLS handshake failures, 429 everywhere, ...) to an
+//! actionable hint. Some signatures only mean something across a round, so
+//! hints are computed for all failed tests of a round at once.
+
+use std::fmt;
+
+use crate::models::TestCase;
+
+/// Failed tests of one kind needed before a status counts as round-wide
+const WIDESPREAD: usize = 2;
+
+/// Recognised cause of a test failure
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FailureSignature {
+    ConnectionRefused,
+    DnsFailure,
+    Timeout,
+    TlsHandshake,
+    /// 404 on several tests: nothing is routed at all
+    NotFoundEverywhere,
+    /// 404 on one test: its route is missing
+    RouteMissing,
+    /// 429 on several tests other than Rate Limiting
+    RateLimitedEverywhere,
+    /// 502/503: the gateway has no healthy backend
+    NoHealthyBackend,
+}
+
+impl FailureSignature {
+    /// What to check or run next
+    pub fn hint(&self) -> &'static str {
+        match self {
+            FailureSignature::ConnectionRefused => {
+                "Nothing is listening on the gateway address: check that the Gateway is \
+                 Programmed and that --ip and the ports match its listeners \
+                 (`deploy health <gateway> --ip <ip>`)"
+            }
+            FailureSignature::DnsFailure => {
+                "Hostname did not resolve: pass `--resolve HOST=IP` or add it to /etc/hosts"
+            }
+            FailureSignature::Timeout => {
+                "Requests time out: the gateway address is unreachable (LoadBalancer IP \
+                 pending, firewall) or the backend hangs (`wait http://<ip>/`)"
+            }
+            FailureSignature::TlsHandshake => {
+                "TLS handshake failed: check that the HTTPS listener's certificate Secret \
+                 exists and covers --hostname"
+            }
+            FailureSignature::NotFoundEverywhere => {
+                "404 on every route: GatewayClass or Gateway not Accepted, or routes not \
+                 attached (`deploy preflight <gateway> --ip <ip>`)"
+            }
+            FailureSignature::RouteMissing => {
+                "The test's route is missing or not attached: apply it \
+                 (`deploy manifest --bundle <dir>`) and check its status conditions"
+            }
+            FailureSignature::RateLimitedEverywhere => {
+                "429 on several tests: a rate limit applies to every route, e.g. a policy \
+                 left from an earlier run (`cleanup`)"
+            }
+            FailureSignature::NoHealthyBackend => {
+                "The gateway has no healthy backend: deploy the echo backends \
+                 (`deploy manifest --bundle <dir>`) and wait until they are ready"
+            }
+        }
+    }
+
+    /// Signatures a single message shows, before round-wide rules apply
+    fn detect(test_case: Option<TestCase>, message: &str) -> Vec<Self> {
+        let lower = message.to_lowercase();
+        let mut found = Vec::new();
+        if lower.contains("connection refused") {
+            found.push(FailureSignature::ConnectionRefused);
+        }
+        if ["dns error", "failed to lookup", "name or service not known"]
+            .iter()
+            .any(|s| lower.contains(s))
+        {
+            found.push(FailureSignature::DnsFailure);
+        }
+        if lower.contains("timeout after") || lower.contains("timed out") {
+            found.push(FailureSignature::Timeout);
+        }
+        // Backend TLS reports its own handshake failures as 502/503
+        if test_case != Some(TestCase::BackendTls)
+            && ["tls error", "handshake", "invalid peer certificate"]
+                .iter()
+                .any(|s| lower.contains(s))
+        {
+            found.push(FailureSignature::TlsHandshake);
+        }
+        if mentions_status(&lower, 404) {
+            found.push(FailureSignature::RouteMissing);
+        }
+        // The Rate Limiting test expects its own 429s
+        if test_case != Some(TestCase::RateLimiting) && mentions_status(&lower, 429) {
+            found.push(FailureSignature::RateLimitedEverywhere);
+        }
+        if mentions_status(&lower, 502) || mentions_status(&lower, 503) {
+            found.push(FailureSignature::NoHealthyBackend);
+        }
+        found
+    }
+}
+
+impl fmt::Display for FailureSignature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.hint())
+    }
+}
+
+/// Whether a message reports an HTTP status, as the tests word it
+fn mentions_status(message: &str, code: u16) -> bool {
+    [
+        format!("status {code}"),
+        format!("returned {code}"),
+        format!("returns {code}"),
+        format!("({code})"),
+    ]
+    .iter()
+    .any(|pattern| message.contains(pattern.as_str()))
+}
+
+/// Hint for one failed test
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FailureHint {
+    pub test_number: u8,
+    pub signature: FailureSignature,
+}
+
+impl FailureHint {
+    pub fn hint(&self) -> &'static str {
+        self.signature.hint()
+    }
+}
+
+/// Hints for the failed tests of one round, given as (test number, message)
+///
+/// A 404 or 429 seen by several tests becomes a round-wide hint on each of
+/// them; a single 429 outside the Rate Limiting test gets no hint.
+pub fn failure_hints<'a>(failures: impl IntoIterator<Item = (u8, &'a str)>) -> Vec<FailureHint> {
+    let detected: Vec<(u8, Vec<FailureSignature>)> = failures
+        .into_iter()
+        .map(|(number, message)| {
+            (
+                number,
+                FailureSignature::detect(TestCase::from_number(number), message),
+            )
+        })
+        .collect();
+    let count = |signature: FailureSignature| {
+        detected
+            .iter()
+            .filter(|(_, found)| found.contains(&signature))
+            .count()
+    };
+    let not_found_everywhere = count(FailureSignature::RouteMissing) >= WIDESPREAD;
+    let rate_limited_everywhere = count(FailureSignature::RateLimitedEverywhere) >= WIDESPREAD;
+
+    let mut hints = Vec::new();
+    for (test_number, found) in detected {
+        let mut signatures: Vec<FailureSignature> = found
+            .into_iter()
+            .filter_map(|signature| match signature {
+                FailureSignature::RouteMissing if not_found_everywhere => {
+                    Some(FailureSignature::NotFoundEverywhere)
+                }
+                FailureSignature::RateLimitedEverywhere if !rate_limited_everywhere => None,
+                signature => Some(signature),
+            })
+            .collect();
+        signatures.sort();
+        signatures.dedup();
+        hints.extend(signatures.into_iter().map(|signature| FailureHint {
+            test_number,
+            signature,
+        }));
+    }
+    hints
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signatures(hints: &[FailureHint], test_number: u8) -> Vec<FailureSignature> {
+        hints
+            .iter()
+            .filter(|h| h.test_number == test_number)
+            .map(|h| h.signature)
+            .collect()
+    }
+
+    #[test]
+    fn test_single_failure_signatures() {
+        let hints = failure_hints([
+            (
+                1,
+                "✗ app1.example.com failed: Connection refused to http://10.0.0.1/",
+            ),
+            (4, "✗ TLS error: invalid peer certificate: UnknownIssuer"),
+            (
+                6,
+                "✗ Backend unreachable (status 503), possible TLS handshake failure",
+            ),
+            (2, "✗ /api/v1 expected api-v1 but got status 404"),
+        ]);
+        assert_eq!(signatures(&hints, 1), [FailureSignature::ConnectionRefused]);
+        assert_eq!(signatures(&hints, 4), [FailureSignature::TlsHandshake]);
+        assert_eq!(signatures(&hints, 6), [FailureSignature::NoHealthyBackend]);
+        assert_eq!(signatures(&hints, 2), [FailureSignature::RouteMissing]);
+    }
+
+    #[test]
+    fn test_round_wide_signatures() {
+        let hints = failure_hints([
+            (1, "✗ app1.example.com expected app1 but got status 404"),
+            (2, "✗ /api/v1 expected api-v1 but got status 404"),
+            (8, "Rate limited (429): 0"),
+            (11, "✗ Request failed with status 429"),
+        ]);
+        assert_eq!(
+            signatures(&hints, 1),
+            [FailureSignature::NotFoundEverywhere]
+        );
+        assert_eq!(
+            signatures(&hints, 2),
+            [FailureSignature::NotFoundEverywhere]
+        );
+        // One test besides Rate Limiting is not "everywhere"
+        assert!(signatures(&hints, 8).is_empty());
+        assert!(signatures(&hints, 11).is_empty());
+    }
+
+    #[test]
+    fn test_latency_is_not_a_status() {
+        assert!(failure_hints([(3, "✓ X-Version: v1 -> version-v1 (404ms)")]).is_empty());
+    }
+}
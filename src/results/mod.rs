@@ -4,10 +4,23 @@
 
 #![allow(dead_code)]
 
+mod bundle;
+mod charts;
 mod compare;
+mod features;
+mod gate;
+mod headers;
+mod hints;
+mod openmetrics;
 mod report;
 mod storage;
 
-pub use compare::{ComparisonFormatter, GatewayComparator};
+pub use charts::export_charts;
+pub use compare::{catalog_warning, ComparisonFormatter, GatewayComparator};
+pub use features::FeatureCatalog;
+pub use gate::{GateCondition, GateReport, GateSource};
+pub use headers::{HeaderProbe, HeaderTarget};
+pub use hints::failure_hints;
+pub use openmetrics::{OpenMetrics, OPENMETRICS_EXTENSION};
 pub use report::{ReportFormat, ReportGenerator};
-pub use storage::ResultsStorage;
+pub use storage::{generate_run_id, Annotation, ResultsStorage, StoredTestRun, TestRunConfig};
@@ -0,0 +1,350 @@
+//! OpenMetrics snapshots
+//!
+//! Renders the summary metrics of a test run or benchmark in the OpenMetrics
+//! text format (<https://openmetrics.io>), so Prometheus-compatible scrapers
+//! and offline tooling can ingest runs without parsing the JSON results.
+
+use std::fmt::Write;
+
+use crate::benchmark::BenchmarkResult;
+use crate::results::storage::StoredTestRun;
+
+/// Metric name prefix
+const PREFIX: &str = "gateway_poc";
+
+/// File extension of snapshots
+pub const OPENMETRICS_EXTENSION: &str = "prom";
+
+/// Metric family type
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum MetricType {
+    Gauge,
+    Counter,
+}
+
+impl MetricType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            MetricType::Gauge => "gauge",
+            MetricType::Counter => "counter",
+        }
+    }
+}
+
+/// One metric family and its samples
+#[derive(Clone, Debug)]
+pub struct Family {
+    name: String,
+    kind: MetricType,
+    help: String,
+    unit: Option<&'static str>,
+    samples: Vec<(Vec<(String, String)>, f64)>,
+}
+
+impl Family {
+    /// Declare the unit (the family name must end in `_<unit>`)
+    pub fn unit(&mut self, unit: &'static str) -> &mut Self {
+        self.unit = Some(unit);
+        self
+    }
+
+    /// Add a sample
+    pub fn sample(&mut self, labels: &[(&str, &str)], value: f64) -> &mut Self {
+        let labels = labels
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        self.samples.push((labels, value));
+        self
+    }
+}
+
+/// OpenMetrics exposition builder
+#[derive(Clone, Debug, Default)]
+pub struct OpenMetrics {
+    families: Vec<Family>,
+}
+
+impl OpenMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gauge family `gateway_poc_<name>`, created on first use
+    pub fn gauge(&mut self, name: &str, help: &str) -> &mut Family {
+        self.family(name, MetricType::Gauge, help)
+    }
+
+    /// Counter family `gateway_poc_<name>` (samples get the `_total` suffix)
+    pub fn counter(&mut self, name: &str, help: &str) -> &mut Family {
+        self.family(name, MetricType::Counter, help)
+    }
+
+    fn family(&mut self, name: &str, kind: MetricType, help: &str) -> &mut Family {
+        let name = format!("{PREFIX}_{name}");
+        let index = match self.families.iter().position(|f| f.name == name) {
+            Some(index) => index,
+            None => {
+                self.families.push(Family {
+                    name,
+                    kind,
+                    help: help.to_string(),
+                    unit: None,
+                    samples: Vec::new(),
+                });
+                self.families.len() - 1
+            }
+        };
+        &mut self.families[index]
+    }
+
+    /// Render in the OpenMetrics text format
+    pub fn render(&self) -> String {
+        let mut output = String::new();
+        for family in self.families.iter().filter(|f| !f.samples.is_empty()) {
+            writeln!(output, "# TYPE {} {}", family.name, family.kind.as_str()).unwrap();
+            if let Some(unit) = family.unit {
+                writeln!(output, "# UNIT {} {}", family.name, unit).unwrap();
+            }
+            writeln!(output, "# HELP {} {}", family.name, escape(&family.help)).unwrap();
+
+            let suffix = match family.kind {
+                MetricType::Counter => "_total",
+                MetricType::Gauge => "",
+            };
+            for (labels, value) in &family.samples {
+                output.push_str(&family.name);
+                output.push_str(suffix);
+                if !labels.is_empty() {
+                    let labels: Vec<String> = labels
+                        .iter()
+                        .map(|(k, v)| format!("{k}=\"{}\"", escape(v)))
+                        .collect();
+                    write!(output, "{{{}}}", labels.join(",")).unwrap();
+                }
+                writeln!(output, " {}", format_value(*value)).unwrap();
+            }
+        }
+        output.push_str("# EOF\n");
+        output
+    }
+
+    /// Snapshot of a stored test run
+    pub fn from_test_run(run: &StoredTestRun) -> Self {
+        let mut metrics = Self::new();
+        let gateway = run.gateway.as_str();
+        let run_id = run.id.as_str();
+        let labels = [("gateway", gateway), ("run_id", run_id)];
+
+        metrics
+            .gauge("test_run_start_timestamp_seconds", "Run start time")
+            .unit("seconds")
+            .sample(&labels, run.started_at.timestamp() as f64);
+        metrics
+            .gauge("test_rounds", "Rounds executed")
+            .sample(&labels, run.rounds as f64);
+        metrics
+            .gauge("test_run_partial", "1 if the run stopped early")
+            .sample(&labels, if run.partial.is_some() { 1.0 } else { 0.0 });
+        metrics
+            .gauge(
+                "gateway_pod_crashes",
+                "Gateway pod restarts or losses during the run",
+            )
+            .sample(&labels, run.crashes().count() as f64);
+
+        if let Some(agg) = &run.aggregate {
+            metrics
+                .gauge("test_pass_ratio", "Average pass rate across rounds")
+                .sample(&labels, agg.avg_pass_rate);
+            metrics
+                .gauge("test_round_duration_seconds", "Average round duration")
+                .unit("seconds")
+                .sample(&labels, agg.avg_duration_ms as f64 / 1000.0);
+
+            for (test, stats) in &agg.test_stats {
+                let labels = [("gateway", gateway), ("run_id", run_id), ("test", test)];
+                metrics
+                    .gauge("test_case_pass_ratio", "Pass rate of a test across rounds")
+                    .sample(&labels, stats.pass_rate);
+                metrics
+                    .gauge(
+                        "test_case_duration_seconds",
+                        "Average duration of a test across rounds",
+                    )
+                    .unit("seconds")
+                    .sample(&labels, stats.avg_duration_ms as f64 / 1000.0);
+                metrics
+                    .gauge("test_case_flaky", "Passes of a test that needed a retry")
+                    .sample(&labels, stats.flaky_count as f64);
+            }
+        }
+
+        metrics
+    }
+
+    /// Snapshot of benchmark results
+    pub fn from_benchmarks(results: &[BenchmarkResult]) -> Self {
+        let mut metrics = Self::new();
+        for result in results {
+            let m = &result.metrics;
+            let gateway = result.config.gateway.name();
+            let labels = [("gateway", gateway)];
+
+            metrics
+                .gauge("benchmark_start_timestamp_seconds", "Benchmark start time")
+                .unit("seconds")
+                .sample(&labels, result.start_time as f64);
+            metrics
+                .gauge("benchmark_duration_seconds", "Measured duration")
+                .unit("seconds")
+                .sample(&labels, m.throughput.duration_secs);
+            metrics
+                .counter("benchmark_requests", "Requests sent")
+                .sample(&labels, m.throughput.total_requests as f64);
+            metrics
+                .counter("benchmark_failed_requests", "Failed requests")
+                .sample(&labels, m.throughput.failed_requests as f64);
+            metrics
+                .gauge("benchmark_throughput_rps", "Requests per second")
+                .sample(&labels, m.throughput.rps);
+            metrics
+                .gauge("benchmark_success_ratio", "Successful request ratio")
+                .sample(&labels, m.throughput.success_rate);
+
+            let percentiles = &m.latency.percentiles;
+            for (quantile, ms) in [
+                ("0.5", percentiles.p50),
+                ("0.9", percentiles.p90),
+                ("0.95", percentiles.p95),
+                ("0.99", percentiles.p99),
+                ("0.999", percentiles.p999),
+            ] {
+                metrics
+                    .gauge("benchmark_latency_seconds", "Request latency quantiles")
+                    .unit("seconds")
+                    .sample(&[("gateway", gateway), ("quantile", quantile)], ms / 1000.0);
+            }
+            metrics
+                .gauge("benchmark_latency_mean_seconds", "Mean request latency")
+                .unit("seconds")
+                .sample(&labels, m.latency.mean / 1000.0);
+
+            if let Some(usage) = result.resources.as_ref().filter(|r| !r.is_empty()) {
+                metrics
+                    .gauge("benchmark_gateway_cpu_cores", "Average gateway CPU usage")
+                    .sample(&labels, usage.avg_cpu_cores());
+                metrics
+                    .gauge(
+                        "benchmark_gateway_memory_peak_bytes",
+                        "Peak gateway memory working set",
+                    )
+                    .unit("bytes")
+                    .sample(&labels, usage.peak_memory_bytes() as f64);
+            }
+            metrics
+                .gauge(
+                    "gateway_pod_crashes",
+                    "Gateway pod restarts or losses during the run",
+                )
+                .sample(&labels, result.crashes.len() as f64);
+        }
+        metrics
+    }
+}
+
+/// Escape a label value or help text
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmark::{BenchmarkConfig, Metrics};
+    use crate::models::{GatewayImpl, TestCase, TestResult, TestRoundSummary};
+
+    #[test]
+    fn test_render() {
+        let mut metrics = OpenMetrics::new();
+        metrics
+            .counter("requests", "Requests sent")
+            .sample(&[("gateway", "nginx")], 10.0);
+        metrics
+            .gauge("latency_seconds", "Latency")
+            .unit("seconds")
+            .sample(&[("test", "Say \"hi\"")], 0.25)
+            .sample(&[], f64::INFINITY);
+        metrics.gauge("unused", "No samples");
+
+        assert_eq!(
+            metrics.render(),
+            "# TYPE gateway_poc_requests counter\n\
+             # HELP gateway_poc_requests Requests sent\n\
+             gateway_poc_requests_total{gateway=\"nginx\"} 10\n\
+             # TYPE gateway_poc_latency_seconds gauge\n\
+             # UNIT gateway_poc_latency_seconds seconds\n\
+             # HELP gateway_poc_latency_seconds Latency\n\
+             gateway_poc_latency_seconds{test=\"Say \\\"hi\\\"\"} 0.25\n\
+             gateway_poc_latency_seconds +Inf\n\
+             # EOF\n"
+        );
+    }
+
+    #[test]
+    fn test_snapshots() {
+        let mut run = StoredTestRun::new(GatewayImpl::Envoy, "10.0.0.1");
+        let results = vec![
+            TestResult::pass(TestCase::HostRouting, 250),
+            TestResult::fail(TestCase::PathRouting, 500, "404"),
+        ];
+        run.add_round(1, &TestRoundSummary::new(1, "envoy", results));
+        run.calculate_aggregate();
+
+        let text = OpenMetrics::from_test_run(&run).render();
+        assert!(text.contains(&format!(
+            "gateway_poc_test_pass_ratio{{gateway=\"Envoy Gateway\",run_id=\"{}\"}} 0.5\n",
+            run.id
+        )));
+        assert!(text.contains("test=\"Host Routing\"} 0.25\n"));
+        assert!(text.ends_with("# EOF\n"));
+
+        let mut result = BenchmarkResult {
+            config: BenchmarkConfig::new(GatewayImpl::Nginx, "10.0.0.1"),
+            metrics: Metrics::default(),
+            start_time: 0,
+            end_time: 10,
+            warmup_performed: false,
+            paused_secs: 0.0,
+            workers: Vec::new(),
+            resources: None,
+            crashes: Vec::new(),
+        };
+        result.metrics.throughput.total_requests = 1000;
+        result.metrics.latency.percentiles.p99 = 12.5;
+        let text = OpenMetrics::from_benchmarks(&[result]).render();
+        assert!(text.contains(
+            "gateway_poc_benchmark_requests_total{gateway=\"NGINX Gateway Fabric\"} 1000\n"
+        ));
+        assert!(text
+            .contains("gateway_poc_benchmark_latency_seconds{gateway=\"NGINX Gateway Fabric\",quantile=\"0.99\"} 0.0125\n"));
+        assert_eq!(
+            text.matches("# TYPE gateway_poc_benchmark_latency_seconds")
+                .count(),
+            1
+        );
+    }
+}
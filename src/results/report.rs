@@ -6,8 +6,9 @@ use std::fmt::Write;
 
 use chrono::{DateTime, Utc};
 
+use crate::results::charts::Chart;
 use crate::results::compare::{GatewayComparator, GatewayComparison};
-use crate::results::storage::{ResultsStorage, StoredTestRun};
+use crate::results::storage::{Annotation, ResultsStorage, StoredTestRun};
 
 /// Report generator
 pub struct ReportGenerator {
@@ -33,9 +34,9 @@ impl ReportGenerator {
     pub fn comparison_report(&self, runs: &[StoredTestRun], format: ReportFormat) -> String {
         let comparison = GatewayComparator::compare(runs);
         match format {
-            ReportFormat::Text => self.format_text_comparison(&comparison),
-            ReportFormat::Markdown => self.format_markdown_comparison(&comparison),
-            ReportFormat::Html => self.format_html_comparison(&comparison),
+            ReportFormat::Text => self.format_text_comparison(&comparison, runs),
+            ReportFormat::Markdown => self.format_markdown_comparison(&comparison, runs),
+            ReportFormat::Html => self.format_html_comparison(&comparison, runs),
         }
     }
 
@@ -53,6 +54,20 @@ impl ReportGenerator {
         writeln!(output, "Started: {}", format_datetime(&run.started_at)).unwrap();
         writeln!(output, "Completed: {}", format_datetime(&run.completed_at)).unwrap();
         writeln!(output, "Rounds: {}", run.rounds).unwrap();
+        writeln!(output, "Test Catalog: {}", run.catalog_label()).unwrap();
+        if let Some(profile) = &run.config.profile {
+            writeln!(output, "Profile: {profile}").unwrap();
+        }
+        if let Some(reason) = &run.partial {
+            writeln!(output, "Partial Run: {reason}").unwrap();
+        }
+        for (round, crash) in run.crashes() {
+            writeln!(
+                output,
+                "Tainted Round {round}: gateway pod crashed: {crash}"
+            )
+            .unwrap();
+        }
         writeln!(output).unwrap();
 
         // Aggregate stats
@@ -88,9 +103,9 @@ impl ReportGenerator {
             for (name, stats) in &agg.test_stats {
                 writeln!(
                     output,
-                    "{:<25} {:>7.1}% {:>8} {:>8} {:>8}",
+                    "{:<25} {:>8} {:>8} {:>8} {:>8}",
                     truncate(name, 25),
-                    stats.pass_rate * 100.0,
+                    stats.pass_rate_label(),
                     stats.avg_duration_ms,
                     stats.min_duration_ms,
                     stats.max_duration_ms
@@ -114,6 +129,19 @@ impl ReportGenerator {
             .unwrap();
         }
 
+        let hints = run.failure_hints();
+        if !hints.is_empty() {
+            writeln!(output, "\n{:-^70}", " Failure Hints ").unwrap();
+            for (test, hint) in &hints {
+                writeln!(output, "{test}: {hint}").unwrap();
+            }
+        }
+
+        if !run.annotations.is_empty() {
+            writeln!(output, "\n{:-^70}", " Reviewer Notes ").unwrap();
+            write_text_notes(&mut output, &run.annotations);
+        }
+
         writeln!(output, "\n{:=^70}", "").unwrap();
         output
     }
@@ -137,6 +165,20 @@ impl ReportGenerator {
         )
         .unwrap();
         writeln!(output, "| Rounds | {} |", run.rounds).unwrap();
+        writeln!(output, "| Test Catalog | {} |", run.catalog_label()).unwrap();
+        if let Some(profile) = &run.config.profile {
+            writeln!(output, "| Profile | {profile} |").unwrap();
+        }
+        if let Some(reason) = &run.partial {
+            writeln!(output, "| Partial Run | {reason} |").unwrap();
+        }
+        for (round, crash) in run.crashes() {
+            writeln!(
+                output,
+                "| Tainted Round {round} | Gateway pod crashed: {crash} |"
+            )
+            .unwrap();
+        }
 
         // Aggregate stats
         if let Some(agg) = &run.aggregate {
@@ -179,9 +221,9 @@ impl ReportGenerator {
             for (name, stats) in &agg.test_stats {
                 writeln!(
                     output,
-                    "| {} | {:.1}% | {} | {} | {} |",
+                    "| {} | {} | {} | {} | {} |",
                     name,
-                    stats.pass_rate * 100.0,
+                    stats.pass_rate_label(),
                     stats.avg_duration_ms,
                     stats.min_duration_ms,
                     stats.max_duration_ms
@@ -199,6 +241,20 @@ impl ReportGenerator {
             writeln!(output, "- **Duration:** {}ms\n", summary.duration_ms).unwrap();
         }
 
+        let hints = run.failure_hints();
+        if !hints.is_empty() {
+            writeln!(output, "## Failure Hints\n").unwrap();
+            for (test, hint) in &hints {
+                writeln!(output, "- **{test}:** {hint}").unwrap();
+            }
+            writeln!(output).unwrap();
+        }
+
+        if !run.annotations.is_empty() {
+            writeln!(output, "## Reviewer Notes\n").unwrap();
+            write_markdown_notes(&mut output, &run.annotations);
+        }
+
         output
     }
 
@@ -222,6 +278,7 @@ impl ReportGenerator {
         tr:hover {{ background: #f8f9fa; }}
         .pass {{ color: #28a745; font-weight: bold; }}
         .fail {{ color: #dc3545; font-weight: bold; }}
+        .na {{ color: #999; }}
         .stat-card {{ display: inline-block; background: #f8f9fa; padding: 20px; margin: 10px; border-radius: 8px; min-width: 150px; text-align: center; }}
         .stat-value {{ font-size: 24px; font-weight: bold; color: #007bff; }}
         .stat-label {{ color: #666; font-size: 14px; }}
@@ -270,7 +327,9 @@ impl ReportGenerator {
             .unwrap();
 
             for (name, stats) in &agg.test_stats {
-                let pass_class = if stats.pass_rate >= 0.99 {
+                let pass_class = if stats.is_not_supported() {
+                    "na"
+                } else if stats.pass_rate >= 0.99 {
                     "pass"
                 } else {
                     "fail"
@@ -280,14 +339,14 @@ impl ReportGenerator {
                     r#"
             <tr>
                 <td>{}</td>
-                <td class="{}">{:.1}%</td>
+                <td class="{}">{}</td>
                 <td>{} / {}</td>
                 <td>{}ms</td>
                 <td>{}ms / {}ms</td>
             </tr>"#,
                     name,
                     pass_class,
-                    stats.pass_rate * 100.0,
+                    stats.pass_rate_label(),
                     stats.pass_count,
                     stats.fail_count,
                     stats.avg_duration_ms,
@@ -300,6 +359,26 @@ impl ReportGenerator {
             writeln!(output, "        </table>").unwrap();
         }
 
+        let hints = run.failure_hints();
+        if !hints.is_empty() {
+            writeln!(output, "\n        <h2>Failure Hints</h2>\n        <ul>").unwrap();
+            for (test, hint) in &hints {
+                writeln!(
+                    output,
+                    "            <li><strong>{}:</strong> {}</li>",
+                    escape_html(test),
+                    escape_html(hint)
+                )
+                .unwrap();
+            }
+            writeln!(output, "        </ul>").unwrap();
+        }
+
+        if !run.annotations.is_empty() {
+            writeln!(output, "\n        <h2>Reviewer Notes</h2>").unwrap();
+            write_html_notes(&mut output, &run.annotations);
+        }
+
         writeln!(
             output,
             r#"
@@ -330,11 +409,24 @@ impl ReportGenerator {
         output
     }
 
-    fn format_text_comparison(&self, comparison: &GatewayComparison) -> String {
-        crate::results::compare::ComparisonFormatter::format_table(comparison)
+    fn format_text_comparison(
+        &self,
+        comparison: &GatewayComparison,
+        runs: &[StoredTestRun],
+    ) -> String {
+        let mut output = crate::results::compare::ComparisonFormatter::format_table(comparison);
+        for run in runs.iter().filter(|r| !r.annotations.is_empty()) {
+            writeln!(output, "\nReviewer notes - {}:", run.gateway).unwrap();
+            write_text_notes(&mut output, &run.annotations);
+        }
+        output
     }
 
-    fn format_markdown_comparison(&self, comparison: &GatewayComparison) -> String {
+    fn format_markdown_comparison(
+        &self,
+        comparison: &GatewayComparison,
+        runs: &[StoredTestRun],
+    ) -> String {
         let mut output = String::new();
 
         writeln!(output, "# Gateway API Comparison Report\n").unwrap();
@@ -373,6 +465,31 @@ impl ReportGenerator {
         )
         .unwrap();
 
+        if comparison.is_mixed_catalog() {
+            writeln!(
+                output,
+                "\n> **Warning:** results mix test catalog versions ({}); pass rates may not be comparable.",
+                comparison.catalog_versions.join(", ")
+            )
+            .unwrap();
+        }
+
+        if !comparison.backend_topology.is_empty() {
+            writeln!(output, "\n## Backend Topology\n").unwrap();
+            writeln!(output, "| Gateway | Backends |").unwrap();
+            writeln!(output, "|---------|----------|").unwrap();
+            for (gateway, topology) in &comparison.backend_topology {
+                writeln!(output, "| {gateway} | {topology} |").unwrap();
+            }
+            if comparison.is_mixed_topology() {
+                writeln!(
+                    output,
+                    "\n> **Warning:** backend placement differs between gateways; differences may come from the backends rather than the gateway."
+                )
+                .unwrap();
+            }
+        }
+
         writeln!(output, "\n## Rankings by Pass Rate\n").unwrap();
         writeln!(output, "| Rank | Gateway | Pass Rate |").unwrap();
         writeln!(output, "|------|---------|-----------|").unwrap();
@@ -406,6 +523,31 @@ impl ReportGenerator {
             writeln!(output, "| {gateway} | {wins} |").unwrap();
         }
 
+        if !comparison.test_comparisons.is_empty() {
+            writeln!(output, "\n## Results by Test\n").unwrap();
+            write!(output, "| Test |").unwrap();
+            for gateway in &comparison.gateways {
+                write!(output, " {gateway} |").unwrap();
+            }
+            write!(output, "\n|------|").unwrap();
+            for _ in &comparison.gateways {
+                write!(output, ":---:|").unwrap();
+            }
+            writeln!(output).unwrap();
+            for test in &comparison.test_comparisons {
+                write!(output, "| {} |", test.test_name).unwrap();
+                for gateway in &comparison.gateways {
+                    write!(output, " {} |", test.cell(gateway)).unwrap();
+                }
+                writeln!(output).unwrap();
+            }
+            writeln!(
+                output,
+                "\nPass rate per test; N/A: the gateway does not support the test and it does not count against its pass rate."
+            )
+            .unwrap();
+        }
+
         writeln!(output, "\n## Test Result Distribution\n").unwrap();
         writeln!(
             output,
@@ -426,10 +568,24 @@ impl ReportGenerator {
         )
         .unwrap();
 
+        let annotated: Vec<&StoredTestRun> =
+            runs.iter().filter(|r| !r.annotations.is_empty()).collect();
+        if !annotated.is_empty() {
+            writeln!(output, "\n## Reviewer Notes\n").unwrap();
+            for run in annotated {
+                writeln!(output, "### {}\n", run.gateway).unwrap();
+                write_markdown_notes(&mut output, &run.annotations);
+            }
+        }
+
         output
     }
 
-    fn format_html_comparison(&self, comparison: &GatewayComparison) -> String {
+    fn format_html_comparison(
+        &self,
+        comparison: &GatewayComparison,
+        runs: &[StoredTestRun],
+    ) -> String {
         let mut output = String::new();
 
         writeln!(output, r#"<!DOCTYPE html>
@@ -449,8 +605,10 @@ impl ReportGenerator {
         tr:hover {{ background: #f8f9fa; }}
         .winner {{ background: #d4edda !important; }}
         .rank-1 {{ font-weight: bold; color: #28a745; }}
+        .na {{ color: #999; }}
         .charts {{ display: flex; flex-wrap: wrap; gap: 20px; }}
         .chart {{ flex: 1; min-width: 300px; background: #f8f9fa; padding: 20px; border-radius: 8px; }}
+        .chart svg {{ width: 100%; height: auto; }}
         .bar {{ height: 20px; background: #007bff; border-radius: 4px; margin: 5px 0; }}
     </style>
 </head>
@@ -527,10 +685,38 @@ impl ReportGenerator {
             .unwrap();
         }
 
+        writeln!(output, "        </table>").unwrap();
+
+        if !comparison.test_comparisons.is_empty() {
+            write!(
+                output,
+                "\n        <h2>Results by Test</h2>\n        <table>\n            <tr><th>Test</th>"
+            )
+            .unwrap();
+            for gateway in &comparison.gateways {
+                write!(output, "<th>{}</th>", escape_html(gateway)).unwrap();
+            }
+            writeln!(output, "</tr>").unwrap();
+            for test in &comparison.test_comparisons {
+                write!(
+                    output,
+                    "            <tr><td>{}</td>",
+                    escape_html(&test.test_name)
+                )
+                .unwrap();
+                for gateway in &comparison.gateways {
+                    let cell = test.cell(gateway);
+                    let class = if cell == "N/A" { " class=\"na\"" } else { "" };
+                    write!(output, "<td{class}>{}</td>", escape_html(&cell)).unwrap();
+                }
+                writeln!(output, "</tr>").unwrap();
+            }
+            writeln!(output, "        </table>").unwrap();
+        }
+
         writeln!(
             output,
-            r#"        </table>
-
+            r#"
         <h2>Test Statistics</h2>
         <table>
             <tr>
@@ -540,16 +726,42 @@ impl ReportGenerator {
             <tr><td>Universal Pass</td><td>{}</td></tr>
             <tr><td>Universal Fail</td><td>{}</td></tr>
             <tr><td>Mixed Results</td><td>{}</td></tr>
-        </table>
-    </div>
-</body>
-</html>"#,
+        </table>"#,
             comparison.summary.universal_pass,
             comparison.summary.universal_fail,
             comparison.summary.mixed_results
         )
         .unwrap();
 
+        let charts: Vec<String> = Chart::all()
+            .into_iter()
+            .filter(|c| c.has_data(runs))
+            .filter_map(|c| c.render_svg(runs).ok())
+            .collect();
+        if !charts.is_empty() {
+            writeln!(
+                output,
+                "\n        <h2>Charts</h2>\n        <div class=\"charts\">"
+            )
+            .unwrap();
+            for svg in charts {
+                writeln!(output, "            <div class=\"chart\">{svg}</div>").unwrap();
+            }
+            writeln!(output, "        </div>").unwrap();
+        }
+
+        for run in runs.iter().filter(|r| !r.annotations.is_empty()) {
+            writeln!(
+                output,
+                "\n        <h2>Reviewer Notes - {}</h2>",
+                escape_html(&run.gateway)
+            )
+            .unwrap();
+            write_html_notes(&mut output, &run.annotations);
+        }
+
+        writeln!(output, "    </div>\n</body>\n</html>").unwrap();
+
         output
     }
 }
@@ -581,6 +793,62 @@ impl ReportFormat {
     }
 }
 
+fn write_text_notes(output: &mut String, annotations: &[Annotation]) {
+    for annotation in annotations {
+        writeln!(
+            output,
+            "{}  {}",
+            annotation.created_at.format("%Y-%m-%d"),
+            annotation
+        )
+        .unwrap();
+    }
+}
+
+fn write_markdown_notes(output: &mut String, annotations: &[Annotation]) {
+    for annotation in annotations {
+        write!(
+            output,
+            "- **{}:** {}",
+            annotation.subject(),
+            annotation.note
+        )
+        .unwrap();
+        if let Some(author) = &annotation.author {
+            write!(output, " — _{author}_").unwrap();
+        }
+        writeln!(output).unwrap();
+    }
+    writeln!(output).unwrap();
+}
+
+fn write_html_notes(output: &mut String, annotations: &[Annotation]) {
+    writeln!(output, "        <ul>").unwrap();
+    for annotation in annotations {
+        let author = annotation
+            .author
+            .as_deref()
+            .map(|a| format!(" <em>({})</em>", escape_html(a)))
+            .unwrap_or_default();
+        writeln!(
+            output,
+            "            <li><strong>{}:</strong> {}{}</li>",
+            escape_html(&annotation.subject()),
+            escape_html(&annotation.note),
+            author
+        )
+        .unwrap();
+    }
+    writeln!(output, "        </ul>").unwrap();
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
 fn format_datetime(dt: &DateTime<Utc>) -> String {
     dt.format("%Y-%m-%d %H:%M:%S UTC").to_string()
 }
@@ -614,6 +882,48 @@ mod tests {
         assert!(ReportFormat::from_str("unknown").is_none());
     }
 
+    #[test]
+    fn test_report_notes() {
+        let mut run = StoredTestRun::new(crate::models::GatewayImpl::Kong, "10.0.0.1");
+        run.annotate(Annotation::new(Some(8), "Kong needs <plugin> install"));
+
+        let generator = ReportGenerator::new(ResultsStorage::new("."));
+        let markdown = generator.gateway_report(&run, ReportFormat::Markdown);
+        assert!(markdown.contains("- **Test 8: Rate Limiting:** Kong needs <plugin> install"));
+
+        let html = generator.gateway_report(&run, ReportFormat::Html);
+        assert!(html.contains("Kong needs &lt;plugin&gt; install"));
+    }
+
+    #[test]
+    fn test_report_hints() {
+        use crate::models::{TestCase, TestResult, TestRoundSummary};
+
+        let mut run = StoredTestRun::new(crate::models::GatewayImpl::Envoy, "10.0.0.1");
+        let results = vec![
+            TestResult::fail(
+                TestCase::HostRouting,
+                5,
+                "✗ app1 expected app1 but got status 404",
+            ),
+            TestResult::fail(TestCase::PathRouting, 5, "✗ /api/v1 returned 404"),
+        ];
+        for round in 1..=2 {
+            run.add_round(
+                round,
+                &TestRoundSummary::new(round, "envoy", results.clone()),
+            );
+        }
+        assert_eq!(run.failure_hints().len(), 2);
+
+        let generator = ReportGenerator::new(ResultsStorage::new("."));
+        let text = generator.gateway_report(&run, ReportFormat::Text);
+        assert!(text.contains("Failure Hints"));
+        assert!(text.contains("Host Routing: 404 on every route"));
+        let markdown = generator.gateway_report(&run, ReportFormat::Markdown);
+        assert!(markdown.contains("- **Path Routing:** 404 on every route"));
+    }
+
     #[test]
     fn test_truncate() {
         assert_eq!(truncate("short", 10), "short");
@@ -6,12 +6,32 @@ use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
+use std::fmt;
 use std::fs::{self, File};
 use std::io::{BufReader, BufWriter};
 use std::path::{Path, PathBuf};
 use tracing::{debug, info};
 
-use crate::models::{GatewayImpl, TestResult, TestRoundSummary, TestStatus};
+use crate::benchmark::BenchmarkResult;
+use crate::chaos::ChaosResult;
+use crate::models::{
+    BackendHits, BackendTopology, GatewayImpl, GatewaySetup, HttpTraffic, PodCrash, SoakReport,
+    TestCase, TestResult, TestRoundSummary, TestStatus, TEST_CATALOG_VERSION,
+};
+use crate::results::hints::{failure_hints, FailureSignature};
+use crate::results::openmetrics::{OpenMetrics, OPENMETRICS_EXTENSION};
+
+/// Subdirectory of benchmark snapshots (not a gateway)
+const BENCHMARKS_DIR: &str = "benchmarks";
+
+/// Subdirectory of post-install configuration dumps (not a gateway)
+const SETUPS_DIR: &str = "setups";
+
+/// Subdirectory of replay transcripts (not a gateway)
+const REPLAYS_DIR: &str = "replays";
+
+/// Subdirectory of chaos experiment results (not a gateway)
+const CHAOS_DIR: &str = "chaos";
 
 /// Stored test run containing all results
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -45,6 +65,83 @@ pub struct StoredTestRun {
 
     /// Environment info
     pub environment: EnvironmentInfo,
+
+    /// Test catalog version (absent in runs stored before versioning)
+    #[serde(default)]
+    pub catalog_version: Option<u32>,
+
+    /// Reviewer notes added after the run
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub annotations: Vec<Annotation>,
+
+    /// Why the run stopped early (None = complete)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partial: Option<String>,
+
+    /// Gateway configuration captured at install time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gateway_setup: Option<GatewaySetup>,
+
+    /// Time-bucketed results of a soak run (only its latest round is kept
+    /// in `summaries`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub soak: Option<SoakReport>,
+}
+
+/// Reviewer note attached to a stored run
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Annotation {
+    /// Test case number (None = the run as a whole)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test_number: Option<u8>,
+
+    /// Note text
+    pub note: String,
+
+    /// Reviewer name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+
+    /// Timestamp when the note was added
+    pub created_at: DateTime<Utc>,
+}
+
+impl Annotation {
+    /// Create a note on a test (or the whole run)
+    pub fn new(test_number: Option<u8>, note: impl Into<String>) -> Self {
+        Self {
+            test_number,
+            note: note.into(),
+            author: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn with_author(mut self, author: Option<String>) -> Self {
+        self.author = author.filter(|a| !a.trim().is_empty());
+        self
+    }
+
+    /// What the note is about ("Test 8: Rate Limiting" or "Run")
+    pub fn subject(&self) -> String {
+        match self.test_number {
+            Some(n) => match TestCase::from_number(n) {
+                Some(test_case) => test_case.to_string(),
+                None => format!("Test {n}"),
+            },
+            None => "Run".to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Annotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.subject(), self.note)?;
+        if let Some(author) = &self.author {
+            write!(f, " ({author})")?;
+        }
+        Ok(())
+    }
 }
 
 /// Stored round summary
@@ -56,15 +153,23 @@ pub struct StoredRoundSummary {
     /// Total tests run
     pub total: usize,
 
-    /// Tests passed
+    /// Tests passed (including flaky)
     pub passed: usize,
 
+    /// Tests that passed only on retry
+    #[serde(default)]
+    pub flaky: usize,
+
     /// Tests failed
     pub failed: usize,
 
     /// Tests skipped
     pub skipped: usize,
 
+    /// Tests the gateway does not support (excluded from the pass rate)
+    #[serde(default)]
+    pub not_supported: usize,
+
     /// Pass rate (0.0 - 1.0)
     pub pass_rate: f64,
 
@@ -73,6 +178,14 @@ pub struct StoredRoundSummary {
 
     /// Individual test results
     pub results: Vec<StoredTestResult>,
+
+    /// Gateway pod crashes during the round (results are tainted)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub crashes: Vec<PodCrash>,
+
+    /// HTTP traffic sent during the round
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub traffic: Option<HttpTraffic>,
 }
 
 /// Stored test result
@@ -90,6 +203,14 @@ pub struct StoredTestResult {
     /// Whether test passed
     pub passed: bool,
 
+    /// Whether the test passed only on retry
+    #[serde(default)]
+    pub flaky: bool,
+
+    /// Whether the gateway lacks the capability the test needs (N/A)
+    #[serde(default)]
+    pub not_supported: bool,
+
     /// Duration in milliseconds
     pub duration_ms: u64,
 
@@ -101,6 +222,10 @@ pub struct StoredTestResult {
 
     /// Additional details
     pub details: BTreeMap<String, String>,
+
+    /// Backend pods that served the test
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub backends: Vec<BackendHits>,
 }
 
 /// Aggregate statistics across all rounds
@@ -125,6 +250,50 @@ pub struct AggregateStats {
     pub test_stats: BTreeMap<String, TestStats>,
 }
 
+impl AggregateStats {
+    /// Statistics of a soak run, which keeps counters instead of rounds
+    fn from_soak(report: &SoakReport) -> Self {
+        let test_stats = report
+            .tests
+            .iter()
+            .map(|(number, stats)| {
+                let name = TestCase::from_number(*number)
+                    .map(|t| t.name().to_string())
+                    .unwrap_or_else(|| format!("Test {number}"));
+                let applicable = stats.runs() + stats.skips;
+                let total = applicable + stats.not_supported;
+                let stats = TestStats {
+                    pass_count: stats.passes,
+                    fail_count: applicable - stats.passes,
+                    flaky_count: stats.flakes,
+                    not_supported_count: stats.not_supported,
+                    pass_rate: if applicable > 0 {
+                        stats.passes as f64 / applicable as f64
+                    } else {
+                        0.0
+                    },
+                    avg_duration_ms: stats
+                        .total_duration_ms
+                        .checked_div(total as u64)
+                        .unwrap_or(0),
+                    min_duration_ms: stats.min_duration_ms,
+                    max_duration_ms: stats.max_duration_ms,
+                };
+                (name, stats)
+            })
+            .collect();
+
+        Self {
+            avg_pass_rate: report.avg_pass_rate() / 100.0,
+            min_pass_rate: report.min_pass_rate / 100.0,
+            max_pass_rate: report.max_pass_rate / 100.0,
+            avg_duration_ms: report.total_duration_ms / report.rounds.max(1) as u64,
+            total_duration_ms: report.total_duration_ms,
+            test_stats,
+        }
+    }
+}
+
 /// Statistics for a single test across rounds
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TestStats {
@@ -134,7 +303,15 @@ pub struct TestStats {
     /// Number of times failed
     pub fail_count: u32,
 
-    /// Pass rate
+    /// Number of passes that needed a retry
+    #[serde(default)]
+    pub flaky_count: u32,
+
+    /// Rounds the gateway reported the test as not supported
+    #[serde(default)]
+    pub not_supported_count: u32,
+
+    /// Pass rate over the rounds the test was supported
     pub pass_rate: f64,
 
     /// Average duration
@@ -147,6 +324,22 @@ pub struct TestStats {
     pub max_duration_ms: u64,
 }
 
+impl TestStats {
+    /// Whether the gateway never supported the test
+    pub fn is_not_supported(&self) -> bool {
+        self.pass_count + self.fail_count == 0 && self.not_supported_count > 0
+    }
+
+    /// Pass rate as a percentage, or N/A when not supported
+    pub fn pass_rate_label(&self) -> String {
+        if self.is_not_supported() {
+            "N/A".to_string()
+        } else {
+            format!("{:.1}%", self.pass_rate * 100.0)
+        }
+    }
+}
+
 /// Test run configuration
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TestRunConfig {
@@ -167,6 +360,10 @@ pub struct TestRunConfig {
 
     /// Concurrency level
     pub concurrency: usize,
+
+    /// Test profile the run was configured from (`test --profile`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
 }
 
 /// Environment information
@@ -197,6 +394,7 @@ impl Default for TestRunConfig {
             timeout_secs: 30,
             parallel: false,
             concurrency: 4,
+            profile: None,
         }
     }
 }
@@ -227,9 +425,111 @@ impl StoredTestRun {
             aggregate: None,
             config: TestRunConfig::default(),
             environment: EnvironmentInfo::default(),
+            catalog_version: Some(TEST_CATALOG_VERSION),
+            annotations: Vec::new(),
+            partial: None,
+            gateway_setup: None,
+            soak: None,
         }
     }
 
+    /// Mark the run as stopped early
+    pub fn mark_partial(&mut self, reason: impl Into<String>) {
+        self.partial = Some(reason.into());
+    }
+
+    /// Attach a reviewer note
+    pub fn annotate(&mut self, annotation: Annotation) {
+        self.annotations.push(annotation);
+    }
+
+    /// Gateway pod crashes with the round they tainted
+    pub fn crashes(&self) -> impl Iterator<Item = (u32, &PodCrash)> {
+        self.summaries
+            .iter()
+            .flat_map(|s| s.crashes.iter().map(move |c| (s.round, c)))
+    }
+
+    /// Backend placement across all rounds of the run
+    pub fn backend_topology(&self) -> Option<BackendTopology> {
+        BackendTopology::from_hits(
+            self.summaries
+                .iter()
+                .flat_map(|s| &s.results)
+                .flat_map(|r| &r.backends),
+        )
+    }
+
+    /// Whether any round of the run executed the given test
+    pub fn has_test(&self, test_number: u8) -> bool {
+        self.summaries
+            .iter()
+            .flat_map(|s| &s.results)
+            .any(|r| r.test_number == test_number)
+    }
+
+    /// Implementation the run tested (None if no longer known)
+    pub fn implementation(&self) -> Option<GatewayImpl> {
+        GatewayImpl::all()
+            .into_iter()
+            .find(|g| g.name() == self.gateway)
+            .or_else(|| GatewayImpl::from_str(&self.gateway))
+    }
+
+    /// Hints for failed tests, per round and then deduplicated across
+    /// rounds, as (test name, hint) ordered by test number
+    pub fn failure_hints(&self) -> Vec<(String, &'static str)> {
+        let mut hints: Vec<(u8, FailureSignature)> = self
+            .summaries
+            .iter()
+            .flat_map(|s| {
+                failure_hints(
+                    s.results
+                        .iter()
+                        .filter(|r| !r.passed && !r.not_supported)
+                        .map(|r| (r.test_number, r.error.as_deref().unwrap_or(""))),
+                )
+            })
+            .map(|h| (h.test_number, h.signature))
+            .collect();
+        hints.sort();
+        hints.dedup();
+        hints
+            .into_iter()
+            .map(|(number, signature)| {
+                let name = TestCase::from_number(number)
+                    .map(|t| t.name().to_string())
+                    .unwrap_or_else(|| format!("Test {number}"));
+                (name, signature.hint())
+            })
+            .collect()
+    }
+
+    /// Numbers of the tests that did not pass in some round, ascending
+    pub fn failed_tests(&self) -> Vec<u8> {
+        let mut failed: Vec<u8> = self
+            .summaries
+            .iter()
+            .flat_map(|s| &s.results)
+            .filter(|r| !r.passed && !r.not_supported)
+            .map(|r| r.test_number)
+            .chain(self.soak.iter().flat_map(|soak| {
+                soak.tests
+                    .iter()
+                    .filter(|(_, s)| s.failures + s.errors > 0)
+                    .map(|(number, _)| *number)
+            }))
+            .collect();
+        failed.sort_unstable();
+        failed.dedup();
+        failed
+    }
+
+    /// Catalog version label ("v1", or "unknown" for legacy runs)
+    pub fn catalog_label(&self) -> String {
+        catalog_label(self.catalog_version)
+    }
+
     /// Set configuration
     pub fn with_config(mut self, config: TestRunConfig) -> Self {
         self.config = config;
@@ -244,6 +544,18 @@ impl StoredTestRun {
         self.completed_at = Utc::now();
     }
 
+    /// Replace the rounds with a soak report and the latest round it ran
+    pub fn record_soak(&mut self, report: &SoakReport, latest: Option<&TestRoundSummary>) {
+        self.summaries = latest
+            .map(|s| StoredRoundSummary::from_round_summary(s.round, s))
+            .into_iter()
+            .collect();
+        self.rounds = report.rounds;
+        self.completed_at = report.updated_at;
+        self.aggregate = (report.rounds > 0).then(|| AggregateStats::from_soak(report));
+        self.soak = Some(report.clone());
+    }
+
     /// Calculate aggregate statistics
     pub fn calculate_aggregate(&mut self) {
         if self.summaries.is_empty() {
@@ -252,7 +564,7 @@ impl StoredTestRun {
 
         let mut pass_rates: Vec<f64> = Vec::new();
         let mut durations: Vec<u64> = Vec::new();
-        let mut test_results: BTreeMap<String, Vec<(bool, u64)>> = BTreeMap::new();
+        let mut test_results: BTreeMap<String, Vec<&StoredTestResult>> = BTreeMap::new();
 
         for summary in &self.summaries {
             pass_rates.push(summary.pass_rate);
@@ -262,7 +574,7 @@ impl StoredTestRun {
                 test_results
                     .entry(result.test_name.clone())
                     .or_default()
-                    .push((result.passed, result.duration_ms));
+                    .push(result);
             }
         }
 
@@ -274,11 +586,18 @@ impl StoredTestRun {
 
         let mut test_stats: BTreeMap<String, TestStats> = BTreeMap::new();
         for (name, results) in test_results {
-            let pass_count = results.iter().filter(|(p, _)| *p).count() as u32;
-            let fail_count = results.len() as u32 - pass_count;
-            let pass_rate = pass_count as f64 / results.len() as f64;
-
-            let durs: Vec<u64> = results.iter().map(|(_, d)| *d).collect();
+            let not_supported_count = results.iter().filter(|r| r.not_supported).count() as u32;
+            let applicable = results.len() as u32 - not_supported_count;
+            let pass_count = results.iter().filter(|r| r.passed).count() as u32;
+            let fail_count = applicable - pass_count;
+            let flaky_count = results.iter().filter(|r| r.flaky).count() as u32;
+            let pass_rate = if applicable > 0 {
+                pass_count as f64 / applicable as f64
+            } else {
+                0.0
+            };
+
+            let durs: Vec<u64> = results.iter().map(|r| r.duration_ms).collect();
             let avg_dur = durs.iter().sum::<u64>() / durs.len() as u64;
             let min_dur = *durs.iter().min().unwrap_or(&0);
             let max_dur = *durs.iter().max().unwrap_or(&0);
@@ -288,6 +607,8 @@ impl StoredTestRun {
                 TestStats {
                     pass_count,
                     fail_count,
+                    flaky_count,
+                    not_supported_count,
                     pass_rate,
                     avg_duration_ms: avg_dur,
                     min_duration_ms: min_dur,
@@ -316,8 +637,8 @@ impl StoredRoundSummary {
             .map(StoredTestResult::from_test_result)
             .collect();
 
-        let pass_rate = if summary.total > 0 {
-            summary.passed as f64 / summary.total as f64
+        let pass_rate = if summary.applicable() > 0 {
+            summary.passed as f64 / summary.applicable() as f64
         } else {
             0.0
         };
@@ -326,11 +647,15 @@ impl StoredRoundSummary {
             round,
             total: summary.total,
             passed: summary.passed,
+            flaky: summary.flaky,
             failed: summary.failed,
             skipped: summary.skipped,
+            not_supported: summary.not_supported,
             pass_rate,
             duration_ms: summary.total_duration_ms,
             results,
+            crashes: summary.crashes.clone(),
+            traffic: summary.traffic.clone(),
         }
     }
 }
@@ -342,17 +667,39 @@ impl StoredTestResult {
             test_number: result.test_case.number(),
             test_name: result.test_case.name().to_string(),
             category: result.test_case.category().to_string(),
-            passed: result.status == TestStatus::Pass,
+            passed: result.status.is_success(),
+            flaky: result.status == TestStatus::Flaky,
+            not_supported: result.status == TestStatus::NotSupported,
             duration_ms: result.duration_ms,
             status_code: None,
             error: result.message.clone(),
-            details: BTreeMap::new(),
+            details: result
+                .details
+                .as_ref()
+                .and_then(|d| d.as_object())
+                .map(|d| {
+                    d.iter()
+                        .filter(|(_, v)| !v.is_null())
+                        .map(|(k, v)| match v {
+                            serde_json::Value::String(s) => (k.clone(), s.clone()),
+                            v => (k.clone(), v.to_string()),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+            backends: result.backends.clone(),
         }
     }
 }
 
+/// Read a stored chaos experiment result
+fn read_chaos(path: &Path) -> Result<ChaosResult> {
+    let file = File::open(path).context("Failed to open chaos result")?;
+    serde_json::from_reader(BufReader::new(file)).context("Failed to parse chaos result")
+}
+
 /// Generate unique run ID
-fn generate_run_id() -> String {
+pub fn generate_run_id() -> String {
     let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
     let random: u32 = rand::random::<u32>() % 10000;
     format!("{timestamp}_{random:04}")
@@ -393,10 +740,15 @@ impl ResultsStorage {
     }
 
     /// Get path for a specific run
-    fn run_path(&self, gateway: &str, run_id: &str) -> PathBuf {
+    pub(super) fn run_path(&self, gateway: &str, run_id: &str) -> PathBuf {
         self.gateway_dir(gateway).join(format!("{run_id}.json"))
     }
 
+    /// Directory of benchmark snapshots
+    pub(super) fn benchmarks_dir(&self) -> PathBuf {
+        self.base_dir.join(BENCHMARKS_DIR)
+    }
+
     /// Save a test run
     pub fn save(&self, run: &StoredTestRun) -> Result<PathBuf> {
         let gateway_dir = self.gateway_dir(&run.gateway);
@@ -407,11 +759,111 @@ impl ResultsStorage {
         let writer = BufWriter::new(file);
 
         serde_json::to_writer_pretty(writer, run).context("Failed to write results")?;
+        fs::write(
+            path.with_extension(OPENMETRICS_EXTENSION),
+            OpenMetrics::from_test_run(run).render(),
+        )
+        .context("Failed to write OpenMetrics snapshot")?;
 
         info!("Saved test results to {}", path.display());
         Ok(path)
     }
 
+    /// Path of a gateway's latest configuration dump
+    fn setup_path(&self, gateway: &str) -> PathBuf {
+        self.base_dir
+            .join(SETUPS_DIR)
+            .join(format!("{}.json", gateway.to_lowercase()))
+    }
+
+    /// Transcript file for a replay of a run's failures (directory created)
+    pub fn replay_transcript(&self, run_id: &str) -> Result<PathBuf> {
+        let dir = self.base_dir.join(REPLAYS_DIR);
+        fs::create_dir_all(&dir)?;
+        Ok(dir.join(format!("{run_id}.log")))
+    }
+
+    /// Save a gateway's configuration dump, replacing the previous one
+    pub fn save_setup(&self, setup: &GatewaySetup) -> Result<PathBuf> {
+        let path = self.setup_path(&setup.gateway);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(setup)?)
+            .context("Failed to write gateway setup")?;
+        Ok(path)
+    }
+
+    /// Latest configuration dump of a gateway, if one was captured
+    pub fn load_setup(&self, gateway: &str) -> Result<Option<GatewaySetup>> {
+        let path = self.setup_path(gateway);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let file = File::open(&path).context("Failed to open gateway setup")?;
+        serde_json::from_reader(BufReader::new(file))
+            .map(Some)
+            .context("Failed to parse gateway setup")
+    }
+
+    /// Save an OpenMetrics snapshot of benchmark results
+    pub fn save_benchmark_metrics(&self, results: &[BenchmarkResult]) -> Result<PathBuf> {
+        let dir = self.benchmarks_dir();
+        fs::create_dir_all(&dir)?;
+
+        let path = dir.join(format!("{}.{OPENMETRICS_EXTENSION}", generate_run_id()));
+        fs::write(&path, OpenMetrics::from_benchmarks(results).render())
+            .context("Failed to write OpenMetrics snapshot")?;
+        Ok(path)
+    }
+
+    /// Save a chaos experiment's result
+    pub fn save_chaos(&self, result: &ChaosResult) -> Result<PathBuf> {
+        let dir = self.base_dir.join(CHAOS_DIR);
+        fs::create_dir_all(&dir)?;
+
+        let path = dir.join(format!("{}.json", result.id));
+        fs::write(&path, serde_json::to_string_pretty(result)?)
+            .context("Failed to write chaos result")?;
+        info!("Saved chaos result to {}", path.display());
+        Ok(path)
+    }
+
+    /// Load a chaos experiment's result
+    pub fn load_chaos(&self, id: &str) -> Result<ChaosResult> {
+        let path = self.base_dir.join(CHAOS_DIR).join(format!("{id}.json"));
+        if !path.exists() {
+            anyhow::bail!(
+                "Chaos experiment {id} not found in {}",
+                self.base_dir.display()
+            );
+        }
+        read_chaos(&path)
+    }
+
+    /// All chaos experiment results, newest first
+    pub fn list_chaos(&self) -> Result<Vec<ChaosResult>> {
+        let dir = self.base_dir.join(CHAOS_DIR);
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut results = Vec::new();
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.extension().map(|e| e == "json").unwrap_or(false) {
+                match read_chaos(&path) {
+                    Ok(result) => results.push(result),
+                    Err(e) => {
+                        debug!("Failed to load {}: {}", path.display(), e);
+                    }
+                }
+            }
+        }
+        results.sort_by_key(|r| std::cmp::Reverse(r.started_at));
+        Ok(results)
+    }
+
     /// Load a test run
     pub fn load(&self, gateway: &str, run_id: &str) -> Result<StoredTestRun> {
         let path = self.run_path(gateway, run_id);
@@ -425,6 +877,17 @@ impl ResultsStorage {
         Ok(run)
     }
 
+    /// Find a run by ID across all gateways
+    pub fn find(&self, run_id: &str) -> Result<StoredTestRun> {
+        for gateway in self.list_gateways()? {
+            let path = self.run_path(&gateway, run_id);
+            if path.exists() {
+                return self.load_from_path(&path);
+            }
+        }
+        anyhow::bail!("Run {run_id} not found in {}", self.base_dir.display())
+    }
+
     /// Load all runs for a gateway
     pub fn load_gateway(&self, gateway: &str) -> Result<Vec<StoredTestRun>> {
         let gateway_dir = self.gateway_dir(gateway);
@@ -448,6 +911,7 @@ impl ResultsStorage {
         }
 
         // Sort by timestamp
+        #[allow(clippy::unnecessary_sort_by)]
         runs.sort_by(|a, b| b.started_at.cmp(&a.started_at));
         Ok(runs)
     }
@@ -470,7 +934,9 @@ impl ResultsStorage {
             let entry = entry?;
             if entry.file_type()?.is_dir() {
                 if let Some(name) = entry.file_name().to_str() {
-                    gateways.push(name.to_string());
+                    if ![BENCHMARKS_DIR, SETUPS_DIR, REPLAYS_DIR, CHAOS_DIR].contains(&name) {
+                        gateways.push(name.to_string());
+                    }
                 }
             }
         }
@@ -503,11 +969,13 @@ impl ResultsStorage {
                             .as_ref()
                             .map(|a| a.avg_pass_rate)
                             .unwrap_or(0.0),
+                        catalog_version: run.catalog_version,
                     });
                 }
             }
         }
 
+        #[allow(clippy::unnecessary_sort_by)]
         runs.sort_by(|a, b| b.started_at.cmp(&a.started_at));
         Ok(runs)
     }
@@ -525,6 +993,10 @@ impl ResultsStorage {
             fs::remove_file(&path)?;
             info!("Deleted results: {}", path.display());
         }
+        let metrics = path.with_extension(OPENMETRICS_EXTENSION);
+        if metrics.exists() {
+            fs::remove_file(&metrics)?;
+        }
         Ok(())
     }
 
@@ -596,6 +1068,14 @@ pub struct RunInfo {
     pub started_at: DateTime<Utc>,
     pub rounds: u32,
     pub pass_rate: f64,
+    pub catalog_version: Option<u32>,
+}
+
+/// Format a catalog version for display
+pub fn catalog_label(version: Option<u32>) -> String {
+    version
+        .map(|v| format!("v{v}"))
+        .unwrap_or_else(|| "unknown".to_string())
 }
 
 /// Export format
@@ -637,8 +1117,167 @@ mod tests {
     fn test_stored_test_run() {
         let run = StoredTestRun::new(GatewayImpl::Nginx, "10.0.0.1");
         assert_eq!(run.gateway, "NGINX Gateway Fabric");
+        assert_eq!(run.implementation(), Some(GatewayImpl::Nginx));
         assert_eq!(run.gateway_ip, "10.0.0.1");
         assert_eq!(run.rounds, 0);
+        assert_eq!(run.catalog_version, Some(TEST_CATALOG_VERSION));
+    }
+
+    #[test]
+    fn test_failed_tests() {
+        let result = |number: u8, passed: bool| StoredTestResult {
+            test_number: number,
+            test_name: format!("Test {number}"),
+            category: "Routing".to_string(),
+            passed,
+            flaky: false,
+            not_supported: false,
+            duration_ms: 10,
+            status_code: None,
+            error: None,
+            details: BTreeMap::new(),
+            backends: Vec::new(),
+        };
+        let round = |results: Vec<StoredTestResult>| StoredRoundSummary {
+            round: 1,
+            total: results.len(),
+            passed: 0,
+            flaky: 0,
+            failed: 0,
+            skipped: 0,
+            not_supported: 0,
+            pass_rate: 0.0,
+            duration_ms: 0,
+            results,
+            crashes: Vec::new(),
+            traffic: None,
+        };
+
+        let mut run = StoredTestRun::new(GatewayImpl::Nginx, "10.0.0.1");
+        assert!(run.failed_tests().is_empty());
+        run.summaries = vec![
+            round(vec![result(1, true), result(7, false), result(3, false)]),
+            round(vec![result(1, false), result(7, false)]),
+        ];
+        assert_eq!(run.failed_tests(), vec![1, 3, 7]);
+    }
+
+    #[test]
+    fn test_record_soak() {
+        let settings = crate::models::SoakSettings::new(std::time::Duration::from_secs(3600));
+        let mut report = SoakReport::new(&settings, Utc::now());
+        let rounds = [
+            TestRoundSummary::new(
+                1,
+                "NGINX Gateway Fabric",
+                vec![
+                    TestResult::pass(TestCase::HostRouting, 100),
+                    TestResult::fail(TestCase::PathRouting, 50, "404"),
+                ],
+            ),
+            TestRoundSummary::new(
+                2,
+                "NGINX Gateway Fabric",
+                vec![
+                    TestResult::pass(TestCase::HostRouting, 100),
+                    TestResult::pass(TestCase::PathRouting, 50),
+                ],
+            ),
+        ];
+        for summary in &rounds {
+            report.record(summary, Utc::now());
+        }
+
+        let mut run = StoredTestRun::new(GatewayImpl::Nginx, "10.0.0.1");
+        run.record_soak(&report, rounds.last());
+        assert_eq!(run.rounds, 2);
+        assert_eq!(run.summaries.len(), 1);
+        // The latest round passed; the soak still remembers the failure
+        assert_eq!(run.failed_tests(), vec![TestCase::PathRouting.number()]);
+
+        let aggregate = run.aggregate.as_ref().unwrap();
+        assert_eq!(aggregate.avg_pass_rate, 0.75);
+        assert_eq!(aggregate.min_pass_rate, 0.5);
+        let path = &aggregate.test_stats[TestCase::PathRouting.name()];
+        assert_eq!((path.pass_count, path.fail_count), (1, 1));
+        assert_eq!(path.pass_rate, 0.5);
+
+        let json = serde_json::to_string(&run).unwrap();
+        let loaded: StoredTestRun = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.soak.unwrap().rounds, 2);
+    }
+
+    #[test]
+    fn test_chaos_results() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = ResultsStorage::new(dir.path());
+        assert!(storage.list_chaos().unwrap().is_empty());
+
+        let result = ChaosResult::analyze(
+            "20260101_000000_0001",
+            "envoy",
+            crate::chaos::ChaosFault::PodKill,
+            Utc::now(),
+            1_000,
+            99.9,
+            Vec::new(),
+        );
+        storage.save_chaos(&result).unwrap();
+
+        let loaded = storage.load_chaos(&result.id).unwrap();
+        assert_eq!(loaded.fault, crate::chaos::ChaosFault::PodKill);
+        assert_eq!(storage.list_chaos().unwrap().len(), 1);
+        assert!(storage.load_chaos("missing").is_err());
+        // The chaos directory is not a gateway
+        assert!(storage.list_gateways().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_legacy_run_catalog_version() {
+        let mut value =
+            serde_json::to_value(StoredTestRun::new(GatewayImpl::Nginx, "10.0.0.1")).unwrap();
+        value.as_object_mut().unwrap().remove("catalog_version");
+
+        let run: StoredTestRun = serde_json::from_value(value).unwrap();
+        assert_eq!(run.catalog_version, None);
+        assert_eq!(run.catalog_label(), "unknown");
+    }
+
+    #[test]
+    fn test_annotations() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = ResultsStorage::new(dir.path());
+
+        let mut run = StoredTestRun::new(GatewayImpl::Kong, "10.0.0.1");
+        storage.save(&run).unwrap();
+        assert!(serde_json::to_value(&run)
+            .unwrap()
+            .get("annotations")
+            .is_none());
+
+        run.annotate(
+            Annotation::new(Some(8), "Kong needs plugin install")
+                .with_author(Some("alice".to_string())),
+        );
+        let path = storage.save(&run).unwrap();
+        assert!(path.with_extension(OPENMETRICS_EXTENSION).exists());
+
+        storage.save_benchmark_metrics(&[]).unwrap();
+        storage.replay_transcript(&run.id).unwrap();
+        storage
+            .save_setup(&GatewaySetup::new(GatewayImpl::Kong.name()))
+            .unwrap();
+        assert_eq!(storage.list_gateways().unwrap(), vec!["kong gateway"]);
+        assert!(storage.load_setup("Kong Gateway").unwrap().is_some());
+        assert!(storage.load_setup("Contour").unwrap().is_none());
+
+        let found = storage.find(&run.id).unwrap();
+        assert_eq!(found.annotations.len(), 1);
+        assert_eq!(
+            found.annotations[0].to_string(),
+            "[Test 8: Rate Limiting] Kong needs plugin install (alice)"
+        );
+        assert!(storage.find("missing").is_err());
     }
 
     #[test]
@@ -0,0 +1,172 @@
+//! Status badges
+//!
+//! Builds shields.io endpoint descriptors
+//! (<https://shields.io/badges/endpoint-badge>) from the latest round
+//! summaries, e.g. for `https://img.shields.io/endpoint?url=<serve>/api/v1/badges/best`.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::models::{TestCase, TestRoundSummary};
+
+/// Colour for badges without data
+const NO_DATA_COLOR: &str = "lightgrey";
+
+/// shields.io endpoint badge descriptor
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Badge {
+    pub schema_version: u8,
+    pub label: String,
+    pub message: String,
+    pub color: String,
+}
+
+impl Badge {
+    pub fn new(label: impl Into<String>, message: impl Into<String>, color: &str) -> Self {
+        Self {
+            schema_version: 1,
+            label: label.into(),
+            message: message.into(),
+            color: color.to_string(),
+        }
+    }
+
+    fn no_data(label: impl Into<String>) -> Self {
+        Self::new(label, "no data", NO_DATA_COLOR)
+    }
+
+    /// Pass rate of a gateway's latest round
+    pub fn pass_rate(gateway: &str, summary: Option<&TestRoundSummary>) -> Self {
+        let label = format!("{gateway} pass rate");
+        match summary.filter(|s| s.total > 0) {
+            Some(summary) => {
+                let rate = summary.pass_rate();
+                Self::new(
+                    label,
+                    format!("{rate:.1}% ({}/{})", summary.passed, summary.total),
+                    pass_rate_color(rate),
+                )
+            }
+            None => Self::no_data(label),
+        }
+    }
+
+    /// Load Test p99 latency of a gateway's latest round
+    pub fn p99(gateway: &str, summary: Option<&TestRoundSummary>) -> Self {
+        let label = format!("{gateway} p99");
+        match summary.and_then(load_test_p99) {
+            Some(p99) => Self::new(label, format!("{p99:.1}ms"), p99_color(p99)),
+            None => Self::no_data(label),
+        }
+    }
+
+    /// Gateway with the highest pass rate (ties broken by p99, then duration)
+    pub fn best_gateway(latest: &BTreeMap<String, TestRoundSummary>) -> Self {
+        let best = latest
+            .iter()
+            .filter(|(_, s)| s.total > 0)
+            .max_by(|(_, a), (_, b)| {
+                a.pass_rate()
+                    .total_cmp(&b.pass_rate())
+                    .then_with(|| {
+                        let p99 = |s| load_test_p99(s).unwrap_or(f64::MAX);
+                        p99(b).total_cmp(&p99(a))
+                    })
+                    .then_with(|| b.total_duration_ms.cmp(&a.total_duration_ms))
+            });
+
+        match best {
+            Some((gateway, summary)) => {
+                let rate = summary.pass_rate();
+                Self::new(
+                    "best gateway",
+                    format!("{gateway} ({rate:.1}%)"),
+                    pass_rate_color(rate),
+                )
+            }
+            None => Self::no_data("best gateway"),
+        }
+    }
+}
+
+/// p99 latency recorded by the Load Test (test 16)
+fn load_test_p99(summary: &TestRoundSummary) -> Option<f64> {
+    summary
+        .results
+        .iter()
+        .find(|r| r.test_case == TestCase::LoadTest)
+        .and_then(|r| r.details.as_ref())
+        .and_then(|d| d.get("p99_latency_ms"))
+        .and_then(|v| v.as_f64())
+}
+
+fn pass_rate_color(rate: f64) -> &'static str {
+    match rate {
+        r if r >= 100.0 => "brightgreen",
+        r if r >= 90.0 => "green",
+        r if r >= 75.0 => "yellow",
+        r if r >= 50.0 => "orange",
+        _ => "red",
+    }
+}
+
+fn p99_color(ms: f64) -> &'static str {
+    match ms {
+        m if m < 50.0 => "brightgreen",
+        m if m < 100.0 => "green",
+        m if m < 250.0 => "yellow",
+        m if m < 500.0 => "orange",
+        _ => "red",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TestResult;
+
+    fn summary(gateway: &str, failures: usize, p99: f64) -> TestRoundSummary {
+        let mut results = vec![TestResult::pass(TestCase::LoadTest, 100)
+            .with_details(serde_json::json!({ "p99_latency_ms": p99 }))];
+        results.extend((0..failures).map(|_| TestResult::fail(TestCase::HostRouting, 10, "404")));
+        results.push(TestResult::pass(TestCase::PathRouting, 10));
+        TestRoundSummary::new(1, gateway, results)
+    }
+
+    #[test]
+    fn test_gateway_badges() {
+        let nginx = summary("nginx", 0, 42.0);
+        let badge = Badge::pass_rate("nginx", Some(&nginx));
+        assert_eq!(badge.message, "100.0% (2/2)");
+        assert_eq!(badge.color, "brightgreen");
+
+        let badge = Badge::p99("nginx", Some(&nginx));
+        assert_eq!(
+            (badge.message.as_str(), badge.color.as_str()),
+            ("42.0ms", "brightgreen")
+        );
+
+        let badge = Badge::p99("kong", None);
+        assert_eq!(
+            (badge.message.as_str(), badge.color.as_str()),
+            ("no data", "lightgrey")
+        );
+
+        let json = serde_json::to_value(Badge::pass_rate("nginx", Some(&nginx))).unwrap();
+        assert_eq!(json["schemaVersion"], 1);
+        assert_eq!(json["label"], "nginx pass rate");
+    }
+
+    #[test]
+    fn test_best_gateway() {
+        let mut latest = BTreeMap::new();
+        assert_eq!(Badge::best_gateway(&latest).message, "no data");
+
+        latest.insert("nginx".to_string(), summary("nginx", 1, 20.0));
+        latest.insert("envoy".to_string(), summary("envoy", 0, 80.0));
+        latest.insert("istio".to_string(), summary("istio", 0, 30.0));
+        let badge = Badge::best_gateway(&latest);
+        assert_eq!(badge.message, "istio (100.0%)");
+    }
+}
@@ -0,0 +1,11 @@
+//! Monitoring daemon
+//!
+//! Runs the test suite on a schedule and exposes a remote control API
+//! for chatops and external schedulers.
+
+#![allow(dead_code)]
+
+mod badge;
+mod server;
+
+pub use server::{ServeConfig, ServeTarget, Server};
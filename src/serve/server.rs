@@ -0,0 +1,541 @@
+//! Monitoring daemon with a remote control API
+//!
+//! Periodically runs the test suite against configured gateways and serves
+//! a small bearer-token authenticated REST API:
+//!
+//! - `GET  /api/v1/status`  - daemon state
+//! - `GET  /api/v1/results` - latest round summary per gateway
+//! - `GET  /api/v1/targets` - configured target gateways
+//! - `PUT  /api/v1/targets` - replace target gateways
+//! - `POST /api/v1/runs`    - trigger an on-demand run
+//! - `GET  /api/v1/badges/best` and `/api/v1/badges/<gateway>/{pass-rate,p99}`
+//!   - shields.io endpoint badges (token-free with `--public-badges`)
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{Mutex, Notify};
+use tracing::{debug, info, warn};
+
+use super::badge::Badge;
+use crate::executor::ParallelExecutor;
+use crate::models::{GatewayConfig, GatewayImpl, TestRoundSummary};
+
+/// Maximum accepted request size (headers + body)
+const MAX_REQUEST_BYTES: usize = 64 * 1024;
+
+/// A gateway the daemon tests
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServeTarget {
+    /// Gateway implementation short name
+    pub gateway: String,
+    /// Gateway IP address
+    pub ip: String,
+}
+
+impl ServeTarget {
+    /// Parse from `gateway=ip`
+    pub fn parse(spec: &str) -> Result<Self> {
+        let (gateway, ip) = spec
+            .split_once('=')
+            .with_context(|| format!("Invalid target '{spec}', expected gateway=ip"))?;
+        let target = Self {
+            gateway: gateway.trim().to_string(),
+            ip: ip.trim().to_string(),
+        };
+        target.implementation()?;
+        Ok(target)
+    }
+
+    fn implementation(&self) -> Result<GatewayImpl> {
+        GatewayImpl::from_str(&self.gateway)
+            .ok_or_else(|| anyhow::anyhow!("Unknown gateway: {}", self.gateway))
+    }
+}
+
+/// Daemon configuration
+#[derive(Clone, Debug)]
+pub struct ServeConfig {
+    /// Listen address
+    pub bind: String,
+    /// Bearer token required on every request
+    pub token: String,
+    /// Interval between scheduled runs (0 = on demand only)
+    pub interval_secs: u64,
+    /// Max concurrent tests per gateway
+    pub concurrent: usize,
+    /// Initial target gateways
+    pub targets: Vec<ServeTarget>,
+    /// Serve badge endpoints without the bearer token
+    pub public_badges: bool,
+}
+
+/// Shared daemon state
+#[derive(Debug, Default, Serialize)]
+struct ServeState {
+    targets: Vec<ServeTarget>,
+    latest: BTreeMap<String, TestRoundSummary>,
+    running: bool,
+    runs_completed: u64,
+    last_run_at: Option<String>,
+}
+
+/// Monitoring daemon
+pub struct Server {
+    config: ServeConfig,
+    state: Arc<Mutex<ServeState>>,
+    trigger: Arc<Notify>,
+}
+
+impl Server {
+    pub fn new(config: ServeConfig) -> Self {
+        let state = ServeState {
+            targets: config.targets.clone(),
+            ..Default::default()
+        };
+
+        Self {
+            config,
+            state: Arc::new(Mutex::new(state)),
+            trigger: Arc::new(Notify::new()),
+        }
+    }
+
+    /// Run the scheduler and API server until the process exits
+    pub async fn run(&self) -> Result<()> {
+        let listener = TcpListener::bind(&self.config.bind)
+            .await
+            .with_context(|| format!("Failed to bind {}", self.config.bind))?;
+        info!("Control API listening on {}", self.config.bind);
+
+        let scheduler = tokio::spawn(scheduler_loop(
+            self.state.clone(),
+            self.trigger.clone(),
+            self.config.interval_secs,
+            self.config.concurrent,
+        ));
+
+        loop {
+            let (stream, peer) = match listener.accept().await {
+                Ok(conn) => conn,
+                Err(e) => {
+                    warn!("Failed to accept connection: {}", e);
+                    continue;
+                }
+            };
+            debug!("Connection from {}", peer);
+
+            let state = self.state.clone();
+            let trigger = self.trigger.clone();
+            let token = self.config.token.clone();
+            let public_badges = self.config.public_badges;
+            tokio::spawn(async move {
+                if let Err(e) =
+                    handle_connection(stream, &token, public_badges, &state, &trigger).await
+                {
+                    debug!("Connection error from {}: {}", peer, e);
+                }
+            });
+
+            if scheduler.is_finished() {
+                anyhow::bail!("Scheduler stopped unexpectedly");
+            }
+        }
+    }
+}
+
+/// Run the suite on every interval tick or on-demand trigger
+async fn scheduler_loop(
+    state: Arc<Mutex<ServeState>>,
+    trigger: Arc<Notify>,
+    interval_secs: u64,
+    concurrent: usize,
+) {
+    loop {
+        if interval_secs > 0 {
+            tokio::select! {
+                _ = tokio::time::sleep(Duration::from_secs(interval_secs)) => {}
+                _ = trigger.notified() => {}
+            }
+        } else {
+            trigger.notified().await;
+        }
+
+        let targets = {
+            let mut s = state.lock().await;
+            s.running = true;
+            s.targets.clone()
+        };
+
+        info!("Starting run for {} targets", targets.len());
+        let executor = ParallelExecutor::new(concurrent);
+        let mut summaries = Vec::new();
+        for target in &targets {
+            let Ok(implementation) = target.implementation() else {
+                continue;
+            };
+            let config = GatewayConfig::new(implementation);
+            match executor.run_all_parallel(&target.ip, &config).await {
+                Ok(summary) => summaries.push((target.gateway.clone(), summary)),
+                Err(e) => warn!("Run for {} failed: {}", target.gateway, e),
+            }
+        }
+
+        let mut s = state.lock().await;
+        s.latest.extend(summaries);
+        s.running = false;
+        s.runs_completed += 1;
+        s.last_run_at = Some(chrono::Utc::now().to_rfc3339());
+    }
+}
+
+/// Parsed HTTP request
+#[derive(Debug, PartialEq)]
+struct Request {
+    method: String,
+    path: String,
+    headers: BTreeMap<String, String>,
+    body: String,
+}
+
+/// Parse a raw HTTP/1.1 request
+fn parse_request(raw: &str) -> Option<Request> {
+    let (head, body) = raw.split_once("\r\n\r\n")?;
+    let mut lines = head.lines();
+    let mut request_line = lines.next()?.split_whitespace();
+    let method = request_line.next()?.to_string();
+    let path = request_line.next()?.to_string();
+
+    let headers = lines
+        .filter_map(|l| l.split_once(':'))
+        .map(|(k, v)| (k.trim().to_lowercase(), v.trim().to_string()))
+        .collect();
+
+    Some(Request {
+        method,
+        path,
+        headers,
+        body: body.to_string(),
+    })
+}
+
+/// Compare tokens in constant time: every provided byte is checked, so the
+/// time taken reveals neither the first mismatch nor the expected length
+fn token_matches(expected: &str, provided: &str) -> bool {
+    let (expected, provided) = (expected.as_bytes(), provided.as_bytes());
+    if expected.is_empty() {
+        return false;
+    }
+    let mut diff = expected.len() ^ provided.len();
+    for (i, &byte) in provided.iter().enumerate() {
+        let want = expected[i % expected.len()];
+        diff |= std::hint::black_box(usize::from(want ^ byte));
+    }
+    diff == 0
+}
+
+fn is_authorized(request: &Request, token: &str) -> bool {
+    request
+        .headers
+        .get("authorization")
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(|t| token_matches(token, t))
+        .unwrap_or(false)
+}
+
+/// Badge requests, which shields.io cannot authenticate
+fn is_badge_request(request: &Request) -> bool {
+    request.method == "GET" && request.path.starts_with("/api/v1/badges/")
+}
+
+/// Read one request, dispatch it and write the response
+async fn handle_connection(
+    mut stream: TcpStream,
+    token: &str,
+    public_badges: bool,
+    state: &Mutex<ServeState>,
+    trigger: &Notify,
+) -> Result<()> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+
+    let request = loop {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok(());
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        if buf.len() > MAX_REQUEST_BYTES {
+            write_response(&mut stream, 413, r#"{"error":"request too large"}"#).await?;
+            return Ok(());
+        }
+
+        let raw = String::from_utf8_lossy(&buf);
+        if let Some(request) = parse_request(&raw) {
+            let expected: usize = request
+                .headers
+                .get("content-length")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0);
+            if request.body.len() >= expected {
+                break request;
+            }
+        }
+    };
+
+    let authorized =
+        is_authorized(&request, token) || (public_badges && is_badge_request(&request));
+    let (status, body) = if authorized {
+        route(&request, state, trigger).await
+    } else {
+        (401, r#"{"error":"unauthorized"}"#.to_string())
+    };
+
+    info!("{} {} -> {}", request.method, request.path, status);
+    write_response(&mut stream, status, &body).await
+}
+
+/// Dispatch an authorized request
+async fn route(request: &Request, state: &Mutex<ServeState>, trigger: &Notify) -> (u16, String) {
+    if is_badge_request(request) {
+        return badge(request, state).await;
+    }
+
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/api/v1/status") => {
+            let s = state.lock().await;
+            let status = serde_json::json!({
+                "running": s.running,
+                "runs_completed": s.runs_completed,
+                "last_run_at": s.last_run_at,
+                "targets": s.targets.len(),
+            });
+            (200, status.to_string())
+        }
+        ("GET", "/api/v1/results") => (200, to_json(&state.lock().await.latest)),
+        ("GET", "/api/v1/targets") => (200, to_json(&state.lock().await.targets)),
+        ("PUT", "/api/v1/targets") => {
+            match serde_json::from_str::<Vec<ServeTarget>>(&request.body) {
+                Ok(targets) => {
+                    if let Some(bad) = targets.iter().find(|t| t.implementation().is_err()) {
+                        return (
+                            400,
+                            serde_json::json!({ "error": format!("unknown gateway: {}", bad.gateway) })
+                                .to_string(),
+                        );
+                    }
+                    let mut s = state.lock().await;
+                    s.targets = targets;
+                    (200, to_json(&s.targets))
+                }
+                Err(e) => (
+                    400,
+                    serde_json::json!({ "error": e.to_string() }).to_string(),
+                ),
+            }
+        }
+        ("POST", "/api/v1/runs") => {
+            if state.lock().await.running {
+                (409, r#"{"error":"run already in progress"}"#.to_string())
+            } else {
+                trigger.notify_one();
+                (202, r#"{"status":"scheduled"}"#.to_string())
+            }
+        }
+        _ => (404, r#"{"error":"not found"}"#.to_string()),
+    }
+}
+
+/// Serve a shields.io endpoint badge
+async fn badge(request: &Request, state: &Mutex<ServeState>) -> (u16, String) {
+    let path = request.path.split('?').next().unwrap_or_default();
+    let segments: Vec<&str> = path
+        .trim_start_matches("/api/v1/badges/")
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    let s = state.lock().await;
+    let badge = match segments.as_slice() {
+        ["best"] => Badge::best_gateway(&s.latest),
+        [gateway, kind] => {
+            let known = s
+                .targets
+                .iter()
+                .any(|t| t.gateway.eq_ignore_ascii_case(gateway))
+                || s.latest.keys().any(|g| g.eq_ignore_ascii_case(gateway));
+            if !known {
+                return (404, r#"{"error":"unknown gateway"}"#.to_string());
+            }
+            let summary = s
+                .latest
+                .iter()
+                .find(|(g, _)| g.eq_ignore_ascii_case(gateway))
+                .map(|(_, summary)| summary);
+            match *kind {
+                "pass-rate" => Badge::pass_rate(gateway, summary),
+                "p99" => Badge::p99(gateway, summary),
+                _ => return (404, r#"{"error":"unknown badge"}"#.to_string()),
+            }
+        }
+        _ => return (404, r#"{"error":"not found"}"#.to_string()),
+    };
+    (200, to_json(&badge))
+}
+
+fn to_json<T: Serialize>(value: &T) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+async fn write_response(stream: &mut TcpStream, status: u16, body: &str) -> Result<()> {
+    let reason = match status {
+        200 => "OK",
+        202 => "Accepted",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        409 => "Conflict",
+        413 => "Payload Too Large",
+        _ => "Error",
+    };
+    let response = format!(
+        "HTTP/1.1 {status} {reason}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(method: &str, path: &str, auth: Option<&str>, body: &str) -> Request {
+        let mut raw = format!("{method} {path} HTTP/1.1\r\nHost: localhost\r\n");
+        if let Some(token) = auth {
+            raw.push_str(&format!("Authorization: Bearer {token}\r\n"));
+        }
+        raw.push_str(&format!("Content-Length: {}\r\n\r\n{body}", body.len()));
+        parse_request(&raw).unwrap()
+    }
+
+    #[test]
+    fn test_parse_request() {
+        let req = request("PUT", "/api/v1/targets", Some("secret"), "[]");
+        assert_eq!(req.method, "PUT");
+        assert_eq!(req.path, "/api/v1/targets");
+        assert_eq!(req.headers["authorization"], "Bearer secret");
+        assert_eq!(req.body, "[]");
+        assert!(parse_request("GET / HTTP/1.1\r\n").is_none());
+    }
+
+    #[test]
+    fn test_authorization() {
+        assert!(is_authorized(
+            &request("GET", "/", Some("secret"), ""),
+            "secret"
+        ));
+        assert!(!is_authorized(
+            &request("GET", "/", Some("wrong!"), ""),
+            "secret"
+        ));
+        assert!(!is_authorized(&request("GET", "/", None, ""), "secret"));
+    }
+
+    #[test]
+    fn test_token_matches() {
+        assert!(token_matches("secret", "secret"));
+        assert!(!token_matches("secret", "secreT"));
+        assert!(!token_matches("secret", "secret1"));
+        assert!(!token_matches("secret", "secretsecret"));
+        assert!(!token_matches("secret", ""));
+        assert!(!token_matches("", ""));
+    }
+
+    #[test]
+    fn test_parse_target() {
+        let t = ServeTarget::parse("envoy=10.0.0.2").unwrap();
+        assert_eq!(t.gateway, "envoy");
+        assert_eq!(t.ip, "10.0.0.2");
+        assert!(ServeTarget::parse("envoy").is_err());
+        assert!(ServeTarget::parse("unknown=10.0.0.2").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_route_targets_and_runs() {
+        let state = Mutex::new(ServeState::default());
+        let trigger = Notify::new();
+
+        let body = r#"[{"gateway":"nginx","ip":"10.0.0.1"}]"#;
+        let (status, _) = route(
+            &request("PUT", "/api/v1/targets", None, body),
+            &state,
+            &trigger,
+        )
+        .await;
+        assert_eq!(status, 200);
+        assert_eq!(state.lock().await.targets.len(), 1);
+
+        let bad = r#"[{"gateway":"bogus","ip":"10.0.0.1"}]"#;
+        let (status, _) = route(
+            &request("PUT", "/api/v1/targets", None, bad),
+            &state,
+            &trigger,
+        )
+        .await;
+        assert_eq!(status, 400);
+
+        let (status, _) = route(&request("POST", "/api/v1/runs", None, ""), &state, &trigger).await;
+        assert_eq!(status, 202);
+
+        state.lock().await.running = true;
+        let (status, _) = route(&request("POST", "/api/v1/runs", None, ""), &state, &trigger).await;
+        assert_eq!(status, 409);
+
+        let (status, _) = route(&request("GET", "/nope", None, ""), &state, &trigger).await;
+        assert_eq!(status, 404);
+    }
+
+    #[tokio::test]
+    async fn test_route_badges() {
+        let state = Mutex::new(ServeState {
+            targets: vec![ServeTarget::parse("nginx=10.0.0.1").unwrap()],
+            ..Default::default()
+        });
+        let trigger = Notify::new();
+
+        let req = request("GET", "/api/v1/badges/nginx/pass-rate", None, "");
+        assert!(is_badge_request(&req));
+        let (status, body) = route(&req, &state, &trigger).await;
+        assert_eq!(status, 200);
+        assert!(body.contains(r#""message":"no data""#));
+
+        let (status, body) = route(
+            &request("GET", "/api/v1/badges/best?cache=300", None, ""),
+            &state,
+            &trigger,
+        )
+        .await;
+        assert_eq!(status, 200);
+        assert!(body.contains(r#""schemaVersion":1"#));
+
+        let (status, _) = route(
+            &request("GET", "/api/v1/badges/kong/p99", None, ""),
+            &state,
+            &trigger,
+        )
+        .await;
+        assert_eq!(status, 404);
+        assert!(!is_badge_request(&request(
+            "PUT",
+            "/api/v1/badges/best",
+            None,
+            ""
+        )));
+    }
+}
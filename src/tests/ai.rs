@@ -0,0 +1,496 @@
+//! Experimental AI route tests for Gateway API
+//!
+//! Tests 19-21 (`--experimental ai`): AI Streaming, AI Token Latency,
+//! AI Body Limit. They target an OpenAI-compatible chat completions route
+//! (an AI backend such as kgateway's agentgateway or Envoy AI Gateway in
+//! front of a mock model) and check what matters for inference traffic:
+//! streamed completions are not buffered, tokens arrive quickly, and
+//! oversized prompts are rejected before they reach the model.
+
+#![allow(dead_code)]
+
+use anyhow::Result;
+use std::time::Instant;
+use tracing::info;
+
+use crate::benchmark::LatencyStats;
+use crate::http::{authority, HttpClient, HttpRequest, StreamedResponse};
+use crate::models::{TestCase, TestResult, TestStatus};
+
+/// Chat completions route of the AI backend (`deploy manifest -r ai`)
+pub const AI_ROUTE_PATH: &str = "/ai/v1/chat/completions";
+
+/// Mock model service behind the AI route
+pub const AI_BACKEND: &str = "mock-llm";
+
+/// Model name sent in requests
+pub const AI_MODEL: &str = "mock-llm";
+
+/// Streams whose first token arrives after this share of the stream's
+/// duration count as buffered by the gateway
+const BUFFERED_FIRST_TOKEN_SHARE: f64 = 0.5;
+
+/// OpenAI-style chat completion request body
+fn chat_request(prompt: &str, max_tokens: u32, stream: bool) -> String {
+    serde_json::json!({
+        "model": AI_MODEL,
+        "messages": [{ "role": "user", "content": prompt }],
+        "max_tokens": max_tokens,
+        "stream": stream,
+    })
+    .to_string()
+}
+
+/// Token events of a streamed (server-sent events) completion
+#[derive(Clone, Debug, Default, PartialEq)]
+struct TokenStream {
+    /// Milliseconds from the request until each token event arrived
+    arrivals_ms: Vec<f64>,
+    /// Whether the stream ended with `data: [DONE]`
+    done: bool,
+}
+
+impl TokenStream {
+    /// Split `body` into events; `arrival_ms` maps a byte position to the
+    /// time it was received
+    fn parse(body: &str, arrival_ms: impl Fn(usize) -> Option<f64>) -> Self {
+        let mut stream = Self::default();
+        let mut end = 0;
+        for event in body.split_inclusive("\n\n") {
+            end += event.len();
+            let data: Vec<&str> = event
+                .lines()
+                .filter_map(|line| line.strip_prefix("data:"))
+                .map(str::trim)
+                .collect();
+            match data.as_slice() {
+                [] => {}
+                ["[DONE]"] => stream.done = true,
+                _ => {
+                    if let Some(ms) = arrival_ms(end - 1) {
+                        stream.arrivals_ms.push(ms);
+                    }
+                }
+            }
+        }
+        stream
+    }
+
+    fn from_response(streamed: &StreamedResponse) -> Self {
+        Self::parse(&streamed.response.body, |position| {
+            streamed.arrival_ms(position)
+        })
+    }
+
+    fn tokens(&self) -> usize {
+        self.arrivals_ms.len()
+    }
+
+    fn first_token_ms(&self) -> Option<f64> {
+        self.arrivals_ms.first().copied()
+    }
+
+    /// Gaps between consecutive token events
+    fn inter_token_ms(&self) -> Vec<f64> {
+        self.arrivals_ms.windows(2).map(|w| w[1] - w[0]).collect()
+    }
+
+    /// Whether tokens arrived over time rather than in one burst at the end
+    fn is_incremental(&self) -> bool {
+        match (self.arrivals_ms.first(), self.arrivals_ms.last()) {
+            (Some(first), Some(last)) if self.tokens() > 1 => {
+                *first < last * BUFFERED_FIRST_TOKEN_SHARE
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Test 19: AI Streaming
+#[derive(Clone, Debug)]
+pub struct AiStreamingTest {
+    pub gateway_ip: String,
+    pub gateway_port: u16,
+    pub path: String,
+    pub max_tokens: u32,
+    pub min_tokens: usize,
+}
+
+impl AiStreamingTest {
+    pub fn new(gateway_ip: impl Into<String>, gateway_port: u16) -> Self {
+        Self {
+            gateway_ip: gateway_ip.into(),
+            gateway_port,
+            path: AI_ROUTE_PATH.to_string(),
+            max_tokens: 256,
+            min_tokens: 50,
+        }
+    }
+
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    pub fn max_tokens(mut self, tokens: u32) -> Self {
+        self.max_tokens = tokens;
+        self
+    }
+
+    pub async fn run(&self, client: &HttpClient) -> Result<TestResult> {
+        info!("Running AI Streaming Test");
+        let start = Instant::now();
+        let mut details = Vec::new();
+
+        let url = format!(
+            "http://{}{}",
+            authority(&self.gateway_ip, self.gateway_port),
+            self.path
+        );
+        let request = HttpRequest::post(url)
+            .header("Content-Type", "application/json")
+            .header("Accept", "text/event-stream")
+            .body(chat_request(
+                "Write a long story about gateways.",
+                self.max_tokens,
+                true,
+            ));
+
+        let status = match client.send_streaming(request).await {
+            Ok(streamed) if streamed.response.is_success() => {
+                let stream = TokenStream::from_response(&streamed);
+                details.push(format!(
+                    "Received {} tokens in {} chunks",
+                    stream.tokens(),
+                    streamed.chunks.len()
+                ));
+                if stream.tokens() < self.min_tokens {
+                    details.push(format!(
+                        "✗ Expected at least {} streamed tokens",
+                        self.min_tokens
+                    ));
+                    TestStatus::Fail
+                } else if !stream.done {
+                    details.push("✗ Stream ended without [DONE]".to_string());
+                    TestStatus::Fail
+                } else if !stream.is_incremental() {
+                    details.push(format!(
+                        "✗ Response was buffered (first token at {:.0}ms of {:.0}ms)",
+                        stream.first_token_ms().unwrap_or_default(),
+                        stream.arrivals_ms.last().copied().unwrap_or_default()
+                    ));
+                    TestStatus::Fail
+                } else {
+                    details.push(format!(
+                        "✓ Tokens streamed incrementally (first at {:.0}ms, last at {:.0}ms)",
+                        stream.first_token_ms().unwrap_or_default(),
+                        stream.arrivals_ms.last().copied().unwrap_or_default()
+                    ));
+                    TestStatus::Pass
+                }
+            }
+            Ok(streamed) => {
+                details.push(format!(
+                    "✗ Streaming request returned status {}",
+                    streamed.response.status_code
+                ));
+                TestStatus::Fail
+            }
+            Err(e) => {
+                details.push(format!("✗ Streaming request failed: {e}"));
+                TestStatus::Fail
+            }
+        };
+
+        Ok(TestResult {
+            test_case: TestCase::AiStreaming,
+            status,
+            duration_ms: start.elapsed().as_millis() as u64,
+            message: Some(details.join("\n")),
+            details: None,
+            retries: 0,
+            backends: Vec::new(),
+        })
+    }
+}
+
+/// Test 20: AI Token Latency
+#[derive(Clone, Debug)]
+pub struct AiTokenLatencyTest {
+    pub gateway_ip: String,
+    pub gateway_port: u16,
+    pub path: String,
+    pub requests: usize,
+    pub max_tokens: u32,
+    pub max_first_token_ms: f64,
+    pub max_inter_token_p99_ms: f64,
+}
+
+impl AiTokenLatencyTest {
+    pub fn new(gateway_ip: impl Into<String>, gateway_port: u16) -> Self {
+        Self {
+            gateway_ip: gateway_ip.into(),
+            gateway_port,
+            path: AI_ROUTE_PATH.to_string(),
+            requests: 5,
+            max_tokens: 64,
+            max_first_token_ms: 2000.0,
+            max_inter_token_p99_ms: 250.0,
+        }
+    }
+
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    pub fn requests(mut self, requests: usize) -> Self {
+        self.requests = requests;
+        self
+    }
+
+    pub fn max_first_token_ms(mut self, ms: f64) -> Self {
+        self.max_first_token_ms = ms;
+        self
+    }
+
+    pub fn max_inter_token_p99_ms(mut self, ms: f64) -> Self {
+        self.max_inter_token_p99_ms = ms;
+        self
+    }
+
+    pub async fn run(&self, client: &HttpClient) -> Result<TestResult> {
+        info!("Running AI Token Latency Test ({} requests)", self.requests);
+        let start = Instant::now();
+        let mut details = Vec::new();
+        let url = format!(
+            "http://{}{}",
+            authority(&self.gateway_ip, self.gateway_port),
+            self.path
+        );
+
+        let mut first_token = Vec::new();
+        let mut inter_token = Vec::new();
+        let mut failures = 0;
+        for i in 0..self.requests {
+            let request = HttpRequest::post(&url)
+                .header("Content-Type", "application/json")
+                .header("Accept", "text/event-stream")
+                .body(chat_request(
+                    &format!("Count to {}.", self.max_tokens),
+                    self.max_tokens,
+                    true,
+                ));
+            match client.send_streaming(request).await {
+                Ok(streamed) if streamed.response.is_success() => {
+                    let stream = TokenStream::from_response(&streamed);
+                    match stream.first_token_ms() {
+                        Some(ms) => {
+                            first_token.push(ms);
+                            inter_token.extend(stream.inter_token_ms());
+                        }
+                        None => {
+                            failures += 1;
+                            details.push(format!("✗ Request {}: no tokens streamed", i + 1));
+                        }
+                    }
+                }
+                Ok(streamed) => {
+                    failures += 1;
+                    details.push(format!(
+                        "✗ Request {}: status {}",
+                        i + 1,
+                        streamed.response.status_code
+                    ));
+                }
+                Err(e) => {
+                    failures += 1;
+                    details.push(format!("✗ Request {}: {e}", i + 1));
+                }
+            }
+        }
+
+        let ttft = LatencyStats::from_samples(&first_token);
+        let itl = LatencyStats::from_samples(&inter_token);
+        let status = if failures > 0 || first_token.is_empty() {
+            TestStatus::Fail
+        } else {
+            details.push(format!(
+                "Time to first token: p50 {:.0}ms, max {:.0}ms",
+                ttft.percentiles.p50, ttft.max
+            ));
+            details.push(format!(
+                "Inter-token latency: p50 {:.1}ms, p99 {:.1}ms",
+                itl.percentiles.p50, itl.percentiles.p99
+            ));
+
+            let mut violations = Vec::new();
+            if ttft.max > self.max_first_token_ms {
+                violations.push(format!(
+                    "✗ Time to first token {:.0}ms exceeds {:.0}ms",
+                    ttft.max, self.max_first_token_ms
+                ));
+            }
+            if itl.percentiles.p99 > self.max_inter_token_p99_ms {
+                violations.push(format!(
+                    "✗ Inter-token p99 {:.1}ms exceeds {:.0}ms",
+                    itl.percentiles.p99, self.max_inter_token_p99_ms
+                ));
+            }
+            if violations.is_empty() {
+                details.push("✓ Token latency within thresholds".to_string());
+                TestStatus::Pass
+            } else {
+                details.extend(violations);
+                TestStatus::Fail
+            }
+        };
+
+        Ok(TestResult {
+            test_case: TestCase::AiTokenLatency,
+            status,
+            duration_ms: start.elapsed().as_millis() as u64,
+            message: Some(details.join("\n")),
+            details: Some(serde_json::json!({
+                "requests": self.requests,
+                "failures": failures,
+                "first_token_p50_ms": ttft.percentiles.p50,
+                "first_token_max_ms": ttft.max,
+                "inter_token_p50_ms": itl.percentiles.p50,
+                "inter_token_p99_ms": itl.percentiles.p99,
+            })),
+            retries: 0,
+            backends: Vec::new(),
+        })
+    }
+}
+
+/// Test 21: AI Body Limit
+#[derive(Clone, Debug)]
+pub struct AiBodyLimitTest {
+    pub gateway_ip: String,
+    pub gateway_port: u16,
+    pub path: String,
+    /// Largest request body the route should accept
+    pub limit_bytes: usize,
+}
+
+impl AiBodyLimitTest {
+    pub fn new(gateway_ip: impl Into<String>, gateway_port: u16) -> Self {
+        Self {
+            gateway_ip: gateway_ip.into(),
+            gateway_port,
+            path: AI_ROUTE_PATH.to_string(),
+            limit_bytes: 1024 * 1024,
+        }
+    }
+
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    pub fn limit_bytes(mut self, bytes: usize) -> Self {
+        self.limit_bytes = bytes;
+        self
+    }
+
+    pub async fn run(&self, client: &HttpClient) -> Result<TestResult> {
+        info!(
+            "Running AI Body Limit Test ({} byte limit)",
+            self.limit_bytes
+        );
+        let start = Instant::now();
+        let mut details = Vec::new();
+        let url = format!(
+            "http://{}{}",
+            authority(&self.gateway_ip, self.gateway_port),
+            self.path
+        );
+        let send = |prompt_bytes: usize| {
+            let request = HttpRequest::post(&url)
+                .header("Content-Type", "application/json")
+                .body(chat_request(&"a".repeat(prompt_bytes), 1, false));
+            client.send(request)
+        };
+
+        let small = match send(1024).await {
+            Ok(resp) if resp.is_success() => {
+                details.push(format!("✓ 1 KiB prompt accepted ({})", resp.status_code));
+                true
+            }
+            Ok(resp) => {
+                details.push(format!("✗ 1 KiB prompt returned {}", resp.status_code));
+                false
+            }
+            Err(e) => {
+                details.push(format!("✗ 1 KiB prompt failed: {e}"));
+                false
+            }
+        };
+
+        let oversized = self.limit_bytes * 2;
+        let large = match send(oversized).await {
+            Ok(resp) if resp.status_code == 413 => {
+                details.push(format!("✓ {oversized} byte prompt rejected with 413"));
+                true
+            }
+            Ok(resp) if resp.is_success() => {
+                details.push(format!(
+                    "✗ {oversized} byte prompt accepted; body limit not enforced"
+                ));
+                false
+            }
+            Ok(resp) => {
+                details.push(format!(
+                    "✗ {oversized} byte prompt returned {} instead of 413",
+                    resp.status_code
+                ));
+                false
+            }
+            Err(e) => {
+                details.push(format!("✗ {oversized} byte prompt failed: {e}"));
+                false
+            }
+        };
+
+        Ok(TestResult {
+            test_case: TestCase::AiBodyLimit,
+            status: if small && large {
+                TestStatus::Pass
+            } else {
+                TestStatus::Fail
+            },
+            duration_ms: start.elapsed().as_millis() as u64,
+            message: Some(details.join("\n")),
+            details: None,
+            retries: 0,
+            backends: Vec::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_stream_timing() {
+        let body = "data: {\"t\":1}\n\ndata: {\"t\":2}\n\n: keep-alive\n\ndata: [DONE]\n\n";
+        let first_chunk = body.find("data: {\"t\":2}").unwrap();
+        let incremental =
+            TokenStream::parse(body, |p| Some(if p < first_chunk { 20.0 } else { 120.0 }));
+        assert_eq!(incremental.arrivals_ms, vec![20.0, 120.0]);
+        assert!(incremental.done);
+        assert_eq!(incremental.inter_token_ms(), vec![100.0]);
+        assert!(incremental.is_incremental());
+
+        let buffered = TokenStream::parse(body, |_| Some(900.0));
+        assert_eq!(buffered.tokens(), 2);
+        assert!(!buffered.is_incremental());
+
+        let truncated = TokenStream::parse("data: x\n\n", |_| Some(5.0));
+        assert!(!truncated.done);
+        assert_eq!(truncated.tokens(), 1);
+    }
+}
@@ -1,6 +1,6 @@
 //! Gateway API test implementations
 //!
-//! This module contains all 17 test cases for Gateway API validation.
+//! This module contains all 34 test cases for Gateway API validation.
 //!
 //! ## Test Categories
 //!
@@ -28,174 +28,625 @@
 //! - Health Check
 //! - Load Test
 //! - Failover Recovery
+//!
+//! ### Added Traffic Tests (18)
+//! - Consistent Hashing
+//!
+//! ### Experimental AI Tests (19-21, `--experimental ai`)
+//! - AI Streaming
+//! - AI Token Latency
+//! - AI Body Limit
+//!
+//! ### Added Network Tests (22)
+//! - Dual Stack
+//!
+//! ### Added Host Handling Tests (23-25)
+//! - Wildcard Hostname
+//! - Missing Host Header
+//! - SNI/Host Mismatch
+//!
+//! ### Added Protocol Tests (26-30)
+//! - Large Body
+//! - Header Handling
+//! - Forwarded Headers
+//! - Compression
+//! - CORS
+//!
+//! ### Added TLS Tests (31)
+//! - TLS Policy
+//!
+//! ### Added Protocol Tests (32)
+//! - HTTP/2
+//!
+//! ### Added Resilience Tests (33, `--experimental disruptive`)
+//! - Zero-Downtime Reload
+//!
+//! ### Added Routing Tests (34)
+//! - Route Propagation
 
 #![allow(dead_code)]
 
 mod advanced;
+mod ai;
+mod network;
+mod progressive;
+mod protocol;
+mod resilience;
 mod routing;
 mod tls;
 mod traffic;
 
 // Re-export routing tests
-pub use routing::{HeaderRoutingTest, HostRoutingTest, PathRoutingTest, RoutingTestSuite};
+pub use routing::{
+    HeaderRoutingTest, HostRoutingTest, MissingHostTest, PathRoutingTest, RoutePropagationTest,
+    RoutingTestSuite, WildcardHostnameTest, WILDCARD_BACKEND, WILDCARD_HOSTNAME,
+};
 
 // Re-export TLS tests
-pub use tls::{BackendTlsTest, HttpsRedirectTest, TlsTerminationTest, TlsTestSuite};
+pub use tls::{
+    BackendTlsTest, HttpsRedirectTest, RedirectCase, SniMismatchTest, TlsPolicyTest,
+    TlsTerminationTest, TlsTestSuite, BACKEND_TLS_HOSTNAME, BACKEND_TLS_PATH, BACKEND_TLS_PORT,
+    BACKEND_TLS_SERVICE, SNI_MISMATCH_BACKEND, SNI_MISMATCH_HOST,
+};
 
 // Re-export traffic tests
 pub use traffic::{
-    CanaryTrafficTest, RateLimitingTest, SessionAffinityTest, TimeoutRetryTest, TrafficTestSuite,
+    AffinityType, CanaryTrafficTest, ConsistentHashTest, RateLimitingTest, SessionAffinityTest,
+    TimeoutRetryTest, TrafficTestSuite, CONSISTENT_HASH_BACKEND, CONSISTENT_HASH_HEADER,
+    CONSISTENT_HASH_PATH, CONSISTENT_HASH_ROUTE, RATE_LIMIT_BURST, RATE_LIMIT_PATH,
+    RATE_LIMIT_ROUTE, RATE_LIMIT_RPS, SESSION_AFFINITY_BACKEND, SESSION_AFFINITY_PATH,
+    SESSION_AFFINITY_ROUTE, SESSION_COOKIE,
 };
 
+// Re-export progressive delivery scenario
+pub use progressive::{parse_steps, ProgressiveCanary};
+
+// Re-export experimental AI tests
+pub use ai::{AiBodyLimitTest, AiStreamingTest, AiTokenLatencyTest, AI_BACKEND, AI_ROUTE_PATH};
+
+// Re-export network tests
+pub use network::DualStackTest;
+
+// Re-export protocol tests
+pub use protocol::{
+    CompressionTest, CorsTest, ForwardedHeadersTest, HeaderHandlingTest, Http2Test, LargeBodyTest,
+    CORS_HEADER, CORS_METHODS, CORS_ORIGIN, CORS_PATH, CORS_POLICY, CORS_ROUTE,
+};
+
+// Re-export resilience tests
+pub use resilience::ZeroDowntimeReloadTest;
+
 // Re-export advanced tests
 pub use advanced::{
     AdvancedTestSuite, CrossNamespaceTest, FailoverRecoveryTest, GrpcRoutingTest,
-    HeaderModifierTest, HealthCheckTest, LoadTest, UrlRewriteTest,
+    HeaderModifierTest, HealthCheckTest, LoadTest, UrlRewriteTest, CROSS_NAMESPACE_TARGETS,
+    FAILOVER_BACKEND, HOSTNAME_REWRITE_PATH, HOSTNAME_REWRITE_TARGET,
 };
 
+use crate::deploy::{HttpRouteManifest, ManifestGenerator, PolicyManifest};
 use crate::http::HttpClient;
-use crate::models::{TestCase, TestResult};
+use crate::k8s::{
+    BackendTlsFixture, CrossNamespaceFixture, DeploymentScaler, FaultInjector, K8sClient,
+    PolicyFixture, RolloutRestarter,
+};
+use crate::models::{
+    BackendFault, CanarySettings, FailoverSettings, GatewayConfig, GatewayImpl, LoadTestSettings,
+    TestCase, TestResult,
+};
+use crate::utils::Teardown;
 use anyhow::Result;
 
-/// Run all 17 test cases
+/// gRPC listener port used when the gateway config has none
+pub const DEFAULT_GRPC_PORT: u16 = 9090;
+
+/// Service port of the test topology's echo backends
+const BACKEND_PORT: u16 = 8080;
+
+/// Run the suites' tests (1-18) against the gateway's configured ports;
+/// [`run_test`] covers the whole catalog
 pub async fn run_all_tests(
     gateway_ip: &str,
-    http_port: u16,
-    https_port: u16,
-    grpc_port: u16,
-    hostname: &str,
+    gateway: &GatewayConfig,
+    timeout_secs: u64,
 ) -> Result<Vec<TestResult>> {
-    let _client = HttpClient::new()?;
+    let client = HttpClient::with_timeout(timeout_secs)?;
+    let http_port = gateway.http_port;
+    let https_port = gateway.https_port;
+    let grpc_port = gateway.grpc_port.unwrap_or(DEFAULT_GRPC_PORT);
     let mut results = Vec::new();
 
     // Routing tests (1-3)
-    let routing_suite = RoutingTestSuite::new(gateway_ip, http_port)?;
+    let routing_suite = RoutingTestSuite::new(gateway_ip, http_port)?.with_client(client.clone());
     results.extend(routing_suite.run_all().await?);
 
     // TLS tests (4-6)
-    let tls_suite = TlsTestSuite::new(gateway_ip, http_port, https_port, hostname)?;
+    let tls_suite = TlsTestSuite::new(gateway_ip, http_port, https_port, &gateway.hostname)?
+        .with_client(client.clone());
     results.extend(tls_suite.run_all().await?);
 
-    // Traffic tests (7-10)
-    let traffic_suite = TrafficTestSuite::new(gateway_ip, http_port)?;
+    // Traffic tests (7-10, 18)
+    let traffic_suite = TrafficTestSuite::new(gateway_ip, http_port)?.with_client(client.clone());
     results.extend(traffic_suite.run_all().await?);
 
     // Advanced tests (11-17)
-    let advanced_suite = AdvancedTestSuite::new(gateway_ip, http_port, grpc_port)?;
+    let advanced_suite =
+        AdvancedTestSuite::new(gateway_ip, http_port, grpc_port)?.with_client(client);
     results.extend(advanced_suite.run_all().await?);
 
     Ok(results)
 }
 
-/// Run a specific test case
+/// Run a specific test case against the gateway's configured ports
+///
+/// The test's fixture, if any, is set up first; fixture changes register
+/// their undo actions with `teardown`.
+#[allow(clippy::too_many_arguments)]
 pub async fn run_test(
     test_case: TestCase,
+    client: &HttpClient,
     gateway_ip: &str,
-    http_port: u16,
-    https_port: u16,
-    grpc_port: u16,
-    hostname: &str,
+    gateway: &GatewayConfig,
+    load_test: &LoadTestSettings,
+    canary: &CanarySettings,
+    failover: &FailoverSettings,
+    teardown: &Teardown,
 ) -> Result<TestResult> {
-    let client = HttpClient::new()?;
+    // Best effort: without the fixture the objects may be configured by hand
+    let fixture = match test_fixture(test_case, client, gateway).await {
+        Some(fixture) => Some(match fixture.setup(teardown).await {
+            Ok(description) => format!("Fixture: {description}"),
+            Err(e) => format!("⚠ Fixture setup failed: {e:#}"),
+        }),
+        None => None,
+    };
+
+    let mut result = run_test_case(
+        test_case, client, gateway_ip, gateway, load_test, canary, failover, teardown,
+    )
+    .await?;
+    if let Some(fixture) = fixture {
+        result.message = Some(match result.message.take() {
+            Some(message) => format!("{fixture}\n{message}"),
+            None => fixture,
+        });
+    }
+    Ok(result)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_test_case(
+    test_case: TestCase,
+    client: &HttpClient,
+    gateway_ip: &str,
+    gateway: &GatewayConfig,
+    load_test: &LoadTestSettings,
+    canary: &CanarySettings,
+    failover: &FailoverSettings,
+    teardown: &Teardown,
+) -> Result<TestResult> {
+    let http_port = gateway.http_port;
+    let https_port = gateway.https_port;
+    let grpc_port = gateway.grpc_port.unwrap_or(DEFAULT_GRPC_PORT);
+    let hostname = gateway.hostname.as_str();
 
     match test_case {
         TestCase::HostRouting => {
             HostRoutingTest::new(gateway_ip, http_port)
                 .add_hostname("app1.example.com", "app1")
                 .add_hostname("app2.example.com", "app2")
-                .run(&client)
+                .run(client)
                 .await
         }
         TestCase::PathRouting => {
             PathRoutingTest::new(gateway_ip, http_port)
                 .add_prefix("/api/v1", "api-v1")
                 .add_prefix("/api/v2", "api-v2")
-                .run(&client)
+                .run(client)
                 .await
         }
         TestCase::HeaderRouting => {
             HeaderRoutingTest::new(gateway_ip, http_port)
                 .add_header_rule("X-Version", "v1", "version-v1")
-                .run(&client)
+                .run(client)
                 .await
         }
         TestCase::TlsTermination => {
             TlsTerminationTest::new(gateway_ip, https_port, hostname)
-                .run(&client)
+                .run(client)
                 .await
         }
         TestCase::HttpsRedirect => {
             HttpsRedirectTest::new(gateway_ip, http_port, https_port)
-                .run(&client)
+                .with_hostname(hostname)
+                .add_path("/api/login")
+                .run(client)
                 .await
         }
         TestCase::BackendTls => {
             BackendTlsTest::new(gateway_ip, https_port)
-                .run(&client)
+                .run(client)
                 .await
         }
         TestCase::CanaryTraffic => {
             CanaryTrafficTest::new(gateway_ip, http_port)
                 .add_backend("stable", 90)
                 .add_backend("canary", 10)
-                .run(&client)
+                .with_confidence(canary.confidence)
+                .run(client)
                 .await
         }
         TestCase::RateLimiting => {
             RateLimitingTest::new(gateway_ip, http_port)
-                .run(&client)
+                .run(client)
                 .await
         }
         TestCase::TimeoutRetry => {
             TimeoutRetryTest::new(gateway_ip, http_port)
-                .run(&client)
+                .run(client)
                 .await
         }
         TestCase::SessionAffinity => {
             SessionAffinityTest::new(gateway_ip, http_port)
-                .run(&client)
+                .with_affinity_type(affinity_type(gateway, client))
+                .with_source_addresses(client.config().source_addresses.clone())
+                .run(client)
                 .await
         }
         TestCase::UrlRewrite => {
             UrlRewriteTest::new(gateway_ip, http_port)
+                .with_hostname(hostname)
                 .add_rewrite("/old-api", "/new-api")
-                .run(&client)
+                .add_hostname_rewrite(HOSTNAME_REWRITE_PATH, HOSTNAME_REWRITE_TARGET)
+                .run(client)
                 .await
         }
         TestCase::HeaderModifier => {
             HeaderModifierTest::new(gateway_ip, http_port)
                 .expect_response_header("X-Gateway", "true")
-                .run(&client)
+                .run(client)
                 .await
         }
         TestCase::CrossNamespace => {
             CrossNamespaceTest::new(gateway_ip, http_port)
-                .add_route("/ns-a", "namespace-a", "service-a")
-                .run(&client)
+                .with_default_routes()
+                .run(client)
                 .await
         }
         TestCase::GrpcRouting => {
             GrpcRoutingTest::new(gateway_ip, grpc_port)
                 .add_service("helloworld.Greeter", "SayHello", "grpc-backend")
-                .run(&client)
+                .run(client)
                 .await
         }
         TestCase::HealthCheck => {
             HealthCheckTest::new(gateway_ip, http_port)
-                .run(&client)
+                .run(client)
                 .await
         }
         TestCase::LoadTest => {
-            LoadTest::new(gateway_ip, http_port)
-                .concurrent_users(10)
-                .total_requests(100)
-                .run(&client)
+            LoadTest::from_settings(gateway_ip, http_port, load_test)
+                .with_hostname(hostname)
+                .run(client)
                 .await
         }
         TestCase::FailoverRecovery => {
+            // Without access to the backend the test only observes
+            let injector = match failover.fault {
+                BackendFault::None => None,
+                _ => K8sClient::new(&gateway.namespace)
+                    .await
+                    .ok()
+                    .map(FaultInjector::new),
+            };
             FailoverRecoveryTest::new(gateway_ip, http_port)
-                .run(&client)
+                .with_fault(failover.fault, injector)
+                .with_teardown(teardown.clone())
+                .run(client)
+                .await
+        }
+        TestCase::ConsistentHashing => {
+            // Scaling the backend changes the cluster, so it comes with
+            // --setup-policies like the other fixtures
+            let scaler = if gateway.setup_policies {
+                K8sClient::new(&gateway.namespace)
+                    .await
+                    .ok()
+                    .map(DeploymentScaler::new)
+            } else {
+                None
+            };
+            ConsistentHashTest::new(gateway_ip, http_port)
+                .with_scaler(scaler)
+                .with_teardown(teardown.clone())
+                .run(client)
+                .await
+        }
+        TestCase::AiStreaming => {
+            AiStreamingTest::new(gateway_ip, http_port)
+                .run(client)
+                .await
+        }
+        TestCase::AiTokenLatency => {
+            AiTokenLatencyTest::new(gateway_ip, http_port)
+                .run(client)
+                .await
+        }
+        TestCase::AiBodyLimit => {
+            AiBodyLimitTest::new(gateway_ip, http_port)
+                .run(client)
                 .await
         }
+        TestCase::DualStack => DualStackTest::new(gateway_ip, http_port).run(client).await,
+        TestCase::WildcardHostname => {
+            WildcardHostnameTest::new(gateway_ip, http_port)
+                .run(client)
+                .await
+        }
+        TestCase::MissingHost => {
+            MissingHostTest::new(gateway_ip, http_port)
+                .run(client)
+                .await
+        }
+        TestCase::LargeBody => LargeBodyTest::new(gateway_ip, http_port).run(client).await,
+        TestCase::Cors => CorsTest::new(gateway_ip, http_port).run(client).await,
+        TestCase::Compression => {
+            CompressionTest::new(gateway_ip, http_port)
+                .run(client)
+                .await
+        }
+        TestCase::ForwardedHeaders => {
+            ForwardedHeadersTest::new(gateway_ip, http_port)
+                .run(client)
+                .await
+        }
+        TestCase::HeaderHandling => {
+            HeaderHandlingTest::new(gateway_ip, http_port)
+                .run(client)
+                .await
+        }
+        TestCase::SniMismatch => {
+            SniMismatchTest::new(gateway_ip, https_port, hostname)
+                .run(client)
+                .await
+        }
+        TestCase::TlsPolicy => {
+            TlsPolicyTest::new(gateway_ip, https_port, hostname)
+                .run(client)
+                .await
+        }
+        TestCase::Http2 => {
+            Http2Test::new(gateway_ip, https_port, hostname)
+                .run(client)
+                .await
+        }
+        TestCase::ZeroDowntimeReload => {
+            let restarter = K8sClient::new(&gateway.namespace)
+                .await
+                .ok()
+                .map(RolloutRestarter::new);
+            ZeroDowntimeReloadTest::new(
+                gateway_ip,
+                http_port,
+                &gateway.name,
+                gateway.implementation,
+            )
+            .with_hostname(hostname)
+            .with_gateway_namespace(gateway.pods_namespace())
+            .with_restarter(restarter)
+            .run(client)
+            .await
+        }
+        TestCase::RoutePropagation => {
+            // Creating and deleting a route on the user's Gateway changes the
+            // cluster, so it comes with --setup-policies like the fixtures
+            if !gateway.setup_policies {
+                return Ok(TestResult::skip(
+                    test_case,
+                    "Creates an HTTPRoute on the gateway; run with --setup-policies",
+                ));
+            }
+            let k8s = K8sClient::new(&gateway.namespace).await.ok();
+            RoutePropagationTest::new(gateway_ip, http_port, &gateway.name, gateway.implementation)
+                .with_k8s(k8s)
+                .with_teardown(teardown.clone())
+                .run(client)
+                .await
+        }
+    }
+}
+
+/// Cluster objects a test needs, set up by [`run_test`] before the test runs
+enum TestFixture {
+    Policy(PolicyFixture),
+    CrossNamespace(CrossNamespaceFixture),
+    BackendTls(BackendTlsFixture),
+}
+
+impl TestFixture {
+    /// Create the objects, registering their removal with `teardown`;
+    /// returns what was set up
+    async fn setup(&self, teardown: &Teardown) -> Result<String> {
+        match self {
+            TestFixture::Policy(fixture) => {
+                fixture.setup(teardown).await?;
+                Ok(fixture.describe().join(", "))
+            }
+            TestFixture::CrossNamespace(fixture) => {
+                fixture.setup(teardown).await?;
+                Ok(format!(
+                    "{} route(s) from {} with ReferenceGrants",
+                    fixture.targets().len(),
+                    fixture.route_namespace()
+                ))
+            }
+            TestFixture::BackendTls(fixture) => {
+                fixture.setup(teardown).await?;
+                Ok(format!(
+                    "TLS backend {} with {} certificate and backend TLS policy",
+                    fixture.route_name(),
+                    fixture.refs().ca_config_map
+                ))
+            }
+        }
+    }
+}
+
+/// Fixture for `test_case`; None unless policy setup is enabled, the
+/// implementation supports what the test needs, and the cluster is reachable
+async fn test_fixture(
+    test_case: TestCase,
+    client: &HttpClient,
+    gateway: &GatewayConfig,
+) -> Option<TestFixture> {
+    if !gateway.setup_policies {
+        return None;
+    }
+    match test_case {
+        TestCase::BackendTls => {
+            let k8s = K8sClient::new(&gateway.namespace).await.ok()?;
+            let fixture = BackendTlsFixture::new(
+                k8s,
+                &gateway.name,
+                BACKEND_TLS_SERVICE,
+                BACKEND_TLS_HOSTNAME,
+            )
+            .with_port(BACKEND_TLS_PORT)
+            .with_path(BACKEND_TLS_PATH)
+            .with_image_pull_secrets(gateway.image_pull_secrets.clone());
+            let refs = fixture.refs();
+            let policy = ManifestGenerator::new(gateway.implementation)
+                .namespace(&gateway.namespace)
+                .backend_tls_policy(
+                    "backend-tls",
+                    BACKEND_TLS_SERVICE,
+                    BACKEND_TLS_HOSTNAME,
+                    &refs.ca_config_map,
+                    &refs.client_secret,
+                )?;
+            Some(TestFixture::BackendTls(fixture.with_policy(&policy)))
+        }
+        TestCase::RateLimiting => policy_fixture(
+            gateway,
+            RATE_LIMIT_ROUTE,
+            RATE_LIMIT_PATH,
+            "echo",
+            |gen, route| {
+                let policy = gen.rate_limit_policy(
+                    "rate-limit",
+                    RATE_LIMIT_ROUTE,
+                    &gateway.name,
+                    RATE_LIMIT_RPS,
+                    RATE_LIMIT_BURST,
+                )?;
+                gen.attach_policy(route, &policy.metadata.name);
+                Some(policy)
+            },
+        )
+        .await
+        .map(TestFixture::Policy),
+        TestCase::SessionAffinity => {
+            let affinity = affinity_type(gateway, client);
+            policy_fixture(
+                gateway,
+                SESSION_AFFINITY_ROUTE,
+                SESSION_AFFINITY_PATH,
+                SESSION_AFFINITY_BACKEND,
+                |gen, _| match affinity {
+                    AffinityType::SourceIp => gen.source_ip_affinity_policy(
+                        "session-affinity",
+                        SESSION_AFFINITY_ROUTE,
+                        SESSION_AFFINITY_BACKEND,
+                    ),
+                    _ => gen.session_affinity_policy(
+                        "session-affinity",
+                        SESSION_AFFINITY_ROUTE,
+                        SESSION_AFFINITY_BACKEND,
+                        SESSION_COOKIE,
+                    ),
+                },
+            )
+            .await
+            .map(TestFixture::Policy)
+        }
+        TestCase::CrossNamespace => {
+            let k8s = K8sClient::new(&gateway.namespace).await.ok()?;
+            let fixture = CROSS_NAMESPACE_TARGETS.iter().fold(
+                CrossNamespaceFixture::new(k8s, &gateway.name)
+                    .with_image_pull_secrets(gateway.image_pull_secrets.clone()),
+                |fixture, (path, namespace, service)| {
+                    fixture.with_target(*path, *namespace, *service)
+                },
+            );
+            Some(TestFixture::CrossNamespace(fixture))
+        }
+        TestCase::ConsistentHashing => policy_fixture(
+            gateway,
+            CONSISTENT_HASH_ROUTE,
+            CONSISTENT_HASH_PATH,
+            CONSISTENT_HASH_BACKEND,
+            |gen, _| {
+                gen.consistent_hash_policy(
+                    "consistent-hash-policy",
+                    CONSISTENT_HASH_ROUTE,
+                    CONSISTENT_HASH_BACKEND,
+                    CONSISTENT_HASH_HEADER,
+                )
+            },
+        )
+        .await
+        .map(TestFixture::Policy),
+        TestCase::Cors => policy_fixture(gateway, CORS_ROUTE, CORS_PATH, "echo", |gen, route| {
+            let policy = gen.cors_policy(
+                CORS_POLICY,
+                CORS_ROUTE,
+                CORS_ORIGIN,
+                &CORS_METHODS,
+                &[CORS_HEADER],
+            )?;
+            gen.attach_policy(route, &policy.metadata.name);
+            Some(policy)
+        })
+        .await
+        .map(TestFixture::Policy),
+        _ => None,
+    }
+}
+
+/// Session affinity mode: NGINX hashes on the client IP; elsewhere client
+/// addresses to send from switch the test (and its policy) to source-IP
+/// affinity
+fn affinity_type(gateway: &GatewayConfig, client: &HttpClient) -> AffinityType {
+    if gateway.implementation == GatewayImpl::Nginx || !client.config().source_addresses.is_empty()
+    {
+        AffinityType::SourceIp
+    } else {
+        AffinityType::Cookie
+    }
+}
+
+/// Fixture applying an implementation-specific policy together with the route
+/// to `backend` it governs; None unless the implementation has the policy and
+/// the cluster is reachable
+///
+/// `policy` builds the policy and may adjust the route to attach it.
+async fn policy_fixture(
+    gateway: &GatewayConfig,
+    route_name: &str,
+    path: &str,
+    backend: &str,
+    policy: impl FnOnce(&ManifestGenerator, &mut HttpRouteManifest) -> Option<PolicyManifest>,
+) -> Option<PolicyFixture> {
+    let gen = ManifestGenerator::new(gateway.implementation).namespace(&gateway.namespace);
+    let mut route = gen.http_route_path(route_name, &gateway.name, path, backend, BACKEND_PORT);
+    let policy = policy(&gen, &mut route)?;
+    let k8s = K8sClient::new(&gateway.namespace).await.ok()?;
+
+    let mut fixture = PolicyFixture::new(k8s)
+        .with_manifest(&route)
+        .with_manifest(&policy)
+        .with_route(route_name);
+    if let Some((key, value)) = gen.policy_service_annotation(&policy) {
+        fixture = fixture.with_service_annotation(backend, key, value);
     }
+    Some(fixture)
 }
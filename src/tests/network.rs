@@ -0,0 +1,147 @@
+//! Network-level tests for Gateway API
+//!
+//! Test 22: Dual Stack
+
+use anyhow::Result;
+use std::net::IpAddr;
+use tracing::info;
+
+use crate::http::{resolve_all, HttpClient, IpFamily};
+use crate::models::{TestCase, TestResult, TestStatus};
+
+/// Test 22: Dual Stack
+///
+/// Resolves the gateway address in both families and sends requests over
+/// each. A single-family gateway is skipped unless `--ip-family dual`
+/// requires both.
+#[derive(Clone, Debug)]
+pub struct DualStackTest {
+    /// Gateway IP or dual-stack hostname
+    pub gateway: String,
+    pub gateway_port: u16,
+    pub path: String,
+}
+
+impl DualStackTest {
+    pub fn new(gateway: impl Into<String>, gateway_port: u16) -> Self {
+        Self {
+            gateway: gateway.into(),
+            gateway_port,
+            path: "/".to_string(),
+        }
+    }
+
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    pub async fn run(&self, client: &HttpClient) -> Result<TestResult> {
+        info!("Running Dual Stack Test");
+        let start = std::time::Instant::now();
+        let mut details = Vec::new();
+        let dns = &client.config().dns_overrides;
+
+        let status = match resolve_all(dns, &self.gateway).await {
+            Ok(addrs) => {
+                let v4 = addrs.iter().find(|a| a.is_ipv4()).copied();
+                let v6 = addrs.iter().find(|a| a.is_ipv6()).copied();
+                let mut routed = 0;
+                let mut failed = 0;
+                let mut missing = Vec::new();
+                for (family, addr) in [("IPv4", v4), ("IPv6", v6)] {
+                    match addr {
+                        Some(addr) if self.check(client, family, addr, &mut details).await => {
+                            routed += 1
+                        }
+                        Some(_) => failed += 1,
+                        None => missing.push(family),
+                    }
+                }
+
+                if failed > 0 {
+                    TestStatus::Fail
+                } else if missing.is_empty() {
+                    details.push("✓ Both address families route".to_string());
+                    TestStatus::Pass
+                } else if dns.family == IpFamily::Dual || routed == 0 {
+                    details.push(format!(
+                        "✗ No {} address for {}",
+                        missing.join("/"),
+                        self.gateway
+                    ));
+                    TestStatus::Fail
+                } else {
+                    details.push(format!(
+                        "○ No {} address for {}; pass a dual-stack hostname as --ip \
+                         (--ip-family dual requires both)",
+                        missing.join("/"),
+                        self.gateway
+                    ));
+                    TestStatus::Skip
+                }
+            }
+            Err(e) => {
+                details.push(format!("✗ Could not resolve {}: {e}", self.gateway));
+                TestStatus::Fail
+            }
+        };
+
+        Ok(TestResult {
+            test_case: TestCase::DualStack,
+            status,
+            duration_ms: start.elapsed().as_millis() as u64,
+            message: Some(details.join("\n")),
+            details: None,
+            retries: 0,
+            backends: Vec::new(),
+        })
+    }
+
+    /// Request the path over one family's address
+    async fn check(
+        &self,
+        client: &HttpClient,
+        family: &str,
+        addr: IpAddr,
+        details: &mut Vec<String>,
+    ) -> bool {
+        match client
+            .test_path_routing(&addr.to_string(), self.gateway_port, &self.path)
+            .await
+        {
+            Ok(resp) if resp.is_success() => {
+                details.push(format!(
+                    "✓ {family} {addr}: {} ({}ms)",
+                    resp.status_code, resp.duration_ms
+                ));
+                true
+            }
+            Ok(resp) => {
+                details.push(format!("✗ {family} {addr}: status {}", resp.status_code));
+                false
+            }
+            Err(e) => {
+                details.push(format!("✗ {family} {addr}: {e}"));
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_unreachable_family_fails() {
+        // Nothing listens on the discard port, so the IPv4 request fails
+        let client = HttpClient::with_timeout(1).unwrap();
+        let result = DualStackTest::new("127.0.0.1", 9)
+            .run(&client)
+            .await
+            .unwrap();
+        assert_eq!(result.status, TestStatus::Fail);
+        assert!(result.message.unwrap().contains("✗ IPv4 127.0.0.1"));
+    }
+}
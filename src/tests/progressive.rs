@@ -0,0 +1,515 @@
+//! Progressive canary delivery scenario
+//!
+//! Emulates a progressive delivery controller: walks a canary through
+//! increasing route weights (1% → 5% → 25% → 50% → 100% by default),
+//! measures how long each weight change takes to reach the data plane and
+//! validates the observed split against a binomial confidence interval.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+use crate::http::{authority, HttpClient};
+use crate::k8s::HTTPRouteManager;
+use crate::models::CanarySettings;
+
+/// Default canary weight steps in percent
+pub const DEFAULT_STEPS: [u32; 5] = [1, 5, 25, 50, 100];
+
+/// Progressive canary rollout over an existing HTTPRoute rule
+#[derive(Clone, Debug)]
+pub struct ProgressiveCanary {
+    pub gateway_ip: String,
+    pub gateway_port: u16,
+    pub path: String,
+    pub hostname: String,
+    /// HTTPRoute holding the weighted rule
+    pub route: String,
+    pub namespace: String,
+    /// Rule index within the route
+    pub rule: usize,
+    pub stable: String,
+    pub canary: String,
+    /// Canary weights in percent, in rollout order
+    pub steps: Vec<u32>,
+    /// Requests used to validate each step
+    pub samples: usize,
+    /// Requests per propagation probe window
+    pub probe_size: usize,
+    /// Confidence level for distribution bounds (0.99)
+    pub confidence: f64,
+    pub propagation_timeout: Duration,
+    /// Restore 100% stable when a step fails
+    pub rollback: bool,
+}
+
+/// Outcome of one rollout step
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CanaryStep {
+    /// Target canary weight in percent
+    pub weight: u32,
+    /// Time until the data plane reflected the new weight
+    /// (None if the change is too small to detect with one probe window)
+    pub propagation_ms: Option<u64>,
+    /// Whether propagation timed out
+    pub propagation_timed_out: bool,
+    /// Responses attributed to a backend
+    pub samples: usize,
+    pub canary_hits: usize,
+    /// Requests that failed or could not be attributed
+    pub unattributed: usize,
+    /// Observed canary share in percent
+    pub observed_percent: f64,
+    /// Confidence interval of the observed share in percent
+    pub lower_percent: f64,
+    pub upper_percent: f64,
+    /// Whether the target weight lies within the interval
+    pub within_bounds: bool,
+}
+
+/// Result of a progressive rollout
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProgressiveCanaryResult {
+    pub route: String,
+    pub stable: String,
+    pub canary: String,
+    pub confidence: f64,
+    pub steps: Vec<CanaryStep>,
+    /// Whether every step passed and the rollout reached its final weight
+    pub promoted: bool,
+    /// Whether traffic was rolled back to stable
+    pub rolled_back: bool,
+    pub duration_ms: u64,
+}
+
+impl ProgressiveCanary {
+    pub fn new(
+        gateway_ip: impl Into<String>,
+        gateway_port: u16,
+        route: impl Into<String>,
+        stable: impl Into<String>,
+        canary: impl Into<String>,
+    ) -> Self {
+        Self {
+            gateway_ip: gateway_ip.into(),
+            gateway_port,
+            path: "/".to_string(),
+            hostname: "example.com".to_string(),
+            route: route.into(),
+            namespace: "default".to_string(),
+            rule: 0,
+            stable: stable.into(),
+            canary: canary.into(),
+            steps: DEFAULT_STEPS.to_vec(),
+            samples: 400,
+            probe_size: 50,
+            confidence: CanarySettings::default().confidence,
+            propagation_timeout: Duration::from_secs(60),
+            rollback: true,
+        }
+    }
+
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    pub fn with_hostname(mut self, hostname: impl Into<String>) -> Self {
+        self.hostname = hostname.into();
+        self
+    }
+
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = namespace.into();
+        self
+    }
+
+    pub fn with_rule(mut self, rule: usize) -> Self {
+        self.rule = rule;
+        self
+    }
+
+    pub fn with_steps(mut self, steps: Vec<u32>) -> Self {
+        self.steps = steps;
+        self
+    }
+
+    pub fn with_samples(mut self, samples: usize, probe_size: usize) -> Self {
+        self.samples = samples.max(1);
+        self.probe_size = probe_size.max(1);
+        self
+    }
+
+    pub fn with_confidence(mut self, confidence: f64) -> Self {
+        self.confidence = confidence;
+        self
+    }
+
+    pub fn with_propagation_timeout(mut self, timeout: Duration) -> Self {
+        self.propagation_timeout = timeout;
+        self
+    }
+
+    pub fn with_rollback(mut self, rollback: bool) -> Self {
+        self.rollback = rollback;
+        self
+    }
+
+    fn url(&self) -> String {
+        format!(
+            "http://{}{}",
+            authority(&self.gateway_ip, self.gateway_port),
+            self.path
+        )
+    }
+
+    /// Walk the canary through every step
+    pub async fn run(
+        &self,
+        client: &HttpClient,
+        routes: &HTTPRouteManager,
+    ) -> Result<ProgressiveCanaryResult> {
+        let start = Instant::now();
+        let z = z_score(self.confidence);
+        let mut steps = Vec::new();
+        let mut previous = 0;
+        let mut promoted = true;
+
+        info!(
+            "Progressive canary on {}/{}: {} -> {} ({:?}%)",
+            self.namespace, self.route, self.stable, self.canary, self.steps
+        );
+
+        for &weight in &self.steps {
+            if let Err(e) = self.set_weight(routes, weight).await {
+                // Earlier steps already moved traffic to the canary
+                if self.rollback && !steps.is_empty() {
+                    warn!("Canary step failed, rolling back to {}", self.stable);
+                    if let Err(rollback) = self.set_weight(routes, 0).await {
+                        return Err(e.context(format!("Rollback also failed: {rollback:#}")));
+                    }
+                }
+                return Err(e);
+            }
+            let changed = Instant::now();
+
+            // Wait for the data plane to reflect the change, if detectable
+            let detectable = is_detectable(previous, weight, self.probe_size, z);
+            let mut propagation_ms = None;
+            let mut timed_out = false;
+            if detectable {
+                loop {
+                    let (hits, attributed, _) = self.sample(client, self.probe_size).await;
+                    if reflects(weight, previous, hits, attributed, z) {
+                        propagation_ms = Some(changed.elapsed().as_millis() as u64);
+                        break;
+                    }
+                    if changed.elapsed() >= self.propagation_timeout {
+                        timed_out = true;
+                        break;
+                    }
+                }
+            }
+
+            let (hits, attributed, unattributed) = self.sample(client, self.samples).await;
+            let step = evaluate_step(weight, hits, attributed, unattributed, z)
+                .with_propagation(propagation_ms, timed_out);
+
+            info!(
+                "  {:>3}%: observed {:.2}% [{:.2}, {:.2}] propagation {} -> {}",
+                weight,
+                step.observed_percent,
+                step.lower_percent,
+                step.upper_percent,
+                step.propagation_ms
+                    .map(|ms| format!("{ms}ms"))
+                    .unwrap_or_else(|| "n/a".to_string()),
+                if step.passed() { "ok" } else { "failed" }
+            );
+
+            let passed = step.passed();
+            steps.push(step);
+            if !passed {
+                promoted = false;
+                break;
+            }
+            previous = weight;
+        }
+
+        let mut rolled_back = false;
+        if !promoted && self.rollback {
+            warn!("Canary step failed, rolling back to {}", self.stable);
+            self.set_weight(routes, 0).await?;
+            rolled_back = true;
+        }
+
+        Ok(ProgressiveCanaryResult {
+            route: self.route.clone(),
+            stable: self.stable.clone(),
+            canary: self.canary.clone(),
+            confidence: self.confidence,
+            steps,
+            promoted,
+            rolled_back,
+            duration_ms: start.elapsed().as_millis() as u64,
+        })
+    }
+
+    async fn set_weight(&self, routes: &HTTPRouteManager, weight: u32) -> Result<()> {
+        let canary = weight.min(100) as i32;
+        routes
+            .set_weights(
+                &self.route,
+                &self.namespace,
+                self.rule,
+                &[(&self.stable, 100 - canary), (&self.canary, canary)],
+            )
+            .await
+            .with_context(|| format!("Failed to set canary weight to {weight}%"))?;
+        Ok(())
+    }
+
+    /// Send `count` requests, returning (canary hits, attributed, unattributed)
+    async fn sample(&self, client: &HttpClient, count: usize) -> (usize, usize, usize) {
+        let url = self.url();
+        let mut canary_hits = 0;
+        let mut attributed = 0;
+
+        for _ in 0..count {
+            if let Ok(resp) = client.get_with_host(&url, &self.hostname).await {
+                if !resp.is_success() {
+                    continue;
+                }
+                let Some(echo) = resp.echo() else {
+                    continue;
+                };
+                if echo.served_by(&self.canary) {
+                    canary_hits += 1;
+                    attributed += 1;
+                } else if echo.served_by(&self.stable) {
+                    attributed += 1;
+                }
+            }
+        }
+
+        (canary_hits, attributed, count - attributed)
+    }
+}
+
+impl CanaryStep {
+    fn with_propagation(mut self, propagation_ms: Option<u64>, timed_out: bool) -> Self {
+        self.propagation_ms = propagation_ms;
+        self.propagation_timed_out = timed_out;
+        self
+    }
+
+    /// Whether the step met its distribution and propagation checks
+    pub fn passed(&self) -> bool {
+        self.within_bounds && !self.propagation_timed_out && self.samples > 0
+    }
+}
+
+impl ProgressiveCanaryResult {
+    /// Longest observed propagation time
+    pub fn max_propagation_ms(&self) -> Option<u64> {
+        self.steps.iter().filter_map(|s| s.propagation_ms).max()
+    }
+
+    /// Table of steps
+    pub fn format_table(&self) -> String {
+        let mut output = format!(
+            "Progressive canary {} -> {} on {} ({}% confidence)\n",
+            self.stable,
+            self.canary,
+            self.route,
+            self.confidence * 100.0
+        );
+        output.push_str(&format!(
+            "{:>7} {:>10} {:>20} {:>9} {:>13}  Result\n",
+            "Weight", "Observed", "Interval", "Samples", "Propagation"
+        ));
+        output.push_str(&format!("{:-<75}\n", ""));
+
+        for step in &self.steps {
+            let propagation = match (step.propagation_ms, step.propagation_timed_out) {
+                (_, true) => "timeout".to_string(),
+                (Some(ms), _) => format!("{ms}ms"),
+                (None, _) => "n/a".to_string(),
+            };
+            output.push_str(&format!(
+                "{:>6}% {:>9.2}% {:>20} {:>9} {:>13}  {}\n",
+                step.weight,
+                step.observed_percent,
+                format!("[{:.2}, {:.2}]", step.lower_percent, step.upper_percent),
+                step.samples,
+                propagation,
+                if step.passed() { "✓" } else { "✗" }
+            ));
+        }
+
+        output.push_str(&format!("{:-<75}\n", ""));
+        output.push_str(&match (self.promoted, self.rolled_back) {
+            (true, _) => "Canary promoted\n".to_string(),
+            (false, true) => format!("Canary failed; rolled back to {}\n", self.stable),
+            (false, false) => "Canary failed; weights left in place\n".to_string(),
+        });
+        if let Some(ms) = self.max_propagation_ms() {
+            output.push_str(&format!("Max propagation time: {ms}ms\n"));
+        }
+
+        output
+    }
+}
+
+/// Evaluate a step's observed distribution against its target weight
+pub fn evaluate_step(
+    weight: u32,
+    canary_hits: usize,
+    attributed: usize,
+    unattributed: usize,
+    z: f64,
+) -> CanaryStep {
+    let (lower, upper) = wilson_interval(canary_hits, attributed, z);
+    let target = weight as f64 / 100.0;
+    let observed = if attributed > 0 {
+        canary_hits as f64 / attributed as f64
+    } else {
+        0.0
+    };
+
+    CanaryStep {
+        weight,
+        propagation_ms: None,
+        propagation_timed_out: false,
+        samples: attributed,
+        canary_hits,
+        unattributed,
+        observed_percent: observed * 100.0,
+        lower_percent: lower * 100.0,
+        upper_percent: upper * 100.0,
+        within_bounds: attributed > 0 && (lower..=upper).contains(&target),
+    }
+}
+
+/// Wilson score interval for a binomial proportion
+pub fn wilson_interval(successes: usize, trials: usize, z: f64) -> (f64, f64) {
+    if trials == 0 {
+        return (0.0, 1.0);
+    }
+    wilson_bounds(successes as f64 / trials as f64, trials, z)
+}
+
+/// Wilson score interval for proportion `p` observed over `trials`
+fn wilson_bounds(p: f64, trials: usize, z: f64) -> (f64, f64) {
+    let n = trials as f64;
+    let z2 = z * z;
+    let denom = 1.0 + z2 / n;
+    let center = (p + z2 / (2.0 * n)) / denom;
+    let half = z * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt() / denom;
+    ((center - half).max(0.0), (center + half).min(1.0))
+}
+
+/// Two-sided z-score for a confidence level (0.99)
+pub fn z_score(confidence: f64) -> f64 {
+    upper_quantile((1.0 - confidence.clamp(0.5, 0.99999)) / 2.0)
+}
+
+/// Point of the standard normal distribution exceeded with probability
+/// `alpha` (at most 0.5)
+pub fn upper_quantile(alpha: f64) -> f64 {
+    // Abramowitz & Stegun 26.2.23
+    let t = (-2.0 * alpha.clamp(1e-9, 0.5).ln()).sqrt();
+    t - (2.515517 + 0.802853 * t + 0.010328 * t * t)
+        / (1.0 + 1.432788 * t + 0.189269 * t * t + 0.001308 * t * t * t)
+}
+
+/// Whether a window of `n` requests can tell the new weight from the old one
+fn is_detectable(previous: u32, weight: u32, n: usize, z: f64) -> bool {
+    let (new, old) = (weight as f64 / 100.0, previous as f64 / 100.0);
+    let (new_lower, new_upper) = wilson_bounds(new, n.max(1), z);
+    let (old_lower, old_upper) = wilson_bounds(old, n.max(1), z);
+    !(new_lower..=new_upper).contains(&old) && !(old_lower..=old_upper).contains(&new)
+}
+
+/// Whether a probe window matches the new weight and not the old one
+fn reflects(weight: u32, previous: u32, hits: usize, attributed: usize, z: f64) -> bool {
+    if attributed == 0 {
+        return false;
+    }
+    let (lower, upper) = wilson_interval(hits, attributed, z);
+    let range = lower..=upper;
+    range.contains(&(weight as f64 / 100.0)) && !range.contains(&(previous as f64 / 100.0))
+}
+
+/// Parse comma-separated canary steps ("1,5,25,50,100")
+pub fn parse_steps(s: &str) -> Result<Vec<u32>> {
+    let steps: Vec<u32> = s
+        .split(',')
+        .map(|p| {
+            p.trim()
+                .trim_end_matches('%')
+                .parse::<u32>()
+                .with_context(|| format!("Invalid canary step '{p}'"))
+        })
+        .collect::<Result<_>>()?;
+
+    if steps.is_empty() || steps.iter().any(|&w| w > 100) {
+        anyhow::bail!("Canary steps must be between 0 and 100");
+    }
+    if steps.windows(2).any(|w| w[0] >= w[1]) {
+        anyhow::bail!("Canary steps must be increasing");
+    }
+    Ok(steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_z_score() {
+        assert!((z_score(0.95) - 1.96).abs() < 0.01);
+        assert!((z_score(0.99) - 2.576).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_wilson_interval() {
+        let z = z_score(0.99);
+        let (lower, upper) = wilson_interval(0, 400, z);
+        assert_eq!(lower, 0.0);
+        assert!(upper > 0.01 && upper < 0.03);
+
+        let (lower, upper) = wilson_interval(100, 400, z);
+        assert!(lower < 0.25 && upper > 0.25);
+        assert!(!(lower..=upper).contains(&0.5));
+    }
+
+    #[test]
+    fn test_evaluate_step() {
+        let z = z_score(0.99);
+        assert!(evaluate_step(25, 104, 400, 0, z).passed());
+        assert!(!evaluate_step(25, 200, 400, 0, z).passed());
+        assert!(evaluate_step(100, 400, 400, 0, z).passed());
+        assert!(!evaluate_step(5, 0, 0, 400, z).passed());
+    }
+
+    #[test]
+    fn test_detectability_and_reflects() {
+        let z = z_score(0.99);
+        // 1% vs 0% cannot be told apart with 50 requests
+        assert!(!is_detectable(0, 1, 50, z));
+        assert!(is_detectable(25, 50, 50, z));
+
+        assert!(reflects(50, 25, 26, 50, z));
+        assert!(!reflects(50, 25, 13, 50, z));
+    }
+
+    #[test]
+    fn test_parse_steps() {
+        assert_eq!(parse_steps("1,5,25,50,100").unwrap(), DEFAULT_STEPS);
+        assert_eq!(parse_steps("10%, 100%").unwrap(), vec![10, 100]);
+        assert!(parse_steps("50,25").is_err());
+        assert!(parse_steps("150").is_err());
+    }
+}
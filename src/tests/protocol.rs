@@ -0,0 +1,1678 @@
+//! HTTP protocol handling tests for Gateway API
+//!
+//! Test 26: Large Body
+//! Test 27: Header Handling
+//! Test 28: Forwarded Headers
+//! Test 29: Compression
+//! Test 30: CORS
+//! Test 32: HTTP/2
+
+use anyhow::Result;
+use std::net::{IpAddr, UdpSocket};
+use tracing::{debug, info};
+
+use crate::http::{
+    authority, resolve_all, HttpClient, HttpRequest, HttpResponse, RawResponse, TlsConnection,
+    TlsProbe,
+};
+use crate::models::{TestCase, TestResult, TestStatus, BEHAVIOR_DETAIL};
+
+/// Header sent twice by the header handling test
+pub const DUPLICATE_HEADER: &str = "X-Dup-Test";
+
+/// Header sent with a mixed-case name
+pub const MIXED_CASE_HEADER: &str = "X-MiXeD-CaSe-Test";
+
+/// Header named in Connection, so hop-by-hop for that request
+pub const CONNECTION_HEADER: &str = "X-Hop-Test";
+
+/// Client address claimed in the forwarding headers of the spoofed request
+/// (TEST-NET-3, never a real client)
+pub const SPOOFED_CLIENT: &str = "203.0.113.7";
+
+/// Route carrying the CORS policy
+pub const CORS_ROUTE: &str = "cors";
+
+/// CORS policy applied to [`CORS_ROUTE`]
+pub const CORS_POLICY: &str = "cors-policy";
+
+/// Path of the CORS route
+pub const CORS_PATH: &str = "/cors";
+
+/// Origin the CORS policy allows
+pub const CORS_ORIGIN: &str = "https://allowed.example.com";
+
+/// Origin the CORS policy does not allow
+pub const CORS_OTHER_ORIGIN: &str = "https://other.example.com";
+
+/// Methods the CORS policy allows
+pub const CORS_METHODS: [&str; 3] = ["GET", "POST", "PUT"];
+
+/// Request header the CORS policy allows
+pub const CORS_HEADER: &str = "X-Cors-Test";
+
+/// Header numbering the streams of the HTTP/2 test
+pub const STREAM_HEADER: &str = "X-Stream-Id";
+
+/// Concurrent streams the HTTP/2 test opens on one connection
+const HTTP2_STREAMS: usize = 20;
+
+/// Header padding the echoed response so it is worth compressing
+const COMPRESSION_PADDING: usize = 4 * 1024;
+
+/// Accept-Encoding values sent by the compression test (None = no header)
+const ACCEPT_ENCODINGS: [Option<&str>; 6] = [
+    None,
+    Some("identity"),
+    Some("gzip"),
+    Some("br"),
+    Some("gzip, br"),
+    Some("gzip;q=0, br;q=0"),
+];
+
+const KIB: usize = 1024;
+const MIB: usize = 1024 * KIB;
+
+/// Slowest upload rate (bytes per second) a large body is given time for,
+/// on top of the client timeout
+const MIN_UPLOAD_RATE: usize = MIB;
+
+/// Test 26: Large request bodies
+///
+/// Streams bodies of increasing size through the gateway (chunked, so the
+/// gateway decides whether to buffer) until one is refused. Default body
+/// limits differ enormously between implementations, so the largest
+/// accepted size, buffering and latency are reported rather than judged;
+/// only a rejected 1 KiB body fails the test.
+#[derive(Clone, Debug)]
+pub struct LargeBodyTest {
+    pub gateway_ip: String,
+    pub gateway_port: u16,
+    pub path: String,
+    /// Body sizes in bytes, smallest first
+    pub sizes: Vec<usize>,
+}
+
+/// Outcome of one body size
+#[derive(Clone, Debug, PartialEq)]
+struct BodyStep {
+    size: usize,
+    /// Response status (None = connection error)
+    status: Option<u16>,
+    duration_ms: u64,
+    /// The backend saw a Content-Length instead of a chunked body
+    buffered: Option<bool>,
+}
+
+impl BodyStep {
+    fn accepted(&self) -> bool {
+        self.status.is_some_and(|s| (200..300).contains(&s))
+    }
+}
+
+impl LargeBodyTest {
+    pub fn new(gateway_ip: impl Into<String>, gateway_port: u16) -> Self {
+        Self {
+            gateway_ip: gateway_ip.into(),
+            gateway_port,
+            path: "/".to_string(),
+            sizes: vec![KIB, 64 * KIB, MIB, 10 * MIB, 100 * MIB],
+        }
+    }
+
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    pub fn with_sizes(mut self, sizes: Vec<usize>) -> Self {
+        self.sizes = sizes;
+        self
+    }
+
+    pub async fn run(&self, client: &HttpClient) -> Result<TestResult> {
+        info!("Running Large Body Test");
+        let start = std::time::Instant::now();
+        let mut details = Vec::new();
+        let url = format!(
+            "http://{}{}",
+            authority(&self.gateway_ip, self.gateway_port),
+            self.path
+        );
+
+        let mut steps = Vec::new();
+        for &size in &self.sizes {
+            debug!("Sending {} byte body", size);
+            let request = HttpRequest::post(&url)
+                .header("Content-Type", "application/octet-stream")
+                .filler(size, true)
+                .timeout(body_timeout(client.config().timeout_secs, size));
+            let step = match client.send(request).await {
+                Ok(resp) => BodyStep {
+                    size,
+                    status: Some(resp.status_code),
+                    duration_ms: resp.duration_ms,
+                    buffered: upstream_buffered(&resp),
+                },
+                Err(e) => {
+                    details.push(format!("✗ {}: {e}", format_size(size)));
+                    BodyStep {
+                        size,
+                        status: None,
+                        duration_ms: 0,
+                        buffered: None,
+                    }
+                }
+            };
+            let accepted = step.accepted();
+            if let Some(status) = step.status {
+                let mark = if accepted { "✓" } else { "✗" };
+                details.push(format!(
+                    "{mark} {}: {status} ({}ms)",
+                    format_size(size),
+                    step.duration_ms
+                ));
+            }
+            steps.push(step);
+            if !accepted {
+                break;
+            }
+        }
+
+        let status = if steps.first().is_some_and(BodyStep::accepted) {
+            details.extend(summarize(&steps));
+            TestStatus::Pass
+        } else {
+            details.push("✗ Small request bodies are not accepted".to_string());
+            TestStatus::Fail
+        };
+
+        Ok(TestResult {
+            test_case: TestCase::LargeBody,
+            status,
+            duration_ms: start.elapsed().as_millis() as u64,
+            message: Some(details.join("\n")),
+            details: None,
+            retries: 0,
+            backends: Vec::new(),
+        })
+    }
+}
+
+/// Time allowed for a `size` byte upload: the client timeout plus the time
+/// the body takes at [`MIN_UPLOAD_RATE`]
+fn body_timeout(timeout_secs: u64, size: usize) -> std::time::Duration {
+    std::time::Duration::from_secs(timeout_secs + (size / MIN_UPLOAD_RATE) as u64)
+}
+
+/// Whether the gateway buffered the chunked upload, from the framing the
+/// echo backend reports receiving
+fn upstream_buffered(resp: &HttpResponse) -> Option<bool> {
+    if resp
+        .echoed_header("transfer-encoding")
+        .is_some_and(|te| te.to_ascii_lowercase().contains("chunked"))
+    {
+        Some(false)
+    } else if resp.echoed_header("content-length").is_some() {
+        Some(true)
+    } else {
+        None
+    }
+}
+
+/// Largest accepted size, where the limit lies, buffering and latency
+fn summarize(steps: &[BodyStep]) -> Vec<String> {
+    let mut lines = Vec::new();
+    let accepted: Vec<&BodyStep> = steps.iter().filter(|s| s.accepted()).collect();
+    let Some(largest) = accepted.last() else {
+        return lines;
+    };
+
+    match steps.iter().find(|s| !s.accepted()) {
+        Some(refused) => {
+            let how = match refused.status {
+                Some(413) => "413 Payload Too Large".to_string(),
+                Some(status) => format!("status {status}"),
+                None => "connection error".to_string(),
+            };
+            lines.push(format!(
+                "Max accepted body: {} (limit below {}, refused with {how})",
+                format_size(largest.size),
+                format_size(refused.size)
+            ));
+            if refused.status != Some(413) {
+                lines.push("⚠ Oversized bodies should be refused with 413".to_string());
+            }
+        }
+        None => lines.push(format!(
+            "Max accepted body: {} (no limit reached)",
+            format_size(largest.size)
+        )),
+    }
+
+    match largest.buffered {
+        Some(true) => lines.push("Buffering: gateway buffers request bodies".to_string()),
+        Some(false) => lines.push("Buffering: request bodies are streamed".to_string()),
+        None => {}
+    }
+
+    let smallest = accepted[0];
+    if largest.size > smallest.size {
+        let per_mib = largest.duration_ms.saturating_sub(smallest.duration_ms) as f64
+            / (largest.size - smallest.size) as f64
+            * MIB as f64;
+        lines.push(format!(
+            "Latency: {}ms at {} vs {}ms at {} ({per_mib:.1}ms per MiB)",
+            largest.duration_ms,
+            format_size(largest.size),
+            smallest.duration_ms,
+            format_size(smallest.size)
+        ));
+    }
+    lines
+}
+
+fn format_size(bytes: usize) -> String {
+    if bytes >= MIB && bytes.is_multiple_of(MIB) {
+        format!("{} MiB", bytes / MIB)
+    } else if bytes >= KIB && bytes.is_multiple_of(KIB) {
+        format!("{} KiB", bytes / KIB)
+    } else {
+        format!("{bytes} B")
+    }
+}
+
+/// Test 27: Header handling
+///
+/// Sends, over a raw connection, a duplicated header, a mixed-case header
+/// name and hop-by-hop headers (Connection and the header it names,
+/// Keep-Alive, TE, Transfer-Encoding), then reads what the echo backend
+/// received. Dropped values or forwarded hop-by-hop headers fail; how
+/// names and duplicates were normalized is recorded for comparison.
+#[derive(Clone, Debug)]
+pub struct HeaderHandlingTest {
+    pub gateway_ip: String,
+    pub gateway_port: u16,
+    pub path: String,
+}
+
+impl HeaderHandlingTest {
+    pub fn new(gateway_ip: impl Into<String>, gateway_port: u16) -> Self {
+        Self {
+            gateway_ip: gateway_ip.into(),
+            gateway_port,
+            path: "/".to_string(),
+        }
+    }
+
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    fn request(&self) -> String {
+        [
+            format!("POST {} HTTP/1.1", self.path),
+            format!("Host: {}", authority(&self.gateway_ip, self.gateway_port)),
+            format!("{DUPLICATE_HEADER}: first"),
+            format!("{DUPLICATE_HEADER}: second"),
+            format!("{MIXED_CASE_HEADER}: mixed"),
+            format!("Connection: close, {CONNECTION_HEADER}"),
+            format!("{CONNECTION_HEADER}: secret"),
+            "Keep-Alive: timeout=5".to_string(),
+            "TE: trailers, deflate".to_string(),
+            "Transfer-Encoding: chunked".to_string(),
+            String::new(),
+            "5\r\nhello\r\n0\r\n\r\n".to_string(),
+        ]
+        .join("\r\n")
+    }
+
+    pub async fn run(&self, client: &HttpClient) -> Result<TestResult> {
+        info!("Running Header Handling Test");
+        let start = std::time::Instant::now();
+        let mut details = Vec::new();
+
+        let status = match client
+            .send_raw(&self.gateway_ip, self.gateway_port, &self.request())
+            .await
+        {
+            Ok(Some(raw)) if (200..300).contains(&raw.status) => {
+                let echoed = raw.into_response().echoed_headers();
+                if echoed.is_empty() {
+                    details.push("○ Backend does not echo request headers".to_string());
+                    TestStatus::Skip
+                } else {
+                    let passed = check_headers(&echoed, &mut details);
+                    if passed {
+                        TestStatus::Pass
+                    } else {
+                        TestStatus::Fail
+                    }
+                }
+            }
+            Ok(Some(raw)) => {
+                details.push(format!("✗ Request rejected with {}", raw.status));
+                TestStatus::Fail
+            }
+            Ok(None) => {
+                details.push("✗ Connection closed without a response".to_string());
+                TestStatus::Fail
+            }
+            Err(e) => {
+                details.push(format!("✗ {e:#}"));
+                TestStatus::Fail
+            }
+        };
+
+        Ok(TestResult {
+            test_case: TestCase::HeaderHandling,
+            status,
+            duration_ms: start.elapsed().as_millis() as u64,
+            message: Some(details.join("\n")),
+            details: None,
+            retries: 0,
+            backends: Vec::new(),
+        })
+    }
+}
+
+/// Check the headers the backend received, returning whether all passed
+fn check_headers(echoed: &[(String, String)], details: &mut Vec<String>) -> bool {
+    let mut passed = true;
+    let named = |name: &str| -> Vec<&(String, String)> {
+        echoed
+            .iter()
+            .filter(|(key, _)| key.eq_ignore_ascii_case(name))
+            .collect()
+    };
+
+    let duplicates = named(DUPLICATE_HEADER);
+    let joined = duplicates
+        .iter()
+        .map(|(_, value)| value.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    if joined.contains("first") && joined.contains("second") {
+        if duplicates.len() > 1 {
+            details.push(format!(
+                "✓ Duplicate header kept as {} fields",
+                duplicates.len()
+            ));
+        } else {
+            details.push(format!("✓ Duplicate header merged into \"{joined}\""));
+        }
+    } else {
+        passed = false;
+        details.push(format!(
+            "✗ Duplicate header values lost (backend saw \"{joined}\")"
+        ));
+    }
+
+    match named(MIXED_CASE_HEADER).first() {
+        Some((name, value)) if value == "mixed" => {
+            let case = if name == MIXED_CASE_HEADER {
+                "name case preserved".to_string()
+            } else if *name == MIXED_CASE_HEADER.to_ascii_lowercase() {
+                "name lowercased".to_string()
+            } else {
+                format!("name sent as {name}")
+            };
+            details.push(format!("✓ Mixed-case header forwarded ({case})"));
+        }
+        _ => {
+            passed = false;
+            details.push(format!("✗ {MIXED_CASE_HEADER} did not reach the backend"));
+        }
+    }
+
+    let mut hop_by_hop_removed = true;
+    for name in [CONNECTION_HEADER, "Keep-Alive"] {
+        if !named(name).is_empty() {
+            hop_by_hop_removed = false;
+            details.push(format!("✗ Hop-by-hop header {name} forwarded"));
+        }
+    }
+    let hop = CONNECTION_HEADER.to_ascii_lowercase();
+    if named("Connection")
+        .iter()
+        .any(|(_, value)| value.to_ascii_lowercase().contains(&hop))
+    {
+        hop_by_hop_removed = false;
+        details.push("✗ Client Connection header forwarded".to_string());
+    }
+    match named("TE").first() {
+        Some((_, value)) if value.trim().eq_ignore_ascii_case("trailers") => {
+            details.push("○ TE reduced to \"trailers\"".to_string())
+        }
+        Some((_, value)) => {
+            hop_by_hop_removed = false;
+            details.push(format!("✗ Hop-by-hop header TE forwarded (\"{value}\")"));
+        }
+        None => {}
+    }
+    if hop_by_hop_removed {
+        details.push("✓ Hop-by-hop headers removed".to_string());
+    }
+    passed &= hop_by_hop_removed;
+
+    let framing = match (
+        named("Transfer-Encoding").first(),
+        named("Content-Length").first(),
+    ) {
+        (Some((_, te)), _) => format!("Transfer-Encoding {te}"),
+        (None, Some((_, length))) => format!("Content-Length {length}"),
+        (None, None) => "not reported".to_string(),
+    };
+    details.push(format!("○ Upstream body framing: {framing}"));
+    passed
+}
+
+/// Test 28: Forwarded headers
+///
+/// Reads the X-Forwarded-For, Forwarded and X-Real-IP headers the echo
+/// backend receives, once for a plain request and once for a request
+/// already carrying a (spoofed) chain. The gateway must add the client
+/// address and append to or replace a client-supplied chain, never pass it
+/// on as is. How it does so is recorded as the run's behavior for the
+/// gateway comparison.
+#[derive(Clone, Debug)]
+pub struct ForwardedHeadersTest {
+    pub gateway_ip: String,
+    pub gateway_port: u16,
+    pub path: String,
+}
+
+/// Forwarding headers the backend received
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct Forwarding {
+    xff: Option<String>,
+    forwarded: Option<String>,
+    real_ip: Option<String>,
+}
+
+impl Forwarding {
+    fn from_response(resp: &HttpResponse) -> Self {
+        Self {
+            xff: resp.echoed_header("x-forwarded-for"),
+            forwarded: resp.echoed_header("forwarded"),
+            real_ip: resp.echoed_header("x-real-ip"),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.xff.is_none() && self.forwarded.is_none() && self.real_ip.is_none()
+    }
+
+    /// Client address as the gateway saw it: the last X-Forwarded-For hop,
+    /// else X-Real-IP, else the last `for=` of Forwarded
+    fn client(&self) -> Option<String> {
+        let last_xff = self
+            .xff
+            .as_deref()
+            .and_then(|xff| xff.rsplit(',').next())
+            .map(|hop| hop.trim().to_string());
+        let last_for = || {
+            self.forwarded.as_deref().and_then(|forwarded| {
+                forwarded
+                    .rsplit([',', ';'])
+                    .find_map(|pair| pair.trim().strip_prefix("for="))
+                    .map(|node| {
+                        let node = node.trim_matches('"');
+                        node.trim_start_matches('[')
+                            .split(']')
+                            .next()
+                            .unwrap_or(node)
+                            .to_string()
+                    })
+            })
+        };
+        last_xff.or_else(|| self.real_ip.clone()).or_else(last_for)
+    }
+}
+
+impl ForwardedHeadersTest {
+    pub fn new(gateway_ip: impl Into<String>, gateway_port: u16) -> Self {
+        Self {
+            gateway_ip: gateway_ip.into(),
+            gateway_port,
+            path: "/".to_string(),
+        }
+    }
+
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    pub async fn run(&self, client: &HttpClient) -> Result<TestResult> {
+        info!("Running Forwarded Headers Test");
+        let start = std::time::Instant::now();
+        let mut details = Vec::new();
+        let url = format!(
+            "http://{}{}",
+            authority(&self.gateway_ip, self.gateway_port),
+            self.path
+        );
+
+        let plain = client.send(HttpRequest::get(&url)).await;
+        let spoofed = client
+            .send(
+                HttpRequest::get(&url)
+                    .header("X-Forwarded-For", SPOOFED_CLIENT)
+                    .header("Forwarded", format!("for={SPOOFED_CLIENT}")),
+            )
+            .await;
+
+        let mut behavior = None;
+        let status = match (plain, spoofed) {
+            (Err(e), _) | (_, Err(e)) => {
+                details.push(format!("✗ Request failed: {e}"));
+                TestStatus::Fail
+            }
+            (Ok(plain), Ok(spoofed)) => {
+                if let Some(resp) = [&plain, &spoofed].into_iter().find(|r| !r.is_success()) {
+                    details.push(format!("✗ Request returned {}", resp.status_code));
+                    TestStatus::Fail
+                } else {
+                    let plain = Forwarding::from_response(&plain);
+                    if plain.is_empty() && spoofed.echoed_headers().is_empty() {
+                        details.push("○ Backend does not echo request headers".to_string());
+                        TestStatus::Skip
+                    } else {
+                        let client_ip = self.local_ip(client).await;
+                        let spoofed = Forwarding::from_response(&spoofed);
+                        let (passed, summary) =
+                            check_forwarding(&plain, &spoofed, client_ip, &mut details);
+                        behavior = Some(summary);
+                        if passed {
+                            TestStatus::Pass
+                        } else {
+                            TestStatus::Fail
+                        }
+                    }
+                }
+            }
+        };
+
+        Ok(TestResult {
+            test_case: TestCase::ForwardedHeaders,
+            status,
+            duration_ms: start.elapsed().as_millis() as u64,
+            message: Some(details.join("\n")),
+            details: behavior.map(|behavior| serde_json::json!({ BEHAVIOR_DETAIL: behavior })),
+            retries: 0,
+            backends: Vec::new(),
+        })
+    }
+
+    /// Local address used towards the gateway (what an un-NATed gateway sees)
+    async fn local_ip(&self, client: &HttpClient) -> Option<IpAddr> {
+        if let Some(address) = client.config().local_address {
+            return Some(address);
+        }
+        let gateway = resolve_all(&client.config().dns_overrides, &self.gateway_ip)
+            .await
+            .ok()?
+            .into_iter()
+            .next()?;
+        // Connecting a UDP socket only selects the route; nothing is sent
+        let bind = if gateway.is_ipv4() {
+            "0.0.0.0:0"
+        } else {
+            "[::]:0"
+        };
+        let socket = UdpSocket::bind(bind).ok()?;
+        socket.connect((gateway, self.gateway_port)).ok()?;
+        Some(socket.local_addr().ok()?.ip())
+    }
+}
+
+/// Check the forwarding headers of the plain and the spoofed request,
+/// returning whether they pass and a one-line behavior summary
+fn check_forwarding(
+    plain: &Forwarding,
+    spoofed: &Forwarding,
+    client_ip: Option<IpAddr>,
+    details: &mut Vec<String>,
+) -> (bool, String) {
+    let mut passed = true;
+    let mut behavior = Vec::new();
+
+    for (name, value) in [
+        ("X-Forwarded-For", &plain.xff),
+        ("Forwarded", &plain.forwarded),
+        ("X-Real-IP", &plain.real_ip),
+    ] {
+        match value {
+            Some(value) => details.push(format!("✓ {name}: {value}")),
+            None => details.push(format!("○ {name} not set")),
+        }
+    }
+
+    match (plain.client(), client_ip) {
+        (None, _) => {
+            passed = false;
+            details.push("✗ Gateway does not pass the client address on".to_string());
+            behavior.push("client IP not forwarded".to_string());
+        }
+        (Some(seen), Some(ip)) if seen == ip.to_string() => {
+            details.push(format!("✓ Client IP preserved ({seen})"));
+            behavior.push("client IP kept".to_string());
+        }
+        (Some(seen), Some(ip)) => {
+            details.push(format!(
+                "⚠ Client seen as {seen}, not {ip} (NAT or SNAT before the gateway)"
+            ));
+            behavior.push(format!("client seen as {seen}"));
+        }
+        (Some(seen), None) => {
+            details.push(format!("✓ Client seen as {seen}"));
+            behavior.push(format!("client seen as {seen}"));
+        }
+    }
+
+    // A client-supplied chain must be appended to or replaced, never trusted as is
+    let xff_mode = match spoofed.xff.as_deref().map(str::trim) {
+        Some(xff) if xff == SPOOFED_CLIENT => {
+            passed = false;
+            details.push("✗ Client-supplied X-Forwarded-For passed on unchanged".to_string());
+            "XFF passed through"
+        }
+        Some(xff) if xff.starts_with(SPOOFED_CLIENT) => {
+            details.push(format!("✓ X-Forwarded-For appended ({xff})"));
+            "XFF appended"
+        }
+        Some(xff) => {
+            details.push(format!("✓ X-Forwarded-For replaced ({xff})"));
+            "XFF replaced"
+        }
+        None => {
+            details.push("○ X-Forwarded-For dropped".to_string());
+            "XFF dropped"
+        }
+    };
+    behavior.insert(0, xff_mode.to_string());
+    if spoofed.real_ip.as_deref() == Some(SPOOFED_CLIENT) {
+        details.push("⚠ X-Real-IP taken from the client-supplied chain".to_string());
+        behavior.push("X-Real-IP spoofable".to_string());
+    }
+    if plain.forwarded.is_some() {
+        behavior.push("Forwarded".to_string());
+    }
+
+    (passed, behavior.join(", "))
+}
+
+/// Test 29: Compression
+///
+/// Requests a padded (compressible) echo with several Accept-Encoding
+/// values over raw connections, so the body arrives exactly as the gateway
+/// sent it. The echo backend never compresses, so an encoded response was
+/// compressed by the gateway. Encodings the client did not accept, gzip
+/// bodies that are not gzip and encoded responses without
+/// `Vary: Accept-Encoding` are reported. Clients accepting no encoding must
+/// get an identity body; a gateway that asks the backend for an encoding on
+/// their behalf decompresses for them. Whether the gateway compresses,
+/// decompresses, passes Accept-Encoding through or strips it is recorded as
+/// its behavior.
+#[derive(Clone, Debug)]
+pub struct CompressionTest {
+    pub gateway_ip: String,
+    pub gateway_port: u16,
+    pub path: String,
+}
+
+/// Outcome of one Accept-Encoding value
+#[derive(Clone, Debug)]
+struct EncodingStep {
+    accept: Option<&'static str>,
+    content_encoding: Option<String>,
+    vary: Option<String>,
+    body: Vec<u8>,
+    /// Accept-Encoding the backend received
+    upstream_accept: Option<String>,
+}
+
+impl CompressionTest {
+    pub fn new(gateway_ip: impl Into<String>, gateway_port: u16) -> Self {
+        Self {
+            gateway_ip: gateway_ip.into(),
+            gateway_port,
+            path: "/".to_string(),
+        }
+    }
+
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    fn request(&self, accept: Option<&str>) -> String {
+        let mut lines = vec![
+            format!("GET {} HTTP/1.1", self.path),
+            format!("Host: {}", authority(&self.gateway_ip, self.gateway_port)),
+            format!(
+                "X-Padding: {}",
+                "compress-me ".repeat(COMPRESSION_PADDING / 12)
+            ),
+            "Connection: close".to_string(),
+        ];
+        if let Some(accept) = accept {
+            lines.push(format!("Accept-Encoding: {accept}"));
+        }
+        lines.push(String::new());
+        lines.push(String::new());
+        lines.join("\r\n")
+    }
+
+    pub async fn run(&self, client: &HttpClient) -> Result<TestResult> {
+        info!("Running Compression Test");
+        let start = std::time::Instant::now();
+        let mut details = Vec::new();
+        let mut steps = Vec::new();
+        let mut failed = false;
+
+        for accept in ACCEPT_ENCODINGS {
+            let label = accept.unwrap_or("(none)");
+            match client
+                .send_raw(&self.gateway_ip, self.gateway_port, &self.request(accept))
+                .await
+            {
+                Ok(Some(raw)) if (200..300).contains(&raw.status) => {
+                    debug!(
+                        "Accept-Encoding {label}: {} bytes, {:?}",
+                        raw.body.len(),
+                        raw.header("content-encoding")
+                    );
+                    steps.push(EncodingStep::from_raw(accept, raw));
+                }
+                Ok(Some(raw)) => {
+                    details.push(format!("✗ Accept-Encoding {label}: status {}", raw.status));
+                    failed = true;
+                }
+                Ok(None) => {
+                    details.push(format!("✗ Accept-Encoding {label}: no response"));
+                    failed = true;
+                }
+                Err(e) => {
+                    details.push(format!("✗ Accept-Encoding {label}: {e:#}"));
+                    failed = true;
+                }
+            }
+        }
+
+        let mut behavior = None;
+        if !steps.is_empty() {
+            let (passed, summary) = check_compression(&steps, &mut details);
+            failed |= !passed;
+            behavior = Some(summary);
+        }
+        let status = if failed {
+            TestStatus::Fail
+        } else {
+            TestStatus::Pass
+        };
+
+        Ok(TestResult {
+            test_case: TestCase::Compression,
+            status,
+            duration_ms: start.elapsed().as_millis() as u64,
+            message: Some(details.join("\n")),
+            details: behavior.map(|behavior| serde_json::json!({ BEHAVIOR_DETAIL: behavior })),
+            retries: 0,
+            backends: Vec::new(),
+        })
+    }
+}
+
+impl EncodingStep {
+    fn from_raw(accept: Option<&'static str>, raw: RawResponse) -> Self {
+        let content_encoding = raw
+            .header("content-encoding")
+            .map(|e| e.trim().to_ascii_lowercase())
+            .filter(|e| e != "identity");
+        let vary = raw.header("vary").map(str::to_string);
+        // Only an identity body can be read for what the backend received
+        let upstream_accept = content_encoding
+            .is_none()
+            .then(|| raw.clone().into_response().echoed_header("accept-encoding"))
+            .flatten();
+        Self {
+            accept,
+            content_encoding,
+            vary,
+            body: raw.body,
+            upstream_accept,
+        }
+    }
+
+    /// Accept-Encoding the gateway sent the backend for a client that
+    /// accepted no compression: the gateway decompresses for that client
+    fn decompressed_for_client(&self) -> Option<&str> {
+        let compressions = ["gzip", "br", "deflate", "zstd"];
+        if compressions.iter().any(|e| self.accepts(e)) {
+            return None;
+        }
+        self.upstream_accept.as_deref().filter(|upstream| {
+            upstream.split(',').any(|item| {
+                let name = item.split(';').next().unwrap_or_default().trim();
+                compressions.iter().any(|e| name.eq_ignore_ascii_case(e))
+            })
+        })
+    }
+
+    /// Whether the client accepted `encoding` (q=0 refuses it)
+    fn accepts(&self, encoding: &str) -> bool {
+        self.accept.is_some_and(|accept| {
+            accept.split(',').any(|item| {
+                let mut parts = item.split(';').map(str::trim);
+                let name = parts.next().unwrap_or_default();
+                let refused = parts.any(|p| p.replace(' ', "") == "q=0");
+                (name.eq_ignore_ascii_case(encoding) || name == "*") && !refused
+            })
+        })
+    }
+}
+
+/// Check each response's encoding and headers, returning whether they pass
+/// and a one-line behavior summary
+fn check_compression(steps: &[EncodingStep], details: &mut Vec<String>) -> (bool, String) {
+    let mut passed = true;
+    let identity_len = steps
+        .iter()
+        .find(|s| s.content_encoding.is_none())
+        .map(|s| s.body.len());
+    let mut used: Vec<&str> = Vec::new();
+    let mut decompresses = false;
+
+    for step in steps {
+        let label = step.accept.unwrap_or("(none)");
+        let Some(encoding) = step.content_encoding.as_deref() else {
+            match step.decompressed_for_client() {
+                Some(upstream) => {
+                    decompresses = true;
+                    details.push(format!(
+                        "✓ Accept-Encoding {label}: identity body ({} bytes), backend asked \
+                         for {upstream} (decompressed by the gateway)",
+                        step.body.len()
+                    ));
+                }
+                None => details.push(format!(
+                    "○ Accept-Encoding {label}: not compressed ({} bytes)",
+                    step.body.len()
+                )),
+            }
+            continue;
+        };
+        if !step.accepts(encoding) {
+            passed = false;
+            details.push(format!(
+                "✗ Accept-Encoding {label}: answered with {encoding}, which was not accepted"
+            ));
+            continue;
+        }
+        if encoding == "gzip" && !step.body.starts_with(&[0x1f, 0x8b]) {
+            passed = false;
+            details.push(format!(
+                "✗ Accept-Encoding {label}: Content-Encoding gzip but the body is not gzip"
+            ));
+            continue;
+        }
+        let ratio = identity_len
+            .filter(|&len| len > 0)
+            .map(|len| {
+                format!(
+                    ", {:.0}% of identity",
+                    step.body.len() as f64 / len as f64 * 100.0
+                )
+            })
+            .unwrap_or_default();
+        details.push(format!(
+            "✓ Accept-Encoding {label}: {encoding} ({} bytes{ratio})",
+            step.body.len()
+        ));
+        let varies = step.vary.as_deref().is_some_and(|vary| {
+            vary.split(',')
+                .any(|v| v.trim().eq_ignore_ascii_case("accept-encoding") || v.trim() == "*")
+        });
+        if !varies {
+            details.push(format!(
+                "⚠ Accept-Encoding {label}: encoded response without Vary: Accept-Encoding"
+            ));
+        }
+        if !used.contains(&encoding) {
+            used.push(encoding);
+        }
+    }
+
+    let behavior = if !used.is_empty() {
+        let mut behavior = format!("compresses ({})", used.join(", "));
+        if decompresses {
+            behavior.push_str(", decompresses");
+        }
+        behavior
+    } else if decompresses {
+        "decompresses".to_string()
+    } else {
+        // Without compression, the gateway either forwards Accept-Encoding
+        // (leaving it to the backend) or strips it
+        let forwarded = steps
+            .iter()
+            .filter(|s| s.accept.is_some())
+            .all(|s| s.upstream_accept.is_some());
+        if forwarded {
+            details.push("Accept-Encoding is passed through to the backend".to_string());
+            "passes through".to_string()
+        } else {
+            details.push("Accept-Encoding is stripped before the backend".to_string());
+            "strips Accept-Encoding".to_string()
+        }
+    };
+    (passed, behavior)
+}
+
+/// Test 30: CORS
+///
+/// Sends OPTIONS preflights to a route with the implementation's CORS
+/// policy, from the allowed origin and from another one, then a simple
+/// cross-origin GET. The policy allows credentials, so the allowed origin
+/// must be echoed (not `*`) with Access-Control-Allow-Credentials and the
+/// matching -Methods/-Headers; the other origin must not be allowed.
+#[derive(Clone, Debug)]
+pub struct CorsTest {
+    pub gateway_ip: String,
+    pub gateway_port: u16,
+    pub path: String,
+}
+
+impl CorsTest {
+    pub fn new(gateway_ip: impl Into<String>, gateway_port: u16) -> Self {
+        Self {
+            gateway_ip: gateway_ip.into(),
+            gateway_port,
+            path: CORS_PATH.to_string(),
+        }
+    }
+
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    fn preflight(&self, url: &str, origin: &str) -> HttpRequest {
+        HttpRequest::new("OPTIONS", url)
+            .header("Origin", origin)
+            .header("Access-Control-Request-Method", "PUT")
+            .header(
+                "Access-Control-Request-Headers",
+                CORS_HEADER.to_ascii_lowercase(),
+            )
+    }
+
+    pub async fn run(&self, client: &HttpClient) -> Result<TestResult> {
+        info!("Running CORS Test");
+        let start = std::time::Instant::now();
+        let mut details = Vec::new();
+
+        let url = format!(
+            "http://{}{}",
+            authority(&self.gateway_ip, self.gateway_port),
+            self.path
+        );
+        let mut passed = true;
+
+        match client.send(self.preflight(&url, CORS_ORIGIN)).await {
+            Ok(resp) => passed &= check_preflight(&resp, &mut details),
+            Err(e) => {
+                details.push(format!("✗ Preflight failed: {e:#}"));
+                passed = false;
+            }
+        }
+
+        match client.send(self.preflight(&url, CORS_OTHER_ORIGIN)).await {
+            Ok(resp) => match resp.get_header("access-control-allow-origin") {
+                Some(allowed) if allowed == CORS_OTHER_ORIGIN || allowed == "*" => {
+                    details.push(format!(
+                        "✗ Preflight from {CORS_OTHER_ORIGIN} allowed ({allowed})"
+                    ));
+                    passed = false;
+                }
+                _ => details.push(format!(
+                    "✓ Preflight from {CORS_OTHER_ORIGIN} not allowed ({})",
+                    resp.status_code
+                )),
+            },
+            Err(e) => {
+                details.push(format!(
+                    "✗ Preflight from {CORS_OTHER_ORIGIN} failed: {e:#}"
+                ));
+                passed = false;
+            }
+        }
+
+        match client
+            .send(HttpRequest::get(&url).header("Origin", CORS_ORIGIN))
+            .await
+        {
+            Ok(resp) if resp.is_success() => match resp.get_header("access-control-allow-origin") {
+                Some(allowed) if allowed == CORS_ORIGIN => {
+                    details.push(format!("✓ Cross-origin GET allowed ({allowed})"))
+                }
+                other => {
+                    details.push(format!(
+                        "✗ Cross-origin GET without matching Access-Control-Allow-Origin ({})",
+                        other.map(String::as_str).unwrap_or("none")
+                    ));
+                    passed = false;
+                }
+            },
+            Ok(resp) => {
+                details.push(format!("✗ Cross-origin GET returned {}", resp.status_code));
+                passed = false;
+            }
+            Err(e) => {
+                details.push(format!("✗ Cross-origin GET failed: {e:#}"));
+                passed = false;
+            }
+        }
+
+        Ok(TestResult {
+            test_case: TestCase::Cors,
+            status: if passed {
+                TestStatus::Pass
+            } else {
+                TestStatus::Fail
+            },
+            duration_ms: start.elapsed().as_millis() as u64,
+            message: Some(details.join("\n")),
+            details: None,
+            retries: 0,
+            backends: Vec::new(),
+        })
+    }
+}
+
+/// Check the answer to the preflight from the allowed origin, returning
+/// whether it passes
+fn check_preflight(resp: &HttpResponse, details: &mut Vec<String>) -> bool {
+    if !resp.is_success() {
+        details.push(format!("✗ Preflight returned {}", resp.status_code));
+        return false;
+    }
+    let mut passed = true;
+    // The policy allows credentials, and browsers treat `*` in a credentialed
+    // answer as a literal value rather than a wildcard
+    let lists = |name: &str, wanted: &str| {
+        resp.get_header(name).is_some_and(|values| {
+            values
+                .split(',')
+                .any(|v| v.trim().eq_ignore_ascii_case(wanted))
+        })
+    };
+
+    match resp.get_header("access-control-allow-credentials") {
+        Some(value) if value.eq_ignore_ascii_case("true") => {
+            details.push("✓ Access-Control-Allow-Credentials: true".to_string());
+        }
+        other => {
+            details.push(format!(
+                "✗ Access-Control-Allow-Credentials: {} (the policy allows credentials)",
+                other.map(String::as_str).unwrap_or("missing")
+            ));
+            passed = false;
+        }
+    }
+    match resp.get_header("access-control-allow-origin") {
+        Some(origin) if origin == CORS_ORIGIN => {
+            details.push(format!("✓ Access-Control-Allow-Origin: {origin}"));
+            if !lists("vary", "origin") {
+                details.push("⚠ Origin-specific answer without Vary: Origin".to_string());
+            }
+        }
+        Some(origin) if origin == "*" => {
+            details.push(
+                "✗ Access-Control-Allow-Origin: * (browsers reject it with credentials)"
+                    .to_string(),
+            );
+            passed = false;
+        }
+        other => {
+            details.push(format!(
+                "✗ Access-Control-Allow-Origin: {}",
+                other.map(String::as_str).unwrap_or("missing")
+            ));
+            passed = false;
+        }
+    }
+    for (header, wanted) in [
+        ("access-control-allow-methods", "PUT"),
+        ("access-control-allow-headers", CORS_HEADER),
+    ] {
+        if lists(header, wanted) {
+            details.push(format!("✓ {header} includes {wanted}"));
+        } else {
+            details.push(format!(
+                "✗ {header} does not include {wanted} ({})",
+                resp.get_header(header)
+                    .map(String::as_str)
+                    .unwrap_or("missing")
+            ));
+            passed = false;
+        }
+    }
+    if let Some(max_age) = resp.get_header("access-control-max-age") {
+        details.push(format!("Access-Control-Max-Age: {max_age}"));
+    }
+    // A gateway-answered preflight never reaches the echo backend
+    if !resp.echoed_headers().is_empty() {
+        details.push("⚠ Preflight was forwarded to the backend".to_string());
+    }
+    passed
+}
+
+/// Test 32: HTTP/2
+///
+/// Records which protocol ALPN selects on the HTTPS listener when a client
+/// offers h2 and http/1.1. When it is h2, sends concurrent requests as
+/// streams of one connection and checks each gets its own response. A
+/// client offering only http/1.1 must still be served. Listeners without h2
+/// pass; the negotiated protocol is what gateway comparisons show.
+#[derive(Clone, Debug)]
+pub struct Http2Test {
+    pub gateway_ip: String,
+    pub https_port: u16,
+    pub hostname: String,
+    pub streams: usize,
+}
+
+impl Http2Test {
+    pub fn new(
+        gateway_ip: impl Into<String>,
+        https_port: u16,
+        hostname: impl Into<String>,
+    ) -> Self {
+        Self {
+            gateway_ip: gateway_ip.into(),
+            https_port,
+            hostname: hostname.into(),
+            streams: HTTP2_STREAMS,
+        }
+    }
+
+    pub fn with_streams(mut self, streams: usize) -> Self {
+        self.streams = streams.max(1);
+        self
+    }
+
+    pub async fn run(&self, client: &HttpClient) -> Result<TestResult> {
+        info!("Running HTTP/2 Test");
+        let start = std::time::Instant::now();
+        let mut details = Vec::new();
+        let config = client.config();
+
+        let ip = resolve_all(&config.dns_overrides, &self.gateway_ip)
+            .await
+            .ok()
+            .and_then(|addrs| addrs.first().copied());
+        let (status, behavior) = match ip {
+            Some(ip) => {
+                let probe = TlsProbe::new(&self.hostname)
+                    .with_timeout(config.connect_timeout_secs)
+                    .with_accept_invalid_certs(true);
+                let timeout = std::time::Duration::from_secs(config.timeout_secs);
+                let (negotiated, behavior) = self
+                    .check_negotiated(&probe, ip, timeout, &mut details)
+                    .await;
+                let fallback = self.check_fallback(&probe, ip, timeout, &mut details).await;
+                let behavior = if fallback {
+                    behavior
+                } else {
+                    format!("{behavior}, no HTTP/1.1 fallback")
+                };
+                let status = if negotiated && fallback {
+                    TestStatus::Pass
+                } else {
+                    TestStatus::Fail
+                };
+                (status, Some(behavior))
+            }
+            None => {
+                details.push(format!("✗ Could not resolve {}", self.gateway_ip));
+                (TestStatus::Fail, None)
+            }
+        };
+
+        Ok(TestResult {
+            test_case: TestCase::Http2,
+            status,
+            duration_ms: start.elapsed().as_millis() as u64,
+            message: Some(details.join("\n")),
+            details: behavior.map(|behavior| serde_json::json!({ BEHAVIOR_DETAIL: behavior })),
+            retries: 0,
+            backends: Vec::new(),
+        })
+    }
+
+    /// Offer h2 and http/1.1, then use what was selected
+    async fn check_negotiated(
+        &self,
+        probe: &TlsProbe,
+        ip: IpAddr,
+        timeout: std::time::Duration,
+        details: &mut Vec<String>,
+    ) -> (bool, String) {
+        let connection = match TlsConnection::open(probe, ip, self.https_port).await {
+            Ok(connection) => connection,
+            Err(e) => {
+                details.push(format!("✗ TLS handshake offering h2, http/1.1: {e:#}"));
+                return (false, "handshake failed".to_string());
+            }
+        };
+        let alpn = connection.tls().alpn.clone();
+        details.push(format!(
+            "○ ALPN offering h2, http/1.1: {}",
+            alpn.as_deref().unwrap_or("none")
+        ));
+
+        if !connection.is_http2() {
+            details.push("⚠ HTTP/2 is not offered on the HTTPS listener".to_string());
+            let passed = self.single_request(&connection, timeout, details).await;
+            return (
+                passed,
+                format!("{} only", alpn.as_deref().unwrap_or("no ALPN")),
+            );
+        }
+
+        let ids: Vec<String> = (1..=self.streams).map(|i| i.to_string()).collect();
+        let started = std::time::Instant::now();
+        let responses = futures::future::join_all(ids.iter().map(|id| async {
+            match tokio::time::timeout(
+                timeout,
+                connection.get(&self.hostname, "/", &[(STREAM_HEADER, id.as_str())]),
+            )
+            .await
+            {
+                Ok(result) => result,
+                Err(_) => Err(anyhow::anyhow!("timed out after {}s", timeout.as_secs())),
+            }
+        }))
+        .await;
+        let elapsed = started.elapsed().as_millis();
+        let (passed, summary) = check_streams(&ids, &responses, details);
+        details.push(format!(
+            "○ {} streams on one connection took {elapsed}ms",
+            ids.len()
+        ));
+        (passed, format!("h2, {summary}"))
+    }
+
+    /// Offer only http/1.1, as clients without HTTP/2 do
+    async fn check_fallback(
+        &self,
+        probe: &TlsProbe,
+        ip: IpAddr,
+        timeout: std::time::Duration,
+        details: &mut Vec<String>,
+    ) -> bool {
+        let probe = probe.clone().with_alpn(vec!["http/1.1".to_string()]);
+        match TlsConnection::open(&probe, ip, self.https_port).await {
+            Ok(connection) => {
+                details.push(format!(
+                    "○ ALPN offering http/1.1: {}",
+                    connection.tls().alpn.as_deref().unwrap_or("none")
+                ));
+                self.single_request(&connection, timeout, details).await
+            }
+            Err(e) => {
+                details.push(format!("✗ TLS handshake offering only http/1.1: {e:#}"));
+                false
+            }
+        }
+    }
+
+    async fn single_request(
+        &self,
+        connection: &TlsConnection,
+        timeout: std::time::Duration,
+        details: &mut Vec<String>,
+    ) -> bool {
+        let protocol = if connection.is_http2() {
+            "HTTP/2"
+        } else {
+            "HTTP/1.1"
+        };
+        match tokio::time::timeout(timeout, connection.get(&self.hostname, "/", &[])).await {
+            Ok(Ok(resp)) if resp.is_success() => {
+                details.push(format!(
+                    "✓ {protocol} request: {} ({}ms)",
+                    resp.status_code, resp.duration_ms
+                ));
+                true
+            }
+            Ok(Ok(resp)) => {
+                details.push(format!(
+                    "✗ {protocol} request returned {}",
+                    resp.status_code
+                ));
+                false
+            }
+            Ok(Err(e)) => {
+                details.push(format!("✗ {protocol} request failed: {e:#}"));
+                false
+            }
+            Err(_) => {
+                details.push(format!(
+                    "✗ {protocol} request timed out after {}s",
+                    timeout.as_secs()
+                ));
+                false
+            }
+        }
+    }
+}
+
+/// Check concurrent streams each got a successful response of their own;
+/// returns whether they did and a summary for the comparison
+fn check_streams(
+    ids: &[String],
+    responses: &[Result<HttpResponse>],
+    details: &mut Vec<String>,
+) -> (bool, String) {
+    let mut ok = 0;
+    let mut mixed_up = 0;
+    let mut unverified = 0;
+    for (id, response) in ids.iter().zip(responses) {
+        match response {
+            Ok(resp) if resp.is_success() => {
+                let echoed = resp
+                    .echoed_headers()
+                    .into_iter()
+                    .find(|(name, _)| name.eq_ignore_ascii_case(STREAM_HEADER))
+                    .map(|(_, value)| value);
+                match echoed {
+                    Some(value) if value == *id => ok += 1,
+                    Some(value) => {
+                        details.push(format!("✗ Stream {id} got the response of stream {value}"));
+                        mixed_up += 1;
+                    }
+                    None => {
+                        ok += 1;
+                        unverified += 1;
+                    }
+                }
+            }
+            Ok(resp) => details.push(format!("✗ Stream {id} returned {}", resp.status_code)),
+            Err(e) => details.push(format!("✗ Stream {id} failed: {e:#}")),
+        }
+    }
+
+    let total = ids.len();
+    let passed = ok == total && mixed_up == 0;
+    let symbol = if passed { "✓" } else { "✗" };
+    details.push(format!(
+        "{symbol} {ok}/{total} concurrent streams succeeded"
+    ));
+    if unverified > 0 {
+        details.push(format!(
+            "○ Backend does not echo {STREAM_HEADER}; {unverified} responses not matched to their stream"
+        ));
+    }
+    (passed, format!("{ok}/{total} streams"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(size: usize, status: Option<u16>, duration_ms: u64) -> BodyStep {
+        BodyStep {
+            size,
+            status,
+            duration_ms,
+            buffered: Some(true),
+        }
+    }
+
+    #[test]
+    fn test_summarize_limit() {
+        let steps = [
+            step(KIB, Some(200), 2),
+            step(MIB, Some(200), 12),
+            step(10 * MIB, Some(413), 5),
+        ];
+        let lines = summarize(&steps);
+        assert_eq!(
+            lines[0],
+            "Max accepted body: 1 MiB (limit below 10 MiB, refused with 413 Payload Too Large)"
+        );
+        assert_eq!(lines[1], "Buffering: gateway buffers request bodies");
+        assert!(lines[2].ends_with("(10.0ms per MiB)"));
+
+        let reset = [step(KIB, Some(200), 2), step(MIB, None, 0)];
+        assert!(summarize(&reset)
+            .iter()
+            .any(|l| l.starts_with("⚠ Oversized bodies")));
+    }
+
+    #[test]
+    fn test_body_timeout() {
+        use std::time::Duration;
+
+        assert_eq!(body_timeout(30, KIB), Duration::from_secs(30));
+        assert_eq!(body_timeout(30, 100 * MIB), Duration::from_secs(130));
+    }
+
+    #[test]
+    fn test_check_preflight() {
+        use std::collections::HashMap;
+
+        let response = |headers: &[(&str, &str)]| {
+            let headers = headers
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<HashMap<_, _>>();
+            HttpResponse::fixture(204, headers, "")
+        };
+        let allowed = response(&[
+            ("access-control-allow-origin", CORS_ORIGIN),
+            ("access-control-allow-credentials", "true"),
+            ("access-control-allow-methods", "GET, POST, PUT"),
+            ("access-control-allow-headers", "x-cors-test"),
+            ("vary", "Origin"),
+        ]);
+        let mut details = Vec::new();
+        assert!(check_preflight(&allowed, &mut details), "{details:?}");
+
+        let wildcard_credentials = response(&[
+            ("access-control-allow-origin", "*"),
+            ("access-control-allow-credentials", "true"),
+            ("access-control-allow-methods", "*"),
+            ("access-control-allow-headers", "*"),
+        ]);
+        assert!(!check_preflight(&wildcard_credentials, &mut Vec::new()));
+
+        // The policy allows credentials, so a bare `*` is wrong too
+        let wildcard = response(&[
+            ("access-control-allow-origin", "*"),
+            ("access-control-allow-methods", "PUT"),
+            ("access-control-allow-headers", "x-cors-test"),
+        ]);
+        assert!(!check_preflight(&wildcard, &mut Vec::new()));
+
+        let no_methods = response(&[("access-control-allow-origin", CORS_ORIGIN)]);
+        assert!(!check_preflight(&no_methods, &mut Vec::new()));
+    }
+
+    #[test]
+    fn test_check_compression() {
+        let step = |accept, encoding: Option<&str>, body: &[u8]| EncodingStep {
+            accept,
+            content_encoding: encoding.map(str::to_string),
+            vary: Some("Accept-Encoding".to_string()),
+            body: body.to_vec(),
+            upstream_accept: None,
+        };
+        let identity = step(Some("identity"), None, &[b'a'; 100]);
+        let gzip = step(Some("gzip, br"), Some("gzip"), &[0x1f, 0x8b, 8, 0]);
+        let mut details = Vec::new();
+        let (passed, behavior) = check_compression(&[identity.clone(), gzip], &mut details);
+        assert!(passed, "{details:?}");
+        assert_eq!(behavior, "compresses (gzip)");
+
+        let refused = step(Some("gzip;q=0, br;q=0"), Some("gzip"), &[0x1f, 0x8b]);
+        assert!(!refused.accepts("gzip"));
+        let (passed, _) = check_compression(&[identity.clone(), refused], &mut Vec::new());
+        assert!(!passed);
+
+        let mislabeled = step(Some("gzip"), Some("gzip"), b"plain");
+        let (passed, _) = check_compression(&[identity.clone(), mislabeled], &mut Vec::new());
+        assert!(!passed);
+
+        // Asking the backend for gzip on behalf of a client without
+        // Accept-Encoding means the gateway decompresses
+        let decompressed = EncodingStep {
+            upstream_accept: Some("gzip".to_string()),
+            ..step(None, None, &[b'a'; 100])
+        };
+        let (passed, behavior) = check_compression(&[decompressed, identity], &mut Vec::new());
+        assert!(passed);
+        assert_eq!(behavior, "decompresses");
+        let forwarded = EncodingStep {
+            upstream_accept: Some("gzip".to_string()),
+            ..step(Some("gzip"), None, &[b'a'; 100])
+        };
+        assert!(forwarded.decompressed_for_client().is_none());
+    }
+
+    #[test]
+    fn test_check_forwarding() {
+        let client: IpAddr = "10.0.0.5".parse().unwrap();
+        let plain = Forwarding {
+            xff: Some("10.0.0.5".to_string()),
+            ..Default::default()
+        };
+        let appended = Forwarding {
+            xff: Some(format!("{SPOOFED_CLIENT}, 10.0.0.5")),
+            real_ip: Some(SPOOFED_CLIENT.to_string()),
+            ..Default::default()
+        };
+        let mut details = Vec::new();
+        let (passed, behavior) = check_forwarding(&plain, &appended, Some(client), &mut details);
+        assert!(passed);
+        assert_eq!(
+            behavior,
+            "XFF appended, client IP kept, X-Real-IP spoofable"
+        );
+
+        let trusted = Forwarding {
+            xff: Some(SPOOFED_CLIENT.to_string()),
+            ..Default::default()
+        };
+        let (passed, _) = check_forwarding(&plain, &trusted, Some(client), &mut Vec::new());
+        assert!(!passed);
+
+        let forwarded = Forwarding {
+            forwarded: Some("for=192.0.2.1;proto=http, for=\"[2001:db8::1]:4711\"".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(forwarded.client().as_deref(), Some("2001:db8::1"));
+    }
+
+    #[test]
+    fn test_check_headers() {
+        let header = |name: &str, value: &str| (name.to_string(), value.to_string());
+        let mut details = Vec::new();
+        let clean = [
+            header("x-dup-test", "first,second"),
+            header("x-mixed-case-test", "mixed"),
+            header("Content-Length", "5"),
+        ];
+        assert!(check_headers(&clean, &mut details));
+        assert_eq!(
+            details,
+            [
+                "✓ Duplicate header merged into \"first,second\"",
+                "✓ Mixed-case header forwarded (name lowercased)",
+                "✓ Hop-by-hop headers removed",
+                "○ Upstream body framing: Content-Length 5",
+            ]
+        );
+
+        let leaky = [
+            header("X-Dup-Test", "second"),
+            header("X-MiXeD-CaSe-Test", "mixed"),
+            header("X-Hop-Test", "secret"),
+            header("Te", "trailers, deflate"),
+        ];
+        let mut details = Vec::new();
+        assert!(!check_headers(&leaky, &mut details));
+        assert_eq!(details.iter().filter(|d| d.starts_with('✗')).count(), 3);
+
+        // Hop-by-hop removal is reported even when another check fails
+        let lost = [
+            header("x-dup-test", "second"),
+            header("x-mixed-case-test", "mixed"),
+        ];
+        let mut details = Vec::new();
+        assert!(!check_headers(&lost, &mut details));
+        assert!(details.contains(&"✓ Hop-by-hop headers removed".to_string()));
+    }
+
+    #[test]
+    fn test_check_streams() {
+        let response = |status_code: u16, id: &str| {
+            Ok(HttpResponse::fixture(
+                status_code,
+                Default::default(),
+                format!(r#"{{"headers": {{"X-Stream-Id": "{id}"}}}}"#),
+            ))
+        };
+        let ids: Vec<String> = ["1", "2", "3"].map(String::from).to_vec();
+
+        let mut details = Vec::new();
+        let all = [response(200, "1"), response(200, "2"), response(200, "3")];
+        assert_eq!(
+            check_streams(&ids, &all, &mut details),
+            (true, "3/3 streams".to_string())
+        );
+
+        let mut details = Vec::new();
+        let swapped = [
+            response(200, "2"),
+            response(503, "2"),
+            Err(anyhow::anyhow!("reset")),
+        ];
+        assert_eq!(
+            check_streams(&ids, &swapped, &mut details),
+            (false, "0/3 streams".to_string())
+        );
+        assert_eq!(details[0], "✗ Stream 1 got the response of stream 2");
+        assert_eq!(details[1], "✗ Stream 2 returned 503");
+    }
+
+    #[tokio::test]
+    async fn test_http2_against_echo_server() {
+        use crate::http::multiplex::tests::https_echo_server;
+        let client = HttpClient::new().unwrap();
+
+        let address = https_echo_server("gateway.test", &["h2", "http/1.1"]).await;
+        let result = Http2Test::new("127.0.0.1", address.port(), "gateway.test")
+            .run(&client)
+            .await
+            .unwrap();
+        assert_eq!(result.status, TestStatus::Pass, "{:?}", result.message);
+        assert_eq!(
+            result.details.unwrap()[BEHAVIOR_DETAIL],
+            "h2, 20/20 streams"
+        );
+
+        let address = https_echo_server("gateway.test", &["http/1.1"]).await;
+        let result = Http2Test::new("127.0.0.1", address.port(), "gateway.test")
+            .run(&client)
+            .await
+            .unwrap();
+        assert_eq!(result.status, TestStatus::Pass, "{:?}", result.message);
+        assert_eq!(result.details.unwrap()[BEHAVIOR_DETAIL], "http/1.1 only");
+    }
+}
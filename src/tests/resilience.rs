@@ -0,0 +1,352 @@
+//! Resilience tests for Gateway API
+//!
+//! Test 33 (`--experimental disruptive`): Zero-Downtime Reload
+
+use anyhow::Result;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+use tracing::info;
+
+use crate::http::{authority, HttpClient};
+use crate::k8s::{RolloutRestarter, Workload};
+use crate::models::{GatewayImpl, TestCase, TestResult, TestStatus, BEHAVIOR_DETAIL};
+
+/// Requests sent, served and lost while the gateway rolled out
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ReloadTraffic {
+    pub requests: u64,
+    /// 5xx responses
+    pub server_errors: u64,
+    /// Requests without a response (connection reset or refused, timeout)
+    pub dropped: u64,
+    /// First failure, for the report
+    pub first_failure: Option<String>,
+}
+
+impl ReloadTraffic {
+    /// Record one request's status code (None = no response)
+    pub fn record(&mut self, status: Option<u16>, failure: impl FnOnce() -> String) {
+        self.requests += 1;
+        let failed = match status {
+            Some(code) if code >= 500 => {
+                self.server_errors += 1;
+                true
+            }
+            Some(_) => false,
+            None => {
+                self.dropped += 1;
+                true
+            }
+        };
+        if failed && self.first_failure.is_none() {
+            self.first_failure = Some(failure());
+        }
+    }
+
+    pub fn failed(&self) -> u64 {
+        self.server_errors + self.dropped
+    }
+
+    /// Share of requests that failed, in percent
+    pub fn error_rate(&self) -> f64 {
+        if self.requests == 0 {
+            0.0
+        } else {
+            self.failed() as f64 / self.requests as f64 * 100.0
+        }
+    }
+}
+
+/// Test 33: Zero-Downtime Reload
+///
+/// Rollout-restarts the workloads proxying the Gateway under test (its
+/// data plane, or the controller where that proxies itself) in the gateway
+/// pods' namespace while workers keep sending requests, and counts the
+/// requests dropped or answered with 5xx until the rollout has finished and
+/// settled. A gateway that drains connections and hands over listeners
+/// loses none. The restart disturbs every other test, so the test is opt-in
+/// and runs alone.
+#[derive(Clone)]
+pub struct ZeroDowntimeReloadTest {
+    pub gateway_ip: String,
+    pub gateway_port: u16,
+    pub hostname: String,
+    pub path: String,
+    /// Gateway resource whose data plane is restarted
+    pub gateway_name: String,
+    pub implementation: GatewayImpl,
+    /// Restarts the workloads (None = no cluster access)
+    pub restarter: Option<RolloutRestarter>,
+    /// Namespace of the gateway pods
+    pub gateway_namespace: String,
+    pub workers: u32,
+    pub request_interval_ms: u64,
+    pub rollout_timeout_secs: u64,
+    /// Traffic kept flowing after the rollout completes
+    pub settle_ms: u64,
+}
+
+impl ZeroDowntimeReloadTest {
+    pub fn new(
+        gateway_ip: impl Into<String>,
+        gateway_port: u16,
+        gateway_name: impl Into<String>,
+        implementation: GatewayImpl,
+    ) -> Self {
+        Self {
+            gateway_ip: gateway_ip.into(),
+            gateway_port,
+            hostname: "example.com".to_string(),
+            path: "/".to_string(),
+            gateway_name: gateway_name.into(),
+            implementation,
+            restarter: None,
+            gateway_namespace: "default".to_string(),
+            workers: 4,
+            request_interval_ms: 50,
+            rollout_timeout_secs: 300,
+            settle_ms: 5000,
+        }
+    }
+
+    pub fn with_hostname(mut self, hostname: impl Into<String>) -> Self {
+        self.hostname = hostname.into();
+        self
+    }
+
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    /// Restart the gateway through `restarter` (None = skip the test)
+    pub fn with_restarter(mut self, restarter: Option<RolloutRestarter>) -> Self {
+        self.restarter = restarter;
+        self
+    }
+
+    pub fn with_gateway_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.gateway_namespace = namespace.into();
+        self
+    }
+
+    fn url(&self) -> String {
+        format!(
+            "http://{}{}",
+            authority(&self.gateway_ip, self.gateway_port),
+            self.path
+        )
+    }
+
+    pub async fn run(&self, client: &HttpClient) -> Result<TestResult> {
+        let test_case = TestCase::ZeroDowntimeReload;
+        let Some(restarter) = &self.restarter else {
+            return Ok(TestResult::skip(
+                test_case,
+                "Needs cluster access to restart the gateway",
+            ));
+        };
+        info!("Running Zero-Downtime Reload Test");
+        let start = Instant::now();
+        let mut details = Vec::new();
+
+        match client.get_with_host(&self.url(), &self.hostname).await {
+            Ok(resp) if resp.status_code < 500 => {
+                details.push(format!("✓ Baseline request: {}", resp.status_code))
+            }
+            Ok(resp) => {
+                details.push(format!("✗ Baseline request: {}", resp.status_code));
+                return Ok(self.result(start, TestStatus::Fail, details, None));
+            }
+            Err(e) => {
+                details.push(format!("✗ Baseline request failed: {e}"));
+                return Ok(self.result(start, TestStatus::Fail, details, None));
+            }
+        }
+
+        let Some(selector) = self.implementation.serving_pod_selector(&self.gateway_name) else {
+            return Ok(TestResult::skip(
+                test_case,
+                format!("No pod selector known for {}", self.implementation),
+            ));
+        };
+        let workloads = restarter
+            .find(
+                std::slice::from_ref(&selector),
+                Some(&self.gateway_namespace),
+            )
+            .await?;
+        if workloads.is_empty() {
+            return Ok(TestResult::skip(
+                test_case,
+                format!(
+                    "No workloads own pods matching {selector} in namespace {} (set --gateway-namespace)",
+                    self.gateway_namespace
+                ),
+            ));
+        }
+
+        let traffic = Arc::new(Mutex::new(ReloadTraffic::default()));
+        let stop = CancellationToken::new();
+        let handles = self.send_traffic(client, &traffic, &stop);
+
+        let rollout_start = Instant::now();
+        let rollout = self.rollout(restarter, &workloads).await;
+        let rollout_ms = rollout_start.elapsed().as_millis() as u64;
+        if rollout.is_ok() {
+            tokio::time::sleep(Duration::from_millis(self.settle_ms)).await;
+        }
+        stop.cancel();
+        for handle in handles {
+            let _ = handle.await;
+        }
+        let traffic = traffic.lock().unwrap().clone();
+
+        let names: Vec<String> = workloads.iter().map(|w| w.to_string()).collect();
+        details.push(format!("Restarted {}", names.join(", ")));
+        let status = match &rollout {
+            Err(e) => {
+                details.push(format!("✗ Rollout did not complete: {e:#}"));
+                TestStatus::Error
+            }
+            Ok(()) if traffic.failed() == 0 => {
+                details.push(format!(
+                    "✓ Rolled out in {rollout_ms}ms; all {} requests served",
+                    traffic.requests
+                ));
+                TestStatus::Pass
+            }
+            Ok(()) => {
+                details.push(format!(
+                    "✗ Rolled out in {rollout_ms}ms; {} of {} requests failed ({} dropped, {} 5xx, {:.2}%)",
+                    traffic.failed(),
+                    traffic.requests,
+                    traffic.dropped,
+                    traffic.server_errors,
+                    traffic.error_rate()
+                ));
+                if let Some(failure) = &traffic.first_failure {
+                    details.push(format!("  First failure: {failure}"));
+                }
+                TestStatus::Fail
+            }
+        };
+
+        let behavior = if rollout.is_err() {
+            "rollout did not complete".to_string()
+        } else if traffic.failed() == 0 {
+            format!(
+                "zero downtime over {:.1}s rollout",
+                rollout_ms as f64 / 1000.0
+            )
+        } else {
+            format!(
+                "{} dropped, {} 5xx of {} during rollout",
+                traffic.dropped, traffic.server_errors, traffic.requests
+            )
+        };
+        let measured = serde_json::json!({
+            "workloads": names,
+            "rollout_ms": rollout_ms,
+            "requests": traffic.requests,
+            "dropped": traffic.dropped,
+            "server_errors": traffic.server_errors,
+            "error_rate": traffic.error_rate(),
+            BEHAVIOR_DETAIL: behavior,
+        });
+        Ok(self.result(start, status, details, Some(measured)))
+    }
+
+    /// Restart every workload, then wait for all of them to roll out
+    async fn rollout(&self, restarter: &RolloutRestarter, workloads: &[Workload]) -> Result<()> {
+        for workload in workloads {
+            restarter.restart(workload).await?;
+        }
+        let timeout = Duration::from_secs(self.rollout_timeout_secs);
+        for workload in workloads {
+            restarter.wait_rolled_out(workload, timeout).await?;
+        }
+        Ok(())
+    }
+
+    /// Workers sending requests until `stop` is cancelled
+    fn send_traffic(
+        &self,
+        client: &HttpClient,
+        traffic: &Arc<Mutex<ReloadTraffic>>,
+        stop: &CancellationToken,
+    ) -> Vec<tokio::task::JoinHandle<()>> {
+        (0..self.workers.max(1))
+            .map(|_| {
+                let client = client.clone();
+                let url = self.url();
+                let hostname = self.hostname.clone();
+                let traffic = traffic.clone();
+                let stop = stop.clone();
+                let interval = Duration::from_millis(self.request_interval_ms);
+                tokio::spawn(async move {
+                    while !stop.is_cancelled() {
+                        let result = client.get_with_host(&url, &hostname).await;
+                        let status = result.as_ref().ok().map(|r| r.status_code);
+                        traffic.lock().unwrap().record(status, || match &result {
+                            Ok(resp) => format!("status {}", resp.status_code),
+                            Err(e) => e.to_string(),
+                        });
+                        tokio::select! {
+                            _ = stop.cancelled() => break,
+                            _ = tokio::time::sleep(interval) => {}
+                        }
+                    }
+                })
+            })
+            .collect()
+    }
+
+    fn result(
+        &self,
+        start: Instant,
+        status: TestStatus,
+        details: Vec<String>,
+        measured: Option<serde_json::Value>,
+    ) -> TestResult {
+        TestResult {
+            test_case: TestCase::ZeroDowntimeReload,
+            status,
+            duration_ms: start.elapsed().as_millis() as u64,
+            message: Some(details.join("\n")),
+            details: measured,
+            retries: 0,
+            backends: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reload_traffic() {
+        let mut traffic = ReloadTraffic::default();
+        traffic.record(Some(200), || unreachable!());
+        traffic.record(Some(404), || unreachable!());
+        traffic.record(None, || "connection reset".to_string());
+        traffic.record(Some(503), || "status 503".to_string());
+
+        assert_eq!(traffic.requests, 4);
+        assert_eq!(traffic.dropped, 1);
+        assert_eq!(traffic.server_errors, 1);
+        assert_eq!(traffic.failed(), 2);
+        assert_eq!(traffic.error_rate(), 50.0);
+        assert_eq!(traffic.first_failure.as_deref(), Some("connection reset"));
+    }
+
+    #[tokio::test]
+    async fn test_reload_without_cluster_access() {
+        let test = ZeroDowntimeReloadTest::new("10.0.0.1", 80, "eg-gateway", GatewayImpl::Envoy);
+        let result = test.run(&HttpClient::new().unwrap()).await.unwrap();
+        assert_eq!(result.status, TestStatus::Skip);
+        assert_eq!(result.test_case, TestCase::ZeroDowntimeReload);
+    }
+}
@@ -1,14 +1,28 @@
 //! Routing tests for Gateway API
 //!
 //! Tests 1-3: Host Routing, Path Routing, Header Routing
+//! Tests 23-24: Wildcard Hostname, Missing Host Header
+//! Test 34: Route Propagation
 
 #![allow(dead_code)]
 
 use anyhow::Result;
+use std::time::{Duration, Instant};
 use tracing::{debug, info};
 
-use crate::http::HttpClient;
-use crate::models::{TestCase, TestResult, TestStatus};
+use crate::http::{authority, EchoResponse, HttpClient, HttpResponse};
+use crate::k8s::{HTTPRouteBuilder, HTTPRouteManager, K8sClient, RuleBuilder, GATEWAY_LABEL};
+use crate::models::{GatewayImpl, TestCase, TestResult, TestStatus, BEHAVIOR_DETAIL};
+use crate::utils::Teardown;
+
+/// Hostname of the wildcard test route
+pub const WILDCARD_HOSTNAME: &str = "*.wildcard.example.com";
+
+/// Backend behind the wildcard route
+pub const WILDCARD_BACKEND: &str = "wildcard";
+
+/// Request header the propagation test route sets to its token
+pub const PROPAGATION_HEADER: &str = "X-Route-Probe";
 
 /// Test 1: Host-based routing
 #[derive(Clone, Debug)]
@@ -60,19 +74,21 @@ impl HostRoutingTest {
 
             match response {
                 Ok(resp) => {
-                    let passed = resp.is_success() && resp.body_contains(&mapping.expected_backend);
+                    let checked = routed_to(&resp, &mapping.expected_backend)
+                        .and_then(|echo| received_host(&echo, &mapping.hostname));
 
-                    if passed {
-                        details.push(format!(
+                    match checked {
+                        Ok(()) => details.push(format!(
                             "✓ {} -> {} ({}ms)",
                             mapping.hostname, mapping.expected_backend, resp.duration_ms
-                        ));
-                    } else {
-                        all_passed = false;
-                        details.push(format!(
-                            "✗ {} expected {} but got status {}",
-                            mapping.hostname, mapping.expected_backend, resp.status_code
-                        ));
+                        )),
+                        Err(reason) => {
+                            all_passed = false;
+                            details.push(format!(
+                                "✗ {} expected {} but {reason}",
+                                mapping.hostname, mapping.expected_backend
+                            ));
+                        }
                     }
                 }
                 Err(e) => {
@@ -94,6 +110,8 @@ impl HostRoutingTest {
             duration_ms: duration.as_millis() as u64,
             message: Some(details.join("\n")),
             details: None,
+            retries: 0,
+            backends: Vec::new(),
         })
     }
 }
@@ -170,19 +188,26 @@ impl PathRoutingTest {
 
             match response {
                 Ok(resp) => {
-                    let passed = resp.is_success() && resp.body_contains(&mapping.expected_backend);
-
-                    if passed {
-                        details.push(format!(
+                    let checked = routed_to(&resp, &mapping.expected_backend).and_then(|echo| {
+                        if echo.path == mapping.path {
+                            Ok(())
+                        } else {
+                            Err(format!("the backend received path {}", echo.path))
+                        }
+                    });
+
+                    match checked {
+                        Ok(()) => details.push(format!(
                             "✓ {} -> {} ({}ms)",
                             mapping.path, mapping.expected_backend, resp.duration_ms
-                        ));
-                    } else {
-                        all_passed = false;
-                        details.push(format!(
-                            "✗ {} expected {} but got status {}",
-                            mapping.path, mapping.expected_backend, resp.status_code
-                        ));
+                        )),
+                        Err(reason) => {
+                            all_passed = false;
+                            details.push(format!(
+                                "✗ {} expected {} but {reason}",
+                                mapping.path, mapping.expected_backend
+                            ));
+                        }
                     }
                 }
                 Err(e) => {
@@ -204,6 +229,8 @@ impl PathRoutingTest {
             duration_ms: duration.as_millis() as u64,
             message: Some(details.join("\n")),
             details: None,
+            retries: 0,
+            backends: Vec::new(),
         })
     }
 }
@@ -274,25 +301,33 @@ impl HeaderRoutingTest {
 
             match response {
                 Ok(resp) => {
-                    let passed = resp.is_success() && resp.body_contains(&rule.expected_backend);
-
-                    if passed {
-                        details.push(format!(
+                    let checked = routed_to(&resp, &rule.expected_backend).and_then(|echo| {
+                        match echo.header(&rule.header_name) {
+                            Some(value) if value == rule.header_value => Ok(()),
+                            Some(value) => {
+                                Err(format!("the backend received {}={value}", rule.header_name))
+                            }
+                            None => {
+                                Err(format!("the backend did not receive {}", rule.header_name))
+                            }
+                        }
+                    });
+
+                    match checked {
+                        Ok(()) => details.push(format!(
                             "✓ {}={} -> {} ({}ms)",
                             rule.header_name,
                             rule.header_value,
                             rule.expected_backend,
                             resp.duration_ms
-                        ));
-                    } else {
-                        all_passed = false;
-                        details.push(format!(
-                            "✗ {}={} expected {} but got status {}",
-                            rule.header_name,
-                            rule.header_value,
-                            rule.expected_backend,
-                            resp.status_code
-                        ));
+                        )),
+                        Err(reason) => {
+                            all_passed = false;
+                            details.push(format!(
+                                "✗ {}={} expected {} but {reason}",
+                                rule.header_name, rule.header_value, rule.expected_backend
+                            ));
+                        }
                     }
                 }
                 Err(e) => {
@@ -317,8 +352,467 @@ impl HeaderRoutingTest {
             duration_ms: duration.as_millis() as u64,
             message: Some(details.join("\n")),
             details: None,
+            retries: 0,
+            backends: Vec::new(),
+        })
+    }
+}
+
+/// Test 23: Wildcard hostname matching
+///
+/// A route for `*.wildcard.example.com` must match any subdomain (a
+/// wildcard label matches one or more labels in Gateway API) but not the
+/// apex `wildcard.example.com`.
+#[derive(Clone, Debug)]
+pub struct WildcardHostnameTest {
+    pub gateway_ip: String,
+    pub gateway_port: u16,
+    /// Wildcard hostname of the route, e.g. `*.wildcard.example.com`
+    pub wildcard: String,
+    pub expected_backend: String,
+}
+
+impl WildcardHostnameTest {
+    pub fn new(gateway_ip: impl Into<String>, gateway_port: u16) -> Self {
+        Self {
+            gateway_ip: gateway_ip.into(),
+            gateway_port,
+            wildcard: WILDCARD_HOSTNAME.to_string(),
+            expected_backend: WILDCARD_BACKEND.to_string(),
+        }
+    }
+
+    pub fn with_wildcard(
+        mut self,
+        wildcard: impl Into<String>,
+        expected_backend: impl Into<String>,
+    ) -> Self {
+        self.wildcard = wildcard.into();
+        self.expected_backend = expected_backend.into();
+        self
+    }
+
+    /// Hostnames that must match, and the apex that must not
+    fn hostnames(&self) -> (Vec<String>, String) {
+        let apex = self.wildcard.trim_start_matches("*.").to_string();
+        (vec![format!("app.{apex}"), format!("a.b.{apex}")], apex)
+    }
+
+    pub async fn run(&self, client: &HttpClient) -> Result<TestResult> {
+        info!("Running Wildcard Hostname Test");
+        let start = std::time::Instant::now();
+        let mut all_passed = true;
+        let mut details = Vec::new();
+        let (matching, apex) = self.hostnames();
+
+        for hostname in &matching {
+            debug!("Testing hostname: {}", hostname);
+            match client
+                .test_host_routing(&self.gateway_ip, self.gateway_port, hostname)
+                .await
+            {
+                Ok(resp) => match routed_to(&resp, &self.expected_backend)
+                    .and_then(|echo| received_host(&echo, hostname))
+                {
+                    Ok(()) => details.push(format!(
+                        "✓ {} -> {} ({}ms)",
+                        hostname, self.expected_backend, resp.duration_ms
+                    )),
+                    Err(reason) => {
+                        all_passed = false;
+                        details.push(format!(
+                            "✗ {} expected {} but {reason}",
+                            hostname, self.expected_backend
+                        ));
+                    }
+                },
+                Err(e) => {
+                    all_passed = false;
+                    details.push(format!("✗ {} failed: {}", hostname, e));
+                }
+            }
+        }
+
+        match client
+            .test_host_routing(&self.gateway_ip, self.gateway_port, &apex)
+            .await
+        {
+            Ok(resp) if routed_to(&resp, &self.expected_backend).is_ok() => {
+                all_passed = false;
+                details.push(format!(
+                    "✗ {} matched {} (the apex is not covered by {})",
+                    apex, self.expected_backend, self.wildcard
+                ));
+            }
+            Ok(resp) => details.push(format!(
+                "✓ {} not matched by {} (status {})",
+                apex, self.wildcard, resp.status_code
+            )),
+            Err(e) => details.push(format!("✓ {} not matched ({})", apex, e)),
+        }
+
+        Ok(TestResult {
+            test_case: TestCase::WildcardHostname,
+            status: if all_passed {
+                TestStatus::Pass
+            } else {
+                TestStatus::Fail
+            },
+            duration_ms: start.elapsed().as_millis() as u64,
+            message: Some(details.join("\n")),
+            details: None,
+            retries: 0,
+            backends: Vec::new(),
+        })
+    }
+}
+
+/// Test 24: Request without a Host header
+///
+/// HTTP/1.1 requires a Host header (RFC 9112 §3.2), so a gateway must
+/// answer 400 instead of routing the request to some default backend. The
+/// request is written to a raw socket because HTTP clients always add one.
+#[derive(Clone, Debug)]
+pub struct MissingHostTest {
+    pub gateway_ip: String,
+    pub gateway_port: u16,
+    pub path: String,
+}
+
+impl MissingHostTest {
+    pub fn new(gateway_ip: impl Into<String>, gateway_port: u16) -> Self {
+        Self {
+            gateway_ip: gateway_ip.into(),
+            gateway_port,
+            path: "/".to_string(),
+        }
+    }
+
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    pub async fn run(&self, client: &HttpClient) -> Result<TestResult> {
+        info!("Running Missing Host Header Test");
+        let start = std::time::Instant::now();
+        let mut details = Vec::new();
+        let request = format!("GET {} HTTP/1.1\r\nConnection: close\r\n\r\n", self.path);
+
+        let status = match self.status(client, &request).await {
+            Ok(Some(400)) => {
+                details.push("✓ Rejected with 400 Bad Request".to_string());
+                TestStatus::Pass
+            }
+            Ok(Some(code)) if (400..500).contains(&code) => {
+                details.push(format!("✓ Rejected with {code}"));
+                details.push("⚠ RFC 9112 expects 400 Bad Request".to_string());
+                TestStatus::Pass
+            }
+            Ok(None) => {
+                details.push("✓ Connection closed without a response".to_string());
+                details.push("⚠ RFC 9112 expects 400 Bad Request".to_string());
+                TestStatus::Pass
+            }
+            Ok(Some(code)) => {
+                details.push(format!(
+                    "✗ Request without Host answered with {code} (expected 400)"
+                ));
+                TestStatus::Fail
+            }
+            Err(e) => {
+                details.push(format!("✗ {e:#}"));
+                TestStatus::Fail
+            }
+        };
+
+        // HTTP/1.0 allows omitting Host; how it is routed is informational
+        let request = format!("GET {} HTTP/1.0\r\n\r\n", self.path);
+        match self.status(client, &request).await {
+            Ok(Some(code)) => details.push(format!("○ HTTP/1.0 without Host: {code}")),
+            Ok(None) => details.push("○ HTTP/1.0 without Host: closed".to_string()),
+            Err(e) => details.push(format!("○ HTTP/1.0 without Host: {e}")),
+        }
+
+        Ok(TestResult {
+            test_case: TestCase::MissingHost,
+            status,
+            duration_ms: start.elapsed().as_millis() as u64,
+            message: Some(details.join("\n")),
+            details: None,
+            retries: 0,
+            backends: Vec::new(),
         })
     }
+
+    /// Status of the response to a raw request (None = closed without one)
+    async fn status(&self, client: &HttpClient, request: &str) -> Result<Option<u16>> {
+        let response = client
+            .send_raw(&self.gateway_ip, self.gateway_port, request)
+            .await?;
+        Ok(response.map(|r| r.status))
+    }
+}
+
+/// Test 34: Route Propagation
+///
+/// Creates a fresh HTTPRoute for a unique hostname and polls the gateway
+/// until it serves the route, then deletes the route and polls until it
+/// stops. The route sets a request header to a per-run token, so a response
+/// only counts as served by this route when the backend echoes the token.
+/// Both latencies are measured from when the API server accepted the change.
+#[derive(Clone)]
+pub struct RoutePropagationTest {
+    pub gateway_ip: String,
+    pub gateway_port: u16,
+    pub gateway_name: String,
+    pub implementation: GatewayImpl,
+    /// Creates and deletes the route (None = no cluster access)
+    pub k8s: Option<K8sClient>,
+    pub teardown: Teardown,
+    pub backend: String,
+    pub backend_port: u16,
+    pub poll_interval_ms: u64,
+    /// Longest wait for the route to appear or disappear
+    pub timeout_ms: u64,
+}
+
+impl RoutePropagationTest {
+    pub fn new(
+        gateway_ip: impl Into<String>,
+        gateway_port: u16,
+        gateway_name: impl Into<String>,
+        implementation: GatewayImpl,
+    ) -> Self {
+        Self {
+            gateway_ip: gateway_ip.into(),
+            gateway_port,
+            gateway_name: gateway_name.into(),
+            implementation,
+            k8s: None,
+            teardown: Teardown::new(),
+            backend: "echo".to_string(),
+            backend_port: 8080,
+            poll_interval_ms: 10,
+            timeout_ms: 60_000,
+        }
+    }
+
+    /// Create the route through `k8s` (None = skip the test)
+    pub fn with_k8s(mut self, k8s: Option<K8sClient>) -> Self {
+        self.k8s = k8s;
+        self
+    }
+
+    pub fn with_teardown(mut self, teardown: Teardown) -> Self {
+        self.teardown = teardown;
+        self
+    }
+
+    pub async fn run(&self, client: &HttpClient) -> Result<TestResult> {
+        let test_case = TestCase::RoutePropagation;
+        let Some(k8s) = &self.k8s else {
+            return Ok(TestResult::skip(
+                test_case,
+                "Needs cluster access to create the route",
+            ));
+        };
+        info!("Running Route Propagation Test");
+        let start = Instant::now();
+        let mut details = Vec::new();
+
+        let token = format!("{:08x}", rand::random::<u32>());
+        let name = format!("route-propagation-{token}");
+        let hostname = propagation_hostname(&token);
+        let namespace = k8s.namespace().to_string();
+        let route = HTTPRouteBuilder::new(&name, &namespace)
+            .label(GATEWAY_LABEL, self.implementation.short_name())
+            .parent_ref(&self.gateway_name)
+            .hostname(&hostname)
+            .rule(
+                RuleBuilder::new()
+                    .path_prefix("/")
+                    .set_request_header(PROPAGATION_HEADER, &token)
+                    .backend(&self.backend, self.backend_port)
+                    .build(),
+            )
+            .build();
+
+        let undo = {
+            let (client, name, namespace) = (k8s.clone(), name.clone(), namespace.clone());
+            self.teardown
+                .register(format!("delete HTTPRoute {namespace}/{name}"), move || {
+                    let (client, name, namespace) =
+                        (client.clone(), name.clone(), namespace.clone());
+                    async move {
+                        HTTPRouteManager::new(client)
+                            .delete(&name, &namespace)
+                            .await
+                    }
+                })
+        };
+        if let Err(e) = HTTPRouteManager::new(k8s.clone())
+            .create(&route, &namespace)
+            .await
+        {
+            undo.commit();
+            details.push(format!("✗ {e:#}"));
+            return Ok(self.result(start, TestStatus::Error, details, None));
+        }
+
+        let created = Instant::now();
+        let attach_ms = match self.wait_for(client, &hostname, &token, true).await {
+            Probe::Reached(probes) => {
+                let ms = created.elapsed().as_millis() as u64;
+                details.push(format!(
+                    "✓ Served {hostname} {ms}ms after creating the route ({probes} probes)"
+                ));
+                Some(ms)
+            }
+            Probe::TimedOut(last) => {
+                details.push(format!(
+                    "✗ Not served {}ms after creating the route (last probe: {last})",
+                    self.timeout_ms
+                ));
+                None
+            }
+        };
+
+        let deleted = undo.run().await;
+        let removed = Instant::now();
+        let detach_ms = match (&deleted, attach_ms) {
+            (Err(e), _) => {
+                details.push(format!("✗ {e:#}"));
+                None
+            }
+            (Ok(()), None) => None,
+            (Ok(()), Some(_)) => match self.wait_for(client, &hostname, &token, false).await {
+                Probe::Reached(probes) => {
+                    let ms = removed.elapsed().as_millis() as u64;
+                    details.push(format!(
+                        "✓ Stopped serving {ms}ms after deleting the route ({probes} probes)"
+                    ));
+                    Some(ms)
+                }
+                Probe::TimedOut(last) => {
+                    details.push(format!(
+                        "✗ Still served {}ms after deleting the route (last probe: {last})",
+                        self.timeout_ms
+                    ));
+                    None
+                }
+            },
+        };
+
+        let status = match (&deleted, attach_ms, detach_ms) {
+            (Err(_), _, _) => TestStatus::Error,
+            (Ok(()), Some(_), Some(_)) => TestStatus::Pass,
+            _ => TestStatus::Fail,
+        };
+        let behavior = match (attach_ms, detach_ms) {
+            (Some(attach), Some(detach)) => format!("serves in {attach}ms, removes in {detach}ms"),
+            (Some(attach), None) => format!("serves in {attach}ms, removal not observed"),
+            _ => format!("not served within {}ms", self.timeout_ms),
+        };
+        let measured = serde_json::json!({
+            "attach_ms": attach_ms,
+            "detach_ms": detach_ms,
+            "poll_interval_ms": self.poll_interval_ms,
+            BEHAVIOR_DETAIL: behavior,
+        });
+        Ok(self.result(start, status, details, Some(measured)))
+    }
+
+    /// Poll the route's hostname until the gateway serves it (`present`) or
+    /// no longer does, returning the number of probes or the last outcome
+    ///
+    /// Transport errors neither confirm nor rule out the route while it is
+    /// being programmed, so they only keep the poll going.
+    async fn wait_for(
+        &self,
+        client: &HttpClient,
+        hostname: &str,
+        token: &str,
+        present: bool,
+    ) -> Probe {
+        let url = format!("http://{}/", authority(&self.gateway_ip, self.gateway_port));
+        let interval = Duration::from_millis(self.poll_interval_ms);
+        let deadline = Instant::now() + Duration::from_millis(self.timeout_ms);
+        let mut probes = 0;
+        loop {
+            probes += 1;
+            let last = match client.get_with_host(&url, hostname).await {
+                Ok(resp) if serves_route(&resp, token) == present => return Probe::Reached(probes),
+                Ok(resp) => format!("status {}", resp.status_code),
+                Err(e) => e.to_string(),
+            };
+            if Instant::now() >= deadline {
+                return Probe::TimedOut(last);
+            }
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    fn result(
+        &self,
+        start: Instant,
+        status: TestStatus,
+        details: Vec<String>,
+        measured: Option<serde_json::Value>,
+    ) -> TestResult {
+        TestResult {
+            test_case: TestCase::RoutePropagation,
+            status,
+            duration_ms: start.elapsed().as_millis() as u64,
+            message: Some(details.join("\n")),
+            details: measured,
+            retries: 0,
+            backends: Vec::new(),
+        }
+    }
+}
+
+/// Outcome of polling for a route change
+enum Probe {
+    /// The change was observed after this many probes
+    Reached(u32),
+    /// Not observed in time; the last probe's outcome
+    TimedOut(String),
+}
+
+/// Hostname only the propagation route with `token` matches
+fn propagation_hostname(token: &str) -> String {
+    format!("{token}.propagation.example.com")
+}
+
+/// Whether a response came through the propagation route with `token`
+fn serves_route(resp: &HttpResponse, token: &str) -> bool {
+    resp.is_success() && resp.echoed_header(PROPAGATION_HEADER).as_deref() == Some(token)
+}
+
+/// Check a routed request was answered by `backend`, returning what the
+/// backend received, or why the response does not show that
+fn routed_to(resp: &HttpResponse, backend: &str) -> std::result::Result<EchoResponse, String> {
+    if !resp.is_success() {
+        return Err(format!("got status {}", resp.status_code));
+    }
+    let echo = resp
+        .echo()
+        .ok_or_else(|| "the response is not from an echo backend".to_string())?;
+    match echo.backend() {
+        Some(served_by) if served_by == backend => Ok(echo),
+        Some(served_by) => Err(format!("{served_by} answered")),
+        None => Err("the backend did not identify itself".to_string()),
+    }
+}
+
+/// Check the backend received the Host the request was sent with
+fn received_host(echo: &EchoResponse, hostname: &str) -> std::result::Result<(), String> {
+    if echo.hostname().eq_ignore_ascii_case(hostname) {
+        Ok(())
+    } else {
+        Err(format!("the backend received Host {}", echo.host))
+    }
 }
 
 /// Combined routing test runner
@@ -337,6 +831,12 @@ impl RoutingTestSuite {
         })
     }
 
+    /// Use a shared client (e.g. with the configured timeout)
+    pub fn with_client(mut self, client: HttpClient) -> Self {
+        self.client = client;
+        self
+    }
+
     pub async fn run_all(&self) -> Result<Vec<TestResult>> {
         let mut results = Vec::new();
 
@@ -394,4 +894,93 @@ mod tests {
         assert_eq!(test.header_rules.len(), 1);
         assert_eq!(test.header_rules[0].header_name, "X-Env");
     }
+
+    #[test]
+    fn test_wildcard_hostnames() {
+        let (matching, apex) = WildcardHostnameTest::new("10.0.0.1", 80).hostnames();
+        assert_eq!(
+            matching,
+            ["app.wildcard.example.com", "a.b.wildcard.example.com"]
+        );
+        assert_eq!(apex, "wildcard.example.com");
+    }
+
+    #[test]
+    fn test_routed_to() {
+        let response = |status_code: u16, body: &str| {
+            HttpResponse::fixture(status_code, Default::default(), body)
+        };
+        let app1 = response(
+            200,
+            r#"{"path": "/", "host": "app1.example.com:80", "service": "app1", "pod": "app1-6d4f9c7b8-x2x4q"}"#,
+        );
+
+        let echo = routed_to(&app1, "app1").unwrap();
+        assert_eq!(received_host(&echo, "app1.example.com"), Ok(()));
+        assert_eq!(
+            received_host(&echo, "app2.example.com"),
+            Err("the backend received Host app1.example.com:80".to_string())
+        );
+        // A substring of the backend that answered is not a match
+        assert_eq!(routed_to(&app1, "app"), Err("app1 answered".to_string()));
+        assert_eq!(
+            routed_to(&response(404, "app1"), "app1"),
+            Err("got status 404".to_string())
+        );
+        assert_eq!(
+            routed_to(&response(200, "app1"), "app1"),
+            Err("the response is not from an echo backend".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_missing_host_status() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // A server routing Host-less requests fails the test
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut buf = [0u8; 1024];
+                let _ = stream.read(&mut buf).await;
+                let _ = stream
+                    .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n")
+                    .await;
+            }
+        });
+        let client = HttpClient::with_timeout(2).unwrap();
+        let result = MissingHostTest::new("127.0.0.1", port)
+            .run(&client)
+            .await
+            .unwrap();
+        assert_eq!(result.status, TestStatus::Fail);
+    }
+
+    #[test]
+    fn test_serves_route() {
+        let response = |status_code: u16, token: &str| {
+            HttpResponse::fixture(
+                status_code,
+                Default::default(),
+                format!(r#"{{"path": "/", "headers": {{"x-route-probe": "{token}"}}}}"#),
+            )
+        };
+        assert!(serves_route(&response(200, "0badf00d"), "0badf00d"));
+        // A route left over from an earlier run carries another token
+        assert!(!serves_route(&response(200, "deadbeef"), "0badf00d"));
+        assert!(!serves_route(&response(404, "0badf00d"), "0badf00d"));
+        assert_eq!(
+            propagation_hostname("0badf00d"),
+            "0badf00d.propagation.example.com"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_route_propagation_without_cluster_access() {
+        let test = RoutePropagationTest::new("10.0.0.1", 80, "gateway", GatewayImpl::Envoy);
+        let result = test.run(&HttpClient::new().unwrap()).await.unwrap();
+        assert_eq!(result.status, TestStatus::Skip);
+        assert_eq!(result.test_case, TestCase::RoutePropagation);
+    }
 }
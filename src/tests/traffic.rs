@@ -1,26 +1,46 @@
 //! Traffic management tests for Gateway API
 //!
 //! Tests 7-10: Canary Traffic, Rate Limiting, Timeout & Retry, Session Affinity
+//! Test 18: Consistent Hashing
 
 #![allow(dead_code)]
 
 use anyhow::Result;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::net::IpAddr;
 use std::time::Duration;
 use tracing::{debug, info};
 
-use crate::http::HttpClient;
-use crate::models::{TestCase, TestResult, TestStatus};
+use crate::http::{authority, HttpClient, HttpResponse};
+use crate::k8s::DeploymentScaler;
+use crate::models::{CanarySettings, TestCase, TestResult, TestStatus};
+use crate::utils::{Teardown, Undo};
+
+use super::progressive::{upper_quantile, z_score};
+
+/// Fewest requests the canary test sends, whatever the weights
+const MIN_CANARY_SAMPLES: usize = 100;
+
+/// Most requests the canary test sends; very small shares are checked less
+/// precisely rather than taking minutes
+const MAX_CANARY_SAMPLES: usize = 20_000;
 
 /// Test 7: Canary Traffic (Weighted Routing)
+///
+/// Checks the observed split against the route weights with a chi-squared
+/// goodness-of-fit test. Unless a sample size is set, sends enough requests
+/// for the smallest share to be measured (see [`required_samples`]), so a
+/// 99/1 split is checked as rigorously as 90/10.
 #[derive(Clone, Debug)]
 pub struct CanaryTrafficTest {
     pub gateway_ip: String,
     pub gateway_port: u16,
     pub path: String,
     pub weights: Vec<WeightedBackend>,
-    pub sample_size: usize,
-    pub tolerance_percent: f64,
+    /// Requests to send (None = enough for the weights and confidence)
+    pub sample_size: Option<usize>,
+    /// Confidence level of the goodness-of-fit test (0.99 = 99%)
+    pub confidence: f64,
 }
 
 #[derive(Clone, Debug)]
@@ -36,8 +56,8 @@ impl CanaryTrafficTest {
             gateway_port,
             path: "/".to_string(),
             weights: Vec::new(),
-            sample_size: 100,
-            tolerance_percent: 10.0,
+            sample_size: None,
+            confidence: CanarySettings::default().confidence,
         }
     }
 
@@ -54,42 +74,40 @@ impl CanaryTrafficTest {
         self
     }
 
+    /// Send exactly `size` requests instead of the calculated number
     pub fn sample_size(mut self, size: usize) -> Self {
-        self.sample_size = size;
+        self.sample_size = Some(size);
         self
     }
 
-    pub fn tolerance(mut self, percent: f64) -> Self {
-        self.tolerance_percent = percent;
+    pub fn with_confidence(mut self, confidence: f64) -> Self {
+        self.confidence = confidence;
         self
     }
 
+    /// Requests the test sends
+    pub fn samples(&self) -> usize {
+        self.sample_size.unwrap_or_else(|| {
+            let weights: Vec<u32> = self.weights.iter().map(|w| w.weight).collect();
+            required_samples(&weights, self.confidence)
+        })
+    }
+
     pub async fn run(&self, client: &HttpClient) -> Result<TestResult> {
-        info!(
-            "Running Canary Traffic Test with {} samples",
-            self.sample_size
-        );
+        let samples = self.samples();
+        info!("Running Canary Traffic Test with {} samples", samples);
         let start = std::time::Instant::now();
-        let mut details = Vec::new();
-
-        // Calculate expected percentages
-        let total_weight: u32 = self.weights.iter().map(|w| w.weight).sum();
-        let expected: HashMap<String, f64> = self
-            .weights
-            .iter()
-            .map(|w| {
-                (
-                    w.name.clone(),
-                    (w.weight as f64 / total_weight as f64) * 100.0,
-                )
-            })
-            .collect();
+        let mut details = vec![format!(
+            "○ {samples} requests, {}% confidence",
+            self.confidence * 100.0
+        )];
 
         // Count actual distribution
         let mut counts: HashMap<String, usize> = HashMap::new();
         let mut failures = 0;
+        let mut unattributed = 0;
 
-        for _ in 0..self.sample_size {
+        for _ in 0..samples {
             let response = client
                 .test_path_routing(&self.gateway_ip, self.gateway_port, &self.path)
                 .await;
@@ -97,11 +115,11 @@ impl CanaryTrafficTest {
             match response {
                 Ok(resp) if resp.is_success() => {
                     // Identify which backend responded
-                    for backend in &self.weights {
-                        if resp.body_contains(&backend.name) {
-                            *counts.entry(backend.name.clone()).or_insert(0) += 1;
-                            break;
-                        }
+                    let echo = resp.echo().unwrap_or_default();
+                    if let Some(backend) = self.weights.iter().find(|w| echo.served_by(&w.name)) {
+                        *counts.entry(backend.name.clone()).or_insert(0) += 1;
+                    } else {
+                        unattributed += 1;
                     }
                 }
                 _ => {
@@ -110,54 +128,178 @@ impl CanaryTrafficTest {
             }
         }
 
-        // Analyze distribution
-        let successful = self.sample_size - failures;
-        let mut all_within_tolerance = true;
-
-        for backend in &self.weights {
-            let count = counts.get(&backend.name).copied().unwrap_or(0);
-            let actual_percent = if successful > 0 {
-                (count as f64 / successful as f64) * 100.0
-            } else {
-                0.0
-            };
-            let expected_percent = expected.get(&backend.name).copied().unwrap_or(0.0);
-            let diff = (actual_percent - expected_percent).abs();
-
-            if diff <= self.tolerance_percent {
-                details.push(format!(
-                    "✓ {} actual: {:.1}%, expected: {:.1}% (diff: {:.1}%)",
-                    backend.name, actual_percent, expected_percent, diff
-                ));
-            } else {
-                all_within_tolerance = false;
-                details.push(format!(
-                    "✗ {} actual: {:.1}%, expected: {:.1}% (diff: {:.1}% > tolerance {}%)",
-                    backend.name, actual_percent, expected_percent, diff, self.tolerance_percent
-                ));
-            }
-        }
+        let fit = GoodnessOfFit::check(&self.weights, &counts, self.confidence);
+        details.extend(fit.lines.iter().cloned());
 
         if failures > 0 {
             details.push(format!("⚠ {failures} requests failed"));
         }
+        if unattributed > 0 {
+            details.push(format!(
+                "⚠ {unattributed} responses came from none of the weighted backends"
+            ));
+        }
 
         let duration = start.elapsed();
 
         Ok(TestResult {
             test_case: TestCase::CanaryTraffic,
-            status: if all_within_tolerance && failures < self.sample_size / 10 {
+            status: if fit.passed && failures < samples / 10 {
                 TestStatus::Pass
             } else {
                 TestStatus::Fail
             },
             duration_ms: duration.as_millis() as u64,
             message: Some(details.join("\n")),
-            details: None,
+            details: Some(serde_json::json!({
+                "samples": samples,
+                "confidence": self.confidence,
+                "chi_squared": fit.statistic,
+                "critical_value": fit.critical,
+                "degrees_of_freedom": fit.degrees_of_freedom,
+            })),
+            retries: 0,
+            backends: Vec::new(),
         })
     }
 }
 
+/// Chi-squared goodness-of-fit of an observed split to route weights
+#[derive(Clone, Debug)]
+struct GoodnessOfFit {
+    passed: bool,
+    statistic: f64,
+    critical: f64,
+    degrees_of_freedom: usize,
+    lines: Vec<String>,
+}
+
+impl GoodnessOfFit {
+    /// Test `counts` (responses per backend) against `weights`; backends
+    /// weighted 0 must receive nothing and are left out of the statistic
+    fn check(
+        weights: &[WeightedBackend],
+        counts: &HashMap<String, usize>,
+        confidence: f64,
+    ) -> Self {
+        let count = |backend: &WeightedBackend| counts.get(&backend.name).copied().unwrap_or(0);
+        let total_weight: u32 = weights.iter().map(|w| w.weight).sum();
+        let observed: usize = weights.iter().map(count).sum();
+        let mut lines = Vec::new();
+        let mut passed = observed > 0;
+
+        let mut statistic = 0.0;
+        let mut weighted = 0;
+        for backend in weights {
+            let hits = count(backend);
+            let share = backend.weight as f64 / total_weight.max(1) as f64;
+            let actual = hits as f64 / observed.max(1) as f64;
+            if backend.weight == 0 {
+                let symbol = if hits == 0 { "✓" } else { "✗" };
+                passed &= hits == 0;
+                lines.push(format!(
+                    "{symbol} {}: {hits} responses (weight 0)",
+                    backend.name
+                ));
+                continue;
+            }
+            weighted += 1;
+            let expected = share * observed as f64;
+            if expected > 0.0 {
+                statistic += (hits as f64 - expected).powi(2) / expected;
+            }
+            lines.push(format!(
+                "○ {}: {:.2}% ({hits}/{observed}), expected {:.2}%",
+                backend.name,
+                actual * 100.0,
+                share * 100.0
+            ));
+        }
+
+        let degrees_of_freedom = weighted.max(1) - 1;
+        let critical = if degrees_of_freedom == 0 {
+            0.0
+        } else {
+            chi_squared_critical(degrees_of_freedom, confidence)
+        };
+        let fits = statistic <= critical || degrees_of_freedom == 0;
+        passed &= fits;
+
+        if observed == 0 {
+            lines.push("✗ No responses from the weighted backends".to_string());
+        } else if degrees_of_freedom > 0 {
+            let (symbol, relation, verdict) = if fits {
+                ("✓", "≤", "split matches the weights")
+            } else {
+                ("✗", ">", "split differs from the weights")
+            };
+            lines.push(format!(
+                "{symbol} χ² = {statistic:.2} {relation} {critical:.2} ({degrees_of_freedom} df, {}% confidence): {verdict}",
+                confidence * 100.0
+            ));
+        }
+
+        Self {
+            passed,
+            statistic,
+            critical,
+            degrees_of_freedom,
+            lines,
+        }
+    }
+}
+
+/// Requests needed to check a split of `weights` at `confidence`
+///
+/// Enough for the smallest non-zero share to be estimated within half its
+/// size at that confidence, and for every expected count to be at least 5
+/// (where the chi-squared approximation holds), within
+/// [`MIN_CANARY_SAMPLES`, `MAX_CANARY_SAMPLES`].
+pub fn required_samples(weights: &[u32], confidence: f64) -> usize {
+    let total: u32 = weights.iter().sum();
+    let Some(&smallest) = weights.iter().filter(|&&w| w > 0).min() else {
+        return MIN_CANARY_SAMPLES;
+    };
+    let share = smallest as f64 / total as f64;
+    if share >= 1.0 {
+        return MIN_CANARY_SAMPLES;
+    }
+    let z = z_score(confidence);
+    let precision = z * z * (1.0 - share) / (share * 0.25);
+    let expected = 5.0 / share;
+    (precision.max(expected).ceil() as usize).clamp(MIN_CANARY_SAMPLES, MAX_CANARY_SAMPLES)
+}
+
+/// Value a chi-squared statistic with `df` degrees of freedom exceeds with
+/// probability `1 - confidence` (closed forms for one and two degrees of
+/// freedom, the Wilson-Hilferty approximation beyond)
+pub fn chi_squared_critical(df: usize, confidence: f64) -> f64 {
+    match df {
+        0 | 1 => {
+            let z = z_score(confidence);
+            return z * z;
+        }
+        2 => return -2.0 * (1.0 - confidence).ln(),
+        _ => {}
+    }
+    let k = df as f64;
+    let z = upper_quantile(1.0 - confidence);
+    let h = 2.0 / (9.0 * k);
+    k * (1.0 - h + z * h.sqrt()).powi(3)
+}
+
+/// Path of the rate-limited route
+pub const RATE_LIMIT_PATH: &str = "/rate-limited";
+
+/// HTTPRoute the rate-limit policy applies to
+pub const RATE_LIMIT_ROUTE: &str = "rate-limited";
+
+/// Requests per second the rate-limit policy allows
+pub const RATE_LIMIT_RPS: u32 = 10;
+
+/// Burst the rate-limit policy allows on top of [`RATE_LIMIT_RPS`]
+pub const RATE_LIMIT_BURST: u32 = 5;
+
 /// Test 8: Rate Limiting
 #[derive(Clone, Debug)]
 pub struct RateLimitingTest {
@@ -174,9 +316,9 @@ impl RateLimitingTest {
         Self {
             gateway_ip: gateway_ip.into(),
             gateway_port,
-            path: "/rate-limited".to_string(),
-            requests_per_second: 10,
-            burst_size: 5,
+            path: RATE_LIMIT_PATH.to_string(),
+            requests_per_second: RATE_LIMIT_RPS,
+            burst_size: RATE_LIMIT_BURST,
             test_duration_secs: 5,
         }
     }
@@ -258,6 +400,8 @@ impl RateLimitingTest {
             duration_ms: duration.as_millis() as u64,
             message: Some(details.join("\n")),
             details: None,
+            retries: 0,
+            backends: Vec::new(),
         })
     }
 }
@@ -381,10 +525,24 @@ impl TimeoutRetryTest {
             duration_ms: duration.as_millis() as u64,
             message: Some(details.join("\n")),
             details: None,
+            retries: 0,
+            backends: Vec::new(),
         })
     }
 }
 
+/// Path of the session affinity route
+pub const SESSION_AFFINITY_PATH: &str = "/session";
+
+/// HTTPRoute the session affinity policy applies to
+pub const SESSION_AFFINITY_ROUTE: &str = "session-affinity";
+
+/// Deployment/Service behind the session affinity route
+pub const SESSION_AFFINITY_BACKEND: &str = "session-backend";
+
+/// Cookie the session affinity policy hashes on
+pub const SESSION_COOKIE: &str = "gateway-poc-session";
+
 /// Test 10: Session Affinity
 #[derive(Clone, Debug)]
 pub struct SessionAffinityTest {
@@ -393,9 +551,11 @@ pub struct SessionAffinityTest {
     pub path: String,
     pub num_requests: usize,
     pub affinity_type: AffinityType,
+    /// Client addresses for source-IP affinity (empty = the OS-chosen one)
+    pub source_addresses: Vec<IpAddr>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum AffinityType {
     Cookie,
     Header,
@@ -407,9 +567,10 @@ impl SessionAffinityTest {
         Self {
             gateway_ip: gateway_ip.into(),
             gateway_port,
-            path: "/session".to_string(),
+            path: SESSION_AFFINITY_PATH.to_string(),
             num_requests: 10,
             affinity_type: AffinityType::Cookie,
+            source_addresses: Vec::new(),
         }
     }
 
@@ -423,6 +584,12 @@ impl SessionAffinityTest {
         self
     }
 
+    /// Send source-IP affinity requests from each of these local addresses
+    pub fn with_source_addresses(mut self, addresses: Vec<IpAddr>) -> Self {
+        self.source_addresses = addresses;
+        self
+    }
+
     pub fn num_requests(mut self, count: usize) -> Self {
         self.num_requests = count;
         self
@@ -433,27 +600,43 @@ impl SessionAffinityTest {
         let start = std::time::Instant::now();
         let mut details = Vec::new();
 
+        if self.affinity_type == AffinityType::SourceIp {
+            let status = self.check_source_ip(client, &mut details).await?;
+            return Ok(TestResult {
+                test_case: TestCase::SessionAffinity,
+                status,
+                duration_ms: start.elapsed().as_millis() as u64,
+                message: Some(details.join("\n")),
+                details: None,
+                retries: 0,
+                backends: Vec::new(),
+            });
+        }
+
         // First request to get session cookie
         let first_response = client
             .test_path_routing(&self.gateway_ip, self.gateway_port, &self.path)
             .await?;
 
         if !first_response.is_success() {
+            details.push(format!(
+                "First request failed with status {}",
+                first_response.status_code
+            ));
             return Ok(TestResult {
                 test_case: TestCase::SessionAffinity,
                 status: TestStatus::Fail,
                 duration_ms: start.elapsed().as_millis() as u64,
-                message: Some(format!(
-                    "First request failed with status {}",
-                    first_response.status_code
-                )),
+                message: Some(details.join("\n")),
                 details: None,
+                retries: 0,
+                backends: Vec::new(),
             });
         }
 
         // Extract session info from first response
         let session_cookie = first_response.get_header("set-cookie");
-        let first_backend = extract_backend_id(&first_response.body);
+        let first_backend = backend_pod(&first_response);
 
         if first_backend.is_none() {
             details.push("⚠ Could not identify backend from response".to_string());
@@ -464,17 +647,19 @@ impl SessionAffinityTest {
         let mut different_backend_count = 0;
         let mut headers = HashMap::new();
 
-        // Include cookie if available
+        // Include cookie if available (its name=value pair, without attributes)
         if let Some(cookie) = session_cookie {
-            headers.insert("Cookie".to_string(), cookie.clone());
+            let pair = cookie.split(';').next().unwrap_or(cookie).trim();
+            headers.insert("Cookie".to_string(), pair.to_string());
         }
 
         for _ in 1..self.num_requests {
             let response = client
                 .get_with_headers(
                     &format!(
-                        "http://{}:{}{}",
-                        self.gateway_ip, self.gateway_port, self.path
+                        "http://{}{}",
+                        authority(&self.gateway_ip, self.gateway_port),
+                        self.path
                     ),
                     headers.clone(),
                 )
@@ -482,7 +667,7 @@ impl SessionAffinityTest {
 
             if let Ok(resp) = response {
                 if resp.is_success() {
-                    let backend = extract_backend_id(&resp.body);
+                    let backend = backend_pod(&resp);
                     if backend == first_backend {
                         same_backend_count += 1;
                     } else {
@@ -525,29 +710,407 @@ impl SessionAffinityTest {
             duration_ms: duration.as_millis() as u64,
             message: Some(details.join("\n")),
             details: None,
+            retries: 0,
+            backends: Vec::new(),
+        })
+    }
+
+    /// Requests from one client address, each on a new connection, should
+    /// all reach the same backend
+    async fn check_source_ip(
+        &self,
+        client: &HttpClient,
+        details: &mut Vec<String>,
+    ) -> Result<TestStatus> {
+        let url = format!(
+            "http://{}{}",
+            authority(&self.gateway_ip, self.gateway_port),
+            self.path
+        );
+        let sources: Vec<Option<IpAddr>> = if self.source_addresses.is_empty() {
+            details.push(
+                "⚠ One client address only; pass --source-address (repeatable) to vary it"
+                    .to_string(),
+            );
+            vec![None]
+        } else {
+            self.source_addresses.iter().copied().map(Some).collect()
+        };
+
+        let mut consistent = 0;
+        let mut unidentified = 0;
+        let mut pinned_backends = HashSet::new();
+        for source in &sources {
+            let label = source.map_or_else(|| "default address".to_string(), |a| a.to_string());
+            let source_client = client.unpooled(*source)?;
+            let mut counts: HashMap<Option<String>, usize> = HashMap::new();
+            let mut errors = 0;
+            for _ in 0..self.num_requests {
+                match source_client.get(&url).await {
+                    Ok(resp) if resp.is_success() => {
+                        *counts.entry(backend_pod(&resp)).or_default() += 1;
+                    }
+                    _ => errors += 1,
+                }
+            }
+
+            let (backend, hits) = counts
+                .iter()
+                .max_by_key(|(_, n)| **n)
+                .map(|(backend, n)| (backend.clone(), *n))
+                .unwrap_or_default();
+            details.push(format!(
+                "{label}: {hits}/{} to {} ({} backends, {errors} errors)",
+                self.num_requests,
+                backend.as_deref().unwrap_or("unidentified backend"),
+                counts.len()
+            ));
+            // Responses that do not name their pod all look like one backend
+            if backend.is_none() && hits > 0 {
+                unidentified += 1;
+            } else if hits as f64 >= self.num_requests as f64 * 0.9 {
+                consistent += 1;
+                pinned_backends.extend(backend);
+            }
+        }
+
+        if sources.len() > 1 {
+            details.push(format!(
+                "{} backends across {} client addresses",
+                pinned_backends.len(),
+                sources.len()
+            ));
+        }
+        if unidentified > 0 {
+            details.push(format!(
+                "✗ {unidentified} of {} client addresses reached a backend that does not \
+                 identify its pod, so affinity cannot be checked",
+                sources.len()
+            ));
+            Ok(TestStatus::Fail)
+        } else if consistent == sources.len() {
+            details.push("✓ Source-IP affinity working".to_string());
+            Ok(TestStatus::Pass)
+        } else {
+            details.push(format!(
+                "✗ {} of {} client addresses spread across backends (is the client IP \
+                 preserved up to the gateway?)",
+                sources.len() - consistent,
+                sources.len()
+            ));
+            Ok(TestStatus::Fail)
+        }
+    }
+}
+
+/// Pod that answered, as the echo backend reports it
+fn backend_pod(resp: &HttpResponse) -> Option<String> {
+    resp.backend_identity().map(|backend| backend.pod)
+}
+
+/// HTTPRoute the consistent-hash policy applies to
+pub const CONSISTENT_HASH_ROUTE: &str = "consistent-hash";
+
+/// Path routed to the consistent-hash backend
+pub const CONSISTENT_HASH_PATH: &str = "/hash";
+
+/// Request header the consistent-hash policy is keyed on
+pub const CONSISTENT_HASH_HEADER: &str = "X-User-Id";
+
+/// Deployment/Service behind the consistent-hash route
+pub const CONSISTENT_HASH_BACKEND: &str = "hash-backend";
+
+/// Test 18: Consistent Hashing
+#[derive(Clone)]
+pub struct ConsistentHashTest {
+    pub gateway_ip: String,
+    pub gateway_port: u16,
+    pub path: String,
+    pub header: String,
+    pub num_keys: usize,
+    pub requests_per_key: usize,
+    /// Minimum fraction of keys that must stick to a single backend
+    pub min_stability: f64,
+    /// Scales the backend to check how many keys move (skipped when None)
+    pub scaler: Option<DeploymentScaler>,
+    /// Registers the replica restore so an aborted test still restores it
+    pub teardown: Teardown,
+    pub deployment: String,
+    /// Wait after a rollout for the gateway to pick up the new endpoints
+    pub settle_secs: u64,
+    pub rollout_timeout_secs: u64,
+}
+
+impl ConsistentHashTest {
+    pub fn new(gateway_ip: impl Into<String>, gateway_port: u16) -> Self {
+        Self {
+            gateway_ip: gateway_ip.into(),
+            gateway_port,
+            path: CONSISTENT_HASH_PATH.to_string(),
+            header: CONSISTENT_HASH_HEADER.to_string(),
+            num_keys: 20,
+            requests_per_key: 5,
+            min_stability: 0.95,
+            scaler: None,
+            teardown: Teardown::new(),
+            deployment: CONSISTENT_HASH_BACKEND.to_string(),
+            settle_secs: 5,
+            rollout_timeout_secs: 120,
+        }
+    }
+
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    pub fn with_header(mut self, header: impl Into<String>) -> Self {
+        self.header = header.into();
+        self
+    }
+
+    pub fn num_keys(mut self, count: usize) -> Self {
+        self.num_keys = count;
+        self
+    }
+
+    pub fn requests_per_key(mut self, count: usize) -> Self {
+        self.requests_per_key = count;
+        self
+    }
+
+    /// Scale `deployment` by one replica to check key remapping
+    pub fn with_scaler(mut self, scaler: Option<DeploymentScaler>) -> Self {
+        self.scaler = scaler;
+        self
+    }
+
+    pub fn with_deployment(mut self, deployment: impl Into<String>) -> Self {
+        self.deployment = deployment.into();
+        self
+    }
+
+    /// Registry that restores the original replica count if the test is aborted
+    pub fn with_teardown(mut self, teardown: Teardown) -> Self {
+        self.teardown = teardown;
+        self
+    }
+
+    pub async fn run(&self, client: &HttpClient) -> Result<TestResult> {
+        info!("Running Consistent Hashing Test ({})", self.header);
+        let start = std::time::Instant::now();
+        let mut details = Vec::new();
+
+        let before = stable_mapping(&self.observe_keys(client).await);
+        let (stability, backends) = mapping_stats(&before);
+        details.push(format!(
+            "{} keys → {} backends, {:.1}% stable",
+            before.len(),
+            backends,
+            stability * 100.0
+        ));
+
+        let mut status = if before.is_empty() {
+            details.push("✗ Could not identify backends from responses".to_string());
+            TestStatus::Fail
+        } else if stability < self.min_stability {
+            details.push(format!(
+                "✗ Keys not pinned to a backend (need {:.0}% stable)",
+                self.min_stability * 100.0
+            ));
+            TestStatus::Fail
+        } else if backends < 2 {
+            details.push(
+                "○ Only one backend observed; consistent hashing cannot be verified".to_string(),
+            );
+            TestStatus::Skip
+        } else {
+            details.push(format!("✓ Keys map stably on {}", self.header));
+            TestStatus::Pass
+        };
+
+        if status == TestStatus::Pass {
+            match &self.scaler {
+                Some(scaler) => {
+                    if !self
+                        .check_scaling(client, scaler, &before, &mut details)
+                        .await
+                    {
+                        status = TestStatus::Fail;
+                    }
+                }
+                None => details.push(
+                    "⚠ Scaling check skipped (needs --setup-policies and cluster access)"
+                        .to_string(),
+                ),
+            }
+        }
+
+        Ok(TestResult {
+            test_case: TestCase::ConsistentHashing,
+            status,
+            duration_ms: start.elapsed().as_millis() as u64,
+            message: Some(details.join("\n")),
+            details: None,
+            retries: 0,
+            backends: Vec::new(),
+        })
+    }
+
+    /// Backends seen per key over `requests_per_key` requests
+    async fn observe_keys(&self, client: &HttpClient) -> BTreeMap<String, Vec<String>> {
+        let url = format!(
+            "http://{}{}",
+            authority(&self.gateway_ip, self.gateway_port),
+            self.path
+        );
+        let mut observations = BTreeMap::new();
+
+        for i in 0..self.num_keys {
+            let key = format!("user-{i}");
+            let headers = HashMap::from([(self.header.clone(), key.clone())]);
+            let mut seen = Vec::new();
+            for _ in 0..self.requests_per_key {
+                match client.get_with_headers(&url, headers.clone()).await {
+                    Ok(resp) if resp.is_success() => {
+                        if let Some(backend) = backend_pod(&resp) {
+                            seen.push(backend);
+                        }
+                    }
+                    Ok(resp) => debug!("Key {} got status {}", key, resp.status_code),
+                    Err(e) => debug!("Key {} request failed: {}", key, e),
+                }
+            }
+            if !seen.is_empty() {
+                observations.insert(key, seen);
+            }
+        }
+
+        observations
+    }
+
+    /// Scale out by one replica, compare key placement, then scale back
+    async fn check_scaling(
+        &self,
+        client: &HttpClient,
+        scaler: &DeploymentScaler,
+        before: &BTreeMap<String, Option<String>>,
+        details: &mut Vec<String>,
+    ) -> bool {
+        let timeout = Duration::from_secs(self.rollout_timeout_secs);
+        let original = match scaler.replicas(&self.deployment).await {
+            Ok(replicas) => replicas,
+            Err(e) => {
+                details.push(format!("⚠ Scaling check skipped: {e}"));
+                return true;
+            }
+        };
+        let scaled = original + 1;
+
+        let restore = {
+            let scaler = scaler.clone();
+            let deployment = self.deployment.clone();
+            self.teardown.register(
+                format!(
+                    "restore deployment {} to {original} replicas",
+                    self.deployment
+                ),
+                move || {
+                    let scaler = scaler.clone();
+                    let deployment = deployment.clone();
+                    async move { scaler.scale_and_wait(&deployment, original, timeout).await }
+                },
+            )
+        };
+
+        if let Err(e) = scaler
+            .scale_and_wait(&self.deployment, scaled, timeout)
+            .await
+        {
+            details.push(format!("✗ Scale-out to {scaled} replicas failed: {e}"));
+            self.restore(restore, original, details).await;
+            return false;
+        }
+        tokio::time::sleep(Duration::from_secs(self.settle_secs)).await;
+
+        let after = stable_mapping(&self.observe_keys(client).await);
+        self.restore(restore, original, details).await;
+
+        let (stability, backends) = mapping_stats(&after);
+        let moved = remapped_fraction(before, &after);
+        // Ideal consistent hashing moves ~1/n keys; modulo hashing moves most
+        let allowed = max_remapped_fraction(scaled);
+        details.push(format!(
+            "After scaling {original}→{scaled}: {backends} backends, {:.1}% stable, {:.1}% of keys moved (≤{:.0}% allowed)",
+            stability * 100.0,
+            moved * 100.0,
+            allowed * 100.0
+        ));
+
+        if stability < self.min_stability {
+            details.push("✗ Keys not stable after scaling".to_string());
+            false
+        } else if moved > allowed {
+            details.push("✗ Too many keys remapped; hashing is not consistent".to_string());
+            false
+        } else {
+            details.push("✓ Minimal key remapping after scaling".to_string());
+            true
+        }
+    }
+
+    async fn restore(&self, restore: Undo, replicas: i32, details: &mut Vec<String>) {
+        if let Err(e) = restore.run().await {
+            details.push(format!(
+                "⚠ Failed to restore {} to {replicas} replicas: {e}",
+                self.deployment
+            ));
+        }
+    }
+}
+
+/// Backend each key was pinned to; None if its requests were spread
+fn stable_mapping(
+    observations: &BTreeMap<String, Vec<String>>,
+) -> BTreeMap<String, Option<String>> {
+    observations
+        .iter()
+        .map(|(key, seen)| {
+            let pinned = seen.iter().all(|b| b == &seen[0]).then(|| seen[0].clone());
+            (key.clone(), pinned)
         })
+        .collect()
+}
+
+/// (fraction of stable keys, distinct backends among stable keys)
+fn mapping_stats(mapping: &BTreeMap<String, Option<String>>) -> (f64, usize) {
+    if mapping.is_empty() {
+        return (0.0, 0);
     }
+    let backends: HashSet<&String> = mapping.values().flatten().collect();
+    let stable = mapping.values().filter(|b| b.is_some()).count();
+    (stable as f64 / mapping.len() as f64, backends.len())
 }
 
-/// Extract backend identifier from response body
-fn extract_backend_id(body: &str) -> Option<String> {
-    // Try to find backend identifier patterns
-    // Common patterns: "pod-xxx", "backend-xxx", "server: xxx"
-    if let Some(start) = body.find("pod-") {
-        let end = body[start..]
-            .find(|c: char| !c.is_alphanumeric() && c != '-')
-            .map(|i| start + i)
-            .unwrap_or(body.len().min(start + 50));
-        return Some(body[start..end].to_string());
-    }
-    if let Some(start) = body.find("backend-") {
-        let end = body[start..]
-            .find(|c: char| !c.is_alphanumeric() && c != '-')
-            .map(|i| start + i)
-            .unwrap_or(body.len().min(start + 50));
-        return Some(body[start..end].to_string());
-    }
-    None
+/// Fraction of keys stable in both mappings that moved to another backend
+fn remapped_fraction(
+    before: &BTreeMap<String, Option<String>>,
+    after: &BTreeMap<String, Option<String>>,
+) -> f64 {
+    let pairs: Vec<(&String, &String)> = before
+        .iter()
+        .filter_map(|(key, b)| Some((b.as_ref()?, after.get(key)?.as_ref()?)))
+        .collect();
+    if pairs.is_empty() {
+        return 0.0;
+    }
+    let moved = pairs.iter().filter(|(b, a)| b != a).count();
+    moved as f64 / pairs.len() as f64
+}
+
+/// Allowed remapped fraction when scaling to `replicas` (1.5x the ideal 1/n)
+fn max_remapped_fraction(replicas: i32) -> f64 {
+    (1.5 / replicas.max(1) as f64).min(1.0)
 }
 
 /// Combined traffic test runner
@@ -566,14 +1129,19 @@ impl TrafficTestSuite {
         })
     }
 
+    /// Use a shared client (e.g. with the configured timeout)
+    pub fn with_client(mut self, client: HttpClient) -> Self {
+        self.client = client;
+        self
+    }
+
     pub async fn run_all(&self) -> Result<Vec<TestResult>> {
         let mut results = Vec::new();
 
         // Canary traffic test
         let canary_test = CanaryTrafficTest::new(&self.gateway_ip, self.gateway_port)
             .add_backend("stable", 90)
-            .add_backend("canary", 10)
-            .sample_size(100);
+            .add_backend("canary", 10);
         results.push(canary_test.run(&self.client).await?);
 
         // Rate limiting test
@@ -590,6 +1158,10 @@ impl TrafficTestSuite {
             SessionAffinityTest::new(&self.gateway_ip, self.gateway_port).num_requests(10);
         results.push(session_test.run(&self.client).await?);
 
+        // Consistent hashing test (no scaling check without cluster access)
+        let hash_test = ConsistentHashTest::new(&self.gateway_ip, self.gateway_port);
+        results.push(hash_test.run(&self.client).await?);
+
         Ok(results)
     }
 }
@@ -603,12 +1175,88 @@ mod tests {
         let test = CanaryTrafficTest::new("10.0.0.1", 80)
             .add_backend("stable", 90)
             .add_backend("canary", 10)
-            .sample_size(200)
-            .tolerance(15.0);
+            .with_confidence(0.95);
 
         assert_eq!(test.weights.len(), 2);
-        assert_eq!(test.sample_size, 200);
-        assert_eq!(test.tolerance_percent, 15.0);
+        assert_eq!(test.samples(), required_samples(&[90, 10], 0.95));
+        assert_eq!(test.sample_size(200).samples(), 200);
+    }
+
+    #[test]
+    fn test_required_samples() {
+        // z² (1 - p) / (p / 2)² at 99%: the 1% share needs ten times the requests
+        assert!((238..=240).contains(&required_samples(&[90, 10], 0.99)));
+        assert!((2620..=2640).contains(&required_samples(&[99, 1], 0.99)));
+        assert!(required_samples(&[99, 1], 0.95) < required_samples(&[99, 1], 0.99));
+        assert_eq!(required_samples(&[50, 50], 0.99), MIN_CANARY_SAMPLES);
+        assert_eq!(required_samples(&[100, 0], 0.99), MIN_CANARY_SAMPLES);
+        assert_eq!(required_samples(&[9999, 1], 0.99), MAX_CANARY_SAMPLES);
+    }
+
+    #[test]
+    fn test_chi_squared_critical() {
+        let close = |a: f64, b: f64| (a - b).abs() < 0.05;
+        assert!(close(chi_squared_critical(1, 0.99), 6.635));
+        assert!(close(chi_squared_critical(1, 0.95), 3.841));
+        assert!(close(chi_squared_critical(2, 0.95), 5.991));
+        assert!(close(chi_squared_critical(3, 0.99), 11.345));
+    }
+
+    #[test]
+    fn test_goodness_of_fit() {
+        let weights = |split: &[(&str, u32)]| -> Vec<WeightedBackend> {
+            split
+                .iter()
+                .map(|(name, weight)| WeightedBackend {
+                    name: name.to_string(),
+                    weight: *weight,
+                })
+                .collect()
+        };
+        let counts = |split: &[(&str, usize)]| -> HashMap<String, usize> {
+            split.iter().map(|(n, c)| (n.to_string(), *c)).collect()
+        };
+        let ninety_ten = weights(&[("stable", 90), ("canary", 10)]);
+        let ninety_nine_one = weights(&[("stable", 99), ("canary", 1)]);
+
+        // Sampling noise passes
+        let fit = GoodnessOfFit::check(
+            &ninety_ten,
+            &counts(&[("stable", 210), ("canary", 29)]),
+            0.99,
+        );
+        assert!(fit.passed, "{:?}", fit.lines);
+        assert_eq!(fit.degrees_of_freedom, 1);
+        let fit = GoodnessOfFit::check(
+            &ninety_nine_one,
+            &counts(&[("stable", 2595), ("canary", 33)]),
+            0.99,
+        );
+        assert!(fit.passed, "{:?}", fit.lines);
+
+        // A canary that gets nothing, or a 50/50 split, fails
+        let fit = GoodnessOfFit::check(&ninety_nine_one, &counts(&[("stable", 2628)]), 0.99);
+        assert!(!fit.passed);
+        assert!(
+            fit.lines[2].starts_with("✗ χ² = 26.55 > 6.6"),
+            "{}",
+            fit.lines[2]
+        );
+        let fit = GoodnessOfFit::check(
+            &ninety_ten,
+            &counts(&[("stable", 120), ("canary", 119)]),
+            0.99,
+        );
+        assert!(!fit.passed);
+
+        // Weight 0 must receive nothing; no responses at all fails
+        let all_stable = weights(&[("stable", 100), ("canary", 0)]);
+        let fit = GoodnessOfFit::check(&all_stable, &counts(&[("stable", 100)]), 0.99);
+        assert!(fit.passed, "{:?}", fit.lines);
+        let fit =
+            GoodnessOfFit::check(&all_stable, &counts(&[("stable", 99), ("canary", 1)]), 0.99);
+        assert!(!fit.passed);
+        assert!(!GoodnessOfFit::check(&ninety_ten, &HashMap::new(), 0.99).passed);
     }
 
     #[test]
@@ -631,15 +1279,39 @@ mod tests {
     }
 
     #[test]
-    fn test_extract_backend_id() {
-        assert_eq!(
-            extract_backend_id("Server: pod-abc123-xyz"),
-            Some("pod-abc123-xyz".to_string())
-        );
+    fn test_consistent_hash_mapping() {
+        let observations = BTreeMap::from([
+            ("user-0".to_string(), vec!["pod-a".to_string(); 3]),
+            (
+                "user-1".to_string(),
+                vec!["pod-a".to_string(), "pod-b".to_string()],
+            ),
+            ("user-2".to_string(), vec!["pod-b".to_string(); 3]),
+            ("user-3".to_string(), vec!["pod-c".to_string(); 3]),
+        ]);
+        let before = stable_mapping(&observations);
+        assert_eq!(before["user-1"], None);
+        assert_eq!(mapping_stats(&before), (0.75, 3));
+
+        let mut after = before.clone();
+        after.insert("user-3".to_string(), Some("pod-d".to_string()));
+        assert!((remapped_fraction(&before, &after) - 1.0 / 3.0).abs() < 1e-9);
+        assert_eq!(remapped_fraction(&before, &before), 0.0);
+
+        assert_eq!(max_remapped_fraction(3), 0.5);
+        assert_eq!(max_remapped_fraction(1), 1.0);
+    }
+
+    #[test]
+    fn test_backend_pod() {
+        let response = |body: &str| HttpResponse::fixture(200, HashMap::new(), body);
         assert_eq!(
-            extract_backend_id("backend-v1 responding"),
-            Some("backend-v1".to_string())
+            backend_pod(&response(
+                r#"{"path": "/session", "service": "session", "pod": "session-6d4f9c7b8-x2x4q"}"#
+            )),
+            Some("session-6d4f9c7b8-x2x4q".to_string())
         );
-        assert_eq!(extract_backend_id("no backend info"), None);
+        // Text merely mentioning a pod is not an identity
+        assert_eq!(backend_pod(&response("Server: pod-abc123-xyz")), None);
     }
 }
@@ -0,0 +1,393 @@
+//! Terminal dashboard rendering
+
+use anyhow::{Context, Result};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Gauge, Paragraph, Row, Sparkline, Table, Wrap};
+use ratatui::Frame;
+use std::io::IsTerminal;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::error::TryRecvError;
+use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::task::JoinHandle;
+
+use super::progress::{progress_channel, ProgressEvent, ProgressReporter};
+use super::state::{DashboardState, TestProgress, ERROR_WINDOW};
+use crate::executor::{StopReason, SuiteCancel};
+use crate::models::{TestCase, TestStatus};
+
+/// Redraw interval
+const TICK: Duration = Duration::from_millis(100);
+
+/// Full-screen live dashboard running on a blocking thread
+pub struct Dashboard {
+    reporter: ProgressReporter,
+    handle: JoinHandle<Result<()>>,
+}
+
+impl Dashboard {
+    /// Start the dashboard; None if stdout is not a terminal
+    ///
+    /// Quitting mid-run closes the dashboard and stops `cancel` (if any) as
+    /// Ctrl-C would; the run itself winds down normally.
+    pub fn start(title: impl Into<String>, cancel: Option<SuiteCancel>) -> Option<Self> {
+        if !std::io::stdout().is_terminal() {
+            return None;
+        }
+
+        let (reporter, receiver) = progress_channel();
+        let state = DashboardState::new(title);
+        let handle = tokio::task::spawn_blocking(move || run(state, receiver, cancel));
+        Some(Self { reporter, handle })
+    }
+
+    /// Handle for runners to publish progress
+    pub fn reporter(&self) -> ProgressReporter {
+        self.reporter.clone()
+    }
+
+    /// Show the summary screen and wait until the user closes it
+    pub async fn finish(self, summary: impl Into<String>) -> Result<()> {
+        self.reporter.emit(ProgressEvent::Finished {
+            summary: summary.into(),
+        });
+        drop(self.reporter);
+        self.handle.await.context("Dashboard thread panicked")?
+    }
+}
+
+/// Event/draw loop; returns once the summary screen is dismissed, the user
+/// quits mid-run, or all reporters are dropped without a summary
+fn run(
+    mut state: DashboardState,
+    mut receiver: UnboundedReceiver<ProgressEvent>,
+    cancel: Option<SuiteCancel>,
+) -> Result<()> {
+    let mut terminal = ratatui::init();
+    let started = Instant::now();
+
+    // Ok(true) when the user quit before the run finished
+    let result = (|| -> Result<bool> {
+        loop {
+            loop {
+                match receiver.try_recv() {
+                    Ok(event) => state.apply(event),
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) if state.is_finished() => break,
+                    Err(TryRecvError::Disconnected) => return Ok(false),
+                }
+            }
+
+            terminal.draw(|frame| render(frame, &state, started.elapsed()))?;
+
+            if event::poll(TICK)? {
+                if let Event::Key(key) = event::read()? {
+                    if key.kind != KeyEventKind::Press {
+                        continue;
+                    }
+                    let interrupt = key.code == KeyCode::Char('c')
+                        && key.modifiers.contains(KeyModifiers::CONTROL);
+                    let quit = matches!(key.code, KeyCode::Char('q') | KeyCode::Esc) || interrupt;
+                    if state.is_finished() && (quit || key.code == KeyCode::Enter) {
+                        return Ok(false);
+                    }
+                    if quit {
+                        return Ok(true);
+                    }
+                }
+            }
+        }
+    })();
+
+    ratatui::restore();
+    if result? {
+        match &cancel {
+            Some(cancel) => {
+                eprintln!("Interrupted; finishing running tests");
+                cancel.cancel(StopReason::Interrupted);
+            }
+            None => eprintln!("Dashboard closed; press Ctrl-C to stop"),
+        }
+    }
+    Ok(())
+}
+
+/// Draw the whole dashboard
+pub fn render(frame: &mut Frame, state: &DashboardState, elapsed: Duration) {
+    let [header, body, footer] = Layout::vertical([
+        Constraint::Length(4),
+        Constraint::Min(0),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    render_header(frame, header, state, elapsed);
+
+    if let Some(summary) = &state.summary {
+        let paragraph = Paragraph::new(summary.as_str())
+            .block(Block::bordered().title(" Summary "))
+            .wrap(Wrap { trim: false });
+        frame.render_widget(paragraph, body);
+        frame.render_widget(
+            Paragraph::new("Press q or Enter to exit").style(dim()),
+            footer,
+        );
+        return;
+    }
+
+    match (&state.benchmark, state.tests.is_empty()) {
+        (Some(_), true) => render_benchmark(frame, body, state),
+        (Some(_), false) => {
+            let [tests, bench] =
+                Layout::vertical([Constraint::Min(0), Constraint::Length(12)]).areas(body);
+            render_tests(frame, tests, state);
+            render_benchmark(frame, bench, state);
+        }
+        (None, _) => render_tests(frame, body, state),
+    }
+
+    frame.render_widget(Paragraph::new("q: abort run").style(dim()), footer);
+}
+
+fn render_header(frame: &mut Frame, area: Rect, state: &DashboardState, elapsed: Duration) {
+    let mut spans = vec![Span::styled(
+        state.title.clone(),
+        Style::default().add_modifier(Modifier::BOLD),
+    )];
+    if state.total_rounds > 0 {
+        spans.push(Span::raw(format!(
+            "  round {}/{}",
+            state.round, state.total_rounds
+        )));
+    }
+    spans.push(Span::raw(format!("  {:.0}s", elapsed.as_secs_f64())));
+
+    let (passed, failed, skipped, errors, running) = state.counts();
+    let mut lines = vec![Line::from(spans)];
+    if !state.tests.is_empty() {
+        let mut counts = vec![
+            Span::styled(format!("✓ {passed}"), status_style(TestStatus::Pass)),
+            Span::raw("  "),
+            Span::styled(format!("✗ {failed}"), status_style(TestStatus::Fail)),
+            Span::raw("  "),
+            Span::styled(format!("○ {skipped}"), status_style(TestStatus::Skip)),
+            Span::raw("  "),
+            Span::styled(format!("! {errors}"), status_style(TestStatus::Error)),
+            Span::raw(format!("  running {running}")),
+        ];
+        if !state.rounds.is_empty() {
+            let rounds: Vec<String> = state
+                .rounds
+                .iter()
+                .map(|(round, passed, total)| format!("R{round} {passed}/{total}"))
+                .collect();
+            counts.push(Span::styled(format!("  {}", rounds.join(" ")), dim()));
+        }
+        lines.push(Line::from(counts));
+    }
+
+    frame.render_widget(Paragraph::new(lines).block(Block::bordered()), area);
+}
+
+fn render_tests(frame: &mut Frame, area: Rect, state: &DashboardState) {
+    let rows = state.tests.iter().map(|(number, progress)| {
+        let name = TestCase::from_number(*number)
+            .map(|t| t.name())
+            .unwrap_or_default();
+        let (status, style, duration, message) = match progress {
+            TestProgress::Pending => ("pending".to_string(), dim(), String::new(), String::new()),
+            TestProgress::Running => (
+                "running".to_string(),
+                Style::default().fg(Color::Cyan),
+                String::new(),
+                String::new(),
+            ),
+            TestProgress::Done {
+                status,
+                duration_ms,
+                message,
+            } => (
+                format!("{} {}", status.symbol(), status),
+                status_style(*status),
+                format!("{duration_ms}ms"),
+                message
+                    .as_deref()
+                    .and_then(|m| m.lines().next())
+                    .unwrap_or_default()
+                    .to_string(),
+            ),
+        };
+        Row::new(vec![
+            format!("{number:2}"),
+            name.to_string(),
+            status,
+            duration,
+            message,
+        ])
+        .style(style)
+    });
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(3),
+            Constraint::Length(20),
+            Constraint::Length(10),
+            Constraint::Length(9),
+            Constraint::Min(10),
+        ],
+    )
+    .header(Row::new(vec!["#", "Test", "Status", "Duration", "Message"]).style(bold()))
+    .block(Block::bordered().title(" Tests "));
+    frame.render_widget(table, area);
+}
+
+fn render_benchmark(frame: &mut Frame, area: Rect, state: &DashboardState) {
+    let Some(panel) = &state.benchmark else {
+        return;
+    };
+    let [gauge, stats, latency, errors] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Length(1),
+        Constraint::Min(4),
+        Constraint::Length(4),
+    ])
+    .areas(area);
+
+    let live = &panel.latest;
+    let label = if live.warmup {
+        format!("{} warming up ({:.0}s)", panel.gateway, live.elapsed_secs)
+    } else {
+        format!(
+            "{} {:.0}s / {}s",
+            panel.gateway,
+            (live.elapsed_secs - panel.warmup_secs as f64).max(0.0),
+            panel.duration_secs
+        )
+    };
+    frame.render_widget(
+        Gauge::default()
+            .block(Block::bordered().title(" Benchmark "))
+            .gauge_style(Style::default().fg(Color::Cyan))
+            .ratio(panel.progress())
+            .label(label),
+        gauge,
+    );
+
+    frame.render_widget(
+        Paragraph::new(format!(
+            " {:.1} RPS | mean {:.2}ms | p99 {:.2}ms | {} requests | errors {} total, {} last {}s",
+            live.rps,
+            live.mean_ms,
+            live.p99_ms,
+            live.total_requests,
+            live.total_errors,
+            panel.rolling_errors(),
+            ERROR_WINDOW
+        )),
+        stats,
+    );
+
+    let p99: Vec<u64> = panel.p99_history.iter().copied().collect();
+    frame.render_widget(
+        Sparkline::default()
+            .block(Block::bordered().title(" p99 latency "))
+            .style(Style::default().fg(Color::Green))
+            .data(&p99),
+        latency,
+    );
+
+    let failures: Vec<u64> = panel.error_history.iter().copied().collect();
+    frame.render_widget(
+        Sparkline::default()
+            .block(Block::bordered().title(" errors/s "))
+            .style(Style::default().fg(Color::Red))
+            .data(&failures),
+        errors,
+    );
+}
+
+fn status_style(status: TestStatus) -> Style {
+    let color = match status {
+        TestStatus::Pass => Color::Green,
+        TestStatus::Flaky => Color::LightGreen,
+        TestStatus::Fail => Color::Red,
+        TestStatus::Skip => Color::Yellow,
+        TestStatus::Error => Color::Magenta,
+        TestStatus::NotSupported => Color::DarkGray,
+    };
+    Style::default().fg(color)
+}
+
+fn bold() -> Style {
+    Style::default().add_modifier(Modifier::BOLD)
+}
+
+fn dim() -> Style {
+    Style::default().fg(Color::DarkGray)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmark::LiveSample;
+    use crate::models::TestResult;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn draw(state: &DashboardState) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+        terminal
+            .draw(|frame| render(frame, state, Duration::from_secs(5)))
+            .unwrap();
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect()
+    }
+
+    #[test]
+    fn test_render_progress() {
+        let mut state = DashboardState::new("envoy");
+        state.apply(ProgressEvent::RoundStarted {
+            round: 1,
+            total_rounds: 1,
+            tests: vec![TestCase::HostRouting, TestCase::PathRouting],
+        });
+        state.apply(ProgressEvent::TestFinished {
+            result: TestResult::pass(TestCase::HostRouting, 12),
+        });
+        state.apply(ProgressEvent::BenchmarkStarted {
+            gateway: "envoy".to_string(),
+            duration_secs: 30,
+            warmup_secs: 0,
+        });
+        state.apply(ProgressEvent::BenchmarkSample(LiveSample {
+            elapsed_secs: 3.0,
+            rps: 250.0,
+            ..Default::default()
+        }));
+
+        let screen = draw(&state);
+        assert!(screen.contains("Host Routing"));
+        assert!(screen.contains("12ms"));
+        assert!(screen.contains("250.0 RPS"));
+    }
+
+    #[test]
+    fn test_render_summary() {
+        let mut state = DashboardState::new("envoy");
+        state.apply(ProgressEvent::Finished {
+            summary: "Pass Rate: 100.0%".to_string(),
+        });
+
+        let screen = draw(&state);
+        assert!(screen.contains("Pass Rate: 100.0%"));
+        assert!(screen.contains("Press q or Enter to exit"));
+    }
+}
@@ -0,0 +1,12 @@
+//! Interactive terminal dashboard
+//!
+//! Runners publish [`ProgressEvent`]s through a [`ProgressReporter`]; the
+//! dashboard renders per-test status, live benchmark latency and errors, and
+//! a final summary screen.
+
+mod dashboard;
+mod progress;
+mod state;
+
+pub use dashboard::Dashboard;
+pub use progress::{emit, ProgressEvent, ProgressReporter};
@@ -0,0 +1,63 @@
+//! Progress events emitted by test and benchmark runners
+
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+
+use crate::benchmark::LiveSample;
+use crate::models::{TestCase, TestResult};
+
+/// Live progress of a test or benchmark run
+#[derive(Clone, Debug)]
+pub enum ProgressEvent {
+    /// A round of tests is starting
+    RoundStarted {
+        round: u32,
+        total_rounds: u32,
+        tests: Vec<TestCase>,
+    },
+    /// A test started executing
+    TestStarted { test: TestCase },
+    /// A test completed
+    TestFinished { result: TestResult },
+    /// A round completed
+    RoundFinished {
+        round: u32,
+        passed: usize,
+        total: usize,
+    },
+    /// A benchmark started
+    BenchmarkStarted {
+        gateway: String,
+        duration_secs: u64,
+        warmup_secs: u64,
+    },
+    /// Rolling benchmark statistics (about once per second)
+    BenchmarkSample(LiveSample),
+    /// The run completed; the dashboard switches to the summary screen
+    Finished { summary: String },
+}
+
+/// Cheap, cloneable handle runners use to publish progress
+#[derive(Clone, Debug)]
+pub struct ProgressReporter {
+    sender: UnboundedSender<ProgressEvent>,
+}
+
+impl ProgressReporter {
+    /// Publish an event; ignored once the dashboard has closed
+    pub fn emit(&self, event: ProgressEvent) {
+        let _ = self.sender.send(event);
+    }
+}
+
+/// Create a reporter and the receiver a dashboard consumes
+pub fn progress_channel() -> (ProgressReporter, UnboundedReceiver<ProgressEvent>) {
+    let (sender, receiver) = unbounded_channel();
+    (ProgressReporter { sender }, receiver)
+}
+
+/// Emit through an optional reporter
+pub fn emit(reporter: &Option<ProgressReporter>, event: ProgressEvent) {
+    if let Some(reporter) = reporter {
+        reporter.emit(event);
+    }
+}
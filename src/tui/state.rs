@@ -0,0 +1,228 @@
+//! Dashboard state built from progress events
+
+use std::collections::{BTreeMap, VecDeque};
+
+use super::progress::ProgressEvent;
+use crate::benchmark::LiveSample;
+use crate::models::TestStatus;
+
+/// Latency/error points kept for sparklines (one per sample)
+pub const HISTORY_LEN: usize = 120;
+
+/// Samples counted in the rolling error total
+pub const ERROR_WINDOW: usize = 10;
+
+/// Progress of a single test in the current round
+#[derive(Clone, Debug, PartialEq)]
+pub enum TestProgress {
+    Pending,
+    Running,
+    Done {
+        status: TestStatus,
+        duration_ms: u64,
+        message: Option<String>,
+    },
+}
+
+/// Live benchmark panel
+#[derive(Clone, Debug, Default)]
+pub struct BenchmarkPanel {
+    pub gateway: String,
+    pub duration_secs: u64,
+    pub warmup_secs: u64,
+    pub latest: LiveSample,
+    /// p99 latency per sample in microseconds (sparkline data)
+    pub p99_history: VecDeque<u64>,
+    /// Failed requests per sample
+    pub error_history: VecDeque<u64>,
+}
+
+impl BenchmarkPanel {
+    /// Failed requests over the last `ERROR_WINDOW` samples
+    pub fn rolling_errors(&self) -> u64 {
+        self.error_history.iter().rev().take(ERROR_WINDOW).sum()
+    }
+
+    /// Fraction of the run completed (0.0-1.0)
+    pub fn progress(&self) -> f64 {
+        let total = (self.duration_secs + self.warmup_secs) as f64;
+        if total <= 0.0 {
+            return 0.0;
+        }
+        (self.latest.elapsed_secs / total).clamp(0.0, 1.0)
+    }
+}
+
+/// Everything the dashboard renders
+#[derive(Clone, Debug)]
+pub struct DashboardState {
+    pub title: String,
+    pub round: u32,
+    pub total_rounds: u32,
+    pub tests: BTreeMap<u8, TestProgress>,
+    /// (round, passed, total) for completed rounds
+    pub rounds: Vec<(u32, usize, usize)>,
+    pub benchmark: Option<BenchmarkPanel>,
+    /// Final summary once the run completed
+    pub summary: Option<String>,
+}
+
+impl DashboardState {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            round: 0,
+            total_rounds: 0,
+            tests: BTreeMap::new(),
+            rounds: Vec::new(),
+            benchmark: None,
+            summary: None,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.summary.is_some()
+    }
+
+    /// Apply a progress event
+    pub fn apply(&mut self, event: ProgressEvent) {
+        match event {
+            ProgressEvent::RoundStarted {
+                round,
+                total_rounds,
+                tests,
+            } => {
+                self.round = round;
+                self.total_rounds = total_rounds;
+                self.tests = tests
+                    .into_iter()
+                    .map(|t| (t.number(), TestProgress::Pending))
+                    .collect();
+            }
+            ProgressEvent::TestStarted { test } => {
+                self.tests.insert(test.number(), TestProgress::Running);
+            }
+            ProgressEvent::TestFinished { result } => {
+                self.tests.insert(
+                    result.test_case.number(),
+                    TestProgress::Done {
+                        status: result.status,
+                        duration_ms: result.duration_ms,
+                        message: result.message,
+                    },
+                );
+            }
+            ProgressEvent::RoundFinished {
+                round,
+                passed,
+                total,
+            } => self.rounds.push((round, passed, total)),
+            ProgressEvent::BenchmarkStarted {
+                gateway,
+                duration_secs,
+                warmup_secs,
+            } => {
+                self.benchmark = Some(BenchmarkPanel {
+                    gateway,
+                    duration_secs,
+                    warmup_secs,
+                    ..Default::default()
+                });
+            }
+            ProgressEvent::BenchmarkSample(sample) => {
+                let panel = self.benchmark.get_or_insert_with(BenchmarkPanel::default);
+                push_capped(&mut panel.p99_history, (sample.p99_ms * 1000.0) as u64);
+                push_capped(&mut panel.error_history, sample.errors);
+                panel.latest = sample;
+            }
+            ProgressEvent::Finished { summary } => self.summary = Some(summary),
+        }
+    }
+
+    /// (passed, failed, skipped, errors, running) in the current round
+    pub fn counts(&self) -> (usize, usize, usize, usize, usize) {
+        let mut counts = (0, 0, 0, 0, 0);
+        for progress in self.tests.values() {
+            match progress {
+                TestProgress::Done { status, .. } => match status {
+                    TestStatus::Pass | TestStatus::Flaky => counts.0 += 1,
+                    TestStatus::Fail => counts.1 += 1,
+                    TestStatus::Skip | TestStatus::NotSupported => counts.2 += 1,
+                    TestStatus::Error => counts.3 += 1,
+                },
+                TestProgress::Running => counts.4 += 1,
+                TestProgress::Pending => {}
+            }
+        }
+        counts
+    }
+}
+
+fn push_capped(history: &mut VecDeque<u64>, value: u64) {
+    if history.len() == HISTORY_LEN {
+        history.pop_front();
+    }
+    history.push_back(value);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{TestCase, TestResult};
+
+    #[test]
+    fn test_round_progress() {
+        let mut state = DashboardState::new("envoy");
+        state.apply(ProgressEvent::RoundStarted {
+            round: 1,
+            total_rounds: 3,
+            tests: TestCase::all(),
+        });
+        assert_eq!(state.tests.len(), 34);
+
+        state.apply(ProgressEvent::TestStarted {
+            test: TestCase::HostRouting,
+        });
+        state.apply(ProgressEvent::TestFinished {
+            result: TestResult::fail(TestCase::PathRouting, 12, "404"),
+        });
+        assert_eq!(state.counts(), (0, 1, 0, 0, 1));
+
+        state.apply(ProgressEvent::RoundFinished {
+            round: 1,
+            passed: 17,
+            total: 18,
+        });
+        assert_eq!(state.rounds, vec![(1, 17, 18)]);
+        assert!(!state.is_finished());
+
+        state.apply(ProgressEvent::Finished {
+            summary: "done".to_string(),
+        });
+        assert!(state.is_finished());
+    }
+
+    #[test]
+    fn test_benchmark_history() {
+        let mut state = DashboardState::new("bench");
+        state.apply(ProgressEvent::BenchmarkStarted {
+            gateway: "nginx".to_string(),
+            duration_secs: 100,
+            warmup_secs: 20,
+        });
+        for i in 0..(HISTORY_LEN + 5) {
+            state.apply(ProgressEvent::BenchmarkSample(LiveSample {
+                elapsed_secs: i as f64,
+                p99_ms: 1.5,
+                errors: 1,
+                ..Default::default()
+            }));
+        }
+
+        let panel = state.benchmark.unwrap();
+        assert_eq!(panel.p99_history.len(), HISTORY_LEN);
+        assert_eq!(panel.p99_history[0], 1500);
+        assert_eq!(panel.rolling_errors(), ERROR_WINDOW as u64);
+        assert_eq!(panel.progress(), 1.0);
+    }
+}
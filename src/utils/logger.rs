@@ -4,8 +4,16 @@
 
 #![allow(dead_code)]
 
-use tracing::Level;
-use tracing_subscriber::EnvFilter;
+use anyhow::{Context, Result};
+use std::fs::OpenOptions;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tracing::{Level, Subscriber};
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer};
 
 /// Log level configuration
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -28,6 +36,16 @@ impl LogLevel {
         }
     }
 
+    /// Level for `-v` repetitions; `--quiet` keeps only errors
+    pub fn from_verbosity(verbose: u8, quiet: bool) -> Self {
+        match (quiet, verbose) {
+            (true, _) => LogLevel::Error,
+            (false, 0) => LogLevel::Info,
+            (false, 1) => LogLevel::Debug,
+            (false, _) => LogLevel::Trace,
+        }
+    }
+
     pub fn from_str(s: &str) -> Option<Self> {
         match s.to_lowercase().as_str() {
             "trace" => Some(LogLevel::Trace),
@@ -40,6 +58,134 @@ impl LogLevel {
     }
 }
 
+/// Log line format
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Compact human-readable lines
+    #[default]
+    Text,
+    /// One JSON object per line, including the active span chain
+    Json,
+}
+
+impl LogFormat {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "text" | "compact" => Some(LogFormat::Text),
+            "json" => Some(LogFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Logging setup
+#[derive(Clone, Debug)]
+pub struct LogConfig {
+    pub level: LogLevel,
+    pub format: LogFormat,
+    /// Write logs to this file (appending) instead of the console
+    pub file: Option<PathBuf>,
+    /// Apply the level to dependencies (hyper, kube, ...) as well
+    pub all_targets: bool,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            level: LogLevel::Info,
+            format: LogFormat::Text,
+            file: None,
+            all_targets: false,
+        }
+    }
+}
+
+/// Verbosity that logs everything (`-vvv`)
+pub const MAX_VERBOSITY: u8 = 3;
+
+impl LogConfig {
+    pub fn new(level: LogLevel) -> Self {
+        Self {
+            level,
+            ..Default::default()
+        }
+    }
+
+    /// `-v` = debug, `-vv` = trace, `-vvv` = trace including dependencies
+    pub fn from_verbosity(verbose: u8, quiet: bool) -> Self {
+        Self {
+            all_targets: !quiet && verbose >= MAX_VERBOSITY,
+            ..Self::new(LogLevel::from_verbosity(verbose, quiet))
+        }
+    }
+
+    /// `EnvFilter` directive for this configuration
+    pub fn filter_directive(&self) -> String {
+        let level = self.level.to_tracing_level();
+        if self.all_targets || level <= Level::INFO {
+            level.to_string().to_lowercase()
+        } else {
+            format!("info,gateway_poc={}", level.to_string().to_lowercase())
+        }
+    }
+
+    pub fn with_format(mut self, format: LogFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    pub fn with_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.file = Some(path.into());
+        self
+    }
+}
+
+/// Formatting layer for a writer
+fn format_layer<S, W>(format: LogFormat, writer: W, ansi: bool) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    match format {
+        LogFormat::Text => tracing_subscriber::fmt::layer()
+            .with_target(false)
+            .with_ansi(ansi)
+            .compact()
+            .with_writer(writer)
+            .boxed(),
+        LogFormat::Json => tracing_subscriber::fmt::layer()
+            .json()
+            .with_current_span(true)
+            .with_span_list(true)
+            .with_writer(writer)
+            .boxed(),
+    }
+}
+
+/// Initialize logging (`RUST_LOG` overrides the configured level)
+pub fn init_logging(config: &LogConfig) -> Result<()> {
+    let filter = EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| EnvFilter::new(config.filter_directive()));
+
+    let layer = match &config.file {
+        Some(path) => {
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .with_context(|| format!("Failed to open log file {}", path.display()))?;
+            format_layer(config.format, Mutex::new(file), false)
+        }
+        None => format_layer(config.format, std::io::stdout, true),
+    };
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(layer)
+        .try_init()
+        .context("Failed to initialize logging")
+}
+
 /// Initialize the logger with specified level
 pub fn init_logger(level: LogLevel) {
     let filter = EnvFilter::new(format!("gateway_poc={}", level.to_tracing_level()));
@@ -61,4 +207,35 @@ mod tests {
         assert_eq!(LogLevel::from_str("DEBUG"), Some(LogLevel::Debug));
         assert_eq!(LogLevel::from_str("unknown"), None);
     }
+
+    #[test]
+    fn test_verbosity_filter() {
+        assert_eq!(
+            LogConfig::from_verbosity(0, false).filter_directive(),
+            "info"
+        );
+        assert_eq!(
+            LogConfig::from_verbosity(1, false).filter_directive(),
+            "info,gateway_poc=debug"
+        );
+        assert_eq!(
+            LogConfig::from_verbosity(2, false).filter_directive(),
+            "info,gateway_poc=trace"
+        );
+        assert_eq!(
+            LogConfig::from_verbosity(3, false).filter_directive(),
+            "trace"
+        );
+        assert_eq!(
+            LogConfig::from_verbosity(0, true).filter_directive(),
+            "error"
+        );
+    }
+
+    #[test]
+    fn test_log_format_from_str() {
+        assert_eq!(LogFormat::from_str("json"), Some(LogFormat::Json));
+        assert_eq!(LogFormat::from_str("TEXT"), Some(LogFormat::Text));
+        assert_eq!(LogFormat::from_str("xml"), None);
+    }
 }
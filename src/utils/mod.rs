@@ -1,6 +1,10 @@
 //! Utility modules
 //!
-//! Common utilities for logging, timing, and helpers.
+//! Common utilities for logging, timing, teardown, and helpers.
 
 mod logger;
+mod teardown;
 mod timer;
+
+pub use logger::{init_logging, LogConfig, LogFormat, MAX_VERBOSITY};
+pub use teardown::{Teardown, Undo};
@@ -0,0 +1,327 @@
+//! Teardown registry
+//!
+//! Every resource created during a run (gateway installs, fixture changes,
+//! worker pods, VMs) registers an undo action. Pending actions run in
+//! reverse registration order on every exit path: after the work finishes,
+//! when it fails, or when it is cancelled (a dropped future or Ctrl-C), so a
+//! failed run doesn't leave the cluster littered.
+
+#![allow(dead_code)]
+
+use anyhow::Result;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Retries of a failing undo action
+const DEFAULT_RETRIES: u32 = 3;
+
+/// Delay before the first retry (doubled on each further retry)
+const DEFAULT_BACKOFF: Duration = Duration::from_secs(1);
+
+type UndoFuture = Pin<Box<dyn Future<Output = Result<()>> + Send>>;
+type UndoFn = Box<dyn Fn() -> UndoFuture + Send + Sync>;
+
+struct UndoAction {
+    id: u64,
+    description: String,
+    action: UndoFn,
+}
+
+/// Shared registry of undo actions
+#[derive(Clone)]
+pub struct Teardown {
+    actions: Arc<Mutex<Vec<UndoAction>>>,
+    next_id: Arc<AtomicU64>,
+    retries: u32,
+    backoff: Duration,
+}
+
+impl Default for Teardown {
+    fn default() -> Self {
+        Self {
+            actions: Arc::default(),
+            next_id: Arc::default(),
+            retries: DEFAULT_RETRIES,
+            backoff: DEFAULT_BACKOFF,
+        }
+    }
+}
+
+impl fmt::Debug for Teardown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Teardown")
+            .field("pending", &self.pending())
+            .field("retries", &self.retries)
+            .finish()
+    }
+}
+
+impl Teardown {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Retries of a failing undo action
+    pub fn with_retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Delay before the first retry
+    pub fn with_backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Register the undo action of a resource that was just created
+    ///
+    /// The action stays pending until the returned handle commits or runs
+    /// it; dropping the handle leaves it to [`Teardown::unwind`].
+    pub fn register<F, Fut>(&self, description: impl Into<String>, action: F) -> Undo
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send + 'static,
+    {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.lock().push(UndoAction {
+            id,
+            description: description.into(),
+            action: Box::new(move || Box::pin(action())),
+        });
+        Undo {
+            teardown: self.clone(),
+            id,
+        }
+    }
+
+    /// Number of pending undo actions
+    pub fn pending(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Run every pending undo action, newest first
+    pub async fn unwind(&self) -> TeardownReport {
+        let mut report = TeardownReport::default();
+        loop {
+            // Release the lock before running the action
+            let Some(action) = self.lock().pop() else {
+                break;
+            };
+            match self.execute(&action).await {
+                Ok(()) => report.undone.push(action.description),
+                Err(e) => {
+                    warn!("Teardown failed: {}: {:#}", action.description, e);
+                    report.failed.push((action.description, format!("{e:#}")));
+                }
+            }
+        }
+        report
+    }
+
+    /// Run `work`, then unwind whether it succeeded, failed, or was
+    /// interrupted by Ctrl-C
+    pub async fn guard<T>(&self, work: impl Future<Output = Result<T>>) -> Result<T> {
+        let outcome = tokio::select! {
+            result = work => result,
+            _ = tokio::signal::ctrl_c() => {
+                warn!("Interrupted; tearing down created resources");
+                Err(anyhow::anyhow!("Interrupted"))
+            }
+        };
+        let report = self.unwind().await;
+        if !report.is_empty() {
+            info!("{}", report);
+        }
+        outcome
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<UndoAction>> {
+        self.actions.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn take(&self, id: u64) -> Option<UndoAction> {
+        let mut actions = self.lock();
+        let index = actions.iter().position(|a| a.id == id)?;
+        Some(actions.remove(index))
+    }
+
+    /// Run an action, retrying with exponential backoff
+    async fn execute(&self, action: &UndoAction) -> Result<()> {
+        let mut delay = self.backoff;
+        let mut attempt = 0;
+        loop {
+            match (action.action)().await {
+                Ok(()) => return Ok(()),
+                Err(e) if attempt >= self.retries => return Err(e),
+                Err(e) => {
+                    attempt += 1;
+                    warn!(
+                        "Teardown of {} failed (attempt {}/{}): {:#}",
+                        action.description,
+                        attempt,
+                        self.retries + 1,
+                        e
+                    );
+                    tokio::time::sleep(delay).await;
+                    delay *= 2;
+                }
+            }
+        }
+    }
+}
+
+/// Handle to one registered undo action
+pub struct Undo {
+    teardown: Teardown,
+    id: u64,
+}
+
+impl Undo {
+    /// Keep the resource: drop its undo action
+    pub fn commit(self) {
+        self.teardown.take(self.id);
+    }
+
+    /// Undo now (with retries) instead of at teardown
+    pub async fn run(self) -> Result<()> {
+        match self.teardown.take(self.id) {
+            Some(action) => self.teardown.execute(&action).await,
+            None => Ok(()),
+        }
+    }
+}
+
+/// Outcome of unwinding a registry
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TeardownReport {
+    /// Undo actions that succeeded
+    pub undone: Vec<String>,
+    /// Undo actions that failed after all retries, with the error
+    pub failed: Vec<(String, String)>,
+}
+
+impl TeardownReport {
+    pub fn is_empty(&self) -> bool {
+        self.undone.is_empty() && self.failed.is_empty()
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+impl fmt::Display for TeardownReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Teardown: {} undone", self.undone.len())?;
+        if !self.failed.is_empty() {
+            write!(f, ", {} failed", self.failed.len())?;
+            for (description, error) in &self.failed {
+                write!(f, "\n  ✗ {description}: {error}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicU32;
+
+    fn recorder(log: &Arc<Mutex<Vec<String>>>, name: &str) -> impl Fn() -> UndoFuture {
+        let log = log.clone();
+        let name = name.to_string();
+        move || {
+            let log = log.clone();
+            let name = name.clone();
+            Box::pin(async move {
+                log.lock().unwrap().push(name);
+                Ok(())
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unwind_in_reverse_order() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let teardown = Teardown::new();
+        teardown.register("namespace", recorder(&log, "namespace"));
+        let kept = teardown.register("release", recorder(&log, "release"));
+        teardown.register("route", recorder(&log, "route"));
+        let early = teardown.register("pod", recorder(&log, "pod"));
+        assert_eq!(teardown.pending(), 4);
+
+        kept.commit();
+        early.run().await.unwrap();
+        let report = teardown.unwind().await;
+
+        assert_eq!(*log.lock().unwrap(), ["pod", "route", "namespace"]);
+        assert_eq!(report.undone, ["route", "namespace"]);
+        assert!(report.is_clean());
+        assert_eq!(teardown.pending(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_retries_and_failures() {
+        let attempts = Arc::new(AtomicU32::new(0));
+        let teardown = Teardown::new().with_retries(2).with_backoff(Duration::ZERO);
+
+        let counter = attempts.clone();
+        teardown.register("flaky", move || {
+            let counter = counter.clone();
+            async move {
+                if counter.fetch_add(1, Ordering::SeqCst) < 2 {
+                    anyhow::bail!("conflict");
+                }
+                Ok(())
+            }
+        });
+        teardown.register("broken", || async { anyhow::bail!("forbidden") });
+
+        let report = teardown.unwind().await;
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+        assert_eq!(report.undone, ["flaky"]);
+        assert_eq!(
+            report.failed,
+            [("broken".to_string(), "forbidden".to_string())]
+        );
+        assert_eq!(
+            report.to_string(),
+            "Teardown: 1 undone, 1 failed\n  ✗ broken: forbidden"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_guard_unwinds_cancelled_work() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let teardown = Teardown::new();
+
+        let result: Result<()> = teardown
+            .guard(async {
+                teardown.register("vm", recorder(&log, "vm"));
+                anyhow::bail!("VM did not become ready")
+            })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(*log.lock().unwrap(), ["vm"]);
+
+        // Dropping work mid-flight leaves its actions registered
+        let work = {
+            let teardown = teardown.clone();
+            let log = log.clone();
+            async move {
+                teardown.register("fixture", recorder(&log, "fixture"));
+                tokio::time::sleep(Duration::from_secs(60)).await;
+            }
+        };
+        let _ = tokio::time::timeout(Duration::from_millis(10), work).await;
+        assert_eq!(teardown.pending(), 1);
+        teardown.unwind().await;
+        assert_eq!(*log.lock().unwrap(), ["vm", "fixture"]);
+    }
+}